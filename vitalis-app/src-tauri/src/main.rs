@@ -1,133 +1,1452 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::Manager;
-use vitalis_core::application::{get_genbank_metadata, GenBankMetadata};
-use vitalis_core::domain::primer::{PrimerDesignParams, PrimerDesignResult};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager, Window, WindowEvent};
+use vitalis_core::application::{get_genbank_metadata, CommandError, GenBankMetadata};
+use vitalis_core::domain::alignment::{AlignmentSummary, ConservationMethod};
+use vitalis_core::domain::analysis_result::AnalysisResult;
+use vitalis_core::domain::assay::AssayDocument;
+use vitalis_core::domain::assembly::{AssemblyDesign, AssemblyPrimerParams};
+use vitalis_core::domain::backbone::{BackboneMatch, VectorBackbone};
+use vitalis_core::domain::bed::BedTrack;
+use vitalis_core::domain::cloning::CloningProduct;
+use vitalis_core::domain::codon_usage::{CodonHost, CodonUsageComparison, CodonUsageParams};
+use vitalis_core::domain::concentration::{
+    ConcentrationConversion, ConcentrationUnit, MoleculeKind,
+};
+use vitalis_core::domain::construct::{ConstructTag, ExpressionConstruct};
+use vitalis_core::domain::contamination::ContaminationReport;
+use vitalis_core::domain::coordinates::{ConvertedCoordinate, CoordinateSystem};
+use vitalis_core::domain::copy_format::CopyStyle;
+use vitalis_core::domain::crispr::{CasPam, GuideDesignParams, GuideDesignResult};
+use vitalis_core::domain::dilution::{DilutionSeries, OligoResuspension, StandardCurveParams};
+use vitalis_core::domain::dotplot::DotPlot;
+use vitalis_core::domain::edit::EditResult;
+use vitalis_core::domain::export_format::ExportFormat;
+use vitalis_core::domain::feature::{FeatureDisplay, SequenceFeature};
+use vitalis_core::domain::frameshift::FrameshiftReport;
+use vitalis_core::domain::gc_skew::GcSkewProfile;
+use vitalis_core::domain::homology_arm::{HomologyArmDesign, HomologyArmParams};
+use vitalis_core::domain::linker::{LinkerParams, LinkerSet};
+use vitalis_core::domain::map_model::{MapModel, MapStyle};
+use vitalis_core::domain::melt_curve::{MeltCurve, MeltCurveParams};
+use vitalis_core::domain::melting_map::{MeltingMap, MeltingMapParams, TmGrid, TmGridParams};
+use vitalis_core::domain::mutation::{MutationRates, SimulatedMutant};
+use vitalis_core::domain::navigation::{Bookmark, NavigationHit, NavigationTarget};
+use vitalis_core::domain::nullomer::{NullomerParams, NullomerReport};
+use vitalis_core::domain::oligo::{
+    CrossTalkParams, CrossTalkReport, DegenerateEstimate, Oligo, OligoAmount, OligoAmountUnit,
+    OligoProperties,
+};
+use vitalis_core::domain::orf::{FrameTranslation, Orf};
+use vitalis_core::domain::plugin::PluginInfo;
+use vitalis_core::domain::primer::{
+    AmpliconQcParams, HrmTilingParams, HrmTilingResult, JunctionPrimerParams,
+    JunctionVerificationSet, PanelStressParams, PanelStressReport, PrimerBindingSite,
+    PrimerDesignParams, PrimerDesignProgress, PrimerDesignResult, PrimerDuplex, PrimerPair,
+    QpcrAssay, QpcrAssayParams, RelaxationPlan,
+};
+use vitalis_core::domain::probe_label::{ProbeLabelInput, ProbeLabelParams, ProbeLabelPlan};
+use vitalis_core::domain::project::{ProjectFormat, ProjectManifest, ProjectSummary};
+use vitalis_core::domain::protein::{HydropathyProfile, ProteomeExport, SignalPeptide};
+use vitalis_core::domain::protein_motif::{MotifHit, ProteinMotif};
+use vitalis_core::domain::rbs::{RbsParams, RbsPrediction};
+use vitalis_core::domain::read_set::{ReadPage, ReadQualityStats};
+use vitalis_core::domain::read_stats::{ReadSetDistributions, ReadSetParams, ReadSetStats};
+use vitalis_core::domain::reference::{
+    EntrezSettings, ReferenceGenome, ReferencePrimerDesign, ReferenceSource,
+    RegisterReferenceRequest,
+};
+use vitalis_core::domain::region::Region;
+use vitalis_core::domain::repeat::{RepeatParams, RepeatReport};
+use vitalis_core::domain::reproducibility::SessionProvenance;
+use vitalis_core::domain::restriction::{
+    CutterSummary, DigestProtocol, DigestProtocolParams, RestrictionEnzyme, RestrictionMap,
+};
+use vitalis_core::domain::scaffold::ScaffoldSplit;
+use vitalis_core::domain::screening::{ScreeningParams, ScreeningStrategy};
+use vitalis_core::domain::search::{MotifMatch, SearchOptions, SearchPage, TranslatedHit};
+use vitalis_core::domain::thermodynamics::SaltCorrectionParams;
+use vitalis_core::domain::track::ScoreTrack;
+use vitalis_core::domain::validation::InputLimits;
+use vitalis_core::domain::variant::{HgvsDescription, Variant};
+use vitalis_core::domain::warning::WarningPolicy;
+use vitalis_core::domain::{CodonUsage, KmerSpectrum, StatsOptions, Strand, Topology};
 use vitalis_core::{
-    calculate_primer_gc, calculate_primer_tm, design_primers, detailed_stats,
-    detailed_stats_enhanced, evaluate_primer_multiplex, export, get_meta, get_window,
-    import_from_file, import_sequence, parse_and_import, parse_preview, stats, storage_info,
-    window_stats, DetailedStatsEnhancedResponse, ExportResponse, ImportFromFileRequest,
-    ImportResponse, ParsePreviewResponse, WindowStatsItem,
+    add_bookmark, add_variants, autosave_project, calculate_kmer_spectrum,
+    calculate_oligo_properties, calculate_primer_gc, calculate_primer_tm, check_primer_specificity,
+    close_workspace, codon_usage, compare_codon_usage, configure_reference_settings,
+    conservation_track, convert_concentration, convert_coordinates, convert_oligo_amount,
+    cutter_summary, delete_range, describe_variant_hgvs, design_assembly_primers,
+    design_expression_construct, design_guides, design_homology_arms, design_hrm_tiling,
+    design_junction_primers, design_primers, design_primers_at_reference, design_primers_streaming,
+    design_qpcr_assay, detailed_stats, detailed_stats_enhanced, detect_backbones,
+    detect_frameshift, digest_protocol, dilution_series, dotplot, evaluate_primer_multiplex,
+    export, export_assay_document, export_bed, export_project_archive, export_proteome,
+    find_in_sequence, find_nullomers, find_orfs, find_protein_in_workspace, find_repeats,
+    find_restriction_sites, format_sequence_for_copy, gc_skew_profile, generate_linkers,
+    get_entrez_settings, get_features, get_input_limits, get_meta, get_reads, get_score_tracks,
+    get_session_provenance, get_variants, get_warning_policy, get_window, import_alignment,
+    import_bed, import_fastq_readset, import_from_file, import_gff3, import_records_from_file,
+    import_reference_region, import_sequence, insert_bases, list_analysis_plugins, list_bookmarks,
+    list_reference_genomes, list_restriction_enzymes, list_sequences, list_workspaces,
+    materialize_amplicon, melting_map, next_feature, oligo_cross_talk, oligo_resuspension,
+    open_project, open_workspace, parse_and_import, parse_and_import_all, parse_preview,
+    plan_standard_curve, predict_melt_curve, predict_rbs, predict_signal_peptide, previous_feature,
+    primer_duplex, protein_hydropathy, qc_primer_pair, read_set_statistics, readset_distributions,
+    readset_stats, redo_edit, register_backbone, register_reference_genome, remove_bookmark,
+    render_map_model, replace_range, run_analysis_plugin, run_script, save_project,
+    scan_protein_motifs, screen_vector_contamination, search_motif, set_autosave,
+    set_entrez_settings, set_feature_display, set_reproducibility_mode, set_topology,
+    set_warning_policy, simulate_cloning, simulate_mutations, split_at_gaps, stats, storage_info,
+    stress_test_primer_panel, suggest_probe_labels, suggest_relaxation, suggest_screening_strategy,
+    tm_grid, translate, undo_edit, unregister_reference_genome, window_stats, with_workspace,
+    DetailedStatsEnhancedResponse, ExportResponse, ImportFileResponse, ImportFromFileRequest,
+    ImportRecordsResponse, ImportResponse, ParsePreviewResponse, WindowStatsItem,
+    DEFAULT_WORKSPACE,
 };
 
 // Tauri command handlers - vitalis-coreのAPI関数をラップ
+/// 自動保存の間隔
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 既定のワークスペースを使うウィンドウ
+const MAIN_WINDOW: &str = "main";
+
+/// ウィンドウラベルごとのワークスペース。main ウィンドウは既定のワークスペース、
+/// ほかのウィンドウは最初のコマンドで専用のワークスペースを開く。
+/// 別のウィンドウと同じワークスペースを使うには join_workspace で明示する
+#[derive(Default)]
+struct WindowWorkspaces(Mutex<HashMap<String, String>>);
+
+impl WindowWorkspaces {
+    fn workspace_of(&self, label: &str) -> Result<String, String> {
+        let mut windows = self.0.lock().map_err(|e| e.to_string())?;
+        if let Some(workspace_id) = windows.get(label) {
+            return Ok(workspace_id.clone());
+        }
+        let workspace_id = if label == MAIN_WINDOW {
+            DEFAULT_WORKSPACE.to_string()
+        } else {
+            open_workspace()?
+        };
+        windows.insert(label.to_string(), workspace_id.clone());
+        Ok(workspace_id)
+    }
+
+    /// ウィンドウを既存のワークスペースに移す
+    fn join(&self, label: &str, workspace_id: String) -> Result<(), String> {
+        if !list_workspaces()?.contains(&workspace_id) {
+            return Err(format!("Workspace not found: {}", workspace_id));
+        }
+        let mut windows = self.0.lock().map_err(|e| e.to_string())?;
+        let previous = windows.insert(label.to_string(), workspace_id);
+        Self::close_unused(&windows, previous)
+    }
+
+    /// 閉じたウィンドウの割り当てを外す
+    fn release(&self, label: &str) -> Result<(), String> {
+        let mut windows = self.0.lock().map_err(|e| e.to_string())?;
+        let previous = windows.remove(label);
+        Self::close_unused(&windows, previous)
+    }
+
+    /// どのウィンドウも使わなくなったワークスペースを閉じる（既定のワークスペースは残す）
+    fn close_unused(
+        windows: &HashMap<String, String>,
+        workspace_id: Option<String>,
+    ) -> Result<(), String> {
+        match workspace_id {
+            Some(workspace_id)
+                if workspace_id != DEFAULT_WORKSPACE
+                    && !windows.values().any(|id| *id == workspace_id) =>
+            {
+                close_workspace(workspace_id)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 呼び出し元ウィンドウのワークスペースでコアの処理を実行する。
+/// 入力を検証するコマンドのエラーは CommandError のまま UI に返す
+fn in_window<T, E: From<String>>(
+    window: &Window,
+    run: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    let workspace_id = window
+        .state::<WindowWorkspaces>()
+        .workspace_of(window.label())?;
+    with_workspace(&workspace_id, run)?
+}
+
+#[tauri::command]
+async fn tauri_current_workspace(window: Window) -> Result<String, String> {
+    window
+        .state::<WindowWorkspaces>()
+        .workspace_of(window.label())
+}
+
+#[tauri::command]
+async fn tauri_join_workspace(window: Window, workspace_id: String) -> Result<(), String> {
+    window
+        .state::<WindowWorkspaces>()
+        .join(window.label(), workspace_id)
+}
+
 #[tauri::command]
-async fn tauri_parse_and_import(content: String, format: String) -> Result<ImportResponse, String> {
-    parse_and_import(content, format).map_err(|e| e.to_string())
+async fn tauri_list_workspaces() -> Result<Vec<String>, String> {
+    list_workspaces()
+}
+
+#[tauri::command]
+async fn tauri_parse_and_import(
+    window: Window,
+    content: String,
+    format: String,
+) -> Result<ImportResponse, CommandError> {
+    in_window(&window, || parse_and_import(content, format))
+}
+
+#[tauri::command]
+async fn tauri_parse_and_import_all(
+    window: Window,
+    content: String,
+    format: String,
+) -> Result<Vec<String>, CommandError> {
+    in_window(&window, || parse_and_import_all(content, format))
 }
 
 #[tauri::command]
 async fn tauri_parse_preview(
+    window: Window,
     content: String,
     format: String,
-) -> Result<ParsePreviewResponse, String> {
-    parse_preview(content, format).map_err(|e| e.to_string())
+) -> Result<ParsePreviewResponse, CommandError> {
+    in_window(&window, || parse_preview(content, format))
 }
 
 #[tauri::command]
 async fn tauri_import_sequence(
+    window: Window,
     content: String,
     format: String,
     sequence_index: usize,
-) -> Result<ImportResponse, String> {
-    import_sequence(content, format, sequence_index).map_err(|e| e.to_string())
+) -> Result<ImportResponse, CommandError> {
+    in_window(&window, || import_sequence(content, format, sequence_index))
+}
+
+#[tauri::command]
+async fn tauri_import_from_file(
+    window: Window,
+    request: ImportFromFileRequest,
+) -> Result<ImportFileResponse, String> {
+    in_window(&window, || {
+        import_from_file(request).map_err(|e| e.to_string())
+    })
 }
 
 #[tauri::command]
-async fn tauri_import_from_file(request: ImportFromFileRequest) -> Result<ImportResponse, String> {
-    import_from_file(request).map_err(|e| e.to_string())
+async fn tauri_import_records_from_file(
+    window: Window,
+    request: ImportFromFileRequest,
+) -> Result<ImportRecordsResponse, String> {
+    in_window(&window, || {
+        import_records_from_file(request).map_err(|e| e.to_string())
+    })
 }
 
 #[tauri::command]
 async fn tauri_get_window(
+    window: Window,
     seq_id: String,
-    start: usize,
-    end: usize,
-) -> Result<vitalis_core::WindowResponse, String> {
-    get_window(seq_id, start, end).map_err(|e| e.to_string())
+    region: Region,
+) -> Result<vitalis_core::WindowResponse, CommandError> {
+    in_window(&window, || get_window(seq_id, region))
 }
 
 #[tauri::command]
-async fn tauri_stats(seq_id: String) -> Result<vitalis_core::SequenceStats, String> {
-    stats(seq_id).map_err(|e| e.to_string())
+async fn tauri_stats(
+    window: Window,
+    seq_id: String,
+) -> Result<vitalis_core::SequenceStats, String> {
+    in_window(&window, || stats(seq_id).map_err(|e| e.to_string()))
 }
 
 #[tauri::command]
 async fn tauri_detailed_stats(
+    window: Window,
     seq_id: String,
-) -> Result<vitalis_core::DetailedStatsResponse, String> {
-    detailed_stats(seq_id).map_err(|e| e.to_string())
+    options: Option<StatsOptions>,
+) -> Result<vitalis_core::DetailedStatsResponse, CommandError> {
+    in_window(&window, || detailed_stats(seq_id, options))
 }
 
 #[tauri::command]
 async fn tauri_detailed_stats_enhanced(
+    window: Window,
     seq_id: String,
-) -> Result<DetailedStatsEnhancedResponse, String> {
-    detailed_stats_enhanced(seq_id).map_err(|e| e.to_string())
+    options: Option<StatsOptions>,
+) -> Result<DetailedStatsEnhancedResponse, CommandError> {
+    in_window(&window, || detailed_stats_enhanced(seq_id, options))
+}
+
+#[tauri::command]
+async fn tauri_calculate_kmer_spectrum(
+    window: Window,
+    seq_id: String,
+    k: usize,
+) -> Result<KmerSpectrum, CommandError> {
+    in_window(&window, || calculate_kmer_spectrum(seq_id, k))
 }
 
 #[tauri::command]
 async fn tauri_window_stats(
+    window: Window,
     seq_id: String,
     window_size: usize,
     step: usize,
-) -> Result<Vec<WindowStatsItem>, String> {
-    window_stats(seq_id, window_size, step).map_err(|e| e.to_string())
+    options: Option<StatsOptions>,
+) -> Result<Vec<WindowStatsItem>, CommandError> {
+    in_window(&window, || window_stats(seq_id, window_size, step, options))
 }
 
 #[tauri::command]
-async fn tauri_export(seq_id: String, format: String) -> Result<ExportResponse, String> {
-    export(seq_id, format).map_err(|e| e.to_string())
+async fn tauri_gc_skew_profile(
+    window: Window,
+    seq_id: String,
+    window_size: usize,
+    step: usize,
+) -> Result<GcSkewProfile, CommandError> {
+    in_window(&window, || gc_skew_profile(seq_id, window_size, step))
 }
 
 #[tauri::command]
-async fn tauri_get_meta(seq_id: String) -> Result<vitalis_core::SequenceMeta, String> {
-    get_meta(seq_id).map_err(|e| e.to_string())
+async fn tauri_export(
+    window: Window,
+    seq_id: String,
+    format: String,
+) -> Result<ExportResponse, String> {
+    in_window(&window, || {
+        export(seq_id, format).map_err(|e| e.to_string())
+    })
 }
 
 #[tauri::command]
-async fn tauri_storage_info() -> Result<serde_json::Value, String> {
-    storage_info().map_err(|e| e.to_string())
+async fn tauri_get_meta(
+    window: Window,
+    seq_id: String,
+) -> Result<vitalis_core::SequenceMeta, String> {
+    in_window(&window, || get_meta(seq_id).map_err(|e| e.to_string()))
 }
 
 #[tauri::command]
-async fn tauri_read_file(file_path: String) -> Result<String, String> {
-    std::fs::read_to_string(&file_path).map_err(|e| e.to_string())
+async fn tauri_list_sequences(window: Window) -> Result<Vec<vitalis_core::SequenceMeta>, String> {
+    in_window(&window, || list_sequences().map_err(|e| e.to_string()))
 }
 
 #[tauri::command]
-async fn tauri_get_genbank_metadata(content: String) -> Result<GenBankMetadata, String> {
-    get_genbank_metadata(content).map_err(|e| e.to_string())
+async fn tauri_storage_info(window: Window) -> Result<serde_json::Value, String> {
+    in_window(&window, || storage_info().map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_read_file(window: Window, file_path: String) -> Result<String, String> {
+    in_window(&window, || {
+        std::fs::read_to_string(&file_path).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_genbank_metadata(
+    window: Window,
+    content: String,
+) -> Result<GenBankMetadata, CommandError> {
+    in_window(&window, || get_genbank_metadata(content))
 }
 
 #[tauri::command]
 async fn tauri_design_primers(
+    window: Window,
     seq_id: String,
-    start: usize,
-    end: usize,
+    region: Region,
+    params: Option<PrimerDesignParams>,
+) -> Result<PrimerDesignResult, CommandError> {
+    in_window(&window, || design_primers(seq_id, region, params))
+}
+
+/// 逐次設計の途中経過イベント（`run_id` で設計の実行を区別する）
+const PRIMER_DESIGN_PROGRESS_EVENT: &str = "primer-design-progress";
+
+#[derive(Clone, Serialize)]
+struct PrimerDesignProgressEvent {
+    run_id: String,
+    #[serde(flatten)]
+    progress: PrimerDesignProgress,
+}
+
+#[tauri::command]
+async fn tauri_design_primers_streaming(
+    window: Window,
+    run_id: String,
+    seq_id: String,
+    region: Region,
     params: Option<PrimerDesignParams>,
-) -> Result<PrimerDesignResult, String> {
-    design_primers(seq_id, start, end, params).map_err(|e| e.to_string())
+) -> Result<PrimerDesignResult, CommandError> {
+    in_window(&window, || {
+        design_primers_streaming(seq_id, region, params, |progress| {
+            let event = PrimerDesignProgressEvent {
+                run_id: run_id.clone(),
+                progress,
+            };
+            // 途中経過が届かなくても最終結果は返す
+            let _ = window.emit_to(window.label(), PRIMER_DESIGN_PROGRESS_EVENT, event);
+        })
+    })
+}
+
+#[tauri::command]
+async fn tauri_suggest_relaxation(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    params: Option<PrimerDesignParams>,
+    min_pairs: Option<usize>,
+) -> Result<RelaxationPlan, CommandError> {
+    in_window(&window, || {
+        suggest_relaxation(seq_id, region, params, min_pairs)
+    })
+}
+
+#[tauri::command]
+async fn tauri_melting_map(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    params: Option<MeltingMapParams>,
+) -> Result<MeltingMap, CommandError> {
+    in_window(&window, || melting_map(seq_id, region, params))
+}
+
+#[tauri::command]
+async fn tauri_tm_grid(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    params: Option<TmGridParams>,
+) -> Result<TmGrid, CommandError> {
+    in_window(&window, || tm_grid(seq_id, region, params))
+}
+
+#[tauri::command]
+async fn tauri_predict_melt_curve(
+    window: Window,
+    amplicon_sequence: String,
+    salt_conditions: Option<SaltCorrectionParams>,
+    params: Option<MeltCurveParams>,
+) -> Result<MeltCurve, CommandError> {
+    in_window(&window, || {
+        predict_melt_curve(amplicon_sequence, salt_conditions, params)
+    })
 }
 
 #[tauri::command]
-async fn tauri_calculate_primer_tm(sequence: String) -> Result<f32, String> {
-    calculate_primer_tm(sequence).map_err(|e| e.to_string())
+async fn tauri_calculate_primer_tm(
+    window: Window,
+    sequence: String,
+) -> Result<DegenerateEstimate, CommandError> {
+    in_window(&window, || calculate_primer_tm(sequence))
 }
 
 #[tauri::command]
-async fn tauri_calculate_primer_gc(sequence: String) -> Result<f32, String> {
-    calculate_primer_gc(sequence).map_err(|e| e.to_string())
+async fn tauri_calculate_primer_gc(
+    window: Window,
+    sequence: String,
+) -> Result<DegenerateEstimate, CommandError> {
+    in_window(&window, || calculate_primer_gc(sequence))
+}
+
+#[tauri::command]
+async fn tauri_convert_concentration(
+    window: Window,
+    seq_id: String,
+    molecule: MoleculeKind,
+    value: f64,
+    unit: ConcentrationUnit,
+) -> Result<ConcentrationConversion, CommandError> {
+    in_window(&window, || {
+        convert_concentration(seq_id, molecule, value, unit)
+    })
+}
+
+#[tauri::command]
+async fn tauri_calculate_oligo_properties(
+    window: Window,
+    sequence: String,
+) -> Result<OligoProperties, CommandError> {
+    in_window(&window, || calculate_oligo_properties(sequence))
+}
+
+#[tauri::command]
+async fn tauri_convert_oligo_amount(
+    window: Window,
+    sequence: String,
+    amount: f64,
+    unit: OligoAmountUnit,
+) -> Result<OligoAmount, CommandError> {
+    in_window(&window, || convert_oligo_amount(sequence, amount, unit))
+}
+
+#[tauri::command]
+async fn tauri_oligo_resuspension(
+    window: Window,
+    nmoles: f64,
+    target_conc: f64,
+) -> Result<OligoResuspension, CommandError> {
+    in_window(&window, || oligo_resuspension(nmoles, target_conc))
+}
+
+#[tauri::command]
+async fn tauri_dilution_series(
+    window: Window,
+    stock: f64,
+    target: f64,
+    volume: f64,
+) -> Result<DilutionSeries, CommandError> {
+    in_window(&window, || dilution_series(stock, target, volume))
+}
+
+#[tauri::command]
+async fn tauri_generate_linkers(
+    window: Window,
+    length: usize,
+    gc_percent: f64,
+    params: Option<LinkerParams>,
+) -> Result<LinkerSet, CommandError> {
+    in_window(&window, || generate_linkers(length, gc_percent, params))
 }
 
 #[tauri::command]
 async fn tauri_evaluate_primer_multiplex(
+    window: Window,
     seq_id: String,
     primer_pairs: Vec<serde_json::Value>,
 ) -> Result<serde_json::Value, String> {
-    evaluate_primer_multiplex(seq_id, primer_pairs).map_err(|e| e.to_string())
+    in_window(&window, || {
+        evaluate_primer_multiplex(seq_id, primer_pairs).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_materialize_amplicon(
+    window: Window,
+    seq_id: String,
+    pair: PrimerPair,
+) -> Result<ImportResponse, String> {
+    in_window(&window, || {
+        materialize_amplicon(seq_id, pair).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_features(
+    window: Window,
+    seq_id: String,
+    region: Region,
+) -> Result<Vec<SequenceFeature>, CommandError> {
+    in_window(&window, || get_features(seq_id, region))
+}
+
+#[tauri::command]
+async fn tauri_add_variants(
+    window: Window,
+    seq_id: String,
+    variants: Vec<Variant>,
+) -> Result<Vec<String>, String> {
+    in_window(&window, || {
+        add_variants(seq_id, variants).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_variants(window: Window, seq_id: String) -> Result<Vec<Variant>, String> {
+    in_window(&window, || get_variants(seq_id).map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_design_guides(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    pam: CasPam,
+    params: Option<GuideDesignParams>,
+) -> Result<GuideDesignResult, CommandError> {
+    in_window(&window, || design_guides(seq_id, region, pam, params))
+}
+
+#[tauri::command]
+async fn tauri_design_hrm_tiling(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    params: Option<HrmTilingParams>,
+) -> Result<HrmTilingResult, CommandError> {
+    in_window(&window, || design_hrm_tiling(seq_id, region, params))
+}
+
+#[tauri::command]
+async fn tauri_suggest_probe_labels(
+    window: Window,
+    probes: Vec<ProbeLabelInput>,
+    multiplex: usize,
+    params: Option<ProbeLabelParams>,
+) -> Result<ProbeLabelPlan, CommandError> {
+    in_window(&window, || suggest_probe_labels(probes, multiplex, params))
+}
+
+#[tauri::command]
+async fn tauri_design_qpcr_assay(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    params: Option<QpcrAssayParams>,
+) -> Result<QpcrAssay, CommandError> {
+    in_window(&window, || design_qpcr_assay(seq_id, region, params))
+}
+
+#[tauri::command]
+async fn tauri_list_restriction_enzymes(window: Window) -> Result<Vec<RestrictionEnzyme>, String> {
+    in_window(&window, || {
+        list_restriction_enzymes().map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_find_restriction_sites(
+    window: Window,
+    seq_id: String,
+    enzymes: Vec<String>,
+) -> Result<RestrictionMap, String> {
+    in_window(&window, || {
+        find_restriction_sites(seq_id, enzymes).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_cutter_summary(
+    window: Window,
+    seq_id: String,
+    enzyme_set: Vec<String>,
+) -> Result<CutterSummary, String> {
+    in_window(&window, || {
+        cutter_summary(seq_id, enzyme_set).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_digest_protocol(
+    window: Window,
+    seq_id: String,
+    enzymes: Vec<String>,
+    params: Option<DigestProtocolParams>,
+) -> Result<DigestProtocol, CommandError> {
+    in_window(&window, || digest_protocol(seq_id, enzymes, params))
+}
+
+#[tauri::command]
+async fn tauri_set_feature_display(
+    window: Window,
+    seq_id: String,
+    feature_id: String,
+    display: FeatureDisplay,
+) -> Result<(), CommandError> {
+    in_window(&window, || set_feature_display(seq_id, feature_id, display))
+}
+
+#[tauri::command]
+async fn tauri_add_bookmark(
+    window: Window,
+    seq_id: String,
+    position: usize,
+    label: Option<String>,
+) -> Result<Bookmark, String> {
+    in_window(&window, || {
+        add_bookmark(seq_id, position, label).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_list_bookmarks(window: Window, seq_id: String) -> Result<Vec<Bookmark>, String> {
+    in_window(&window, || {
+        list_bookmarks(seq_id).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_remove_bookmark(
+    window: Window,
+    seq_id: String,
+    bookmark_id: String,
+) -> Result<(), String> {
+    in_window(&window, || {
+        remove_bookmark(seq_id, bookmark_id).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_next_feature(
+    window: Window,
+    seq_id: String,
+    from_pos: usize,
+    types: Vec<NavigationTarget>,
+) -> Result<Option<NavigationHit>, String> {
+    in_window(&window, || {
+        next_feature(seq_id, from_pos, types).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_previous_feature(
+    window: Window,
+    seq_id: String,
+    from_pos: usize,
+    types: Vec<NavigationTarget>,
+) -> Result<Option<NavigationHit>, String> {
+    in_window(&window, || {
+        previous_feature(seq_id, from_pos, types).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_import_bed(
+    window: Window,
+    seq_id: String,
+    content: String,
+) -> Result<Vec<String>, CommandError> {
+    in_window(&window, || import_bed(seq_id, content))
+}
+
+#[tauri::command]
+async fn tauri_export_bed(
+    window: Window,
+    seq_id: String,
+    track: BedTrack,
+) -> Result<ExportResponse, CommandError> {
+    in_window(&window, || export_bed(seq_id, track))
+}
+
+#[tauri::command]
+async fn tauri_import_gff3(
+    window: Window,
+    seq_id: String,
+    text: String,
+    gff_seqid: Option<String>,
+) -> Result<Vec<String>, CommandError> {
+    in_window(&window, || import_gff3(seq_id, text, gff_seqid))
+}
+
+#[tauri::command]
+async fn tauri_search_motif(
+    window: Window,
+    seq_id: String,
+    pattern: String,
+    allow_mismatches: usize,
+) -> Result<Vec<MotifMatch>, CommandError> {
+    in_window(&window, || search_motif(seq_id, pattern, allow_mismatches))
+}
+
+#[tauri::command]
+async fn tauri_find_in_sequence(
+    window: Window,
+    seq_id: String,
+    query: String,
+    options: Option<SearchOptions>,
+) -> Result<SearchPage, CommandError> {
+    in_window(&window, || find_in_sequence(seq_id, query, options))
+}
+
+#[tauri::command]
+async fn tauri_scan_protein_motifs(
+    window: Window,
+    protein_or_seq_id: String,
+    patterns: Option<Vec<ProteinMotif>>,
+) -> Result<Vec<MotifHit>, CommandError> {
+    in_window(&window, || scan_protein_motifs(protein_or_seq_id, patterns))
+}
+
+#[tauri::command]
+async fn tauri_protein_hydropathy(
+    app_window: Window,
+    seq_id: String,
+    window: usize,
+) -> Result<HydropathyProfile, CommandError> {
+    in_window(&app_window, || protein_hydropathy(seq_id, window))
+}
+
+#[tauri::command]
+async fn tauri_predict_signal_peptide(
+    window: Window,
+    protein_or_seq_id: String,
+) -> Result<Vec<SignalPeptide>, String> {
+    in_window(&window, || {
+        predict_signal_peptide(protein_or_seq_id).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_design_expression_construct(
+    window: Window,
+    gene_cds: String,
+    vector_seq_id: String,
+    cloning_sites: Vec<String>,
+    tags: Vec<ConstructTag>,
+) -> Result<ExpressionConstruct, CommandError> {
+    in_window(&window, || {
+        design_expression_construct(gene_cds, vector_seq_id, cloning_sites, tags)
+    })
+}
+
+#[tauri::command]
+async fn tauri_simulate_cloning(
+    window: Window,
+    vector_seq_id: String,
+    insert_seq_id: String,
+    enzymes: Vec<String>,
+) -> Result<CloningProduct, CommandError> {
+    in_window(&window, || {
+        simulate_cloning(vector_seq_id, insert_seq_id, enzymes)
+    })
+}
+
+#[tauri::command]
+async fn tauri_design_assembly_primers(
+    window: Window,
+    fragment_seq_ids: Vec<String>,
+    overlap_length: usize,
+    params: Option<AssemblyPrimerParams>,
+) -> Result<AssemblyDesign, CommandError> {
+    in_window(&window, || {
+        design_assembly_primers(fragment_seq_ids, overlap_length, params)
+    })
+}
+
+#[tauri::command]
+async fn tauri_format_sequence_for_copy(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    style: Option<CopyStyle>,
+) -> Result<String, CommandError> {
+    in_window(&window, || format_sequence_for_copy(seq_id, region, style))
+}
+
+#[tauri::command]
+async fn tauri_convert_coordinates(
+    window: Window,
+    seq_id: String,
+    position: String,
+    from: CoordinateSystem,
+    to: CoordinateSystem,
+) -> Result<ConvertedCoordinate, String> {
+    in_window(&window, || {
+        convert_coordinates(seq_id, position, from, to).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_describe_variant_hgvs(
+    window: Window,
+    seq_id: String,
+    change: Variant,
+) -> Result<HgvsDescription, CommandError> {
+    in_window(&window, || describe_variant_hgvs(seq_id, change))
+}
+
+#[tauri::command]
+async fn tauri_simulate_mutations(
+    window: Window,
+    seq_id: String,
+    n_variants: usize,
+    rates: Option<MutationRates>,
+) -> Result<Vec<SimulatedMutant>, CommandError> {
+    in_window(&window, || simulate_mutations(seq_id, n_variants, rates))
+}
+
+#[tauri::command]
+async fn tauri_split_at_gaps(
+    window: Window,
+    seq_id: String,
+    min_gap: usize,
+) -> Result<ScaffoldSplit, CommandError> {
+    in_window(&window, || split_at_gaps(seq_id, min_gap))
+}
+
+#[tauri::command]
+async fn tauri_stress_test_primer_panel(
+    window: Window,
+    template_seq_id: String,
+    pairs: Vec<PrimerPair>,
+    variant_seq_ids: Option<Vec<String>>,
+    params: Option<PanelStressParams>,
+) -> Result<PanelStressReport, CommandError> {
+    in_window(&window, || {
+        stress_test_primer_panel(template_seq_id, pairs, variant_seq_ids, params)
+    })
+}
+
+#[tauri::command]
+async fn tauri_render_map_model(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    style: Option<MapStyle>,
+) -> Result<MapModel, CommandError> {
+    in_window(&window, || render_map_model(seq_id, region, style))
+}
+
+#[tauri::command]
+async fn tauri_export_project_archive(
+    window: Window,
+    path: String,
+    seq_ids: Vec<String>,
+    primer_pairs: Vec<PrimerPair>,
+    format: Option<ExportFormat>,
+) -> Result<ProjectManifest, CommandError> {
+    in_window(&window, || {
+        export_project_archive(path, seq_ids, primer_pairs, format)
+    })
+}
+
+#[tauri::command]
+async fn tauri_save_project(
+    window: Window,
+    path: String,
+    format: Option<ProjectFormat>,
+) -> Result<ProjectSummary, String> {
+    in_window(&window, || {
+        save_project(path, format).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_open_project(window: Window, path: String) -> Result<ProjectSummary, String> {
+    in_window(&window, || open_project(path).map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_set_autosave(window: Window, enabled: bool) -> Result<(), String> {
+    in_window(&window, || set_autosave(enabled).map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_set_warning_policy(window: Window, policy: WarningPolicy) -> Result<(), String> {
+    in_window(&window, || {
+        set_warning_policy(policy).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_warning_policy(window: Window) -> Result<WarningPolicy, String> {
+    in_window(&window, || get_warning_policy().map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_export_assay_document(
+    window: Window,
+    seq_id: String,
+    pair: PrimerPair,
+    probe: Option<String>,
+    path: String,
+    format: Option<ExportFormat>,
+) -> Result<AssayDocument, CommandError> {
+    in_window(&window, || {
+        export_assay_document(seq_id, pair, probe, path, format)
+    })
+}
+
+#[tauri::command]
+async fn tauri_register_reference_genome(
+    window: Window,
+    request: RegisterReferenceRequest,
+) -> Result<ReferenceGenome, String> {
+    in_window(&window, || {
+        register_reference_genome(request).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_unregister_reference_genome(
+    window: Window,
+    reference_id: String,
+) -> Result<(), String> {
+    in_window(&window, || {
+        unregister_reference_genome(reference_id).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_list_reference_genomes(window: Window) -> Result<Vec<ReferenceGenome>, String> {
+    in_window(&window, || {
+        list_reference_genomes().map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_import_reference_region(
+    window: Window,
+    reference_id: String,
+    contig: String,
+    region: Region,
+) -> Result<ImportResponse, String> {
+    in_window(&window, || {
+        import_reference_region(reference_id, contig, region).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_design_primers_at_reference(
+    window: Window,
+    source: ReferenceSource,
+    locus: String,
+    params: Option<PrimerDesignParams>,
+) -> Result<ReferencePrimerDesign, CommandError> {
+    in_window(&window, || {
+        design_primers_at_reference(source, locus, params)
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_entrez_settings(window: Window) -> Result<EntrezSettings, String> {
+    in_window(&window, get_entrez_settings)
+}
+
+#[tauri::command]
+async fn tauri_set_entrez_settings(
+    window: Window,
+    settings: EntrezSettings,
+) -> Result<(), CommandError> {
+    in_window(&window, || set_entrez_settings(settings))
+}
+
+#[tauri::command]
+async fn tauri_import_alignment(
+    window: Window,
+    content: String,
+    format: String,
+    name: Option<String>,
+) -> Result<AlignmentSummary, CommandError> {
+    in_window(&window, || import_alignment(content, format, name))
+}
+
+#[tauri::command]
+async fn tauri_conservation_track(
+    window: Window,
+    alignment_id: String,
+    reference_row: String,
+    method: Option<ConservationMethod>,
+) -> Result<ScoreTrack, String> {
+    in_window(&window, || {
+        conservation_track(alignment_id, reference_row, method).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_score_tracks(window: Window, seq_id: String) -> Result<Vec<ScoreTrack>, String> {
+    in_window(&window, || {
+        get_score_tracks(seq_id).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_oligo_cross_talk(
+    window: Window,
+    oligos: Vec<Oligo>,
+    params: Option<CrossTalkParams>,
+) -> Result<CrossTalkReport, CommandError> {
+    in_window(&window, || oligo_cross_talk(oligos, params))
+}
+
+#[tauri::command]
+async fn tauri_get_input_limits(window: Window) -> Result<InputLimits, String> {
+    in_window(&window, || get_input_limits().map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_design_homology_arms(
+    window: Window,
+    seq_id: String,
+    site: Region,
+    insert: String,
+    params: Option<HomologyArmParams>,
+) -> Result<HomologyArmDesign, CommandError> {
+    in_window(&window, || {
+        design_homology_arms(seq_id, site, insert, params)
+    })
+}
+
+#[tauri::command]
+async fn tauri_design_junction_primers(
+    window: Window,
+    seq_id: String,
+    junctions: Option<Vec<usize>>,
+    params: Option<JunctionPrimerParams>,
+) -> Result<JunctionVerificationSet, CommandError> {
+    in_window(&window, || {
+        design_junction_primers(seq_id, junctions, params)
+    })
+}
+
+#[tauri::command]
+async fn tauri_suggest_screening_strategy(
+    window: Window,
+    construct_seq_id: String,
+    parent_seq_id: String,
+    params: Option<ScreeningParams>,
+) -> Result<ScreeningStrategy, CommandError> {
+    in_window(&window, || {
+        suggest_screening_strategy(construct_seq_id, parent_seq_id, params)
+    })
+}
+
+#[tauri::command]
+async fn tauri_set_reproducibility_mode(
+    window: Window,
+    seed: Option<u64>,
+) -> Result<SessionProvenance, String> {
+    in_window(&window, || {
+        set_reproducibility_mode(seed).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_session_provenance(window: Window) -> Result<SessionProvenance, String> {
+    in_window(&window, || {
+        get_session_provenance().map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_screen_vector_contamination(
+    window: Window,
+    seq_id: String,
+) -> Result<ContaminationReport, CommandError> {
+    in_window(&window, || screen_vector_contamination(seq_id))
+}
+
+#[tauri::command]
+async fn tauri_read_set_statistics(
+    window: Window,
+    seq_ids: Vec<String>,
+    params: Option<ReadSetParams>,
+) -> Result<ReadSetStats, CommandError> {
+    in_window(&window, || read_set_statistics(seq_ids, params))
+}
+
+#[tauri::command]
+async fn tauri_readset_distributions(
+    window: Window,
+    readset_id: String,
+    bins: usize,
+) -> Result<ReadSetDistributions, CommandError> {
+    in_window(&window, || readset_distributions(readset_id, bins))
+}
+
+#[tauri::command]
+async fn tauri_import_fastq_readset(
+    window: Window,
+    file_path: String,
+) -> Result<ImportRecordsResponse, String> {
+    in_window(&window, || {
+        import_fastq_readset(file_path).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_readset_stats(
+    window: Window,
+    readset_id: String,
+) -> Result<ReadQualityStats, String> {
+    in_window(&window, || {
+        readset_stats(readset_id).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_get_reads(
+    window: Window,
+    readset_id: String,
+    offset: usize,
+    count: usize,
+) -> Result<ReadPage, CommandError> {
+    in_window(&window, || get_reads(readset_id, offset, count))
+}
+
+#[tauri::command]
+async fn tauri_find_orfs(
+    window: Window,
+    seq_id: String,
+    min_length: usize,
+    genetic_code: Option<u8>,
+) -> Result<Vec<Orf>, CommandError> {
+    in_window(&window, || find_orfs(seq_id, min_length, genetic_code))
+}
+
+#[tauri::command]
+async fn tauri_find_nullomers(
+    window: Window,
+    seq_ids: Vec<String>,
+    k: usize,
+    params: Option<NullomerParams>,
+) -> Result<NullomerReport, CommandError> {
+    in_window(&window, || find_nullomers(seq_ids, k, params))
+}
+
+#[tauri::command]
+async fn tauri_find_repeats(
+    window: Window,
+    seq_id: String,
+    params: Option<RepeatParams>,
+) -> Result<RepeatReport, CommandError> {
+    in_window(&window, || find_repeats(seq_id, params))
+}
+
+#[tauri::command]
+async fn tauri_dotplot(
+    app_window: Window,
+    seq_id_a: String,
+    seq_id_b: String,
+    window: usize,
+    threshold: Option<usize>,
+) -> Result<DotPlot, CommandError> {
+    in_window(&app_window, || {
+        dotplot(seq_id_a, seq_id_b, window, threshold)
+    })
+}
+
+#[tauri::command]
+async fn tauri_translate(
+    window: Window,
+    seq_id: String,
+    frame: i8,
+    genetic_code: Option<u8>,
+) -> Result<FrameTranslation, CommandError> {
+    in_window(&window, || translate(seq_id, frame, genetic_code))
+}
+
+#[tauri::command]
+async fn tauri_codon_usage(
+    window: Window,
+    seq_id: String,
+    region: Option<Region>,
+    params: Option<CodonUsageParams>,
+) -> Result<CodonUsage, CommandError> {
+    in_window(&window, || codon_usage(seq_id, region, params))
+}
+
+#[tauri::command]
+async fn tauri_compare_codon_usage(
+    window: Window,
+    seq_id: String,
+    organism: CodonHost,
+    region: Option<Region>,
+    genetic_code: Option<u8>,
+) -> Result<CodonUsageComparison, String> {
+    in_window(&window, || {
+        compare_codon_usage(seq_id, organism, region, genetic_code).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_predict_rbs(
+    window: Window,
+    seq_id: String,
+    start_codon: usize,
+    params: Option<RbsParams>,
+) -> Result<AnalysisResult<RbsPrediction>, CommandError> {
+    in_window(&window, || predict_rbs(seq_id, start_codon, params))
+}
+
+#[tauri::command]
+async fn tauri_list_analysis_plugins(window: Window) -> Result<Vec<PluginInfo>, String> {
+    in_window(&window, || {
+        list_analysis_plugins().map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_run_analysis_plugin(
+    window: Window,
+    seq_id: String,
+    name: String,
+    params: Option<serde_json::Value>,
+) -> Result<AnalysisResult<serde_json::Value>, String> {
+    in_window(&window, || {
+        run_analysis_plugin(seq_id, name, params).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_run_script(
+    window: Window,
+    seq_id: String,
+    script: String,
+) -> Result<serde_json::Value, CommandError> {
+    in_window(&window, || run_script(seq_id, script))
+}
+
+#[tauri::command]
+async fn tauri_insert_bases(
+    window: Window,
+    seq_id: String,
+    position: usize,
+    bases: String,
+) -> Result<EditResult, CommandError> {
+    in_window(&window, || insert_bases(seq_id, position, bases))
+}
+
+#[tauri::command]
+async fn tauri_delete_range(
+    window: Window,
+    seq_id: String,
+    region: Region,
+) -> Result<EditResult, CommandError> {
+    in_window(&window, || delete_range(seq_id, region))
+}
+
+#[tauri::command]
+async fn tauri_replace_range(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    bases: String,
+) -> Result<EditResult, CommandError> {
+    in_window(&window, || replace_range(seq_id, region, bases))
+}
+
+#[tauri::command]
+async fn tauri_set_topology(
+    window: Window,
+    seq_id: String,
+    topology: Topology,
+) -> Result<EditResult, String> {
+    in_window(&window, || {
+        set_topology(seq_id, topology).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_undo_edit(window: Window, seq_id: String) -> Result<EditResult, String> {
+    in_window(&window, || undo_edit(seq_id).map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_redo_edit(window: Window, seq_id: String) -> Result<EditResult, String> {
+    in_window(&window, || redo_edit(seq_id).map_err(|e| e.to_string()))
+}
+
+#[tauri::command]
+async fn tauri_export_proteome(
+    window: Window,
+    seq_id: String,
+    min_orf_len: usize,
+    path: String,
+) -> Result<ProteomeExport, String> {
+    in_window(&window, || {
+        export_proteome(seq_id, min_orf_len, path).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_detect_frameshift(
+    window: Window,
+    seq_id: String,
+    region: Region,
+    expected_protein: String,
+    genetic_code: Option<u8>,
+) -> Result<FrameshiftReport, CommandError> {
+    in_window(&window, || {
+        detect_frameshift(seq_id, region, expected_protein, genetic_code)
+    })
+}
+
+#[tauri::command]
+async fn tauri_check_primer_specificity(
+    window: Window,
+    primer_sequence: String,
+    max_mismatches: usize,
+) -> Result<Vec<PrimerBindingSite>, CommandError> {
+    in_window(&window, || {
+        check_primer_specificity(primer_sequence, max_mismatches)
+    })
+}
+
+#[tauri::command]
+async fn tauri_primer_duplex(
+    window: Window,
+    primer_sequence: String,
+    seq_id: String,
+    start: usize,
+    strand: Strand,
+) -> Result<PrimerDuplex, CommandError> {
+    in_window(&window, || {
+        primer_duplex(primer_sequence, seq_id, start, strand)
+    })
+}
+
+#[tauri::command]
+async fn tauri_find_protein_in_workspace(
+    window: Window,
+    query: String,
+    genetic_code: Option<u8>,
+) -> Result<Vec<TranslatedHit>, CommandError> {
+    in_window(&window, || find_protein_in_workspace(query, genetic_code))
+}
+
+#[tauri::command]
+async fn tauri_qc_primer_pair(
+    window: Window,
+    pair: PrimerPair,
+    params: Option<AmpliconQcParams>,
+) -> Result<PrimerPair, String> {
+    in_window(&window, || {
+        qc_primer_pair(pair, params).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_plan_standard_curve(
+    window: Window,
+    pair: PrimerPair,
+    stock: f64,
+    unit: ConcentrationUnit,
+    params: Option<StandardCurveParams>,
+) -> Result<PrimerPair, CommandError> {
+    in_window(&window, || plan_standard_curve(pair, stock, unit, params))
+}
+
+#[tauri::command]
+async fn tauri_register_backbone(
+    window: Window,
+    name: String,
+    seq_id: String,
+) -> Result<VectorBackbone, String> {
+    in_window(&window, || {
+        register_backbone(name, seq_id).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+async fn tauri_detect_backbones(
+    window: Window,
+    seq_id: String,
+) -> Result<Vec<BackboneMatch>, String> {
+    in_window(&window, || {
+        detect_backbones(seq_id).map_err(|e| e.to_string())
+    })
 }
 
 fn main() {
@@ -135,8 +1454,20 @@ fn main() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .manage(WindowWorkspaces::default())
+        .on_window_event(|window, event| {
+            if let WindowEvent::Destroyed = event {
+                if let Err(e) = window.state::<WindowWorkspaces>().release(window.label()) {
+                    eprintln!("Failed to close workspace: {}", e);
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
+            tauri_current_workspace,
+            tauri_join_workspace,
+            tauri_list_workspaces,
             tauri_parse_and_import,
+            tauri_parse_and_import_all,
             tauri_parse_preview,
             tauri_import_sequence,
             tauri_import_from_file,
@@ -145,17 +1476,140 @@ fn main() {
             tauri_detailed_stats,
             tauri_detailed_stats_enhanced,
             tauri_window_stats,
+            tauri_gc_skew_profile,
+            tauri_calculate_kmer_spectrum,
             tauri_export,
             tauri_get_meta,
+            tauri_list_sequences,
             tauri_storage_info,
             tauri_read_file,
             tauri_get_genbank_metadata,
             tauri_design_primers,
+            tauri_design_primers_streaming,
+            tauri_suggest_relaxation,
+            tauri_melting_map,
+            tauri_tm_grid,
+            tauri_predict_melt_curve,
             tauri_calculate_primer_tm,
             tauri_calculate_primer_gc,
-            tauri_evaluate_primer_multiplex
+            tauri_calculate_oligo_properties,
+            tauri_convert_concentration,
+            tauri_convert_oligo_amount,
+            tauri_oligo_resuspension,
+            tauri_dilution_series,
+            tauri_generate_linkers,
+            tauri_evaluate_primer_multiplex,
+            tauri_materialize_amplicon,
+            tauri_get_features,
+            tauri_add_variants,
+            tauri_get_variants,
+            tauri_design_hrm_tiling,
+            tauri_design_qpcr_assay,
+            tauri_suggest_probe_labels,
+            tauri_design_guides,
+            tauri_list_restriction_enzymes,
+            tauri_find_restriction_sites,
+            tauri_cutter_summary,
+            tauri_digest_protocol,
+            tauri_set_feature_display,
+            tauri_add_bookmark,
+            tauri_list_bookmarks,
+            tauri_remove_bookmark,
+            tauri_next_feature,
+            tauri_previous_feature,
+            tauri_import_bed,
+            tauri_export_bed,
+            tauri_import_gff3,
+            tauri_find_in_sequence,
+            tauri_search_motif,
+            tauri_scan_protein_motifs,
+            tauri_protein_hydropathy,
+            tauri_predict_signal_peptide,
+            tauri_design_expression_construct,
+            tauri_simulate_cloning,
+            tauri_design_assembly_primers,
+            tauri_register_backbone,
+            tauri_detect_backbones,
+            tauri_qc_primer_pair,
+            tauri_plan_standard_curve,
+            tauri_format_sequence_for_copy,
+            tauri_convert_coordinates,
+            tauri_describe_variant_hgvs,
+            tauri_simulate_mutations,
+            tauri_split_at_gaps,
+            tauri_stress_test_primer_panel,
+            tauri_render_map_model,
+            tauri_export_project_archive,
+            tauri_save_project,
+            tauri_open_project,
+            tauri_set_autosave,
+            tauri_set_warning_policy,
+            tauri_get_warning_policy,
+            tauri_export_assay_document,
+            tauri_register_reference_genome,
+            tauri_unregister_reference_genome,
+            tauri_list_reference_genomes,
+            tauri_import_reference_region,
+            tauri_design_primers_at_reference,
+            tauri_get_entrez_settings,
+            tauri_set_entrez_settings,
+            tauri_import_alignment,
+            tauri_conservation_track,
+            tauri_get_score_tracks,
+            tauri_oligo_cross_talk,
+            tauri_get_input_limits,
+            tauri_design_homology_arms,
+            tauri_design_junction_primers,
+            tauri_suggest_screening_strategy,
+            tauri_import_records_from_file,
+            tauri_set_reproducibility_mode,
+            tauri_get_session_provenance,
+            tauri_screen_vector_contamination,
+            tauri_read_set_statistics,
+            tauri_readset_distributions,
+            tauri_import_fastq_readset,
+            tauri_readset_stats,
+            tauri_get_reads,
+            tauri_find_orfs,
+            tauri_find_nullomers,
+            tauri_find_repeats,
+            tauri_dotplot,
+            tauri_translate,
+            tauri_codon_usage,
+            tauri_compare_codon_usage,
+            tauri_predict_rbs,
+            tauri_list_analysis_plugins,
+            tauri_run_analysis_plugin,
+            tauri_run_script,
+            tauri_insert_bases,
+            tauri_delete_range,
+            tauri_replace_range,
+            tauri_set_topology,
+            tauri_undo_edit,
+            tauri_redo_edit,
+            tauri_export_proteome,
+            tauri_check_primer_specificity,
+            tauri_primer_duplex,
+            tauri_find_protein_in_workspace,
+            tauri_detect_frameshift
         ])
         .setup(|app| {
+            // 登録した参照ゲノムはアプリ設定ディレクトリに保存
+            let settings = app.path().app_config_dir()?.join("references.json");
+            if let Err(e) = configure_reference_settings(settings.to_string_lossy().to_string()) {
+                eprintln!("Failed to load reference genome settings: {}", e);
+            }
+            // ワークスペースごとに、自動保存が有効なときだけ現在のプロジェクトファイルに書き込む
+            std::thread::spawn(|| loop {
+                std::thread::sleep(AUTOSAVE_INTERVAL);
+                for workspace_id in list_workspaces().unwrap_or_default() {
+                    if let Err(e) =
+                        with_workspace(&workspace_id, autosave_project).and_then(|saved| saved)
+                    {
+                        eprintln!("Autosave failed ({}): {}", workspace_id, e);
+                    }
+                }
+            });
             #[cfg(debug_assertions)]
             {
                 let window = app.get_webview_window("main").unwrap();
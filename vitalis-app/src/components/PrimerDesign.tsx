@@ -1,5 +1,17 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import {
+  ConstraintViolation,
+  constraintViolations,
+  formatCommandError,
+  formatWarning,
+  Locale,
+  sortBySeverity,
+  Warning,
+} from '../types/warning';
+
+const locale: Locale = navigator.language.startsWith('ja') ? 'ja' : 'en';
 
 interface PrimerDesignParams {
   length_min: number;
@@ -12,6 +24,8 @@ interface PrimerDesignParams {
   max_self_dimer: number;
   max_hairpin: number;
   max_hetero_dimer: number;
+  search_flank?: number;
+  search_anywhere?: boolean;
 }
 
 interface Primer {
@@ -31,7 +45,7 @@ interface ValidationResults {
   hairpin_check: boolean;
   hetero_dimer_check?: boolean;
   specificity?: number;
-  warnings: string[];
+  warnings: Warning[];
 }
 
 interface PrimerPair {
@@ -51,10 +65,66 @@ interface PrimerPair {
 
 interface MultiplexCompatibility {
   compatibility_matrix: { [key: string]: { [key: string]: number } };
-  warnings: string[];
+  warnings: Warning[];
   overall_score: number;
 }
 
+type RejectionReason =
+  | 'tm'
+  | 'gc'
+  | 'quality_mask'
+  | 'position_weighting'
+  | 'tm_difference'
+  | 'hetero_dimer'
+  | 'product_size';
+
+interface NearMiss {
+  reason: RejectionReason;
+  excess: number;
+  primers: Primer[];
+}
+
+interface DesignDiagnostics {
+  candidates_evaluated: number;
+  pairs_evaluated: number;
+  rejected: Record<RejectionReason, number>;
+  flagged: { self_dimer: number; hairpin: number; three_prime: number };
+  near_misses: NearMiss[];
+}
+
+const rejectionLabels: Record<RejectionReason, { label: string; unit: string }> = {
+  tm: { label: 'Tm out of range', unit: '°C' },
+  gc: { label: 'GC content out of range', unit: '%' },
+  quality_mask: { label: 'Low-quality bases', unit: '' },
+  position_weighting: { label: 'Low-weight bases', unit: '' },
+  tm_difference: { label: 'Pair Tm difference', unit: '°C' },
+  hetero_dimer: { label: 'Hetero-dimer', unit: 'kcal/mol' },
+  product_size: { label: 'Product size', unit: 'bp' },
+};
+
+type RelaxedParameter = 'tm_range' | 'gc_range' | 'length_range' | 'hetero_dimer' | 'search_flank';
+
+interface RelaxationSuggestion {
+  parameter: RelaxedParameter;
+  change: number;
+  params: PrimerDesignParams;
+  pairs: number;
+}
+
+interface RelaxationPlan {
+  min_pairs: number;
+  current_pairs: number;
+  suggestions: RelaxationSuggestion[];
+}
+
+const relaxationLabels: Record<RelaxedParameter, (change: number) => string> = {
+  tm_range: (change) => `Widen Tm range by ${change} °C on each side`,
+  gc_range: (change) => `Widen GC range by ${change}% on each side`,
+  length_range: (change) => `Widen primer length range by ${change} nt on each side`,
+  hetero_dimer: (change) => `Allow hetero-dimers ${change} kcal/mol stronger`,
+  search_flank: (change) => `Search ${change} bp further around the target`,
+};
+
 interface PrimerDesignResult {
   pairs: PrimerPair[];
   design_params: PrimerDesignParams;
@@ -62,6 +132,23 @@ interface PrimerDesignResult {
   target_start: number;
   target_end: number;
   multiplex_compatibility?: MultiplexCompatibility;
+  blocked_pairs: string[];
+  diagnostics?: DesignDiagnostics;
+}
+
+// Validated pairs streamed while the design runs (unranked)
+interface PrimerDesignProgress {
+  run_id: string;
+  pairs: PrimerPair[];
+  pairs_found: number;
+  pairs_evaluated: number;
+  pairs_total: number;
+}
+
+interface StreamedPairs {
+  pairs: PrimerPair[];
+  evaluated: number;
+  total: number;
 }
 
 interface PrimerDesignProps {
@@ -79,6 +166,8 @@ const defaultParams: PrimerDesignParams = {
   max_self_dimer: -15.0,  // More permissive (allow stronger self-dimers)
   max_hairpin: -12.0,     // More permissive (allow stronger hairpins)
   max_hetero_dimer: -20.0, // Much more permissive (allow stronger hetero-dimers)
+  search_flank: 50,
+  search_anywhere: false,
 };
 
 export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
@@ -88,6 +177,9 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
   const [designResult, setDesignResult] = useState<PrimerDesignResult | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [violations, setViolations] = useState<ConstraintViolation[]>([]);
+  const [relaxation, setRelaxation] = useState<RelaxationPlan | null>(null);
+  const [streamed, setStreamed] = useState<StreamedPairs | null>(null);
 
   const handleDesignPrimers = async () => {
     if (!sequenceId) {
@@ -97,24 +189,72 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
 
     setLoading(true);
     setError(null);
+    setViolations([]);
+    setDesignResult(null);
+    setStreamed(null);
+
+    const runId = crypto.randomUUID();
+    const unlisten = await listen<PrimerDesignProgress>('primer-design-progress', (event) => {
+      const progress = event.payload;
+      if (progress.run_id !== runId) return;
+      setStreamed((current) => ({
+        pairs: [...(current?.pairs ?? []), ...progress.pairs],
+        evaluated: progress.pairs_evaluated,
+        total: progress.pairs_total,
+      }));
+    });
 
     try {
-      const result = await invoke<PrimerDesignResult>('tauri_design_primers', {
+      const result = await invoke<PrimerDesignResult>('tauri_design_primers_streaming', {
+        runId,
         seqId: sequenceId,
-        start: targetStart - 1, // Convert to 0-based indexing
-        end: targetEnd - 1,
+        // 1-based inclusive input -> 0-based half-open region
+        region: { start: targetStart - 1, end: targetEnd, strand: 'Forward' },
         params: params,
       });
 
       setDesignResult(result);
+      setRelaxation(null);
+    } catch (err) {
+      setViolations(constraintViolations(err) ?? []);
+      setError(formatCommandError(err, locale));
+    } finally {
+      unlisten();
+      setStreamed(null);
+      setLoading(false);
+    }
+  };
+
+  const handleSuggestRelaxation = async () => {
+    if (!sequenceId) return;
+    setLoading(true);
+    setError(null);
+    setViolations([]);
+    try {
+      const plan = await invoke<RelaxationPlan>('tauri_suggest_relaxation', {
+        seqId: sequenceId,
+        region: { start: targetStart - 1, end: targetEnd, strand: 'Forward' },
+        params: params,
+        minPairs: 3,
+      });
+      setRelaxation(plan);
     } catch (err) {
-      setError(err as string);
+      setViolations(constraintViolations(err) ?? []);
+      setError(formatCommandError(err, locale));
     } finally {
       setLoading(false);
     }
   };
 
-  const handleParamChange = (key: keyof PrimerDesignParams, value: number) => {
+  /** Input style, outlined in red when the backend rejected `params.<key>` */
+  const inputClass = (key: keyof PrimerDesignParams) =>
+    `w-full px-3 py-2 border rounded focus:ring-2 focus:ring-blue-500 ${
+      violations.some((violation) => violation.field === `params.${key}`)
+        ? 'border-red-500'
+        : 'border-gray-300'
+    }`;
+
+  const handleParamChange = (key: Exclude<keyof PrimerDesignParams, 'search_anywhere'>, value: number) => {
     setParams(prev => ({ ...prev, [key]: value }));
   };
 
@@ -177,7 +317,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.length_min}
                   onChange={(e) => handleParamChange('length_min', parseInt(e.target.value) || 18)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('length_min')}
                   min="10"
                   max="50"
                 />
@@ -188,7 +328,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.length_max}
                   onChange={(e) => handleParamChange('length_max', parseInt(e.target.value) || 25)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('length_max')}
                   min="10"
                   max="50"
                 />
@@ -206,7 +346,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.tm_min}
                   onChange={(e) => handleParamChange('tm_min', parseFloat(e.target.value) || 55)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('tm_min')}
                   step="0.1"
                   min="40"
                   max="80"
@@ -218,7 +358,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.tm_max}
                   onChange={(e) => handleParamChange('tm_max', parseFloat(e.target.value) || 65)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('tm_max')}
                   step="0.1"
                   min="40"
                   max="80"
@@ -230,7 +370,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.tm_optimal}
                   onChange={(e) => handleParamChange('tm_optimal', parseFloat(e.target.value) || 60)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('tm_optimal')}
                   step="0.1"
                   min="40"
                   max="80"
@@ -249,7 +389,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.gc_min}
                   onChange={(e) => handleParamChange('gc_min', parseFloat(e.target.value) || 40)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('gc_min')}
                   step="1"
                   min="0"
                   max="100"
@@ -261,7 +401,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.gc_max}
                   onChange={(e) => handleParamChange('gc_max', parseFloat(e.target.value) || 60)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('gc_max')}
                   step="1"
                   min="0"
                   max="100"
@@ -280,7 +420,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.max_self_dimer}
                   onChange={(e) => handleParamChange('max_self_dimer', parseFloat(e.target.value) || -5)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('max_self_dimer')}
                   step="0.1"
                   max="0"
                 />
@@ -291,13 +431,40 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                   type="number"
                   value={params.max_hairpin}
                   onChange={(e) => handleParamChange('max_hairpin', parseFloat(e.target.value) || -3)}
-                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500"
+                  className={inputClass('max_hairpin')}
                   step="0.1"
                   max="0"
                 />
               </div>
             </div>
           </div>
+
+          {/* Search Range */}
+          <div>
+            <h4 className="font-medium text-gray-700 mb-3">Search Range</h4>
+            <div className="space-y-3">
+              <div>
+                <label className="block text-sm text-gray-600 mb-1">Flank around target ends (bp)</label>
+                <input
+                  type="number"
+                  value={params.search_flank ?? 50}
+                  onChange={(e) => handleParamChange('search_flank', parseInt(e.target.value) || 50)}
+                  disabled={params.search_anywhere}
+                  className="w-full px-3 py-2 border border-gray-300 rounded focus:ring-2 focus:ring-blue-500 disabled:bg-gray-100"
+                  min="0"
+                  max="500"
+                />
+              </div>
+              <label className="flex items-center gap-2 text-sm text-gray-600">
+                <input
+                  type="checkbox"
+                  checked={params.search_anywhere ?? false}
+                  onChange={(e) => setParams(prev => ({ ...prev, search_anywhere: e.target.checked }))}
+                />
+                Anywhere within the product size limit (3000 bp)
+              </label>
+            </div>
+          </div>
         </div>
 
         <div className="mt-6">
@@ -318,12 +485,36 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
             <div className="text-red-400">⚠️</div>
             <div className="ml-3">
               <h3 className="text-sm font-medium text-red-800">Error</h3>
-              <p className="text-sm text-red-700 mt-1">{error}</p>
+              <p className="text-sm text-red-700 mt-1 whitespace-pre-line">{error}</p>
             </div>
           </div>
         </div>
       )}
 
+      {/* Candidates found so far */}
+      {loading && streamed && (
+        <div className="bg-white rounded-lg shadow-sm border border-gray-200 p-6">
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">
+            {streamed.pairs.length} candidate pairs found so far
+          </h3>
+          <div className="w-full bg-gray-200 rounded-full h-2 mb-4">
+            <div
+              className="h-2 rounded-full bg-blue-600"
+              style={{ width: `${streamed.total ? (streamed.evaluated / streamed.total) * 100 : 0}%` }}
+            ></div>
+          </div>
+          <ul className="space-y-1 text-sm font-mono">
+            {streamed.pairs.slice(0, 5).map((pair) => (
+              <li key={pair.id}>
+                {pair.forward.sequence} / {pair.reverse.sequence}{' '}
+                <span className="text-gray-500">({pair.amplicon_length} bp)</span>
+              </li>
+            ))}
+          </ul>
+          <p className="text-xs text-gray-500 mt-2">Unranked; the final ranked list replaces these when the search completes.</p>
+        </div>
+      )}
+
       {/* Results Display */}
       {designResult && (
         <div className="bg-white rounded-lg shadow-sm border border-gray-200 p-6">
@@ -338,13 +529,85 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
               <p className="text-gray-600">
                 Try adjusting the design parameters or target region to find suitable primers.
               </p>
+              {designResult.diagnostics && (
+                <div className="mt-4 text-left max-w-xl mx-auto text-sm">
+                  <p className="text-gray-700 mb-2">
+                    {designResult.diagnostics.candidates_evaluated} candidates and{' '}
+                    {designResult.diagnostics.pairs_evaluated} pairs evaluated. Rejected by:
+                  </p>
+                  <ul className="space-y-1">
+                    {(Object.keys(rejectionLabels) as RejectionReason[])
+                      .filter((reason) => designResult.diagnostics!.rejected[reason] > 0)
+                      .map((reason) => (
+                        <li key={reason} className="flex justify-between">
+                          <span>{rejectionLabels[reason].label}</span>
+                          <span className="font-mono">{designResult.diagnostics!.rejected[reason]}</span>
+                        </li>
+                      ))}
+                  </ul>
+                  <p className="text-gray-500 mt-2">
+                    Flagged (not rejected): self-dimer {designResult.diagnostics.flagged.self_dimer}, hairpin{' '}
+                    {designResult.diagnostics.flagged.hairpin}, 3' end {designResult.diagnostics.flagged.three_prime}
+                  </p>
+                  {designResult.diagnostics.near_misses.length > 0 && (
+                    <div className="mt-3">
+                      <h5 className="font-medium text-gray-900 mb-1">Closest near-misses</h5>
+                      <ul className="space-y-1">
+                        {designResult.diagnostics.near_misses.map((miss, index) => (
+                          <li key={index} className="font-mono text-xs">
+                            {rejectionLabels[miss.reason].label} +{miss.excess.toFixed(1)}{' '}
+                            {rejectionLabels[miss.reason].unit}:{' '}
+                            {miss.primers.map((primer) => `${primer.sequence} (${primer.tm.toFixed(1)}°C)`).join(' / ')}
+                          </li>
+                        ))}
+                      </ul>
+                    </div>
+                  )}
+                  <div className="mt-3">
+                    <button
+                      onClick={handleSuggestRelaxation}
+                      disabled={loading}
+                      className="px-3 py-1 rounded bg-blue-600 text-white text-sm disabled:opacity-50"
+                    >
+                      Suggest relaxed parameters
+                    </button>
+                    {relaxation && relaxation.suggestions.length === 0 && (
+                      <p className="text-gray-500 mt-2">
+                        No single parameter change yields {relaxation.min_pairs} pairs.
+                      </p>
+                    )}
+                    <ul className="space-y-1 mt-2">
+                      {relaxation?.suggestions.map((suggestion) => (
+                        <li key={suggestion.parameter} className="flex justify-between items-center">
+                          <span>
+                            {relaxationLabels[suggestion.parameter](suggestion.change)} ({suggestion.pairs} pairs)
+                          </span>
+                          <button
+                            onClick={() => setParams(suggestion.params)}
+                            className="text-blue-600 hover:underline text-xs"
+                          >
+                            Apply
+                          </button>
+                        </li>
+                      ))}
+                    </ul>
+                  </div>
+                </div>
+              )}
             </div>
           ) : (
             <div className="space-y-4">
               {designResult.pairs.slice(0, 5).map((pair, index) => (
                 <div key={pair.id} className="border border-gray-200 rounded-lg p-4">
                   <div className="flex justify-between items-start mb-3">
-                    <h4 className="font-medium text-gray-900">Primer Pair {index + 1}</h4>
+                    <h4 className="font-medium text-gray-900">
+                      Primer Pair {index + 1}
+                      {designResult.blocked_pairs.includes(pair.id) && (
+                        <span className="ml-2 px-2 py-0.5 rounded bg-red-100 text-red-800 text-xs">
+                          Not saved to project
+                        </span>
+                      )}
+                    </h4>
                     <div className="text-sm text-gray-500">
                       Amplicon: {pair.amplicon_length} bp
                     </div>
@@ -424,10 +687,12 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                       <div className="bg-yellow-50 border border-yellow-200 rounded p-2">
                         <div className="text-xs font-medium text-yellow-800 mb-1">⚠️ Validation Warnings:</div>
                         <ul className="text-xs text-yellow-700 space-y-1">
-                          {pair.validation_results.warnings.map((warning, wIndex) => (
+                          {sortBySeverity(pair.validation_results.warnings).map((warning, wIndex) => (
                             <li key={wIndex} className="flex items-start">
-                              <span className="text-yellow-600 mr-1">•</span>
-                              {warning}
+                              <span className={warning.severity === 'error' ? 'text-red-600 mr-1' : 'text-yellow-600 mr-1'}>
+                                •
+                              </span>
+                              {formatWarning(warning, locale)}
                             </li>
                           ))}
                         </ul>
@@ -512,7 +777,7 @@ export const PrimerDesign: React.FC<PrimerDesignProps> = ({ sequenceId }) => {
                         {designResult.multiplex_compatibility.warnings.map((warning, wIndex) => (
                           <li key={wIndex} className="flex items-start">
                             <span className="text-yellow-600 mr-1 mt-0.5">•</span>
-                            <span>{warning}</span>
+                            <span>{formatWarning(warning, locale)}</span>
                           </li>
                         ))}
                       </ul>
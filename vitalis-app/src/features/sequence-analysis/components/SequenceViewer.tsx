@@ -1,5 +1,8 @@
 import { useState, useEffect, useRef } from "react";
 import { tauriApi } from "../../../lib/tauri-api";
+import { formatCommandError, Locale } from "../../../types/warning";
+
+const locale: Locale = navigator.language.startsWith("ja") ? "ja" : "en";
 
 interface SequenceViewerProps {
   sequenceId: string;
@@ -28,7 +31,7 @@ export const SequenceViewer = ({ sequenceId, sequenceLength }: SequenceViewerPro
       setWindowStart(start);
     } catch (err) {
       console.error("Error loading sequence window:", err);
-      setError(err instanceof Error ? err.message : "Failed to load sequence");
+      setError(formatCommandError(err, locale));
     } finally {
       setLoading(false);
     }
@@ -1,6 +1,9 @@
 import { useState } from "react";
 import { tauriApi } from "../../../lib/tauri-api";
 import { SequenceInputData, ParsePreviewResponse } from "../../../types/sequence";
+import { formatCommandError, Locale } from "../../../types/warning";
+
+const locale: Locale = navigator.language.startsWith("ja") ? "ja" : "en";
 
 export const useSequenceParser = () => {
   const [loading, setLoading] = useState(false);
@@ -24,7 +27,7 @@ export const useSequenceParser = () => {
       }
     } catch (err) {
       console.error("Error parsing sequence:", err);
-      setError(err instanceof Error ? err.message : "Unknown error occurred");
+      setError(formatCommandError(err, locale));
     } finally {
       setLoading(false);
     }
@@ -40,7 +43,7 @@ export const useSequenceParser = () => {
       setPreview(null); // Clear preview after successful import
     } catch (err) {
       console.error("Error importing sequence:", err);
-      setError(err instanceof Error ? err.message : "Unknown error occurred");
+      setError(formatCommandError(err, locale));
     } finally {
       setLoading(false);
     }
@@ -55,7 +58,7 @@ export const useSequenceParser = () => {
       setSequenceId(result.seq_id);
     } catch (err) {
       console.error("Error parsing sequence:", err);
-      setError(err instanceof Error ? err.message : "Unknown error occurred");
+      setError(formatCommandError(err, locale));
     } finally {
       setLoading(false);
     }
@@ -96,7 +99,7 @@ export const useSequenceParser = () => {
       }
     } catch (err) {
       console.error("Error importing file:", err);
-      setError(err instanceof Error ? err.message : "Unknown error occurred");
+      setError(formatCommandError(err, locale));
     } finally {
       setLoading(false);
     }
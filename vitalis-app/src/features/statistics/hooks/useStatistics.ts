@@ -1,6 +1,9 @@
 import { useState } from "react";
 import { tauriApi } from "../../../lib/tauri-api";
 import { DetailedStats } from "../../../types/sequence";
+import { formatCommandError, Locale } from "../../../types/warning";
+
+const locale: Locale = navigator.language.startsWith("ja") ? "ja" : "en";
 
 export const useStatistics = () => {
   const [loading, setLoading] = useState(false);
@@ -16,7 +19,7 @@ export const useStatistics = () => {
       setStats(result);
     } catch (err) {
       console.error("Error getting statistics:", err);
-      setError(err instanceof Error ? err.message : "Unknown error occurred");
+      setError(formatCommandError(err, locale));
     } finally {
       setLoading(false);
     }
@@ -0,0 +1,147 @@
+// Structured warnings and validation violations returned by vitalis-core.
+// `message` is the English default; `formatWarning` renders other locales from `code` + `params`.
+
+export type WarningCategory =
+  | 'melting_temperature'
+  | 'gc_content'
+  | 'length'
+  | 'repeat'
+  | 'three_prime_end'
+  | 'secondary_structure'
+  | 'position_weighting'
+  | 'multiplex'
+  | 'amplicon'
+  | 'standard_curve'
+  | 'labeling'
+  | 'other';
+
+export type WarningSeverity = 'info' | 'warning' | 'error';
+
+export interface Warning {
+  code: string;
+  params?: Record<string, string | number | boolean | null>;
+  severity: WarningSeverity;
+  category: WarningCategory;
+  message: string;
+}
+
+/** Severities that keep a designed primer pair out of the project (`set_warning_policy`). */
+export interface WarningPolicy {
+  blocking: WarningSeverity[];
+}
+
+export interface ConstraintViolation {
+  field: string;
+  code: string;
+  params?: Record<string, string | number | null>;
+  message: string;
+}
+
+/** Error of a command that validates its input (`CommandError` in vitalis-core). */
+export type CommandError =
+  | { kind: 'validation'; detail: { violations: ConstraintViolation[] } }
+  | { kind: 'other'; detail: string };
+
+export type Locale = 'en' | 'ja';
+
+type Params = NonNullable<Warning['params']>;
+
+const fixed = (value: unknown, digits: number) =>
+  typeof value === 'number' ? value.toFixed(digits) : String(value);
+
+const ja: Record<string, (p: Params) => string> = {
+  low_tm: (p) => `Tm が低い: ${fixed(p.tm, 1)}°C（推奨 ${p.min}–${p.max}°C）`,
+  high_tm: (p) => `Tm が高い: ${fixed(p.tm, 1)}°C（推奨 ${p.min}–${p.max}°C）`,
+  low_gc: (p) => `GC 含量が低い: ${fixed(p.gc, 1)}%（推奨 ${p.min}–${p.max}%）`,
+  high_gc: (p) => `GC 含量が高い: ${fixed(p.gc, 1)}%（推奨 ${p.min}–${p.max}%）`,
+  short_primer: (p) => `プライマーが短い: ${p.length} bp（推奨 ${p.min}–${p.max} bp）`,
+  long_primer: (p) => `プライマーが長い: ${p.length} bp（推奨 ${p.min}–${p.max} bp）`,
+  single_base_repeat: (p) => `単一塩基の連続 ${p.repeat} を含む`,
+  dinucleotide_repeat: () => 'ジヌクレオチドの繰り返しを含む',
+  long_single_base_repeat: (p) => `単一塩基の長い連続 ${p.repeat} を含む`,
+  at_rich_three_prime_end: () => "3'末端が AT に富み、結合が弱い可能性がある",
+  weak_three_prime_terminus: () => "3'末端が AA/TT で弱い",
+  strong_self_dimer: (p) => `強いセルフダイマー: ${fixed(p.delta_g, 1)} kcal/mol`,
+  moderate_self_dimer: (p) => `中程度のセルフダイマー: ${fixed(p.delta_g, 1)} kcal/mol`,
+  strong_hairpin: (p) => `強いヘアピン: ${fixed(p.delta_g, 1)} kcal/mol`,
+  moderate_hairpin: (p) => `中程度のヘアピン: ${fixed(p.delta_g, 1)} kcal/mol`,
+  low_weight_bases: (p) => `重みの低い塩基が ${p.count} 個ある`,
+  oligo_self_dimer: (p) =>
+    `${p.oligo} のセルフダイマー ΔG ${fixed(p.delta_g, 1)} kcal/mol が ${fixed(p.threshold, 1)} を下回る`,
+  oligo_hairpin: (p) =>
+    `${p.oligo} のヘアピン ΔG ${fixed(p.delta_g, 1)} kcal/mol が ${fixed(p.threshold, 1)} を下回る`,
+  probe_more_g_than_c: () => 'プローブはどちらの鎖でも G が C より多い',
+  multiplex_tm_difference: (p) => `${p.pair1} と ${p.pair2} の Tm 差が大きい（${fixed(p.difference, 1)}°C）`,
+  strong_cross_reactivity: (p) =>
+    `${p.pair1} と ${p.pair2} の間に強い交差反応（ΔG ${fixed(p.delta_g, 1)} kcal/mol）`,
+  moderate_cross_reactivity: (p) =>
+    `${p.pair1} と ${p.pair2} の間に中程度の交差反応（ΔG ${fixed(p.delta_g, 1)} kcal/mol）`,
+  multiplex_amplicon_size_difference: (p) =>
+    `${p.pair1}（${p.length1} bp）と ${p.pair2}（${p.length2} bp）の増幅産物長の差が大きい`,
+  multiplex_gc_difference: (p) => `${p.pair1} と ${p.pair2} の GC 含量の差が大きい（${fixed(p.difference, 1)}%）`,
+  amplicon_low_window_gc: (p) => `${p.window} bp ウィンドウ内で GC が ${fixed(p.gc, 0)}% まで下がる`,
+  amplicon_high_window_gc: (p) => `${p.window} bp ウィンドウ内で GC が ${fixed(p.gc, 0)}% に達する`,
+  amplicon_homopolymer: (p) => `${p.position} に ${p.length} 塩基の poly-${p.base}`,
+  amplicon_hairpins: (p) =>
+    `${fixed(p.threshold, 1)} kcal/mol を下回るヘアピンが ${p.count} 個（最強 ${fixed(p.delta_g, 1)}、位置 ${p.position}）`,
+  amplicon_tandem_repeats: (p) => `タンデムリピートが増幅産物の ${fixed(p.percent, 0)}% を占める`,
+  probe_channel_conflict: (p) =>
+    `反応 ${p.reaction}: ${p.probe1} と ${p.probe2} が ${p.channel} チャネルを共有している`,
+  multiplex_exceeds_channels: (p) =>
+    `反応 ${p.reaction} のプローブ ${p.probes} 本に対し、使えるチャネルは ${p.channels} 個`,
+  fluorophore_outside_channels: (p) => `${p.probe} の ${p.fluorophore} はどのチャネルでも検出できない`,
+  low_copy_standard: (p) =>
+    `${p.label} は 1 反応あたり ${fixed(p.copies, 1)} コピー。${p.min_copies} コピー未満はポアソン誤差で信頼できない`,
+  few_standards: (p) => `標準が ${p.points} 点のみ。増幅効率の算出には ${p.recommended} 点以上を推奨`,
+};
+
+const jaViolations: Record<string, (p: Params) => string> = {
+  out_of_range: (p) => `${p.min}〜${p.max} の範囲で指定する（指定値 ${p.value}）`,
+  too_large: (p) => `${p.max} 以下で指定する（指定値 ${p.value}）`,
+  not_finite: (p) => `有限の数値で指定する（指定値 ${p.value}）`,
+  exceeds_field: (p) => `${p.other} を超えてはならない（${p.value} > ${p.other_value}）`,
+  invalid_delimiter: (p) => `区切り文字 '${p.delimiter}' は使えない`,
+  invalid_color: (p) => `色は #RRGGBB 形式で指定する（指定値 ${p.value}）`,
+  empty: () => '空にできない',
+  not_iupac_code: (p) => `IUPAC コードでない文字を含む: '${p.character}'`,
+  not_amino_acid: (p) => `アミノ酸でない文字を含む: '${p.character}'`,
+  not_less_than_length: (p) => `配列長（${p.length}）未満で指定する`,
+};
+
+const severityRank: Record<WarningSeverity, number> = { error: 0, warning: 1, info: 2 };
+
+/** Warnings ordered from most to least severe, keeping the backend order within a severity. */
+export function sortBySeverity(warnings: Warning[]): Warning[] {
+  return [...warnings].sort((a, b) => severityRank[a.severity] - severityRank[b.severity]);
+}
+
+/** Display text of a warning in `locale`, falling back to the English message. */
+export function formatWarning(warning: Warning, locale: Locale): string {
+  const template = locale === 'ja' ? ja[warning.code] : undefined;
+  return template ? template(warning.params ?? {}) : warning.message;
+}
+
+/** Display text of a violated constraint in `locale`, prefixed with the offending field. */
+export function formatViolation(violation: ConstraintViolation, locale: Locale): string {
+  const template = locale === 'ja' ? jaViolations[violation.code] : undefined;
+  return `${violation.field} ${template ? template(violation.params ?? {}) : violation.message}`;
+}
+
+function isCommandError(error: unknown): error is CommandError {
+  return typeof error === 'object' && error !== null && 'kind' in error && 'detail' in error;
+}
+
+/** Violations of a command rejected by input validation, or `null` for any other failure. */
+export function constraintViolations(error: unknown): ConstraintViolation[] | null {
+  return isCommandError(error) && error.kind === 'validation' ? error.detail.violations : null;
+}
+
+/** Display text of a failed `invoke`: each violation on its own line, otherwise the error message. */
+export function formatCommandError(error: unknown, locale: Locale): string {
+  if (isCommandError(error)) {
+    return error.kind === 'validation'
+      ? error.detail.violations.map((violation) => formatViolation(violation, locale)).join('\n')
+      : error.detail;
+  }
+  return error instanceof Error ? error.message : String(error);
+}
@@ -0,0 +1,58 @@
+//! Dot plot benchmarks (`cargo bench --bench dotplot_bench`).
+//!
+//! Performance target (release build, single core): < 1 s for a 1 Mb × 1 Mb
+//! comparison, with exact 20-mer windows and with one mismatch allowed
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::time::Duration;
+use vitalis_core::services::DotPlotService;
+
+const LENGTH: usize = 1_000_000;
+
+/// Reproducible pseudo-random sequence (~50% GC)
+fn random_sequence(length: usize, seed: u64) -> String {
+    let mut state = seed;
+    (0..length)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            b"ACGT"[(state >> 33) as usize % 4] as char
+        })
+        .collect()
+}
+
+/// `sequence` with every 100th base substituted, so diagonals break into runs
+fn diverged(sequence: &str) -> String {
+    sequence
+        .bytes()
+        .enumerate()
+        .map(|(i, base)| match (i % 100, base) {
+            (0, b'A') => 'C',
+            (0, _) => 'A',
+            (_, base) => base as char,
+        })
+        .collect()
+}
+
+fn bench_dotplot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dotplot_1mb");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let service = DotPlotService::new();
+    let a = random_sequence(LENGTH, 42);
+    let b = diverged(&a);
+    for (window, threshold) in [(20, 20), (20, 19)] {
+        group.bench_with_input(
+            BenchmarkId::new(format!("window_{}", window), threshold),
+            &(window, threshold),
+            |bencher, &(window, threshold)| {
+                bencher.iter(|| black_box(service.compare(black_box(&a), &b, window, threshold)))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_dotplot);
+criterion_main!(benches);
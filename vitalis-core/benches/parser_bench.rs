@@ -1,8 +1,12 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::io::Write;
+use std::path::Path;
 use std::time::Duration;
 use vitalis_core::application::{get_window, parse_and_import};
+use vitalis_core::infrastructure::FileSequenceRepository;
 use vitalis_core::io::{parse_fasta, parse_fastq};
 use vitalis_core::stats::calculate_detailed_stats;
+use vitalis_core::Region;
 
 fn generate_fasta(length: usize) -> String {
     let mut result = String::new();
@@ -41,6 +45,21 @@ fn generate_fastq(length: usize) -> String {
     result
 }
 
+fn generate_contigs(count: usize, length: usize) -> String {
+    let bases = ['A', 'T', 'C', 'G'];
+    let mut result = String::with_capacity(count * (length + length / 80 + 32));
+    for contig in 0..count {
+        result.push_str(&format!(">contig_{} len={}\n", contig, length));
+        for i in 0..length {
+            result.push(bases[(i + contig) % 4]);
+            if (i + 1) % 80 == 0 || i + 1 == length {
+                result.push('\n');
+            }
+        }
+    }
+    result
+}
+
 fn bench_fasta_parsing(c: &mut Criterion) {
     let mut group = c.benchmark_group("fasta_parsing");
     group.measurement_time(Duration::from_secs(10));
@@ -116,14 +135,20 @@ fn bench_window_access(c: &mut Criterion) {
 
     group.bench_function("get_window_1kb", |b| {
         b.iter(|| {
-            let result = get_window(black_box(seq_id.clone()), black_box(0), black_box(1000));
+            let result = get_window(
+                black_box(seq_id.clone()),
+                Region::new(black_box(0), black_box(1000)),
+            );
             black_box(result)
         })
     });
 
     group.bench_function("get_window_10kb", |b| {
         b.iter(|| {
-            let result = get_window(black_box(seq_id.clone()), black_box(0), black_box(10000));
+            let result = get_window(
+                black_box(seq_id.clone()),
+                Region::new(black_box(0), black_box(10000)),
+            );
             black_box(result)
         })
     });
@@ -131,6 +156,38 @@ fn bench_window_access(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_multi_record_import(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multi_record_import");
+    group.measurement_time(Duration::from_secs(10));
+    group.sample_size(20);
+
+    // Metagenome-like file: 50k contigs of 300 bp (~17 MB)
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(generate_contigs(50_000, 300).as_bytes())
+        .unwrap();
+    let import = |path: &Path, threads: usize| {
+        let mut repository = FileSequenceRepository::new();
+        repository
+            .import_records_from_file(path, "fasta", threads)
+            .unwrap()
+            .len()
+    };
+
+    let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut thread_counts = vec![1, 2, 4, available];
+    thread_counts.sort_unstable();
+    thread_counts.dedup();
+    for threads in thread_counts {
+        group.bench_with_input(
+            BenchmarkId::new("fasta_50k_contigs", threads),
+            &threads,
+            |b, &threads| b.iter(|| black_box(import(file.path(), black_box(threads)))),
+        );
+    }
+
+    group.finish();
+}
+
 fn bench_integration_test(c: &mut Criterion) {
     let mut group = c.benchmark_group("integration");
     group.measurement_time(Duration::from_secs(10));
@@ -154,6 +211,7 @@ criterion_group!(
     bench_fastq_parsing,
     bench_stats_calculation,
     bench_window_access,
+    bench_multi_record_import,
     bench_integration_test
 );
 criterion_main!(benches);
@@ -0,0 +1,93 @@
+//! Primer design and thermodynamics benchmarks (`cargo bench --bench primer_bench`).
+//!
+//! Performance targets (release build, single core; compare local runs against these):
+//! - `design_primers` with a 500 bp target: < 100 ms for 1 kb, 10 kb and 100 kb templates
+//!   (candidates are only searched near the target, so the template size should not matter)
+//! - `calculate_tm_nearest_neighbor`: < 5 µs for a 20-mer, < 10 µs for a 30-mer
+//! - `calculate_enhanced_hairpin`: < 20 µs for a 20-mer, < 200 µs for a 40-mer
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::time::Duration;
+use vitalis_core::domain::iupac::reverse_complement;
+use vitalis_core::domain::primer::{PrimerDesignParams, PrimerDesignService};
+use vitalis_core::domain::thermodynamic_calculator::ThermodynamicCalculator;
+use vitalis_core::services::PrimerDesignServiceImpl;
+
+/// Reproducible pseudo-random sequence (~50% GC)
+fn random_sequence(length: usize, seed: u64) -> String {
+    let mut state = seed;
+    (0..length)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            b"ACGT"[(state >> 33) as usize % 4] as char
+        })
+        .collect()
+}
+
+fn bench_design_primers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("design_primers");
+    group.measurement_time(Duration::from_secs(10));
+
+    let service = PrimerDesignServiceImpl::new();
+    // Relaxed Tm/GC windows so the random templates always yield candidate pairs
+    let params = PrimerDesignParams {
+        tm_min: 40.0,
+        tm_max: 80.0,
+        gc_min: 20.0,
+        gc_max: 80.0,
+        ..PrimerDesignParams::default()
+    };
+    for length in [1_000, 10_000, 100_000] {
+        let template = random_sequence(length, 42);
+        // 500 bp target in the middle of the template
+        let start = length / 2 - 250;
+        group.bench_with_input(
+            BenchmarkId::new("500bp_target", length),
+            &template,
+            |b, template| {
+                b.iter(|| {
+                    let result =
+                        service.design_primers(black_box(template), start, start + 500, &params);
+                    black_box(result)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_thermodynamics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("thermodynamics");
+    let calculator = ThermodynamicCalculator::new_nndb_2024();
+
+    for length in [20, 30] {
+        let oligo = random_sequence(length, 7);
+        group.bench_with_input(
+            BenchmarkId::new("tm_nearest_neighbor", length),
+            &oligo,
+            |b, oligo| {
+                b.iter(|| black_box(calculator.calculate_tm_nearest_neighbor(black_box(oligo))))
+            },
+        );
+    }
+
+    for length in [20, 40] {
+        // Stem-loop (stem of length / 4, 4 nt loop) plus a random tail, so the
+        // search has a hairpin to score
+        let stem = random_sequence(length / 4, 11);
+        let tail = random_sequence(length - 2 * stem.len() - 4, 13);
+        let oligo = format!("{}TTTT{}{}", stem, reverse_complement(&stem), tail);
+        group.bench_with_input(
+            BenchmarkId::new("enhanced_hairpin", oligo.len()),
+            &oligo,
+            |b, oligo| {
+                b.iter(|| black_box(calculator.calculate_enhanced_hairpin(black_box(oligo))))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_design_primers, bench_thermodynamics);
+criterion_main!(benches);
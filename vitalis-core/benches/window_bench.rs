@@ -0,0 +1,96 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use vitalis_core::domain::{Sequence, SequenceRepository, Topology};
+use vitalis_core::infrastructure::FileSequenceRepository;
+
+/// Counts heap allocations so the benches can report allocations per scroll
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const SEQUENCE_LENGTH: usize = 1_000_000;
+const VIEWPORT: usize = 2_000;
+const STEP: usize = 500;
+
+fn viewer_repository() -> (FileSequenceRepository, String) {
+    let mut repository = FileSequenceRepository::new();
+    let seq_id = repository
+        .store_sequence(Sequence {
+            id: "chr_test".to_string(),
+            name: String::new(),
+            sequence: "ATCG".repeat(SEQUENCE_LENGTH / 4),
+            topology: Topology::Linear,
+        })
+        .unwrap();
+    (repository, seq_id)
+}
+
+/// Scroll the viewport across the whole sequence, returning the bases seen
+fn scroll<T: AsRef<str>>(mut window: impl FnMut(usize, usize) -> T) -> usize {
+    (0..SEQUENCE_LENGTH - VIEWPORT)
+        .step_by(STEP)
+        .map(|start| window(start, start + VIEWPORT).as_ref().len())
+        .sum()
+}
+
+/// Allocations (count, bytes) made by one full scroll
+fn allocations_per_scroll<T: AsRef<str>>(window: impl FnMut(usize, usize) -> T) -> (usize, usize) {
+    let (count, bytes) = (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    );
+    black_box(scroll(window));
+    (
+        ALLOCATIONS.load(Ordering::Relaxed) - count,
+        ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    )
+}
+
+fn bench_viewer_scrolling(c: &mut Criterion) {
+    let (repository, seq_id) = viewer_repository();
+    let copied = |start, end| repository.get_window(&seq_id, start, end).unwrap();
+    let shared = |start, end| repository.get_window_slice(&seq_id, start, end).unwrap();
+
+    // Target: shared windows make no per-window copy of the bases
+    let scrolls = (SEQUENCE_LENGTH - VIEWPORT).div_ceil(STEP);
+    for (name, (count, bytes)) in [
+        ("copied (get_window)", allocations_per_scroll(copied)),
+        ("shared (get_window_slice)", allocations_per_scroll(shared)),
+    ] {
+        println!(
+            "viewer_scrolling/{}: {} allocations, {} bytes over {} windows",
+            name, count, bytes, scrolls
+        );
+    }
+
+    let mut group = c.benchmark_group("viewer_scrolling");
+    group.measurement_time(Duration::from_secs(10));
+    group.bench_function("copied_2kb_windows", |b| {
+        b.iter(|| black_box(scroll(copied)))
+    });
+    group.bench_function("shared_2kb_windows", |b| {
+        b.iter(|| black_box(scroll(shared)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_viewer_scrolling);
+criterion_main!(benches);
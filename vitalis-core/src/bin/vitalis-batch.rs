@@ -0,0 +1,45 @@
+// Headless batch runner: vitalis-batch <job.yaml|job.json>
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: vitalis-batch <job.yaml|job.json>");
+        return ExitCode::from(2);
+    };
+
+    match vitalis_core::run_batch_job(path) {
+        Ok(report) => {
+            for task in &report.tasks {
+                let status = if task.skipped {
+                    "skipped"
+                } else if task.success {
+                    "ok"
+                } else {
+                    "failed"
+                };
+                let target = task
+                    .path
+                    .as_ref()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_default();
+                eprintln!(
+                    "{:<8} {:<18} {} ({} ms)",
+                    status, task.task, target, task.duration_ms
+                );
+                if let Some(error) = &task.error {
+                    eprintln!("         {}", error);
+                }
+            }
+            eprintln!("{} succeeded, {} failed", report.succeeded, report.failed);
+            if report.is_success() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 多重アラインメントの1行（ギャップは '-' または '.'）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentRow {
+    pub name: String,
+    pub aligned: String,
+}
+
+impl AlignmentRow {
+    pub fn ungapped(&self) -> String {
+        self.aligned.chars().filter(|&c| !is_gap(c)).collect()
+    }
+}
+
+pub fn is_gap(c: char) -> bool {
+    c == '-' || c == '.'
+}
+
+/// 取り込んだ多重アラインメント（全行が同じ列数）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultipleAlignment {
+    pub id: String,
+    pub name: String,
+    pub rows: Vec<AlignmentRow>,
+}
+
+impl MultipleAlignment {
+    pub fn columns(&self) -> usize {
+        self.rows
+            .first()
+            .map(|row| row.aligned.chars().count())
+            .unwrap_or(0)
+    }
+
+    pub fn row_index(&self, name: &str) -> Option<usize> {
+        self.rows.iter().position(|row| row.name == name)
+    }
+
+    pub fn summary(&self) -> AlignmentSummary {
+        AlignmentSummary {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            row_names: self.rows.iter().map(|row| row.name.clone()).collect(),
+            columns: self.columns(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentSummary {
+    pub id: String,
+    pub name: String,
+    pub row_names: Vec<String>,
+    pub columns: usize,
+}
+
+/// 保存分スコアの計算方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConservationMethod {
+    /// 1 − 列のシャノンエントロピー / log2(5)（A・C・G・T・ギャップの5記号）
+    #[default]
+    Entropy,
+    /// 基準行と同じ塩基を持つ他の行の割合
+    Identity,
+}
+
+/// 取り込んだアラインメントのストア
+#[derive(Debug, Default)]
+pub struct AlignmentStore {
+    alignments: HashMap<String, MultipleAlignment>,
+    next_id: usize,
+}
+
+impl AlignmentStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// アラインメントを追加して採番したIDを返す
+    pub fn add(&mut self, name: &str, rows: Vec<AlignmentRow>) -> String {
+        self.next_id += 1;
+        let id = format!("aln_{}", self.next_id);
+        self.alignments.insert(
+            id.clone(),
+            MultipleAlignment {
+                id: id.clone(),
+                name: name.to_string(),
+                rows,
+            },
+        );
+        id
+    }
+
+    pub fn get(&self, alignment_id: &str) -> Option<&MultipleAlignment> {
+        self.alignments.get(alignment_id)
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// CRC-64/GO-ISO（多項式 x^64 + x^4 + x^3 + x + 1 の反転表現）
+const CRC64_POLY: u64 = 0xD800_0000_0000_0000;
+
+const CRC64_TABLE: [u64; 256] = crc64_table();
+
+const fn crc64_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                CRC64_POLY ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// 配列のチェックサム（大文字にした塩基の CRC-64 を16桁の16進数で）。
+/// 解析後に配列が編集されたかどうかの判定に使う
+pub fn sequence_checksum(sequence: &str) -> String {
+    let crc = !sequence.bytes().fold(!0u64, |crc, byte| {
+        CRC64_TABLE[((crc ^ byte.to_ascii_uppercase() as u64) & 0xFF) as usize] ^ (crc >> 8)
+    });
+    format!("{:016X}", crc)
+}
+
+/// 解析結果の共通の入れ物。どの配列をどの条件・どのバージョンで解析したかを結果と一緒に持ち、
+/// フロントエンド・セッション保存・レポートが結果の型によらず扱えるようにする
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisResult<T> {
+    /// 結果の種類（"rbs_prediction" など）
+    pub result_type: String,
+    pub seq_id: String,
+    /// 解析した配列のチェックサム（`sequence_checksum`）
+    pub sequence_checksum: String,
+    /// 解析の条件（既定値で補ったもの）
+    pub parameters: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    /// 解析した vitalis-core のバージョン
+    pub engine_version: String,
+    pub result: T,
+}
+
+impl<T> AnalysisResult<T> {
+    pub fn new<P: Serialize>(
+        result_type: &str,
+        seq_id: &str,
+        sequence: &str,
+        parameters: &P,
+        result: T,
+    ) -> Self {
+        Self {
+            result_type: result_type.to_string(),
+            seq_id: seq_id.to_string(),
+            sequence_checksum: sequence_checksum(sequence),
+            parameters: serde_json::to_value(parameters).unwrap_or_default(),
+            created_at: Utc::now(),
+            engine_version: env!("CARGO_PKG_VERSION").to_string(),
+            result,
+        }
+    }
+
+    /// 解析した時点から配列が変わっていないか
+    pub fn is_current(&self, sequence: &str) -> bool {
+        self.sequence_checksum == sequence_checksum(sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_tracks_sequence_changes() {
+        // CRC-64/GO-ISO のチェック値
+        assert_eq!(sequence_checksum("123456789"), "B90956C775A41001");
+        assert_eq!(sequence_checksum("acgtn"), sequence_checksum("ACGTN"));
+
+        let result = AnalysisResult::new(
+            "gc_content",
+            "seq_1",
+            "ACGTACGT",
+            &serde_json::json!({ "window": 4 }),
+            50.0,
+        );
+        assert_eq!(result.parameters["window"], 4);
+        assert_eq!(result.engine_version, env!("CARGO_PKG_VERSION"));
+        assert!(result.is_current("acgtacgt"));
+        assert!(!result.is_current("ACGTACGA"));
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// MIQE チェックリストの1項目（value が None の項目は実験者が記入する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MiqeItem {
+    /// チェックリストの区分（"Target information" など）
+    pub section: String,
+    pub item: String,
+    pub value: Option<String>,
+}
+
+/// qPCR アッセイの記録文書（HTML として書き出した内容）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssayDocument {
+    pub pair_id: String,
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    pub items: Vec<MiqeItem>,
+    /// 設計結果から埋められず記入が必要な項目の数
+    pub missing_items: usize,
+    pub warnings: Vec<String>,
+}
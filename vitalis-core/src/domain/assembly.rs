@@ -0,0 +1,88 @@
+use super::primer::Primer;
+use super::thermodynamic_calculator::ThermodynamicProvenance;
+use serde::{Deserialize, Serialize};
+
+/// Gibson / In-Fusion アセンブリ用プライマー設計の条件（オーバーラップ長は別途指定する）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssemblyPrimerParams {
+    /// 最後の断片を最初の断片につないで環状にする（ベクター断片を含むアセンブリ）
+    pub circular: bool,
+    /// アニーリング部分の目標 Tm（°C）。この Tm に達する最短の長さを使う
+    pub anneal_tm: f32,
+    /// アニーリング部分の長さ範囲
+    pub anneal_length_min: usize,
+    pub anneal_length_max: usize,
+    /// オーバーラップ Tm の最高と最低の差の上限（°C）
+    pub max_overlap_tm_spread: f32,
+    /// オーバーラップ Tm の下限（°C、反応温度 50 °C 付近で対合が保てる目安）
+    pub min_overlap_tm: f32,
+    /// 許容するヘアピン ΔG の下限（kcal/mol、これより安定なオーバーラップは警告）
+    pub max_hairpin: f32,
+}
+
+impl Default for AssemblyPrimerParams {
+    fn default() -> Self {
+        Self {
+            circular: true,
+            anneal_tm: 60.0,
+            anneal_length_min: 18,
+            anneal_length_max: 30,
+            max_overlap_tm_spread: 5.0,
+            min_overlap_tm: 48.0,
+            max_hairpin: -3.0,
+        }
+    }
+}
+
+/// 1断片を増幅するプライマー（配列は 5' テールを含む）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssemblyFragment {
+    pub seq_id: String,
+    pub name: String,
+    pub forward: Primer,
+    pub reverse: Primer,
+    /// 各プライマーの 5' 側に付けた隣の断片とのホモロジー配列（末端の断片では空）
+    pub forward_tail: String,
+    pub reverse_tail: String,
+    /// アニーリング部分の長さ
+    pub forward_anneal_length: usize,
+    pub reverse_anneal_length: usize,
+    /// テールを含む増幅産物長
+    pub amplicon_length: usize,
+}
+
+/// 隣り合う断片のオーバーラップ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssemblyJunction {
+    /// 上流・下流の断片（fragments のインデックス）
+    pub left: usize,
+    pub right: usize,
+    /// アセンブリ産物上のオーバーラップ開始位置（環状で原点をまたぐ場合は末尾側）
+    pub position: usize,
+    pub overlap: String,
+    pub tm: f32,
+    /// オーバーラップ内で最も安定なヘアピンの ΔG（kcal/mol、構造がなければ 0）
+    pub hairpin_delta_g: f32,
+    pub hairpin_prone: bool,
+}
+
+/// アセンブリ用プライマーの設計結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssemblyDesign {
+    /// 連結順の断片
+    pub fragments: Vec<AssemblyFragment>,
+    /// 環状では断片数、直鎖では断片数 − 1 個
+    pub junctions: Vec<AssemblyJunction>,
+    /// 組み上がる配列（断片をこの順に連結したもの）
+    pub assembled_sequence: String,
+    pub circular: bool,
+    pub overlap_length: usize,
+    /// オーバーラップ Tm の最高と最低の差（°C）
+    pub overlap_tm_spread: f32,
+    /// 差が max_overlap_tm_spread 以内で、すべて min_overlap_tm 以上か
+    pub tm_uniform: bool,
+    /// オーバーラップの Tm・ΔG の計算に用いたパラメータセットと条件
+    pub provenance: Option<ThermodynamicProvenance>,
+    pub warnings: Vec<String>,
+}
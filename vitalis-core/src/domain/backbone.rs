@@ -0,0 +1,212 @@
+use super::Strand;
+use serde::{Deserialize, Serialize};
+
+/// ベクター骨格を構成する要素（シグネチャ配列の完全一致で検出）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackboneElement {
+    pub name: String,
+    pub feature_type: String,
+    /// 要素に固有の部分配列（5'→3'）
+    pub signature: String,
+}
+
+impl BackboneElement {
+    pub fn new(name: &str, feature_type: &str, signature: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            feature_type: feature_type.to_string(),
+            signature: signature.to_string(),
+        }
+    }
+}
+
+/// 既知のベクター骨格（構成要素の組み合わせで定義）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VectorBackbone {
+    pub name: String,
+    pub elements: Vec<BackboneElement>,
+    /// ユーザー登録の骨格か
+    #[serde(default)]
+    pub user_defined: bool,
+}
+
+/// 配列上で検出された骨格要素（座標は0-based半開区間、環状配列では end が長さを超え得る）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ElementHit {
+    pub name: String,
+    pub feature_type: String,
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+}
+
+/// 骨格の検出結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackboneMatch {
+    pub backbone: String,
+    pub user_defined: bool,
+    /// 検出できた要素の割合（0.0〜1.0）
+    pub score: f64,
+    pub found: Vec<ElementHit>,
+    pub missing: Vec<String>,
+    /// 挿入断片と推定される区間（検出要素間の最大の空白領域）。
+    /// 環状配列で原点をまたぐ場合は insert_start > insert_end
+    pub insert_start: Option<usize>,
+    pub insert_end: Option<usize>,
+}
+
+/// 一般的なベクター要素のライブラリ
+pub fn common_elements() -> Vec<BackboneElement> {
+    vec![
+        BackboneElement::new("AmpR", "CDS", "ATGAGTATTCAACATTTCCGTGTCGCCCTTATTCCC"),
+        BackboneElement::new("KanR", "CDS", "ATGAGCCATATTCAACGGGAAACGTCTTGCTC"),
+        BackboneElement::new(
+            "NeoR/KanR",
+            "CDS",
+            "ATGATTGAACAAGATGGATTGCACGCAGGTTCTCCGGCCGC",
+        ),
+        BackboneElement::new(
+            "CmR",
+            "CDS",
+            "ATGGAGAAAAAAATCACTGGATATACCACCGTTGATATATCCCAATGG",
+        ),
+        BackboneElement::new(
+            "ori",
+            "rep_origin",
+            "TTGAGATCCTTTTTTTCTGCGCGTAATCTGCTGCTTGCAAACAAAAAAACCACCGCTACCAGCGGTGG",
+        ),
+        BackboneElement::new(
+            "f1 ori",
+            "rep_origin",
+            "ACGCGCCCTGTAGCGGCGCATTAAGCGCGGCGGGTGTGGTGGTTACGCGCAGCGTGACCGCTACACTTGCCAGCGCCC",
+        ),
+        BackboneElement::new("T7 promoter", "promoter", "TAATACGACTCACTATAGG"),
+        BackboneElement::new("T3 promoter", "promoter", "AATTAACCCTCACTAAAGG"),
+        BackboneElement::new("SP6 promoter", "promoter", "ATTTAGGTGACACTATAG"),
+        BackboneElement::new("lac operator", "protein_bind", "TTGTGAGCGGATAACAA"),
+        BackboneElement::new(
+            "T7 terminator",
+            "terminator",
+            "CTAGCATAACCCCTTGGGGCCTCTAAACGGGTCTTGAGGGGTTTTTTG",
+        ),
+        BackboneElement::new(
+            "CMV promoter",
+            "promoter",
+            "CGCAAATGGGCGGTAGGCGTGTACGGTGGGAGGTCTATATAAGCAGAGCT",
+        ),
+        BackboneElement::new(
+            "SV40 promoter",
+            "promoter",
+            "CTGTGGAATGTGTGTCAGTTAGGGTGTGGAAAGTCCCCAGGCTCCCCAGCAGGCAGAAGTATG",
+        ),
+        BackboneElement::new(
+            "bGH poly(A) signal",
+            "polyA_signal",
+            "CTGTGCCTTCTAGTTGCCAGCCATCTGTTGTTTGCCCCTCCCCCGTGCCTTCCTTGACCCTGGAAGGTGCC",
+        ),
+        BackboneElement::new("M13 fwd", "primer_bind", "GTAAAACGACGGCCAGT"),
+        BackboneElement::new("M13 rev", "primer_bind", "CAGGAAACAGCTATGAC"),
+    ]
+}
+
+/// 組み込みのベクター骨格（要素名は `common_elements` を参照）
+pub fn builtin_backbones() -> Vec<VectorBackbone> {
+    let library = common_elements();
+    let backbone = |name: &str, element_names: &[&str]| VectorBackbone {
+        name: name.to_string(),
+        elements: element_names
+            .iter()
+            .filter_map(|element| library.iter().find(|e| e.name == *element).cloned())
+            .collect(),
+        user_defined: false,
+    };
+
+    vec![
+        backbone(
+            "pUC19",
+            &["AmpR", "ori", "lac operator", "M13 fwd", "M13 rev"],
+        ),
+        backbone(
+            "pBluescript II",
+            &[
+                "AmpR",
+                "ori",
+                "f1 ori",
+                "lac operator",
+                "T7 promoter",
+                "T3 promoter",
+                "M13 fwd",
+                "M13 rev",
+            ],
+        ),
+        backbone(
+            "pGEM-T",
+            &[
+                "AmpR",
+                "ori",
+                "f1 ori",
+                "lac operator",
+                "T7 promoter",
+                "SP6 promoter",
+            ],
+        ),
+        backbone(
+            "pET (KanR)",
+            &[
+                "KanR",
+                "ori",
+                "f1 ori",
+                "T7 promoter",
+                "lac operator",
+                "T7 terminator",
+            ],
+        ),
+        backbone(
+            "pET (AmpR)",
+            &[
+                "AmpR",
+                "ori",
+                "f1 ori",
+                "T7 promoter",
+                "lac operator",
+                "T7 terminator",
+            ],
+        ),
+        backbone(
+            "pcDNA3.1",
+            &[
+                "CMV promoter",
+                "T7 promoter",
+                "bGH poly(A) signal",
+                "f1 ori",
+                "SV40 promoter",
+                "NeoR/KanR",
+                "ori",
+                "AmpR",
+            ],
+        ),
+    ]
+}
+
+/// ユーザー登録のベクター骨格
+#[derive(Debug, Default)]
+pub struct BackboneLibrary {
+    backbones: Vec<VectorBackbone>,
+}
+
+impl BackboneLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 骨格を登録（同名の登録済み骨格は置き換える）
+    pub fn register(&mut self, mut backbone: VectorBackbone) {
+        backbone.user_defined = true;
+        self.backbones.retain(|b| b.name != backbone.name);
+        self.backbones.push(backbone);
+    }
+
+    pub fn get_all(&self) -> &[VectorBackbone] {
+        &self.backbones
+    }
+}
@@ -0,0 +1,155 @@
+use super::primer::PrimerDesignParams;
+use super::project::ProjectFormat;
+use super::region::Region;
+use super::reproducibility::SessionProvenance;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// GUI なしで実行するバッチジョブ（YAML または JSON のジョブファイル）。
+/// 相対パスはジョブファイルのあるディレクトリを基準にする
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchJob {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 再現性モードのシード（指定すると同じジョブから同じ結果を得られる）
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// 出力先のディレクトリ（省略時はジョブファイルのディレクトリ）
+    #[serde(default)]
+    pub output_dir: Option<PathBuf>,
+    /// 実行レポートの出力先（省略時は出力ディレクトリの batch_report.json）
+    #[serde(default)]
+    pub report: Option<PathBuf>,
+    /// 失敗したステップがあっても後続のステップを実行する
+    #[serde(default)]
+    pub continue_on_error: bool,
+    pub inputs: Vec<BatchInput>,
+    #[serde(default)]
+    pub steps: Vec<BatchStep>,
+}
+
+/// 取り込むファイル。`id` でステップから参照する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchInput {
+    pub id: String,
+    pub path: PathBuf,
+    /// "fasta" または "fastq"（省略時は拡張子から判定）
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// 1つの解析。`input` は `BatchInput::id`、`output` は出力ファイル（JSON または配列テキスト）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "analysis", rename_all = "snake_case")]
+pub enum BatchStep {
+    Stats {
+        input: String,
+        output: PathBuf,
+    },
+    DesignPrimers {
+        input: String,
+        regions: Vec<Region>,
+        #[serde(default)]
+        params: Option<PrimerDesignParams>,
+        output: PathBuf,
+    },
+    FindOrfs {
+        input: String,
+        #[serde(default = "BatchStep::default_min_orf_length")]
+        min_length: usize,
+        #[serde(default)]
+        genetic_code: Option<u8>,
+        output: PathBuf,
+    },
+    RestrictionSites {
+        input: String,
+        enzymes: Vec<String>,
+        output: PathBuf,
+    },
+    Export {
+        input: String,
+        format: String,
+        output: PathBuf,
+    },
+    /// 取り込んだ配列と設計したプライマーをプロジェクトファイルに保存
+    SaveProject {
+        output: PathBuf,
+        #[serde(default)]
+        format: Option<ProjectFormat>,
+    },
+}
+
+impl BatchStep {
+    fn default_min_orf_length() -> usize {
+        100
+    }
+
+    /// レポートに記録する解析名
+    pub fn analysis(&self) -> &'static str {
+        match self {
+            BatchStep::Stats { .. } => "stats",
+            BatchStep::DesignPrimers { .. } => "design_primers",
+            BatchStep::FindOrfs { .. } => "find_orfs",
+            BatchStep::RestrictionSites { .. } => "restriction_sites",
+            BatchStep::Export { .. } => "export",
+            BatchStep::SaveProject { .. } => "save_project",
+        }
+    }
+
+    pub fn input(&self) -> Option<&str> {
+        match self {
+            BatchStep::Stats { input, .. }
+            | BatchStep::DesignPrimers { input, .. }
+            | BatchStep::FindOrfs { input, .. }
+            | BatchStep::RestrictionSites { input, .. }
+            | BatchStep::Export { input, .. } => Some(input),
+            BatchStep::SaveProject { .. } => None,
+        }
+    }
+
+    pub fn output(&self) -> &PathBuf {
+        match self {
+            BatchStep::Stats { output, .. }
+            | BatchStep::DesignPrimers { output, .. }
+            | BatchStep::FindOrfs { output, .. }
+            | BatchStep::RestrictionSites { output, .. }
+            | BatchStep::Export { output, .. }
+            | BatchStep::SaveProject { output, .. } => output,
+        }
+    }
+}
+
+/// 取り込みまたはステップ1件の実行結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTaskReport {
+    /// "import" または解析名
+    pub task: String,
+    pub input: Option<String>,
+    /// 書き出したファイル（取り込みでは取り込んだファイル）
+    pub path: Option<PathBuf>,
+    pub success: bool,
+    pub error: Option<String>,
+    /// 失敗したステップの後で実行しなかった
+    #[serde(default)]
+    pub skipped: bool,
+    pub duration_ms: u64,
+}
+
+/// バッチジョブの実行レポート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchReport {
+    pub name: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub tasks: Vec<BatchTaskReport>,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub provenance: SessionProvenance,
+}
+
+impl BatchReport {
+    pub fn is_success(&self) -> bool {
+        self.failed == 0 && self.tasks.iter().all(|task| !task.skipped)
+    }
+}
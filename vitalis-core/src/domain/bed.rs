@@ -0,0 +1,54 @@
+use super::Strand;
+use serde::{Deserialize, Serialize};
+
+/// BED の1行（座標は BED と同じ0-based半開区間）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BedRecord {
+    pub chrom: String,
+    pub start: usize,
+    pub end: usize,
+    /// 列4〜6（BED3 では None）
+    pub name: Option<String>,
+    pub score: Option<f64>,
+    pub strand: Option<Strand>,
+}
+
+/// BED として書き出す区間の種類
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BedTrack {
+    /// 登録済みのアノテーション（feature_types が空ならすべて、分割された区間は全体を1行にする）
+    Features {
+        #[serde(default)]
+        feature_types: Vec<String>,
+    },
+    /// 6フレームの ORF（min_length アミノ酸以上、genetic_code は NCBI の表番号）
+    Orfs {
+        min_length: usize,
+        #[serde(default)]
+        genetic_code: Option<u8>,
+    },
+    /// GC% が threshold 以上のウィンドウ（重なる・接するウィンドウは1区間にまとめる）
+    GcWindows {
+        window_size: usize,
+        step: usize,
+        threshold: f64,
+    },
+    /// 配列に保存した設計済みプライマーの結合部位
+    PrimerSites,
+}
+
+impl BedTrack {
+    /// track 行に書く名前
+    pub fn name(&self) -> String {
+        match self {
+            Self::Features { feature_types } if !feature_types.is_empty() => {
+                feature_types.join(",")
+            }
+            Self::Features { .. } => "features".to_string(),
+            Self::Orfs { min_length, .. } => format!("ORFs >= {} aa", min_length),
+            Self::GcWindows { threshold, .. } => format!("GC >= {}%", threshold),
+            Self::PrimerSites => "primer sites".to_string(),
+        }
+    }
+}
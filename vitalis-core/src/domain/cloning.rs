@@ -0,0 +1,50 @@
+use super::feature::SequenceFeature;
+use super::restriction::OverhangType;
+use super::Strand;
+use serde::{Deserialize, Serialize};
+
+/// 挿入断片とベクターの連結部
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LigationJunction {
+    /// コンストラクト上の連結位置（上鎖）
+    pub position: usize,
+    /// 連結部の上流側の末端を作った酵素
+    pub left_enzyme: String,
+    /// 連結部の下流側の末端を作った酵素
+    pub right_enzyme: String,
+    pub overhang_type: OverhangType,
+    /// 対合した突出配列（コンストラクトの上鎖側、平滑末端では空）
+    pub overhang: String,
+    /// 連結後もどちらかの酵素の認識配列が残るか（BamHI/BglII のような組み合わせでは消える）
+    pub site_regenerated: bool,
+}
+
+/// 制限酵素消化とライゲーションで作ったコンストラクト（座標は0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CloningProduct {
+    /// 保存後のコンストラクト配列ID（保存前は空）
+    pub seq_id: String,
+    /// 環状のコンストラクト配列
+    pub sequence: String,
+    pub enzymes: Vec<String>,
+    /// 除いたベクター上の区間 [start, end)（原点をまたぐ場合は start > end、1酵素では空区間）
+    pub vector_start: usize,
+    pub vector_end: usize,
+    /// 使った挿入断片の上鎖の区間（挿入配列の座標、原点をまたぐ場合は start > end）
+    pub fragment_start: usize,
+    pub fragment_end: usize,
+    /// 挿入配列に対する断片の向き
+    pub insert_orientation: Strand,
+    /// コンストラクト上の挿入断片の区間
+    pub insert_start: usize,
+    pub insert_end: usize,
+    /// 末端の組み合わせで挿入方向が1通りに決まるか
+    pub directional: bool,
+    /// ベクターの両末端と連結できる挿入断片の候補数（最長のものを使う）
+    pub candidate_fragments: usize,
+    /// 上流側（ベクター | 挿入断片）、下流側（挿入断片 | ベクター）の順
+    pub junctions: Vec<LigationJunction>,
+    /// ベクターと挿入断片から移したアノテーションと挿入断片の領域
+    pub features: Vec<SequenceFeature>,
+    pub warnings: Vec<String>,
+}
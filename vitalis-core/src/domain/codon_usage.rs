@@ -0,0 +1,204 @@
+use super::genetic_code::GeneticCode;
+use serde::{Deserialize, Serialize};
+
+/// 表の並び（NCBI translation table と同じ TCAG 順）
+const BASE_ORDER: [u8; 4] = [b'T', b'C', b'A', b'G'];
+
+/// 大腸菌 K-12 の1000コドンあたりの使用頻度（Kazusa Codon Usage Database）
+const ESCHERICHIA_COLI: [f64; 64] = [
+    19.7, 15.0, 15.2, 11.9, 5.7, 5.5, 7.8, 8.0, // TT*, TC*
+    16.8, 14.6, 1.8, 0.2, 5.9, 8.0, 1.0, 10.7, // TA*, TG*
+    11.9, 10.5, 5.3, 46.9, 8.4, 6.4, 6.6, 26.7, // CT*, CC*
+    15.8, 13.1, 12.1, 27.7, 21.1, 26.0, 4.3, 4.1, // CA*, CG*
+    30.5, 18.2, 3.7, 24.8, 8.0, 22.8, 6.4, 11.5, // AT*, AC*
+    21.9, 24.4, 33.6, 9.8, 7.2, 16.6, 1.4, 1.6, // AA*, AG*
+    16.8, 11.7, 11.5, 26.4, 10.7, 31.6, 21.1, 38.5, // GT*, GC*
+    37.9, 20.5, 43.7, 18.4, 21.3, 33.4, 9.2, 8.6, // GA*, GG*
+];
+
+/// 出芽酵母の1000コドンあたりの使用頻度（Kazusa Codon Usage Database）
+const SACCHAROMYCES_CEREVISIAE: [f64; 64] = [
+    26.1, 18.4, 26.2, 27.2, 23.5, 14.2, 18.7, 8.6, // TT*, TC*
+    18.8, 14.8, 1.1, 0.5, 8.1, 4.8, 0.7, 10.4, // TA*, TG*
+    12.3, 5.4, 13.4, 10.5, 13.5, 6.8, 18.3, 5.3, // CT*, CC*
+    13.6, 7.8, 27.3, 12.1, 6.4, 2.6, 3.0, 1.7, // CA*, CG*
+    30.1, 17.2, 17.8, 20.9, 20.3, 12.7, 17.8, 8.0, // AT*, AC*
+    35.7, 24.8, 41.9, 30.8, 14.2, 9.8, 21.3, 9.2, // AA*, AG*
+    22.1, 11.8, 11.8, 10.8, 21.2, 12.6, 16.2, 6.2, // GT*, GC*
+    37.6, 20.2, 45.6, 19.2, 23.9, 9.8, 10.9, 6.0, // GA*, GG*
+];
+
+/// ヒトの1000コドンあたりの使用頻度（Kazusa Codon Usage Database）
+const HOMO_SAPIENS: [f64; 64] = [
+    17.6, 20.3, 7.7, 12.9, 15.2, 17.7, 12.2, 4.4, // TT*, TC*
+    12.2, 15.3, 1.0, 0.8, 10.6, 12.6, 1.6, 13.2, // TA*, TG*
+    13.2, 19.6, 7.2, 39.6, 17.5, 19.8, 16.9, 6.9, // CT*, CC*
+    10.9, 15.1, 12.3, 34.2, 4.5, 10.4, 6.2, 11.4, // CA*, CG*
+    16.0, 20.8, 7.5, 22.0, 13.1, 18.9, 15.1, 6.1, // AT*, AC*
+    17.0, 19.1, 24.4, 31.9, 12.1, 19.5, 12.2, 12.0, // AA*, AG*
+    11.0, 14.5, 7.1, 28.1, 18.4, 27.7, 15.8, 7.4, // GT*, GC*
+    21.8, 25.1, 29.0, 39.6, 10.8, 22.2, 16.5, 16.5, // GA*, GG*
+];
+
+/// 稀なコドンを判定する基準の宿主
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CodonHost {
+    #[default]
+    EscherichiaColi,
+    SaccharomycesCerevisiae,
+    HomoSapiens,
+}
+
+impl CodonHost {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodonHost::EscherichiaColi => "Escherichia coli K-12",
+            CodonHost::SaccharomycesCerevisiae => "Saccharomyces cerevisiae",
+            CodonHost::HomoSapiens => "Homo sapiens",
+        }
+    }
+
+    fn table(&self) -> &'static [f64; 64] {
+        match self {
+            CodonHost::EscherichiaColi => &ESCHERICHIA_COLI,
+            CodonHost::SaccharomycesCerevisiae => &SACCHAROMYCES_CEREVISIAE,
+            CodonHost::HomoSapiens => &HOMO_SAPIENS,
+        }
+    }
+
+    /// 宿主での1000コドンあたりの使用頻度（ACGT 以外を含むコドンは None）
+    pub fn per_thousand(&self, codon: &[u8]) -> Option<f64> {
+        codon_index(codon).map(|i| self.table()[i])
+    }
+
+    /// 相対適応度: 同じアミノ酸（終止を含む）を指定する同義コドンのうち
+    /// 宿主で最も使われるものに対する使用頻度の比（0〜1）
+    pub fn relative_adaptiveness(&self, codon: &[u8], code: &GeneticCode) -> Option<f64> {
+        let frequency = self.per_thousand(codon)?;
+        let amino_acid = code.translate_codon(codon);
+        let best = (0..64)
+            .filter(|&i| code.translate_codon(&index_codon(i)) == amino_acid)
+            .map(|i| self.table()[i])
+            .fold(0.0, f64::max);
+        Some(if best > 0.0 { frequency / best } else { 0.0 })
+    }
+}
+
+/// 64 コドンを表の並び（TCAG 順）で返す
+pub fn all_codons() -> impl Iterator<Item = [u8; 3]> {
+    (0..64).map(index_codon)
+}
+
+fn codon_index(codon: &[u8]) -> Option<usize> {
+    if codon.len() != 3 {
+        return None;
+    }
+    codon.iter().try_fold(0, |acc, &base| {
+        let base = match base.to_ascii_uppercase() {
+            b'U' => b'T',
+            other => other,
+        };
+        BASE_ORDER
+            .iter()
+            .position(|&b| b == base)
+            .map(|i| acc * 4 + i)
+    })
+}
+
+fn index_codon(index: usize) -> [u8; 3] {
+    [
+        BASE_ORDER[index / 16],
+        BASE_ORDER[index / 4 % 4],
+        BASE_ORDER[index % 4],
+    ]
+}
+
+/// コドン使用統計の条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodonUsageParams {
+    /// NCBI 遺伝暗号ID
+    pub genetic_code: u8,
+    /// 稀なコドンを判定する宿主
+    pub host: CodonHost,
+    /// 宿主での相対適応度がこれ未満のコドンを稀とみなす（0〜1）
+    pub rare_threshold: f64,
+}
+
+impl Default for CodonUsageParams {
+    fn default() -> Self {
+        Self {
+            genetic_code: 1,
+            host: CodonHost::default(),
+            rare_threshold: 0.2,
+        }
+    }
+}
+
+/// 配列に現れた、宿主で稀なコドン
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RareCodon {
+    pub codon: String,
+    pub amino_acid: char,
+    /// 配列中の出現数
+    pub count: usize,
+    /// 宿主での1000コドンあたりの使用頻度
+    pub host_per_thousand: f64,
+    /// 宿主での相対適応度
+    pub relative_adaptiveness: f64,
+}
+
+/// 1コドン分の、配列と宿主の使用頻度の比較
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodonAdaptiveness {
+    pub codon: String,
+    pub amino_acid: char,
+    /// 配列中の出現数
+    pub count: usize,
+    /// 配列での1000コドンあたりの使用頻度
+    pub per_thousand: f64,
+    /// 宿主での1000コドンあたりの使用頻度
+    pub host_per_thousand: f64,
+    /// 宿主での相対適応度
+    pub relative_adaptiveness: f64,
+}
+
+/// 遺伝子のコドン使用と宿主のコドン使用表の比較
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodonUsageComparison {
+    pub host: CodonHost,
+    pub host_name: String,
+    /// Codon Adaptation Index（数えられるコドンがなければ None）
+    pub cai: Option<f64>,
+    /// CAI の計算に使ったコドン数（終止コドンと同義コドンのないアミノ酸を除く）
+    pub cai_codons: usize,
+    /// 64 コドンすべて（TCAG 順）
+    pub codons: Vec<CodonAdaptiveness>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_adaptiveness_follows_host() {
+        let code = GeneticCode::standard();
+        let ecoli = CodonHost::EscherichiaColi;
+        let yeast = CodonHost::SaccharomycesCerevisiae;
+
+        // AGG は大腸菌では稀だが、酵母ではアルギニンの主要コドンに近い
+        assert!(ecoli.relative_adaptiveness(b"AGG", &code).unwrap() < 0.1);
+        assert!(yeast.relative_adaptiveness(b"AGG", &code).unwrap() > 0.4);
+        // 最も使われる同義コドンは 1、単一コドンのアミノ酸も 1
+        assert_eq!(ecoli.relative_adaptiveness(b"CTG", &code), Some(1.0));
+        assert_eq!(ecoli.relative_adaptiveness(b"ATG", &code), Some(1.0));
+        assert_eq!(ecoli.relative_adaptiveness(b"ANG", &code), None);
+
+        for host in [ecoli, yeast, CodonHost::HomoSapiens] {
+            let total: f64 = (0..64)
+                .map(|i| host.per_thousand(&index_codon(i)).unwrap())
+                .sum();
+            assert!((total - 1000.0).abs() < 5.0, "{}: {}", host.name(), total);
+        }
+    }
+}
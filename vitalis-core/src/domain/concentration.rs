@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// 濃度換算の対象分子
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoleculeKind {
+    /// 二本鎖 DNA（配列とその相補鎖）
+    DsDna,
+    /// 一本鎖 DNA（オリゴなど）
+    SsDna,
+    /// タンパク質（核酸配列の場合は +1 フレームで最初の終止コドンまで翻訳する）
+    Protein,
+}
+
+/// 濃度の単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConcentrationUnit {
+    NgPerUl,
+    Nanomolar,
+    CopiesPerUl,
+}
+
+/// 同じ濃度を各単位で表したもの
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcentrationConversion {
+    pub molecule: MoleculeKind,
+    /// 塩基対数・塩基数・残基数
+    pub length: usize,
+    /// 分子量（g/mol）
+    pub molecular_weight: f64,
+    pub ng_per_ul: f64,
+    pub nanomolar: f64,
+    pub copies_per_ul: f64,
+}
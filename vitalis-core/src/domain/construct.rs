@@ -0,0 +1,104 @@
+use super::feature::SequenceFeature;
+use super::primer::Primer;
+use super::rbs::RbsPrediction;
+use super::restriction::RestrictionSite;
+use serde::{Deserialize, Serialize};
+
+/// 融合タグの付加位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagTerminus {
+    N,
+    C,
+}
+
+/// 発現コンストラクトに付加する融合タグ
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConstructTag {
+    /// 組み込みタグ名（大文字小文字は区別しない）、または `protein` を指定した任意の名前
+    pub name: String,
+    pub terminus: TagTerminus,
+    /// タグのアミノ酸配列（省略時は組み込みタグから解決）
+    #[serde(default)]
+    pub protein: Option<String>,
+    /// タグと目的タンパク質の間のリンカー（アミノ酸配列、None でリンカーなし）
+    #[serde(default = "default_linker")]
+    pub linker: Option<String>,
+}
+
+fn default_linker() -> Option<String> {
+    Some(DEFAULT_LINKER.to_string())
+}
+
+/// 既定の柔軟性リンカー
+pub const DEFAULT_LINKER: &str = "GGGGS";
+
+/// 組み込みの融合タグ（名前, アミノ酸配列）
+pub fn builtin_tags() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("His6", "HHHHHH"),
+        ("FLAG", "DYKDDDDK"),
+        ("HA", "YPYDVPDYA"),
+        ("Myc", "EQKLISEEDL"),
+        ("V5", "GKPIPNPLLGLDST"),
+        ("Strep-II", "WSHPQFEK"),
+    ]
+}
+
+/// 大腸菌での使用頻度順の同義コドン（先頭が最適コドン）
+pub fn ecoli_codons(amino_acid: char) -> &'static [&'static str] {
+    match amino_acid.to_ascii_uppercase() {
+        'A' => &["GCG", "GCC", "GCA", "GCT"],
+        'R' => &["CGT", "CGC", "CGG", "CGA"],
+        'N' => &["AAC", "AAT"],
+        'D' => &["GAT", "GAC"],
+        'C' => &["TGC", "TGT"],
+        'Q' => &["CAG", "CAA"],
+        'E' => &["GAA", "GAG"],
+        'G' => &["GGC", "GGT", "GGG", "GGA"],
+        'H' => &["CAT", "CAC"],
+        'I' => &["ATT", "ATC", "ATA"],
+        'L' => &["CTG", "TTA", "TTG", "CTT", "CTC"],
+        'K' => &["AAA", "AAG"],
+        'M' => &["ATG"],
+        'F' => &["TTT", "TTC"],
+        'P' => &["CCG", "CCA", "CCT", "CCC"],
+        'S' => &["AGC", "TCT", "TCC", "TCG", "AGT", "TCA"],
+        'T' => &["ACC", "ACG", "ACT", "ACA"],
+        'W' => &["TGG"],
+        'Y' => &["TAT", "TAC"],
+        'V' => &["GTG", "GTT", "GTC", "GTA"],
+        '*' => &["TAA", "TGA", "TAG"],
+        _ => &[],
+    }
+}
+
+/// 設計された発現コンストラクト（座標はコンストラクト上の0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpressionConstruct {
+    /// 保存後のコンストラクト配列ID（保存前は空）
+    pub seq_id: String,
+    pub sequence: String,
+    /// 融合タンパク質のORF（開始コドンから終止コドンまで）
+    pub orf_start: usize,
+    pub orf_end: usize,
+    /// 融合タンパク質の翻訳産物（終止コドンは含まない）
+    pub protein: String,
+    /// ORF全体が読み枠を保ったまま翻訳され、内部に終止コドンがないか
+    pub frame_verified: bool,
+    /// 挿入断片を増幅するクローニングプライマー（制限酵素サイト付き）
+    pub forward_primer: Primer,
+    pub reverse_primer: Primer,
+    /// クローニングに使うサイト以外に残ったクローニング酵素のサイト
+    pub unwanted_sites: Vec<RestrictionSite>,
+    /// コドン置換で除去したサイト数
+    pub sites_removed: usize,
+    /// 置き換えたベクター上の区間 [start, end)
+    pub vector_start: usize,
+    pub vector_end: usize,
+    /// ベクター由来と新規に付加したアノテーション
+    pub features: Vec<SequenceFeature>,
+    /// 開始コドン上流の RBS の推定強度
+    #[serde(default)]
+    pub rbs: Option<RbsPrediction>,
+    pub warnings: Vec<String>,
+}
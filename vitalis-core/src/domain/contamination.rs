@@ -0,0 +1,124 @@
+use super::backbone::common_elements;
+use super::Strand;
+use serde::{Deserialize, Serialize};
+
+/// 混入配列の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContaminantKind {
+    /// シーケンスアダプター
+    Adapter,
+    /// クローニングベクターの骨格要素・マルチクローニングサイト
+    Vector,
+}
+
+/// 混入検出に使うシグネチャ配列（5'→3'）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContaminantSignature {
+    pub name: String,
+    pub kind: ContaminantKind,
+    pub sequence: String,
+}
+
+impl ContaminantSignature {
+    pub fn new(name: &str, kind: ContaminantKind, sequence: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            sequence: sequence.to_string(),
+        }
+    }
+}
+
+/// 一致の強さ（UniVec / VecScreen の基準。配列端の一致は短くても強く扱う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MatchStrength {
+    Weak,
+    Moderate,
+    Strong,
+}
+
+impl MatchStrength {
+    /// スコア（一致 +1、不一致 -5）と配列端からの距離で分類する
+    pub fn classify(score: i32, terminal: bool) -> Option<Self> {
+        let (strong, moderate, weak) = if terminal { (24, 19, 16) } else { (30, 25, 23) };
+        if score >= strong {
+            Some(MatchStrength::Strong)
+        } else if score >= moderate {
+            Some(MatchStrength::Moderate)
+        } else if score >= weak {
+            Some(MatchStrength::Weak)
+        } else {
+            None
+        }
+    }
+}
+
+/// シグネチャと一致した区間（座標は0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContaminationHit {
+    pub signature: String,
+    pub kind: ContaminantKind,
+    pub start: usize,
+    pub end: usize,
+    /// シグネチャが一致した鎖
+    pub strand: Strand,
+    pub score: i32,
+    /// 一致区間の塩基一致率（%）
+    pub identity: f64,
+    /// 配列端から 25 bp 以内にかかる一致か
+    pub terminal: bool,
+    pub strength: MatchStrength,
+}
+
+/// ベクター混入スクリーニングの結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContaminationReport {
+    pub seq_id: String,
+    pub length: usize,
+    /// 位置順
+    pub hits: Vec<ContaminationHit>,
+    /// いずれかの一致に含まれる塩基数
+    pub contaminated_bases: usize,
+    /// 最も強い一致（なければ None）
+    pub strongest: Option<MatchStrength>,
+}
+
+/// 組み込みの混入シグネチャ（よく使われるベクター要素、MCS、シーケンスアダプター）
+pub fn univec_signatures() -> Vec<ContaminantSignature> {
+    let mut signatures: Vec<ContaminantSignature> = common_elements()
+        .into_iter()
+        .map(|element| ContaminantSignature {
+            name: element.name,
+            kind: ContaminantKind::Vector,
+            sequence: element.signature,
+        })
+        .collect();
+    signatures.extend([
+        ContaminantSignature::new(
+            "pUC19 MCS",
+            ContaminantKind::Vector,
+            "GAATTCGAGCTCGGTACCCGGGGATCCTCTAGAGTCGACCTGCAGGCATGCAAGCTT",
+        ),
+        ContaminantSignature::new(
+            "Illumina TruSeq adapter (read 1)",
+            ContaminantKind::Adapter,
+            "AGATCGGAAGAGCACACGTCTGAACTCCAGTCAC",
+        ),
+        ContaminantSignature::new(
+            "Illumina TruSeq adapter (read 2)",
+            ContaminantKind::Adapter,
+            "AGATCGGAAGAGCGTCGTGTAGGGAAAGAGTGT",
+        ),
+        ContaminantSignature::new(
+            "Illumina Nextera transposase adapter",
+            ContaminantKind::Adapter,
+            "CTGTCTCTTATACACATCTCCGAGCCCACGAGAC",
+        ),
+        ContaminantSignature::new(
+            "Illumina small RNA 3' adapter",
+            ContaminantKind::Adapter,
+            "TGGAATTCTCGGGTGCCAAGGAACTCCAGTCAC",
+        ),
+    ]);
+    signatures
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// 位置の表記体系
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CoordinateSystem {
+    /// 内部表現（0-based）
+    ZeroBased,
+    /// 表示用（1-based）
+    OneBased,
+    /// CDS相対のHGVS c. 表記（c.1 が開始コドンのA、c.-N は上流、c.*N は終止コドン下流、
+    /// c.N+K / c.N-K はイントロン内）
+    Cds { feature_id: String },
+    /// タンパク質相対のHGVS p. 表記（コドン番号）
+    Protein { feature_id: String },
+}
+
+/// 変換結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConvertedCoordinate {
+    /// 内部座標（0-based）
+    pub position: usize,
+    /// 変換先の表記
+    pub display: String,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// 書式付きコピーのスタイル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CopyStyle {
+    /// ブロック（空白区切り）の塩基数（0 でブロック分けしない）
+    pub block_size: usize,
+    /// 1行の塩基数（0 で改行しない）
+    pub line_width: usize,
+    /// 行頭に1-basedの位置番号を付けるか
+    pub numbering: bool,
+    /// 相補鎖の行を付けるか
+    pub complement: bool,
+    /// 範囲先頭を読み枠とした翻訳の行を付けるか（アミノ酸はコドン中央の塩基の下に表示）
+    pub translation: bool,
+    /// 翻訳に用いるNCBI遺伝暗号ID
+    pub genetic_code: u8,
+}
+
+impl Default for CopyStyle {
+    fn default() -> Self {
+        Self {
+            block_size: 10,
+            line_width: 60,
+            numbering: true,
+            complement: false,
+            translation: false,
+            genetic_code: 1,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use super::thermodynamic_calculator::ThermodynamicProvenance;
+use super::Strand;
+use serde::{Deserialize, Serialize};
+
+/// ヌクレアーゼごとの PAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CasPam {
+    /// SpCas9（3' 側 NGG、スペーサー 20 nt）
+    Ngg,
+    /// SaCas9（3' 側 NNGRRT、スペーサー 21 nt）
+    Nngrrt,
+    /// Cas12a/Cpf1（5' 側 TTTV、スペーサー 23 nt）
+    Tttv,
+}
+
+impl CasPam {
+    /// PAM の IUPAC 表記（ガイドと同じ鎖の 5'→3'）
+    pub fn motif(&self) -> &'static str {
+        match self {
+            CasPam::Ngg => "NGG",
+            CasPam::Nngrrt => "NNGRRT",
+            CasPam::Tttv => "TTTV",
+        }
+    }
+
+    pub fn spacer_length(&self) -> usize {
+        match self {
+            CasPam::Ngg => 20,
+            CasPam::Nngrrt => 21,
+            CasPam::Tttv => 23,
+        }
+    }
+
+    /// PAM がプロトスペーサーの 5' 側にあるか（Cas12a）
+    pub fn is_five_prime(&self) -> bool {
+        matches!(self, CasPam::Tttv)
+    }
+
+    /// PAM 側の端から切断位置までの塩基数（Cas9 は PAM の3塩基手前、
+    /// Cas12a は PAM の鎖で PAM から 18 塩基目の後ろ）
+    pub fn cut_offset(&self) -> usize {
+        match self {
+            CasPam::Ngg | CasPam::Nngrrt => 3,
+            CasPam::Tttv => 18,
+        }
+    }
+}
+
+/// ガイド設計の条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuideDesignParams {
+    /// スペーサーの GC 含量の範囲（%）
+    pub gc_min: f32,
+    pub gc_max: f32,
+    /// U6 プロモーターの転写終結シグナル（TTTT）を含むスペーサーを除外する
+    pub exclude_poly_t: bool,
+    /// スペーサーのヘアピン ΔG がこれより小さい（安定な）場合に警告する（kcal/mol）
+    pub max_hairpin: f32,
+    /// オフターゲット候補として報告するミスマッチ数の上限
+    pub max_mismatches: usize,
+    /// 返すガイドの数
+    pub max_guides: usize,
+}
+
+impl Default for GuideDesignParams {
+    fn default() -> Self {
+        Self {
+            gc_min: 30.0,
+            gc_max: 80.0,
+            exclude_poly_t: true,
+            max_hairpin: -5.0,
+            max_mismatches: 3,
+            max_guides: 20,
+        }
+    }
+}
+
+/// オフターゲット候補（座標はプロトスペーサーの0-based半開区間）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OffTarget {
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    /// ガイドと同じ向きのプロトスペーサーと PAM
+    pub protospacer: String,
+    pub pam: String,
+    pub mismatches: usize,
+    /// ミスマッチの位置（スペーサーの 5' 末端からの 1-based）
+    pub mismatch_positions: Vec<usize>,
+    /// 切断されやすさの推定（0〜1、PAM に近いミスマッチほど低い）
+    pub score: f32,
+}
+
+/// ガイド RNA の候補（座標はプロトスペーサーの0-based半開区間、PAM は含まない）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuideRna {
+    pub spacer: String,
+    pub pam: String,
+    pub strand: Strand,
+    pub start: usize,
+    pub end: usize,
+    /// 二本鎖切断の位置（この塩基の手前で切れる）
+    pub cut_site: usize,
+    pub gc_content: f32,
+    /// スペーサーの DNA としての Tm（°C）とヘアピン ΔG（kcal/mol）
+    pub tm: f32,
+    pub hairpin_delta_g: f32,
+    /// オンターゲット効率の推定（0〜1）
+    pub on_target_score: f32,
+    /// 読み込んだ配列内での特異性（0〜100、オフターゲット候補がなければ 100）
+    pub specificity_score: f32,
+    pub off_targets: Vec<OffTarget>,
+    pub warnings: Vec<String>,
+}
+
+/// ガイド設計結果（標的領域は [target_start, target_end)）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuideDesignResult {
+    pub guides: Vec<GuideRna>,
+    pub pam: CasPam,
+    pub target_start: usize,
+    pub target_end: usize,
+    /// 領域内の PAM 部位の数（フィルタ前）
+    pub candidates_considered: usize,
+    pub params: GuideDesignParams,
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+}
@@ -0,0 +1,98 @@
+use super::warning::Warning;
+use serde::{Deserialize, Serialize};
+
+/// 乾燥オリゴを目的濃度で溶解するための液量
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OligoResuspension {
+    /// 合成量（nmol）
+    pub nmoles: f64,
+    /// 目的濃度（µM）
+    pub concentration_um: f64,
+    /// 加える TE または水の量（µL）
+    pub volume_ul: f64,
+}
+
+/// 希釈の1段階（前段の液 `transfer_ul` に希釈液 `diluent_ul` を加える）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DilutionStep {
+    /// 持ち込む液の濃度（ストックまたは前段の濃度）
+    pub source_concentration: f64,
+    pub transfer_ul: f64,
+    pub diluent_ul: f64,
+    pub final_volume_ul: f64,
+    /// 丸めた液量で実際に得られる濃度
+    pub concentration: f64,
+    /// この段階の希釈倍率
+    pub fold: f64,
+}
+
+/// ストックから目的濃度までの希釈手順。1段階で持ち込む量が少なすぎる場合は
+/// 10 倍・100 倍の中間希釈を挟む（濃度の単位はストックと目的濃度で共通）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DilutionSeries {
+    pub stock: f64,
+    pub target: f64,
+    pub final_volume_ul: f64,
+    pub steps: Vec<DilutionStep>,
+}
+
+/// qPCR 標準曲線の条件（ストック濃度は別途指定する）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StandardCurveParams {
+    /// 最も濃い標準の1反応あたりコピー数
+    pub top_copies_per_reaction: f64,
+    /// 標準の点数
+    pub points: usize,
+    /// 隣り合う標準の希釈倍率
+    pub fold: f64,
+    /// 1反応に加える鋳型の量（µL）
+    pub template_volume_ul: f64,
+    /// 次の標準へ持ち出した後に各チューブに残る量（µL）
+    pub standard_volume_ul: f64,
+    /// 標準1点あたりの反復数（NTC も同数置く）
+    pub replicates: usize,
+}
+
+impl Default for StandardCurveParams {
+    fn default() -> Self {
+        Self {
+            top_copies_per_reaction: 1e7,
+            points: 7,
+            fold: 10.0,
+            template_volume_ul: 2.0,
+            standard_volume_ul: 90.0,
+            replicates: 3,
+        }
+    }
+}
+
+/// 標準曲線の1点（前の標準 `transfer_ul` を希釈液 `diluent_ul` に加える）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardPoint {
+    /// "S1" から濃い順
+    pub label: String,
+    pub copies_per_ul: f64,
+    pub copies_per_reaction: f64,
+    pub transfer_ul: f64,
+    pub diluent_ul: f64,
+}
+
+/// 増幅産物のストックから作る標準曲線の希釈計画
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardCurvePlan {
+    /// 増幅産物の長さ（bp）と dsDNA としての分子量（g/mol）
+    pub amplicon_length: usize,
+    pub molecular_weight: f64,
+    pub stock_ng_per_ul: f64,
+    pub stock_copies_per_ul: f64,
+    /// ストックから最も濃い標準までの希釈（単位は copies/µL）
+    pub preparation: DilutionSeries,
+    /// 段階希釈で作る標準（S1 は `preparation` の最終段）
+    pub standards: Vec<StandardPoint>,
+    pub template_volume_ul: f64,
+    pub replicates: usize,
+    /// 標準と NTC を合わせたウェル数
+    pub wells: usize,
+    pub warnings: Vec<Warning>,
+}
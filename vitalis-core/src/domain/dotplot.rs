@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+/// k-mer の種の最短長（これより短いと Mb 級の配列で偶然の種が多すぎる）
+pub const MIN_SEED_LENGTH: usize = 10;
+/// k-mer の種の最長（2ビット符号で u64 に収まる長さ）
+pub const MAX_SEED_LENGTH: usize = 32;
+/// ウィンドウ長の上限
+pub const MAX_WINDOW: usize = 1000;
+/// 返す一致の上限
+pub const MAX_HITS: usize = 100_000;
+/// 1本の鎖で調べる種の上限
+pub const MAX_SEEDS: usize = 20_000_000;
+/// 1つの k-mer から作る種（A と B の出現数の積）の上限。超える k-mer は低複雑度の繰り返しとして使わない
+pub const MAX_SEEDS_PER_KMER: usize = 10_000;
+
+/// `window` 塩基中 `threshold` 塩基以上一致するウィンドウが必ず含む、
+/// 完全一致の種の長さ（鳩の巣原理）
+pub fn seed_length(window: usize, threshold: usize) -> usize {
+    (window / (window + 1 - threshold.min(window))).min(MAX_SEED_LENGTH)
+}
+
+/// 種が MIN_SEED_LENGTH 以上になる最小の閾値
+pub fn min_threshold(window: usize) -> usize {
+    (1..=window)
+        .find(|&threshold| seed_length(window, threshold) >= MIN_SEED_LENGTH)
+        .unwrap_or(window)
+}
+
+/// ドットプロットの一致。同じ対角線上で続けて一致したウィンドウを1本の線分にまとめる。
+/// 配列 A の [x, x + length) と配列 B の [y, y + length) が対応し、
+/// 逆相補の一致では A の左端が B の右端に対応する（(x, y + length) から (x + length, y) への線分）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DotPlotHit {
+    pub x: usize,
+    pub y: usize,
+    pub length: usize,
+    pub reverse_complement: bool,
+}
+
+/// ドットプロットの一致
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DotPlotMatches {
+    /// A の位置順
+    pub hits: Vec<DotPlotHit>,
+    /// 繰り返しが多すぎて種にしなかった k-mer の数
+    pub masked_kmers: usize,
+    /// MAX_HITS・MAX_SEEDS で打ち切った
+    pub truncated: bool,
+}
+
+/// 2配列（同じ配列どうしなら自己相同性）のドットプロット
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DotPlot {
+    pub seq_id_a: String,
+    pub seq_id_b: String,
+    pub length_a: usize,
+    pub length_b: usize,
+    pub window: usize,
+    /// ウィンドウ内で一致しなければならない塩基数
+    pub threshold: usize,
+    #[serde(flatten)]
+    pub matches: DotPlotMatches,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_length_guarantees_a_shared_kmer() {
+        // 完全一致なら種はウィンドウそのもの（u64 に収まる範囲で）
+        assert_eq!(seed_length(20, 20), 20);
+        assert_eq!(seed_length(100, 100), MAX_SEED_LENGTH);
+        // 20 塩基中 2 か所違えば、残る一致区間のどれかは 6 塩基以上
+        assert_eq!(seed_length(20, 18), 6);
+        assert_eq!(min_threshold(20), 19);
+        assert_eq!(min_threshold(50), 46);
+    }
+}
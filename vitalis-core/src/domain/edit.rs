@@ -0,0 +1,102 @@
+use super::feature::SequenceFeature;
+use super::Topology;
+use serde::{Deserialize, Serialize};
+
+/// 保存済み配列への編集操作（座標は0-based半開区間）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SequenceEdit {
+    /// `position` の塩基の前に挿入（配列長なら末尾に追加）
+    Insert {
+        position: usize,
+        bases: String,
+    },
+    /// [start, end) を削除
+    Delete {
+        start: usize,
+        end: usize,
+    },
+    /// [start, end) を `bases` に置き換える
+    Replace {
+        start: usize,
+        end: usize,
+        bases: String,
+    },
+    SetTopology {
+        topology: Topology,
+    },
+}
+
+impl SequenceEdit {
+    /// 塩基を書き換える区間と、そこに入る塩基（トポロジー変更は None）
+    pub fn span(&self) -> Option<(usize, usize, &str)> {
+        match self {
+            SequenceEdit::Insert { position, bases } => Some((*position, *position, bases)),
+            SequenceEdit::Delete { start, end } => Some((*start, *end, "")),
+            SequenceEdit::Replace { start, end, bases } => Some((*start, *end, bases)),
+            SequenceEdit::SetTopology { .. } => None,
+        }
+    }
+
+    /// 編集前の境界位置を編集後の位置に写す。書き換えた区間の内側は新しい塩基の範囲に収め、
+    /// 挿入位置ちょうどで始まる区間は後ろへずらし、そこで終わる区間は伸ばさない
+    pub fn map_boundary(&self, position: usize, is_start: bool) -> usize {
+        let Some((start, end, bases)) = self.span() else {
+            return position;
+        };
+        let shifted = || position + bases.len() - (end - start);
+        if is_start {
+            if position >= end {
+                shifted()
+            } else if position <= start {
+                position
+            } else {
+                position.min(start + bases.len())
+            }
+        } else if position <= start {
+            position
+        } else if position >= end {
+            shifted()
+        } else {
+            position.min(start + bases.len())
+        }
+    }
+
+    /// 編集後のアノテーション。区間が消えたものは None
+    pub fn map_feature(&self, feature: &SequenceFeature) -> Option<SequenceFeature> {
+        let mut mapped = feature.clone();
+        mapped.start = self.map_boundary(feature.start, true);
+        mapped.end = self.map_boundary(feature.end, false);
+        if mapped.start >= mapped.end {
+            return None;
+        }
+        for segment in &mut mapped.segments {
+            segment.start = self.map_boundary(segment.start, true);
+            segment.end = self.map_boundary(segment.end, false);
+        }
+        mapped
+            .segments
+            .retain(|segment| segment.start < segment.end);
+        Some(mapped)
+    }
+}
+
+/// 編集の対象（配列・トポロジー・アノテーション）
+#[derive(Debug, Clone)]
+pub struct EditState {
+    pub sequence: String,
+    pub topology: Topology,
+    pub features: Vec<SequenceFeature>,
+}
+
+/// 編集・取り消し・やり直しの結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditResult {
+    pub seq_id: String,
+    /// 適用した操作（取り消しでは元に戻すために適用した逆操作）
+    pub edit: SequenceEdit,
+    pub length: usize,
+    pub topology: Topology,
+    /// 取り消し・やり直しできる編集の数
+    pub undo_depth: usize,
+    pub redo_depth: usize,
+}
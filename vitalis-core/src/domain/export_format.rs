@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+/// 書き出す値の単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Unit {
+    /// 塩基対（整数）
+    BasePairs,
+    Celsius,
+    Percent,
+    KcalPerMol,
+    /// 単位なし（スコア・比率など）
+    Dimensionless,
+}
+
+impl Unit {
+    pub fn symbol(&self) -> Option<&'static str> {
+        match self {
+            Unit::BasePairs => Some("bp"),
+            Unit::Celsius => Some("°C"),
+            Unit::Percent => Some("%"),
+            Unit::KcalPerMol => Some("kcal/mol"),
+            Unit::Dimensionless => None,
+        }
+    }
+}
+
+/// CSV・レポートに書き出す数値の書式。システムのロケールによらず小数点は常に '.'、
+/// 桁区切りや指数表記は使わないので、他のツールでそのまま読み込める
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportFormat {
+    /// CSV の列区切り（小数点と衝突しないよう '.' は使えない）
+    pub delimiter: char,
+    /// ヘッダーに単位を付ける（例: "tm (°C)"）
+    pub units_in_headers: bool,
+    /// 単位ごとの小数桁数
+    pub temperature_precision: usize,
+    pub percent_precision: usize,
+    pub energy_precision: usize,
+    pub dimensionless_precision: usize,
+}
+
+impl Default for ExportFormat {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            units_in_headers: true,
+            temperature_precision: 1,
+            percent_precision: 1,
+            energy_precision: 2,
+            dimensionless_precision: 3,
+        }
+    }
+}
+
+impl ExportFormat {
+    pub fn precision(&self, unit: Unit) -> usize {
+        match unit {
+            Unit::BasePairs => 0,
+            Unit::Celsius => self.temperature_precision,
+            Unit::Percent => self.percent_precision,
+            Unit::KcalPerMol => self.energy_precision,
+            Unit::Dimensionless => self.dimensionless_precision,
+        }
+    }
+
+    /// 列見出し（units_in_headers なら "name (unit)"）
+    pub fn header(&self, name: &str, unit: Unit) -> String {
+        match unit.symbol() {
+            Some(symbol) if self.units_in_headers => format!("{} ({})", name, symbol),
+            _ => name.to_string(),
+        }
+    }
+
+    /// 単位の桁数で数値を書く。非有限値は空欄、-0 は 0 として書く
+    pub fn number(&self, value: f64, unit: Unit) -> String {
+        if !value.is_finite() {
+            return String::new();
+        }
+        let text = format!("{:.*}", self.precision(unit), value);
+        if text.starts_with('-') && text[1..].bytes().all(|b| b == b'0' || b == b'.') {
+            text[1..].to_string()
+        } else {
+            text
+        }
+    }
+
+    /// 本文用の値（単位記号付き。"62.5 °C"、"1200 bp"）
+    pub fn quantity(&self, value: f64, unit: Unit) -> String {
+        let number = self.number(value, unit);
+        match (unit, unit.symbol()) {
+            (Unit::Percent, _) => format!("{}%", number),
+            (_, Some(symbol)) => format!("{} {}", number, symbol),
+            (_, None) => number,
+        }
+    }
+
+    /// CSV の1行（区切り文字・引用符・改行を含む値は引用する）
+    pub fn csv_row<S: AsRef<str>>(&self, fields: &[S]) -> String {
+        let delimiter = self.delimiter.to_string();
+        fields
+            .iter()
+            .map(|field| {
+                let field = field.as_ref();
+                if field.contains(self.delimiter) || field.contains(['"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&delimiter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_and_headers() {
+        let format = ExportFormat::default();
+        assert_eq!(format.header("tm", Unit::Celsius), "tm (°C)");
+        assert_eq!(format.header("score", Unit::Dimensionless), "score");
+        assert_eq!(format.number(62.449, Unit::Celsius), "62.4");
+        assert_eq!(format.number(-0.004, Unit::KcalPerMol), "0.00");
+        assert_eq!(format.number(-1.5e-7, Unit::Dimensionless), "0.000");
+        assert_eq!(
+            format.number(1e21, Unit::BasePairs),
+            "1000000000000000000000"
+        );
+        assert_eq!(format.number(f64::NAN, Unit::Percent), "");
+        assert_eq!(format.quantity(1200.0, Unit::BasePairs), "1200 bp");
+        assert_eq!(format.quantity(52.26, Unit::Percent), "52.3%");
+
+        let semicolons = ExportFormat {
+            delimiter: ';',
+            units_in_headers: false,
+            ..ExportFormat::default()
+        };
+        assert_eq!(semicolons.header("tm", Unit::Celsius), "tm");
+        assert_eq!(
+            semicolons.csv_row(&["a;b", "c,d", "say \"hi\""]),
+            "\"a;b\";c,d;\"say \"\"hi\"\"\""
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use super::{Range, Strand};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 配列アノテーション（座標は0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SequenceFeature {
+    pub id: String,
+    pub feature_type: String,
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    /// join()で分割された区間（単一区間の場合は空）
+    #[serde(default)]
+    pub segments: Vec<Range>,
+    pub qualifiers: HashMap<String, String>,
+    /// 表示スタイル（未指定の場合はストア追加時に種類ごとの既定値を設定）
+    #[serde(default)]
+    pub display: Option<FeatureDisplay>,
+}
+
+/// ラベル表示の指定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LabelVisibility {
+    Always,
+    /// 描画領域に余裕がある場合のみ
+    WhenSpace,
+    Never,
+}
+
+/// アノテーションの表示スタイル（ビューア・プラスミドマップ・レポートで共通）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureDisplay {
+    /// 表示色（#RRGGBB）
+    pub color: String,
+    /// 表示段（0が最優先、値が大きいほど外側・後方に描画）
+    pub tier: u8,
+    pub label: LabelVisibility,
+}
+
+impl FeatureDisplay {
+    pub fn new(color: &str, tier: u8, label: LabelVisibility) -> Self {
+        Self {
+            color: color.to_string(),
+            tier,
+            label,
+        }
+    }
+
+    /// アノテーション種類ごとの既定スタイル
+    pub fn for_type(feature_type: &str) -> Self {
+        use LabelVisibility::*;
+        match feature_type {
+            "CDS" => Self::new("#F5B041", 0, Always),
+            "gene" => Self::new("#7FB3D5", 1, Always),
+            "mRNA" | "exon" => Self::new("#85C1E9", 1, WhenSpace),
+            "promoter" => Self::new("#58D68D", 1, Always),
+            "terminator" => Self::new("#EC7063", 1, Always),
+            "rep_origin" => Self::new("#AF7AC5", 1, Always),
+            "RBS" | "regulatory" => Self::new("#F7DC6F", 2, WhenSpace),
+            "polyA_signal" | "sig_peptide" => Self::new("#F0B27A", 2, WhenSpace),
+            "protein_bind" | "misc_binding" => Self::new("#76D7C4", 2, WhenSpace),
+            "primer_bind" => Self::new("#5DADE2", 2, WhenSpace),
+            "source" => Self::new("#E5E7E9", 4, Never),
+            _ => Self::new("#BDC3C7", 3, WhenSpace),
+        }
+    }
+}
+
+impl SequenceFeature {
+    pub fn new(feature_type: &str, start: usize, end: usize, strand: Strand) -> Self {
+        Self {
+            id: String::new(),
+            feature_type: feature_type.to_string(),
+            start,
+            end,
+            strand,
+            segments: Vec::new(),
+            qualifiers: HashMap::new(),
+            display: Some(FeatureDisplay::for_type(feature_type)),
+        }
+    }
+
+    /// 表示用のラベル（label > Name（GFF3） > gene > product > note > feature_type の順）
+    pub fn label(&self) -> String {
+        ["label", "Name", "gene", "product", "note"]
+            .iter()
+            .find_map(|key| self.qualifiers.get(*key))
+            .cloned()
+            .unwrap_or_else(|| self.feature_type.clone())
+    }
+
+    pub fn overlaps(&self, start: usize, end: usize) -> bool {
+        self.start < end && start < self.end
+    }
+
+    /// 座標を `offset` だけ移動（負方向への移動で0未満になる場合は呼び出し側で除外する）
+    pub fn shifted(&self, offset: isize) -> SequenceFeature {
+        let shift = |pos: usize| (pos as isize + offset) as usize;
+        let mut shifted = self.clone();
+        shifted.start = shift(self.start);
+        shifted.end = shift(self.end);
+        shifted.segments = self
+            .segments
+            .iter()
+            .map(|seg| Range::new(shift(seg.start), shift(seg.end)))
+            .collect();
+        shifted
+    }
+
+    /// 区間 [start, end) に切り詰め、start を原点とする座標に変換
+    /// 区間と重ならない場合は None
+    pub fn clip_and_shift(&self, start: usize, end: usize) -> Option<SequenceFeature> {
+        if !self.overlaps(start, end) {
+            return None;
+        }
+
+        let mut clipped = self.clone();
+        clipped.start = self.start.max(start) - start;
+        clipped.end = self.end.min(end) - start;
+        clipped.segments = self
+            .segments
+            .iter()
+            .filter(|seg| seg.start < end && start < seg.end)
+            .map(|seg| Range::new(seg.start.max(start) - start, seg.end.min(end) - start))
+            .collect();
+
+        if self.start < start || self.end > end {
+            clipped
+                .qualifiers
+                .insert("truncated".to_string(), "true".to_string());
+        }
+
+        Some(clipped)
+    }
+}
+
+/// 配列IDごとのアノテーションストア
+#[derive(Debug, Default)]
+pub struct FeatureStore {
+    features: HashMap<String, Vec<SequenceFeature>>,
+    next_id: usize,
+}
+
+impl FeatureStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// アノテーションを追加（IDが空なら採番する。保存済みの採番IDは以後の採番で重複させない）
+    pub fn add(&mut self, seq_id: &str, mut feature: SequenceFeature) -> String {
+        if feature.id.is_empty() {
+            self.next_id += 1;
+            feature.id = format!("feat_{}", self.next_id);
+        } else if let Some(n) = feature
+            .id
+            .strip_prefix("feat_")
+            .and_then(|n| n.parse::<usize>().ok())
+        {
+            self.next_id = self.next_id.max(n);
+        }
+        if feature.display.is_none() {
+            feature.display = Some(FeatureDisplay::for_type(&feature.feature_type));
+        }
+        let id = feature.id.clone();
+        let entries = self.features.entry(seq_id.to_string()).or_default();
+        entries.push(feature);
+        entries.sort_by_key(|f| (f.start, f.end));
+        id
+    }
+
+    pub fn add_all(&mut self, seq_id: &str, features: Vec<SequenceFeature>) {
+        for feature in features {
+            self.add(seq_id, feature);
+        }
+    }
+
+    pub fn get_all(&self, seq_id: &str) -> &[SequenceFeature] {
+        self.features
+            .get(seq_id)
+            .map(|features| features.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// 区間 [start, end) と重なるアノテーションを取得
+    pub fn get_overlapping(&self, seq_id: &str, start: usize, end: usize) -> Vec<SequenceFeature> {
+        self.get_all(seq_id)
+            .iter()
+            .filter(|f| f.overlaps(start, end))
+            .cloned()
+            .collect()
+    }
+
+    /// 表示スタイルを更新（該当するアノテーションがなければ false）
+    pub fn set_display(&mut self, seq_id: &str, feature_id: &str, display: FeatureDisplay) -> bool {
+        match self
+            .features
+            .get_mut(seq_id)
+            .and_then(|features| features.iter_mut().find(|f| f.id == feature_id))
+        {
+            Some(feature) => {
+                feature.display = Some(display);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove_sequence(&mut self, seq_id: &str) {
+        self.features.remove(seq_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_store_overlap_query() {
+        let mut store = FeatureStore::new();
+        store.add(
+            "seq_1",
+            SequenceFeature::new("gene", 10, 50, Strand::Forward),
+        );
+        store.add(
+            "seq_1",
+            SequenceFeature::new("CDS", 60, 90, Strand::Reverse),
+        );
+
+        assert_eq!(store.get_overlapping("seq_1", 40, 70).len(), 2);
+        assert_eq!(store.get_overlapping("seq_1", 50, 60).len(), 0);
+        assert!(store.get_all("seq_2").is_empty());
+    }
+
+    #[test]
+    fn test_default_display_by_type() {
+        let mut store = FeatureStore::new();
+        let mut feature = SequenceFeature::new("CDS", 0, 30, Strand::Forward);
+        feature.display = None;
+        let id = store.add("seq_1", feature);
+
+        let stored = &store.get_all("seq_1")[0];
+        assert_eq!(stored.display, Some(FeatureDisplay::for_type("CDS")));
+
+        let hidden = FeatureDisplay::new("#000000", 5, LabelVisibility::Never);
+        assert!(store.set_display("seq_1", &id, hidden.clone()));
+        assert_eq!(store.get_all("seq_1")[0].display, Some(hidden));
+        assert!(!store.set_display("seq_1", "feat_missing", FeatureDisplay::for_type("gene")));
+    }
+
+    #[test]
+    fn test_clip_and_shift() {
+        let mut feature = SequenceFeature::new("gene", 10, 50, Strand::Forward);
+        feature.segments = vec![Range::new(10, 20), Range::new(30, 50)];
+
+        let clipped = feature.clip_and_shift(15, 40).unwrap();
+        assert_eq!((clipped.start, clipped.end), (0, 25));
+        assert_eq!(clipped.segments, vec![Range::new(0, 5), Range::new(15, 25)]);
+        assert_eq!(
+            clipped.qualifiers.get("truncated"),
+            Some(&"true".to_string())
+        );
+
+        assert!(feature.clip_and_shift(50, 60).is_none());
+    }
+}
@@ -0,0 +1,46 @@
+use super::region::Region;
+use serde::{Deserialize, Serialize};
+
+/// 期待タンパク質に対する観察 CDS 上のインデルの向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndelKind {
+    /// 観察配列に余分な塩基がある
+    Insertion,
+    /// 観察配列から塩基が抜けている
+    Deletion,
+}
+
+/// 観察 CDS と期待タンパク質のアラインメントで見つかったインデル
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CdsIndel {
+    pub kind: IndelKind,
+    /// 観察 CDS 先頭からの 0-based 位置（挿入は挿入塩基の先頭、欠失は抜けた塩基が入る位置）
+    pub cds_position: usize,
+    /// 配列（順鎖）上の 0-based 半開区間。挿入は挿入塩基の区間、欠失は start == end
+    pub start: usize,
+    pub end: usize,
+    /// 挿入された塩基、または抜けたと推定される塩基（CDS の向き、推定できない塩基は 'N'）
+    pub bases: String,
+    /// 影響を受ける期待タンパク質の残基（0-based）
+    pub residue: usize,
+    /// 塩基数が3の倍数でなく読み枠がずれる
+    pub frameshift: bool,
+}
+
+/// 観察 CDS と期待タンパク質の比較結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FrameshiftReport {
+    pub region: Region,
+    /// 期待タンパク質の残基数（末尾の終止 '*' は除く）
+    pub expected_length: usize,
+    /// 期待どおりの残基をコードしていたコドン数
+    pub matched_residues: usize,
+    /// CDS 上の位置順
+    pub indels: Vec<CdsIndel>,
+    /// 読み枠がずれ始める最初のインデル
+    pub frameshift: Option<CdsIndel>,
+    /// 観察 CDS を先頭から読み枠どおりに翻訳したもの
+    pub observed_translation: String,
+    /// 期待タンパク質の終わりより手前に現れる最初の終止コドン（CDS 上の 0-based 位置）
+    pub premature_stop: Option<usize>,
+}
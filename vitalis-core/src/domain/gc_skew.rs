@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// ウィンドウごとの GC 含量と GC skew
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GcSkewWindow {
+    pub start: usize,
+    pub end: usize,
+    /// ウィンドウの塩基に占める G と C の割合（%）
+    #[serde(default)]
+    pub gc_percent: f64,
+    /// (G - C) / (G + C)
+    pub skew: f64,
+    /// 先頭からの skew の累積
+    pub cumulative: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GcSkewProfile {
+    pub windows: Vec<GcSkewWindow>,
+    /// 累積 skew が最小になるウィンドウの開始位置（細菌ゲノムの複製起点の候補）
+    pub origin: Option<usize>,
+    /// 累積 skew が最大になるウィンドウの開始位置（複製終結点の候補）
+    pub terminus: Option<usize>,
+}
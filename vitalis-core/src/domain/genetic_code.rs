@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+
+/// コドン表の塩基順序（NCBI形式: 第1〜第3塩基とも T, C, A, G の順）
+const BASE_ORDER: [u8; 4] = [b'T', b'C', b'A', b'G'];
+
+/// NCBI の遺伝暗号表（ID, 名称, アミノ酸, 開始コドン）。7, 8, 15, 17〜20, 32 は欠番
+const NCBI_TABLES: [(u8, &str, &str, &str); 25] = [
+    (
+        1,
+        "Standard",
+        "FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "---M------**--*----M---------------M----------------------------",
+    ),
+    (
+        2,
+        "Vertebrate Mitochondrial",
+        "FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+        "----------**--------------------MMMM----------**---M------------",
+    ),
+    (
+        3,
+        "Yeast Mitochondrial",
+        "FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "----------**----------------------MM---------------M------------",
+    ),
+    (
+        4,
+        "Mold, Protozoan, and Coelenterate Mitochondrial; Mycoplasma; Spiroplasma",
+        "FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "--MM------**-------M------------MMMM---------------M------------",
+    ),
+    (
+        5,
+        "Invertebrate Mitochondrial",
+        "FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+        "---M------**--------------------MMMM---------------M------------",
+    ),
+    (
+        6,
+        "Ciliate, Dasycladacean and Hexamita Nuclear",
+        "FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "--------------*--------------------M----------------------------",
+    ),
+    (
+        9,
+        "Echinoderm and Flatworm Mitochondrial",
+        "FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        "-----------------------------------M---------------M------------",
+    ),
+    (
+        10,
+        "Euplotid Nuclear",
+        "FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "-----------------------------------M----------------------------",
+    ),
+    (
+        11,
+        "Bacterial, Archaeal and Plant Plastid",
+        "FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "---M------**--*----M------------MMMM---------------M------------",
+    ),
+    (
+        12,
+        "Alternative Yeast Nuclear",
+        "FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "-------------------M---------------M----------------------------",
+    ),
+    (
+        13,
+        "Ascidian Mitochondrial",
+        "FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+        "---M------------------------------MM---------------M------------",
+    ),
+    (
+        14,
+        "Alternative Flatworm Mitochondrial",
+        "FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        "-----------------------------------M----------------------------",
+    ),
+    (
+        16,
+        "Chlorophycean Mitochondrial",
+        "FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "-----------------------------------M----------------------------",
+    ),
+    (
+        21,
+        "Trematode Mitochondrial",
+        "FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        "-----------------------------------M---------------M------------",
+    ),
+    (
+        22,
+        "Scenedesmus obliquus Mitochondrial",
+        "FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "-----------------------------------M----------------------------",
+    ),
+    (
+        23,
+        "Thraustochytrium Mitochondrial",
+        "FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "--------------------------------M--M---------------M------------",
+    ),
+    (
+        24,
+        "Rhabdopleuridae Mitochondrial",
+        "FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        "---M---------------M---------------M---------------M------------",
+    ),
+    (
+        25,
+        "Candidate Division SR1 and Gracilibacteria",
+        "FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "---M-------------------------------M---------------M------------",
+    ),
+    (
+        26,
+        "Pachysolen tannophilus Nuclear",
+        "FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "-------------------M---------------M----------------------------",
+    ),
+    (
+        27,
+        "Karyorelict Nuclear",
+        "FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "--------------*--------------------M----------------------------",
+    ),
+    (
+        28,
+        "Condylostoma Nuclear",
+        "FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "----------**--*--------------------M----------------------------",
+    ),
+    (
+        29,
+        "Mesodinium Nuclear",
+        "FFLLSSSSYYYYCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "--------------*--------------------M----------------------------",
+    ),
+    (
+        30,
+        "Peritrich Nuclear",
+        "FFLLSSSSYYEECC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "--------------*--------------------M----------------------------",
+    ),
+    (
+        31,
+        "Blastocrithidia Nuclear",
+        "FFLLSSSSYYEECCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        "----------**-----------------------M----------------------------",
+    ),
+    (
+        33,
+        "Cephalodiscidae Mitochondrial",
+        "FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        "---M-------*-------M---------------M---------------M------------",
+    ),
+];
+
+/// 遺伝暗号表（NCBI translation table 形式の64文字で保持）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeneticCode {
+    pub id: u8,
+    pub name: String,
+    /// 64コドンに対応するアミノ酸（'*' は終止）
+    amino_acids: String,
+    /// 開始コドンの位置を 'M' で示す64文字
+    starts: String,
+}
+
+impl GeneticCode {
+    fn new(id: u8, name: &str, amino_acids: &str, starts: &str) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            amino_acids: amino_acids.to_string(),
+            starts: starts.to_string(),
+        }
+    }
+
+    /// 標準遺伝暗号（NCBI code 1）
+    pub fn standard() -> Self {
+        let (id, name, amino_acids, starts) = NCBI_TABLES[0];
+        Self::new(id, name, amino_acids, starts)
+    }
+
+    /// NCBI遺伝暗号IDから取得（1〜33、欠番は None）
+    pub fn by_id(id: u8) -> Option<Self> {
+        NCBI_TABLES
+            .iter()
+            .find(|table| table.0 == id)
+            .map(|&(id, name, amino_acids, starts)| Self::new(id, name, amino_acids, starts))
+    }
+
+    fn index(codon: &[u8]) -> Option<usize> {
+        if codon.len() != 3 {
+            return None;
+        }
+        codon.iter().try_fold(0, |acc, &base| {
+            let base = match base.to_ascii_uppercase() {
+                b'U' => b'T',
+                other => other,
+            };
+            BASE_ORDER
+                .iter()
+                .position(|&b| b == base)
+                .map(|i| acc * 4 + i)
+        })
+    }
+
+    /// コドンを翻訳（曖昧塩基を含む場合は 'X'）
+    pub fn translate_codon(&self, codon: &[u8]) -> char {
+        Self::index(codon)
+            .map(|i| self.amino_acids.as_bytes()[i] as char)
+            .unwrap_or('X')
+    }
+
+    pub fn is_start(&self, codon: &[u8]) -> bool {
+        Self::index(codon).is_some_and(|i| self.starts.as_bytes()[i] == b'M')
+    }
+
+    pub fn is_stop(&self, codon: &[u8]) -> bool {
+        self.translate_codon(codon) == '*'
+    }
+
+    /// 先頭から3塩基ずつ翻訳（端数の塩基は無視）
+    pub fn translate(&self, sequence: &str) -> String {
+        sequence
+            .as_bytes()
+            .chunks_exact(3)
+            .map(|codon| self.translate_codon(codon))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_translation() {
+        let code = GeneticCode::standard();
+        assert_eq!(code.translate("ATGGCCTGGTAAGC"), "MAW*");
+        assert_eq!(code.translate_codon(b"ANG"), 'X');
+        assert!(code.is_start(b"ATG"));
+        assert!(code.is_stop(b"TGA"));
+
+        let mito = GeneticCode::by_id(2).unwrap();
+        assert_eq!(mito.translate("TGAAGAATA"), "W*M");
+        assert!(GeneticCode::by_id(11).unwrap().is_start(b"GTG"));
+        assert!(GeneticCode::by_id(7).is_none());
+
+        // 酵母ミトコンドリアでは CTN が Thr、繊毛虫では TAA/TAG が Gln
+        assert_eq!(GeneticCode::by_id(3).unwrap().translate("CTAATA"), "TM");
+        assert_eq!(GeneticCode::by_id(6).unwrap().translate("TAATGA"), "Q*");
+        assert_eq!(GeneticCode::by_id(25).unwrap().translate("TGA"), "G");
+        assert!(GeneticCode::by_id(33).unwrap().is_start(b"TTG"));
+    }
+}
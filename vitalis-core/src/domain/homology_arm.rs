@@ -0,0 +1,75 @@
+use super::feature::SequenceFeature;
+use super::primer::{AmpliconQc, AmpliconQcParams, PrimerDesignParams, PrimerPair};
+use serde::{Deserialize, Serialize};
+
+/// ホモロジーアーム設計のパラメータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HomologyArmParams {
+    /// 各アームの長さ（recombineering では 50 bp 程度、HDR ドナーでは 300〜1000 bp）
+    pub arm_length: usize,
+    /// アームのリピート・二次構造・GC のスクリーニング条件
+    pub qc: AmpliconQcParams,
+    /// 検証プライマーをアームの外側（または挿入配列の内側）に置く目安の距離
+    pub primer_offset: usize,
+    pub primer: PrimerDesignParams,
+}
+
+impl Default for HomologyArmParams {
+    fn default() -> Self {
+        Self {
+            arm_length: 500,
+            qc: AmpliconQcParams::default(),
+            primer_offset: 100,
+            primer: PrimerDesignParams::default(),
+        }
+    }
+}
+
+/// 鋳型上のホモロジーアーム（座標は鋳型上の0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomologyArm {
+    pub start: usize,
+    pub end: usize,
+    pub sequence: String,
+    pub qc: AmpliconQc,
+}
+
+/// 検証PCRで確認する連結部
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Junction {
+    /// 左アームの外側 → 挿入配列
+    Left,
+    /// 挿入配列 → 右アームの外側
+    Right,
+    /// 挿入配列がない欠失で、両アームの外側をまたぐ
+    Spanning,
+}
+
+/// 連結部ごとの検証プライマー（座標は編集後の遺伝子座上。見つからなければ None）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JunctionPrimers {
+    pub junction: Junction,
+    pub pair: Option<PrimerPair>,
+}
+
+/// 設計されたドナーと検証プライマー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomologyArmDesign {
+    /// 保存後のドナー配列ID（保存前は空）
+    pub donor_seq_id: String,
+    /// 左アーム + 挿入配列 + 右アーム
+    pub donor_sequence: String,
+    /// 置き換える鋳型上の区間 [start, end)（start == end なら挿入のみ）
+    pub site_start: usize,
+    pub site_end: usize,
+    pub left_arm: HomologyArm,
+    pub right_arm: HomologyArm,
+    /// ドナー上の挿入配列の区間
+    pub insert_start: usize,
+    pub insert_end: usize,
+    pub verification: Vec<JunctionPrimers>,
+    /// ドナー上のアーム・挿入配列のアノテーション
+    pub features: Vec<SequenceFeature>,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// 配列ごとの検索インデックスの構築状態
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum IndexStatus {
+    NotBuilt,
+    Building,
+    Ready {
+        kmer_size: usize,
+        /// ファイル由来の配列で記録した行の数（メモリ上の配列では 0）
+        indexed_lines: usize,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+/// k-mer の出現位置インデックス（ACGT のみからなる k-mer を登録、U は T とみなす）。
+/// 出現ごとに k-mer の符号と開始位置を1つの u64 に詰め、昇順に並べて持つ（1出現 8 バイト）
+#[derive(Debug, Clone)]
+pub struct KmerIndex {
+    k: usize,
+    /// (符号 << 32) | 開始位置
+    entries: Vec<u64>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum IndexError {
+    #[error("Sequence of {0} bases is too long to index (at most {max} bases)", max = KmerIndex::MAX_LENGTH)]
+    TooLong(u64),
+}
+
+impl KmerIndex {
+    /// 符号を上位 32 ビットに詰めるため k は 16 まで
+    pub const MAX_K: usize = 16;
+    /// 開始位置を u32 で持つため、これより長い配列は索引できない
+    pub const MAX_LENGTH: u64 = u32::MAX as u64 + 1;
+
+    pub fn build(sequence: &str, k: usize) -> Result<Self, IndexError> {
+        let mut builder = KmerIndexBuilder::new(k, sequence.len() as u64)?;
+        builder.push(sequence)?;
+        Ok(builder.finish())
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// `query` が一致しうる開始位置の候補（位置順）。最も出現の少ない k-mer の出現から求めるので、
+    /// 一致するかは呼び出し側で確かめる。クエリが k 未満、または ACGT 以外を含む場合は None
+    pub fn candidates(&self, query: &[u8]) -> Option<Vec<usize>> {
+        if query.len() < self.k {
+            return None;
+        }
+        let codes: Vec<u64> = query
+            .windows(self.k)
+            .map(encode_kmer)
+            .collect::<Option<_>>()?;
+
+        let (offset, seeds) = codes
+            .iter()
+            .enumerate()
+            .map(|(offset, &code)| (offset, self.occurrences(code)))
+            .min_by_key(|(_, seeds)| seeds.len())?;
+        Some(
+            seeds
+                .iter()
+                .filter_map(|&entry| ((entry & u64::from(u32::MAX)) as usize).checked_sub(offset))
+                .collect(),
+        )
+    }
+
+    /// `text`（インデックス構築元の配列）上で `query` が完全一致する開始位置。
+    /// クエリが k 未満、または ACGT 以外を含む場合は None（呼び出し側で全走査する）
+    pub fn find_exact(&self, query: &str, text: &[u8]) -> Option<Vec<usize>> {
+        let query = query.as_bytes();
+        Some(
+            self.candidates(query)?
+                .into_iter()
+                .filter(|&start| {
+                    text.get(start..start + query.len())
+                        .is_some_and(|window| same_bases(window, query))
+                })
+                .collect(),
+        )
+    }
+
+    fn occurrences(&self, code: u64) -> &[u64] {
+        let from = self.entries.partition_point(|&entry| entry >> 32 < code);
+        let to = self.entries.partition_point(|&entry| entry >> 32 <= code);
+        &self.entries[from..to]
+    }
+}
+
+/// 配列を先頭から順に受け取って `KmerIndex` を作る（配列全体を保持しない）
+pub struct KmerIndexBuilder {
+    k: usize,
+    mask: u64,
+    code: u64,
+    valid: usize,
+    position: u64,
+    entries: Vec<u64>,
+}
+
+impl KmerIndexBuilder {
+    /// `length` は配列長（出現の数だけ領域を確保する）。索引できない長さなら構築前にエラー
+    pub fn new(k: usize, length: u64) -> Result<Self, IndexError> {
+        if length > KmerIndex::MAX_LENGTH {
+            return Err(IndexError::TooLong(length));
+        }
+        let k = k.clamp(1, KmerIndex::MAX_K);
+        Ok(Self {
+            k,
+            mask: (1u64 << (2 * k)) - 1,
+            code: 0,
+            valid: 0,
+            position: 0,
+            entries: Vec::with_capacity(length as usize),
+        })
+    }
+
+    pub fn push(&mut self, chunk: &str) -> Result<(), IndexError> {
+        for base in chunk.bytes() {
+            self.position += 1;
+            if self.position > KmerIndex::MAX_LENGTH {
+                return Err(IndexError::TooLong(self.position));
+            }
+            match encode_base(base) {
+                Some(bits) => {
+                    self.code = ((self.code << 2) | bits) & self.mask;
+                    self.valid += 1;
+                }
+                None => self.valid = 0,
+            }
+            if self.valid >= self.k {
+                let start = self.position - self.k as u64;
+                self.entries.push((self.code << 32) | start);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> KmerIndex {
+        self.entries.sort_unstable();
+        self.entries.shrink_to_fit();
+        KmerIndex {
+            k: self.k,
+            entries: self.entries,
+        }
+    }
+}
+
+/// 同じ塩基か（大文字・小文字と U/T を区別しない）
+pub fn same_bases(window: &[u8], query: &[u8]) -> bool {
+    window
+        .iter()
+        .zip(query)
+        .all(|(&base, &q)| encode_base(base) == encode_base(q))
+}
+
+/// 塩基の2ビット符号（A=0, C=1, G=2, T/U=3）
+pub fn encode_base(base: u8) -> Option<u64> {
+    match base.to_ascii_uppercase() {
+        b'A' => Some(0),
+        b'C' => Some(1),
+        b'G' => Some(2),
+        b'T' | b'U' => Some(3),
+        _ => None,
+    }
+}
+
+fn encode_kmer(kmer: &[u8]) -> Option<u64> {
+    kmer.iter()
+        .try_fold(0u64, |code, &base| Some((code << 2) | encode_base(base)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_exact_matches_scan() {
+        let sequence = "ACGTACGTNNACGTACGTTTGACCAACGTACGTTT";
+        let index = KmerIndex::build(sequence, 4).unwrap();
+        let text = sequence.as_bytes();
+
+        let mut hits = index.find_exact("ACGTACGT", text).unwrap();
+        hits.sort();
+        assert_eq!(hits, vec![0, 10, 25]);
+        assert_eq!(index.find_exact("ACGTTTGA", text), Some(vec![14]));
+        assert_eq!(index.find_exact("GGGGGGGG", text), Some(Vec::new()));
+
+        // 短いクエリや縮重コードは全走査に任せる
+        assert_eq!(index.find_exact("ACG", text), None);
+        assert_eq!(index.find_exact("ACGTNNAC", text), None);
+    }
+
+    #[test]
+    fn test_chunked_build_matches_whole_sequence() {
+        let sequence = "ACGTACGTNNACGTACGTTTGACCAACGTACGTTT".repeat(3);
+        let whole = KmerIndex::build(&sequence, 4).unwrap();
+        let mut builder = KmerIndexBuilder::new(4, sequence.len() as u64).unwrap();
+        for chunk in sequence.as_bytes().chunks(5) {
+            builder.push(std::str::from_utf8(chunk).unwrap()).unwrap();
+        }
+        let chunked = builder.finish();
+        assert_eq!(chunked.entries, whole.entries);
+        assert_eq!(
+            chunked.find_exact("ACGTACGT", sequence.as_bytes()),
+            Some(vec![0, 10, 25, 35, 45, 60, 70, 80, 95])
+        );
+
+        // 位置は u32 で持つので、それより長い配列は確保する前に拒否する
+        assert_eq!(
+            KmerIndexBuilder::new(12, KmerIndex::MAX_LENGTH + 1).err(),
+            Some(IndexError::TooLong(KmerIndex::MAX_LENGTH + 1))
+        );
+    }
+}
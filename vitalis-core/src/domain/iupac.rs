@@ -0,0 +1,156 @@
+// IUPAC塩基コードのユーティリティ
+
+/// IUPACコードが表す塩基の集合（未知のコードは空文字列）
+pub fn expand(code: char) -> &'static str {
+    match code.to_ascii_uppercase() {
+        'A' => "A",
+        'C' => "C",
+        'G' => "G",
+        'T' | 'U' => "T",
+        'R' => "AG",
+        'Y' => "CT",
+        'S' => "CG",
+        'W' => "AT",
+        'K' => "GT",
+        'M' => "AC",
+        'B' => "CGT",
+        'D' => "AGT",
+        'H' => "ACT",
+        'V' => "ACG",
+        'N' => "ACGT",
+        _ => "",
+    }
+}
+
+/// パターン側のIUPACコード `code` が配列の塩基 `base` に一致するか
+pub fn matches(code: char, base: char) -> bool {
+    let base = base.to_ascii_uppercase();
+    let base = if base == 'U' { 'T' } else { base };
+    expand(code).contains(base)
+}
+
+/// `sequence` の `position` から始まる部分が `pattern` に一致するか
+pub fn matches_at(pattern: &[u8], sequence: &[u8], position: usize) -> bool {
+    position + pattern.len() <= sequence.len()
+        && pattern
+            .iter()
+            .zip(&sequence[position..])
+            .all(|(&code, &base)| matches(code as char, base as char))
+}
+
+/// 検索用テキストを作成（大文字化し、環状配列では原点をまたぐ一致のため
+/// 先頭 `overlap` 塩基を末尾に連結する）
+pub fn search_text(sequence: &str, circular: bool, overlap: usize) -> Vec<u8> {
+    let upper = sequence.to_ascii_uppercase();
+    if circular && !upper.is_empty() {
+        upper.bytes().cycle().take(upper.len() + overlap).collect()
+    } else {
+        upper.into_bytes()
+    }
+}
+
+/// `search_text` で作成したテキスト上でパターンが一致する開始位置（0..length）
+pub fn find_all(pattern: &str, text: &[u8], length: usize, circular: bool) -> Vec<usize> {
+    let pattern = pattern.as_bytes();
+    if pattern.is_empty() || (!circular && pattern.len() > length) {
+        return Vec::new();
+    }
+    let last = if circular {
+        length
+    } else {
+        length - pattern.len() + 1
+    };
+    (0..last)
+        .filter(|&i| matches_at(pattern, text, i))
+        .collect()
+}
+
+/// IUPACコードの相補塩基
+pub fn complement(code: char) -> char {
+    let complemented = match code.to_ascii_uppercase() {
+        'A' => 'T',
+        'T' | 'U' => 'A',
+        'G' => 'C',
+        'C' => 'G',
+        'R' => 'Y',
+        'Y' => 'R',
+        'K' => 'M',
+        'M' => 'K',
+        'B' => 'V',
+        'V' => 'B',
+        'D' => 'H',
+        'H' => 'D',
+        other => other,
+    };
+    if code.is_ascii_lowercase() {
+        complemented.to_ascii_lowercase()
+    } else {
+        complemented
+    }
+}
+
+/// IUPACコードを含む配列の逆相補配列
+pub fn reverse_complement(sequence: &str) -> String {
+    sequence.chars().rev().map(complement).collect()
+}
+
+/// 核酸配列とみなせるか（ACGTUN のみで構成される）
+pub fn is_nucleotide(sequence: &str) -> bool {
+    !sequence.is_empty()
+        && sequence
+            .chars()
+            .all(|c| matches!(c.to_ascii_uppercase(), 'A' | 'C' | 'G' | 'T' | 'U' | 'N'))
+}
+
+/// 縮重塩基（ACGT以外のIUPACコード）を含むか
+pub fn is_degenerate(sequence: &str) -> bool {
+    sequence
+        .chars()
+        .any(|c| !matches!(c.to_ascii_uppercase(), 'A' | 'C' | 'G' | 'T' | 'U'))
+}
+
+/// 一本鎖 DNA 中のヌクレオチド残基の質量（g/mol）。縮重塩基は表す塩基の平均、
+/// 未知のコードは None
+pub fn dna_residue_mass(code: char) -> Option<f64> {
+    let bases = expand(code);
+    if bases.is_empty() {
+        return None;
+    }
+    let total: f64 = bases
+        .chars()
+        .map(|base| match base {
+            'A' => 313.21,
+            'C' => 289.18,
+            'G' => 329.21,
+            _ => 304.2,
+        })
+        .sum();
+    Some(total / bases.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degenerate_matching() {
+        assert!(matches_at(b"CYCGRG", b"TTCCCGAGTT", 2));
+        assert!(matches_at(b"CYCGRG", b"CTCGGG", 0));
+        assert!(!matches_at(b"CYCGRG", b"CACGAG", 0));
+        assert!(!matches_at(b"CYCGRG", b"CTCGG", 0));
+    }
+
+    #[test]
+    fn test_find_all_across_origin() {
+        let text = search_text("tcAAAAAg", true, 3);
+        assert_eq!(find_all("GTC", &text, 8, true), vec![7]);
+        assert!(find_all("GTC", &search_text("tcAAAAAg", false, 3), 8, false).is_empty());
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        assert_eq!(reverse_complement("GGTCTC"), "GAGACC");
+        assert_eq!(reverse_complement("CYCGRG"), "CYCGRG");
+        assert_eq!(reverse_complement("acgn"), "ncgt");
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// ランダムリンカー生成の条件（長さと GC% は別途指定する）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkerParams {
+    /// 認識配列を含めない酵素（空なら制限しない）
+    pub avoid_enzymes: Vec<String>,
+    /// 同じ塩基の連続の上限
+    pub max_homopolymer: usize,
+    /// 許容するヘアピン ΔG の下限（kcal/mol、これより安定な構造は不採用）
+    pub max_hairpin: f32,
+    /// 生成する候補の数
+    pub count: usize,
+    /// 乱数シード（指定すると同じ候補を再現できる）
+    pub seed: Option<u64>,
+}
+
+impl Default for LinkerParams {
+    fn default() -> Self {
+        Self {
+            avoid_enzymes: Vec::new(),
+            max_homopolymer: 3,
+            max_hairpin: -2.0,
+            count: 5,
+            seed: None,
+        }
+    }
+}
+
+/// 条件を満たしたリンカー候補
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Linker {
+    pub sequence: String,
+    pub gc_percent: f64,
+    pub tm: f32,
+    /// 最も安定なヘアピンの ΔG（kcal/mol、構造がなければ 0）
+    pub hairpin_delta_g: f32,
+    pub longest_homopolymer: usize,
+}
+
+/// リンカー生成の結果（ヘアピンの弱い順）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkerSet {
+    pub length: usize,
+    /// 要求した GC% と、長さに合わせて丸めた G/C の数
+    pub gc_target: f64,
+    pub gc_count: usize,
+    pub avoided_enzymes: Vec<String>,
+    pub linkers: Vec<Linker>,
+    /// 試行した配列の数
+    pub attempts: usize,
+    /// 使った乱数シード
+    pub seed: u64,
+    pub warnings: Vec<String>,
+}
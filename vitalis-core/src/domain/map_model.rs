@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+/// リニアマップ描画のスタイル（長さの単位は描画座標、SVGではpx）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapStyle {
+    pub width: f64,
+    pub margin: f64,
+    /// 目盛りと数値を描く領域の高さ
+    pub ruler_height: f64,
+    pub feature_height: f64,
+    /// トラック間の間隔
+    pub track_gap: f64,
+    pub font_size: f64,
+    /// 目盛り間隔の目安
+    pub tick_spacing: f64,
+    pub show_labels: bool,
+    /// 描画しないアノテーション種類
+    pub hidden_types: Vec<String>,
+}
+
+impl Default for MapStyle {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            margin: 20.0,
+            ruler_height: 24.0,
+            feature_height: 14.0,
+            track_gap: 6.0,
+            font_size: 11.0,
+            tick_spacing: 80.0,
+            show_labels: true,
+            hidden_types: vec!["source".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MapPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MapLine {
+    pub from: MapPoint,
+    pub to: MapPoint,
+}
+
+/// 矢印の向き（範囲外で切れている末端には矢じりを付けない）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrowDirection {
+    Right,
+    Left,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextAnchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// 目盛り（position は1-basedの塩基位置）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapTick {
+    pub position: usize,
+    pub x: f64,
+    pub label: String,
+}
+
+/// アノテーションを描くトラック（同じ表示段の重ならないアノテーションを1行に詰める）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapTrack {
+    pub index: usize,
+    pub tier: u8,
+    pub y: f64,
+    pub height: f64,
+}
+
+/// アノテーションの図形
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapFeatureGlyph {
+    pub feature_id: String,
+    pub feature_type: String,
+    pub label: String,
+    pub color: String,
+    pub track: usize,
+    /// 外接矩形
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub direction: ArrowDirection,
+    /// 区間ごとの多角形（矢じりは向きの先端側の区間のみ）
+    pub shapes: Vec<Vec<MapPoint>>,
+    /// 区間（エキソン）間を結ぶ線
+    pub connectors: Vec<MapLine>,
+    /// 描画範囲で切り詰めたか
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapLabel {
+    pub feature_id: String,
+    pub text: String,
+    /// テキストのベースライン位置
+    pub x: f64,
+    pub y: f64,
+    pub anchor: TextAnchor,
+    /// 図形の内側に描くか（外側の場合は図形の右隣）
+    pub inside: bool,
+}
+
+/// 解像度に依存しないリニアマップの描画モデル（座標は0-based半開区間 [start, end) を表す）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapModel {
+    pub width: f64,
+    pub height: f64,
+    pub start: usize,
+    pub end: usize,
+    /// 1塩基あたりの描画幅
+    pub scale: f64,
+    pub backbone: MapLine,
+    pub ticks: Vec<MapTick>,
+    pub tracks: Vec<MapTrack>,
+    pub features: Vec<MapFeatureGlyph>,
+    pub labels: Vec<MapLabel>,
+}
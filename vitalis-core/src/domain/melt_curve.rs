@@ -0,0 +1,61 @@
+use super::thermodynamic_calculator::ThermodynamicProvenance;
+use serde::{Deserialize, Serialize};
+
+/// 曲線の点数の上限（温度範囲 / 刻み）
+pub const MAX_MELT_POINTS: usize = 10_000;
+/// ピークとみなす極大の高さ（最大の極大に対する割合）の下限
+pub const MIN_PEAK_FRACTION: f32 = 0.1;
+
+/// 融解曲線（HRM）予測の条件
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MeltCurveParams {
+    /// 温度範囲と刻み（°C）
+    pub temperature_min: f32,
+    pub temperature_max: f32,
+    pub step: f32,
+    /// 各塩基の周りで局所的な融解を評価する長さ（アンプリコンより長ければ全体）
+    pub window: usize,
+}
+
+impl Default for MeltCurveParams {
+    fn default() -> Self {
+        Self {
+            temperature_min: 60.0,
+            temperature_max: 100.0,
+            step: 0.1,
+            window: 25,
+        }
+    }
+}
+
+impl MeltCurveParams {
+    /// 曲線の点数
+    pub fn point_count(&self) -> usize {
+        ((self.temperature_max - self.temperature_min) / self.step).floor() as usize + 1
+    }
+}
+
+/// 融解ピーク（-dF/dT の極大）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeltPeak {
+    /// °C
+    pub temperature: f32,
+    /// その温度の -dF/dT
+    pub height: f32,
+}
+
+/// アンプリコンの融解曲線の予測。蛍光は二本鎖のまま残る塩基対の割合に比例するとみなす
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeltCurve {
+    pub length: usize,
+    /// アンプリコン全体を1つの二本鎖とみなした Tm（°C）
+    pub tm: Option<f32>,
+    /// (温度 °C, -dF/dT) の列。蛍光 F は 1（すべて二本鎖）から 0 に下がる
+    pub points: Vec<(f32, f32)>,
+    /// 温度順
+    pub peaks: Vec<MeltPeak>,
+    pub params: MeltCurveParams,
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+}
@@ -0,0 +1,99 @@
+use super::thermodynamic_calculator::ThermodynamicProvenance;
+use serde::{Deserialize, Serialize};
+
+/// 結合部位の融解マップの条件（ウィンドウをプライマーとみなして評価する）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeltingMapParams {
+    /// ウィンドウ（仮想プライマー）の長さ
+    pub window: usize,
+    /// ウィンドウをずらす間隔
+    pub step: usize,
+    /// 3'末端 ΔG を計算する塩基数
+    pub three_prime_length: usize,
+    /// プライマーに向く Tm の範囲（°C）
+    pub tm_min: f32,
+    pub tm_max: f32,
+    /// 3'末端 ΔG の下限（kcal/mol、これより安定だとミスプライミングしやすい）
+    pub max_three_prime_stability: f32,
+}
+
+impl Default for MeltingMapParams {
+    fn default() -> Self {
+        Self {
+            window: 20,
+            step: 1,
+            three_prime_length: 5,
+            tm_min: 55.0,
+            tm_max: 65.0,
+            max_three_prime_stability: -9.0,
+        }
+    }
+}
+
+/// 1つのウィンドウの評価。ACGT 以外を含むウィンドウは値が None
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeltingPoint {
+    /// ウィンドウの開始位置（0-based）
+    pub position: usize,
+    pub gc_percent: f64,
+    pub tm: Option<f32>,
+    /// 順方向プライマー（ウィンドウそのもの）の3'末端 ΔG
+    pub forward_three_prime_dg: Option<f32>,
+    /// 逆方向プライマー（ウィンドウの逆相補）の3'末端 ΔG
+    pub reverse_three_prime_dg: Option<f32>,
+    /// Tm と3'末端 ΔG が条件内か
+    pub forward_ok: bool,
+    pub reverse_ok: bool,
+}
+
+/// 領域 [start, end) の融解マップ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeltingMap {
+    pub seq_id: String,
+    pub start: usize,
+    pub end: usize,
+    pub params: MeltingMapParams,
+    pub points: Vec<MeltingPoint>,
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+}
+
+/// プライマー長 × 開始位置の Tm グリッドの条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TmGridParams {
+    pub length_min: usize,
+    pub length_max: usize,
+    /// 開始位置をずらす間隔
+    pub step: usize,
+}
+
+impl Default for TmGridParams {
+    fn default() -> Self {
+        Self {
+            length_min: 18,
+            length_max: 30,
+            step: 1,
+        }
+    }
+}
+
+/// 領域 [start, end) 内のプライマー候補すべての Tm（ヒートマップ用）。
+/// `tm[i][j]` は長さ `lengths[i]`、開始位置 `positions[j]` の候補で、領域をはみ出す
+/// 候補や ACGT 以外を含む候補は None
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmGrid {
+    pub seq_id: String,
+    pub start: usize,
+    pub end: usize,
+    pub lengths: Vec<usize>,
+    pub positions: Vec<usize>,
+    pub tm: Vec<Vec<Option<f32>>>,
+    /// 色の尺度に使う Tm の最小値・最大値（値のあるセルがなければ None）
+    pub tm_min: Option<f32>,
+    pub tm_max: Option<f32>,
+    pub params: TmGridParams,
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+}
@@ -1,7 +1,63 @@
 // Domain layer - ビジネスロジックとエンティティ
+pub mod alignment;
+pub mod analysis_result;
+pub mod assay;
+pub mod assembly;
+pub mod backbone;
+pub mod batch;
+pub mod bed;
+pub mod cloning;
+pub mod codon_usage;
+pub mod concentration;
+pub mod construct;
+pub mod contamination;
+pub mod coordinates;
+pub mod copy_format;
+pub mod crispr;
+pub mod dilution;
+pub mod dotplot;
+pub mod edit;
+pub mod export_format;
+pub mod feature;
+pub mod frameshift;
+pub mod gc_skew;
+pub mod genetic_code;
+pub mod homology_arm;
+pub mod index;
+pub mod iupac;
+pub mod linker;
+pub mod map_model;
+pub mod melt_curve;
+pub mod melting_map;
+pub mod mutation;
+pub mod navigation;
+pub mod nullomer;
+pub mod oligo;
+pub mod orf;
+pub mod plugin;
 pub mod primer;
+pub mod probe_label;
+pub mod project;
+pub mod protein;
+pub mod protein_motif;
+pub mod rbs;
+pub mod read_set;
+pub mod read_stats;
+pub mod reference;
+pub mod region;
+pub mod repeat;
+pub mod reproducibility;
+pub mod restriction;
+pub mod scaffold;
+pub mod screening;
+pub mod search;
+pub mod sequence_slice;
 pub mod thermodynamic_calculator;
 pub mod thermodynamics;
+pub mod track;
+pub mod validation;
+pub mod variant;
+pub mod warning;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -24,6 +80,9 @@ pub struct SequenceMetadata {
     pub length: usize,
     pub topology: Topology,
     pub file_path: Option<PathBuf>,
+    /// 他の配列から派生した場合の由来情報
+    #[serde(default)]
+    pub derived_from: Option<SequenceOrigin>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -32,6 +91,40 @@ pub enum Topology {
     Circular,
 }
 
+/// 鎖の向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Strand {
+    #[default]
+    Forward,
+    Reverse,
+}
+
+/// 派生配列の生成方法
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DerivationKind {
+    Amplicon,
+    /// ベクターの [start, end) を発現カセットで置換
+    ExpressionConstruct,
+    /// 親配列全体にランダム変異を導入したコピー
+    SimulatedMutant,
+    /// 親配列の [start, end) のアームで編集部位を挟んだ相同組換え用ドナー
+    HomologyDonor,
+    /// スキャフォールドのギャップ（N の連続）の間の [start, end)
+    Contig,
+    /// 制限酵素消化とライゲーションでベクターの [start, end) を挿入断片で置換
+    /// （原点をまたぐ場合は start > end）
+    Ligation,
+}
+
+/// 派生配列の由来（親配列上の0-based半開区間）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceOrigin {
+    pub parent_id: String,
+    pub start: usize,
+    pub end: usize,
+    pub kind: DerivationKind,
+}
+
 /// 範囲指定
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Range {
@@ -65,6 +158,32 @@ pub struct DetailedStats {
     // Enhanced statistics fields
     pub codon_usage: Option<CodonUsage>,
     pub quality_stats: Option<QualityStats>,
+    /// 分母から除いたギャップ（除外しない場合は None）
+    #[serde(default)]
+    pub gap_stats: Option<GapStats>,
+}
+
+/// 統計の条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsOptions {
+    /// この長さ以上の N の連続（スキャフォールドのギャップ）を塩基数・割合・エントロピーの
+    /// 計算から除く（None なら除かない）
+    pub min_gap_length: Option<usize>,
+    /// 線形配列の末尾で `window_size` に満たないウィンドウも出力する
+    /// （その window_size は実際の長さ。環状配列は原点をまたぐので常に満たす）
+    pub partial_windows: bool,
+}
+
+/// 統計から除いた N の連続（ギャップ）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GapStats {
+    pub min_gap_length: usize,
+    pub count: usize,
+    pub total_length: usize,
+    pub largest: usize,
+    /// ギャップを除いた長さ（割合の分母）
+    pub effective_length: usize,
 }
 
 /// 塩基カウント
@@ -102,6 +221,9 @@ pub struct WindowStats {
     pub window_size: usize,
     pub gc_percent: f64,
     pub entropy: f64,
+    /// ウィンドウ内でギャップとして除いた塩基数
+    #[serde(default)]
+    pub gap_bases: usize,
 }
 
 /// コドン使用統計
@@ -112,7 +234,20 @@ pub struct CodonUsage {
     pub amino_acid_counts: HashMap<char, usize>,
     pub start_codons: usize,
     pub stop_codons: usize,
+    /// 宿主で稀なコドンのうち配列に現れたもの
     pub rare_codons: Vec<String>,
+    /// 稀なコドンの判定に使った宿主と閾値
+    #[serde(default)]
+    pub params: codon_usage::CodonUsageParams,
+    /// rare_codons の出現数と宿主での頻度（多い順）
+    #[serde(default)]
+    pub rare_codon_details: Vec<codon_usage::RareCodon>,
+    /// 稀なコドンの割合（%、翻訳できたコドンに対して）
+    #[serde(default)]
+    pub rare_codon_percent: f64,
+    /// params.host に対する Codon Adaptation Index
+    #[serde(default)]
+    pub cai: Option<f64>,
 }
 
 /// 品質スコア統計（FASTQ用）
@@ -127,6 +262,38 @@ pub struct QualityStats {
     pub quality_distribution: HashMap<u8, usize>,
 }
 
+/// k-mer の出現数と、塩基組成から期待される出現数との比
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KmerCount {
+    pub kmer: String,
+    pub count: u64,
+    pub expected: f64,
+    /// 観測値 / 期待値
+    pub ratio: f64,
+}
+
+/// 出現回数 `occurrences` の k-mer が `kmers` 種類
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KmerFrequency {
+    pub occurrences: u64,
+    pub kmers: u64,
+}
+
+/// k-mer スペクトル（順鎖、ACGT 以外を含む k-mer は数えない）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KmerSpectrum {
+    pub k: usize,
+    pub total_kmers: u64,
+    pub distinct_kmers: u64,
+    pub possible_kmers: u64,
+    /// 出現回数ごとの種類数（出現回数の昇順）
+    pub spectrum: Vec<KmerFrequency>,
+    /// 出現数の多い k-mer
+    pub most_frequent: Vec<KmerCount>,
+    /// 観測値 / 期待値の大きい k-mer（2回以上出現したもの）
+    pub over_represented: Vec<KmerCount>,
+}
+
 // ドメインレイヤーでのRepositoryトレイト定義（依存性の逆転）
 pub trait SequenceRepository {
     type Error: std::error::Error + Send + Sync + 'static;
@@ -151,12 +318,35 @@ pub trait SequenceParser {
 
 // ドメインレイヤーでのStatsサービストレイト定義
 pub trait StatsService {
-    fn calculate_detailed_stats(&self, sequence: &str) -> DetailedStats;
+    fn calculate_detailed_stats(&self, sequence: &str) -> DetailedStats {
+        self.calculate_detailed_stats_with_options(sequence, &StatsOptions::default())
+    }
     fn calculate_window_stats(
         &self,
         sequence: &str,
         window_size: usize,
         step: usize,
+    ) -> Vec<WindowStats> {
+        self.calculate_window_stats_with_options(
+            sequence,
+            window_size,
+            step,
+            &Topology::Linear,
+            &StatsOptions::default(),
+        )
+    }
+    fn calculate_detailed_stats_with_options(
+        &self,
+        sequence: &str,
+        options: &StatsOptions,
+    ) -> DetailedStats;
+    fn calculate_window_stats_with_options(
+        &self,
+        sequence: &str,
+        window_size: usize,
+        step: usize,
+        topology: &Topology,
+        options: &StatsOptions,
     ) -> Vec<WindowStats>;
 }
 
@@ -168,6 +358,8 @@ where
 {
     repository: R,
     stats_service: S,
+    /// リポジトリを書き換え可能な形で渡した回数（変更の有無を安く判定するための番号）
+    revision: u64,
 }
 
 impl<R, S> SequenceAnalysisService<R, S>
@@ -179,12 +371,23 @@ where
         Self {
             repository,
             stats_service,
+            revision: 0,
         }
     }
 
     pub fn analyze_sequence(&mut self, seq_id: &str) -> Result<DetailedStats, R::Error> {
+        self.analyze_sequence_with_options(seq_id, &StatsOptions::default())
+    }
+
+    pub fn analyze_sequence_with_options(
+        &mut self,
+        seq_id: &str,
+        options: &StatsOptions,
+    ) -> Result<DetailedStats, R::Error> {
         let sequence = self.repository.get_sequence(seq_id)?;
-        Ok(self.stats_service.calculate_detailed_stats(&sequence))
+        Ok(self
+            .stats_service
+            .calculate_detailed_stats_with_options(&sequence, options))
     }
 
     pub fn analyze_window(
@@ -192,17 +395,41 @@ where
         seq_id: &str,
         window_size: usize,
         step: usize,
+    ) -> Result<Vec<WindowStats>, R::Error> {
+        self.analyze_window_with_options(seq_id, window_size, step, &StatsOptions::default())
+    }
+
+    pub fn analyze_window_with_options(
+        &mut self,
+        seq_id: &str,
+        window_size: usize,
+        step: usize,
+        options: &StatsOptions,
     ) -> Result<Vec<WindowStats>, R::Error> {
         let sequence = self.repository.get_sequence(seq_id)?;
-        Ok(self
-            .stats_service
-            .calculate_window_stats(&sequence, window_size, step))
+        let topology = self
+            .repository
+            .get_metadata(seq_id)
+            .map_or(Topology::Linear, |metadata| metadata.topology);
+        Ok(self.stats_service.calculate_window_stats_with_options(
+            &sequence,
+            window_size,
+            step,
+            &topology,
+            options,
+        ))
     }
 
     pub fn get_repository_mut(&mut self) -> &mut R {
+        self.revision += 1;
         &mut self.repository
     }
 
+    /// `get_repository_mut` を呼ぶたびに増える番号。同じなら前回から書き換えられていない
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
     pub fn get_repository(&self) -> &R {
         &self.repository
     }
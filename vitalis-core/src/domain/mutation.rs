@@ -0,0 +1,37 @@
+use super::variant::Variant;
+use serde::{Deserialize, Serialize};
+
+/// ランダム変異導入の頻度（いずれも1塩基あたりの確率）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MutationRates {
+    pub snv: f64,
+    pub insertion: f64,
+    pub deletion: f64,
+    /// 挿入・欠失の最大長（長さは 1..=max_indel_length から一様に選ぶ）
+    pub max_indel_length: usize,
+    /// 乱数シード（指定すると同じ変異セットを再現できる）
+    pub seed: Option<u64>,
+}
+
+impl Default for MutationRates {
+    fn default() -> Self {
+        Self {
+            snv: 0.01,
+            insertion: 0.001,
+            deletion: 0.001,
+            max_indel_length: 3,
+            seed: None,
+        }
+    }
+}
+
+/// 変異を導入したテンプレートのコピー
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SimulatedMutant {
+    /// 保存後の派生配列ID（保存前は空）
+    pub seq_id: String,
+    pub sequence: String,
+    /// 導入した変異（テンプレート上の0-based座標、位置順）
+    pub variants: Vec<Variant>,
+}
@@ -0,0 +1,144 @@
+use super::Strand;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// ユーザーが配列上に付けたブックマーク（position は0-based）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    #[serde(default)]
+    pub id: String,
+    pub position: usize,
+    #[serde(default)]
+    pub label: String,
+}
+
+/// 配列IDごとのブックマークストア（位置順に保持）
+#[derive(Debug, Default)]
+pub struct BookmarkStore {
+    bookmarks: HashMap<String, Vec<Bookmark>>,
+    next_id: usize,
+}
+
+impl BookmarkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ブックマークを追加（IDが空なら採番する）
+    pub fn add(&mut self, seq_id: &str, mut bookmark: Bookmark) -> String {
+        if bookmark.id.is_empty() {
+            self.next_id += 1;
+            bookmark.id = format!("bookmark_{}", self.next_id);
+        }
+        let id = bookmark.id.clone();
+        let entries = self.bookmarks.entry(seq_id.to_string()).or_default();
+        entries.push(bookmark);
+        entries.sort_by_key(|b| b.position);
+        id
+    }
+
+    pub fn get_all(&self, seq_id: &str) -> &[Bookmark] {
+        self.bookmarks
+            .get(seq_id)
+            .map(|bookmarks| bookmarks.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// ブックマークを削除（該当するものがなければ false）
+    pub fn remove(&mut self, seq_id: &str, bookmark_id: &str) -> bool {
+        let Some(entries) = self.bookmarks.get_mut(seq_id) else {
+            return false;
+        };
+        let before = entries.len();
+        entries.retain(|b| b.id != bookmark_id);
+        entries.len() < before
+    }
+
+    pub fn remove_sequence(&mut self, seq_id: &str) {
+        self.bookmarks.remove(seq_id);
+    }
+}
+
+/// 移動の向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NavigationDirection {
+    Next,
+    Previous,
+}
+
+/// 移動先として探す対象
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NavigationTarget {
+    /// アノテーション（feature_types が空ならすべての種類）
+    Feature {
+        #[serde(default)]
+        feature_types: Vec<String>,
+    },
+    /// 指定した酵素の認識サイト
+    RestrictionSite {
+        enzymes: Vec<String>,
+    },
+    /// 標準遺伝暗号で `min_length` アミノ酸以上の ORF
+    Orf {
+        min_length: usize,
+    },
+    Bookmark,
+}
+
+/// 見つかった移動先の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NavigationKind {
+    Feature,
+    RestrictionSite,
+    Orf,
+    Bookmark,
+}
+
+/// 移動先（0-based半開区間 [start, end)、移動する座標は start）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NavigationHit {
+    pub kind: NavigationKind,
+    /// アノテーション・ブックマークのID（制限酵素サイト・ORF は None）
+    pub id: Option<String>,
+    /// 表示名（アノテーションのラベル、酵素名、ORF の読み枠と長さ、ブックマーク名）
+    pub label: String,
+    pub start: usize,
+    pub end: usize,
+    /// ブックマークは None
+    pub strand: Option<Strand>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bookmark_store_keeps_position_order() {
+        let mut store = BookmarkStore::new();
+        store.add(
+            "seq_1",
+            Bookmark {
+                id: String::new(),
+                position: 500,
+                label: "late".to_string(),
+            },
+        );
+        let id = store.add(
+            "seq_1",
+            Bookmark {
+                id: String::new(),
+                position: 20,
+                label: "early".to_string(),
+            },
+        );
+
+        assert_eq!(store.get_all("seq_1")[0].id, id);
+        assert!(store.remove("seq_1", &id));
+        assert!(!store.remove("seq_1", &id));
+        assert_eq!(store.get_all("seq_1")[0].label, "late");
+        assert!(store.get_all("seq_2").is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// 欠失 k-mer（ヌロマー）探索の条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NullomerParams {
+    /// 相補鎖の k-mer も出現とみなす（どちらの鎖とも交差反応しない配列を探す）
+    pub both_strands: bool,
+    /// 報告する k-mer の GC 含量の範囲（%）
+    pub gc_min: f64,
+    pub gc_max: f64,
+    /// 報告する k-mer のホモポリマーの上限（None なら制限しない）
+    pub max_homopolymer: Option<usize>,
+    pub max_results: usize,
+}
+
+impl Default for NullomerParams {
+    fn default() -> Self {
+        Self {
+            both_strands: true,
+            gc_min: 0.0,
+            gc_max: 100.0,
+            max_homopolymer: None,
+            max_results: 1000,
+        }
+    }
+}
+
+/// 配列群に現れない k-mer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NullomerReport {
+    pub k: usize,
+    pub sequences: usize,
+    /// 数えた k-mer の位置（ACGT 以外を含む位置は除く）
+    pub scanned_positions: u64,
+    /// 4^k 通りのうち現れた・現れなかった k-mer の数
+    pub possible: u64,
+    pub present: u64,
+    pub absent: u64,
+    /// 欠失 k-mer のうち GC・ホモポリマーの条件を満たすものの数
+    pub matching: u64,
+    /// 条件を満たす欠失 k-mer（辞書順、max_results まで）
+    pub nullomers: Vec<String>,
+    pub truncated: bool,
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+/// 名前付きのオリゴ（バーコード・プローブ・プライマーなど）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Oligo {
+    pub name: String,
+    pub sequence: String,
+}
+
+/// クラスタ間距離の定義
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Linkage {
+    /// 最も近いメンバー間の距離
+    Single,
+    /// 最も遠いメンバー間の距離
+    #[default]
+    Complete,
+    /// メンバー間距離の平均（UPGMA）
+    Average,
+}
+
+/// クロストーク解析のパラメータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrossTalkParams {
+    /// クラスタ間距離がこの値以下なら同じグループとみなす
+    pub max_cluster_distance: f64,
+    pub linkage: Linkage,
+    /// 逆相補鎖との距離も考慮し、小さい方を採用する
+    pub include_reverse_complement: bool,
+}
+
+impl Default for CrossTalkParams {
+    fn default() -> Self {
+        Self {
+            max_cluster_distance: 3.0,
+            linkage: Linkage::Complete,
+            include_reverse_complement: true,
+        }
+    }
+}
+
+/// 階層クラスタリングの結合（ID は 0..n がオリゴ、n + i が i 番目の結合で生じたクラスタ）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterMerge {
+    pub left: usize,
+    pub right: usize,
+    pub distance: f64,
+    pub size: usize,
+}
+
+/// 閾値以下で結合された、2つ以上のオリゴからなるグループ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OligoCluster {
+    pub members: Vec<String>,
+    /// メンバー間の最大編集距離
+    pub max_distance: usize,
+    /// メンバー間の最小編集距離
+    pub min_distance: usize,
+}
+
+/// オリゴセットの編集距離行列とクロストーク候補のグループ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrossTalkReport {
+    pub names: Vec<String>,
+    /// Levenshtein 距離（names と同じ順の対称行列）
+    pub distances: Vec<Vec<usize>>,
+    /// 逆相補鎖の方が近かった組（distances は逆相補鎖との距離）
+    pub reverse_complement_pairs: Vec<(usize, usize)>,
+    pub merges: Vec<ClusterMerge>,
+    pub clusters: Vec<OligoCluster>,
+}
+
+/// 一本鎖オリゴの骨格（配列に U があれば RNA）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NucleicAcid {
+    Dna,
+    Rna,
+}
+
+/// 一本鎖オリゴの 260 nm 吸光係数と分子量、および 1 OD260 あたりの量
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OligoProperties {
+    pub molecule: NucleicAcid,
+    pub length: usize,
+    /// 最近接塩基対法による ε260（L/(mol·cm)）
+    pub extinction_coefficient: f64,
+    /// 5'-OH・3'-OH の合成オリゴとしての分子量（g/mol）
+    pub molecular_weight: f64,
+    pub nmol_per_od: f64,
+    pub ug_per_od: f64,
+}
+
+/// 縮重塩基（IUPAC）を含みうるオリゴの Tm・GC 含量。展開した配列ごとの値の平均と範囲
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DegenerateEstimate {
+    /// 展開した配列の平均（縮重塩基がなければその配列の値）
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    /// 縮重塩基を含み、値が1つに決まらない
+    pub degenerate: bool,
+    /// 縮重塩基の位置（0-based）
+    pub degenerate_positions: Vec<usize>,
+    /// 配列が表す展開の数（u64 に収まらなければ u64::MAX）
+    pub expansions: u64,
+    /// すべての展開を評価した（false なら決定的な標本からの推定）
+    pub exhaustive: bool,
+    pub warnings: Vec<String>,
+}
+
+/// オリゴ量の単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OligoAmountUnit {
+    /// 1 mL・光路長 1 cm で A260 = 1 となる量
+    Od260,
+    Micrograms,
+    Picomoles,
+}
+
+/// 同じ量のオリゴを各単位で表したもの
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OligoAmount {
+    pub od260: f64,
+    pub micrograms: f64,
+    pub picomoles: f64,
+}
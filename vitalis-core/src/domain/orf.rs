@@ -0,0 +1,54 @@
+use super::Strand;
+use serde::{Deserialize, Serialize};
+
+/// オープンリーディングフレーム（座標は順鎖上の0-based半開区間、終止コドンを含む）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Orf {
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    /// 読み枠（+1〜+3 / -1〜-3）
+    pub frame: i8,
+    /// 翻訳産物（終止コドンは含まない）
+    pub protein: String,
+}
+
+impl Orf {
+    pub fn length_aa(&self) -> usize {
+        self.protein.len()
+    }
+
+    /// タンパク質上の残基区間 [aa_start, aa_end) を塩基配列上の区間に変換
+    pub fn dna_range(&self, aa_start: usize, aa_end: usize) -> (usize, usize) {
+        match self.strand {
+            Strand::Forward => (self.start + aa_start * 3, self.start + aa_end * 3),
+            Strand::Reverse => (self.end - aa_end * 3, self.end - aa_start * 3),
+        }
+    }
+}
+
+/// 読み枠途中の終止コドン（座標は順鎖上の0-based半開区間）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InternalStop {
+    /// 翻訳産物上の位置（0-based）
+    pub residue: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// 1つの読み枠で配列全体を翻訳した結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FrameTranslation {
+    /// 読み枠（+1〜+3 / -1〜-3）
+    pub frame: i8,
+    pub strand: Strand,
+    /// NCBI 遺伝暗号ID
+    pub genetic_code: u8,
+    /// 翻訳した区間（順鎖上の0-based半開区間、端数の塩基は含まない）
+    pub start: usize,
+    pub end: usize,
+    /// 翻訳産物（終止コドンは '*'）
+    pub protein: String,
+    /// 末尾以外の終止コドン
+    pub internal_stops: Vec<InternalStop>,
+}
@@ -0,0 +1,55 @@
+use crate::domain::analysis_result::AnalysisResult;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PluginError {
+    #[error("Analysis plugin not found: {0}")]
+    NotFound(String),
+    #[error("Analysis plugin already registered: {0}")]
+    AlreadyRegistered(String),
+    #[error("Invalid parameters for {0}: {1}")]
+    InvalidParameters(String, String),
+    #[error("Analysis {0} failed: {1}")]
+    Failed(String, String),
+}
+
+/// コアのモジュールを変更せずに追加できる配列解析。cargo feature で有効にする解析や
+/// 下流のクレートが実装し、`PluginRegistry` に登録するとアプリケーションのコマンドから
+/// 実行でき、UI にも一覧される
+pub trait SequenceAnalysisPlugin: Send + Sync {
+    /// 一意な名前（コマンドから指定する。"gc_skew" のような snake_case）
+    fn name(&self) -> &str;
+
+    /// UI に表示する説明
+    fn description(&self) -> &str;
+
+    /// 条件の JSON Schema（UI が入力フォームを作るのに使う）
+    fn parameter_schema(&self) -> serde_json::Value;
+
+    /// `sequence` を `params`（省略時は空のオブジェクト）の条件で解析する
+    fn run(
+        &self,
+        seq_id: &str,
+        sequence: &str,
+        params: &serde_json::Value,
+    ) -> Result<AnalysisResult<serde_json::Value>, PluginError>;
+}
+
+/// 登録済みプラグインの情報
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub description: String,
+    pub parameter_schema: serde_json::Value,
+}
+
+impl PluginInfo {
+    pub fn of(plugin: &dyn SequenceAnalysisPlugin) -> Self {
+        Self {
+            name: plugin.name().to_string(),
+            description: plugin.description().to_string(),
+            parameter_schema: plugin.parameter_schema(),
+        }
+    }
+}
@@ -1,3 +1,8 @@
+use super::dilution::StandardCurvePlan;
+use super::feature::SequenceFeature;
+use super::thermodynamic_calculator::ThermodynamicProvenance;
+use super::warning::{Warning, WarningSeverity};
+use super::Strand;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -15,6 +20,78 @@ pub struct PrimerDesignParams {
     pub max_self_dimer: f32,
     pub max_hairpin: f32,
     pub max_hetero_dimer: f32,
+    /// 塩基ごとの重みトラックによる候補の評価（未指定なら評価しない）
+    #[serde(default)]
+    pub weighting: Option<PositionWeighting>,
+    /// 鋳型の塩基ごとの品質値（Sanger 由来の鋳型など、未指定なら評価しない）
+    #[serde(default)]
+    pub quality_mask: Option<QualityMask>,
+    /// Forward は領域の開始、Reverse は終了の前後この塩基数の範囲から候補を探す
+    #[serde(default = "PrimerDesignParams::default_search_flank")]
+    pub search_flank: usize,
+    /// 領域を含み、増幅産物長の上限（3000 bp）に収まる範囲のどこからでも候補を探す
+    /// （search_flank は使わない。領域から離れたプライマーが必要なアッセイ向け）
+    #[serde(default)]
+    pub search_anywhere: bool,
+}
+
+impl PrimerDesignParams {
+    fn default_search_flank() -> usize {
+        50
+    }
+}
+
+/// 品質値が閾値未満の塩基に重なる候補を除外するマスク
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityMask {
+    /// 鋳型配列と同じ長さの Phred 品質値
+    pub qualities: Vec<u8>,
+    #[serde(default = "QualityMask::default_min_quality")]
+    pub min_quality: u8,
+}
+
+impl QualityMask {
+    fn default_min_quality() -> u8 {
+        20
+    }
+
+    /// [start, end) に閾値未満の塩基を含むか（範囲外は低品質とみなす）
+    pub fn masks(&self, start: usize, end: usize) -> bool {
+        self.qualities
+            .get(start..end)
+            .is_none_or(|window| window.iter().any(|&q| q < self.min_quality))
+    }
+}
+
+/// 塩基ごとの重み（保存度など）によるプライマー候補の減点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PositionWeighting {
+    /// 鋳型配列に登録したスコアトラックのID
+    pub track_id: String,
+    /// この値未満の塩基を低重みとみなす
+    pub min_weight: f64,
+    /// 低重みの塩基1つあたりの減点
+    pub penalty_per_base: f32,
+    /// 3'末端からこの塩基数以内の低重み塩基は2倍減点
+    pub three_prime_window: usize,
+    /// 低重みの塩基をこの数より多く含む候補は除外（None なら減点のみ）
+    pub max_low_weight_bases: Option<usize>,
+    /// 重みを反転して評価する（可変領域に設計する場合）
+    pub invert: bool,
+}
+
+impl Default for PositionWeighting {
+    fn default() -> Self {
+        Self {
+            track_id: String::new(),
+            min_weight: 0.8,
+            penalty_per_base: 5.0,
+            three_prime_window: 5,
+            max_low_weight_bases: None,
+            invert: false,
+        }
+    }
 }
 
 impl Default for PrimerDesignParams {
@@ -30,6 +107,10 @@ impl Default for PrimerDesignParams {
             max_self_dimer: -8.0,
             max_hairpin: -5.0,
             max_hetero_dimer: -8.0,
+            weighting: None,
+            quality_mask: None,
+            search_flank: Self::default_search_flank(),
+            search_anywhere: false,
         }
     }
 }
@@ -47,7 +128,10 @@ pub struct Primer {
     pub three_prime_stability: f32,
     pub direction: PrimerDirection,
     pub quality_score: f32,
-    pub quality_warnings: Vec<String>,
+    pub quality_warnings: Vec<Warning>,
+    /// Tm・ΔGの計算に用いたパラメータセットと条件（記録のない旧データでは None）
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -73,6 +157,23 @@ pub struct PrimerPair {
     pub validation_results: ValidationResults,
 }
 
+impl PrimerPair {
+    /// 両プライマーの品質警告・ペアの検証警告・増幅産物QCの警告
+    pub fn warnings(&self) -> impl Iterator<Item = &Warning> {
+        self.forward
+            .quality_warnings
+            .iter()
+            .chain(&self.reverse.quality_warnings)
+            .chain(&self.validation_results.warnings)
+            .chain(
+                self.validation_results
+                    .amplicon_qc
+                    .iter()
+                    .flat_map(|qc| &qc.warnings),
+            )
+    }
+}
+
 /// バリデーション結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResults {
@@ -80,7 +181,13 @@ pub struct ValidationResults {
     pub hairpin_check: bool,
     pub hetero_dimer_check: Option<bool>,
     pub specificity: Option<f32>,
-    pub warnings: Vec<String>,
+    pub warnings: Vec<Warning>,
+    /// 増幅産物のQC（ペア選択時に実施）
+    #[serde(default)]
+    pub amplicon_qc: Option<AmpliconQc>,
+    /// qPCR 標準曲線の希釈計画（アッセイ記録用）
+    #[serde(default)]
+    pub standard_curve: Option<StandardCurvePlan>,
 }
 
 impl ValidationResults {
@@ -91,19 +198,26 @@ impl ValidationResults {
             hetero_dimer_check: None,
             specificity: None,
             warnings: Vec::new(),
+            amplicon_qc: None,
+            standard_curve: None,
         }
     }
 
     pub fn is_valid(&self) -> bool {
         self.self_dimer_check && self.hairpin_check && self.warnings.is_empty()
     }
+
+    /// 最も重い警告の重大度（警告がなければ None）
+    pub fn max_severity(&self) -> Option<WarningSeverity> {
+        self.warnings.iter().map(|warning| warning.severity).max()
+    }
 }
 
 /// マルチプレックス互換性結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiplexCompatibility {
     pub compatibility_matrix: HashMap<String, HashMap<String, f32>>,
-    pub warnings: Vec<String>,
+    pub warnings: Vec<Warning>,
     pub overall_score: f32,
 }
 
@@ -113,16 +227,503 @@ pub struct PrimerDesignResult {
     pub pairs: Vec<PrimerPair>,
     pub design_params: PrimerDesignParams,
     pub target_sequence: String,
+    /// 標的領域 [target_start, target_end)（0-based・半開区間）
     pub target_start: usize,
     pub target_end: usize,
     pub multiplex_compatibility: Option<MultiplexCompatibility>,
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+    /// 保存を止める重大度の警告があり、プロジェクトに保存しなかったペアの ID
+    #[serde(default)]
+    pub blocked_pairs: Vec<String>,
+    /// ペアが1組も見つからなかった場合の、条件ごとの除外数と惜しい候補
+    #[serde(default)]
+    pub diagnostics: Option<DesignDiagnostics>,
+}
+
+impl PrimerDesignResult {
+    /// 鋳型上の位置（標的領域・プライマー位置）を `offset` だけずらす。
+    /// 断片で設計した結果を元の配列の座標で示すため
+    pub fn shift(&mut self, offset: usize) {
+        self.target_start += offset;
+        self.target_end += offset;
+        let near_misses = self
+            .diagnostics
+            .iter_mut()
+            .flat_map(|diagnostics| &mut diagnostics.near_misses)
+            .flat_map(|miss| &mut miss.primers);
+        let paired = self
+            .pairs
+            .iter_mut()
+            .flat_map(|pair| [&mut pair.forward, &mut pair.reverse]);
+        for primer in paired.chain(near_misses) {
+            primer.position += offset;
+        }
+    }
+}
+
+/// 候補を除外した条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    Tm,
+    Gc,
+    QualityMask,
+    PositionWeighting,
+    /// Forward と Reverse の Tm 差
+    TmDifference,
+    HeteroDimer,
+    ProductSize,
+}
+
+/// 条件ごとの除外数。前の条件で除外された候補は後の条件では数えない
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RejectionCounts {
+    /// 単独のプライマー候補（Forward と Reverse の合計）
+    pub tm: usize,
+    pub gc: usize,
+    pub quality_mask: usize,
+    pub position_weighting: usize,
+    /// プライマーの組み合わせ
+    pub tm_difference: usize,
+    pub hetero_dimer: usize,
+    pub product_size: usize,
+}
+
+impl RejectionCounts {
+    pub fn add(&mut self, reason: RejectionReason) {
+        let count = match reason {
+            RejectionReason::Tm => &mut self.tm,
+            RejectionReason::Gc => &mut self.gc,
+            RejectionReason::QualityMask => &mut self.quality_mask,
+            RejectionReason::PositionWeighting => &mut self.position_weighting,
+            RejectionReason::TmDifference => &mut self.tm_difference,
+            RejectionReason::HeteroDimer => &mut self.hetero_dimer,
+            RejectionReason::ProductSize => &mut self.product_size,
+        };
+        *count += 1;
+    }
+}
+
+/// 除外はしないが、検証で警告の対象になる候補の数（Tm・GC を通過した候補のうち）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlaggedCounts {
+    /// ΔG が max_self_dimer を下回る
+    pub self_dimer: usize,
+    /// ΔG が max_hairpin を下回る
+    pub hairpin: usize,
+    /// 3'末端の規則（AT リッチ・AA/TT 末端）に反する
+    pub three_prime: usize,
+}
+
+/// 1つの条件だけで除外された惜しい候補（単独候補は1本、組み合わせは2本）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearMiss {
+    pub reason: RejectionReason,
+    /// 条件を超えた量（°C・%・kcal/mol・bp のいずれか、reason の単位）
+    pub excess: f32,
+    pub primers: Vec<Primer>,
+}
+
+/// 設計の診断（どの条件を緩めればよいかの手がかり）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesignDiagnostics {
+    /// 評価した単独候補と組み合わせの数
+    pub candidates_evaluated: usize,
+    pub pairs_evaluated: usize,
+    pub rejected: RejectionCounts,
+    pub flagged: FlaggedCounts,
+    /// 条件ごとに超過量の小さい順
+    pub near_misses: Vec<NearMiss>,
+}
+
+/// 緩める設計条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelaxedParameter {
+    /// tm_min を下げ tm_max を上げる
+    TmRange,
+    /// gc_min を下げ gc_max を上げる
+    GcRange,
+    /// length_min を短く length_max を長くする
+    LengthRange,
+    /// max_hetero_dimer を下げる（より強いヘテロダイマーを許す）
+    HeteroDimer,
+    /// search_flank を広げる
+    SearchFlank,
+}
+
+/// 設計条件を1つだけ緩める提案
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelaxationSuggestion {
+    pub parameter: RelaxedParameter,
+    /// 緩めた量（°C・%・nt・kcal/mol・bp のいずれか、parameter の単位。範囲は片側あたり）
+    pub change: f32,
+    /// 緩めた後の設計条件（そのまま設計に渡せる）
+    pub params: PrimerDesignParams,
+    /// この条件で再設計して得られたペア数（最大10組）
+    pub pairs: usize,
+}
+
+/// 条件緩和の提案（suggestions は除外数の多かった条件から順に、検索範囲の拡大は最後）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelaxationPlan {
+    pub min_pairs: usize,
+    /// 元の条件で得られたペア数
+    pub current_pairs: usize,
+    pub suggestions: Vec<RelaxationSuggestion>,
+}
+
+/// 逐次設計の途中経過。前回の通知以降に見つかった検証済みのペア（順位付け前）を運ぶ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrimerDesignProgress {
+    pub pairs: Vec<PrimerPair>,
+    /// これまでに見つかったペアの総数
+    pub pairs_found: usize,
+    /// これまでに評価したフォワード×リバースの組み合わせ数
+    pub pairs_evaluated: usize,
+    /// 評価する組み合わせの総数
+    pub pairs_total: usize,
+}
+
+/// 増幅産物（テンプレート上の0-based半開区間 [template_start, template_end)）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Amplicon {
+    pub template_start: usize,
+    pub template_end: usize,
+    pub sequence: String,
+    /// プライマー結合部位とテンプレートから引き継いだアノテーション（増幅産物上の座標）
+    pub features: Vec<SequenceFeature>,
+}
+
+/// 増幅産物QCの閾値
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmpliconQcParams {
+    /// GC含量を評価するウィンドウ長
+    pub gc_window: usize,
+    pub gc_min: f32,
+    pub gc_max: f32,
+    /// 許容する最長ホモポリマー
+    pub max_homopolymer: usize,
+    /// これより安定（ΔGが小さい）なヘアピンを報告（kcal/mol）
+    pub hairpin_delta_g: f32,
+    /// タンデムリピートが占める割合の上限
+    pub max_repeat_fraction: f32,
+}
+
+impl Default for AmpliconQcParams {
+    fn default() -> Self {
+        Self {
+            gc_window: 50,
+            gc_min: 25.0,
+            gc_max: 75.0,
+            max_homopolymer: 8,
+            hairpin_delta_g: -10.0,
+            max_repeat_fraction: 0.3,
+        }
+    }
+}
+
+/// 増幅産物内のヘアピン（座標は増幅産物上の0-based）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmpliconHairpin {
+    pub position: usize,
+    pub stem_length: usize,
+    pub loop_size: usize,
+    pub delta_g: f32,
+}
+
+/// ホモポリマー（同一塩基の連続）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Homopolymer {
+    pub base: char,
+    pub start: usize,
+    pub length: usize,
+}
+
+/// 増幅産物内のタンデムリピート [start, end)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TandemRepeat {
+    pub start: usize,
+    pub end: usize,
+    pub unit: String,
 }
 
+/// 増幅産物のQC結果（PCR失敗要因となるGC偏り・ホモポリマー・ヘアピン・リピート）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmpliconQc {
+    /// ウィンドウGC含量の最小値・最大値（%）
+    pub gc_min_window: f32,
+    pub gc_max_window: f32,
+    pub longest_homopolymer: Option<Homopolymer>,
+    pub hairpins: Vec<AmpliconHairpin>,
+    pub tandem_repeats: Vec<TandemRepeat>,
+    pub repeat_fraction: f32,
+    pub passed: bool,
+    pub warnings: Vec<Warning>,
+    /// ヘアピンΔGの計算条件
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+}
+
+/// HRMタイリング設計パラメータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HrmTilingParams {
+    /// 増幅産物の最大長（HRMでは100 bp未満が目安）
+    pub max_amplicon_length: usize,
+    pub primer: PrimerDesignParams,
+}
+
+impl Default for HrmTilingParams {
+    fn default() -> Self {
+        Self {
+            max_amplicon_length: 99,
+            primer: PrimerDesignParams::default(),
+        }
+    }
+}
+
+/// バリアントごとの予測融解温度シフト
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantMeltShift {
+    pub variant_id: String,
+    pub position: usize,
+    /// 変異型増幅産物のTm - 野生型増幅産物のTm（計算不能な場合は None）
+    pub delta_tm: Option<f32>,
+}
+
+/// HRM用の短い増幅産物（座標はテンプレート上の0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HrmAmplicon {
+    pub forward: Primer,
+    pub reverse: Primer,
+    pub start: usize,
+    pub end: usize,
+    /// プライマー結合部位を除いた内部領域
+    pub internal_start: usize,
+    pub internal_end: usize,
+    pub sequence: String,
+    pub predicted_tm: Option<f32>,
+    pub variants: Vec<VariantMeltShift>,
+}
+
+/// HRMタイリング結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HrmTilingResult {
+    pub amplicons: Vec<HrmAmplicon>,
+    /// どの増幅産物の内部領域にも配置できなかったバリアントID
+    pub uncovered_variants: Vec<String>,
+    pub params: HrmTilingParams,
+    /// Tm予測に用いたパラメータセットと条件
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+}
+
+/// qPCR（TaqMan）アッセイ設計パラメータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QpcrAssayParams {
+    pub primer: PrimerDesignParams,
+    /// 増幅産物長の範囲（bp）
+    pub amplicon_min: usize,
+    pub amplicon_max: usize,
+    /// Forward と Reverse の Tm 差の上限（°C）
+    pub max_primer_tm_difference: f32,
+    pub probe_length_min: usize,
+    pub probe_length_max: usize,
+    /// プライマーの平均 Tm に対するプローブ Tm の差の範囲（°C）
+    pub probe_tm_offset_min: f32,
+    pub probe_tm_offset_max: f32,
+    pub probe_gc_min: f32,
+    pub probe_gc_max: f32,
+}
+
+impl Default for QpcrAssayParams {
+    fn default() -> Self {
+        Self {
+            primer: PrimerDesignParams {
+                gc_min: 30.0,
+                gc_max: 80.0,
+                ..PrimerDesignParams::default()
+            },
+            amplicon_min: 70,
+            amplicon_max: 150,
+            max_primer_tm_difference: 2.0,
+            probe_length_min: 18,
+            probe_length_max: 30,
+            probe_tm_offset_min: 8.0,
+            probe_tm_offset_max: 10.0,
+            probe_gc_min: 30.0,
+            probe_gc_max: 80.0,
+        }
+    }
+}
+
+/// qPCR アッセイ（プライマー2本と加水分解プローブ、座標はテンプレート上の0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QpcrAssay {
+    pub forward: Primer,
+    pub reverse: Primer,
+    /// 内部プローブ（direction はプローブ配列が一致する鎖。5'末端は G 以外）
+    pub probe: Primer,
+    pub amplicon_start: usize,
+    pub amplicon_end: usize,
+    pub amplicon_sequence: String,
+    /// プローブ Tm − プライマーの平均 Tm（°C）
+    pub probe_tm_offset: f32,
+    /// 3本のオリゴの二次構造・相互作用をまとめた検証結果
+    pub validation_results: ValidationResults,
+    pub params: QpcrAssayParams,
+    /// Tm・ΔGの計算に用いたパラメータセットと条件
+    #[serde(default)]
+    pub provenance: Option<ThermodynamicProvenance>,
+}
+
+/// パネルのストレステスト条件（変異テンプレートへのプライマー結合判定）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelStressParams {
+    /// 結合とみなすプライマー全体の最大ミスマッチ数
+    pub max_mismatches: usize,
+    /// 3'末端から数えてミスマッチを厳しく制限する塩基数
+    pub three_prime_window: usize,
+    /// 3'末端ウィンドウ内で許容するミスマッチ数
+    pub max_three_prime_mismatches: usize,
+    /// 元の増幅産物長に対する産物長の許容倍率
+    pub max_product_ratio: f64,
+    /// 増幅できるテンプレートの割合がこれ未満のペアを脆弱とする
+    pub fragile_threshold: f64,
+}
+
+impl Default for PanelStressParams {
+    fn default() -> Self {
+        Self {
+            max_mismatches: 2,
+            three_prime_window: 5,
+            max_three_prime_mismatches: 0,
+            max_product_ratio: 2.0,
+            fragile_threshold: 0.9,
+        }
+    }
+}
+
+/// プライマーペアごとのストレステスト結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairStressResult {
+    pub pair_id: String,
+    pub templates_tested: usize,
+    pub templates_amplified: usize,
+    /// 増幅できたテンプレートの割合（0.0〜1.0）
+    pub amplified_fraction: f64,
+    pub fragile: bool,
+    /// 増幅できなかったテンプレートの配列ID
+    pub failed_templates: Vec<String>,
+}
+
+/// プライマーパネルのストレステストレポート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanelStressReport {
+    /// 評価に用いた変異テンプレートの配列ID
+    pub templates: Vec<String>,
+    pub pairs: Vec<PairStressResult>,
+    /// 脆弱なペアのID（増幅割合の低い順）
+    pub fragile_pairs: Vec<String>,
+    pub params: PanelStressParams,
+}
+
+/// 連結部をまたぐ検証PCR（コロニーPCR）の設計パラメータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JunctionPrimerParams {
+    /// 増幅産物の最大長
+    pub max_amplicon_length: usize,
+    pub primer: PrimerDesignParams,
+}
+
+impl Default for JunctionPrimerParams {
+    fn default() -> Self {
+        Self {
+            max_amplicon_length: 800,
+            primer: PrimerDesignParams::default(),
+        }
+    }
+}
+
+/// 1か所の連結部の検証PCR（フォワードは連結部の上流、リバースは下流に結合する）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JunctionPcr {
+    /// 連結部の位置（この塩基の直前で2つの部品がつながる）
+    pub junction: usize,
+    /// 連結する部品（例: "vector | insert"）
+    pub label: String,
+    /// 条件を満たすペアが見つからなければ None
+    pub pair: Option<PrimerPair>,
+}
+
+/// コンストラクトの全連結部の検証プライマーセット
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JunctionVerificationSet {
+    pub seq_id: String,
+    pub junctions: Vec<JunctionPcr>,
+    pub params: JunctionPrimerParams,
+    pub warnings: Vec<String>,
+}
+
+/// 読み込み済み配列上のプライマー結合候補部位（0-based半開区間、環状配列で原点をまたぐ
+/// 場合は end が配列長を超える）。strand はプライマー配列が一致する鎖
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrimerBindingSite {
+    pub seq_id: String,
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    pub mismatches: usize,
+    /// 3'末端側 `THREE_PRIME_WINDOW` 塩基内のミスマッチ数
+    pub three_prime_mismatches: usize,
+    /// 3'末端の塩基がミスマッチ（伸長がほぼ起こらない）
+    pub terminal_mismatch: bool,
+    /// 結合部位の塩基配列（プライマーの向き、5'→3'）
+    pub matched: String,
+}
+
+/// プライマー/鋳型二本鎖の1塩基対
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DuplexPair {
+    /// プライマー上の位置（5'末端から 0-based）
+    pub index: usize,
+    pub primer_base: char,
+    /// 対合する鋳型鎖の塩基
+    pub template_base: char,
+    pub mismatch: bool,
+    /// この塩基対と 3' 側の隣の塩基対のスタッキング ΔG（kcal/mol）。
+    /// 3'末端の塩基対と、パラメータのない組では None
+    pub stack_delta_g: Option<f32>,
+}
+
+/// 結合部位でのプライマーと鋳型のアラインメント
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrimerDuplex {
+    pub site: PrimerBindingSite,
+    /// 表示用の3行：プライマー（5'→3'）、対合記号（'|' が一致、空白がミスマッチ）、鋳型鎖（3'→5'）
+    pub primer_line: String,
+    pub match_line: String,
+    pub template_line: String,
+    pub pairs: Vec<DuplexPair>,
+    /// ミスマッチのプライマー上の位置
+    pub mismatch_positions: Vec<usize>,
+    /// 末端効果とスタッキングの合計 ΔG（kcal/mol、パラメータのない組は 0 とみなす）
+    pub delta_g: f32,
+    pub provenance: ThermodynamicProvenance,
+}
+
+/// 3'末端のミスマッチとして数える範囲（塩基数）
+pub const THREE_PRIME_WINDOW: usize = 5;
+
 /// プライマー設計サービストレイト
 pub trait PrimerDesignService {
     type Error: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static;
 
-    /// 指定範囲でプライマーペアを設計
+    /// 標的領域 [start, end)（0-based・半開区間）を挟むプライマーペアを設計
     fn design_primers(
         &self,
         sequence: &str,
@@ -166,13 +767,14 @@ pub trait PrimerDesignService {
         &self,
         pair1: &PrimerPair,
         pair2: &PrimerPair,
-        warnings: &mut Vec<String>,
+        warnings: &mut Vec<Warning>,
     ) -> f32;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::warning::WarningCode;
 
     #[test]
     fn test_primer_design_params_default() {
@@ -191,7 +793,13 @@ mod tests {
         validation.hairpin_check = true;
         assert!(validation.is_valid());
 
-        validation.warnings.push("Warning message".to_string());
+        validation.warnings.push(
+            WarningCode::Text {
+                text: "Warning message".to_string(),
+            }
+            .into(),
+        );
         assert!(!validation.is_valid());
+        assert_eq!(validation.max_severity(), Some(WarningSeverity::Warning));
     }
 }
@@ -0,0 +1,179 @@
+use super::warning::Warning;
+use serde::{Deserialize, Serialize};
+
+/// 装置の検出チャネル（励起・蛍光フィルタの通過域、nm）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstrumentChannel {
+    pub name: String,
+    pub excitation_min: f32,
+    pub excitation_max: f32,
+    pub emission_min: f32,
+    pub emission_max: f32,
+}
+
+impl InstrumentChannel {
+    fn new(name: &str, excitation: (f32, f32), emission: (f32, f32)) -> Self {
+        Self {
+            name: name.to_string(),
+            excitation_min: excitation.0,
+            excitation_max: excitation.1,
+            emission_min: emission.0,
+            emission_max: emission.1,
+        }
+    }
+
+    /// 励起・蛍光の極大波長がどちらも通過域に入る色素を検出できる
+    pub fn detects(&self, dye: &Fluorophore) -> bool {
+        (self.excitation_min..=self.excitation_max).contains(&dye.excitation_nm)
+            && (self.emission_min..=self.emission_max).contains(&dye.emission_nm)
+    }
+
+    pub fn detects_emission(&self, emission_nm: f32) -> bool {
+        (self.emission_min..=self.emission_max).contains(&emission_nm)
+    }
+}
+
+/// 一般的な5チャネルのリアルタイム PCR 装置
+pub fn default_channels() -> Vec<InstrumentChannel> {
+    vec![
+        InstrumentChannel::new("FAM", (470.0, 500.0), (510.0, 535.0)),
+        InstrumentChannel::new("HEX", (515.0, 545.0), (545.0, 570.0)),
+        InstrumentChannel::new("ROX", (570.0, 600.0), (600.0, 625.0)),
+        InstrumentChannel::new("Cy5", (630.0, 660.0), (660.0, 685.0)),
+        InstrumentChannel::new("Cy5.5", (665.0, 695.0), (690.0, 720.0)),
+    ]
+}
+
+/// 5'末端に付ける蛍光色素（極大波長 nm）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fluorophore {
+    pub name: String,
+    pub excitation_nm: f32,
+    pub emission_nm: f32,
+}
+
+/// 組み込みの蛍光色素（励起波長順）
+pub fn builtin_fluorophores() -> Vec<Fluorophore> {
+    [
+        ("FAM", 495.0, 520.0),
+        ("TET", 521.0, 536.0),
+        ("JOE", 520.0, 548.0),
+        ("HEX", 535.0, 556.0),
+        ("VIC", 538.0, 554.0),
+        ("Cy3", 550.0, 570.0),
+        ("TAMRA", 557.0, 583.0),
+        ("ROX", 588.0, 608.0),
+        ("Texas Red", 596.0, 615.0),
+        ("Quasar 670", 647.0, 670.0),
+        ("Cy5", 649.0, 670.0),
+        ("Cy5.5", 675.0, 694.0),
+        ("Quasar 705", 690.0, 705.0),
+    ]
+    .into_iter()
+    .map(|(name, excitation_nm, emission_nm)| Fluorophore {
+        name: name.to_string(),
+        excitation_nm,
+        emission_nm,
+    })
+    .collect()
+}
+
+/// 3'末端（二重消光では内部にも）に付けるクエンチャー
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quencher {
+    pub name: String,
+    /// 消光できる色素の蛍光波長の範囲（nm）
+    pub absorption_min: f32,
+    pub absorption_max: f32,
+    /// 蛍光性クエンチャー（TAMRA）自身の蛍光極大（ダーククエンチャーは None）
+    pub emission_nm: Option<f32>,
+    /// 9塩基目付近の内部クエンチャーを併用する二重消光
+    pub double_quenched: bool,
+}
+
+impl Quencher {
+    pub fn quenches(&self, dye: &Fluorophore) -> bool {
+        (self.absorption_min..=self.absorption_max).contains(&dye.emission_nm)
+    }
+}
+
+/// 組み込みのクエンチャー
+pub fn builtin_quenchers() -> Vec<Quencher> {
+    [
+        ("BHQ-1", 480.0, 580.0, None, false),
+        ("BHQ-2", 560.0, 670.0, None, false),
+        ("BHQ-3", 620.0, 730.0, None, false),
+        ("Iowa Black FQ", 420.0, 620.0, None, false),
+        ("Iowa Black RQ", 500.0, 700.0, None, false),
+        ("ZEN/Iowa Black FQ", 420.0, 620.0, None, true),
+        ("TAO/Iowa Black RQ", 500.0, 700.0, None, true),
+        ("TAMRA", 500.0, 560.0, Some(583.0), false),
+    ]
+    .into_iter()
+    .map(
+        |(name, absorption_min, absorption_max, emission_nm, double_quenched)| Quencher {
+            name: name.to_string(),
+            absorption_min,
+            absorption_max,
+            emission_nm,
+            double_quenched,
+        },
+    )
+    .collect()
+}
+
+/// 標識を選ぶプローブ（fluorophore を指定すると、その色素のまま検証する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeLabelInput {
+    pub name: String,
+    pub sequence: String,
+    #[serde(default)]
+    pub fluorophore: Option<String>,
+}
+
+/// 標識選択の条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProbeLabelParams {
+    /// 装置の検出チャネル（既定は一般的な5チャネル）
+    pub channels: Vec<InstrumentChannel>,
+    /// この長さ（nt）以上のプローブには二重消光を優先して勧める
+    pub double_quench_length: usize,
+}
+
+impl Default for ProbeLabelParams {
+    fn default() -> Self {
+        Self {
+            channels: default_channels(),
+            double_quench_length: 25,
+        }
+    }
+}
+
+/// 色素とクエンチャーの組み合わせ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelOption {
+    pub fluorophore: String,
+    pub quencher: String,
+    pub double_quenched: bool,
+}
+
+/// プローブ1本分の提案（options は推奨順）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeLabelSuggestion {
+    pub probe: String,
+    /// 何番目の反応か（1-based、multiplex 本ずつ入力順にまとめる）
+    pub reaction: usize,
+    /// 割り当てたチャネル（空きチャネルがない・色素を検出できない場合は None）
+    pub channel: Option<String>,
+    pub options: Vec<LabelOption>,
+}
+
+/// マルチプレックスの標識計画
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeLabelPlan {
+    pub multiplex: usize,
+    pub suggestions: Vec<ProbeLabelSuggestion>,
+    pub channels: Vec<InstrumentChannel>,
+    pub warnings: Vec<Warning>,
+}
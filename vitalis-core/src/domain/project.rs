@@ -0,0 +1,199 @@
+use super::feature::SequenceFeature;
+use super::primer::PrimerPair;
+use super::reproducibility::SessionProvenance;
+use super::warning::WarningPolicy;
+use super::{SequenceMetadata, SequenceOrigin, Topology};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// プロジェクトアーカイブに含まれるファイルの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArchiveFileKind {
+    /// アノテーション付き GenBank
+    GenBank,
+    /// プライマー一覧（CSV）
+    PrimerList,
+    /// 解析レポート（HTML）
+    Report,
+}
+
+/// アーカイブ内のファイル（path はアーカイブ内の相対パス）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveFile {
+    pub path: String,
+    pub kind: ArchiveFileKind,
+    pub bytes: usize,
+}
+
+/// アーカイブに収めた配列
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedSequence {
+    pub seq_id: String,
+    pub name: String,
+    pub length: usize,
+    pub topology: Topology,
+    pub feature_count: usize,
+    /// GenBank ファイルのパス
+    pub genbank: String,
+    /// レポートのパス
+    pub report: String,
+    pub derived_from: Option<SequenceOrigin>,
+}
+
+/// アーカイブ直下の manifest.json に書き出すセッションの目録
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub sequences: Vec<ArchivedSequence>,
+    pub primer_pair_count: usize,
+    /// manifest.json 自身を除くファイル
+    pub files: Vec<ArchiveFile>,
+    /// 書き出したセッションの由来情報（再現性モードのシードなど）
+    #[serde(default)]
+    pub provenance: Option<SessionProvenance>,
+}
+
+impl ProjectManifest {
+    pub const FORMAT_VERSION: u32 = 1;
+    pub const PATH: &'static str = "manifest.json";
+}
+
+/// プロジェクトファイルに保存する配列（アノテーションと設計済みプライマーを含む）。
+/// ファイルから取り込んだ配列は `file` に位置だけを記録し、`sequence` は空にする
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSequence {
+    pub seq_id: String,
+    pub metadata: SequenceMetadata,
+    pub sequence: String,
+    /// FASTQ 由来の配列の品質文字列（Phred+33）
+    pub quality: Option<String>,
+    #[serde(default)]
+    pub file: Option<FileRecord>,
+    pub features: Vec<SequenceFeature>,
+    pub primer_pairs: Vec<PrimerPair>,
+}
+
+/// 取り込み元ファイル上の配列レコードの位置。開くときはファイルを読み直さずにここから参照する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileRecord {
+    pub path: PathBuf,
+    /// 配列データの開始バイト位置（ヘッダー行の次の行頭）
+    pub start: u64,
+    pub length: usize,
+    /// 行の長さが揃っていれば .fai と同じ行の配置。不揃いなら None で、開くときに
+    /// レコードの行を数え直す
+    pub lines: Option<LineSpacing>,
+    /// FASTQ の品質行の開始バイト位置
+    pub quality_start: Option<u64>,
+}
+
+/// 長さの揃った配列行の配置（最後の行だけ短くてよい）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineSpacing {
+    /// 先頭行の最初の塩基のバイト位置
+    pub first: u64,
+    /// 行の間隔（改行を含むバイト数）
+    pub byte_step: u64,
+    /// 1行の塩基数
+    pub base_step: usize,
+    pub lines: usize,
+}
+
+/// プロジェクトファイルに保存するセッションの状態
+#[derive(Debug, Clone, Default)]
+pub struct ProjectSnapshot {
+    pub sequences: Vec<ProjectSequence>,
+    /// 次に採番する配列IDの番号（開き直しても ID が重複しないように保存する）
+    pub next_id: usize,
+}
+
+impl ProjectSnapshot {
+    /// 配列の取り込み元ファイル（重複なし、最初に現れた順）
+    pub fn source_files(&self) -> Vec<&PathBuf> {
+        let mut files: Vec<&PathBuf> = Vec::new();
+        for path in self
+            .sequences
+            .iter()
+            .filter_map(|entry| entry.metadata.file_path.as_ref())
+        {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        files
+    }
+}
+
+/// プロジェクトファイルの形式（開くときはファイルの先頭から判定する）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectFormat {
+    /// SQLite（1行1件の JSON）
+    #[default]
+    Sqlite,
+    /// MessagePack を zstd で圧縮した1ファイル。アノテーションやプライマーの多い
+    /// セッションを小さく速く読み書きする
+    Compressed,
+}
+
+/// 現在のプロジェクトファイルと自動保存の設定
+#[derive(Debug, Clone, Default)]
+pub struct ProjectSession {
+    pub path: Option<PathBuf>,
+    /// 自動保存もこの形式で書く
+    pub format: ProjectFormat,
+    pub autosave: bool,
+    /// 設計結果を保存させない警告の重大度
+    pub warning_policy: WarningPolicy,
+    /// 最後に保存した（または開いた）ときのセッションの変更番号。変わっていなければ
+    /// 自動保存は書き込まない
+    pub saved_revision: Option<u64>,
+}
+
+/// 保存・読み込みしたプロジェクトの概要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    pub path: String,
+    pub saved_at: DateTime<Utc>,
+    pub sequence_count: usize,
+    pub feature_count: usize,
+    pub primer_pair_count: usize,
+    pub autosave: bool,
+    #[serde(default)]
+    pub format: ProjectFormat,
+    /// 開いたプロジェクトの配列が参照する取り込み元ファイルのうち、見つからないもの
+    #[serde(default)]
+    pub missing_sources: Vec<String>,
+    /// 取り込み元ファイルがないか、保存したレコードと合わなくなったため読み込めなかった配列。
+    /// プロジェクトファイルには残し、次に保存するときもそのまま書く
+    #[serde(default)]
+    pub unavailable_sequences: Vec<String>,
+}
+
+impl ProjectSummary {
+    pub fn new(
+        snapshot: &ProjectSnapshot,
+        path: String,
+        saved_at: DateTime<Utc>,
+        format: ProjectFormat,
+        autosave: bool,
+    ) -> Self {
+        Self {
+            path,
+            saved_at,
+            format,
+            missing_sources: Vec::new(),
+            unavailable_sequences: Vec::new(),
+            sequence_count: snapshot.sequences.len(),
+            feature_count: snapshot.sequences.iter().map(|s| s.features.len()).sum(),
+            primer_pair_count: snapshot
+                .sequences
+                .iter()
+                .map(|s| s.primer_pairs.len())
+                .sum(),
+            autosave,
+        }
+    }
+}
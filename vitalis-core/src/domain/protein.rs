@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+
+/// アミノ酸の3文字表記（終止は "Ter"、不明は "Xaa"）
+pub fn three_letter_code(residue: char) -> &'static str {
+    match residue.to_ascii_uppercase() {
+        'A' => "Ala",
+        'R' => "Arg",
+        'N' => "Asn",
+        'D' => "Asp",
+        'C' => "Cys",
+        'Q' => "Gln",
+        'E' => "Glu",
+        'G' => "Gly",
+        'H' => "His",
+        'I' => "Ile",
+        'L' => "Leu",
+        'K' => "Lys",
+        'M' => "Met",
+        'F' => "Phe",
+        'P' => "Pro",
+        'S' => "Ser",
+        'T' => "Thr",
+        'W' => "Trp",
+        'Y' => "Tyr",
+        'V' => "Val",
+        'U' => "Sec",
+        '*' => "Ter",
+        _ => "Xaa",
+    }
+}
+
+/// Kyte-Doolittle ハイドロパシー指標
+pub fn kyte_doolittle(residue: char) -> Option<f64> {
+    let value = match residue.to_ascii_uppercase() {
+        'I' => 4.5,
+        'V' => 4.2,
+        'L' => 3.8,
+        'F' => 2.8,
+        'C' => 2.5,
+        'M' => 1.9,
+        'A' => 1.8,
+        'G' => -0.4,
+        'T' => -0.7,
+        'S' => -0.8,
+        'W' => -0.9,
+        'Y' => -1.3,
+        'P' => -1.6,
+        'H' => -3.2,
+        'E' | 'Q' | 'D' | 'N' => -3.5,
+        'K' => -3.9,
+        'R' => -4.5,
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// 水分子の平均質量（g/mol）。ペプチド鎖の両末端の H と OH
+pub const WATER_MASS: f64 = 18.01524;
+
+/// アミノ酸残基の平均質量（g/mol、ペプチド結合で失われる水を除いた値）
+pub fn residue_mass(residue: char) -> Option<f64> {
+    let mass = match residue.to_ascii_uppercase() {
+        'A' => 71.0788,
+        'R' => 156.1875,
+        'N' => 114.1038,
+        'D' => 115.0886,
+        'C' => 103.1388,
+        'E' => 129.1155,
+        'Q' => 128.1307,
+        'G' => 57.0519,
+        'H' => 137.1411,
+        'I' | 'L' => 113.1594,
+        'K' => 128.1741,
+        'M' => 131.1926,
+        'F' => 147.1766,
+        'P' => 97.1167,
+        'S' => 87.0782,
+        'T' => 101.1051,
+        'W' => 186.2132,
+        'Y' => 163.1760,
+        'V' => 99.1326,
+        'U' => 150.0388,
+        'O' => 237.3018,
+        _ => return None,
+    };
+    Some(mass)
+}
+
+/// ハイドロパシープロファイルの1点（position はウィンドウ中心の残基番号、0-based）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HydropathyPoint {
+    pub position: usize,
+    pub score: f64,
+}
+
+/// 推定膜貫通セグメント（残基の0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransmembraneSegment {
+    pub start: usize,
+    pub end: usize,
+    pub mean_score: f64,
+}
+
+/// ハイドロパシー解析結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HydropathyProfile {
+    pub window: usize,
+    pub points: Vec<HydropathyPoint>,
+    pub tm_segments: Vec<TransmembraneSegment>,
+}
+
+/// シグナルペプチド予測（残基番号は0-based、cleavage_site は成熟タンパク質の先頭残基）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignalPeptide {
+    /// 正電荷を持つN領域の終端
+    pub n_region_end: usize,
+    /// 疎水性H領域 [h_region_start, h_region_end)
+    pub h_region_start: usize,
+    pub h_region_end: usize,
+    pub cleavage_site: usize,
+    /// 切断部位の -3〜-1 残基（A-X-A など）
+    pub cleavage_motif: String,
+    pub n_region_charge: i32,
+    pub h_region_hydropathy: f64,
+    /// ORFの翻訳産物から予測した場合の、シグナル配列に対応する塩基配列上の区間と鎖
+    pub dna_start: Option<usize>,
+    pub dna_end: Option<usize>,
+    pub strand: Option<super::Strand>,
+}
+
+/// 翻訳産物の由来
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProteinSource {
+    /// アノテーションされた CDS
+    AnnotatedCds,
+    /// CDS と重ならない ORF
+    PredictedOrf,
+}
+
+/// プロテオームの1エントリ（座標は順鎖上の0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProteinRecord {
+    pub id: String,
+    pub source: ProteinSource,
+    pub start: usize,
+    pub end: usize,
+    pub strand: super::Strand,
+    /// CDS の /product（ORF では None）
+    pub product: Option<String>,
+    /// 終止コドンを除いたアミノ酸配列
+    pub protein: String,
+}
+
+/// プロテオーム FASTA の書き出し結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProteomeExport {
+    pub path: String,
+    pub annotated_cds: usize,
+    pub predicted_orfs: usize,
+    pub records: Vec<ProteinRecord>,
+}
@@ -0,0 +1,79 @@
+use super::Strand;
+use serde::{Deserialize, Serialize};
+
+/// PROSITE形式のタンパク質モチーフ（例: "C-x(2,4)-C-x(3)-[LIVMFYWC]-x(8)-H-x(3,5)-H"）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProteinMotif {
+    pub name: String,
+    pub pattern: String,
+    pub description: String,
+}
+
+impl ProteinMotif {
+    pub fn new(name: &str, pattern: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            description: description.to_string(),
+        }
+    }
+}
+
+/// モチーフの検出結果（start/end はタンパク質上の0-based半開区間）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MotifHit {
+    pub motif: String,
+    pub start: usize,
+    pub end: usize,
+    pub matched: String,
+    /// ORFの翻訳産物から検出した場合の塩基配列上の区間と鎖
+    pub dna_start: Option<usize>,
+    pub dna_end: Option<usize>,
+    pub strand: Option<Strand>,
+}
+
+/// 組み込みモチーフ（精製タグ・局在シグナル・代表的なPROSITEパターン）
+pub fn bundled_motifs() -> Vec<ProteinMotif> {
+    vec![
+        ProteinMotif::new("His-tag", "H(6)", "Polyhistidine affinity tag"),
+        ProteinMotif::new("FLAG", "D-Y-K-D-D-D-D-K", "FLAG epitope tag"),
+        ProteinMotif::new(
+            "HA",
+            "Y-P-Y-D-V-P-D-Y-A",
+            "Influenza hemagglutinin epitope tag",
+        ),
+        ProteinMotif::new("Myc", "E-Q-K-L-I-S-E-E-D-L", "c-Myc epitope tag"),
+        ProteinMotif::new("V5", "G-K-P-I-P-N-P-L-L-G-L-D-S-T", "V5 epitope tag"),
+        ProteinMotif::new(
+            "Strep-tag II",
+            "W-S-H-P-Q-F-E-K",
+            "Strep-Tactin binding tag",
+        ),
+        ProteinMotif::new(
+            "SV40 NLS",
+            "P-K-K-K-R-K-V",
+            "Monopartite nuclear localization signal",
+        ),
+        ProteinMotif::new(
+            "Bipartite NLS",
+            "[KR](2)-x(10,12)-[KR](3)",
+            "Bipartite nuclear localization signal",
+        ),
+        ProteinMotif::new("KDEL", "[KH]-D-E-L>", "ER retention signal"),
+        ProteinMotif::new(
+            "N-glycosylation",
+            "N-{P}-[ST]-{P}",
+            "PS00001 N-glycosylation site",
+        ),
+        ProteinMotif::new(
+            "P-loop",
+            "[AG]-x(4)-G-K-[ST]",
+            "PS00017 ATP/GTP-binding site motif A",
+        ),
+        ProteinMotif::new(
+            "Zinc finger C2H2",
+            "C-x(2,4)-C-x(3)-[LIVMFYWC]-x(8)-H-x(3,5)-H",
+            "PS00028 Zinc finger C2H2 type domain signature",
+        ),
+    ]
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// 16S rRNA 3'末端（3'-AUUCCUCCA-5'）に相補的な Shine-Dalgarno 配列（5'→3'）
+pub const SD_CONSENSUS: &str = "TAAGGAGGT";
+
+/// SD 配列の3'末端から開始コドンまでの最適な間隔（nt）
+pub const OPTIMAL_SPACING: usize = 7;
+
+/// 細菌の開始コドン
+pub const BACTERIAL_START_CODONS: [&str; 3] = ["ATG", "GTG", "TTG"];
+
+/// RBS 強度推定の条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RbsParams {
+    /// 開始コドンの上流で SD 配列を探す長さ（nt）
+    pub upstream: usize,
+    /// SD 配列とみなす16S rRNA との連続相補の最短長
+    pub min_sd_length: usize,
+    /// 間隔が最適値から1 nt ずれるごとの ΔG の罰則（kcal/mol、ずれの2乗に掛ける）
+    pub spacing_penalty: f64,
+    /// 相対強度がこれ未満の RBS を弱いとみなす
+    pub weak_threshold: f64,
+}
+
+impl Default for RbsParams {
+    fn default() -> Self {
+        Self {
+            upstream: 20,
+            min_sd_length: 4,
+            spacing_penalty: 0.3,
+            weak_threshold: 0.1,
+        }
+    }
+}
+
+/// 開始コドン上流の RBS の推定強度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RbsPrediction {
+    /// 開始コドンの位置（0-based）
+    pub start_codon: usize,
+    pub start_codon_sequence: String,
+    /// 最も強く結合する SD 様配列と位置（見つからなければ None）
+    pub sd_sequence: Option<String>,
+    pub sd_position: Option<usize>,
+    /// SD 配列と16S rRNA の結合 ΔG（kcal/mol、37°C）
+    pub sd_delta_g: f64,
+    /// SD 配列の3'末端から開始コドンまでの塩基数
+    pub spacing: Option<usize>,
+    /// 間隔による ΔG の罰則（kcal/mol）
+    pub spacing_delta_g: f64,
+    /// コンセンサス SD を最適間隔に置いた RBS を1とする相対強度
+    pub relative_strength: f64,
+    pub weak: bool,
+}
@@ -0,0 +1,74 @@
+use super::read_stats::HistogramBin;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// FASTQ の品質文字の基準（Phred+33）
+pub const PHRED_OFFSET: u8 = 33;
+
+/// 1つのファイルから取り込んだリードの集まり（seq_id はファイル順）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadSet {
+    pub readset_id: String,
+    pub seq_ids: Vec<String>,
+    /// 取り込み元のファイル（テキストから登録した場合は None）
+    pub file_path: Option<PathBuf>,
+}
+
+impl ReadSet {
+    pub fn len(&self) -> usize {
+        self.seq_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seq_ids.is_empty()
+    }
+
+    /// `offset` 番目から最大 `count` 本の seq_id（範囲外なら空）
+    pub fn page(&self, offset: usize, count: usize) -> &[String] {
+        let start = offset.min(self.seq_ids.len());
+        let end = start.saturating_add(count).min(self.seq_ids.len());
+        &self.seq_ids[start..end]
+    }
+}
+
+/// リード1本（品質は FASTQ 由来のときのみ、Phred+33 の文字列）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Read {
+    pub seq_id: String,
+    pub id: String,
+    pub name: String,
+    pub sequence: String,
+    pub quality: Option<String>,
+}
+
+/// `get_reads` の1ページ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadPage {
+    pub readset_id: String,
+    /// リードセット全体のリード数
+    pub total: usize,
+    pub offset: usize,
+    pub reads: Vec<Read>,
+}
+
+/// リードごとの長さと品質の分布
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadQualityStats {
+    pub readset_id: String,
+    pub read_count: usize,
+    /// 品質を持つリードの数（品質の統計の分母）
+    pub reads_with_quality: usize,
+    pub total_bases: usize,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub mean_length: f64,
+    /// リード長（bp）
+    pub length: Vec<HistogramBin>,
+    /// リードごとの平均品質（Phred、幅1の区間）
+    pub mean_quality: Vec<HistogramBin>,
+    /// リード上の位置ごとの平均品質（その位置まで届いたリードの平均）
+    pub per_position_quality: Vec<f64>,
+    /// 品質が Q20 / Q30 以上の塩基の割合（%）
+    pub q20_percent: f64,
+    pub q30_percent: f64,
+}
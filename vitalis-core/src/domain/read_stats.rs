@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// リードセット統計のパラメータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadSetParams {
+    /// 重複判定に使う先頭の塩基数（長いリードの末端のエラーで重複を見逃さないよう切り詰める）
+    pub dedup_length: usize,
+    /// 希薄化曲線の点数（総リード数を等分したサブサンプル）
+    pub rarefaction_points: usize,
+    /// 過剰出現とみなすリードの割合（%）
+    pub overrepresented_percent: f64,
+    pub max_overrepresented: usize,
+    pub kmer_size: usize,
+    /// 報告する k-mer の観測/期待比の下限
+    pub min_kmer_enrichment: f64,
+    pub max_kmers: usize,
+    /// サブサンプルの乱数シード（指定しなければワークスペースの乱数源から取る）
+    pub seed: Option<u64>,
+}
+
+impl Default for ReadSetParams {
+    fn default() -> Self {
+        Self {
+            dedup_length: 50,
+            rarefaction_points: 10,
+            overrepresented_percent: 0.1,
+            max_overrepresented: 20,
+            kmer_size: 7,
+            min_kmer_enrichment: 3.0,
+            max_kmers: 20,
+            seed: None,
+        }
+    }
+}
+
+/// 重複レベルのヒストグラムの1区間（FastQC と同じ 1..9, 10+, 50+, 100+, 500+, 1k+, 5k+, 10k+）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DuplicationBin {
+    /// 区間のコピー数の範囲（max_copies が None なら上限なし）
+    pub min_copies: usize,
+    pub max_copies: Option<usize>,
+    /// この区間に入る異なる配列の数
+    pub distinct_sequences: usize,
+    pub reads: usize,
+    /// 全リードに対する割合（%）
+    pub percent_of_reads: f64,
+}
+
+/// 希薄化曲線の1点
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RarefactionPoint {
+    pub reads: usize,
+    pub distinct_sequences: usize,
+}
+
+/// 過剰出現しているリード配列
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OverrepresentedSequence {
+    pub sequence: String,
+    pub count: usize,
+    pub percent: f64,
+    /// 一致した既知の混入シグネチャ（アダプター・ベクター）
+    pub possible_source: Option<String>,
+}
+
+/// 期待より多く出現する k-mer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnrichedKmer {
+    pub kmer: String,
+    pub count: usize,
+    /// 塩基組成から期待される出現数に対する比
+    pub observed_over_expected: f64,
+    /// 最も多く出現するリード上の位置（0-based）
+    pub peak_position: usize,
+}
+
+/// リードセットのライブラリ複雑度の統計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadSetStats {
+    pub read_count: usize,
+    pub distinct_sequences: usize,
+    /// 重複除去後に残るリードの割合（%）
+    pub percent_remaining_if_deduplicated: f64,
+    pub duplication_levels: Vec<DuplicationBin>,
+    /// サブサンプルのリード数と異なる配列の数（最後の点は全リード）
+    pub rarefaction: Vec<RarefactionPoint>,
+    /// 出現数の多い順
+    pub overrepresented: Vec<OverrepresentedSequence>,
+    /// 観測/期待比の高い順
+    pub enriched_kmers: Vec<EnrichedKmer>,
+}
+
+/// ヒストグラムの1区間 [start, end)（最後の区間は end を含む）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct HistogramBin {
+    pub start: f64,
+    pub end: f64,
+    pub count: usize,
+}
+
+/// リードごとの GC 含量と長さの分布
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadSetDistributions {
+    pub readset_id: String,
+    pub read_count: usize,
+    /// GC 含量（%、N などを除いた塩基に対する割合）。塩基のないリードは数えない
+    pub gc_content: Vec<HistogramBin>,
+    /// リード長（bp）
+    pub length: Vec<HistogramBin>,
+    pub mean_gc: f64,
+    pub mean_length: f64,
+}
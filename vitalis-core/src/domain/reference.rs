@@ -0,0 +1,154 @@
+use super::primer::PrimerDesignResult;
+use super::region::{Region, RegionError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 参照ゲノム中の1本の配列（染色体・コンティグ）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceContig {
+    pub name: String,
+    pub length: usize,
+}
+
+/// 登録済みのローカル参照ゲノム（FASTA と samtools faidx 形式の .fai の組）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceGenome {
+    pub id: String,
+    pub name: String,
+    /// 生物種（例: "Homo sapiens"）
+    pub organism: Option<String>,
+    /// アセンブリ名（例: "GRCh38"）
+    pub assembly: Option<String>,
+    pub fasta_path: PathBuf,
+    pub fai_path: PathBuf,
+    pub contigs: Vec<ReferenceContig>,
+    pub registered_at: DateTime<Utc>,
+}
+
+impl ReferenceGenome {
+    pub fn contig(&self, name: &str) -> Option<&ReferenceContig> {
+        self.contigs.iter().find(|contig| contig.name == name)
+    }
+}
+
+/// 参照ゲノム上の領域。ゲノムブラウザと同じ "chr7:55,019,017-55,019,400"
+/// （1-based・両端を含む、桁区切りのカンマは任意）の表記から読む
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceLocus {
+    pub contig: String,
+    pub region: Region,
+}
+
+impl ReferenceLocus {
+    pub fn parse(locus: &str) -> Result<Self, RegionError> {
+        let invalid = || RegionError::InvalidLocus(locus.to_string());
+        // コンティグ名に ':' を含むもの（HLA のアレルなど）があるため最後の ':' で分ける
+        let (contig, range) = locus.trim().rsplit_once(':').ok_or_else(invalid)?;
+        let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+        let position = |text: &str| text.trim().replace(',', "").parse::<usize>();
+        let (start, end) = (
+            position(start).map_err(|_| invalid())?,
+            position(end).map_err(|_| invalid())?,
+        );
+        if contig.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            contig: contig.to_string(),
+            region: Region::from_one_based(start, end)?,
+        })
+    }
+}
+
+/// 参照座標の配列の取得元
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReferenceSource {
+    /// 登録済みのローカル参照ゲノム（座標のコンティグ名は .fai の名前）
+    Local { reference_id: String },
+    /// NCBI nuccore（座標のコンティグ名はアクセッション、例: "NC_000007.14"）
+    Entrez,
+}
+
+/// NCBI Entrez E-utilities への接続設定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntrezSettings {
+    /// E-utilities の URL（efetch.fcgi の置かれた場所）
+    pub base_url: String,
+    /// NCBI の API キー。あれば1秒に10件、なければ3件まで要求する
+    pub api_key: Option<String>,
+    /// 1回の要求の待ち時間の上限（秒）
+    pub timeout_secs: u64,
+}
+
+impl Default for EntrezSettings {
+    fn default() -> Self {
+        Self {
+            base_url: "https://eutils.ncbi.nlm.nih.gov/entrez/eutils".to_string(),
+            api_key: None,
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// 参照ゲノム座標で指定した標的のプライマー設計。標的の前後のプライマー探索範囲ごと
+/// 取り込んだ断片で設計し、位置はコンティグ上の座標に直してある
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferencePrimerDesign {
+    pub source: ReferenceSource,
+    pub locus: ReferenceLocus,
+    /// 取り込んだ断片の配列ID
+    pub seq_id: String,
+    /// 断片の開始位置（断片上の位置 + offset がコンティグ上の位置）
+    pub offset: usize,
+    /// 標的・プライマーの位置はコンティグ上の0-based座標
+    pub result: PrimerDesignResult,
+}
+
+/// 参照ゲノムの登録要求（fai_path 省略時は `<fasta_path>.fai` を使う）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterReferenceRequest {
+    pub name: String,
+    pub fasta_path: String,
+    #[serde(default)]
+    pub fai_path: Option<String>,
+    #[serde(default)]
+    pub organism: Option<String>,
+    #[serde(default)]
+    pub assembly: Option<String>,
+}
+
+/// 設定ファイルに保存する参照ゲノムの一覧
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceSettings {
+    #[serde(default)]
+    pub references: Vec<ReferenceGenome>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locus() {
+        let locus = ReferenceLocus::parse("chr7:55,019,017-55,019,400").unwrap();
+        assert_eq!(locus.contig, "chr7");
+        assert_eq!(locus.region, Region::new(55_019_016, 55_019_400));
+
+        let hla = ReferenceLocus::parse("HLA-A*01:01:01:01:100-200").unwrap();
+        assert_eq!(hla.contig, "HLA-A*01:01:01:01");
+
+        for invalid in ["chr7", "chr7:100", ":1-10", "chr7:a-10"] {
+            assert_eq!(
+                ReferenceLocus::parse(invalid),
+                Err(RegionError::InvalidLocus(invalid.to_string()))
+            );
+        }
+        assert_eq!(
+            ReferenceLocus::parse("chr7:0-10"),
+            Err(RegionError::ZeroPosition)
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use super::{Range, Strand};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// 配列上の領域。座標は 0-based の半開区間 [start, end)（end の塩基は含まない）。
+/// strand が Reverse の場合、同じ区間の逆相補鎖を指す
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    pub start: usize,
+    pub end: usize,
+    #[serde(default)]
+    pub strand: Strand,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RegionError {
+    #[error("Invalid range {start}..{end}: start must be less than end")]
+    Empty { start: usize, end: usize },
+    #[error("Invalid range {start}..{end} for sequence of length {length}")]
+    OutOfBounds {
+        start: usize,
+        end: usize,
+        length: usize,
+    },
+    #[error("Invalid position 0: 1-based positions start at 1")]
+    ZeroPosition,
+    #[error("Invalid locus '{0}': expected contig:start-end")]
+    InvalidLocus(String),
+}
+
+impl Region {
+    /// 順鎖の領域 [start, end)
+    pub fn new(start: usize, end: usize) -> Self {
+        Self {
+            start,
+            end,
+            strand: Strand::Forward,
+        }
+    }
+
+    pub fn with_strand(self, strand: Strand) -> Self {
+        Self { strand, ..self }
+    }
+
+    /// 表示用の 1-based・両端を含む座標（例: 1..=10）から変換
+    pub fn from_one_based(start: usize, end: usize) -> Result<Self, RegionError> {
+        if start == 0 || end == 0 {
+            return Err(RegionError::ZeroPosition);
+        }
+        if start > end {
+            return Err(RegionError::Empty {
+                start: start - 1,
+                end,
+            });
+        }
+        Ok(Self::new(start - 1, end))
+    }
+
+    /// 表示用の 1-based・両端を含む座標（空の領域では end < start になる）
+    pub fn one_based(&self) -> (usize, usize) {
+        (self.start + 1, self.end)
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn range(&self) -> Range {
+        Range::new(self.start, self.end)
+    }
+
+    /// 長さ `length` の配列に対して空でなく範囲内にあるかを検証
+    pub fn validate(&self, length: usize) -> Result<(), RegionError> {
+        if self.is_empty() {
+            return Err(RegionError::Empty {
+                start: self.start,
+                end: self.end,
+            });
+        }
+        if self.end > length {
+            return Err(RegionError::OutOfBounds {
+                start: self.start,
+                end: self.end,
+                length,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<Range> for Region {
+    fn from(range: Range) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_based_conversion_round_trips() {
+        let region = Region::from_one_based(1, 10).unwrap();
+        assert_eq!(region, Region::new(0, 10));
+        assert_eq!(region.len(), 10);
+        assert_eq!(region.one_based(), (1, 10));
+
+        assert_eq!(Region::from_one_based(5, 5).unwrap().len(), 1);
+        assert_eq!(Region::from_one_based(0, 5), Err(RegionError::ZeroPosition));
+        assert!(Region::from_one_based(6, 5).is_err());
+    }
+
+    #[test]
+    fn test_validate_against_sequence_length() {
+        assert!(Region::new(0, 8).validate(8).is_ok());
+        assert_eq!(
+            Region::new(4, 9).validate(8),
+            Err(RegionError::OutOfBounds {
+                start: 4,
+                end: 9,
+                length: 8
+            })
+        );
+        assert!(Region::new(5, 5).validate(8).is_err());
+        assert!(Region::new(6, 4).validate(8).is_err());
+
+        let json = serde_json::to_string(&Region::new(2, 4)).unwrap();
+        assert_eq!(json, r#"{"start":2,"end":4,"strand":"Forward"}"#);
+        let parsed: Region = serde_json::from_str(r#"{"start":2,"end":4}"#).unwrap();
+        assert_eq!(parsed.strand, Strand::Forward);
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+/// 縦列反復の単位長の上限
+pub const MAX_PERIOD: usize = 10;
+/// 逆位反復の腕の間（ループ）の長さの上限
+pub const MAX_SPACER: usize = 1000;
+
+/// 縦列反復・逆位反復の探索条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepeatParams {
+    /// 縦列反復の単位長の上限（1〜MAX_PERIOD）
+    pub max_period: usize,
+    /// 縦列反復として報告する最小のコピー数（端数を含む）
+    pub min_copies: f64,
+    /// 縦列反復として報告する最短の長さ（bp）
+    pub min_tandem_length: usize,
+    /// 逆位反復の腕の最短長（bp）
+    pub min_arm_length: usize,
+    /// 逆位反復の腕の間の最大長（0 なら回文だけ）
+    pub max_spacer: usize,
+    /// 縦列反復・逆位反復それぞれで返す上限
+    pub max_results: usize,
+}
+
+impl Default for RepeatParams {
+    fn default() -> Self {
+        Self {
+            max_period: MAX_PERIOD,
+            min_copies: 3.0,
+            min_tandem_length: 12,
+            min_arm_length: 10,
+            max_spacer: 100,
+            max_results: 1000,
+        }
+    }
+}
+
+/// 縦列反復（[start, end) が単位 `consensus` の繰り返し）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TandemRepeat {
+    pub start: usize,
+    pub end: usize,
+    /// 単位長
+    pub period: usize,
+    /// 単位の位相ごとに最も多い塩基（`start` から始まる向き）
+    pub consensus: String,
+    /// (end - start) / period
+    pub copies: f64,
+    /// 単位の繰り返しと一致する塩基の割合（0〜1）
+    pub identity: f64,
+}
+
+/// 逆位反復。[start, start + arm_length) と [end - arm_length, end) が互いに逆相補で、
+/// 間に `spacer` 塩基を挟む（spacer が 0 なら回文）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvertedRepeat {
+    pub start: usize,
+    pub end: usize,
+    pub arm_length: usize,
+    pub spacer: usize,
+    /// 左の腕の配列
+    pub arm: String,
+}
+
+/// 配列中の反復
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepeatReport {
+    pub length: usize,
+    /// 開始位置順
+    pub tandem: Vec<TandemRepeat>,
+    /// 開始位置順（縦列反復の中に収まるものは除く）
+    pub inverted: Vec<InvertedRepeat>,
+    /// max_results で打ち切った
+    pub truncated: bool,
+}
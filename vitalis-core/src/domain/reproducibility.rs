@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// 再現可能な疑似乱数生成器（SplitMix64）
+#[derive(Debug, Clone)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) の一様乱数
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// [0, n) の一様な整数
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// ID と乱数シードの供給源。シードがあれば呼び出し順に決定的な値を返し、
+/// なければ OS の乱数（UUID v4）を使う
+#[derive(Debug, Clone, Default)]
+pub struct EntropySource {
+    rng: Option<SplitMix64>,
+}
+
+impl EntropySource {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rng: Some(SplitMix64::new(seed)),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        match self.rng.as_mut() {
+            Some(rng) => rng.next_u64(),
+            None => Uuid::new_v4().as_u64_pair().0,
+        }
+    }
+
+    /// UUID v4 の形式の ID（シードがあれば決定的）
+    pub fn new_id(&mut self) -> Uuid {
+        match self.rng.as_mut() {
+            Some(rng) => {
+                let mut bytes = [0u8; 16];
+                bytes[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+                bytes[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+                uuid::Builder::from_random_bytes(bytes).into_uuid()
+            }
+            None => Uuid::new_v4(),
+        }
+    }
+}
+
+/// セッションの由来情報（結果を再現するのに必要な条件）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionProvenance {
+    pub started_at: DateTime<Utc>,
+    pub software_version: String,
+    /// 再現性モードのシード（無効なら None）
+    pub seed: Option<u64>,
+    /// 再現性モードを設定した時刻
+    pub seeded_at: Option<DateTime<Utc>>,
+}
+
+struct Workspace {
+    entropy: EntropySource,
+    provenance: SessionProvenance,
+}
+
+lazy_static::lazy_static! {
+    /// ワークスペース全体で共有する乱数源と由来情報
+    static ref WORKSPACE: Mutex<Workspace> = Mutex::new(Workspace {
+        entropy: EntropySource::default(),
+        provenance: SessionProvenance {
+            started_at: Utc::now(),
+            software_version: env!("CARGO_PKG_VERSION").to_string(),
+            seed: None,
+            seeded_at: None,
+        },
+    });
+}
+
+fn with_workspace<T>(f: impl FnOnce(&mut Workspace) -> T) -> T {
+    let mut workspace = WORKSPACE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut workspace)
+}
+
+/// 再現性モードを設定する（Some でシードから乱数列をやり直し、None で解除）
+pub fn set_workspace_seed(seed: Option<u64>) -> SessionProvenance {
+    with_workspace(|workspace| {
+        workspace.entropy = seed.map(EntropySource::seeded).unwrap_or_default();
+        workspace.provenance.seed = seed;
+        workspace.provenance.seeded_at = seed.map(|_| Utc::now());
+        workspace.provenance.clone()
+    })
+}
+
+pub fn session_provenance() -> SessionProvenance {
+    with_workspace(|workspace| workspace.provenance.clone())
+}
+
+/// ワークスペースの乱数源から ID を払い出す（プライマーペアなど）
+pub fn workspace_id() -> Uuid {
+    with_workspace(|workspace| workspace.entropy.new_id())
+}
+
+/// ワークスペースの乱数源から乱数シードを払い出す（確率的なアルゴリズムの既定シード）
+pub fn workspace_u64() -> u64 {
+    with_workspace(|workspace| workspace.entropy.next_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_source_is_deterministic() {
+        let mut a = EntropySource::seeded(7);
+        let mut b = EntropySource::seeded(7);
+        let ids: Vec<Uuid> = (0..3).map(|_| a.new_id()).collect();
+        assert_eq!(ids, (0..3).map(|_| b.new_id()).collect::<Vec<_>>());
+        assert_eq!(ids[0].get_version_num(), 4);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_ne!(EntropySource::seeded(8).new_id(), ids[0]);
+        assert_ne!(EntropySource::default().new_id(), ids[0]);
+    }
+}
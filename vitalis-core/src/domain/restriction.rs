@@ -0,0 +1,350 @@
+use super::{Strand, Topology};
+use serde::{Deserialize, Serialize};
+
+/// 制限酵素（認識配列はIUPAC表記、切断位置は認識配列先頭からのオフセット）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RestrictionEnzyme {
+    pub name: String,
+    /// 認識配列（5'→3'、IUPAC縮重コード可）
+    pub site: String,
+    /// 上鎖の切断位置（認識配列先頭からのオフセット）
+    pub cut_top: isize,
+    /// 下鎖の切断位置（上鎖座標でのオフセット）
+    pub cut_bottom: isize,
+    /// Damメチル化（GATC）で切断が阻害されるか
+    pub dam_sensitive: bool,
+    /// Dcmメチル化（CCWGG）で切断が阻害されるか
+    pub dcm_sensitive: bool,
+    /// 反応温度・熱失活・供給濃度
+    #[serde(default)]
+    pub conditions: ReactionConditions,
+}
+
+impl RestrictionEnzyme {
+    pub fn new(name: &str, site: &str, cut_top: isize, cut_bottom: isize) -> Self {
+        Self {
+            name: name.to_string(),
+            site: site.to_string(),
+            cut_top,
+            cut_bottom,
+            dam_sensitive: false,
+            dcm_sensitive: false,
+            conditions: ReactionConditions::default(),
+        }
+    }
+
+    pub fn dam(mut self) -> Self {
+        self.dam_sensitive = true;
+        self
+    }
+
+    pub fn dcm(mut self) -> Self {
+        self.dcm_sensitive = true;
+        self
+    }
+
+    pub fn incubate_at(mut self, temperature: f64) -> Self {
+        self.conditions.incubation_temp = temperature;
+        self
+    }
+
+    pub fn inactivate_at(mut self, temperature: f64) -> Self {
+        self.conditions.heat_inactivation = Some(HeatInactivation {
+            temperature,
+            minutes: 20,
+        });
+        self
+    }
+
+    pub fn no_inactivation(mut self) -> Self {
+        self.conditions.heat_inactivation = None;
+        self
+    }
+
+    pub fn units_per_ul(mut self, units_per_ul: f64) -> Self {
+        self.conditions.units_per_ul = units_per_ul;
+        self
+    }
+
+    pub fn overhang_type(&self) -> OverhangType {
+        match self.cut_top.cmp(&self.cut_bottom) {
+            std::cmp::Ordering::Less => OverhangType::FivePrime,
+            std::cmp::Ordering::Greater => OverhangType::ThreePrime,
+            std::cmp::Ordering::Equal => OverhangType::Blunt,
+        }
+    }
+}
+
+/// 熱失活の条件
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeatInactivation {
+    /// °C
+    pub temperature: f64,
+    pub minutes: u32,
+}
+
+/// 酵素の反応条件（組み込みテーブルは一般的な市販品の推奨値）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReactionConditions {
+    /// 反応温度（°C）
+    pub incubation_temp: f64,
+    /// 熱失活できない酵素は None
+    pub heat_inactivation: Option<HeatInactivation>,
+    /// 供給濃度（U/µL）
+    pub units_per_ul: f64,
+}
+
+impl Default for ReactionConditions {
+    fn default() -> Self {
+        Self {
+            incubation_temp: 37.0,
+            heat_inactivation: Some(HeatInactivation {
+                temperature: 65.0,
+                minutes: 20,
+            }),
+            units_per_ul: 20.0,
+        }
+    }
+}
+
+/// 切断末端の形状
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverhangType {
+    FivePrime,
+    ThreePrime,
+    Blunt,
+}
+
+/// 切断を阻害する宿主メチル化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Methylation {
+    Dam,
+    Dcm,
+}
+
+impl Methylation {
+    /// メチル化される認識モチーフ
+    pub fn motif(&self) -> &'static str {
+        match self {
+            Methylation::Dam => "GATC",
+            Methylation::Dcm => "CCWGG",
+        }
+    }
+}
+
+/// 配列上の制限酵素サイト（座標は0-based）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RestrictionSite {
+    pub enzyme: String,
+    /// 認識配列の開始位置
+    pub position: usize,
+    /// 認識配列が一致した鎖
+    pub strand: Strand,
+    /// 上鎖の切断位置（この位置の塩基の直前で切断）
+    pub cut_top: usize,
+    /// 下鎖の切断位置（上鎖座標）
+    pub cut_bottom: usize,
+    pub overhang_type: OverhangType,
+    /// 一本鎖突出部分（上鎖側の配列、平滑末端では空）
+    pub overhang: String,
+    /// 切断を阻害するメチル化（空なら切断可能）
+    pub blocked_by: Vec<Methylation>,
+}
+
+impl RestrictionSite {
+    pub fn is_blocked(&self) -> bool {
+        !self.blocked_by.is_empty()
+    }
+}
+
+/// 切断数ごとの酵素一覧における1酵素分のエントリ
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CutterEntry {
+    pub enzyme: String,
+    /// 認識配列の開始位置
+    pub positions: Vec<usize>,
+    /// いずれかのサイトがメチル化で阻害されるか
+    pub blocked: bool,
+}
+
+/// 0/1/2箇所で切断する酵素の一覧（クローニング戦略の検討用）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CutterSummary {
+    pub non_cutters: Vec<String>,
+    /// ユニークサイト（最も重要なため個別に保持）
+    pub single_cutters: Vec<CutterEntry>,
+    pub double_cutters: Vec<CutterEntry>,
+}
+
+/// 切断数による酵素の分類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CutterClass {
+    NonCutter,
+    SingleCutter,
+    MultiCutter,
+}
+
+/// 1酵素で消化したときの切断位置と断片
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnzymeDigest {
+    pub enzyme: String,
+    /// 上鎖の切断位置（昇順、重複なし）
+    pub cut_positions: Vec<usize>,
+    /// 断片長（降順。環状配列ではサイト数と同じ本数）
+    pub fragments: Vec<usize>,
+    pub cutter: CutterClass,
+    /// いずれかのサイトがメチル化で阻害されるか
+    pub blocked: bool,
+}
+
+/// 配列の制限酵素地図（サイトと酵素ごとの消化結果）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RestrictionMap {
+    pub seq_id: String,
+    pub length: usize,
+    pub topology: Topology,
+    /// 位置順
+    pub sites: Vec<RestrictionSite>,
+    /// 指定した酵素の順
+    pub digests: Vec<EnzymeDigest>,
+}
+
+/// 消化プロトコルの条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestProtocolParams {
+    /// 消化する DNA 量（µg）
+    pub dna_amount_ug: f64,
+    /// DNA 溶液の濃度（ng/µL）
+    pub dna_concentration_ng_ul: f64,
+    /// 反応液量（µL）
+    pub reaction_volume_ul: f64,
+    /// DNA 1 µg あたりの酵素量（U）。1 U は 1 µg を 1 時間で切る量なので 5〜10 倍過剰が目安
+    pub units_per_ug: f64,
+    /// 標準の反応時間（min）
+    pub incubation_minutes: f64,
+}
+
+impl Default for DigestProtocolParams {
+    fn default() -> Self {
+        Self {
+            dna_amount_ug: 1.0,
+            dna_concentration_ng_ul: 100.0,
+            reaction_volume_ul: 50.0,
+            units_per_ug: 10.0,
+            incubation_minutes: 60.0,
+        }
+    }
+}
+
+/// 1酵素の使用量
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnzymeDose {
+    pub enzyme: String,
+    pub volume_ul: f64,
+    pub units: f64,
+    /// この量で DNA を切り終えるのに必要な時間（min）
+    pub minutes: f64,
+}
+
+/// 反応液の成分（分注順）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReactionComponent {
+    pub name: String,
+    pub volume_ul: f64,
+}
+
+/// 1温度での反応（温度の異なる酵素は低温のものから順に反応させる）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IncubationStep {
+    /// °C
+    pub temperature: f64,
+    pub minutes: f64,
+    pub enzymes: Vec<String>,
+}
+
+/// 消化の実験プロトコル（ベンチでそのまま使える手順書 `text` 付き）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DigestProtocol {
+    pub map: RestrictionMap,
+    pub params: DigestProtocolParams,
+    pub doses: Vec<EnzymeDose>,
+    pub components: Vec<ReactionComponent>,
+    pub incubation: Vec<IncubationStep>,
+    /// 全酵素が熱失活できる場合のみ
+    pub heat_inactivation: Option<HeatInactivation>,
+    /// 全酵素で切ったときの断片長（降順）
+    pub fragments: Vec<usize>,
+    pub warnings: Vec<String>,
+    pub text: String,
+}
+
+/// 組み込みの制限酵素テーブル（REBASE準拠の代表的な酵素）
+pub fn builtin_enzymes() -> Vec<RestrictionEnzyme> {
+    vec![
+        RestrictionEnzyme::new("AatII", "GACGTC", 5, 1).inactivate_at(80.0),
+        RestrictionEnzyme::new("AgeI", "ACCGGT", 1, 5),
+        RestrictionEnzyme::new("ApaI", "GGGCCC", 5, 1)
+            .dcm()
+            .incubate_at(25.0),
+        RestrictionEnzyme::new("AvaI", "CYCGRG", 1, 5).inactivate_at(80.0),
+        RestrictionEnzyme::new("BamHI", "GGATCC", 1, 5).no_inactivation(),
+        RestrictionEnzyme::new("BbsI", "GAAGAC", 8, 12),
+        RestrictionEnzyme::new("BclI", "TGATCA", 1, 5)
+            .dam()
+            .incubate_at(50.0)
+            .no_inactivation()
+            .units_per_ul(10.0),
+        RestrictionEnzyme::new("BglII", "AGATCT", 1, 5).no_inactivation(),
+        RestrictionEnzyme::new("BsaI", "GGTCTC", 7, 11).inactivate_at(80.0),
+        RestrictionEnzyme::new("BsmBI", "CGTCTC", 7, 11)
+            .incubate_at(55.0)
+            .inactivate_at(80.0),
+        RestrictionEnzyme::new("ClaI", "ATCGAT", 2, 4).dam(),
+        RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5),
+        RestrictionEnzyme::new("EcoRII", "CCWGG", 0, 5).dcm(),
+        RestrictionEnzyme::new("EcoRV", "GATATC", 3, 3).inactivate_at(80.0),
+        RestrictionEnzyme::new("HincII", "GTYRAC", 3, 3),
+        RestrictionEnzyme::new("HindIII", "AAGCTT", 1, 5).inactivate_at(80.0),
+        RestrictionEnzyme::new("KpnI", "GGTACC", 5, 1).no_inactivation(),
+        RestrictionEnzyme::new("MboI", "GATC", 0, 4)
+            .dam()
+            .units_per_ul(5.0),
+        RestrictionEnzyme::new("MluI", "ACGCGT", 1, 5).inactivate_at(80.0),
+        RestrictionEnzyme::new("NcoI", "CCATGG", 1, 5).inactivate_at(80.0),
+        RestrictionEnzyme::new("NdeI", "CATATG", 2, 4),
+        RestrictionEnzyme::new("NheI", "GCTAGC", 1, 5).inactivate_at(80.0),
+        RestrictionEnzyme::new("NotI", "GCGGCCGC", 2, 6),
+        RestrictionEnzyme::new("NruI", "TCGCGA", 3, 3)
+            .dam()
+            .no_inactivation(),
+        RestrictionEnzyme::new("PstI", "CTGCAG", 5, 1).inactivate_at(80.0),
+        RestrictionEnzyme::new("PvuII", "CAGCTG", 3, 3).no_inactivation(),
+        RestrictionEnzyme::new("SacI", "GAGCTC", 5, 1),
+        RestrictionEnzyme::new("SalI", "GTCGAC", 1, 5),
+        RestrictionEnzyme::new("SapI", "GCTCTTC", 8, 11).units_per_ul(10.0),
+        RestrictionEnzyme::new("SmaI", "CCCGGG", 3, 3).incubate_at(25.0),
+        RestrictionEnzyme::new("SpeI", "ACTAGT", 1, 5).inactivate_at(80.0),
+        RestrictionEnzyme::new("SphI", "GCATGC", 5, 1),
+        RestrictionEnzyme::new("StuI", "AGGCCT", 3, 3)
+            .dcm()
+            .no_inactivation(),
+        RestrictionEnzyme::new("XbaI", "TCTAGA", 1, 5).dam(),
+        RestrictionEnzyme::new("XhoI", "CTCGAG", 1, 5),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overhang_type() {
+        let enzymes = builtin_enzymes();
+        let find = |name: &str| enzymes.iter().find(|e| e.name == name).unwrap();
+        assert_eq!(find("EcoRI").overhang_type(), OverhangType::FivePrime);
+        assert_eq!(find("PstI").overhang_type(), OverhangType::ThreePrime);
+        assert_eq!(find("EcoRV").overhang_type(), OverhangType::Blunt);
+    }
+}
@@ -0,0 +1,21 @@
+use super::Range;
+use serde::{Deserialize, Serialize};
+
+/// ギャップの間のコンティグ（スキャフォールド上の0-based半開区間）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contig {
+    /// 派生配列として保存したコンティグの seq_id
+    pub seq_id: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// スキャフォールドを `min_gap` 塩基以上の N の連続で分割した結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScaffoldSplit {
+    pub scaffold_id: String,
+    pub min_gap: usize,
+    pub gaps: Vec<Range>,
+    /// スキャフォールド上の順
+    pub contigs: Vec<Contig>,
+}
@@ -0,0 +1,79 @@
+use super::primer::{JunctionPrimerParams, PrimerPair};
+use serde::{Deserialize, Serialize};
+
+/// クローンのスクリーニング方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScreeningMethod {
+    /// 診断用の制限酵素消化
+    Digest,
+    /// 連結部をまたぐコロニーPCR
+    JunctionPcr,
+}
+
+/// スクリーニング戦略の提案条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreeningParams {
+    /// ゲル上で確認できる最小・最大のバンド長
+    pub min_band: usize,
+    pub max_band: usize,
+    /// 区別できるバンド長の相対差（0.1 なら 10%）
+    pub resolution: f64,
+    /// コンストラクトの消化で許容するバンド数の上限
+    pub max_bands: usize,
+    /// 1回の消化に使う酵素数の上限（1 または 2）
+    pub max_enzymes: usize,
+    /// 提示する消化パターンの数
+    pub max_digests: usize,
+    pub junction: JunctionPrimerParams,
+}
+
+impl Default for ScreeningParams {
+    fn default() -> Self {
+        Self {
+            min_band: 250,
+            max_band: 20_000,
+            resolution: 0.1,
+            max_bands: 5,
+            max_enzymes: 2,
+            max_digests: 5,
+            junction: JunctionPrimerParams::default(),
+        }
+    }
+}
+
+/// 正しいクローン（コンストラクト）と親配列を区別できる消化パターン
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DigestScreen {
+    pub enzymes: Vec<String>,
+    /// 見えるバンドの長さ（降順、min_band 未満は除く）
+    pub construct_bands: Vec<usize>,
+    pub parent_bands: Vec<usize>,
+    /// 相手のパターンに最も近いバンドとの相対差の最大値（大きいほど判別しやすい）
+    pub separation: f64,
+}
+
+/// 連結部PCRで期待される産物長
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PcrScreen {
+    pub junction: usize,
+    pub label: String,
+    pub pair: PrimerPair,
+    /// 正しいクローンでの産物長
+    pub correct_size: usize,
+    /// 親配列（空ベクターなど）での産物長（増幅されない場合は None）
+    pub parent_size: Option<usize>,
+}
+
+/// コンストラクトと親配列を区別するスクリーニング戦略の提案
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreeningStrategy {
+    pub construct_seq_id: String,
+    pub parent_seq_id: String,
+    /// 推奨する方法（どちらでも区別できなければ None）
+    pub recommended: Option<ScreeningMethod>,
+    /// 判別しやすい順
+    pub digests: Vec<DigestScreen>,
+    pub pcr: Vec<PcrScreen>,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,75 @@
+use super::{Range, Strand};
+use serde::{Deserialize, Serialize};
+
+/// 配列内検索のオプション
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    /// 逆相補鎖も検索するか
+    pub both_strands: bool,
+    /// クエリ中のIUPAC縮重コードを解釈するか（false の場合は文字どおり一致）
+    pub iupac: bool,
+    /// 0始まりのページ番号
+    pub page: usize,
+    pub page_size: usize,
+    /// ビューアが1回に取得するウィンドウ長
+    pub window_size: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            both_strands: true,
+            iupac: true,
+            page: 0,
+            page_size: 100,
+            window_size: 1000,
+        }
+    }
+}
+
+/// 検索ヒット（0-based半開区間、環状配列で原点をまたぐ場合は end が配列長を超える）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHit {
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+}
+
+/// 縮重モチーフの一致（0-based半開区間、環状配列で原点をまたぐ場合は end が配列長を超える）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MotifMatch {
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    /// モチーフのIUPACコードに一致しなかった塩基の数
+    pub mismatches: usize,
+    /// 一致した塩基配列（strand の向き、5'→3'）
+    pub matched: String,
+}
+
+/// ページ単位の検索結果と、ヒット表示に必要な最小限の取得ウィンドウ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchPage {
+    pub total_hits: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub hits: Vec<SearchHit>,
+    pub windows: Vec<Range>,
+}
+
+/// アミノ酸配列をコードしうる塩基配列上の区間（6フレーム翻訳検索のヒット）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranslatedHit {
+    pub seq_id: String,
+    /// 0-based半開区間（環状配列で原点をまたぐ場合は end が配列長を超える）
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    /// 読み枠（+1〜+3 / -1〜-3、ORF と同じ定義）
+    pub frame: i8,
+    /// コードしている塩基配列（strand の向き、5'→3'）
+    pub coding: String,
+    /// その翻訳（クエリの 'X' の位置も実際の残基）
+    pub peptide: String,
+}
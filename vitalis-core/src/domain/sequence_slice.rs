@@ -0,0 +1,125 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// 共有バッファ上の塩基列の区間。メモリ上の配列のウィンドウをコピーせずに返し、
+/// シリアライズ時もバッファから直接書き出す
+#[derive(Clone)]
+pub struct SequenceSlice {
+    buffer: Arc<str>,
+    start: usize,
+    end: usize,
+}
+
+impl SequenceSlice {
+    /// buffer[start..end] を参照する（範囲外や文字境界以外なら panic）
+    pub fn new(buffer: Arc<str>, start: usize, end: usize) -> Self {
+        assert!(
+            buffer.is_char_boundary(start) && buffer.is_char_boundary(end) && start <= end,
+            "slice {}..{} is not inside a buffer of length {}",
+            start,
+            end,
+            buffer.len()
+        );
+        Self { buffer, start, end }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.buffer[self.start..self.end]
+    }
+
+    /// 他の配列やウィンドウとバッファを共有しているか
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.buffer) > 1
+    }
+}
+
+impl From<String> for SequenceSlice {
+    fn from(bases: String) -> Self {
+        let end = bases.len();
+        Self::new(bases.into(), 0, end)
+    }
+}
+
+impl Deref for SequenceSlice {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SequenceSlice {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for SequenceSlice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for SequenceSlice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for SequenceSlice {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SequenceSlice {}
+
+impl PartialEq<str> for SequenceSlice {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SequenceSlice {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for SequenceSlice {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl Serialize for SequenceSlice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SequenceSlice {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_shares_buffer_and_serializes_as_string() {
+        let buffer: Arc<str> = Arc::from("ACGTACGTTT");
+        let slice = SequenceSlice::new(buffer.clone(), 2, 7);
+        assert_eq!(slice, "GTACG");
+        assert!(slice.is_shared());
+        assert_eq!(serde_json::to_string(&slice).unwrap(), "\"GTACG\"");
+
+        let owned: SequenceSlice = serde_json::from_str("\"TTA\"").unwrap();
+        assert_eq!(owned, "TTA".to_string());
+        assert!(!owned.is_shared());
+    }
+}
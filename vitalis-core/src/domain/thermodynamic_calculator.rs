@@ -10,7 +10,7 @@ pub struct ThermodynamicCalculator {
 }
 
 /// 熱力学計算の条件設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CalculationConditions {
     /// 温度 (Kelvin)
     pub temperature_k: f32,
@@ -36,6 +36,16 @@ impl Default for CalculationConditions {
     }
 }
 
+/// 計算に用いたパラメータセットと条件（保存・エクスポートした結果の再現用）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ThermodynamicProvenance {
+    /// パラメータセット名とバージョン
+    pub parameter_set: String,
+    pub version: String,
+    pub conditions: CalculationConditions,
+    pub salt: SaltCorrectionParams,
+}
+
 /// 包括的な熱力学計算結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComprehensiveThermodynamicResult {
@@ -53,6 +63,7 @@ pub struct ComprehensiveThermodynamicResult {
     pub corrections_applied: Vec<String>,
     /// 詳細な寄与の内訳
     pub contribution_breakdown: ContributionBreakdown,
+    pub provenance: ThermodynamicProvenance,
 }
 
 /// エネルギー寄与の内訳
@@ -107,6 +118,16 @@ impl ThermodynamicCalculator {
         &self.conditions
     }
 
+    /// 現在のパラメータセットと条件
+    pub fn provenance(&self) -> ThermodynamicProvenance {
+        ThermodynamicProvenance {
+            parameter_set: self.database.name.clone(),
+            version: self.database.version.clone(),
+            conditions: self.conditions.clone(),
+            salt: self.database.salt_correction.clone(),
+        }
+    }
+
     /// 包括的な熱力学計算（NNDB 2024の全機能を活用）
     pub fn calculate_comprehensive(
         &self,
@@ -206,6 +227,7 @@ impl ThermodynamicCalculator {
             formation_probability,
             corrections_applied: corrections,
             contribution_breakdown: breakdown,
+            provenance: self.provenance(),
         })
     }
 
@@ -225,6 +247,24 @@ impl ThermodynamicCalculator {
         salt_conditions: &SaltCorrectionParams,
         temperature_k: f32,
     ) -> Result<f32, ThermodynamicError> {
+        let (total_enthalpy, corrected_entropy) =
+            self.duplex_enthalpy_entropy(sequence, salt_conditions)?;
+
+        // Tm計算: Tm = ΔH / ΔS (エントロピーはcal/mol·Kからkcal/mol·Kに変換)
+        if corrected_entropy != 0.0 {
+            let tm_k = (total_enthalpy * 1000.0) / corrected_entropy; // ΔSをcal/mol·KからJ/mol·Kに変換
+            Ok(tm_k - 273.15) // Kelvinから摂氏に変換
+        } else {
+            Err(ThermodynamicError::ZeroEntropy)
+        }
+    }
+
+    /// 完全相補な二本鎖の最近接法による ΔH (kcal/mol) と塩濃度補正後の ΔS (cal/mol·K)
+    pub fn duplex_enthalpy_entropy(
+        &self,
+        sequence: &str,
+        salt_conditions: &SaltCorrectionParams,
+    ) -> Result<(f32, f32), ThermodynamicError> {
         if sequence.len() < 2 {
             return Err(ThermodynamicError::SequenceTooShort);
         }
@@ -268,14 +308,7 @@ impl ThermodynamicCalculator {
         // 塩濃度補正
         let corrected_entropy =
             self.apply_salt_correction(total_entropy, sequence.len(), salt_conditions);
-
-        // Tm計算: Tm = ΔH / ΔS (エントロピーはcal/mol·Kからkcal/mol·Kに変換)
-        if corrected_entropy != 0.0 {
-            let tm_k = (total_enthalpy * 1000.0) / corrected_entropy; // ΔSをcal/mol·KからJ/mol·Kに変換
-            Ok(tm_k - 273.15) // Kelvinから摂氏に変換
-        } else {
-            Err(ThermodynamicError::ZeroEntropy)
-        }
+        Ok((total_enthalpy, corrected_entropy))
     }
 
     /// ギブス自由エネルギー計算
@@ -373,6 +406,7 @@ impl ThermodynamicCalculator {
             best_alignment_offset: best_alignment,
             all_alignments: alignments,
             is_problematic: max_score < -8.0, // 閾値: -8.0 kcal/mol未満で問題あり
+            provenance: self.provenance(),
         })
     }
 
@@ -435,6 +469,7 @@ impl ThermodynamicCalculator {
             best_hairpin: best_hairpin.cloned(),
             all_hairpins: hairpins,
             is_problematic: min_score < -5.0, // 閾値: -5.0 kcal/mol未満で問題あり
+            provenance: self.provenance(),
         })
     }
 
@@ -488,6 +523,57 @@ impl ThermodynamicCalculator {
             best_alignment_offset: best_alignment,
             all_alignments: alignments,
             is_problematic: max_score < -8.0, // 閾値: -8.0 kcal/mol未満で問題あり
+            provenance: self.provenance(),
+        })
+    }
+
+    /// ミスマッチを含みうる二本鎖の ΔG 内訳。
+    /// `top` は上鎖（5'→3'）、`bottom` は対合する下鎖を同じ位置に並べたもの（3'→5'）。
+    /// スタッキングは最近接パラメータ、なければミスマッチパラメータで求め、
+    /// どちらにもない組（縮重塩基を含む組も）は None として合計では 0 とみなす
+    pub fn calculate_duplex_energy(
+        &self,
+        top: &str,
+        bottom: &str,
+    ) -> Result<DuplexEnergy, ThermodynamicError> {
+        if top.len() != bottom.len() {
+            return Err(ThermodynamicError::InvalidSequence(format!(
+                "{}/{}",
+                top, bottom
+            )));
+        }
+        if top.len() < 2 {
+            return Err(ThermodynamicError::SequenceTooShort);
+        }
+
+        let temperature_k = self.conditions.temperature_k;
+        let top: Vec<char> = top.to_uppercase().chars().collect();
+        let bottom: Vec<char> = bottom.to_uppercase().chars().collect();
+        let stacks: Vec<Option<f32>> = (0..top.len() - 1)
+            .map(|i| {
+                let key = format!("{}{}/{}{}", top[i], top[i + 1], bottom[i], bottom[i + 1]);
+                // 180° 回転した表記（下鎖を上に読む）でも登録されている
+                let rotated = format!("{}{}/{}{}", bottom[i + 1], bottom[i], top[i + 1], top[i]);
+                self.database
+                    .get_nearest_neighbor(&key)
+                    .or_else(|| self.database.get_nearest_neighbor(&rotated))
+                    .or_else(|| self.database.get_mismatch(&key))
+                    .or_else(|| self.database.get_mismatch(&rotated))
+                    .map(|params| params.delta_g(temperature_k))
+            })
+            .collect();
+
+        let initiation: f32 = [top[0], top[top.len() - 1]]
+            .iter()
+            .filter_map(|base| self.database.get_initiation(&base.to_string()))
+            .map(|params| params.delta_g(temperature_k))
+            .sum();
+        let delta_g = initiation + stacks.iter().flatten().sum::<f32>();
+
+        Ok(DuplexEnergy {
+            stacks,
+            delta_g,
+            provenance: self.provenance(),
         })
     }
 
@@ -767,6 +853,7 @@ pub struct SelfDimerAnalysis {
     pub best_alignment_offset: Option<usize>,
     pub all_alignments: Vec<AlignmentResult>,
     pub is_problematic: bool,
+    pub provenance: ThermodynamicProvenance,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -775,6 +862,7 @@ pub struct HairpinAnalysis {
     pub best_hairpin: Option<HairpinStructure>,
     pub all_hairpins: Vec<HairpinStructure>,
     pub is_problematic: bool,
+    pub provenance: ThermodynamicProvenance,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -783,6 +871,17 @@ pub struct HeteroDimerAnalysis {
     pub best_alignment_offset: Option<usize>,
     pub all_alignments: Vec<AlignmentResult>,
     pub is_problematic: bool,
+    pub provenance: ThermodynamicProvenance,
+}
+
+/// 二本鎖の ΔG 内訳
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplexEnergy {
+    /// 位置 i と i+1 の塩基対のスタッキング ΔG（kcal/mol、パラメータがなければ None）
+    pub stacks: Vec<Option<f32>>,
+    /// 末端効果を含む合計 ΔG（kcal/mol）
+    pub delta_g: f32,
+    pub provenance: ThermodynamicProvenance,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -894,6 +993,33 @@ mod tests {
             .contains(&"molecular_crowding".to_string()));
     }
 
+    #[test]
+    fn test_results_record_provenance() {
+        let mut calculator = ThermodynamicCalculator::new_santalucia_1998();
+        let conditions = CalculationConditions {
+            molecular_crowding: true,
+            ..CalculationConditions::default()
+        };
+        calculator.set_conditions(conditions.clone());
+
+        let result = calculator.calculate_comprehensive("ATGCATGC").unwrap();
+        assert_eq!(result.provenance.parameter_set, "SantaLucia");
+        assert_eq!(result.provenance.version, "1998");
+        assert_eq!(result.provenance.conditions, conditions);
+        assert_eq!(result.provenance.salt, SaltCorrectionParams::default());
+
+        let hairpin = calculator
+            .calculate_enhanced_hairpin("GGGCCCAAAGGGCCC")
+            .unwrap();
+        assert_eq!(hairpin.provenance, result.provenance);
+        assert_eq!(
+            ThermodynamicCalculator::new_nndb_2024()
+                .provenance()
+                .version,
+            "2024"
+        );
+    }
+
     #[test]
     fn test_enhanced_self_dimer_analysis() {
         let calculator = ThermodynamicCalculator::new_nndb_2024();
@@ -951,6 +1077,27 @@ mod tests {
         assert!((total_contribution - result.delta_h).abs() < 0.1); // 許容誤差内
     }
 
+    #[test]
+    fn test_duplex_energy_with_mismatch() {
+        let calculator = ThermodynamicCalculator::new_nndb_2024();
+
+        let perfect = calculator
+            .calculate_duplex_energy("ATGCATGC", "TACGTACG")
+            .unwrap();
+        assert!(perfect.stacks.iter().all(|stack| stack.is_some()));
+        let expected = calculator.calculate_delta_g("ATGCATGC", 310.15).unwrap();
+        assert!((perfect.delta_g - expected).abs() < 0.01);
+
+        // 位置 3 の C·T ミスマッチ：前後のスタッキングが WC の値を失う
+        let mismatched = calculator
+            .calculate_duplex_energy("ATGCATGC", "TACTTACG")
+            .unwrap();
+        assert!(mismatched.stacks[2].is_none() && mismatched.stacks[3].is_none());
+        assert!(mismatched.delta_g > perfect.delta_g);
+
+        assert!(calculator.calculate_duplex_energy("ATGC", "TAC").is_err());
+    }
+
     #[test]
     fn test_reverse_complement() {
         let calculator = ThermodynamicCalculator::new_santalucia_1998();
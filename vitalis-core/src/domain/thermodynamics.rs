@@ -25,6 +25,13 @@ impl ThermodynamicParams {
 /// DNA二重鎖形成の熱力学パラメータセット
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DNAThermodynamicsDatabase {
+    /// パラメータセット名（例: "NNDB"）
+    #[serde(default)]
+    pub name: String,
+    /// パラメータセットのバージョン（例: "2024"）
+    #[serde(default)]
+    pub version: String,
+
     /// Watson-Crick塩基対の最近接パラメータ
     /// キー: "AA/TT", "AT/AT", "TA/TA", "CA/TG", "GT/AC", "CT/AG", "GA/TC", "CG/CG", "GC/GC", "GG/CC"
     pub nearest_neighbor: HashMap<String, ThermodynamicParams>,
@@ -61,7 +68,7 @@ pub struct DNAThermodynamicsDatabase {
 }
 
 /// 塩濃度補正のパラメータ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SaltCorrectionParams {
     /// Na+濃度 (M)
     pub sodium_concentration: f32,
@@ -88,6 +95,8 @@ impl DNAThermodynamicsDatabase {
     /// NNDB 2024パラメータで初期化されたデータベースを作成
     pub fn nndb_2024() -> Self {
         let mut db = Self {
+            name: "NNDB".to_string(),
+            version: "2024".to_string(),
             nearest_neighbor: HashMap::new(),
             initiation: HashMap::new(),
             mismatches: HashMap::new(),
@@ -112,6 +121,8 @@ impl DNAThermodynamicsDatabase {
     /// SantaLucia 1998パラメータで初期化されたデータベースを作成（後方互換性）
     pub fn santalucia_1998() -> Self {
         let mut db = Self {
+            name: "SantaLucia".to_string(),
+            version: "1998".to_string(),
             nearest_neighbor: HashMap::new(),
             initiation: HashMap::new(),
             mismatches: HashMap::new(),
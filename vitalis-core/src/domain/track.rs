@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 配列に登録する塩基単位のスコアトラック（values[i] が i 番目の塩基のスコア）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreTrack {
+    pub id: String,
+    pub seq_id: String,
+    pub name: String,
+    /// 由来（例: "conservation:aln_1"）
+    pub source: String,
+    pub values: Vec<f64>,
+}
+
+/// 配列IDごとのスコアトラックストア
+#[derive(Debug, Default)]
+pub struct TrackStore {
+    tracks: HashMap<String, Vec<ScoreTrack>>,
+    next_id: usize,
+}
+
+impl TrackStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// トラックを追加（IDが空なら採番する）
+    pub fn add(&mut self, mut track: ScoreTrack) -> String {
+        if track.id.is_empty() {
+            self.next_id += 1;
+            track.id = format!("track_{}", self.next_id);
+        }
+        let id = track.id.clone();
+        self.tracks
+            .entry(track.seq_id.clone())
+            .or_default()
+            .push(track);
+        id
+    }
+
+    pub fn get_all(&self, seq_id: &str) -> &[ScoreTrack] {
+        self.tracks
+            .get(seq_id)
+            .map(|tracks| tracks.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn get(&self, seq_id: &str, track_id: &str) -> Option<&ScoreTrack> {
+        self.get_all(seq_id)
+            .iter()
+            .find(|track| track.id == track_id)
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// コマンド入力の上限。フロントエンドからの不正な値で長時間の処理やパニックが
+/// 起きないよう、処理を始める前にこの範囲を検証する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputLimits {
+    /// テキストとして受け取る配列・ファイル内容の最大長（バイト）
+    pub max_text_length: usize,
+    /// 1回に取得・整形・描画する領域の最大長
+    pub max_region_length: usize,
+    /// スライディングウィンドウ長の上限
+    pub max_window_size: usize,
+    /// プライマー長の許容範囲
+    pub min_primer_length: usize,
+    pub max_primer_length: usize,
+    /// 1組のプライマー長範囲（length_max − length_min）の上限。候補数はこれに比例する
+    pub max_primer_length_span: usize,
+    /// プライマー候補を探す範囲（search_flank）の上限。候補数はこれに比例する
+    pub max_primer_search_flank: usize,
+    /// HRM 増幅産物長の上限
+    pub max_hrm_amplicon_length: usize,
+    /// qPCR アッセイを探す領域長の上限（候補数は領域長に比例する）
+    pub max_assay_region_length: usize,
+    /// ホモロジーアーム長の上限
+    pub max_homology_arm_length: usize,
+    /// 1回に検証プライマーを設計する連結部数の上限
+    pub max_junctions: usize,
+    /// 検索クエリの最大長
+    pub max_query_length: usize,
+    /// 読み枠を考慮したアラインメントの DP 行列（塩基数 × 残基数）の上限
+    pub max_alignment_cells: usize,
+    /// 検索結果1ページの最大件数
+    pub max_page_size: usize,
+    /// 許容ミスマッチ数 k の上限
+    pub max_mismatches: usize,
+    /// 1回に生成する変異体数の上限
+    pub max_simulated_variants: usize,
+    /// クロストーク解析のオリゴ数の上限（計算量は O(n³)）
+    pub max_oligos: usize,
+    /// オリゴ・プライマー配列の最大長
+    pub max_oligo_length: usize,
+    /// 1回にスキャンするタンパク質モチーフ数の上限
+    pub max_protein_motifs: usize,
+    /// スクリプトの最大長（バイト）
+    pub max_script_length: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            max_text_length: 256 * 1024 * 1024,
+            max_region_length: 10_000_000,
+            max_window_size: 1_000_000,
+            min_primer_length: 8,
+            max_primer_length: 60,
+            max_primer_length_span: 20,
+            max_primer_search_flank: 500,
+            max_hrm_amplicon_length: 1000,
+            max_assay_region_length: 10_000,
+            max_homology_arm_length: 5000,
+            max_junctions: 100,
+            max_query_length: 10_000,
+            max_alignment_cells: 10_000_000,
+            max_page_size: 10_000,
+            max_mismatches: 10,
+            max_simulated_variants: 1000,
+            max_oligos: 2000,
+            max_oligo_length: 500,
+            max_protein_motifs: 500,
+            max_script_length: 64 * 1024,
+        }
+    }
+}
+
+/// 制約違反の種類とパラメータ。フロントエンドは `code` と `params` から表示文を組み立てる
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code", content = "params", rename_all = "snake_case")]
+pub enum ViolationCode {
+    /// min ≤ value ≤ max でない
+    OutOfRange {
+        min: Value,
+        max: Value,
+        value: Value,
+    },
+    TooLarge {
+        max: usize,
+        value: usize,
+    },
+    NotFinite {
+        value: Value,
+    },
+    /// 上限側のパラメータ `other` より大きい
+    ExceedsField {
+        other: String,
+        value: Value,
+        other_value: Value,
+    },
+    InvalidDelimiter {
+        delimiter: char,
+    },
+    /// #RRGGBB 形式の色でない
+    InvalidColor {
+        value: String,
+    },
+    Empty,
+    NotIupacCode {
+        character: char,
+    },
+    NotAminoAcid {
+        character: char,
+    },
+    /// 許容ミスマッチ数が配列長以上
+    NotLessThanLength {
+        length: usize,
+    },
+}
+
+/// 違反した制約1件（field はパラメータ名、ネストした値は "params.tm_min" のように表す）。
+/// message は英語の既定文
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintViolation {
+    pub field: String,
+    #[serde(flatten)]
+    pub code: ViolationCode,
+    pub message: String,
+}
+
+/// 入力検証で見つかった全ての制約違反
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[error("Invalid input: {}", self.summary())]
+pub struct ValidationErrors {
+    pub violations: Vec<ConstraintViolation>,
+}
+
+impl ValidationErrors {
+    /// "field message" を "; " で連結した一覧
+    pub fn summary(&self) -> String {
+        self.violations
+            .iter()
+            .map(|v| format!("{} {}", v.field, v.message))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    pub fn fields(&self) -> Vec<&str> {
+        self.violations.iter().map(|v| v.field.as_str()).collect()
+    }
+}
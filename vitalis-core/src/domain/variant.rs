@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 既知バリアント（position は0-based、reference は参照配列上の塩基）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Variant {
+    #[serde(default)]
+    pub id: String,
+    pub position: usize,
+    pub reference: String,
+    pub alternate: String,
+}
+
+impl Variant {
+    pub fn new(position: usize, reference: &str, alternate: &str) -> Self {
+        Self {
+            id: String::new(),
+            position,
+            reference: reference.to_string(),
+            alternate: alternate.to_string(),
+        }
+    }
+
+    /// 参照配列上で占める区間の終端（挿入の場合も1塩基として扱う）
+    pub fn end(&self) -> usize {
+        self.position + self.reference.len().max(1)
+    }
+
+    pub fn overlaps(&self, start: usize, end: usize) -> bool {
+        self.position < end && start < self.end()
+    }
+
+    /// 参照配列 `sequence`（offset から始まる部分配列）にバリアントを適用
+    pub fn apply(&self, sequence: &str, offset: usize) -> Option<String> {
+        let start = self.position.checked_sub(offset)?;
+        let end = start + self.reference.len();
+        if end > sequence.len() {
+            return None;
+        }
+        Some(format!(
+            "{}{}{}",
+            &sequence[..start],
+            self.alternate,
+            &sequence[end..]
+        ))
+    }
+}
+
+/// HGVS表記によるバリアント記述
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HgvsDescription {
+    /// g. 表記（配列上の1-based座標）
+    pub genomic: String,
+    /// c. 表記に用いたCDSアノテーションのID
+    pub feature_id: Option<String>,
+    pub coding: Option<String>,
+    /// 予測されるタンパク質変化（p.(...)、コーディング領域外の場合は None）
+    pub protein: Option<String>,
+}
+
+/// 配列IDごとのバリアントストア（位置順に保持）
+#[derive(Debug, Default)]
+pub struct VariantStore {
+    variants: HashMap<String, Vec<Variant>>,
+    next_id: usize,
+}
+
+impl VariantStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// バリアントを追加（IDが空なら採番する）
+    pub fn add(&mut self, seq_id: &str, mut variant: Variant) -> String {
+        if variant.id.is_empty() {
+            self.next_id += 1;
+            variant.id = format!("var_{}", self.next_id);
+        }
+        let id = variant.id.clone();
+        let entries = self.variants.entry(seq_id.to_string()).or_default();
+        entries.push(variant);
+        entries.sort_by_key(|v| v.position);
+        id
+    }
+
+    pub fn get_all(&self, seq_id: &str) -> &[Variant] {
+        self.variants
+            .get(seq_id)
+            .map(|variants| variants.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// 区間 [start, end) と重なるバリアントを取得
+    pub fn get_in_range(&self, seq_id: &str, start: usize, end: usize) -> Vec<Variant> {
+        self.get_all(seq_id)
+            .iter()
+            .filter(|v| v.overlaps(start, end))
+            .cloned()
+            .collect()
+    }
+
+    pub fn remove_sequence(&mut self, seq_id: &str) {
+        self.variants.remove(seq_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_store_keeps_position_order() {
+        let mut store = VariantStore::new();
+        store.add("seq_1", Variant::new(40, "A", "G"));
+        let id = store.add("seq_1", Variant::new(10, "C", "T"));
+
+        let variants = store.get_all("seq_1");
+        assert_eq!(variants[0].id, id);
+        assert_eq!(variants[1].position, 40);
+        assert_eq!(store.get_in_range("seq_1", 11, 41).len(), 1);
+    }
+
+    #[test]
+    fn test_apply_variant() {
+        let variant = Variant::new(12, "GA", "T");
+        assert_eq!(variant.apply("ACGTAC", 10).unwrap(), "ACTAC");
+        assert!(variant.apply("ACGTAC", 13).is_none());
+    }
+}
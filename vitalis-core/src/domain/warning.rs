@@ -0,0 +1,559 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 警告の分類（UI で種類ごとに絞り込むため）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCategory {
+    MeltingTemperature,
+    GcContent,
+    Length,
+    Repeat,
+    ThreePrimeEnd,
+    SecondaryStructure,
+    PositionWeighting,
+    Multiplex,
+    Amplicon,
+    StandardCurve,
+    Labeling,
+    Other,
+}
+
+/// 警告の重大度（UI での並べ替えと、設計結果を保存させない重大度の設定に使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningSeverity {
+    /// 参考情報（設計は問題なく使える）
+    Info,
+    /// 確認を推奨
+    Warning,
+    /// 実験が失敗する可能性が高い
+    Error,
+}
+
+/// アルゴリズムが出す警告の種類とパラメータ。`code` と `params` でフロントエンドに渡し、
+/// 表示文はフロントエンドが言語ごとに組み立てる（`Display` は英語の既定文）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "code", content = "params", rename_all = "snake_case")]
+pub enum WarningCode {
+    LowTm {
+        tm: f32,
+        min: f32,
+        max: f32,
+    },
+    HighTm {
+        tm: f32,
+        min: f32,
+        max: f32,
+    },
+    LowGc {
+        gc: f32,
+        min: f32,
+        max: f32,
+    },
+    HighGc {
+        gc: f32,
+        min: f32,
+        max: f32,
+    },
+    ShortPrimer {
+        length: usize,
+        min: usize,
+        max: usize,
+    },
+    LongPrimer {
+        length: usize,
+        min: usize,
+        max: usize,
+    },
+    /// 同じ塩基の4連続
+    SingleBaseRepeat {
+        repeat: String,
+    },
+    DinucleotideRepeat {
+        repeat: String,
+    },
+    /// 同じ塩基の6連続
+    LongSingleBaseRepeat {
+        repeat: String,
+    },
+    /// end は3'末端の3塩基
+    AtRichThreePrimeEnd {
+        end: String,
+    },
+    WeakThreePrimeTerminus {
+        end: String,
+    },
+    /// threshold はこの ΔG を下回ると警告する値
+    StrongSelfDimer {
+        delta_g: f32,
+        threshold: f32,
+    },
+    ModerateSelfDimer {
+        delta_g: f32,
+        threshold: f32,
+    },
+    StrongHairpin {
+        delta_g: f32,
+        threshold: f32,
+    },
+    ModerateHairpin {
+        delta_g: f32,
+        threshold: f32,
+    },
+    /// min_weight を下回る重みの塩基数
+    LowWeightBases {
+        count: usize,
+        min_weight: f64,
+    },
+    /// アッセイ内のオリゴ（"Forward primer"・"Reverse primer"・"Probe"）のセルフダイマー
+    OligoSelfDimer {
+        oligo: String,
+        delta_g: f32,
+        threshold: f32,
+    },
+    OligoHairpin {
+        oligo: String,
+        delta_g: f32,
+        threshold: f32,
+    },
+    ProbeMoreGThanC {
+        g: usize,
+        c: usize,
+    },
+    MultiplexTmDifference {
+        pair1: String,
+        pair2: String,
+        difference: f32,
+    },
+    StrongCrossReactivity {
+        pair1: String,
+        pair2: String,
+        delta_g: f32,
+    },
+    ModerateCrossReactivity {
+        pair1: String,
+        pair2: String,
+        delta_g: f32,
+    },
+    MultiplexAmpliconSizeDifference {
+        pair1: String,
+        length1: usize,
+        pair2: String,
+        length2: usize,
+    },
+    MultiplexGcDifference {
+        pair1: String,
+        pair2: String,
+        difference: f32,
+    },
+    AmpliconLowWindowGc {
+        gc: f32,
+        window: usize,
+    },
+    AmpliconHighWindowGc {
+        gc: f32,
+        window: usize,
+    },
+    /// position は1-based
+    AmpliconHomopolymer {
+        length: usize,
+        base: char,
+        position: usize,
+    },
+    AmpliconHairpins {
+        count: usize,
+        threshold: f32,
+        delta_g: f32,
+        position: usize,
+    },
+    AmpliconTandemRepeats {
+        percent: f32,
+    },
+    /// reaction は1-based
+    ProbeChannelConflict {
+        reaction: usize,
+        channel: String,
+        probe1: String,
+        probe2: String,
+    },
+    MultiplexExceedsChannels {
+        reaction: usize,
+        probes: usize,
+        channels: usize,
+    },
+    FluorophoreOutsideChannels {
+        probe: String,
+        fluorophore: String,
+    },
+    LowCopyStandard {
+        label: String,
+        copies: f64,
+        min_copies: f64,
+    },
+    FewStandards {
+        points: usize,
+        recommended: usize,
+    },
+    /// 構造化以前に保存された警告文
+    Text {
+        text: String,
+    },
+}
+
+impl WarningCode {
+    pub fn category(&self) -> WarningCategory {
+        use WarningCode::*;
+        match self {
+            LowTm { .. } | HighTm { .. } => WarningCategory::MeltingTemperature,
+            LowGc { .. } | HighGc { .. } => WarningCategory::GcContent,
+            ShortPrimer { .. } | LongPrimer { .. } => WarningCategory::Length,
+            SingleBaseRepeat { .. } | DinucleotideRepeat { .. } | LongSingleBaseRepeat { .. } => {
+                WarningCategory::Repeat
+            }
+            AtRichThreePrimeEnd { .. } | WeakThreePrimeTerminus { .. } => {
+                WarningCategory::ThreePrimeEnd
+            }
+            StrongSelfDimer { .. }
+            | ModerateSelfDimer { .. }
+            | StrongHairpin { .. }
+            | ModerateHairpin { .. }
+            | OligoSelfDimer { .. }
+            | OligoHairpin { .. } => WarningCategory::SecondaryStructure,
+            ProbeMoreGThanC { .. } => WarningCategory::GcContent,
+            LowWeightBases { .. } => WarningCategory::PositionWeighting,
+            MultiplexTmDifference { .. }
+            | StrongCrossReactivity { .. }
+            | ModerateCrossReactivity { .. }
+            | MultiplexAmpliconSizeDifference { .. }
+            | MultiplexGcDifference { .. }
+            | ProbeChannelConflict { .. }
+            | MultiplexExceedsChannels { .. } => WarningCategory::Multiplex,
+            AmpliconLowWindowGc { .. }
+            | AmpliconHighWindowGc { .. }
+            | AmpliconHomopolymer { .. }
+            | AmpliconHairpins { .. }
+            | AmpliconTandemRepeats { .. } => WarningCategory::Amplicon,
+            LowCopyStandard { .. } | FewStandards { .. } => WarningCategory::StandardCurve,
+            FluorophoreOutsideChannels { .. } => WarningCategory::Labeling,
+            Text { .. } => WarningCategory::Other,
+        }
+    }
+
+    /// 既定の重大度。強い二次構造や長い単一塩基の連続のように増幅を妨げるものを
+    /// error、推奨範囲からの逸脱を warning、結果に影響しにくいものを info とする
+    pub fn severity(&self) -> WarningSeverity {
+        use WarningCode::*;
+        match self {
+            LongSingleBaseRepeat { .. }
+            | StrongSelfDimer { .. }
+            | StrongHairpin { .. }
+            | OligoSelfDimer { .. }
+            | OligoHairpin { .. }
+            | StrongCrossReactivity { .. }
+            | ProbeChannelConflict { .. }
+            | MultiplexExceedsChannels { .. }
+            | FluorophoreOutsideChannels { .. } => WarningSeverity::Error,
+            WeakThreePrimeTerminus { .. }
+            | MultiplexAmpliconSizeDifference { .. }
+            | MultiplexGcDifference { .. }
+            | AmpliconTandemRepeats { .. }
+            | FewStandards { .. } => WarningSeverity::Info,
+            _ => WarningSeverity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for WarningCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use WarningCode::*;
+        match self {
+            LowTm { tm, min, max } => {
+                write!(f, "Low Tm: {:.1}°C (recommended: {}-{}°C)", tm, min, max)
+            }
+            HighTm { tm, min, max } => {
+                write!(f, "High Tm: {:.1}°C (recommended: {}-{}°C)", tm, min, max)
+            }
+            LowGc { gc, min, max } => write!(
+                f,
+                "Low GC content: {:.1}% (recommended: {}-{}%)",
+                gc, min, max
+            ),
+            HighGc { gc, min, max } => write!(
+                f,
+                "High GC content: {:.1}% (recommended: {}-{}%)",
+                gc, min, max
+            ),
+            ShortPrimer { length, min, max } => write!(
+                f,
+                "Short primer: {} bp (recommended: {}-{} bp)",
+                length, min, max
+            ),
+            LongPrimer { length, min, max } => write!(
+                f,
+                "Long primer: {} bp (recommended: {}-{} bp)",
+                length, min, max
+            ),
+            SingleBaseRepeat { repeat } => write!(
+                f,
+                "Long single nucleotide repeat {} detected in primer",
+                repeat
+            ),
+            DinucleotideRepeat { .. } => write!(f, "Dinucleotide repeat pattern detected in primer"),
+            LongSingleBaseRepeat { repeat } => {
+                write!(f, "Very long single nucleotide repeat {} detected", repeat)
+            }
+            AtRichThreePrimeEnd { .. } => write!(f, "3' end is AT-rich and may have weak binding"),
+            WeakThreePrimeTerminus { .. } => write!(f, "3' end has weak AA/TT terminus"),
+            StrongSelfDimer { delta_g, .. } => {
+                write!(f, "Strong self-dimer potential: {:.1} kcal/mol", delta_g)
+            }
+            ModerateSelfDimer { delta_g, .. } => {
+                write!(f, "Moderate self-dimer potential: {:.1} kcal/mol", delta_g)
+            }
+            StrongHairpin { delta_g, .. } => {
+                write!(f, "Strong hairpin potential: {:.1} kcal/mol", delta_g)
+            }
+            ModerateHairpin { delta_g, .. } => {
+                write!(f, "Moderate hairpin potential: {:.1} kcal/mol", delta_g)
+            }
+            LowWeightBases { count, .. } => write!(f, "{} low-weight bases in primer", count),
+            OligoSelfDimer {
+                oligo,
+                delta_g,
+                threshold,
+            } => write!(
+                f,
+                "{} self-dimer ΔG {:.1} kcal/mol is below {:.1}",
+                oligo, delta_g, threshold
+            ),
+            OligoHairpin {
+                oligo,
+                delta_g,
+                threshold,
+            } => write!(
+                f,
+                "{} hairpin ΔG {:.1} kcal/mol is below {:.1}",
+                oligo, delta_g, threshold
+            ),
+            ProbeMoreGThanC { .. } => write!(f, "Probe has more G than C on both strands"),
+            MultiplexTmDifference {
+                pair1,
+                pair2,
+                difference,
+            } => write!(
+                f,
+                "Large Tm difference between {} and {} ({:.1}°C)",
+                pair1, pair2, difference
+            ),
+            StrongCrossReactivity {
+                pair1,
+                pair2,
+                delta_g,
+            } => write!(
+                f,
+                "Strong cross-reactivity detected between {} and {} (ΔG: {:.1} kcal/mol)",
+                pair1, pair2, delta_g
+            ),
+            ModerateCrossReactivity {
+                pair1,
+                pair2,
+                delta_g,
+            } => write!(
+                f,
+                "Moderate cross-reactivity detected between {} and {} (ΔG: {:.1} kcal/mol)",
+                pair1, pair2, delta_g
+            ),
+            MultiplexAmpliconSizeDifference {
+                pair1,
+                length1,
+                pair2,
+                length2,
+            } => write!(
+                f,
+                "Large amplicon size difference between {} ({} bp) and {} ({} bp)",
+                pair1, length1, pair2, length2
+            ),
+            MultiplexGcDifference {
+                pair1,
+                pair2,
+                difference,
+            } => write!(
+                f,
+                "Large GC content difference between {} and {} ({:.1}%)",
+                pair1, pair2, difference
+            ),
+            AmpliconLowWindowGc { gc, window } => {
+                write!(f, "GC drops to {:.0}% within a {} bp window", gc, window)
+            }
+            AmpliconHighWindowGc { gc, window } => {
+                write!(f, "GC reaches {:.0}% within a {} bp window", gc, window)
+            }
+            AmpliconHomopolymer {
+                length,
+                base,
+                position,
+            } => write!(f, "{}-base poly-{} run at {}", length, base, position),
+            AmpliconHairpins {
+                count,
+                threshold,
+                delta_g,
+                position,
+            } => write!(
+                f,
+                "{} hairpin(s) below {:.1} kcal/mol (strongest {:.1} at {})",
+                count, threshold, delta_g, position
+            ),
+            AmpliconTandemRepeats { percent } => {
+                write!(f, "Tandem repeats cover {:.0}% of the amplicon", percent)
+            }
+            ProbeChannelConflict {
+                reaction,
+                channel,
+                probe1,
+                probe2,
+            } => write!(
+                f,
+                "Reaction {}: {} and {} share the {} channel",
+                reaction, probe1, probe2, channel
+            ),
+            MultiplexExceedsChannels {
+                reaction,
+                probes,
+                channels,
+            } => write!(
+                f,
+                "Reaction {} has {} probes but only {} usable channels",
+                reaction, probes, channels
+            ),
+            FluorophoreOutsideChannels { probe, fluorophore } => write!(
+                f,
+                "{} on {} is not detected by any instrument channel",
+                fluorophore, probe
+            ),
+            LowCopyStandard {
+                label,
+                copies,
+                min_copies,
+            } => write!(
+                f,
+                "{} has {:.1} copies per reaction; Poisson sampling makes points below {} copies unreliable",
+                label, copies, min_copies
+            ),
+            FewStandards {
+                points,
+                recommended,
+            } => write!(
+                f,
+                "Only {} standards; at least {} points are recommended to define efficiency",
+                points, recommended
+            ),
+            Text { text } => write!(f, "{}", text),
+        }
+    }
+}
+
+/// フロントエンドに渡す警告。`code`・`params`（判定に使った値）に加えて重大度、
+/// 絞り込み用の分類と英語の既定文を持つ。
+/// 文字列だけの古い警告（保存済みプロジェクトなど）も `text` コードとして読み込める
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "WarningRepr")]
+pub struct Warning {
+    #[serde(flatten)]
+    pub code: WarningCode,
+    pub severity: WarningSeverity,
+    pub category: WarningCategory,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(code: WarningCode) -> Self {
+        Self {
+            severity: code.severity(),
+            category: code.category(),
+            message: code.to_string(),
+            code,
+        }
+    }
+}
+
+impl From<WarningCode> for Warning {
+    fn from(code: WarningCode) -> Self {
+        Self::new(code)
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WarningRepr {
+    Structured {
+        #[serde(flatten)]
+        code: WarningCode,
+    },
+    Text(String),
+}
+
+impl From<WarningRepr> for Warning {
+    fn from(repr: WarningRepr) -> Self {
+        match repr {
+            WarningRepr::Structured { code } => Self::new(code),
+            WarningRepr::Text(text) => Self::new(WarningCode::Text { text }),
+        }
+    }
+}
+
+/// 設計結果をプロジェクトに保存させない重大度の設定（既定は error のみ）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarningPolicy {
+    pub blocking: Vec<WarningSeverity>,
+}
+
+impl Default for WarningPolicy {
+    fn default() -> Self {
+        Self {
+            blocking: vec![WarningSeverity::Error],
+        }
+    }
+}
+
+impl WarningPolicy {
+    pub fn blocks<'a>(&self, mut warnings: impl Iterator<Item = &'a Warning>) -> bool {
+        warnings.any(|warning| self.blocking.contains(&warning.severity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_json_and_policy() {
+        let warning = Warning::new(WarningCode::StrongHairpin {
+            delta_g: -6.0,
+            threshold: -5.0,
+        });
+        let json = serde_json::to_value(&warning).unwrap();
+        assert_eq!(json["code"], "strong_hairpin");
+        assert_eq!(json["params"]["threshold"], -5.0);
+        assert_eq!(json["severity"], "error");
+        assert_eq!(json["category"], "secondary_structure");
+
+        // 古いプロジェクトの文字列の警告は warning として読み込む
+        let legacy: Warning = serde_json::from_str("\"Low Tm\"").unwrap();
+        assert_eq!(legacy.severity, WarningSeverity::Warning);
+
+        let policy = WarningPolicy::default();
+        assert!(policy.blocks([&warning, &legacy].into_iter()));
+        assert!(!policy.blocks(std::iter::once(&legacy)));
+        let strict = WarningPolicy {
+            blocking: vec![WarningSeverity::Warning, WarningSeverity::Error],
+        };
+        assert!(strict.blocks(std::iter::once(&legacy)));
+    }
+}
@@ -0,0 +1,126 @@
+// Infrastructure: ZIPアーカイブの書き出し（deflate 圧縮、必要なら ZIP64）
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("Cannot write archive: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Cannot write archive: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+/// `path` に ZIP を書き出す。4 GiB を超えるエントリやアーカイブ、65535 を超えるエントリは
+/// ZIP64 で記録する。書き終えるまでは隣の `.part` ファイルに書き、`finish` で置き換えるので、
+/// 途中で失敗しても（drop しても）書きかけのアーカイブは `path` に残らない
+pub struct ZipWriter {
+    writer: Option<zip::ZipWriter<BufWriter<File>>>,
+    options: SimpleFileOptions,
+    path: PathBuf,
+    part: PathBuf,
+}
+
+impl ZipWriter {
+    /// `modified` を全エントリの更新日時として記録する
+    pub fn create(path: &Path, modified: DateTime<Utc>) -> Result<Self, ArchiveError> {
+        let mut options =
+            SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        // ZIP の日時は 1980〜2107 年の範囲のみ（範囲外なら zip の既定値を使う）
+        if let Ok(time) = zip::DateTime::from_date_and_time(
+            modified.year().clamp(1980, 2107) as u16,
+            modified.month() as u8,
+            modified.day() as u8,
+            modified.hour() as u8,
+            modified.minute() as u8,
+            modified.second() as u8,
+        ) {
+            options = options.last_modified_time(time);
+        }
+        let mut part = path.as_os_str().to_owned();
+        part.push(".part");
+        let part = PathBuf::from(part);
+        Ok(Self {
+            writer: Some(zip::ZipWriter::new(BufWriter::new(File::create(&part)?))),
+            options,
+            path: path.to_path_buf(),
+            part,
+        })
+    }
+
+    pub fn add_file(&mut self, name: &str, data: &[u8]) -> Result<(), ArchiveError> {
+        let writer = self
+            .writer
+            .as_mut()
+            .expect("writer is present until finish");
+        let options = self
+            .options
+            .large_file(data.len() as u64 >= u32::MAX as u64);
+        writer.start_file(name, options)?;
+        writer.write_all(data)?;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<(), ArchiveError> {
+        if let Some(writer) = self.writer.take() {
+            writer.finish()?.flush()?;
+            std::fs::rename(&self.part, &self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ZipWriter {
+    fn drop(&mut self) {
+        if self.writer.take().is_some() {
+            let _ = std::fs::remove_file(&self.part);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::io::Read;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_compressed_entries_round_trip() {
+        let file = NamedTempFile::new().unwrap();
+        let modified = Utc.with_ymd_and_hms(2024, 5, 17, 13, 45, 30).unwrap();
+        let mut writer = ZipWriter::create(file.path(), modified).unwrap();
+        let text = "ACGT".repeat(1000);
+        writer.add_file("sequences/a.gb", text.as_bytes()).unwrap();
+        writer.add_file("manifest.json", b"{}").unwrap();
+        writer.finish().unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(file.path()).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut entry = archive.by_name("sequences/a.gb").unwrap();
+        assert_eq!(entry.compression(), CompressionMethod::Deflated);
+        assert!(entry.compressed_size() < entry.size());
+        let time = entry.last_modified().unwrap();
+        assert_eq!(
+            (time.year(), time.month(), time.day(), time.hour()),
+            (2024, 5, 17, 13)
+        );
+        let mut read = String::new();
+        entry.read_to_string(&mut read).unwrap();
+        assert_eq!(read, text);
+    }
+
+    #[test]
+    fn test_unfinished_archive_is_discarded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bundle.zip");
+        let mut writer = ZipWriter::create(&path, Utc::now()).unwrap();
+        writer.add_file("a.txt", b"hello").unwrap();
+        drop(writer);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,149 @@
+// Infrastructure layer: Batch job files (YAML or JSON)
+use crate::domain::batch::{BatchInput, BatchJob};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// レポートの既定のファイル名（出力ディレクトリ内）
+const DEFAULT_REPORT: &str = "batch_report.json";
+
+#[derive(Error, Debug)]
+pub enum BatchJobError {
+    #[error("Cannot read job file {0}: {1}")]
+    Io(String, std::io::Error),
+    #[error("Invalid JSON job file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid YAML job file: {0}")]
+    Yaml(#[from] serde_yaml_ng::Error),
+    #[error("Duplicate input id: {0}")]
+    DuplicateInput(String),
+    #[error("Step {0} refers to unknown input '{1}'")]
+    UnknownInput(usize, String),
+}
+
+/// ジョブファイルを読み、入力と出力ディレクトリの相対パスをジョブファイルのディレクトリ基準に、
+/// レポートの相対パスを出力ディレクトリ基準にする。
+/// 拡張子が .yaml / .yml なら YAML、それ以外は JSON として読む
+pub fn load_batch_job(path: &Path) -> Result<BatchJob, BatchJobError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| BatchJobError::Io(path.display().to_string(), e))?;
+    let mut job = parse_batch_job(&text, is_yaml(path))?;
+
+    let base = path.parent().unwrap_or(Path::new("."));
+    let output_dir = resolve(base, job.output_dir.as_deref().unwrap_or(Path::new("")));
+    for input in &mut job.inputs {
+        input.path = resolve(base, &input.path);
+    }
+    job.report = Some(resolve(
+        &output_dir,
+        job.report.as_deref().unwrap_or(Path::new(DEFAULT_REPORT)),
+    ));
+    job.output_dir = Some(output_dir);
+    Ok(job)
+}
+
+/// ジョブの文字列を読み、入力 ID の重複とステップの参照を確認する
+pub fn parse_batch_job(text: &str, yaml: bool) -> Result<BatchJob, BatchJobError> {
+    let job: BatchJob = if yaml {
+        serde_yaml_ng::from_str(text)?
+    } else {
+        serde_json::from_str(text)?
+    };
+
+    for (i, input) in job.inputs.iter().enumerate() {
+        if job.inputs[..i].iter().any(|other| other.id == input.id) {
+            return Err(BatchJobError::DuplicateInput(input.id.clone()));
+        }
+    }
+    for (i, step) in job.steps.iter().enumerate() {
+        if let Some(input) = step.input() {
+            if !job.inputs.iter().any(|other| other.id == input) {
+                return Err(BatchJobError::UnknownInput(i + 1, input.to_string()));
+            }
+        }
+    }
+    Ok(job)
+}
+
+/// 取り込む形式（指定がなければ .fq / .fastq を FASTQ、それ以外を FASTA とする）
+pub fn input_format(input: &BatchInput) -> String {
+    if let Some(format) = &input.format {
+        return format.to_ascii_lowercase();
+    }
+    let name = input
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    if name.ends_with(".fq") || name.ends_with(".fastq") {
+        "fastq".to_string()
+    } else {
+        "fasta".to_string()
+    }
+}
+
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+        .unwrap_or(false)
+}
+
+fn resolve(base: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::batch::BatchStep;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_yaml_job_resolves_paths() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("overnight.yaml");
+        std::fs::write(
+            &path,
+            "name: overnight
+output_dir: results
+inputs:
+  - id: plasmid
+    path: data/pUC19.fasta
+  - id: reads
+    path: /data/run1.fq
+steps:
+  - analysis: design_primers
+    input: plasmid
+    regions:
+      - { start: 100, end: 400 }
+    output: primers.json
+  - analysis: save_project
+    output: session.vitalis
+",
+        )
+        .unwrap();
+
+        let job = load_batch_job(&path).unwrap();
+        assert_eq!(job.output_dir, Some(dir.path().join("results")));
+        assert_eq!(
+            job.report,
+            Some(dir.path().join("results/batch_report.json"))
+        );
+        assert_eq!(job.inputs[0].path, dir.path().join("data/pUC19.fasta"));
+        assert_eq!(input_format(&job.inputs[0]), "fasta");
+        assert_eq!(input_format(&job.inputs[1]), "fastq");
+        assert!(matches!(
+            &job.steps[0],
+            BatchStep::DesignPrimers { regions, params: None, .. } if regions[0].start == 100
+        ));
+
+        let unknown = r#"{ "inputs": [], "steps": [{ "analysis": "stats", "input": "x", "output": "s.json" }] }"#;
+        assert!(matches!(
+            parse_batch_job(unknown, false),
+            Err(BatchJobError::UnknownInput(1, _))
+        ));
+    }
+}
@@ -0,0 +1,241 @@
+// Infrastructure layer: BED region files
+use crate::domain::bed::BedRecord;
+use crate::domain::feature::SequenceFeature;
+use crate::domain::Strand;
+
+/// BED から取り込んだ区間のアノテーション種類
+const BED_FEATURE_TYPE: &str = "misc_feature";
+
+pub struct BedParser;
+
+impl Default for BedParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BedParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// BED3〜BED6 の列を読む（"track" / "browser" / "#" の行は飛ばし、7列目以降は無視する）。
+    /// 列はタブ区切り、タブがなければ空白区切りとみなす
+    pub fn parse(&self, content: &str) -> Result<Vec<BedRecord>, String> {
+        let mut records = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty()
+                || line.starts_with('#')
+                || line.starts_with("track")
+                || line.starts_with("browser")
+            {
+                continue;
+            }
+
+            let columns: Vec<&str> = if line.contains('\t') {
+                line.split('\t').collect()
+            } else {
+                line.split_whitespace().collect()
+            };
+            if columns.len() < 3 {
+                return Err(format!(
+                    "BED line {}: expected at least 3 columns, found {}",
+                    line_number,
+                    columns.len()
+                ));
+            }
+
+            let position = |value: &str, name: &str| -> Result<usize, String> {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| format!("BED line {}: invalid {} '{}'", line_number, name, value))
+            };
+            let start = position(columns[1], "start")?;
+            let end = position(columns[2], "end")?;
+            if end < start {
+                return Err(format!(
+                    "BED line {}: end {} is before start {}",
+                    line_number, end, start
+                ));
+            }
+
+            let score =
+                match columns.get(4).copied() {
+                    None | Some(".") => None,
+                    Some(value) => Some(value.parse::<f64>().map_err(|_| {
+                        format!("BED line {}: invalid score '{}'", line_number, value)
+                    })?),
+                };
+            let strand = match columns.get(5).copied() {
+                None | Some(".") => None,
+                Some("+") => Some(Strand::Forward),
+                Some("-") => Some(Strand::Reverse),
+                Some(value) => {
+                    return Err(format!(
+                        "BED line {}: invalid strand '{}'",
+                        line_number, value
+                    ))
+                }
+            };
+
+            records.push(BedRecord {
+                chrom: columns[0].to_string(),
+                start,
+                end,
+                name: columns
+                    .get(3)
+                    .filter(|&&name| !name.is_empty() && name != ".")
+                    .map(|name| name.to_string()),
+                score,
+                strand,
+            });
+        }
+
+        Ok(records)
+    }
+
+    /// BED の行をアノテーションに変換する（名前は label、スコアは score に入れる）
+    pub fn to_features(&self, records: &[BedRecord]) -> Vec<SequenceFeature> {
+        records
+            .iter()
+            .map(|record| {
+                let mut feature = SequenceFeature::new(
+                    BED_FEATURE_TYPE,
+                    record.start,
+                    record.end,
+                    record.strand.unwrap_or(Strand::Forward),
+                );
+                feature
+                    .qualifiers
+                    .insert("source".to_string(), "BED".to_string());
+                if let Some(name) = &record.name {
+                    feature.qualifiers.insert("label".to_string(), name.clone());
+                }
+                if let Some(score) = record.score {
+                    feature
+                        .qualifiers
+                        .insert("score".to_string(), score.to_string());
+                }
+                feature
+            })
+            .collect()
+    }
+}
+
+pub struct BedWriter;
+
+impl Default for BedWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BedWriter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// track 行に続けて BED6 で書く（スコアは 0〜1000 の整数に丸め、名前・鎖がなければ "."）
+    pub fn write(&self, track_name: &str, records: &[BedRecord]) -> String {
+        let mut out = format!("track name=\"{}\"\n", track_name.replace('"', "'"));
+        for record in records {
+            let score = record
+                .score
+                .map_or(0, |score| score.round().clamp(0.0, 1000.0) as u16);
+            let strand = match record.strand {
+                Some(Strand::Forward) => "+",
+                Some(Strand::Reverse) => "-",
+                None => ".",
+            };
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                record.chrom,
+                record.start,
+                record.end,
+                record
+                    .name
+                    .as_deref()
+                    .unwrap_or(".")
+                    .replace(['\t', '\n'], " "),
+                score,
+                strand
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bed3_and_bed6() {
+        let bed = "browser position chr1:1-100\n\
+            track name=peaks\n\
+            # comment\n\
+            chr1\t10\t20\n\
+            chr1\t30\t45\tpeak_2\t500\t-\t30\t45\n\
+            chr1 50 60 peak_3 . .\n";
+        let parser = BedParser::new();
+        let records = parser.parse(bed).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!((records[0].start, records[0].end), (10, 20));
+        assert_eq!(records[0].name, None);
+        assert_eq!(records[1].name.as_deref(), Some("peak_2"));
+        assert_eq!(records[1].score, Some(500.0));
+        assert_eq!(records[1].strand, Some(Strand::Reverse));
+        assert_eq!(
+            (records[2].chrom.as_str(), records[2].strand),
+            ("chr1", None)
+        );
+
+        let features = parser.to_features(&records);
+        assert_eq!(features[1].label(), "peak_2");
+        assert_eq!(features[1].strand, Strand::Reverse);
+        assert_eq!(features[0].feature_type, "misc_feature");
+
+        let error = parser.parse("chr1\t10\t20\nchr1\t40\t30\n").unwrap_err();
+        assert!(error.contains("line 2"));
+        assert!(parser.parse("chr1\t10\n").is_err());
+    }
+
+    #[test]
+    fn test_write_bed6_round_trip() {
+        let records = vec![
+            BedRecord {
+                chrom: "pUC19".to_string(),
+                start: 0,
+                end: 12,
+                name: Some("fwd".to_string()),
+                score: Some(612.4),
+                strand: Some(Strand::Forward),
+            },
+            BedRecord {
+                chrom: "pUC19".to_string(),
+                start: 40,
+                end: 52,
+                name: None,
+                score: None,
+                strand: None,
+            },
+        ];
+        let text = BedWriter::new().write("primer sites", &records);
+        assert_eq!(
+            text,
+            "track name=\"primer sites\"\n\
+             pUC19\t0\t12\tfwd\t612\t+\n\
+             pUC19\t40\t52\t.\t0\t.\n"
+        );
+
+        let parsed = BedParser::new().parse(&text).unwrap();
+        assert_eq!(parsed[0].name.as_deref(), Some("fwd"));
+        assert_eq!(
+            (parsed[1].start, parsed[1].end, parsed[1].name.clone()),
+            (40, 52, None)
+        );
+    }
+}
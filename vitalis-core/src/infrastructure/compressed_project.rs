@@ -0,0 +1,237 @@
+// Infrastructure layer: Compressed binary project files (MessagePack + zstd)
+use crate::domain::project::{ProjectSequence, ProjectSnapshot};
+use crate::infrastructure::project_store::ProjectError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// ファイル先頭の識別子（SQLite のファイルと区別する）
+const MAGIC: &[u8; 8] = b"VITALISZ";
+
+/// 圧縮プロジェクトの形式（識別子に続けてリトルエンディアンの u32 で記録する）。
+/// 2: ファイルから取り込んだ配列は塩基の代わりにレコードの位置を保存
+const FORMAT_VERSION: u32 = 2;
+
+/// 古い形式の内容を1つ新しい形式に書き換える処理。`MIGRATIONS[i]` は形式 i + 1 を i + 2 にする。
+/// 形式を上げるときは配列の長さが合わなくなるので、必ず書き換えを加えることになる
+const MIGRATIONS: [fn(&mut serde_json::Value); FORMAT_VERSION as usize - 1] = [add_file_records];
+
+/// 形式 1 → 2: 形式 1 はどの配列も塩基を保存していたので、レコードの位置はない
+fn add_file_records(contents: &mut serde_json::Value) {
+    if let Some(sequences) = contents["sequences"].as_array_mut() {
+        for sequence in sequences {
+            sequence["file"] = serde_json::Value::Null;
+        }
+    }
+}
+
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// 圧縮する内容。構造体はフィールド名付きの MessagePack にするので、
+/// フィールドが増えても serde の既定値で読める
+#[derive(Serialize, Deserialize)]
+struct Contents<S> {
+    saved_at: DateTime<Utc>,
+    next_id: usize,
+    sequences: S,
+}
+
+/// `path` の先頭が圧縮プロジェクトの識別子か
+pub fn is_compressed_project(path: &Path) -> bool {
+    let mut magic = [0u8; MAGIC.len()];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| &magic == MAGIC)
+}
+
+/// `snapshot` を `path` に書く。隣の一時ファイルに書き終えてから置き換えるので、
+/// 途中で失敗しても前の内容が残る
+pub fn save_compressed_project(
+    path: &Path,
+    snapshot: &ProjectSnapshot,
+    saved_at: DateTime<Utc>,
+) -> Result<(), ProjectError> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = Path::new(&temporary);
+
+    let write = || -> Result<(), ProjectError> {
+        let mut writer = BufWriter::new(File::create(temporary)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        let mut encoder = zstd::Encoder::new(writer, COMPRESSION_LEVEL)?;
+        // 読み込み時に展開した内容の破損を検出する
+        encoder.include_checksum(true)?;
+        let contents = Contents {
+            saved_at,
+            next_id: snapshot.next_id,
+            sequences: &snapshot.sequences,
+        };
+        rmp_serde::encode::write_named(&mut encoder, &contents)
+            .map_err(|e| ProjectError::Corrupted(e.to_string()))?;
+        encoder
+            .finish()?
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        Ok(())
+    };
+    match write() {
+        Ok(()) => Ok(std::fs::rename(temporary, path)?),
+        Err(e) => {
+            let _ = std::fs::remove_file(temporary);
+            Err(e)
+        }
+    }
+}
+
+/// `path` の圧縮プロジェクトを読み、保存内容と保存日時を返す。古い形式は
+/// 現在の形式に書き換えてから読む
+pub fn load_compressed_project(
+    path: &Path,
+) -> Result<(ProjectSnapshot, DateTime<Utc>), ProjectError> {
+    if !path.is_file() {
+        return Err(ProjectError::NotFound(path.display().to_string()));
+    }
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut header = [0u8; MAGIC.len() + 4];
+    reader
+        .read_exact(&mut header)
+        .map_err(|_| ProjectError::Corrupted("missing header".to_string()))?;
+    if &header[..MAGIC.len()] != MAGIC {
+        return Err(ProjectError::Corrupted(
+            "not a compressed project".to_string(),
+        ));
+    }
+    let version = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(ProjectError::UnsupportedVersion(version as i64));
+    }
+
+    let mut decoder = zstd::Decoder::with_buffer(reader)?;
+    let corrupted = |e: rmp_serde::decode::Error| ProjectError::Corrupted(e.to_string());
+    let contents: Contents<Vec<ProjectSequence>> = if version == FORMAT_VERSION {
+        rmp_serde::from_read(&mut decoder).map_err(corrupted)?
+    } else {
+        let mut value: serde_json::Value = rmp_serde::from_read(&mut decoder).map_err(corrupted)?;
+        for migrate in &MIGRATIONS[version as usize - 1..] {
+            migrate(&mut value);
+        }
+        serde_json::from_value(value)?
+    };
+    // チェックサムはフレームの終わりまで読んだときに確かめられる
+    std::io::copy(&mut decoder, &mut std::io::sink())
+        .map_err(|e| ProjectError::Corrupted(e.to_string()))?;
+    Ok((
+        ProjectSnapshot {
+            sequences: contents.sequences,
+            next_id: contents.next_id,
+        },
+        contents.saved_at,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::feature::SequenceFeature;
+    use crate::domain::{SequenceMetadata, Strand, Topology};
+    use tempfile::TempDir;
+
+    fn snapshot() -> ProjectSnapshot {
+        let mut gene = SequenceFeature::new("gene", 2, 6, Strand::Reverse);
+        gene.qualifiers
+            .insert("gene".to_string(), "lacZ".to_string());
+        let sequence = "ACGT".repeat(1000);
+        ProjectSnapshot {
+            sequences: vec![ProjectSequence {
+                seq_id: "seq_2".to_string(),
+                metadata: SequenceMetadata {
+                    id: "SEQ_2".to_string(),
+                    name: "plasmid".to_string(),
+                    length: sequence.len(),
+                    topology: Topology::Circular,
+                    file_path: None,
+                    derived_from: None,
+                },
+                quality: Some("I".repeat(sequence.len())),
+                sequence,
+                file: None,
+                features: vec![gene],
+                primer_pairs: Vec::new(),
+            }],
+            next_id: 3,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_is_compressed() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.vitalis");
+        let saved_at = Utc::now();
+        save_compressed_project(&path, &snapshot(), saved_at).unwrap();
+        assert!(is_compressed_project(&path));
+        assert!(std::fs::metadata(&path).unwrap().len() < 1000);
+
+        let (loaded, loaded_at) = load_compressed_project(&path).unwrap();
+        assert_eq!(loaded_at, saved_at);
+        assert_eq!(loaded.next_id, 3);
+        let expected = snapshot();
+        let (sequence, expected) = (&loaded.sequences[0], &expected.sequences[0]);
+        assert_eq!(sequence.sequence, expected.sequence);
+        assert_eq!(sequence.quality, expected.quality);
+        assert_eq!(sequence.metadata.topology, Topology::Circular);
+        assert_eq!(sequence.features, expected.features);
+    }
+
+    #[test]
+    fn test_rejects_corrupted_and_newer_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.vitalis");
+        save_compressed_project(&path, &snapshot(), Utc::now()).unwrap();
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 6;
+        bytes[last] ^= 0xff;
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            load_compressed_project(&path),
+            Err(ProjectError::Corrupted(_))
+        ));
+
+        bytes[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            load_compressed_project(&path),
+            Err(ProjectError::UnsupportedVersion(version)) if version == FORMAT_VERSION as i64 + 1
+        ));
+        assert!(!is_compressed_project(&dir.path().join("missing")));
+    }
+
+    #[test]
+    fn test_upgrades_format_1() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("old.vitalis");
+        let mut sequences = serde_json::to_value(&snapshot().sequences).unwrap();
+        sequences[0].as_object_mut().unwrap().remove("file");
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(1u32.to_le_bytes());
+        let contents = Contents {
+            saved_at: Utc::now(),
+            next_id: 3,
+            sequences,
+        };
+        let encoded = rmp_serde::to_vec_named(&contents).unwrap();
+        bytes.extend(zstd::encode_all(encoded.as_slice(), COMPRESSION_LEVEL).unwrap());
+        std::fs::write(&path, bytes).unwrap();
+
+        let (loaded, _) = load_compressed_project(&path).unwrap();
+        assert_eq!(
+            loaded.sequences[0].sequence,
+            snapshot().sequences[0].sequence
+        );
+        assert_eq!(loaded.sequences[0].file, None);
+    }
+}
@@ -0,0 +1,318 @@
+// Infrastructure layer: NCBI Entrez E-utilities (efetch of nuccore slices)
+use crate::domain::reference::EntrezSettings;
+use std::io::Read;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// API キーなしで1秒に3件、ありで10件（NCBI の利用条件）
+const REQUEST_INTERVAL: Duration = Duration::from_millis(334);
+const REQUEST_INTERVAL_WITH_KEY: Duration = Duration::from_millis(100);
+/// 応答として読む最大バイト数
+const MAX_RESPONSE_BYTES: u64 = 64 * 1024 * 1024;
+/// E-utilities の利用者として送るツール名
+const TOOL: &str = "vitalis";
+
+#[derive(Error, Debug)]
+pub enum EntrezError {
+    #[error("Invalid accession: {0}")]
+    InvalidAccession(String),
+    #[error("Cannot reach NCBI Entrez ({0}); check the network connection or use a local reference genome")]
+    Offline(String),
+    #[error("NCBI Entrez did not respond within {0} s")]
+    Timeout(u64),
+    #[error("Accession not found in NCBI nuccore: {0}")]
+    NotFound(String),
+    #[error("NCBI Entrez returned HTTP {0}: {1}")]
+    Http(u16, String),
+    #[error("Invalid response from NCBI Entrez: {0}")]
+    InvalidResponse(String),
+}
+
+/// efetch で取得した配列
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntrezSequence {
+    pub accession: String,
+    /// FASTA ヘッダーのアクセッションに続く説明
+    pub description: String,
+    pub sequence: String,
+}
+
+/// E-utilities のクライアント。要求は回数制限の間隔を空けて1件ずつ送る
+pub struct EntrezClient {
+    config: Mutex<(EntrezSettings, ureq::Agent)>,
+    /// 前回の要求を送った時刻
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Default for EntrezClient {
+    fn default() -> Self {
+        Self::new(EntrezSettings::default())
+    }
+}
+
+impl EntrezClient {
+    pub fn new(settings: EntrezSettings) -> Self {
+        let agent = Self::agent(&settings);
+        Self {
+            config: Mutex::new((settings, agent)),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    fn agent(settings: &EntrezSettings) -> ureq::Agent {
+        ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(settings.timeout_secs.max(1)))
+            .user_agent(concat!("vitalis/", env!("CARGO_PKG_VERSION")))
+            .build()
+    }
+
+    pub fn settings(&self) -> EntrezSettings {
+        self.config
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+            .clone()
+    }
+
+    /// 接続設定を変える（回数制限の間隔は前回の要求から数え続ける）
+    pub fn configure(&self, settings: EntrezSettings) {
+        let agent = Self::agent(&settings);
+        *self.config.lock().unwrap_or_else(PoisonError::into_inner) = (settings, agent);
+    }
+
+    /// nuccore のアクセッション `accession` の [start, end)（0-based）を正鎖で取得する。
+    /// `end` が配列長を越えれば配列の終わりまで返す
+    pub fn fetch_region(
+        &self,
+        accession: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<EntrezSequence, EntrezError> {
+        if accession.is_empty()
+            || !accession
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.'))
+        {
+            return Err(EntrezError::InvalidAccession(accession.to_string()));
+        }
+        let (settings, agent) = self
+            .config
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        let mut request = agent
+            .get(&format!(
+                "{}/efetch.fcgi",
+                settings.base_url.trim_end_matches('/')
+            ))
+            .query("db", "nuccore")
+            .query("id", accession)
+            .query("rettype", "fasta")
+            .query("retmode", "text")
+            .query("seq_start", &(start + 1).to_string())
+            .query("seq_stop", &end.to_string())
+            .query("strand", "1")
+            .query("tool", TOOL);
+        if let Some(api_key) = &settings.api_key {
+            request = request.query("api_key", api_key);
+        }
+        self.wait_turn(if settings.api_key.is_some() {
+            REQUEST_INTERVAL_WITH_KEY
+        } else {
+            REQUEST_INTERVAL
+        });
+
+        let response = request
+            .call()
+            .map_err(|e| request_error(e, accession, settings.timeout_secs))?;
+        let mut body = String::new();
+        response
+            .into_reader()
+            .take(MAX_RESPONSE_BYTES)
+            .read_to_string(&mut body)
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => {
+                    EntrezError::Timeout(settings.timeout_secs)
+                }
+                _ => EntrezError::InvalidResponse(e.to_string()),
+            })?;
+        parse_fasta_response(&body, accession)
+    }
+
+    /// 前回の要求から回数制限の間隔が空くまで待つ。待つ間は他の要求も待たせる
+    fn wait_turn(&self, interval: Duration) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(wait) = last_request.and_then(|last| interval.checked_sub(last.elapsed())) {
+            std::thread::sleep(wait);
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+fn request_error(error: ureq::Error, accession: &str, timeout_secs: u64) -> EntrezError {
+    match error {
+        // 解釈できない ID には 400 を返す
+        ureq::Error::Status(400 | 404, _) => EntrezError::NotFound(accession.to_string()),
+        ureq::Error::Status(code, response) => {
+            EntrezError::Http(code, response.status_text().to_string())
+        }
+        ureq::Error::Transport(transport) => {
+            let timed_out = std::error::Error::source(&transport)
+                .and_then(|source| source.downcast_ref::<std::io::Error>())
+                .is_some_and(|e| {
+                    matches!(
+                        e.kind(),
+                        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+                    )
+                });
+            if timed_out {
+                EntrezError::Timeout(timeout_secs)
+            } else {
+                EntrezError::Offline(transport.to_string())
+            }
+        }
+    }
+}
+
+/// efetch の FASTA 応答を読む。ID を解釈できなければ FASTA の代わりにエラー文を返す
+fn parse_fasta_response(body: &str, accession: &str) -> Result<EntrezSequence, EntrezError> {
+    let body = body.trim();
+    let Some(record) = body.strip_prefix('>') else {
+        if body.is_empty() || body.contains("Failed to understand id") || body.starts_with("Error")
+        {
+            return Err(EntrezError::NotFound(accession.to_string()));
+        }
+        return Err(EntrezError::InvalidResponse(
+            body.lines().next().unwrap_or_default().to_string(),
+        ));
+    };
+    let (header, bases) = record.split_once('\n').unwrap_or((record, ""));
+    let (id, description) = header
+        .trim()
+        .split_once(char::is_whitespace)
+        .unwrap_or((header.trim(), ""));
+    let sequence: String = bases
+        .lines()
+        .take_while(|line| !line.starts_with('>'))
+        .flat_map(|line| line.trim().chars())
+        .collect();
+    if let Some(invalid) = sequence.chars().find(|c| !c.is_ascii_alphabetic()) {
+        return Err(EntrezError::InvalidResponse(format!(
+            "unexpected character '{}' in the sequence",
+            invalid
+        )));
+    }
+    Ok(EntrezSequence {
+        accession: id.to_string(),
+        description: description.trim().to_string(),
+        sequence,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// `responses` を順に1件ずつ返すローカルサーバー（None は応答しない）。受け取った要求行を返す
+    fn serve(
+        responses: Vec<Option<(u16, &'static str)>>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/eutils", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                requests.push(line.trim().to_string());
+                while line.trim() != "" {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                }
+                match response {
+                    Some((status, body)) => write!(
+                        stream,
+                        "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap(),
+                    // 応答せずに待たせる
+                    None => std::thread::sleep(Duration::from_millis(2500)),
+                }
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    fn client(base_url: String) -> EntrezClient {
+        EntrezClient::new(EntrezSettings {
+            base_url,
+            api_key: None,
+            timeout_secs: 1,
+        })
+    }
+
+    #[test]
+    fn test_fetches_slices_within_rate_limit() {
+        let fasta = ">NC_045512.2:101-112 Severe acute respiratory syndrome coronavirus 2\nACGTAC\nGTNNAA\n";
+        let (url, server) = serve(vec![Some((200, fasta)), Some((200, fasta))]);
+        let client = client(url);
+
+        let started = Instant::now();
+        let first = client.fetch_region("NC_045512.2", 100, 112).unwrap();
+        client.fetch_region("NC_045512.2", 100, 112).unwrap();
+        assert!(started.elapsed() >= REQUEST_INTERVAL);
+        assert_eq!(first.sequence, "ACGTACGTNNAA");
+        assert_eq!(first.accession, "NC_045512.2:101-112");
+        assert!(first.description.starts_with("Severe acute"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /eutils/efetch.fcgi?db=nuccore&id=NC_045512.2"));
+        assert!(requests[0].contains("seq_start=101&seq_stop=112"));
+    }
+
+    #[test]
+    fn test_reports_missing_accessions_and_failures() {
+        let (url, server) = serve(vec![
+            Some((400, "Failed to understand id")),
+            Some((200, "Error: F a i l e d  to understand id: XX_1\n")),
+            Some((503, "")),
+            Some((200, "<html>maintenance</html>")),
+            None,
+        ]);
+        let client = client(url);
+        let fetch = || client.fetch_region("XX_1", 0, 10);
+        assert!(matches!(fetch(), Err(EntrezError::NotFound(_))));
+        assert!(matches!(fetch(), Err(EntrezError::NotFound(_))));
+        assert!(matches!(fetch(), Err(EntrezError::Http(503, _))));
+        assert!(matches!(fetch(), Err(EntrezError::InvalidResponse(_))));
+        assert!(matches!(fetch(), Err(EntrezError::Timeout(1))));
+        server.join().unwrap();
+
+        // 要求の URL に入れられない ID は送らない
+        assert!(matches!(
+            client.fetch_region("NC_1&db=pubmed", 0, 10),
+            Err(EntrezError::InvalidAccession(_))
+        ));
+        // 接続できない
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+        assert!(matches!(
+            self::client(url).fetch_region("NC_1", 0, 10),
+            Err(EntrezError::Offline(_))
+        ));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::domain::{Sequence, SequenceMetadata, Topology};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::{Range, Sequence, SequenceMetadata, Strand, Topology};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -227,6 +228,118 @@ impl GenBankParser {
             topology: record.topology.clone(),
         }
     }
+
+    /// GenBankのフィーチャーをドメインのアノテーションに変換
+    /// 解釈できないロケーションを持つフィーチャーはスキップする
+    pub fn to_features(&self, record: &GenBankRecord) -> Vec<SequenceFeature> {
+        record
+            .features
+            .iter()
+            .filter_map(|feature| {
+                let (segments, strand) = parse_location(&feature.location)?;
+                let start = segments.iter().map(|s| s.start).min()?;
+                let end = segments.iter().map(|s| s.end).max()?;
+
+                let mut converted = SequenceFeature::new(&feature.feature_type, start, end, strand);
+                if segments.len() > 1 {
+                    converted.segments = segments;
+                }
+                converted.qualifiers = feature.qualifiers.clone();
+                // ApE/SnapGene が出力する色指定を優先
+                if let (Some(display), Some(color)) = (
+                    converted.display.as_mut(),
+                    converted.qualifiers.get("ApEinfo_fwdcolor"),
+                ) {
+                    display.color = color.clone();
+                }
+                Some(converted)
+            })
+            .collect()
+    }
+}
+
+/// GenBankロケーション文字列を0-based半開区間のリストに変換
+/// 対応形式: "1..100", "<1..>100", "42", "complement(...)", "join(...)", "order(...)"
+pub fn parse_location(location: &str) -> Option<(Vec<Range>, Strand)> {
+    let location = location.trim();
+
+    if let Some(inner) = location
+        .strip_prefix("complement(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let (segments, strand) = parse_location(inner)?;
+        let strand = match strand {
+            Strand::Forward => Strand::Reverse,
+            Strand::Reverse => Strand::Forward,
+        };
+        return Some((segments, strand));
+    }
+
+    for prefix in ["join(", "order("] {
+        if let Some(inner) = location
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let mut segments = Vec::new();
+            let mut strand = Strand::Forward;
+            for part in split_top_level(inner) {
+                let (part_segments, part_strand) = parse_location(part)?;
+                segments.extend(part_segments);
+                strand = part_strand;
+            }
+            return Some((segments, strand));
+        }
+    }
+
+    let parse_pos = |value: &str| -> Option<usize> {
+        value
+            .trim_matches(|c| c == '<' || c == '>')
+            .parse::<usize>()
+            .ok()
+    };
+
+    if let Some((start, end)) = location.split_once("..") {
+        let start = parse_pos(start)?;
+        let end = parse_pos(end)?;
+        if start == 0 || end < start {
+            return None;
+        }
+        Some((vec![Range::new(start - 1, end)], Strand::Forward))
+    } else if let Some((before, _)) = location.split_once('^') {
+        // 塩基間の位置（挿入点）は直前の1塩基として扱う
+        let pos = parse_pos(before)?;
+        Some((
+            vec![Range::new(pos.saturating_sub(1), pos)],
+            Strand::Forward,
+        ))
+    } else {
+        let pos = parse_pos(location)?;
+        if pos == 0 {
+            return None;
+        }
+        Some((vec![Range::new(pos - 1, pos)], Strand::Forward))
+    }
+}
+
+/// 括弧の外側にあるカンマで分割
+fn split_top_level(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut last = 0;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(value[last..i].trim());
+                last = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(value[last..].trim());
+    parts
 }
 
 #[cfg(test)]
@@ -268,4 +381,17 @@ ORIGIN
         assert!(record.sequence.len() > 0);
         assert!(!record.features.is_empty());
     }
+
+    #[test]
+    fn test_parse_location() {
+        let (segments, strand) = parse_location("265..2283").unwrap();
+        assert_eq!(segments, vec![Range::new(264, 2283)]);
+        assert_eq!(strand, Strand::Forward);
+
+        let (segments, strand) = parse_location("complement(join(10..20,30..>40))").unwrap();
+        assert_eq!(segments, vec![Range::new(9, 20), Range::new(29, 40)]);
+        assert_eq!(strand, Strand::Reverse);
+
+        assert!(parse_location("gap(100)").is_none());
+    }
 }
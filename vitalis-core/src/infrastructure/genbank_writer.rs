@@ -0,0 +1,196 @@
+use crate::domain::feature::{FeatureDisplay, SequenceFeature};
+use crate::domain::{Range, SequenceMetadata, Strand, Topology};
+use chrono::{DateTime, Utc};
+
+/// 修飾子の値を引用符で囲まない修飾子
+const UNQUOTED_QUALIFIERS: [&str; 3] = ["codon_start", "transl_table", "number"];
+
+pub struct GenBankWriter;
+
+impl Default for GenBankWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GenBankWriter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 配列とアノテーションを GenBank フラットファイルとして書き出す。
+    /// 修飾子は GenBankParser で読み戻せるよう折り返さずに1行で出力する
+    pub fn write(
+        &self,
+        metadata: &SequenceMetadata,
+        sequence: &str,
+        features: &[SequenceFeature],
+        date: DateTime<Utc>,
+    ) -> String {
+        let mut out = String::new();
+        let locus: String = metadata
+            .name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("_");
+        let locus = if locus.is_empty() {
+            metadata.id.as_str()
+        } else {
+            locus.as_str()
+        };
+        let molecule = if sequence.contains(['U', 'u']) && !sequence.contains(['T', 't']) {
+            "RNA"
+        } else {
+            "DNA"
+        };
+        let topology = match metadata.topology {
+            Topology::Linear => "linear",
+            Topology::Circular => "circular",
+        };
+
+        out.push_str(&format!(
+            "LOCUS       {:<16} {:>11} bp    {:<6}  {:<8} UNA {}\n",
+            locus,
+            sequence.len(),
+            molecule,
+            topology,
+            date.format("%d-%b-%Y").to_string().to_uppercase()
+        ));
+        out.push_str(&format!("DEFINITION  {}.\n", metadata.name));
+        out.push_str(&format!("ACCESSION   {}\n", metadata.id));
+        out.push_str(&format!("VERSION     {}\n", metadata.id));
+        out.push_str("FEATURES             Location/Qualifiers\n");
+
+        let mut features: Vec<&SequenceFeature> = features.iter().collect();
+        features.sort_by_key(|f| (f.start, std::cmp::Reverse(f.end)));
+        for feature in features {
+            let segments = if feature.segments.is_empty() {
+                vec![Range::new(feature.start, feature.end)]
+            } else {
+                feature.segments.clone()
+            };
+            out.push_str(&format!(
+                "     {:<16}{}\n",
+                feature.feature_type,
+                format_location(&segments, feature.strand)
+            ));
+
+            let mut keys: Vec<&String> = feature.qualifiers.keys().collect();
+            keys.sort();
+            for key in keys {
+                out.push_str(&qualifier_line(key, &feature.qualifiers[key]));
+            }
+            // 既定と異なる表示色は ApE/SnapGene と同じ修飾子で残す
+            if let Some(display) = &feature.display {
+                if display.color != FeatureDisplay::for_type(&feature.feature_type).color
+                    && !feature.qualifiers.contains_key("ApEinfo_fwdcolor")
+                {
+                    out.push_str(&qualifier_line("ApEinfo_fwdcolor", &display.color));
+                }
+            }
+        }
+
+        out.push_str("ORIGIN\n");
+        let lower = sequence.to_ascii_lowercase();
+        for (line, chunk) in lower.as_bytes().chunks(60).enumerate() {
+            out.push_str(&format!("{:>9}", line * 60 + 1));
+            for block in chunk.chunks(10) {
+                out.push(' ');
+                out.push_str(&String::from_utf8_lossy(block));
+            }
+            out.push('\n');
+        }
+        out.push_str("//\n");
+        out
+    }
+}
+
+/// 0-based半開区間のリストを GenBank ロケーション文字列に変換（parse_location の逆変換）
+pub fn format_location(segments: &[Range], strand: Strand) -> String {
+    let parts: Vec<String> = segments
+        .iter()
+        .map(|seg| {
+            if seg.len() == 1 {
+                seg.end.to_string()
+            } else {
+                format!("{}..{}", seg.start + 1, seg.end)
+            }
+        })
+        .collect();
+    let location = if parts.len() == 1 {
+        parts[0].clone()
+    } else {
+        format!("join({})", parts.join(","))
+    };
+    match strand {
+        Strand::Forward => location,
+        Strand::Reverse => format!("complement({})", location),
+    }
+}
+
+fn qualifier_line(key: &str, value: &str) -> String {
+    let indent = " ".repeat(21);
+    if value == "true" {
+        format!("{}/{}\n", indent, key)
+    } else if UNQUOTED_QUALIFIERS.contains(&key) {
+        format!("{}/{}={}\n", indent, key, value)
+    } else {
+        format!("{}/{}=\"{}\"\n", indent, key, value.replace('"', "\"\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::genbank_parser::{parse_location, GenBankParser};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_written_record_parses_back() {
+        let metadata = SequenceMetadata {
+            id: "seq_1".to_string(),
+            name: "pTest vector".to_string(),
+            length: 75,
+            topology: Topology::Circular,
+            file_path: None,
+            derived_from: None,
+        };
+        let sequence = "ATGAAACCCGGGTTTTAA".repeat(4) + "ACG";
+        let mut cds = SequenceFeature::new("CDS", 0, 18, Strand::Forward);
+        cds.qualifiers
+            .insert("gene".to_string(), "testA".to_string());
+        cds.qualifiers
+            .insert("codon_start".to_string(), "1".to_string());
+        let mut spliced = SequenceFeature::new("mRNA", 20, 70, Strand::Reverse);
+        spliced.segments = vec![Range::new(20, 30), Range::new(60, 70)];
+
+        let text = GenBankWriter::new().write(
+            &metadata,
+            &sequence,
+            &[spliced, cds],
+            Utc.with_ymd_and_hms(2024, 5, 17, 0, 0, 0).unwrap(),
+        );
+        assert!(text.starts_with("LOCUS       pTest_vector"));
+        assert!(text.contains("circular UNA 17-MAY-2024"));
+        assert!(text.contains("                     /codon_start=1\n"));
+        assert!(text.contains("\n       61 cccgggtttt aaacg\n"));
+
+        let parser = GenBankParser::new();
+        let record = parser.parse(&text).unwrap();
+        assert_eq!(record.sequence, sequence);
+        assert_eq!(record.topology, Topology::Circular);
+        let features = parser.to_features(&record);
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0].qualifiers["gene"], "testA");
+        assert_eq!(features[1].strand, Strand::Reverse);
+        assert_eq!(features[1].segments.len(), 2);
+        assert_eq!(
+            record.features[1].location,
+            "complement(join(21..30,61..70))"
+        );
+        assert_eq!(
+            parse_location(&format_location(&[Range::new(4, 5)], Strand::Forward)),
+            Some((vec![Range::new(4, 5)], Strand::Forward))
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use crate::domain::feature::SequenceFeature;
+use crate::domain::{Range, Strand};
+use std::collections::HashMap;
+
+/// GFF3 の1行（座標は0-based半開区間に変換済み）
+#[derive(Debug, Clone)]
+pub struct Gff3Record {
+    pub seqid: String,
+    pub source: String,
+    pub feature_type: String,
+    pub start: usize,
+    pub end: usize,
+    pub score: Option<String>,
+    pub strand: Strand,
+    pub phase: Option<u8>,
+    /// 列9の属性（パーセントエンコードは復号済み、複数値はカンマ区切りのまま）
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Gff3Record {
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub struct Gff3Parser;
+
+impl Default for Gff3Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Gff3Parser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// アノテーション行を読む（"##FASTA" 以降の埋め込み配列は無視する）
+    pub fn parse(&self, content: &str) -> Result<Vec<Gff3Record>, String> {
+        let mut records = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim_end_matches('\r');
+            if line.starts_with("##FASTA") {
+                break;
+            }
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split('\t').collect();
+            if columns.len() != 9 {
+                return Err(format!(
+                    "GFF3 line {}: expected 9 tab-separated columns, found {}",
+                    line_number,
+                    columns.len()
+                ));
+            }
+
+            let position = |value: &str, name: &str| -> Result<usize, String> {
+                value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&pos| pos > 0)
+                    .ok_or_else(|| {
+                        format!("GFF3 line {}: invalid {} '{}'", line_number, name, value)
+                    })
+            };
+            let start = position(columns[3], "start")?;
+            let end = position(columns[4], "end")?;
+            if end < start {
+                return Err(format!(
+                    "GFF3 line {}: end {} is before start {}",
+                    line_number, end, start
+                ));
+            }
+
+            let phase = match columns[7] {
+                "." => None,
+                value => Some(
+                    value
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|&phase| phase < 3)
+                        .ok_or_else(|| {
+                            format!("GFF3 line {}: invalid phase '{}'", line_number, value)
+                        })?,
+                ),
+            };
+
+            records.push(Gff3Record {
+                seqid: decode(columns[0]),
+                source: decode(columns[1]),
+                feature_type: decode(columns[2]),
+                start: start - 1,
+                end,
+                score: Some(columns[5])
+                    .filter(|&score| score != ".")
+                    .map(str::to_string),
+                strand: if columns[6] == "-" {
+                    Strand::Reverse
+                } else {
+                    Strand::Forward
+                },
+                phase,
+                attributes: parse_attributes(columns[8]),
+            });
+        }
+
+        Ok(records)
+    }
+
+    /// GFF3 の行をドメインのアノテーションに変換する。同じ ID と種類を持つ行
+    /// （エクソンごとに分かれた CDS など）は区間を segments に持つ1つのアノテーションにまとめる
+    pub fn to_features(&self, records: &[Gff3Record]) -> Vec<SequenceFeature> {
+        let mut features: Vec<SequenceFeature> = Vec::new();
+        let mut by_id: HashMap<(String, String), usize> = HashMap::new();
+
+        for record in records {
+            let key = record
+                .attribute("ID")
+                .map(|id| (id.to_string(), record.feature_type.clone()));
+            if let Some(&index) = key.as_ref().and_then(|key| by_id.get(key)) {
+                let feature = &mut features[index];
+                if feature.segments.is_empty() {
+                    feature
+                        .segments
+                        .push(Range::new(feature.start, feature.end));
+                }
+                feature.segments.push(Range::new(record.start, record.end));
+                feature.segments.sort_by_key(|segment| segment.start);
+                feature.start = feature.start.min(record.start);
+                feature.end = feature.end.max(record.end);
+                continue;
+            }
+
+            let mut feature = SequenceFeature::new(
+                &record.feature_type,
+                record.start,
+                record.end,
+                record.strand,
+            );
+            feature.qualifiers = record.attributes.iter().cloned().collect();
+            feature
+                .qualifiers
+                .insert("source".to_string(), record.source.clone());
+            if let Some(score) = &record.score {
+                feature
+                    .qualifiers
+                    .insert("score".to_string(), score.clone());
+            }
+            if let Some(phase) = record.phase {
+                feature
+                    .qualifiers
+                    .insert("phase".to_string(), phase.to_string());
+            }
+            if let Some(key) = key {
+                by_id.insert(key, features.len());
+            }
+            features.push(feature);
+        }
+
+        features
+    }
+}
+
+/// 列9（"key=value;key=value"）を読む
+fn parse_attributes(column: &str) -> Vec<(String, String)> {
+    if column == "." {
+        return Vec::new();
+    }
+    column
+        .split(';')
+        .filter_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            Some((decode(key), decode(value)))
+        })
+        .collect()
+}
+
+/// パーセントエンコード（"%3B" など）を復号する。不正な並びはそのまま残す
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GFF: &str = "##gff-version 3\n\
+        ##sequence-region chr1 1 1000\n\
+        chr1\tRefSeq\tgene\t101\t400\t.\t+\t.\tID=gene1;Name=abcA\n\
+        chr1\tRefSeq\tCDS\t101\t150\t.\t+\t0\tID=cds1;Parent=gene1;product=ABC%3B transporter\n\
+        chr1\tRefSeq\tCDS\t301\t400\t.\t+\t1\tID=cds1;Parent=gene1;product=ABC%3B transporter\n\
+        chr2\tRefSeq\tgene\t5\t20\t0.5\t-\t.\tID=gene2\n\
+        ##FASTA\n\
+        >chr1\n\
+        ACGT\n";
+
+    #[test]
+    fn test_parse_gff3_records() {
+        let records = Gff3Parser::new().parse(GFF).unwrap();
+        assert_eq!(records.len(), 4);
+
+        let cds = &records[1];
+        assert_eq!((cds.start, cds.end, cds.phase), (100, 150, Some(0)));
+        assert_eq!(cds.attribute("product"), Some("ABC; transporter"));
+        assert_eq!(records[3].strand, Strand::Reverse);
+        assert_eq!(records[3].score.as_deref(), Some("0.5"));
+
+        let error = Gff3Parser::new()
+            .parse("chr1\tsrc\tgene\t10\t5\t.\t+\t.\t.\n")
+            .unwrap_err();
+        assert!(error.contains("line 1"));
+    }
+
+    #[test]
+    fn test_multi_line_cds_becomes_one_feature() {
+        let parser = Gff3Parser::new();
+        let features = parser.to_features(&parser.parse(GFF).unwrap());
+        assert_eq!(features.len(), 3);
+
+        let cds = &features[1];
+        assert_eq!((cds.start, cds.end), (100, 400));
+        assert_eq!(
+            cds.segments,
+            vec![Range::new(100, 150), Range::new(300, 400)]
+        );
+        assert_eq!(cds.qualifiers.get("Parent"), Some(&"gene1".to_string()));
+        assert_eq!(features[0].label(), "abcA");
+    }
+}
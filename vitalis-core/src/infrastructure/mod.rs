@@ -1,8 +1,28 @@
 // Infrastructure layer - 外部依存の具体実装
+pub mod archive;
+pub mod batch_job;
+pub mod bed;
+pub mod compressed_project;
+pub mod entrez;
 pub mod genbank_parser;
+pub mod genbank_writer;
+pub mod gff3_parser;
 pub mod parsers;
+pub mod project_store;
+pub mod reference;
 pub mod storage;
 
+pub use archive::{ArchiveError, ZipWriter};
+pub use batch_job::{input_format, load_batch_job, BatchJobError};
+pub use bed::{BedParser, BedWriter};
+pub use compressed_project::{
+    is_compressed_project, load_compressed_project, save_compressed_project,
+};
+pub use entrez::{EntrezClient, EntrezError, EntrezSequence};
 pub use genbank_parser::{GenBankFeature, GenBankParser, GenBankRecord};
-pub use parsers::{FastaParser, FastqParser};
-pub use storage::FileSequenceRepository;
+pub use genbank_writer::GenBankWriter;
+pub use gff3_parser::{Gff3Parser, Gff3Record};
+pub use parsers::{AlignmentParser, FastaParser, FastqParser};
+pub use project_store::{ProjectError, ProjectRepository};
+pub use reference::ReferenceRegistry;
+pub use storage::{FileSequenceRepository, ProjectDraft, SequenceSource};
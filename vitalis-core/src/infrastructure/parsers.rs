@@ -1,4 +1,5 @@
 // Infrastructure layer: Parser implementations
+use crate::domain::alignment::AlignmentRow;
 use crate::domain::{Sequence, SequenceParser, Topology};
 use thiserror::Error;
 
@@ -109,3 +110,100 @@ impl SequenceParser for FastqParser {
         Ok(sequences)
     }
 }
+
+/// Multiple alignment parser (aligned FASTA and Clustal)
+pub struct AlignmentParser;
+
+impl AlignmentParser {
+    pub fn parse(&self, content: &str, format: &str) -> Result<Vec<AlignmentRow>, ParserError> {
+        let rows: Vec<AlignmentRow> = match format {
+            "fasta" => FastaParser
+                .parse(content)?
+                .into_iter()
+                .map(|sequence| AlignmentRow {
+                    name: sequence.id,
+                    aligned: sequence.sequence,
+                })
+                .collect(),
+            "clustal" => self.parse_clustal(content)?,
+            _ => {
+                return Err(ParserError::InvalidFormat(format!(
+                    "Unsupported alignment format: {}",
+                    format
+                )))
+            }
+        };
+
+        let columns = rows
+            .first()
+            .map(|row| row.aligned.chars().count())
+            .ok_or_else(|| ParserError::InvalidFormat("No sequences found".to_string()))?;
+        if let Some(row) = rows
+            .iter()
+            .find(|row| row.aligned.chars().count() != columns)
+        {
+            return Err(ParserError::LengthMismatch(format!(
+                "row '{}' has {} columns, expected {}",
+                row.name,
+                row.aligned.chars().count(),
+                columns
+            )));
+        }
+        Ok(rows)
+    }
+
+    /// Clustal 形式: ヘッダー行の後に「名前 配列 [累積数]」のブロックが続く
+    fn parse_clustal(&self, content: &str) -> Result<Vec<AlignmentRow>, ParserError> {
+        let mut lines = content.lines().skip_while(|line| line.trim().is_empty());
+        let header = lines.next().unwrap_or_default();
+        if !header.starts_with("CLUSTAL") && !header.starts_with("MUSCLE") {
+            return Err(ParserError::InvalidFormat(
+                "Missing CLUSTAL header".to_string(),
+            ));
+        }
+
+        let mut rows: Vec<AlignmentRow> = Vec::new();
+        for line in lines {
+            // 空行と保存度の記号行（先頭が空白）は読み飛ばす
+            if line.trim().is_empty() || line.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (Some(name), Some(block)) = (parts.next(), parts.next()) else {
+                return Err(ParserError::InvalidFormat(line.to_string()));
+            };
+            match rows.iter_mut().find(|row| row.name == name) {
+                Some(row) => row.aligned.push_str(block),
+                None => rows.push(AlignmentRow {
+                    name: name.to_string(),
+                    aligned: block.to_string(),
+                }),
+            }
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clustal_blocks() {
+        let content = "CLUSTAL W (1.83) multiple sequence alignment\n\n\
+                       seq1    ACGT-A 5\n\
+                       seq2    ACGTTA 6\n\
+                       \x20       **** *\n\n\
+                       seq1    GG 7\n\
+                       seq2    G- 7\n";
+        let rows = AlignmentParser.parse(content, "clustal").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].aligned, "ACGT-AGG");
+        assert_eq!(rows[1].aligned, "ACGTTAG-");
+
+        assert!(matches!(
+            AlignmentParser.parse(">a\nAC-G\n>b\nACG\n", "fasta"),
+            Err(ParserError::LengthMismatch(_))
+        ));
+    }
+}
@@ -0,0 +1,385 @@
+// Infrastructure layer: SQLite project files (sequences, annotations, designed primers)
+use crate::domain::project::{ProjectSequence, ProjectSnapshot};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use std::path::Path;
+use thiserror::Error;
+
+/// プロジェクトファイルの形式（SQLite の user_version に記録する）。
+/// 2: FASTQ 由来の配列の品質列を追加
+/// 3: ファイルから取り込んだ配列は塩基の代わりにレコードの位置（file 列）を保存
+const FORMAT_VERSION: i64 = 3;
+
+const SCHEMA: &str = "
+    CREATE TABLE project (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE sequences (
+        seq_id TEXT PRIMARY KEY,
+        ordinal INTEGER NOT NULL,
+        metadata TEXT NOT NULL,
+        sequence TEXT NOT NULL,
+        quality TEXT,
+        file TEXT
+    );
+    CREATE TABLE features (
+        seq_id TEXT NOT NULL REFERENCES sequences (seq_id),
+        ordinal INTEGER NOT NULL,
+        feature TEXT NOT NULL,
+        PRIMARY KEY (seq_id, ordinal)
+    );
+    CREATE TABLE primer_pairs (
+        seq_id TEXT NOT NULL REFERENCES sequences (seq_id),
+        ordinal INTEGER NOT NULL,
+        pair TEXT NOT NULL,
+        PRIMARY KEY (seq_id, ordinal)
+    );
+";
+
+/// 形式 1 のファイルを開いたときに適用する変更（続けて `UPGRADE_FROM_2` も適用する）
+const UPGRADE_FROM_1: &str = "ALTER TABLE sequences ADD COLUMN quality TEXT;";
+
+/// 形式 2 のファイルを開いたときに適用する変更
+const UPGRADE_FROM_2: &str = "ALTER TABLE sequences ADD COLUMN file TEXT;";
+
+#[derive(Error, Debug)]
+pub enum ProjectError {
+    #[error("Project file not found: {0}")]
+    NotFound(String),
+    #[error("Unsupported project format version {0}")]
+    UnsupportedVersion(i64),
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Invalid project data: {0}")]
+    InvalidData(#[from] serde_json::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Corrupted project file: {0}")]
+    Corrupted(String),
+}
+
+/// SQLite ファイルに保存したプロジェクト。メタデータ・アノテーション・プライマーは
+/// 1行1件の JSON として持ち、構造体にフィールドが増えても serde の既定値で読める
+pub struct ProjectRepository {
+    connection: Connection,
+}
+
+impl ProjectRepository {
+    /// `path` のプロジェクトファイルを開く（なければ空のプロジェクトを作る）
+    pub fn create(path: &Path) -> Result<Self, ProjectError> {
+        Self::init(Connection::open(path)?, true)
+    }
+
+    /// 既存のプロジェクトファイルを開く
+    pub fn open(path: &Path) -> Result<Self, ProjectError> {
+        if !path.is_file() {
+            return Err(ProjectError::NotFound(path.display().to_string()));
+        }
+        Self::init(Connection::open(path)?, false)
+    }
+
+    /// スキーマを作るのは `create` で空のデータベースを開いたときだけ。
+    /// project 表のない（プロジェクトファイルではない）データベースには書き込まない
+    fn init(connection: Connection, create: bool) -> Result<Self, ProjectError> {
+        let version: i64 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version > FORMAT_VERSION {
+            return Err(ProjectError::UnsupportedVersion(version));
+        }
+        let tables: i64 = connection.query_row(
+            "SELECT count(*) FROM sqlite_master WHERE type = 'table'",
+            [],
+            |row| row.get(0),
+        )?;
+        if create && version == 0 && tables == 0 {
+            connection.execute_batch(SCHEMA)?;
+            connection.pragma_update(None, "user_version", FORMAT_VERSION)?;
+            return Ok(Self { connection });
+        }
+
+        let has_project: bool = connection.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'project')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_project {
+            return Err(ProjectError::Corrupted(
+                "Not a Vitalis project (no project table)".to_string(),
+            ));
+        }
+        match version {
+            1 | 2 => {
+                if version == 1 {
+                    connection.execute_batch(UPGRADE_FROM_1)?;
+                }
+                connection.execute_batch(UPGRADE_FROM_2)?;
+                connection.pragma_update(None, "user_version", FORMAT_VERSION)?;
+            }
+            FORMAT_VERSION => {}
+            other => return Err(ProjectError::UnsupportedVersion(other)),
+        }
+        Ok(Self { connection })
+    }
+
+    /// 保存内容を `snapshot` で置き換える（1トランザクションで書くので途中で失敗しても前の内容が残る）
+    pub fn save(
+        &mut self,
+        snapshot: &ProjectSnapshot,
+        saved_at: DateTime<Utc>,
+    ) -> Result<(), ProjectError> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute_batch(
+            "DELETE FROM primer_pairs; DELETE FROM features; DELETE FROM sequences; DELETE FROM project;",
+        )?;
+        {
+            let mut insert_sequence = transaction.prepare(
+                "INSERT INTO sequences (seq_id, ordinal, metadata, sequence, quality, file) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            let mut insert_feature = transaction
+                .prepare("INSERT INTO features (seq_id, ordinal, feature) VALUES (?1, ?2, ?3)")?;
+            let mut insert_pair = transaction
+                .prepare("INSERT INTO primer_pairs (seq_id, ordinal, pair) VALUES (?1, ?2, ?3)")?;
+            for (ordinal, entry) in snapshot.sequences.iter().enumerate() {
+                insert_sequence.execute(params![
+                    entry.seq_id,
+                    ordinal as i64,
+                    serde_json::to_string(&entry.metadata)?,
+                    entry.sequence,
+                    entry.quality,
+                    entry.file.as_ref().map(serde_json::to_string).transpose()?
+                ])?;
+                for (ordinal, feature) in entry.features.iter().enumerate() {
+                    insert_feature.execute(params![
+                        entry.seq_id,
+                        ordinal as i64,
+                        serde_json::to_string(feature)?
+                    ])?;
+                }
+                for (ordinal, pair) in entry.primer_pairs.iter().enumerate() {
+                    insert_pair.execute(params![
+                        entry.seq_id,
+                        ordinal as i64,
+                        serde_json::to_string(pair)?
+                    ])?;
+                }
+            }
+            transaction.execute(
+                "INSERT INTO project (key, value) VALUES ('next_id', ?1), ('saved_at', ?2)",
+                params![snapshot.next_id.to_string(), saved_at.to_rfc3339()],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    pub fn load(&self) -> Result<ProjectSnapshot, ProjectError> {
+        let mut statement = self.connection.prepare(
+            "SELECT seq_id, metadata, sequence, quality, file FROM sequences ORDER BY ordinal",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?;
+
+        let mut sequences = Vec::new();
+        for row in rows {
+            let (seq_id, metadata, sequence, quality, file) = row?;
+            sequences.push(ProjectSequence {
+                metadata: serde_json::from_str(&metadata)?,
+                sequence,
+                quality,
+                file: file.as_deref().map(serde_json::from_str).transpose()?,
+                features: self.json_rows(
+                    "SELECT feature FROM features WHERE seq_id = ?1 ORDER BY ordinal",
+                    &seq_id,
+                )?,
+                primer_pairs: self.json_rows(
+                    "SELECT pair FROM primer_pairs WHERE seq_id = ?1 ORDER BY ordinal",
+                    &seq_id,
+                )?,
+                seq_id,
+            });
+        }
+        let next_id = self
+            .value("next_id")?
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(sequences.len() + 1);
+        Ok(ProjectSnapshot { sequences, next_id })
+    }
+
+    /// 最後に保存した日時（一度も保存していなければ None）
+    pub fn saved_at(&self) -> Result<Option<DateTime<Utc>>, ProjectError> {
+        Ok(self
+            .value("saved_at")?
+            .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+            .map(|saved_at| saved_at.with_timezone(&Utc)))
+    }
+
+    fn value(&self, key: &str) -> Result<Option<String>, ProjectError> {
+        Ok(self
+            .connection
+            .query_row("SELECT value FROM project WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    fn json_rows<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        seq_id: &str,
+    ) -> Result<Vec<T>, ProjectError> {
+        let mut statement = self.connection.prepare_cached(sql)?;
+        let rows = statement.query_map([seq_id], |row| row.get::<_, String>(0))?;
+        rows.map(|json| Ok(serde_json::from_str(&json?)?)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::feature::SequenceFeature;
+    use crate::domain::{SequenceMetadata, Strand, Topology};
+    use tempfile::TempDir;
+
+    fn entry(seq_id: &str, sequence: &str, features: Vec<SequenceFeature>) -> ProjectSequence {
+        ProjectSequence {
+            seq_id: seq_id.to_string(),
+            metadata: SequenceMetadata {
+                id: seq_id.to_uppercase(),
+                name: format!("{} name", seq_id),
+                length: sequence.len(),
+                topology: Topology::Circular,
+                file_path: None,
+                derived_from: None,
+            },
+            sequence: sequence.to_string(),
+            quality: None,
+            file: None,
+            features,
+            primer_pairs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_save_replaces_and_reloads_project() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.vitalis");
+        assert!(matches!(
+            ProjectRepository::open(&path),
+            Err(ProjectError::NotFound(_))
+        ));
+
+        let mut gene = SequenceFeature::new("gene", 2, 6, Strand::Reverse);
+        gene.id = "feat_3".to_string();
+        gene.qualifiers
+            .insert("gene".to_string(), "lacZ".to_string());
+        let mut read = entry("seq_2", "ACGTACGT", vec![gene.clone()]);
+        read.quality = Some("IIII++++".to_string());
+        let first = ProjectSnapshot {
+            sequences: vec![read.clone(), entry("seq_1", "GGGG", Vec::new())],
+            next_id: 3,
+        };
+        let saved_at = Utc::now();
+        let mut store = ProjectRepository::create(&path).unwrap();
+        store.save(&first, saved_at).unwrap();
+        assert_eq!(store.saved_at().unwrap(), Some(saved_at));
+
+        // 保存し直すと前の内容は残らない
+        let second = ProjectSnapshot {
+            sequences: vec![read],
+            next_id: 4,
+        };
+        store.save(&second, Utc::now()).unwrap();
+        drop(store);
+
+        let loaded = ProjectRepository::open(&path).unwrap().load().unwrap();
+        assert_eq!(loaded.next_id, 4);
+        assert_eq!(loaded.sequences.len(), 1);
+        let sequence = &loaded.sequences[0];
+        assert_eq!(
+            (sequence.seq_id.as_str(), sequence.sequence.as_str()),
+            ("seq_2", "ACGTACGT")
+        );
+        assert_eq!(sequence.metadata.topology, Topology::Circular);
+        assert_eq!(sequence.features, vec![gene]);
+        assert_eq!(sequence.quality.as_deref(), Some("IIII++++"));
+    }
+
+    #[test]
+    fn test_upgrades_format_1() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("old.vitalis");
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(&SCHEMA.replace(",\n        quality TEXT,\n        file TEXT", ""))
+            .unwrap();
+        connection
+            .execute(
+                "INSERT INTO sequences (seq_id, ordinal, metadata, sequence) VALUES ('seq_1', 0, ?1, 'ACGT')",
+                [serde_json::to_string(&entry("seq_1", "ACGT", Vec::new()).metadata).unwrap()],
+            )
+            .unwrap();
+        connection.pragma_update(None, "user_version", 1).unwrap();
+        drop(connection);
+
+        let loaded = ProjectRepository::open(&path).unwrap().load().unwrap();
+        assert_eq!(loaded.sequences[0].sequence, "ACGT");
+        assert_eq!(loaded.sequences[0].quality, None);
+        assert_eq!(loaded.sequences[0].file, None);
+    }
+
+    #[test]
+    fn test_rejects_newer_format() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("future.vitalis");
+        Connection::open(&path)
+            .unwrap()
+            .pragma_update(None, "user_version", FORMAT_VERSION + 1)
+            .unwrap();
+        assert!(matches!(
+            ProjectRepository::open(&path),
+            Err(ProjectError::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_rejects_other_databases() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("other.sqlite");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE notes (body TEXT);")
+            .unwrap();
+        assert!(matches!(
+            ProjectRepository::open(&path),
+            Err(ProjectError::Corrupted(_))
+        ));
+        // 保存先に指定しても表を書き足さない
+        assert!(matches!(
+            ProjectRepository::create(&path),
+            Err(ProjectError::Corrupted(_))
+        ));
+        let tables: i64 = Connection::open(&path)
+            .unwrap()
+            .query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tables, 1);
+
+        // 空のデータベースは開けず、作るときだけスキーマを書く
+        let empty = dir.path().join("empty.vitalis");
+        std::fs::File::create(&empty).unwrap();
+        assert!(matches!(
+            ProjectRepository::open(&empty),
+            Err(ProjectError::Corrupted(_))
+        ));
+        assert!(ProjectRepository::create(&empty).is_ok());
+        assert!(ProjectRepository::open(&empty).is_ok());
+    }
+}
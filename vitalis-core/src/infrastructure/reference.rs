@@ -0,0 +1,295 @@
+// Infrastructure layer: Local reference genome packs (FASTA + .fai)
+use crate::domain::reference::{
+    ReferenceContig, ReferenceGenome, ReferenceSettings, RegisterReferenceRequest,
+};
+use crate::domain::reproducibility;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReferenceError {
+    #[error("Reference genome not registered: {0}")]
+    NotFound(String),
+    #[error("Contig '{1}' not found in reference {0}")]
+    ContigNotFound(String, String),
+    #[error("Invalid range {0}..{1} for contig of length {2}")]
+    InvalidRange(usize, usize, usize),
+    #[error("Invalid .fai index line {0}: {1}")]
+    InvalidIndex(usize, String),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Settings error: {0}")]
+    Settings(#[from] serde_json::Error),
+}
+
+/// .fai の1行（name, length, offset, line_bases, line_width）
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaiRecord {
+    pub name: String,
+    pub length: usize,
+    pub offset: u64,
+    pub line_bases: usize,
+    pub line_width: usize,
+}
+
+impl FaiRecord {
+    /// 0-based の塩基位置に対応するファイル内のバイト位置
+    fn byte_position(&self, position: usize) -> u64 {
+        self.offset
+            + (position / self.line_bases * self.line_width + position % self.line_bases) as u64
+    }
+}
+
+/// samtools faidx 形式のインデックスを読み込む
+pub fn parse_fai(content: &str) -> Result<Vec<FaiRecord>, ReferenceError> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let invalid = || ReferenceError::InvalidIndex(i + 1, line.to_string());
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 5 {
+                return Err(invalid());
+            }
+            let number = |field: &str| field.trim().parse::<u64>().map_err(|_| invalid());
+            let record = FaiRecord {
+                name: fields[0].to_string(),
+                length: number(fields[1])? as usize,
+                offset: number(fields[2])?,
+                line_bases: number(fields[3])? as usize,
+                line_width: number(fields[4])? as usize,
+            };
+            if record.line_bases == 0 || record.line_width < record.line_bases {
+                return Err(invalid());
+            }
+            Ok(record)
+        })
+        .collect()
+}
+
+/// samtools faidx 形式で書く
+pub fn write_fai(index: &[FaiRecord]) -> String {
+    index
+        .iter()
+        .map(|record| {
+            format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                record.name, record.length, record.offset, record.line_bases, record.line_width
+            )
+        })
+        .collect()
+}
+
+/// 登録済み参照ゲノムの一覧。設定ファイルを指定した場合は登録・解除のたびに保存する
+#[derive(Debug, Default)]
+pub struct ReferenceRegistry {
+    settings_path: Option<PathBuf>,
+    genomes: Vec<ReferenceGenome>,
+    indexes: HashMap<String, Vec<FaiRecord>>,
+}
+
+impl ReferenceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 設定ファイルから登録内容を読み込む（ファイルがなければ空の一覧で開始）。
+    /// ファイルが移動・削除された参照ゲノムは登録を残したまま、取得時にエラーとする
+    pub fn load(&mut self, settings_path: &Path) -> Result<(), ReferenceError> {
+        let settings: ReferenceSettings = if settings_path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(settings_path)?)?
+        } else {
+            ReferenceSettings::default()
+        };
+        self.settings_path = Some(settings_path.to_path_buf());
+        self.genomes = settings.references;
+        self.indexes.clear();
+        Ok(())
+    }
+
+    pub fn list(&self) -> &[ReferenceGenome] {
+        &self.genomes
+    }
+
+    pub fn get(&self, reference_id: &str) -> Option<&ReferenceGenome> {
+        self.genomes.iter().find(|genome| genome.id == reference_id)
+    }
+
+    pub fn register(
+        &mut self,
+        request: &RegisterReferenceRequest,
+    ) -> Result<ReferenceGenome, ReferenceError> {
+        let fasta_path = PathBuf::from(&request.fasta_path);
+        let fai_path = match &request.fai_path {
+            Some(path) => PathBuf::from(path),
+            None => PathBuf::from(format!("{}.fai", request.fasta_path)),
+        };
+        // FASTA が読めることを登録時に確認
+        File::open(&fasta_path)?;
+        let index = parse_fai(&std::fs::read_to_string(&fai_path)?)?;
+
+        let genome = ReferenceGenome {
+            id: format!("ref_{}", reproducibility::workspace_id().simple()),
+            name: request.name.clone(),
+            organism: request.organism.clone(),
+            assembly: request.assembly.clone(),
+            fasta_path,
+            fai_path,
+            contigs: index
+                .iter()
+                .map(|record| ReferenceContig {
+                    name: record.name.clone(),
+                    length: record.length,
+                })
+                .collect(),
+            registered_at: chrono::Utc::now(),
+        };
+        self.indexes.insert(genome.id.clone(), index);
+        self.genomes.push(genome.clone());
+        self.save()?;
+        Ok(genome)
+    }
+
+    pub fn unregister(&mut self, reference_id: &str) -> Result<(), ReferenceError> {
+        let before = self.genomes.len();
+        self.genomes.retain(|genome| genome.id != reference_id);
+        if self.genomes.len() == before {
+            return Err(ReferenceError::NotFound(reference_id.to_string()));
+        }
+        self.indexes.remove(reference_id);
+        self.save()
+    }
+
+    /// コンティグ上の0-based半開区間 [start, end) の配列を .fai を使って直接読み出す
+    pub fn fetch(
+        &mut self,
+        reference_id: &str,
+        contig: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<String, ReferenceError> {
+        let genome = self
+            .get(reference_id)
+            .ok_or_else(|| ReferenceError::NotFound(reference_id.to_string()))?
+            .clone();
+        if !self.indexes.contains_key(reference_id) {
+            let index = parse_fai(&std::fs::read_to_string(&genome.fai_path)?)?;
+            self.indexes.insert(reference_id.to_string(), index);
+        }
+        let record = self.indexes[reference_id]
+            .iter()
+            .find(|record| record.name == contig)
+            .ok_or_else(|| {
+                ReferenceError::ContigNotFound(reference_id.to_string(), contig.to_string())
+            })?;
+        if start >= end || end > record.length {
+            return Err(ReferenceError::InvalidRange(start, end, record.length));
+        }
+
+        let from = record.byte_position(start);
+        let to = record.byte_position(end - 1) + 1;
+        let mut buffer = vec![0u8; (to - from) as usize];
+        let mut file = File::open(&genome.fasta_path)?;
+        file.seek(SeekFrom::Start(from))?;
+        file.read_exact(&mut buffer)?;
+
+        Ok(buffer
+            .iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .map(|byte| byte.to_ascii_uppercase() as char)
+            .collect())
+    }
+
+    fn save(&self) -> Result<(), ReferenceError> {
+        let Some(path) = &self.settings_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let settings = ReferenceSettings {
+            references: self.genomes.clone(),
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&settings)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// 1行10塩基、CRLF 改行の参照 FASTA とその .fai
+    fn write_pack(dir: &Path) -> PathBuf {
+        let fasta = dir.join("mini.fa");
+        std::fs::write(
+            &fasta,
+            ">chr1 test\r\nACGTACGTAC\r\nGGGGCCCCAA\r\nTT\r\n>chrM\r\nttttaaaacc\r\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("mini.fa.fai"),
+            "chr1\t22\t12\t10\t12\nchrM\t10\t47\t10\t12\n",
+        )
+        .unwrap();
+        fasta
+    }
+
+    #[test]
+    fn test_fetch_across_lines_and_persist() {
+        let dir = TempDir::new().unwrap();
+        let fasta = write_pack(dir.path());
+        let settings = dir.path().join("settings/references.json");
+
+        let mut registry = ReferenceRegistry::new();
+        registry.load(&settings).unwrap();
+        let genome = registry
+            .register(&RegisterReferenceRequest {
+                name: "Mini".to_string(),
+                fasta_path: fasta.to_string_lossy().to_string(),
+                fai_path: None,
+                organism: None,
+                assembly: Some("test1".to_string()),
+            })
+            .unwrap();
+        assert_eq!(genome.contig("chr1").unwrap().length, 22);
+
+        assert_eq!(
+            registry.fetch(&genome.id, "chr1", 8, 21).unwrap(),
+            "ACGGGGCCCCAAT"
+        );
+        assert_eq!(registry.fetch(&genome.id, "chrM", 0, 4).unwrap(), "TTTT");
+        assert!(matches!(
+            registry.fetch(&genome.id, "chr1", 20, 23),
+            Err(ReferenceError::InvalidRange(20, 23, 22))
+        ));
+        assert!(matches!(
+            registry.fetch(&genome.id, "chr2", 0, 1),
+            Err(ReferenceError::ContigNotFound(_, _))
+        ));
+
+        // 設定ファイルから再読み込みしても取得できる
+        let mut reloaded = ReferenceRegistry::new();
+        reloaded.load(&settings).unwrap();
+        assert_eq!(reloaded.list(), registry.list());
+        assert_eq!(reloaded.fetch(&genome.id, "chr1", 0, 3).unwrap(), "ACG");
+
+        reloaded.unregister(&genome.id).unwrap();
+        assert!(reloaded.unregister(&genome.id).is_err());
+    }
+
+    #[test]
+    fn test_parse_fai_rejects_malformed_lines() {
+        assert!(parse_fai("chr1\t100\t6\t60\t61\n\n").is_ok());
+        assert!(matches!(
+            parse_fai("chr1\t100\t6\t60\n"),
+            Err(ReferenceError::InvalidIndex(1, _))
+        ));
+        assert!(parse_fai("chr1\t100\t6\t0\t1\n").is_err());
+    }
+}
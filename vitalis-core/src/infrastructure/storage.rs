@@ -1,10 +1,24 @@
 // Infrastructure layer: Storage implementation
-use crate::domain::{Sequence, SequenceMetadata, SequenceRepository, Topology};
+use crate::domain::alignment::AlignmentStore;
+use crate::domain::backbone::BackboneLibrary;
+use crate::domain::feature::{FeatureStore, SequenceFeature};
+use crate::domain::index::{IndexError, IndexStatus, KmerIndex, KmerIndexBuilder};
+use crate::domain::navigation::BookmarkStore;
+use crate::domain::primer::PrimerPair;
+use crate::domain::project::{FileRecord, LineSpacing, ProjectSequence, ProjectSnapshot};
+use crate::domain::read_set::ReadSet;
+use crate::domain::sequence_slice::SequenceSlice;
+use crate::domain::track::TrackStore;
+use crate::domain::variant::VariantStore;
+use crate::domain::{Sequence, SequenceMetadata, SequenceOrigin, SequenceRepository, Topology};
+use crate::infrastructure::reference::{parse_fai, write_fai, FaiRecord};
+use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -17,6 +31,8 @@ pub enum StorageError {
     ParseError(String),
     #[error("Invalid range: start={0}, end={1}")]
     InvalidRange(usize, usize),
+    #[error("Index error: {0}")]
+    Index(#[from] IndexError),
 }
 
 /// ファイル内のバイト位置を記録
@@ -26,17 +42,787 @@ struct ByteOffset {
     length: usize,
 }
 
-/// 配列のソース（メモリまたはファイル）
+/// 配列のソース（メモリまたはファイル）。ファイル由来の配列はメモリマップと
+/// 取り込み時に作った行インデックスを持ち、ウィンドウは該当する行だけを読む
 #[derive(Debug, Clone)]
 pub enum SequenceSource {
-    Memory(String),
-    File { path: PathBuf, offset: ByteOffset },
+    Memory(Arc<str>),
+    File {
+        path: PathBuf,
+        offset: ByteOffset,
+        map: Arc<MappedFile>,
+        lines: Arc<LineIndex>,
+    },
+}
+
+/// 読み取り専用でメモリマップしたファイル。切り詰めを確かめられるよう開いたまま持つ
+#[derive(Debug)]
+pub struct MappedFile {
+    file: File,
+    map: Mmap,
+}
+
+impl MappedFile {
+    /// マップした内容。取り込み後にファイルが切り詰められていればマップを読まずにエラーを返す
+    fn bytes(&self) -> Result<&[u8], StorageError> {
+        if self.file.metadata()?.len() < self.map.len() as u64 {
+            return Err(StorageError::IoError(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "File was truncated after import",
+            )));
+        }
+        Ok(&self.map)
+    }
+}
+
+/// 取り込むファイルを読み取り専用でメモリマップする
+fn map_file(path: &Path) -> Result<Arc<MappedFile>, StorageError> {
+    let file = File::open(path)?;
+    // SAFETY: 取り込んだファイルは読み取り専用として扱う。取り込み後に外部で書き換えられた
+    // 場合の内容は、記録したバイト位置と同じく保証しない。切り詰められたファイルの範囲外を
+    // 読むと SIGBUS になるため、マップは必ず長さを確かめる `MappedFile::bytes` から読む
+    let map = unsafe { Mmap::map(&file)? };
+    Ok(Arc::new(MappedFile { file, map }))
+}
+
+/// ファイル上の配列行ごとの開始バイト位置（先頭の空白を除く）と、その行の先頭塩基の配列上の位置
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineIndex {
+    layout: LineLayout,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+enum LineLayout {
+    #[default]
+    Empty,
+    /// 行の長さが揃っている（最後の行だけ短くてよい）場合は、.fai と同じく先頭行の位置と
+    /// 行の間隔から計算する（数 GB の配列でも行ごとの表を持たない）
+    Uniform {
+        first: u64,
+        byte_step: u64,
+        base_step: usize,
+        lines: usize,
+    },
+    /// 行の長さが不揃いな場合は行ごとに記録する
+    Lines(Vec<(u64, usize)>),
+}
+
+impl LineIndex {
+    /// 取り込み時に配列行をファイル内の順に追加する
+    fn push(&mut self, byte_pos: u64, seq_pos: usize) {
+        self.layout = match std::mem::take(&mut self.layout) {
+            LineLayout::Empty => LineLayout::Uniform {
+                first: byte_pos,
+                byte_step: 0,
+                base_step: 0,
+                lines: 1,
+            },
+            LineLayout::Uniform {
+                first, lines: 1, ..
+            } => LineLayout::Uniform {
+                first,
+                byte_step: byte_pos - first,
+                base_step: seq_pos,
+                lines: 2,
+            },
+            LineLayout::Uniform {
+                first,
+                byte_step,
+                base_step,
+                lines,
+            } if byte_pos == first + lines as u64 * byte_step && seq_pos == lines * base_step => {
+                LineLayout::Uniform {
+                    first,
+                    byte_step,
+                    base_step,
+                    lines: lines + 1,
+                }
+            }
+            LineLayout::Uniform {
+                first,
+                byte_step,
+                base_step,
+                lines,
+            } => {
+                let mut entries: Vec<(u64, usize)> = (0..lines)
+                    .map(|i| (first + i as u64 * byte_step, i * base_step))
+                    .collect();
+                entries.push((byte_pos, seq_pos));
+                LineLayout::Lines(entries)
+            }
+            LineLayout::Lines(mut entries) => {
+                entries.push((byte_pos, seq_pos));
+                LineLayout::Lines(entries)
+            }
+        };
+    }
+
+    /// .fai の1行から作る（行の長さは揃っている）
+    fn from_fai(record: &FaiRecord) -> Self {
+        let lines = record.length.div_ceil(record.line_bases);
+        let layout = if lines == 0 {
+            LineLayout::Empty
+        } else {
+            LineLayout::Uniform {
+                first: record.offset,
+                byte_step: record.line_width as u64,
+                base_step: record.line_bases,
+                lines,
+            }
+        };
+        Self { layout }
+    }
+
+    /// プロジェクトに保存した行の配置から作り直す
+    fn from_spacing(spacing: LineSpacing) -> Self {
+        let layout = if spacing.lines == 0 {
+            LineLayout::Empty
+        } else {
+            LineLayout::Uniform {
+                first: spacing.first,
+                byte_step: spacing.byte_step,
+                base_step: spacing.base_step,
+                lines: spacing.lines,
+            }
+        };
+        Self { layout }
+    }
+
+    /// プロジェクトに保存する行の配置。行の長さが不揃いなら None（開くときに数え直す）
+    fn spacing(&self) -> Option<LineSpacing> {
+        match self.layout {
+            LineLayout::Empty => Some(LineSpacing {
+                first: 0,
+                byte_step: 0,
+                base_step: 0,
+                lines: 0,
+            }),
+            LineLayout::Uniform {
+                first,
+                byte_step,
+                base_step,
+                lines,
+            } => Some(LineSpacing {
+                first,
+                byte_step,
+                base_step,
+                lines,
+            }),
+            LineLayout::Lines(_) => None,
+        }
+    }
+
+    /// .fai に書ける（最後の行以外の長さが揃い、最後の行がそれより長くない）場合の
+    /// (開始バイト位置, 1行の塩基数, 1行のバイト数)。1行だけの配列は改行を1バイトとみなす
+    fn fai_layout(&self, length: usize) -> Option<(u64, usize, usize)> {
+        match self.layout {
+            LineLayout::Uniform {
+                first, lines: 1, ..
+            } => Some((first, length, length + 1)),
+            LineLayout::Uniform {
+                first,
+                byte_step,
+                base_step,
+                lines,
+            } => (length - (lines - 1) * base_step <= base_step).then_some((
+                first,
+                base_step,
+                byte_step as usize,
+            )),
+            LineLayout::Empty | LineLayout::Lines(_) => None,
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        match &self.layout {
+            LineLayout::Empty => 0,
+            LineLayout::Uniform { lines, .. } => *lines,
+            LineLayout::Lines(entries) => entries.len(),
+        }
+    }
+
+    /// `position` を含む行の開始バイト位置と先頭塩基の位置
+    fn locate(&self, position: usize) -> Option<(u64, usize)> {
+        match &self.layout {
+            LineLayout::Empty => None,
+            LineLayout::Uniform {
+                first,
+                byte_step,
+                base_step,
+                lines,
+            } => {
+                let i = position.checked_div(*base_step).unwrap_or(0).min(lines - 1);
+                Some((first + i as u64 * byte_step, i * base_step))
+            }
+            LineLayout::Lines(entries) => {
+                let i = entries.partition_point(|&(_, start)| start <= position);
+                i.checked_sub(1).map(|i| entries[i])
+            }
+        }
+    }
+}
+
+/// 構築済みの検索インデックス
+#[derive(Debug, Clone)]
+pub struct SequenceIndexes {
+    pub kmer: Arc<KmerIndex>,
+    /// ファイル由来の配列のみ（取り込み時に作ったものを共有する）
+    pub lines: Option<Arc<LineIndex>>,
+}
+
+impl SequenceSource {
+    /// k-mer インデックスを構築し、（ファイル由来なら）行インデックスを添える。
+    /// リポジトリのロックを保持せずに実行できるようソースだけを参照する。
+    /// ファイル由来の配列はチャンクごとに読んで索引に加えるので、配列全体をメモリに載せない
+    pub fn build_indexes(&self, kmer_size: usize) -> Result<SequenceIndexes, StorageError> {
+        match self {
+            SequenceSource::Memory(sequence) => Ok(SequenceIndexes {
+                kmer: Arc::new(KmerIndex::build(sequence, kmer_size)?),
+                lines: None,
+            }),
+            SequenceSource::File { offset, lines, .. } => {
+                let mut builder = KmerIndexBuilder::new(kmer_size, offset.length as u64)?;
+                let mut pushed = Ok(());
+                self.for_each_chunk(1 << 20, |chunk| {
+                    if pushed.is_ok() {
+                        pushed = builder.push(chunk);
+                    }
+                })?;
+                pushed?;
+                Ok(SequenceIndexes {
+                    kmer: Arc::new(builder.finish()),
+                    lines: Some(lines.clone()),
+                })
+            }
+        }
+    }
+
+    /// `get_window` と同じ [start, end)。リポジトリのロックを離したまま読める
+    pub fn window(&self, start: usize, end: usize) -> Result<String, StorageError> {
+        match self {
+            SequenceSource::Memory(sequence) => {
+                memory_window(sequence, start, end).map(|window| window.to_string())
+            }
+            SequenceSource::File {
+                offset, map, lines, ..
+            } => read_file_window(map.bytes()?, offset, lines, start, end),
+        }
+    }
+
+    /// 配列を先頭から `chunk_size` 文字程度ずつ（大文字で）`visit` に渡す。
+    /// ファイル由来の配列は行を読みながら渡すので、全体をメモリに載せない
+    pub fn for_each_chunk(
+        &self,
+        chunk_size: usize,
+        mut visit: impl FnMut(&str),
+    ) -> Result<(), StorageError> {
+        match self {
+            SequenceSource::Memory(sequence) => {
+                let mut rest: &str = sequence;
+                while !rest.is_empty() {
+                    let mut split = chunk_size.min(rest.len());
+                    while !rest.is_char_boundary(split) {
+                        split += 1;
+                    }
+                    let (chunk, tail) = rest.split_at(split);
+                    visit(&chunk.to_ascii_uppercase());
+                    rest = tail;
+                }
+                Ok(())
+            }
+            SequenceSource::File { path, offset, .. } => {
+                let mut reader = BufReader::new(File::open(path)?);
+                reader.seek(SeekFrom::Start(offset.start))?;
+                let mut remaining = offset.length;
+                let mut chunk = String::with_capacity(chunk_size.min(offset.length));
+                let mut line = String::new();
+
+                while remaining > 0 {
+                    line.clear();
+                    if reader.read_line(&mut line)? == 0 {
+                        break;
+                    }
+
+                    let trimmed = line.trim();
+                    if trimmed.starts_with('>')
+                        || trimmed.starts_with('@')
+                        || trimmed.starts_with('+')
+                        || trimmed.is_empty()
+                    {
+                        continue;
+                    }
+                    let take = trimmed.len().min(remaining);
+                    chunk.push_str(&trimmed[..take].to_ascii_uppercase());
+                    remaining -= take;
+
+                    if chunk.len() >= chunk_size {
+                        visit(&chunk);
+                        chunk.clear();
+                    }
+                }
+                if !chunk.is_empty() {
+                    visit(&chunk);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 並列走査で1スレッドが受け持つ最小のバイト数（小さいファイルは分割しない）
+const MIN_SCAN_CHUNK: u64 = 1 << 20;
+
+/// ファイル上の1レコード（ヘッダーと配列データの位置）
+#[derive(Debug, Clone, PartialEq)]
+struct RecordEntry {
+    id: String,
+    name: String,
+    start: u64,
+    length: usize,
+    /// FASTQ の品質行の開始位置
+    quality_start: Option<u64>,
+    lines: LineIndex,
+}
+
+/// 行の先頭の空白を除いた、最初の文字までのバイト数
+fn leading_whitespace(line: &[u8]) -> u64 {
+    (line.len() - line.trim_ascii_start().len()) as u64
+}
+
+/// ヘッダー行（先頭の '>' / '@' を除く）を ID と説明に分ける
+fn parse_header(header: &[u8]) -> (String, String) {
+    let header = String::from_utf8_lossy(header);
+    let parts: Vec<&str> = header.split_whitespace().collect();
+    let id = parts.first().unwrap_or(&"unknown").to_string();
+    let name = parts.get(1..).map(|p| p.join(" ")).unwrap_or_default();
+    (id, name)
+}
+
+/// FASTA を `chunks` 個のバイト範囲に分けてスレッドごとに走査し、ファイル内の順に連結する
+fn scan_fasta(path: &Path, chunks: u64) -> Result<Vec<RecordEntry>, StorageError> {
+    let file_length = std::fs::metadata(path)?.len();
+    let chunks = chunks.clamp(1, file_length.max(1));
+    let chunk_length = file_length.div_ceil(chunks);
+
+    let scanned: Vec<Result<Vec<RecordEntry>, StorageError>> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..chunks)
+            .map(|i| {
+                let start = i * chunk_length;
+                let end = (start + chunk_length).min(file_length);
+                scope.spawn(move || scan_fasta_chunk(path, start, end))
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| {
+                worker.join().unwrap_or_else(|_| {
+                    Err(StorageError::ParseError(
+                        "Import worker panicked".to_string(),
+                    ))
+                })
+            })
+            .collect()
+    });
+
+    let mut records = Vec::new();
+    for chunk in scanned {
+        records.extend(chunk?);
+    }
+    Ok(records)
+}
+
+/// ヘッダー行が [start, end) で始まる FASTA レコードを走査する。
+/// 範囲内で始まった最後のレコードは end を越えて次のヘッダーまで数える
+fn scan_fasta_chunk(path: &Path, start: u64, end: u64) -> Result<Vec<RecordEntry>, StorageError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = Vec::new();
+    let mut position = start;
+    if start > 0 {
+        // 前の範囲から続く行の途中であれば次の行頭まで進める
+        reader.seek(SeekFrom::Start(start - 1))?;
+        position = start - 1 + reader.read_until(b'\n', &mut line)? as u64;
+    }
+
+    let mut records = Vec::new();
+    let mut current: Option<RecordEntry> = None;
+    loop {
+        line.clear();
+        let bytes_read = reader.read_until(b'\n', &mut line)?;
+        if bytes_read == 0 {
+            break;
+        }
+        let line_start = position;
+        position += bytes_read as u64;
+
+        if line.first() == Some(&b'>') {
+            if line_start >= end {
+                break;
+            }
+            records.extend(current.take());
+            let (id, name) = parse_header(&line[1..]);
+            current = Some(RecordEntry {
+                id,
+                name,
+                start: position,
+                length: 0,
+                quality_start: None,
+                lines: LineIndex::default(),
+            });
+        } else if let Some(record) = current.as_mut() {
+            let bases = line.trim_ascii().len();
+            if bases > 0 {
+                record
+                    .lines
+                    .push(line_start + leading_whitespace(&line), record.length);
+                record.length += bases;
+            }
+        } else if line_start >= end {
+            break;
+        }
+    }
+    records.extend(current);
+    Ok(records)
+}
+
+/// FASTQ は品質行が '@' で始まり得るため、先頭から4行ずつ順に走査する
+fn scan_fastq(path: &Path) -> Result<Vec<RecordEntry>, StorageError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = Vec::new();
+    let mut position = 0u64;
+    let mut records = Vec::new();
+    loop {
+        line.clear();
+        let bytes_read = reader.read_until(b'\n', &mut line)?;
+        if bytes_read == 0 {
+            break;
+        }
+        position += bytes_read as u64;
+        if line.trim_ascii().is_empty() {
+            continue;
+        }
+        if line.first() != Some(&b'@') {
+            return Err(StorageError::ParseError("Invalid FASTQ format".to_string()));
+        }
+        let (id, name) = parse_header(&line[1..]);
+        let start = position;
+
+        line.clear();
+        position += reader.read_until(b'\n', &mut line)? as u64;
+        let length = line.trim_ascii().len();
+        let mut lines = LineIndex::default();
+        lines.push(start + leading_whitespace(&line), 0);
+        // '+' 行と品質行（品質行は '@' や '+' で始まりうるので位置で読む）
+        line.clear();
+        position += reader.read_until(b'\n', &mut line)? as u64;
+        let quality_start = position;
+        line.clear();
+        position += reader.read_until(b'\n', &mut line)? as u64;
+        records.push(RecordEntry {
+            id,
+            name,
+            start,
+            length,
+            quality_start: Some(quality_start),
+            lines,
+        });
+    }
+    Ok(records)
+}
+
+/// samtools と同じく FASTA のパスに ".fai" を付けた場所
+fn fai_path(path: &Path) -> PathBuf {
+    let mut fai = path.as_os_str().to_owned();
+    fai.push(".fai");
+    PathBuf::from(fai)
+}
+
+/// `offset` の直前の行がレコード `name` のヘッダー（FASTA の '>' または FASTQ の '@'）で
+/// あれば ID と説明を返す
+fn header_before(map: &[u8], offset: u64, name: &str) -> Option<(String, String)> {
+    let line = map.get(..offset as usize)?.trim_ascii_end();
+    let begin = line
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |i| i + 1);
+    let header = line[begin..]
+        .strip_prefix(b">")
+        .or_else(|| line[begin..].strip_prefix(b"@"))?;
+    let (id, description) = parse_header(header);
+    (id == name).then_some((id, description))
+}
+
+/// FASTA より新しい .fai があれば走査せずにレコードを作る。各レコードの直前の行が同名の
+/// ヘッダーで、配列がファイル内に収まる場合だけ使い、合わなければ古い索引として None を返す
+fn records_from_fai(path: &Path, map: &[u8]) -> Option<Vec<RecordEntry>> {
+    let fai = fai_path(path);
+    let indexed = std::fs::metadata(&fai).ok()?.modified().ok()?;
+    if indexed < std::fs::metadata(path).ok()?.modified().ok()? {
+        return None;
+    }
+    let index = parse_fai(&std::fs::read_to_string(&fai).ok()?).ok()?;
+    index
+        .iter()
+        .map(|record| {
+            let (id, name) = header_before(map, record.offset, &record.name)?;
+            let lines = LineIndex::from_fai(record);
+            let end = lines
+                .locate(record.length.saturating_sub(1))
+                .map_or(record.offset, |(byte_pos, seq_pos)| {
+                    byte_pos + (record.length - seq_pos) as u64
+                });
+            (end <= map.len() as u64).then_some(RecordEntry {
+                id,
+                name,
+                start: record.offset,
+                length: record.length,
+                quality_start: None,
+                lines,
+            })
+        })
+        .collect()
+}
+
+/// 行の長さが不揃いなレコードを開き直すとき、`start` から `length` 塩基分の配列行を数え直す。
+/// 途中で次のレコードやファイルの終わりに達すれば None
+fn scan_record_lines(map: &[u8], start: u64, length: usize) -> Option<LineIndex> {
+    let mut lines = LineIndex::default();
+    let mut seq_pos = 0;
+    let mut position = start as usize;
+    while seq_pos < length {
+        let rest = map.get(position..).filter(|rest| !rest.is_empty())?;
+        let line_length = rest
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(rest.len(), |i| i + 1);
+        let line = &rest[..line_length];
+        if line.first() == Some(&b'>') {
+            return None;
+        }
+        let bases = line.trim_ascii().len();
+        if bases > 0 {
+            lines.push(position as u64 + leading_whitespace(line), seq_pos);
+            seq_pos += bases;
+        }
+        position += line_length;
+    }
+    (seq_pos == length).then_some(lines)
+}
+
+/// 保存したレコードの位置から配列（と FASTQ の品質行）のソースを作り直す。同じファイルの
+/// マップは `maps` で共有する。ファイルがないか、記録した位置の直前が同じ ID のヘッダーでない、
+/// あるいは配列がファイルに収まらない（取り込み後に書き換えられた）場合はエラー
+fn restore_file_record(
+    record: &FileRecord,
+    id: &str,
+    maps: &mut HashMap<PathBuf, Arc<MappedFile>>,
+) -> Result<(SequenceSource, Option<SequenceSource>), StorageError> {
+    let map = match maps.get(&record.path) {
+        Some(map) => map.clone(),
+        None => {
+            let map = map_file(&record.path)?;
+            maps.insert(record.path.clone(), map.clone());
+            map
+        }
+    };
+    let bytes = map.bytes()?;
+    let changed = || {
+        StorageError::ParseError(format!(
+            "Record {} no longer matches {}",
+            id,
+            record.path.display()
+        ))
+    };
+    header_before(bytes, record.start, id).ok_or_else(changed)?;
+    let lines = match record.lines {
+        Some(spacing) => LineIndex::from_spacing(spacing),
+        None => scan_record_lines(bytes, record.start, record.length).ok_or_else(changed)?,
+    };
+    let end = lines
+        .locate(record.length.saturating_sub(1))
+        .map_or(record.start, |(byte_pos, seq_pos)| {
+            byte_pos + (record.length - seq_pos) as u64
+        });
+    let quality_end = record
+        .quality_start
+        .map_or(0, |start| start + record.length as u64);
+    if end.max(quality_end) > bytes.len() as u64 {
+        return Err(changed());
+    }
+
+    let quality = record
+        .quality_start
+        .map(|quality_start| SequenceSource::File {
+            path: record.path.clone(),
+            offset: ByteOffset {
+                start: quality_start,
+                length: record.length,
+            },
+            map: map.clone(),
+            lines: Arc::default(),
+        });
+    let source = SequenceSource::File {
+        path: record.path.clone(),
+        offset: ByteOffset {
+            start: record.start,
+            length: record.length,
+        },
+        map,
+        lines: Arc::new(lines),
+    };
+    Ok((source, quality))
+}
+
+/// 品質行のソースから品質文字列を読む
+fn quality_text(source: &SequenceSource) -> Result<String, StorageError> {
+    match source {
+        SequenceSource::Memory(quality) => Ok(quality.to_string()),
+        SequenceSource::File { offset, map, .. } => {
+            let start = offset.start as usize;
+            let quality = map
+                .bytes()?
+                .get(start..start + offset.length)
+                .ok_or_else(|| StorageError::ParseError("Quality line is truncated".to_string()))?;
+            String::from_utf8(quality.to_vec()).map_err(|e| StorageError::ParseError(e.to_string()))
+        }
+    }
+}
+
+/// 全レコードの行の長さが揃っていれば、次回の取り込みで使う .fai を書く
+fn save_fai(path: &Path, records: &[RecordEntry]) {
+    let index: Option<Vec<FaiRecord>> = records
+        .iter()
+        .map(|record| {
+            let (offset, line_bases, line_width) = record.lines.fai_layout(record.length)?;
+            Some(FaiRecord {
+                name: record.id.clone(),
+                length: record.length,
+                offset,
+                line_bases,
+                line_width,
+            })
+        })
+        .collect();
+    if let Some(index) = index {
+        // 書き込めない場所（読み取り専用のディレクトリなど）でも取り込みは続ける
+        let _ = std::fs::write(fai_path(path), write_fai(&index));
+    }
+}
+
+/// FASTA の全レコード。有効な .fai があれば使い、なければ最大 `threads` スレッドで走査して .fai を書く
+fn indexed_fasta_records(
+    path: &Path,
+    map: &[u8],
+    threads: usize,
+) -> Result<Vec<RecordEntry>, StorageError> {
+    if let Some(records) = records_from_fai(path, map) {
+        return Ok(records);
+    }
+    let chunks = (threads as u64).min(map.len() as u64 / MIN_SCAN_CHUNK);
+    let records = scan_fasta(path, chunks)?;
+    save_fai(path, &records);
+    Ok(records)
+}
+
+/// メモリ上の配列のウィンドウ。大文字のみの区間はコピーせずバッファを共有する
+fn memory_window(seq: &Arc<str>, start: usize, end: usize) -> Result<SequenceSlice, StorageError> {
+    // Handle edge cases consistently
+    if start >= seq.len() {
+        return Err(StorageError::InvalidRange(start, end));
+    }
+
+    // Allow start >= end, return empty string
+    if start >= end {
+        return Ok(SequenceSlice::from(String::new()));
+    }
+
+    // Clamp end to sequence length
+    let end = end.min(seq.len());
+    // Convert to uppercase for consistency
+    if seq.as_bytes()[start..end]
+        .iter()
+        .any(u8::is_ascii_lowercase)
+    {
+        Ok(SequenceSlice::from(seq[start..end].to_ascii_uppercase()))
+    } else {
+        Ok(SequenceSlice::new(seq.clone(), start, end))
+    }
+}
+
+/// メモリマップから [start, end) を読む。行インデックスで `start` を含む行に直接移り、
+/// 区間にかかる行だけを参照する
+fn read_file_window(
+    map: &[u8],
+    offset: &ByteOffset,
+    lines: &LineIndex,
+    start: usize,
+    end: usize,
+) -> Result<String, StorageError> {
+    // Handle edge cases consistently with memory implementation
+    if start >= offset.length {
+        return Err(StorageError::InvalidRange(start, end));
+    }
+
+    // Allow start >= end, return empty string
+    if start >= end {
+        return Ok(String::new());
+    }
+
+    // Clamp end to sequence length
+    let end = end.min(offset.length);
+
+    let (byte_pos, mut current_pos) = lines.locate(start).unwrap_or((offset.start, 0));
+    let mut rest = map.get(byte_pos as usize..).unwrap_or_default();
+    let mut result = String::with_capacity(end - start);
+
+    while current_pos < end && !rest.is_empty() {
+        let line_length = rest
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(rest.len(), |i| i + 1);
+        let (line, tail) = rest.split_at(line_length);
+        rest = tail;
+
+        let trimmed = line.trim_ascii();
+        // Skip header lines and empty lines
+        if trimmed.is_empty() || matches!(trimmed[0], b'>' | b'@' | b'+') {
+            continue;
+        }
+
+        let from = start.saturating_sub(current_pos).min(trimmed.len());
+        let to = (end - current_pos).min(trimmed.len());
+        if from < to {
+            result.push_str(&String::from_utf8_lossy(&trimmed[from..to]).to_ascii_uppercase());
+        }
+        current_pos += trimmed.len();
+    }
+
+    Ok(result)
 }
 
 /// Infrastructure層でのRepositoryトレイト実装
 pub struct FileSequenceRepository {
     pub sequences: HashMap<String, SequenceSource>,
     pub metadata: HashMap<String, SequenceMetadata>,
+    pub features: FeatureStore,
+    pub variants: VariantStore,
+    /// ユーザー登録のベクター骨格
+    pub backbones: BackboneLibrary,
+    /// 取り込んだ多重アラインメント
+    pub alignments: AlignmentStore,
+    /// 塩基単位のスコアトラック（保存度など）
+    pub tracks: TrackStore,
+    /// 複数レコードの取り込み単位（リードセット ID → リードセット）
+    pub read_sets: HashMap<String, ReadSet>,
+    /// 設計したプライマーペア（配列ごとに最新の設計結果）
+    pub designed_primers: HashMap<String, Vec<PrimerPair>>,
+    /// 配列上のブックマーク
+    pub bookmarks: BookmarkStore,
+    /// FASTQ レコードの品質行（seq_id → 位置）
+    qualities: HashMap<String, SequenceSource>,
+    indexes: HashMap<String, SequenceIndexes>,
+    index_status: HashMap<String, IndexStatus>,
+    /// 開いたプロジェクトのうち取り込み元ファイルから読み込めなかった配列（保存時にそのまま書き戻す）
+    unavailable: Vec<ProjectSequence>,
     next_id: usize,
 }
 
@@ -45,6 +831,18 @@ impl FileSequenceRepository {
         Self {
             sequences: HashMap::new(),
             metadata: HashMap::new(),
+            features: FeatureStore::new(),
+            variants: VariantStore::new(),
+            backbones: BackboneLibrary::new(),
+            alignments: AlignmentStore::new(),
+            tracks: TrackStore::new(),
+            read_sets: HashMap::new(),
+            designed_primers: HashMap::new(),
+            bookmarks: BookmarkStore::new(),
+            qualities: HashMap::new(),
+            indexes: HashMap::new(),
+            index_status: HashMap::new(),
+            unavailable: Vec::new(),
             next_id: 1,
         }
     }
@@ -99,6 +897,19 @@ impl FileSequenceRepository {
     }
 
     pub fn parse_fastq(&self, content: &str) -> Result<Vec<Sequence>, StorageError> {
+        Ok(self
+            .parse_fastq_records(content)?
+            .into_iter()
+            .map(|(sequence, _)| sequence)
+            .collect())
+    }
+
+    /// FASTQ の各レコードを品質文字列（Phred+33）とともに読む。
+    /// 品質行の長さが配列と合わないレコードはエラー
+    pub fn parse_fastq_records(
+        &self,
+        content: &str,
+    ) -> Result<Vec<(Sequence, String)>, StorageError> {
         let mut sequences = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
 
@@ -113,28 +924,96 @@ impl FileSequenceRepository {
             let id = parts.first().unwrap_or(&"unknown").to_string();
             let name = parts.get(1..).map(|p| p.join(" ")).unwrap_or_default();
             let sequence = lines[i + 1].to_string();
+            let quality = lines[i + 3].trim_end().to_string();
+            if quality.len() != sequence.len() {
+                return Err(StorageError::ParseError(format!(
+                    "Quality length {} differs from sequence length {} in record {}",
+                    quality.len(),
+                    sequence.len(),
+                    id
+                )));
+            }
 
-            sequences.push(Sequence {
-                id,
-                name,
-                sequence,
-                topology: Topology::Linear,
-            });
+            sequences.push((
+                Sequence {
+                    id,
+                    name,
+                    sequence,
+                    topology: Topology::Linear,
+                },
+                quality,
+            ));
 
-            i += 4; // Skip quality lines
+            i += 4;
         }
 
         Ok(sequences)
     }
 
+    /// テキストの先頭レコードだけを取り込む（全レコードは `import_all_from_text`）
     pub fn import_from_text(
         &mut self,
         content: &str,
         format: &str,
     ) -> Result<String, StorageError> {
-        let sequences = match format {
-            "fasta" => self.parse_fasta(content)?,
-            "fastq" => self.parse_fastq(content)?,
+        let (sequence, quality) = self.parse_text(content, format)?.swap_remove(0);
+        self.store_record(sequence, quality)
+    }
+
+    /// マルチ FASTA/FASTQ テキストの全レコードをメモリに取り込み、レコード順の seq_id を返す
+    pub fn import_all_from_text(
+        &mut self,
+        content: &str,
+        format: &str,
+    ) -> Result<Vec<String>, StorageError> {
+        self.parse_text(content, format)?
+            .into_iter()
+            .map(|(sequence, quality)| self.store_record(sequence, quality))
+            .collect()
+    }
+
+    /// 配列を登録し、FASTQ 由来なら品質も保持する
+    fn store_record(
+        &mut self,
+        sequence: Sequence,
+        quality: Option<String>,
+    ) -> Result<String, StorageError> {
+        let seq_id = self.store_sequence(sequence)?;
+        if let Some(quality) = quality {
+            self.set_quality(&seq_id, quality);
+        }
+        Ok(seq_id)
+    }
+
+    /// 取り込み済みの seq_id を取り込み順（採番順）に並べる
+    pub fn seq_ids(&self) -> Vec<String> {
+        let mut seq_ids: Vec<String> = self.metadata.keys().cloned().collect();
+        seq_ids.sort_by_key(|seq_id| {
+            seq_id
+                .trim_start_matches("seq_")
+                .parse::<usize>()
+                .unwrap_or(usize::MAX)
+        });
+        seq_ids
+    }
+
+    /// テキストのレコードを読む（FASTQ は品質文字列付き）
+    fn parse_text(
+        &self,
+        content: &str,
+        format: &str,
+    ) -> Result<Vec<(Sequence, Option<String>)>, StorageError> {
+        let sequences: Vec<_> = match format {
+            "fasta" => self
+                .parse_fasta(content)?
+                .into_iter()
+                .map(|sequence| (sequence, None))
+                .collect(),
+            "fastq" => self
+                .parse_fastq_records(content)?
+                .into_iter()
+                .map(|(sequence, quality)| (sequence, Some(quality)))
+                .collect(),
             _ => {
                 return Err(StorageError::ParseError(format!(
                     "Unsupported format: {}",
@@ -146,35 +1025,16 @@ impl FileSequenceRepository {
         if sequences.is_empty() {
             return Err(StorageError::ParseError("No sequences found".to_string()));
         }
-
-        // For simplicity, just use the first sequence
-        let sequence = &sequences[0];
-        let seq_id = self.generate_id();
-
-        // Store in memory for text import
-        self.sequences.insert(
-            seq_id.clone(),
-            SequenceSource::Memory(sequence.sequence.clone()),
-        );
-        self.metadata.insert(
-            seq_id.clone(),
-            SequenceMetadata {
-                id: sequence.id.clone(),
-                name: sequence.name.clone(),
-                length: sequence.sequence.len(),
-                topology: sequence.topology.clone(),
-                file_path: None,
-            },
-        );
-
-        Ok(seq_id)
+        Ok(sequences)
     }
 
+    /// ファイルを取り込み、登録した seq_id を返す。
+    /// マルチ FASTA は全レコードをファイル内の順に、FASTQ は先頭レコードだけを登録する
     pub fn import_from_file(
         &mut self,
         file_path: &Path,
         format: &str,
-    ) -> Result<String, StorageError> {
+    ) -> Result<Vec<String>, StorageError> {
         let mut file = File::open(file_path)?;
         let metadata = file.metadata()?;
 
@@ -186,14 +1046,20 @@ impl FileSequenceRepository {
             // For small files, load into memory
             let mut content = String::new();
             file.read_to_string(&mut content)?;
-            let seq_id = self.import_from_text(&content, format)?;
+            let seq_ids = if format == "fasta" {
+                self.import_all_from_text(&content, format)?
+            } else {
+                vec![self.import_from_text(&content, format)?]
+            };
 
             // Update metadata to include file path
-            if let Some(meta) = self.metadata.get_mut(&seq_id) {
-                meta.file_path = Some(file_path.to_path_buf());
+            for seq_id in &seq_ids {
+                if let Some(meta) = self.metadata.get_mut(seq_id) {
+                    meta.file_path = Some(file_path.to_path_buf());
+                }
             }
 
-            Ok(seq_id)
+            Ok(seq_ids)
         }
     }
 
@@ -201,7 +1067,22 @@ impl FileSequenceRepository {
         &mut self,
         file_path: &Path,
         format: &str,
-    ) -> Result<String, StorageError> {
+    ) -> Result<Vec<String>, StorageError> {
+        // Every FASTA record is registered from its .fai (written on the first import)
+        // without rescanning, each with its own byte offset and line index
+        if format == "fasta" {
+            let map = map_file(file_path)?;
+            let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+            let records = indexed_fasta_records(file_path, map.bytes()?, threads)?;
+            if records.is_empty() {
+                return Err(StorageError::ParseError("No sequence found".to_string()));
+            }
+            return Ok(records
+                .into_iter()
+                .map(|record| self.insert_file_record(file_path, &map, record))
+                .collect());
+        }
+
         let file = File::open(file_path)?;
         let mut reader = BufReader::new(file);
         let mut line = String::new();
@@ -235,13 +1116,17 @@ impl FileSequenceRepository {
             header_pos += bytes_read as u64;
         }
 
-        // Count sequence length
+        // Count sequence length and index the byte position of every sequence line
+        let mut lines = LineIndex::default();
+        let mut position = data_start;
         loop {
             line.clear();
             let bytes_read = reader.read_line(&mut line)?;
             if bytes_read == 0 {
                 break;
             }
+            let line_start = position;
+            position += bytes_read as u64;
 
             let trimmed = line.trim();
             if trimmed.starts_with('>') || trimmed.starts_with('@') || trimmed.starts_with('+') {
@@ -249,6 +1134,10 @@ impl FileSequenceRepository {
             }
 
             if !trimmed.is_empty() {
+                lines.push(
+                    line_start + leading_whitespace(line.as_bytes()),
+                    sequence_length,
+                );
                 sequence_length += trimmed.len();
             }
         }
@@ -264,6 +1153,8 @@ impl FileSequenceRepository {
                     start: data_start,
                     length: sequence_length,
                 },
+                map: map_file(file_path)?,
+                lines: Arc::new(lines),
             },
         );
 
@@ -275,72 +1166,376 @@ impl FileSequenceRepository {
                 length: sequence_length,
                 topology: Topology::Linear,
                 file_path: Some(file_path.to_path_buf()),
+                derived_from: None,
             },
         );
 
-        Ok(seq_id)
+        Ok(vec![seq_id])
+    }
+
+    /// 複数レコードのファイル（メタゲノムのコンティグ集など）の全レコードを取り込む。
+    /// FASTA はレコード境界の走査と長さの計算を最大 `threads` スレッドで並列に行い、
+    /// 有効な .fai があれば走査しない。
+    /// seq_id はスレッド数によらずファイル内のレコード順に振られる
+    pub fn import_records_from_file(
+        &mut self,
+        file_path: &Path,
+        format: &str,
+        threads: usize,
+    ) -> Result<Vec<String>, StorageError> {
+        let map = map_file(file_path)?;
+        let records = match format {
+            "fasta" => indexed_fasta_records(file_path, map.bytes()?, threads)?,
+            "fastq" => scan_fastq(file_path)?,
+            _ => {
+                return Err(StorageError::ParseError(format!(
+                    "Unsupported format: {}",
+                    format
+                )))
+            }
+        };
+        if records.is_empty() {
+            return Err(StorageError::ParseError("No sequences found".to_string()));
+        }
+
+        self.sequences.reserve(records.len());
+        self.metadata.reserve(records.len());
+        Ok(records
+            .into_iter()
+            .map(|record| self.insert_file_record(file_path, &map, record))
+            .collect())
+    }
+
+    /// ファイル上のレコードを（FASTQ なら品質行とともに）登録する
+    fn insert_file_record(
+        &mut self,
+        file_path: &Path,
+        map: &Arc<MappedFile>,
+        record: RecordEntry,
+    ) -> String {
+        let seq_id = self.generate_id();
+        if let Some(quality_start) = record.quality_start {
+            self.qualities.insert(
+                seq_id.clone(),
+                SequenceSource::File {
+                    path: file_path.to_path_buf(),
+                    offset: ByteOffset {
+                        start: quality_start,
+                        length: record.length,
+                    },
+                    map: map.clone(),
+                    // 品質行はバイト位置からそのまま読む
+                    lines: Arc::default(),
+                },
+            );
+        }
+        self.sequences.insert(
+            seq_id.clone(),
+            SequenceSource::File {
+                path: file_path.to_path_buf(),
+                offset: ByteOffset {
+                    start: record.start,
+                    length: record.length,
+                },
+                map: map.clone(),
+                lines: Arc::new(record.lines),
+            },
+        );
+        self.metadata.insert(
+            seq_id.clone(),
+            SequenceMetadata {
+                id: record.id,
+                name: record.name,
+                length: record.length,
+                topology: Topology::Linear,
+                file_path: Some(file_path.to_path_buf()),
+                derived_from: None,
+            },
+        );
+        seq_id
+    }
+
+    /// レコードの集まりをリードセットとして登録し、その ID を返す
+    pub fn register_read_set(&mut self, seq_ids: Vec<String>, file_path: Option<&Path>) -> String {
+        let readset_id = format!("readset_{}", self.read_sets.len() + 1);
+        self.read_sets.insert(
+            readset_id.clone(),
+            ReadSet {
+                readset_id: readset_id.clone(),
+                seq_ids,
+                file_path: file_path.map(Path::to_path_buf),
+            },
+        );
+        readset_id
+    }
+
+    /// メモリ上の品質文字列（Phred+33、配列と同じ長さ）を登録する
+    pub fn set_quality(&mut self, seq_id: &str, quality: String) {
+        self.qualities
+            .insert(seq_id.to_string(), SequenceSource::Memory(quality.into()));
+    }
+
+    /// FASTQ レコードの品質文字列（Phred+33）。品質のない配列は None
+    pub fn get_quality(&self, seq_id: &str) -> Result<Option<String>, StorageError> {
+        match self.qualities.get(seq_id) {
+            Some(source) => quality_text(source).map(Some),
+            None if self.sequences.contains_key(seq_id) => Ok(None),
+            None => Err(StorageError::SequenceNotFound(seq_id.to_string())),
+        }
     }
 
-    fn read_file_window(
+    /// `get_window` と同じ区間を返す。メモリ上の配列は新しい文字列を確保せず
+    /// バッファを共有する（ビューアのスクロールなど頻繁な呼び出し向け）
+    pub fn get_window_slice(
         &self,
-        path: &Path,
-        offset: &ByteOffset,
+        seq_id: &str,
         start: usize,
         end: usize,
-    ) -> Result<String, StorageError> {
-        // Handle edge cases consistently with memory implementation
-        if start >= offset.length {
-            return Err(StorageError::InvalidRange(start, end));
+    ) -> Result<SequenceSlice, StorageError> {
+        match self.sequences.get(seq_id) {
+            Some(SequenceSource::Memory(seq)) => memory_window(seq, start, end),
+            Some(SequenceSource::File { .. }) => {
+                self.get_window(seq_id, start, end).map(SequenceSlice::from)
+            }
+            None => Err(StorageError::SequenceNotFound(seq_id.to_string())),
         }
+    }
 
-        // Allow start >= end, return empty string
-        if start >= end {
-            return Ok(String::new());
+    /// インデックス構築用に配列ソースを複製
+    pub fn index_source(&self, seq_id: &str) -> Option<SequenceSource> {
+        self.sequences.get(seq_id).cloned()
+    }
+
+    pub fn index_status(&self, seq_id: &str) -> IndexStatus {
+        self.index_status
+            .get(seq_id)
+            .cloned()
+            .unwrap_or(IndexStatus::NotBuilt)
+    }
+
+    pub fn set_index_status(&mut self, seq_id: &str, status: IndexStatus) {
+        self.index_status.insert(seq_id.to_string(), status);
+    }
+
+    /// 構築済みインデックスを登録（配列が削除済みなら破棄）
+    pub fn install_indexes(&mut self, seq_id: &str, indexes: SequenceIndexes) {
+        if !self.sequences.contains_key(seq_id) {
+            return;
         }
+        self.set_index_status(
+            seq_id,
+            IndexStatus::Ready {
+                kmer_size: indexes.kmer.k(),
+                indexed_lines: indexes.lines.as_deref().map_or(0, LineIndex::line_count),
+            },
+        );
+        self.indexes.insert(seq_id.to_string(), indexes);
+    }
 
-        // Clamp end to sequence length
-        let end = end.min(offset.length);
+    pub fn kmer_index(&self, seq_id: &str) -> Option<Arc<KmerIndex>> {
+        self.indexes.get(seq_id).map(|indexes| indexes.kmer.clone())
+    }
 
-        let mut file = File::open(path)?;
-        let mut reader = BufReader::new(file);
+    /// 他の配列から派生した配列をアノテーション付きで保存
+    pub fn store_derived_sequence(
+        &mut self,
+        sequence: Sequence,
+        origin: SequenceOrigin,
+        features: Vec<SequenceFeature>,
+    ) -> String {
+        let seq_id = self.generate_id();
 
-        // Seek to the data start
-        reader.seek(SeekFrom::Start(offset.start))?;
+        self.sequences.insert(
+            seq_id.clone(),
+            SequenceSource::Memory(sequence.sequence.as_str().into()),
+        );
+        self.metadata.insert(
+            seq_id.clone(),
+            SequenceMetadata {
+                id: sequence.id,
+                name: sequence.name,
+                length: sequence.sequence.len(),
+                topology: sequence.topology,
+                file_path: None,
+                derived_from: Some(origin),
+            },
+        );
+        self.features.add_all(&seq_id, features);
 
-        let mut result = String::new();
-        let mut current_pos = 0;
-        let mut line = String::new();
+        seq_id
+    }
 
-        while current_pos < end {
-            line.clear();
-            let bytes_read = reader.read_line(&mut line)?;
-            if bytes_read == 0 {
-                break;
-            }
+    /// 編集後の配列で置き換える（メモリ上に移し、配列から作った検索インデックスと
+    /// FASTQ の品質は位置が合わなくなるので破棄する）
+    pub fn replace_sequence(
+        &mut self,
+        seq_id: &str,
+        sequence: &str,
+        topology: Topology,
+    ) -> Result<(), StorageError> {
+        let metadata = self
+            .metadata
+            .get_mut(seq_id)
+            .ok_or_else(|| StorageError::SequenceNotFound(seq_id.to_string()))?;
+        metadata.length = sequence.len();
+        metadata.topology = topology;
+        self.sequences
+            .insert(seq_id.to_string(), SequenceSource::Memory(sequence.into()));
+        self.indexes.remove(seq_id);
+        self.index_status.remove(seq_id);
+        self.qualities.remove(seq_id);
+        Ok(())
+    }
 
-            let trimmed = line.trim();
-            // Skip header lines and empty lines
-            if trimmed.starts_with('>')
-                || trimmed.starts_with('@')
-                || trimmed.starts_with('+')
-                || trimmed.is_empty()
-            {
-                continue;
-            }
+    /// プロジェクトファイルに保存する状態。配列ソースを複製するだけなので、リポジトリの
+    /// ロック中でも配列の長さによらずすぐに終わる（塩基は `ProjectDraft::into_snapshot` で読む）
+    pub fn project_draft(&self) -> ProjectDraft {
+        let entries = self
+            .seq_ids()
+            .into_iter()
+            .map(|seq_id| DraftEntry {
+                metadata: self.metadata[&seq_id].clone(),
+                source: self.sequences[&seq_id].clone(),
+                quality: self.qualities.get(&seq_id).cloned(),
+                features: self.features.get_all(&seq_id).to_vec(),
+                primer_pairs: self
+                    .designed_primers
+                    .get(&seq_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                seq_id,
+            })
+            .collect();
+        ProjectDraft {
+            entries,
+            unavailable: self.unavailable.clone(),
+            next_id: self.next_id,
+        }
+    }
 
-            // Process each character in the line
-            for ch in trimmed.chars() {
-                if current_pos >= start && current_pos < end {
-                    result.push(ch.to_ascii_uppercase());
-                }
-                current_pos += 1;
-                if current_pos >= end {
-                    break;
-                }
+    /// 保存したプロジェクトから復元する（配列IDは保存時のまま）。ファイルから取り込んだ配列は
+    /// 記録したレコードの位置から参照し直し、読み込めないものは `unavailable_sequences` に残す
+    pub fn from_project(snapshot: ProjectSnapshot) -> Self {
+        let mut repository = Self::new();
+        repository.next_id = snapshot.next_id.max(1);
+        let mut maps = HashMap::new();
+        for entry in snapshot.sequences {
+            let (source, file_quality, length) = match &entry.file {
+                Some(record) => match restore_file_record(record, &entry.metadata.id, &mut maps) {
+                    Ok((source, quality)) => (source, quality, record.length),
+                    Err(_) => {
+                        repository.unavailable.push(entry);
+                        continue;
+                    }
+                },
+                None => (
+                    SequenceSource::Memory(entry.sequence.as_str().into()),
+                    None,
+                    entry.sequence.len(),
+                ),
+            };
+            let quality = file_quality.or_else(|| {
+                entry
+                    .quality
+                    .map(|quality| SequenceSource::Memory(quality.into()))
+            });
+            let mut metadata = entry.metadata;
+            metadata.length = length;
+            repository.sequences.insert(entry.seq_id.clone(), source);
+            repository.metadata.insert(entry.seq_id.clone(), metadata);
+            if let Some(quality) = quality {
+                repository.qualities.insert(entry.seq_id.clone(), quality);
+            }
+            repository.features.add_all(&entry.seq_id, entry.features);
+            if !entry.primer_pairs.is_empty() {
+                repository
+                    .designed_primers
+                    .insert(entry.seq_id, entry.primer_pairs);
             }
         }
+        repository
+    }
+
+    /// 開いたプロジェクトのうち取り込み元ファイルから読み込めなかった配列の ID
+    pub fn unavailable_sequences(&self) -> Vec<String> {
+        self.unavailable
+            .iter()
+            .map(|entry| entry.seq_id.clone())
+            .collect()
+    }
+}
+
+/// リポジトリのロック中に複製したプロジェクトの内容（`FileSequenceRepository::project_draft`）
+pub struct ProjectDraft {
+    entries: Vec<DraftEntry>,
+    unavailable: Vec<ProjectSequence>,
+    next_id: usize,
+}
+
+struct DraftEntry {
+    seq_id: String,
+    metadata: SequenceMetadata,
+    source: SequenceSource,
+    quality: Option<SequenceSource>,
+    features: Vec<SequenceFeature>,
+    primer_pairs: Vec<PrimerPair>,
+}
 
-        Ok(result)
+impl ProjectDraft {
+    /// 保存するスナップショットを作る。メモリ上の配列は塩基を含め、ファイル上の配列は
+    /// レコードの位置と行の配置だけを記録する
+    pub fn into_snapshot(self) -> Result<ProjectSnapshot, StorageError> {
+        let mut sequences = self
+            .entries
+            .into_iter()
+            .map(DraftEntry::into_project_sequence)
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        sequences.extend(self.unavailable);
+        Ok(ProjectSnapshot {
+            sequences,
+            next_id: self.next_id,
+        })
+    }
+}
+
+impl DraftEntry {
+    fn into_project_sequence(self) -> Result<ProjectSequence, StorageError> {
+        let (sequence, quality, file) = match self.source {
+            SequenceSource::Memory(sequence) => (
+                sequence.to_string(),
+                self.quality.as_ref().map(quality_text).transpose()?,
+                None,
+            ),
+            SequenceSource::File {
+                path,
+                offset,
+                lines,
+                ..
+            } => {
+                let (quality, quality_start) = match self.quality {
+                    Some(SequenceSource::File { offset, .. }) => (None, Some(offset.start)),
+                    Some(SequenceSource::Memory(quality)) => (Some(quality.to_string()), None),
+                    None => (None, None),
+                };
+                let record = FileRecord {
+                    path,
+                    start: offset.start,
+                    length: offset.length,
+                    lines: lines.spacing(),
+                    quality_start,
+                };
+                (String::new(), quality, Some(record))
+            }
+        };
+        Ok(ProjectSequence {
+            seq_id: self.seq_id,
+            metadata: self.metadata,
+            sequence,
+            quality,
+            file,
+            features: self.features,
+            primer_pairs: self.primer_pairs,
+        })
     }
 }
 
@@ -352,7 +1547,7 @@ impl SequenceRepository for FileSequenceRepository {
 
         self.sequences.insert(
             seq_id.clone(),
-            SequenceSource::Memory(sequence.sequence.clone()),
+            SequenceSource::Memory(sequence.sequence.as_str().into()),
         );
         self.metadata.insert(
             seq_id.clone(),
@@ -362,6 +1557,7 @@ impl SequenceRepository for FileSequenceRepository {
                 length: sequence.sequence.len(),
                 topology: sequence.topology,
                 file_path: None,
+                derived_from: None,
             },
         );
 
@@ -373,7 +1569,7 @@ impl SequenceRepository for FileSequenceRepository {
         file_path: &Path,
         format: &str,
     ) -> Result<String, Self::Error> {
-        self.import_from_file(file_path, format)
+        Ok(self.import_from_file(file_path, format)?.swap_remove(0))
     }
 
     fn get_metadata(&self, seq_id: &str) -> Option<SequenceMetadata> {
@@ -382,10 +1578,10 @@ impl SequenceRepository for FileSequenceRepository {
 
     fn get_sequence(&self, seq_id: &str) -> Result<String, Self::Error> {
         match self.sequences.get(seq_id) {
-            Some(SequenceSource::Memory(seq)) => Ok(seq.clone()),
-            Some(SequenceSource::File { path, offset }) => {
-                self.read_file_window(path, offset, 0, offset.length)
-            }
+            Some(SequenceSource::Memory(seq)) => Ok(seq.to_string()),
+            Some(SequenceSource::File {
+                offset, map, lines, ..
+            }) => read_file_window(map.bytes()?, offset, lines, 0, offset.length),
             None => Err(StorageError::SequenceNotFound(seq_id.to_string())),
         }
     }
@@ -393,25 +1589,313 @@ impl SequenceRepository for FileSequenceRepository {
     fn get_window(&self, seq_id: &str, start: usize, end: usize) -> Result<String, Self::Error> {
         match self.sequences.get(seq_id) {
             Some(SequenceSource::Memory(seq)) => {
-                // Handle edge cases consistently
-                if start >= seq.len() {
-                    return Err(StorageError::InvalidRange(start, end));
-                }
+                memory_window(seq, start, end).map(|window| window.to_string())
+            }
+            Some(SequenceSource::File {
+                offset, map, lines, ..
+            }) => read_file_window(map.bytes()?, offset, lines, start, end),
+            None => Err(StorageError::SequenceNotFound(seq_id.to_string())),
+        }
+    }
+}
 
-                // Allow start >= end, return empty string
-                if start >= end {
-                    return Ok(String::new());
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_line_index_window_reads() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, ">lines").unwrap();
+        for line in ["AAAAACCCCC", "GGGGGTTTTT", "ACGTACGTAC", "TTG"] {
+            writeln!(temp_file, "{}", line).unwrap();
+        }
 
-                // Clamp end to sequence length
-                let end = end.min(seq.len());
-                // Convert to uppercase for consistency
-                Ok(seq[start..end].to_ascii_uppercase())
+        let mut repository = FileSequenceRepository::new();
+        let seq_id = repository
+            .import_large_file(temp_file.path(), "fasta")
+            .unwrap()
+            .remove(0);
+        let Some(SequenceSource::File { lines, .. }) = repository.index_source(&seq_id) else {
+            panic!("expected a file source");
+        };
+        // 行の長さが揃っているので行ごとの表は持たない
+        assert!(matches!(
+            lines.layout,
+            LineLayout::Uniform {
+                first: 7,
+                byte_step: 11,
+                base_step: 10,
+                lines: 4
             }
-            Some(SequenceSource::File { path, offset }) => {
-                self.read_file_window(path, offset, start, end)
+        ));
+        let before: Vec<String> = [(0, 5), (8, 23), (29, 33)]
+            .iter()
+            .map(|&(start, end)| repository.get_window(&seq_id, start, end).unwrap())
+            .collect();
+
+        let indexes = repository
+            .index_source(&seq_id)
+            .unwrap()
+            .build_indexes(4)
+            .unwrap();
+        assert_eq!(indexes.lines.as_ref().unwrap().line_count(), 4);
+        repository.install_indexes(&seq_id, indexes);
+        assert_eq!(
+            repository.index_status(&seq_id),
+            IndexStatus::Ready {
+                kmer_size: 4,
+                indexed_lines: 4
             }
-            None => Err(StorageError::SequenceNotFound(seq_id.to_string())),
+        );
+
+        let indexed: Vec<String> = [(0, 5), (8, 23), (29, 33)]
+            .iter()
+            .map(|&(start, end)| repository.get_window(&seq_id, start, end).unwrap())
+            .collect();
+        assert_eq!(indexed, before);
+        assert_eq!(indexed[1], "CCGGGGGTTTTTACG");
+        assert_eq!(indexed[2], "CTTG");
+    }
+
+    #[test]
+    fn test_irregular_lines_fall_back_to_per_line_index() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(
+            temp_file,
+            ">ragged\r\nacgtac\r\n  GGG\r\n\r\nTTTTTTTT\r\nCA\r\n>next\r\nAAAA\r\n"
+        )
+        .unwrap();
+
+        let mut repository = FileSequenceRepository::new();
+        let seq_ids = repository
+            .import_records_from_file(temp_file.path(), "fasta", 1)
+            .unwrap();
+        let Some(SequenceSource::File { lines, .. }) = repository.index_source(&seq_ids[0]) else {
+            panic!("expected a file source");
+        };
+        assert_eq!(lines.line_count(), 4);
+        assert_eq!(lines.locate(7), Some((19, 6)));
+        assert_eq!(lines.locate(18), Some((36, 17)));
+
+        let sequence = repository.get_sequence(&seq_ids[0]).unwrap();
+        assert_eq!(sequence, "ACGTACGGGTTTTTTTTCA");
+        for (start, end) in [(0, 19), (5, 10), (7, 8), (16, 40)] {
+            assert_eq!(
+                repository.get_window(&seq_ids[0], start, end).unwrap(),
+                sequence[start..end.min(sequence.len())]
+            );
+        }
+        assert_eq!(repository.get_sequence(&seq_ids[1]).unwrap(), "AAAA");
+    }
+
+    #[test]
+    fn test_fai_is_written_and_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let fasta = dir.path().join("genome.fa");
+        std::fs::write(
+            &fasta,
+            ">chr1 first\nACGTACGTAC\nGGGGG\n>chr2\nTTTTTTTTTT\nCCCCCCCCCC\nAA\n",
+        )
+        .unwrap();
+
+        let mut repository = FileSequenceRepository::new();
+        repository
+            .import_records_from_file(&fasta, "fasta", 1)
+            .unwrap();
+        let fai = fai_path(&fasta);
+        assert_eq!(
+            std::fs::read_to_string(&fai).unwrap(),
+            "chr1\t15\t12\t10\t11\nchr2\t22\t35\t10\t11\n"
+        );
+
+        // 2番目のレコードだけの .fai は走査せずにそのまま使う
+        std::fs::write(&fai, "chr2\t22\t35\t10\t11\n").unwrap();
+        let seq_ids = repository.import_large_file(&fasta, "fasta").unwrap();
+        assert_eq!(seq_ids.len(), 1);
+        let seq_id = &seq_ids[0];
+        let metadata = repository.get_metadata(seq_id).unwrap();
+        assert_eq!((metadata.id.as_str(), metadata.length), ("chr2", 22));
+        assert_eq!(
+            repository.get_window(seq_id, 8, 21).unwrap(),
+            "TTCCCCCCCCCCA"
+        );
+
+        // ヘッダーと合わない .fai は古いものとして走査し直す
+        std::fs::write(&fai, "chr9\t22\t35\t10\t11\n").unwrap();
+        let seq_ids = repository
+            .import_records_from_file(&fasta, "fasta", 1)
+            .unwrap();
+        assert_eq!(seq_ids.len(), 2);
+        assert_eq!(repository.get_metadata(&seq_ids[0]).unwrap().name, "first");
+    }
+
+    #[test]
+    fn test_file_source_streams_in_chunks() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, ">chunks").unwrap();
+        for line in ["acgtacgtac", "GGGGNNNNNN", "", "TTAC"] {
+            writeln!(temp_file, "{}", line).unwrap();
         }
+        writeln!(temp_file, ">next").unwrap();
+        writeln!(temp_file, "CCCC").unwrap();
+
+        let mut repository = FileSequenceRepository::new();
+        let seq_ids = repository
+            .import_large_file(temp_file.path(), "fasta")
+            .unwrap();
+        assert_eq!(seq_ids.len(), 2);
+        let seq_id = &seq_ids[0];
+        let source = repository.index_source(seq_id).unwrap();
+
+        let mut chunks = Vec::new();
+        source
+            .for_each_chunk(8, |chunk| chunks.push(chunk.to_string()))
+            .unwrap();
+        // 行単位で読み、8 文字に達したら渡す（次のレコードは読まない）
+        assert_eq!(chunks, vec!["ACGTACGTAC", "GGGGNNNNNN", "TTAC"]);
+        assert_eq!(chunks.concat(), repository.get_sequence(seq_id).unwrap());
+
+        // 後続のレコードも自分のオフセットから読める
+        let metadata = repository.get_metadata(&seq_ids[1]).unwrap();
+        assert_eq!((metadata.id.as_str(), metadata.length), ("next", 4));
+        assert_eq!(repository.get_window(&seq_ids[1], 1, 3).unwrap(), "CC");
+    }
+
+    #[test]
+    fn test_truncated_file_fails_cleanly() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, ">truncated").unwrap();
+        writeln!(temp_file, "{}", "ACGT".repeat(1000)).unwrap();
+
+        let mut repository = FileSequenceRepository::new();
+        let seq_ids = repository
+            .import_large_file(temp_file.path(), "fasta")
+            .unwrap();
+        assert_eq!(repository.get_window(&seq_ids[0], 0, 4).unwrap(), "ACGT");
+
+        // 取り込み後に切り詰められたファイルはマップの範囲外を読まずにエラーを返す
+        temp_file.as_file().set_len(16).unwrap();
+        assert!(matches!(
+            repository.get_window(&seq_ids[0], 3000, 3004),
+            Err(StorageError::IoError(_))
+        ));
+        assert!(repository.get_sequence(&seq_ids[0]).is_err());
+    }
+
+    #[test]
+    fn test_parallel_record_scan_is_deterministic() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        for i in 0..50 {
+            writeln!(temp_file, ">contig_{} sample {}", i, i % 3).unwrap();
+            for _ in 0..(i % 4) {
+                writeln!(temp_file, "{}", "ACGTACGTAC".repeat(i % 5 + 1)).unwrap();
+            }
+        }
+
+        let sequential = scan_fasta(temp_file.path(), 1).unwrap();
+        assert_eq!(sequential.len(), 50);
+        assert_eq!(sequential[7].id, "contig_7");
+        assert_eq!(sequential[7].name, "sample 1");
+        assert_eq!(sequential[7].length, 3 * 30);
+        // 範囲の境界が行やレコードの途中に来ても結果は変わらない
+        for chunks in [2, 3, 7, 64, 257] {
+            assert_eq!(scan_fasta(temp_file.path(), chunks).unwrap(), sequential);
+        }
+
+        let mut repository = FileSequenceRepository::new();
+        let seq_ids = repository
+            .import_records_from_file(temp_file.path(), "fasta", 4)
+            .unwrap();
+        assert_eq!(seq_ids[0], "seq_1");
+        assert_eq!(seq_ids[49], "seq_50");
+        assert_eq!(repository.get_metadata("seq_8").unwrap().id, "contig_7");
+        assert_eq!(
+            repository.get_window("seq_8", 25, 35).unwrap(),
+            "CGTACACGTA"
+        );
+    }
+
+    #[test]
+    fn test_fastq_records_keep_qualities() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        // 品質行が '@' や '+' で始まっても配列と取り違えない
+        write!(
+            temp_file,
+            "@r1 first\nACGT\n+\n@I+I\n@r2\nGGCCA\n+r2\n+++++\n"
+        )
+        .unwrap();
+
+        let mut repository = FileSequenceRepository::new();
+        let seq_ids = repository
+            .import_records_from_file(temp_file.path(), "fastq", 1)
+            .unwrap();
+        assert_eq!(repository.get_sequence(&seq_ids[1]).unwrap(), "GGCCA");
+        assert_eq!(
+            repository.get_quality(&seq_ids[0]).unwrap().as_deref(),
+            Some("@I+I")
+        );
+        assert_eq!(
+            repository.get_quality(&seq_ids[1]).unwrap().as_deref(),
+            Some("+++++")
+        );
+
+        let readset_id = repository.register_read_set(seq_ids.clone(), Some(temp_file.path()));
+        let read_set = &repository.read_sets[&readset_id];
+        assert_eq!(read_set.page(1, 10), &seq_ids[1..]);
+        assert!(read_set.page(5, 10).is_empty());
+
+        let in_memory = repository.import_from_text(">m\nACGT", "fasta").unwrap();
+        assert_eq!(repository.get_quality(&in_memory).unwrap(), None);
+        assert!(repository.get_quality("seq_missing").is_err());
+    }
+
+    #[test]
+    fn test_replace_sequence_drops_indexes() {
+        let mut repository = FileSequenceRepository::new();
+        let seq_id = repository
+            .import_from_text(">edit\nACGTACGT", "fasta")
+            .unwrap();
+        let indexes = repository
+            .index_source(&seq_id)
+            .unwrap()
+            .build_indexes(4)
+            .unwrap();
+        repository.install_indexes(&seq_id, indexes);
+
+        repository
+            .replace_sequence(&seq_id, "ACGTTTACGT", Topology::Circular)
+            .unwrap();
+        assert_eq!(repository.get_sequence(&seq_id).unwrap(), "ACGTTTACGT");
+        let metadata = repository.get_metadata(&seq_id).unwrap();
+        assert_eq!(
+            (metadata.length, metadata.topology),
+            (10, Topology::Circular)
+        );
+        assert_eq!(repository.index_status(&seq_id), IndexStatus::NotBuilt);
+        assert!(repository.kmer_index(&seq_id).is_none());
+        assert!(repository
+            .replace_sequence("seq_missing", "A", Topology::Linear)
+            .is_err());
+    }
+
+    #[test]
+    fn test_import_all_records_from_text() {
+        let fastq: String = (0..12)
+            .map(|i| format!("@read_{}\nACGT\n+\nIIII\n", i))
+            .collect();
+        let mut repository = FileSequenceRepository::new();
+        let first = repository.import_from_text(&fastq, "fastq").unwrap();
+        assert_eq!(repository.seq_ids(), vec![first]);
+
+        let seq_ids = repository.import_all_from_text(&fastq, "fastq").unwrap();
+        assert_eq!(seq_ids.len(), 12);
+        assert_eq!(repository.get_metadata(&seq_ids[11]).unwrap().id, "read_11");
+        // seq_10 以降も採番順に並ぶ
+        let listed = repository.seq_ids();
+        assert_eq!(listed[1..], seq_ids[..]);
+        assert_eq!(listed.last().unwrap(), "seq_13");
     }
 }
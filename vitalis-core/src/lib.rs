@@ -10,15 +10,42 @@ pub mod stats;
 pub mod storage;
 
 // Re-export domain types for public API
+pub use domain::region::{Region, RegionError};
 pub use domain::{BaseCount, DetailedStats, Range, Topology, WindowStats};
 
 // Re-export application layer commands for Tauri
 pub use application::{
-    calculate_primer_gc, calculate_primer_tm, design_primers, detailed_stats,
-    detailed_stats_enhanced, evaluate_primer_multiplex, export, get_genbank_metadata, get_meta,
-    get_window, import_from_file, import_sequence, parse_and_import, parse_preview, stats,
-    storage_info, window_stats, DetailedStatsEnhancedResponse, DetailedStatsResponse,
-    ExportResponse, GenBankFeatureInfo, GenBankMetadata, ImportFromFileRequest, ImportResponse,
-    ParsePreviewResponse, SequenceInfo, SequenceMeta, SequenceStats, WindowResponse,
-    WindowStatsItem, WindowStatsResponse,
+    add_bookmark, add_variants, autosave_project, calculate_kmer_spectrum,
+    calculate_oligo_properties, calculate_primer_gc, calculate_primer_tm, check_primer_specificity,
+    close_workspace, codon_usage, compare_codon_usage, configure_reference_settings,
+    conservation_track, convert_concentration, convert_coordinates, convert_oligo_amount,
+    cutter_summary, delete_range, describe_variant_hgvs, design_assembly_primers,
+    design_expression_construct, design_guides, design_homology_arms, design_hrm_tiling,
+    design_junction_primers, design_primers, design_primers_at_reference, design_primers_streaming,
+    design_qpcr_assay, detailed_stats, detailed_stats_enhanced, detect_backbones,
+    detect_frameshift, digest_protocol, dilution_series, dotplot, evaluate_primer_multiplex,
+    export, export_assay_document, export_bed, export_project_archive, export_proteome,
+    find_in_sequence, find_nullomers, find_orfs, find_protein_in_workspace, find_repeats,
+    find_restriction_sites, format_sequence_for_copy, gc_skew_profile, generate_linkers,
+    get_entrez_settings, get_features, get_genbank_metadata, get_input_limits, get_meta, get_reads,
+    get_score_tracks, get_session_provenance, get_variants, get_warning_policy, get_window,
+    import_alignment, import_bed, import_fastq_readset, import_from_file, import_gff3,
+    import_records_from_file, import_reference_region, import_sequence, insert_bases,
+    list_analysis_plugins, list_bookmarks, list_reference_genomes, list_restriction_enzymes,
+    list_sequences, list_workspaces, materialize_amplicon, melting_map, next_feature,
+    oligo_cross_talk, oligo_resuspension, open_project, open_workspace, parse_and_import,
+    parse_and_import_all, parse_preview, plan_standard_curve, predict_melt_curve, predict_rbs,
+    predict_signal_peptide, previous_feature, primer_duplex, protein_hydropathy, qc_primer_pair,
+    read_set_statistics, readset_distributions, readset_stats, redo_edit, register_analysis_plugin,
+    register_backbone, register_reference_genome, remove_bookmark, render_map_model, replace_range,
+    run_analysis_plugin, run_batch_job, run_script, save_project, scan_protein_motifs,
+    screen_vector_contamination, search_motif, set_autosave, set_entrez_settings,
+    set_feature_display, set_reproducibility_mode, set_topology, set_warning_policy,
+    simulate_cloning, simulate_mutations, split_at_gaps, stats, storage_info,
+    stress_test_primer_panel, suggest_probe_labels, suggest_relaxation, suggest_screening_strategy,
+    tm_grid, translate, undo_edit, unregister_reference_genome, window_stats, with_workspace,
+    CommandError, DetailedStatsEnhancedResponse, DetailedStatsResponse, ExportResponse,
+    GenBankFeatureInfo, GenBankMetadata, ImportFileResponse, ImportFromFileRequest,
+    ImportRecordsResponse, ImportResponse, ParsePreviewResponse, SequenceInfo, SequenceMeta,
+    SequenceStats, WindowResponse, WindowStatsItem, WindowStatsResponse, DEFAULT_WORKSPACE,
 };
@@ -0,0 +1,178 @@
+// Service layer: Amplicon extraction from accepted primer pairs
+use crate::domain::feature::SequenceFeature;
+use crate::domain::primer::{Amplicon, PrimerPair};
+use crate::domain::Strand;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AmpliconError {
+    #[error("Primer pair does not define a valid product: forward at {0}, reverse ends at {1}")]
+    InvalidProduct(usize, usize),
+    #[error("Product end {0} exceeds template length {1}")]
+    OutOfTemplate(usize, usize),
+}
+
+/// Amplicon extraction service
+pub struct AmpliconService;
+
+impl Default for AmpliconService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AmpliconService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Cut the product of `pair` out of `template`, annotating both primer
+    /// binding sites and carrying over overlapping template features.
+    pub fn extract(
+        &self,
+        template: &str,
+        template_features: &[SequenceFeature],
+        pair: &PrimerPair,
+    ) -> Result<Amplicon, AmpliconError> {
+        let start = pair.forward.position;
+        let end = pair.reverse.position + pair.reverse.length;
+
+        // The reverse primer must bind inside the product (not upstream of the forward one)
+        if start >= end || start + pair.forward.length > end || pair.reverse.position < start {
+            return Err(AmpliconError::InvalidProduct(start, end));
+        }
+        if end > template.len() {
+            return Err(AmpliconError::OutOfTemplate(end, template.len()));
+        }
+
+        let length = end - start;
+        let mut features: Vec<SequenceFeature> = template_features
+            .iter()
+            .filter_map(|feature| feature.clip_and_shift(start, end))
+            .map(|mut feature| {
+                // IDs belong to the template's store; the new entry gets fresh ones
+                feature.id.clear();
+                feature
+            })
+            .collect();
+
+        let mut forward_site =
+            SequenceFeature::new("primer_bind", 0, pair.forward.length, Strand::Forward);
+        forward_site
+            .qualifiers
+            .insert("label".to_string(), "forward primer".to_string());
+        forward_site
+            .qualifiers
+            .insert("note".to_string(), pair.forward.sequence.clone());
+
+        let mut reverse_site = SequenceFeature::new(
+            "primer_bind",
+            length - pair.reverse.length,
+            length,
+            Strand::Reverse,
+        );
+        reverse_site
+            .qualifiers
+            .insert("label".to_string(), "reverse primer".to_string());
+        reverse_site
+            .qualifiers
+            .insert("note".to_string(), pair.reverse.sequence.clone());
+
+        features.push(forward_site);
+        features.push(reverse_site);
+
+        Ok(Amplicon {
+            template_start: start,
+            template_end: end,
+            sequence: template[start..end].to_string(),
+            features,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::primer::{Primer, PrimerDirection, ValidationResults};
+    use chrono::Utc;
+
+    fn primer(sequence: &str, position: usize, direction: PrimerDirection) -> Primer {
+        Primer {
+            sequence: sequence.to_string(),
+            position,
+            length: sequence.len(),
+            tm: 60.0,
+            gc_content: 50.0,
+            self_dimer_score: 0.0,
+            hairpin_score: 0.0,
+            three_prime_stability: 0.0,
+            direction,
+            quality_score: 100.0,
+            quality_warnings: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    fn pair(forward: Primer, reverse: Primer) -> PrimerPair {
+        PrimerPair {
+            id: "pair_1".to_string(),
+            amplicon_length: (reverse.position + reverse.length).saturating_sub(forward.position),
+            forward,
+            reverse,
+            amplicon_sequence: String::new(),
+            target_gene: None,
+            target_transcript: None,
+            compatibility_score: 0.0,
+            created_by: "test".to_string(),
+            created_at: Utc::now(),
+            tags: Vec::new(),
+            validation_results: ValidationResults::new(),
+        }
+    }
+
+    #[test]
+    fn test_extract_annotates_primers_and_template_features() {
+        let template = "AAAAACCCCCGGGGGTTTTTAAAAACCCCC";
+        let gene = SequenceFeature::new("gene", 0, 12, Strand::Forward);
+        let pair = pair(
+            primer("CCCC", 5, PrimerDirection::Forward),
+            primer("AAAA", 20, PrimerDirection::Reverse),
+        );
+
+        let amplicon = AmpliconService::new()
+            .extract(template, &[gene], &pair)
+            .unwrap();
+
+        assert_eq!(amplicon.sequence, "CCCCCGGGGGTTTTTAAAA");
+        assert_eq!(amplicon.template_start, 5);
+        assert_eq!(amplicon.template_end, 24);
+
+        let copied = &amplicon.features[0];
+        assert_eq!((copied.start, copied.end), (0, 7));
+        assert!(copied.qualifiers.contains_key("truncated"));
+
+        let reverse_site = amplicon.features.last().unwrap();
+        assert_eq!(reverse_site.feature_type, "primer_bind");
+        assert_eq!((reverse_site.start, reverse_site.end), (15, 19));
+        assert_eq!(reverse_site.strand, Strand::Reverse);
+    }
+
+    #[test]
+    fn test_extract_rejects_inverted_pair() {
+        let template = "ACGT".repeat(10);
+        let inverted = pair(
+            primer("CCCC", 20, PrimerDirection::Forward),
+            primer("AAAA", 5, PrimerDirection::Reverse),
+        );
+        let result = AmpliconService::new().extract(&template, &[], &inverted);
+        assert!(matches!(result, Err(AmpliconError::InvalidProduct(_, _))));
+
+        // A long reverse primer starting upstream of the forward primer still ends after it
+        let overlapping = pair(
+            primer(&"C".repeat(20), 10, PrimerDirection::Forward),
+            primer(&"A".repeat(30), 5, PrimerDirection::Reverse),
+        );
+        let result = AmpliconService::new().extract(&template, &[], &overlapping);
+        assert!(matches!(result, Err(AmpliconError::InvalidProduct(10, 35))));
+    }
+}
@@ -0,0 +1,255 @@
+// Service layer: Amplicon QC for selected primer pairs
+use crate::domain::primer::{
+    AmpliconHairpin, AmpliconQc, AmpliconQcParams, Homopolymer, PrimerPair, TandemRepeat,
+};
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+use crate::domain::warning::{WarningCategory, WarningCode};
+
+/// ヘアピン探索のウィンドウ長と移動幅
+const HAIRPIN_WINDOW: usize = 40;
+const HAIRPIN_STEP: usize = 20;
+/// タンデムリピートとみなす単位長の範囲と最小長
+const REPEAT_UNIT_MAX: usize = 6;
+const REPEAT_MIN_COPIES: usize = 3;
+const REPEAT_MIN_LENGTH: usize = 12;
+
+/// Amplicon QC service
+pub struct AmpliconQcService {
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for AmpliconQcService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AmpliconQcService {
+    pub fn new() -> Self {
+        Self {
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// 増幅産物をQCし、結果と警告をペアのバリデーション結果に追加
+    pub fn apply(&self, pair: &mut PrimerPair, params: &AmpliconQcParams) {
+        let qc = self.analyze(&pair.amplicon_sequence, params);
+        let validation = &mut pair.validation_results;
+        validation
+            .warnings
+            .retain(|warning| warning.category != WarningCategory::Amplicon);
+        validation.warnings.extend(qc.warnings.iter().cloned());
+        validation.amplicon_qc = Some(qc);
+    }
+
+    pub fn analyze(&self, amplicon: &str, params: &AmpliconQcParams) -> AmpliconQc {
+        let sequence = amplicon.to_ascii_uppercase();
+        let bytes = sequence.as_bytes();
+        let mut warnings = Vec::new();
+
+        // ウィンドウGC含量（増幅産物がウィンドウより短い場合は全体）
+        let window = params.gc_window.clamp(1, bytes.len().max(1));
+        let gc_values: Vec<f32> = bytes
+            .windows(window)
+            .map(|w| {
+                w.iter().filter(|&&b| b == b'G' || b == b'C').count() as f32 * 100.0 / window as f32
+            })
+            .collect();
+        let gc_min_window = gc_values.iter().copied().fold(f32::INFINITY, f32::min);
+        let gc_max_window = gc_values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let (gc_min_window, gc_max_window) = if gc_values.is_empty() {
+            (0.0, 0.0)
+        } else {
+            (gc_min_window, gc_max_window)
+        };
+        if !gc_values.is_empty() && gc_min_window < params.gc_min {
+            warnings.push(
+                WarningCode::AmpliconLowWindowGc {
+                    gc: gc_min_window,
+                    window,
+                }
+                .into(),
+            );
+        }
+        if gc_max_window > params.gc_max {
+            warnings.push(
+                WarningCode::AmpliconHighWindowGc {
+                    gc: gc_max_window,
+                    window,
+                }
+                .into(),
+            );
+        }
+
+        let longest_homopolymer = longest_homopolymer(bytes);
+        if let Some(run) = longest_homopolymer
+            .as_ref()
+            .filter(|run| run.length > params.max_homopolymer)
+        {
+            warnings.push(
+                WarningCode::AmpliconHomopolymer {
+                    length: run.length,
+                    base: run.base,
+                    position: run.start + 1,
+                }
+                .into(),
+            );
+        }
+
+        let hairpins = self.hairpins(&sequence, params.hairpin_delta_g);
+        if let Some(worst) = hairpins.iter().min_by(|a, b| {
+            a.delta_g
+                .partial_cmp(&b.delta_g)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }) {
+            warnings.push(
+                WarningCode::AmpliconHairpins {
+                    count: hairpins.len(),
+                    threshold: params.hairpin_delta_g,
+                    delta_g: worst.delta_g,
+                    position: worst.position + 1,
+                }
+                .into(),
+            );
+        }
+
+        let tandem_repeats = tandem_repeats(bytes);
+        let mut covered = vec![false; bytes.len()];
+        for repeat in &tandem_repeats {
+            covered[repeat.start..repeat.end].fill(true);
+        }
+        let repeat_fraction = if bytes.is_empty() {
+            0.0
+        } else {
+            covered.iter().filter(|&&c| c).count() as f32 / bytes.len() as f32
+        };
+        if repeat_fraction > params.max_repeat_fraction {
+            warnings.push(
+                WarningCode::AmpliconTandemRepeats {
+                    percent: repeat_fraction * 100.0,
+                }
+                .into(),
+            );
+        }
+
+        AmpliconQc {
+            gc_min_window,
+            gc_max_window,
+            longest_homopolymer,
+            hairpins,
+            tandem_repeats,
+            repeat_fraction,
+            passed: warnings.is_empty(),
+            warnings,
+            provenance: Some(self.calculator.provenance()),
+        }
+    }
+
+    /// 重なりのあるウィンドウごとに最も安定なヘアピンを評価し、閾値未満のものを返す
+    fn hairpins(&self, sequence: &str, threshold: f32) -> Vec<AmpliconHairpin> {
+        let mut hairpins: Vec<AmpliconHairpin> = Vec::new();
+        let last = sequence.len().saturating_sub(HAIRPIN_WINDOW);
+        for offset in (0..=last).step_by(HAIRPIN_STEP) {
+            let end = (offset + HAIRPIN_WINDOW).min(sequence.len());
+            let Ok(analysis) = self
+                .calculator
+                .calculate_enhanced_hairpin(&sequence[offset..end])
+            else {
+                continue;
+            };
+            let Some(best) = analysis.best_hairpin.filter(|h| h.score < threshold) else {
+                continue;
+            };
+
+            let position = offset + best.start_pos;
+            // 隣接ウィンドウで同じヘアピンを重複して数えない
+            if !hairpins.iter().any(|h| h.position == position) {
+                hairpins.push(AmpliconHairpin {
+                    position,
+                    stem_length: best.stem_length,
+                    loop_size: best.loop_size,
+                    delta_g: best.score,
+                });
+            }
+        }
+        hairpins
+    }
+}
+
+fn longest_homopolymer(bytes: &[u8]) -> Option<Homopolymer> {
+    let mut longest: Option<Homopolymer> = None;
+    let mut start = 0;
+    for i in 1..=bytes.len() {
+        if i == bytes.len() || bytes[i] != bytes[start] {
+            if longest.as_ref().is_none_or(|run| i - start > run.length) {
+                longest = Some(Homopolymer {
+                    base: bytes[start] as char,
+                    start,
+                    length: i - start,
+                });
+            }
+            start = i;
+        }
+    }
+    longest
+}
+
+/// 単位長2〜6塩基のタンデムリピート（3コピー以上かつ12塩基以上）を検出
+fn tandem_repeats(bytes: &[u8]) -> Vec<TandemRepeat> {
+    let mut repeats = Vec::new();
+    for unit in 2..=REPEAT_UNIT_MAX {
+        let mut start = 0;
+        while start + unit < bytes.len() {
+            let mut end = start + unit;
+            while end < bytes.len() && bytes[end] == bytes[end - unit] {
+                end += 1;
+            }
+            let length = end - start;
+            let is_homopolymer = bytes[start..start + unit]
+                .iter()
+                .all(|&b| b == bytes[start]);
+            if !is_homopolymer && length >= REPEAT_MIN_LENGTH.max(unit * REPEAT_MIN_COPIES) {
+                repeats.push(TandemRepeat {
+                    start,
+                    end,
+                    unit: String::from_utf8_lossy(&bytes[start..start + unit]).to_string(),
+                });
+                start = end;
+            } else {
+                start += 1;
+            }
+        }
+    }
+    repeats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_homopolymer_repeats_and_gc() {
+        let amplicon = format!(
+            "{}{}{}{}",
+            "ATGCTAGCTAGGCTAACGATCGATGCTAGCTAGCGGATCCGTAGCTAGTC",
+            "A".repeat(12),
+            "CA".repeat(15),
+            "GCGGCCGCGGGCCCGCGGCCGCGGCCGCGCGGCCGCCCGGGCGCGCCGGC"
+        );
+        let qc = AmpliconQcService::new().analyze(&amplicon, &AmpliconQcParams::default());
+
+        let run = qc.longest_homopolymer.unwrap();
+        assert_eq!((run.base, run.length, run.start), ('A', 12, 50));
+        assert!(qc.tandem_repeats.iter().any(|r| r.end - r.start >= 30));
+        assert!(qc.gc_max_window > 75.0);
+        assert!(!qc.passed);
+    }
+
+    #[test]
+    fn test_clean_amplicon_passes() {
+        let amplicon = "GCTAAAGACAATTACATAACATACACGTCAGCACGAAACTTGTTGGCCCAGTGTGAATCGCTTAAGGGTTAAGTAAGTGTGATGCATACGCCTTTACTTG";
+        let qc = AmpliconQcService::new().analyze(amplicon, &AmpliconQcParams::default());
+        assert!(qc.hairpins.is_empty(), "{:?}", qc.hairpins);
+        assert!(qc.passed, "{:?}", qc.warnings);
+    }
+}
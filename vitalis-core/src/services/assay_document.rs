@@ -0,0 +1,518 @@
+// Service layer: MIQE-oriented documentation of qPCR assays
+use crate::domain::assay::MiqeItem;
+use crate::domain::export_format::{ExportFormat, Unit};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::primer::{Primer, PrimerPair};
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+use crate::services::project_archive::escape_html;
+use chrono::{DateTime, Utc};
+
+/// 推奨アニーリング温度は Tm の低い方のプライマーからこの分だけ下げる（°C）
+const ANNEALING_OFFSET: f32 = 5.0;
+/// これ以下の増幅産物は2ステップ（アニーリングと伸長を兼ねる）で回す
+const TWO_STEP_MAX_AMPLICON: usize = 150;
+/// qPCR で効率よく増幅できる増幅産物の上限（bp）
+const MAX_QPCR_AMPLICON: usize = 200;
+/// プライマー間の Tm 差の許容値（°C）
+const MAX_PRIMER_TM_DIFFERENCE: f32 = 5.0;
+/// 加水分解プローブの Tm はプライマーよりこれ以上高くする（°C）
+const MIN_PROBE_TM_MARGIN: f32 = 5.0;
+
+/// Assay documentation service
+pub struct AssayDocumentService {
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for AssayDocumentService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AssayDocumentService {
+    pub fn new() -> Self {
+        Self {
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// 設計結果から MIQE チェックリストの項目を埋める。鋳型 `name` 上の
+    /// exon/intron アノテーションからプライマーの位置を記述する
+    pub fn checklist(
+        &self,
+        name: &str,
+        pair: &PrimerPair,
+        features: &[SequenceFeature],
+        probe: Option<&str>,
+        format: &ExportFormat,
+    ) -> Vec<MiqeItem> {
+        let validation = &pair.validation_results;
+        let celsius = |value: f32| format.quantity(value as f64, Unit::Celsius);
+        let energy = |value: f32| format.quantity(value as f64, Unit::KcalPerMol);
+        let both = |value: fn(&Primer) -> String| {
+            format!("F {} / R {}", value(&pair.forward), value(&pair.reverse))
+        };
+        let start = pair.forward.position;
+        let end = pair.reverse.position + pair.reverse.length;
+        let provenance = pair
+            .forward
+            .provenance
+            .clone()
+            .unwrap_or_else(|| self.calculator.provenance());
+        let annealing = pair.forward.tm.min(pair.reverse.tm) - ANNEALING_OFFSET;
+
+        let mut items = Vec::new();
+        let mut add = |section: &str, item: &str, value: Option<String>| {
+            items.push(MiqeItem {
+                section: section.to_string(),
+                item: item.to_string(),
+                value,
+            });
+        };
+
+        let section = "Target information";
+        add(section, "Gene symbol", pair.target_gene.clone());
+        add(section, "Sequence accession number", Some(name.to_string()));
+        add(
+            section,
+            "Location of amplicon",
+            Some(format!("{}..{} of {}", start + 1, end, name)),
+        );
+        add(
+            section,
+            "Amplicon length",
+            Some(format.quantity(pair.amplicon_length as f64, Unit::BasePairs)),
+        );
+        add(
+            section,
+            "In silico specificity screen",
+            validation
+                .specificity
+                .map(|specificity| format.quantity(specificity as f64, Unit::Dimensionless)),
+        );
+        add(
+            section,
+            "Location of each primer by exon or intron",
+            self.exon_location(pair, features),
+        );
+        add(
+            section,
+            "Splice variants targeted",
+            pair.target_transcript.clone(),
+        );
+
+        let section = "Oligonucleotides";
+        add(
+            section,
+            "Primer sequences",
+            Some(both(|primer| primer.sequence.clone())),
+        );
+        add(section, "Probe sequence", probe.map(str::to_string));
+        add(section, "Location and identity of any modifications", None);
+        add(section, "Manufacturer of oligonucleotides", None);
+        add(section, "Purification method", None);
+
+        let section = "Thermodynamic parameters";
+        add(
+            section,
+            "Primer Tm",
+            Some(format!(
+                "F {} / R {}",
+                celsius(pair.forward.tm),
+                celsius(pair.reverse.tm)
+            )),
+        );
+        if let Some(probe) = probe {
+            add(
+                section,
+                "Probe Tm",
+                self.calculator
+                    .calculate_tm_nearest_neighbor(probe)
+                    .ok()
+                    .map(celsius),
+            );
+        }
+        add(
+            section,
+            "Primer GC content",
+            Some(format!(
+                "F {} / R {}",
+                format.quantity(pair.forward.gc_content as f64, Unit::Percent),
+                format.quantity(pair.reverse.gc_content as f64, Unit::Percent)
+            )),
+        );
+        add(
+            section,
+            "Self-dimer ΔG",
+            Some(format!(
+                "F {} / R {}",
+                energy(pair.forward.self_dimer_score),
+                energy(pair.reverse.self_dimer_score)
+            )),
+        );
+        add(
+            section,
+            "Hairpin ΔG",
+            Some(format!(
+                "F {} / R {}",
+                energy(pair.forward.hairpin_score),
+                energy(pair.reverse.hairpin_score)
+            )),
+        );
+        add(
+            section,
+            "Primer-dimer check",
+            validation
+                .hetero_dimer_check
+                .map(|passed| if passed { "passed" } else { "failed" }.to_string()),
+        );
+        add(
+            section,
+            "Parameter set",
+            Some(format!(
+                "{} {} (Na+ {:.0} mM, Mg2+ {:.1} mM, oligo {:.0} nM)",
+                provenance.parameter_set,
+                provenance.version,
+                provenance.salt.sodium_concentration * 1e3,
+                provenance.salt.magnesium_concentration * 1e3,
+                provenance.conditions.primer_concentration * 1e9
+            )),
+        );
+        add(
+            section,
+            "Amplicon QC",
+            validation.amplicon_qc.as_ref().map(|qc| {
+                format!(
+                    "{} (window GC {}–{})",
+                    if qc.passed { "passed" } else { "failed" },
+                    format.quantity(qc.gc_min_window as f64, Unit::Percent),
+                    format.quantity(qc.gc_max_window as f64, Unit::Percent)
+                )
+            }),
+        );
+        add(
+            section,
+            "Design warnings",
+            Some(if validation.warnings.is_empty() {
+                "none".to_string()
+            } else {
+                validation
+                    .warnings
+                    .iter()
+                    .map(|warning| warning.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            }),
+        );
+
+        let section = "qPCR protocol";
+        add(
+            section,
+            "Primer concentration",
+            Some(format!(
+                "{:.0} nM each (design condition)",
+                provenance.conditions.primer_concentration * 1e9
+            )),
+        );
+        add(
+            section,
+            "Mg2+ concentration",
+            Some(format!(
+                "{:.1} mM (design condition)",
+                provenance.salt.magnesium_concentration * 1e3
+            )),
+        );
+        add(
+            section,
+            "Thermocycling parameters",
+            Some(self.cycling(pair.amplicon_length, annealing, format)),
+        );
+        add(
+            section,
+            "Melt curve",
+            Some(match probe {
+                Some(_) => "not required (hydrolysis probe)".to_string(),
+                None => "65–95 °C after cycling to confirm a single product".to_string(),
+            }),
+        );
+        add(section, "Polymerase identity and concentration", None);
+        add(section, "Reaction volume and amount of template", None);
+        add(section, "Instrument", None);
+
+        let section = "qPCR validation";
+        add(
+            section,
+            "Calibration curve",
+            validation.standard_curve.as_ref().and_then(|plan| {
+                let (top, bottom) = (plan.standards.first()?, plan.standards.last()?);
+                Some(format!(
+                    "{} points, {:.3e} to {:.3e} copies per reaction, {} replicates",
+                    plan.standards.len(),
+                    top.copies_per_reaction,
+                    bottom.copies_per_reaction,
+                    plan.replicates
+                ))
+            }),
+        );
+        add(section, "PCR efficiency calculated from slope", None);
+        add(section, "r² of calibration curve", None);
+        add(section, "Linear dynamic range", None);
+        add(section, "Limit of detection", None);
+        add(
+            section,
+            "Evidence of specificity (gel, sequencing, melt)",
+            None,
+        );
+        items
+    }
+
+    /// 設計値から読み取れる、qPCR アッセイとしての注意点
+    pub fn warnings(&self, pair: &PrimerPair, probe: Option<&str>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let tm_difference = (pair.forward.tm - pair.reverse.tm).abs();
+        if tm_difference > MAX_PRIMER_TM_DIFFERENCE {
+            warnings.push(format!(
+                "Primer Tm values differ by {:.1} °C (at most {} °C recommended)",
+                tm_difference, MAX_PRIMER_TM_DIFFERENCE
+            ));
+        }
+        if pair.amplicon_length > MAX_QPCR_AMPLICON {
+            warnings.push(format!(
+                "Amplicon is {} bp; qPCR amplicons of at most {} bp amplify most efficiently",
+                pair.amplicon_length, MAX_QPCR_AMPLICON
+            ));
+        }
+        if pair.validation_results.specificity.is_none() {
+            warnings.push("No in silico specificity screen recorded".to_string());
+        }
+        if let Some(tm) =
+            probe.and_then(|probe| self.calculator.calculate_tm_nearest_neighbor(probe).ok())
+        {
+            let primer_tm = pair.forward.tm.max(pair.reverse.tm);
+            if tm < primer_tm + MIN_PROBE_TM_MARGIN {
+                warnings.push(format!(
+                    "Probe Tm ({:.1} °C) should be at least {} °C above the primer Tm ({:.1} °C)",
+                    tm, MIN_PROBE_TM_MARGIN, primer_tm
+                ));
+            }
+        }
+        warnings
+    }
+
+    /// チェックリストの HTML。記入が必要な項目は空欄で強調する
+    pub fn html(
+        &self,
+        pair: &PrimerPair,
+        items: &[MiqeItem],
+        warnings: &[String],
+        created_at: DateTime<Utc>,
+    ) -> String {
+        let title = format!("qPCR assay {}", escape_html(&pair.id));
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", title));
+        out.push_str(
+            "<style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse}\
+             td,th{border:1px solid #ccc;padding:2px 8px;text-align:left}\
+             td.missing{background:#fff3cd}</style>\n",
+        );
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!("<h1>{}</h1>\n", title));
+        out.push_str(&format!(
+            "<p>MIQE checklist &middot; generated {}</p>\n",
+            created_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+
+        if !warnings.is_empty() {
+            out.push_str("<h2>Warnings</h2>\n<ul>\n");
+            for warning in warnings {
+                out.push_str(&format!("<li>{}</li>\n", escape_html(warning)));
+            }
+            out.push_str("</ul>\n");
+        }
+
+        let mut section = "";
+        for item in items {
+            if item.section != section {
+                if !section.is_empty() {
+                    out.push_str("</table>\n");
+                }
+                section = &item.section;
+                out.push_str(&format!("<h2>{}</h2>\n<table>\n", escape_html(section)));
+            }
+            match &item.value {
+                Some(value) => out.push_str(&format!(
+                    "<tr><th>{}</th><td>{}</td></tr>\n",
+                    escape_html(&item.item),
+                    escape_html(value)
+                )),
+                None => out.push_str(&format!(
+                    "<tr><th>{}</th><td class=\"missing\"></td></tr>\n",
+                    escape_html(&item.item)
+                )),
+            }
+        }
+        if !section.is_empty() {
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// exon/intron アノテーションに対するプライマーの位置（アノテーションがなければ None）
+    fn exon_location(&self, pair: &PrimerPair, features: &[SequenceFeature]) -> Option<String> {
+        let structural: Vec<&SequenceFeature> = features
+            .iter()
+            .filter(|feature| matches!(feature.feature_type.as_str(), "exon" | "intron"))
+            .collect();
+        if structural.is_empty() {
+            return None;
+        }
+
+        let locate = |primer: &Primer| {
+            let (start, end) = (primer.position, primer.position + primer.length);
+            let labels: Vec<String> = structural
+                .iter()
+                .filter(|feature| feature.overlaps(start, end))
+                .map(|feature| match feature.qualifiers.get("number") {
+                    Some(number) => format!("{} {}", feature.feature_type, number),
+                    None => feature.label(),
+                })
+                .collect();
+            if labels.is_empty() {
+                "unannotated".to_string()
+            } else {
+                labels.join(" / ")
+            }
+        };
+        Some(format!(
+            "F {}; R {}",
+            locate(&pair.forward),
+            locate(&pair.reverse)
+        ))
+    }
+
+    /// 推奨サイクル条件（短い増幅産物はアニーリングと伸長を兼ねる2ステップ）
+    fn cycling(&self, amplicon_length: usize, annealing: f32, format: &ExportFormat) -> String {
+        let annealing = format.quantity(annealing as f64, Unit::Celsius);
+        if amplicon_length <= TWO_STEP_MAX_AMPLICON {
+            format!(
+                "95 °C 2 min; 40 cycles of 95 °C 15 s, {} 60 s (combined annealing/extension)",
+                annealing
+            )
+        } else {
+            // 伸長は 1 kb あたり 60 秒、最短 15 秒
+            let extension = (amplicon_length * 60).div_ceil(1000).max(15);
+            format!(
+                "95 °C 2 min; 40 cycles of 95 °C 15 s, {} 30 s, 72 °C {} s",
+                annealing, extension
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::primer::{PrimerDirection, ValidationResults};
+    use crate::domain::Strand;
+
+    fn primer(sequence: &str, position: usize, tm: f32, direction: PrimerDirection) -> Primer {
+        Primer {
+            sequence: sequence.to_string(),
+            position,
+            length: sequence.len(),
+            tm,
+            gc_content: 50.0,
+            self_dimer_score: -2.5,
+            hairpin_score: -0.5,
+            three_prime_stability: 0.0,
+            direction,
+            quality_score: 100.0,
+            quality_warnings: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    fn pair(amplicon_length: usize) -> PrimerPair {
+        PrimerPair {
+            id: "pair_1".to_string(),
+            forward: primer("ACGTACGTACGTACGTACGT", 100, 60.2, PrimerDirection::Forward),
+            reverse: primer(
+                "TTGCAATTGCAATTGCAATT",
+                80 + amplicon_length,
+                59.1,
+                PrimerDirection::Reverse,
+            ),
+            amplicon_length,
+            amplicon_sequence: String::new(),
+            target_gene: Some("GAPDH".to_string()),
+            target_transcript: None,
+            compatibility_score: 0.0,
+            created_by: "test".to_string(),
+            created_at: Utc::now(),
+            tags: Vec::new(),
+            validation_results: ValidationResults::new(),
+        }
+    }
+
+    #[test]
+    fn test_checklist_fills_design_items() {
+        let service = AssayDocumentService::new();
+        let features: Vec<SequenceFeature> = [(0, 150), (150, 400)]
+            .iter()
+            .enumerate()
+            .map(|(index, &(start, end))| {
+                let mut exon = SequenceFeature::new("exon", start, end, Strand::Forward);
+                exon.qualifiers
+                    .insert("number".to_string(), (index + 1).to_string());
+                exon
+            })
+            .collect();
+        let items = service.checklist(
+            "NM_002046",
+            &pair(120),
+            &features,
+            None,
+            &ExportFormat::default(),
+        );
+        let value = |name: &str| {
+            items
+                .iter()
+                .find(|item| item.item == name)
+                .and_then(|item| item.value.clone())
+        };
+
+        assert_eq!(value("Gene symbol").as_deref(), Some("GAPDH"));
+        assert_eq!(
+            value("Location of amplicon").as_deref(),
+            Some("101..220 of NM_002046")
+        );
+        assert_eq!(value("Primer Tm").as_deref(), Some("F 60.2 °C / R 59.1 °C"));
+        assert_eq!(
+            value("Location of each primer by exon or intron").as_deref(),
+            Some("F exon 1; R exon 2")
+        );
+        assert!(value("Thermocycling parameters")
+            .unwrap()
+            .contains("54.1 °C 60 s"));
+        assert_eq!(value("Probe sequence"), None);
+        assert_eq!(value("In silico specificity screen"), None);
+    }
+
+    #[test]
+    fn test_warnings_and_html() {
+        let service = AssayDocumentService::new();
+        let long = pair(350);
+        let warnings = service.warnings(&long, Some("ACGT"));
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].starts_with("Amplicon is 350 bp"));
+        assert!(warnings[2].starts_with("Probe Tm"));
+
+        let items = service.checklist("tpl", &long, &[], None, &ExportFormat::default());
+        let html = service.html(&long, &items, &warnings, Utc::now());
+        assert!(html.contains("<h2>Target information</h2>"));
+        assert!(html.contains("72 °C 21 s"));
+        assert!(html.contains("<td class=\"missing\"></td>"));
+    }
+}
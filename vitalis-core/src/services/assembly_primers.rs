@@ -0,0 +1,356 @@
+// Service layer: Primers with homology tails for Gibson / In-Fusion assembly
+use crate::domain::assembly::{
+    AssemblyDesign, AssemblyFragment, AssemblyJunction, AssemblyPrimerParams,
+};
+use crate::domain::iupac;
+use crate::domain::primer::{PrimerDesignService, PrimerDirection};
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+use crate::services::PrimerDesignServiceImpl;
+use thiserror::Error;
+
+/// 標準的な脱塩グレードで合成できるオリゴ長の目安（これより長いと精製が必要）
+const MAX_STANDARD_OLIGO_LENGTH: usize = 60;
+
+#[derive(Error, Debug)]
+pub enum AssemblyError {
+    #[error("At least two fragments are required, got {0}")]
+    FragmentCount(usize),
+    #[error("Fragment {0} is {1} bp; at least {2} bp are needed for its primers and overlaps")]
+    FragmentTooShort(String, usize, usize),
+}
+
+/// 連結する断片
+pub struct AssemblyInput<'a> {
+    pub seq_id: &'a str,
+    pub name: &'a str,
+    pub sequence: &'a str,
+}
+
+/// Gibson / In-Fusion assembly primer design service
+pub struct AssemblyPrimerService {
+    primer_service: PrimerDesignServiceImpl,
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for AssemblyPrimerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AssemblyPrimerService {
+    pub fn new() -> Self {
+        Self {
+            primer_service: PrimerDesignServiceImpl::new(),
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// 断片を並べた順に連結するプライマーを設計する。
+    /// 各連結部のオーバーラップは上流断片の末尾と下流断片の先頭から半分ずつ取り、
+    /// 上流側の半分を下流断片のフォワードプライマーに、下流側の半分を上流断片の
+    /// リバースプライマーに 5' テールとして付ける
+    pub fn design(
+        &self,
+        fragments: &[AssemblyInput],
+        overlap_length: usize,
+        params: &AssemblyPrimerParams,
+    ) -> Result<AssemblyDesign, AssemblyError> {
+        if fragments.len() < 2 {
+            return Err(AssemblyError::FragmentCount(fragments.len()));
+        }
+        let left_share = overlap_length.div_ceil(2);
+        let right_share = overlap_length - left_share;
+        let min_length = params.anneal_length_min.max(left_share);
+        if let Some(short) = fragments
+            .iter()
+            .find(|fragment| fragment.sequence.len() < min_length)
+        {
+            return Err(AssemblyError::FragmentTooShort(
+                short.seq_id.to_string(),
+                short.sequence.len(),
+                min_length,
+            ));
+        }
+
+        let sequences: Vec<String> = fragments
+            .iter()
+            .map(|fragment| fragment.sequence.to_ascii_uppercase())
+            .collect();
+        let n = fragments.len();
+        let junction_count = if params.circular { n } else { n - 1 };
+        let mut forward_tails = vec![String::new(); n];
+        let mut reverse_tails = vec![String::new(); n];
+        let mut junctions = Vec::with_capacity(junction_count);
+        let mut warnings = Vec::new();
+
+        let mut end = 0;
+        for left in 0..junction_count {
+            let right = (left + 1) % n;
+            let upstream = &sequences[left];
+            let left_part = &upstream[upstream.len() - left_share..];
+            let right_part = &sequences[right][..right_share];
+            forward_tails[right] = left_part.to_string();
+            reverse_tails[left] = iupac::reverse_complement(right_part);
+            end += upstream.len();
+
+            let overlap = format!("{}{}", left_part, right_part);
+            let tm = self
+                .calculator
+                .calculate_tm_nearest_neighbor(&overlap)
+                .unwrap_or(0.0);
+            let hairpin_delta_g = self
+                .calculator
+                .calculate_enhanced_hairpin(&overlap)
+                .map(|hairpin| hairpin.min_score)
+                .unwrap_or(0.0);
+            let hairpin_prone = hairpin_delta_g < params.max_hairpin;
+
+            let label = format!("{} | {}", fragments[left].name, fragments[right].name);
+            if tm < params.min_overlap_tm {
+                warnings.push(format!(
+                    "Overlap {} has Tm {:.1}°C, below {:.1}°C; lengthen the overlap",
+                    label, tm, params.min_overlap_tm
+                ));
+            }
+            if hairpin_prone {
+                warnings.push(format!(
+                    "Overlap {} forms a hairpin (ΔG {:.1} kcal/mol); the ends may not anneal",
+                    label, hairpin_delta_g
+                ));
+            }
+            junctions.push(AssemblyJunction {
+                left,
+                right,
+                position: end - left_share,
+                overlap,
+                tm,
+                hairpin_delta_g,
+                hairpin_prone,
+            });
+        }
+
+        let (tm_min, tm_max) = junctions
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), junction| {
+                (min.min(junction.tm), max.max(junction.tm))
+            });
+        let overlap_tm_spread = tm_max - tm_min;
+        if overlap_tm_spread > params.max_overlap_tm_spread {
+            warnings.push(format!(
+                "Overlap Tm ranges from {:.1} to {:.1}°C (spread {:.1}°C > {:.1}°C); adjust overlaps so every junction anneals alike",
+                tm_min, tm_max, overlap_tm_spread, params.max_overlap_tm_spread
+            ));
+        }
+        let tm_uniform =
+            overlap_tm_spread <= params.max_overlap_tm_spread && tm_min >= params.min_overlap_tm;
+
+        let fragments = fragments
+            .iter()
+            .zip(&sequences)
+            .zip(forward_tails.into_iter().zip(reverse_tails))
+            .map(|((input, sequence), (forward_tail, reverse_tail))| {
+                let (forward_anneal, forward_tm) =
+                    self.anneal_length(sequence, PrimerDirection::Forward, params);
+                let (reverse_anneal, reverse_tm) =
+                    self.anneal_length(sequence, PrimerDirection::Reverse, params);
+                for (direction, tm) in [("forward", forward_tm), ("reverse", reverse_tm)] {
+                    if tm < params.anneal_tm {
+                        warnings.push(format!(
+                            "{} {} primer anneals at only {:.1}°C (target {:.1}°C)",
+                            input.name, direction, tm, params.anneal_tm
+                        ));
+                    }
+                }
+
+                let forward = self.primer_service.evaluate_primer(
+                    format!("{}{}", forward_tail, &sequence[..forward_anneal]),
+                    0,
+                    PrimerDirection::Forward,
+                );
+                let reverse = self.primer_service.evaluate_primer(
+                    format!(
+                        "{}{}",
+                        reverse_tail,
+                        iupac::reverse_complement(&sequence[sequence.len() - reverse_anneal..])
+                    ),
+                    sequence.len() - reverse_anneal,
+                    PrimerDirection::Reverse,
+                );
+                for (direction, primer) in [("forward", &forward), ("reverse", &reverse)] {
+                    if primer.length > MAX_STANDARD_OLIGO_LENGTH {
+                        warnings.push(format!(
+                            "{} {} primer is {} nt; oligos over {} nt usually need PAGE purification",
+                            input.name, direction, primer.length, MAX_STANDARD_OLIGO_LENGTH
+                        ));
+                    }
+                }
+
+                AssemblyFragment {
+                    seq_id: input.seq_id.to_string(),
+                    name: input.name.to_string(),
+                    amplicon_length: sequence.len() + forward_tail.len() + reverse_tail.len(),
+                    forward,
+                    reverse,
+                    forward_tail,
+                    reverse_tail,
+                    forward_anneal_length: forward_anneal,
+                    reverse_anneal_length: reverse_anneal,
+                }
+            })
+            .collect();
+
+        Ok(AssemblyDesign {
+            fragments,
+            junctions,
+            assembled_sequence: sequences.concat(),
+            circular: params.circular,
+            overlap_length,
+            overlap_tm_spread,
+            tm_uniform,
+            provenance: Some(self.calculator.provenance()),
+            warnings,
+        })
+    }
+
+    /// 目標 Tm に達する最短のアニーリング長とその Tm（達しなければ上限の長さ）
+    fn anneal_length(
+        &self,
+        sequence: &str,
+        direction: PrimerDirection,
+        params: &AssemblyPrimerParams,
+    ) -> (usize, f32) {
+        let max = params.anneal_length_max.min(sequence.len());
+        let region_tm = |length: usize| {
+            let region = match direction {
+                PrimerDirection::Forward => sequence[..length].to_string(),
+                PrimerDirection::Reverse => {
+                    iupac::reverse_complement(&sequence[sequence.len() - length..])
+                }
+            };
+            self.primer_service.calculate_tm(&region)
+        };
+        (params.anneal_length_min.min(max)..=max)
+            .map(|length| (length, region_tm(length)))
+            .find(|&(_, tm)| tm >= params.anneal_tm)
+            .unwrap_or_else(|| (max, region_tm(max)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 再現可能な擬似乱数配列（GC 約50%）
+    fn random_sequence(length: usize, seed: u64) -> String {
+        let mut state = seed;
+        (0..length)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4] as char
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_circular_assembly_tails_rebuild_product() {
+        let sequences = [
+            random_sequence(900, 3),
+            random_sequence(400, 5),
+            random_sequence(600, 9),
+        ];
+        let inputs: Vec<AssemblyInput> = ["vector", "insert", "tag"]
+            .iter()
+            .zip(&sequences)
+            .map(|(name, sequence)| AssemblyInput {
+                seq_id: name,
+                name,
+                sequence,
+            })
+            .collect();
+        let params = AssemblyPrimerParams::default();
+        let design = AssemblyPrimerService::new()
+            .design(&inputs, 30, &params)
+            .unwrap();
+
+        assert_eq!(design.junctions.len(), 3);
+        assert_eq!(design.assembled_sequence.len(), 1900);
+        // 連結部 insert | tag のオーバーラップは上流の末尾 15 塩基と下流の先頭 15 塩基
+        let junction = &design.junctions[1];
+        assert_eq!(
+            (junction.left, junction.right, junction.position),
+            (1, 2, 1285)
+        );
+        assert_eq!(
+            junction.overlap,
+            format!("{}{}", &sequences[1][385..], &sequences[2][..15])
+        );
+        assert_eq!(design.junctions[2].position, 1885);
+
+        for (i, fragment) in design.fragments.iter().enumerate() {
+            // テールを含む増幅産物の両端が隣の断片と overlap_length だけ重なる
+            let amplicon = format!(
+                "{}{}{}",
+                fragment.forward_tail,
+                sequences[i],
+                iupac::reverse_complement(&fragment.reverse_tail)
+            );
+            assert_eq!(fragment.amplicon_length, amplicon.len());
+            assert!(amplicon.starts_with(&fragment.forward.sequence));
+            assert!(amplicon.ends_with(&iupac::reverse_complement(&fragment.reverse.sequence)));
+            assert!(amplicon.starts_with(&design.junctions[(i + 2) % 3].overlap));
+            assert!(amplicon.ends_with(&design.junctions[i].overlap));
+            assert!(fragment.forward_anneal_length >= params.anneal_length_min);
+        }
+        assert_eq!(
+            design.tm_uniform,
+            design.overlap_tm_spread <= params.max_overlap_tm_spread
+                && design
+                    .junctions
+                    .iter()
+                    .all(|j| j.tm >= params.min_overlap_tm)
+        );
+        assert!(design.provenance.is_some());
+    }
+
+    #[test]
+    fn test_linear_assembly_flags_hairpin_overlap() {
+        // 上流の末尾と下流の先頭で GC に富む逆向き反復を作る
+        let left = format!("{}GGGCGCGCCC", random_sequence(300, 21));
+        let right = format!("ATTTTTGGGCGCGCCC{}", random_sequence(300, 23));
+        let inputs = [
+            AssemblyInput {
+                seq_id: "a",
+                name: "a",
+                sequence: &left,
+            },
+            AssemblyInput {
+                seq_id: "b",
+                name: "b",
+                sequence: &right,
+            },
+        ];
+        let params = AssemblyPrimerParams {
+            circular: false,
+            ..AssemblyPrimerParams::default()
+        };
+        let service = AssemblyPrimerService::new();
+        let design = service.design(&inputs, 20, &params).unwrap();
+
+        assert_eq!(design.junctions.len(), 1);
+        assert_eq!(design.junctions[0].overlap, "GGGCGCGCCCATTTTTGGGC");
+        // 直鎖では外側のプライマーにテールを付けない
+        assert!(design.fragments[0].forward_tail.is_empty());
+        assert!(design.fragments[1].reverse_tail.is_empty());
+        assert_eq!(design.fragments[0].reverse_tail, "GCCCAAAAAT");
+        assert!(design.junctions[0].hairpin_prone);
+        assert!(design.warnings.iter().any(|w| w.contains("hairpin")));
+
+        assert!(matches!(
+            service.design(&inputs[..1], 20, &params),
+            Err(AssemblyError::FragmentCount(1))
+        ));
+    }
+}
@@ -0,0 +1,236 @@
+// Service layer: Known vector backbone detection
+use crate::domain::backbone::{
+    builtin_backbones, BackboneElement, BackboneMatch, ElementHit, VectorBackbone,
+};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::iupac;
+use crate::domain::{Strand, Topology};
+
+/// 報告する骨格の最低スコア（検出要素の割合）
+const MIN_BACKBONE_SCORE: f64 = 0.75;
+/// 登録配列から切り出すシグネチャの長さ
+const SIGNATURE_LENGTH: usize = 40;
+/// アノテーションのない登録配列から切り出すシグネチャの間隔
+const SEGMENT_SPACING: usize = 500;
+
+/// Vector backbone detection service
+pub struct BackboneDetectionService {
+    backbones: Vec<VectorBackbone>,
+}
+
+impl Default for BackboneDetectionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackboneDetectionService {
+    /// 組み込み骨格で初期化
+    pub fn new() -> Self {
+        Self::with_backbones(builtin_backbones())
+    }
+
+    pub fn with_backbones(backbones: Vec<VectorBackbone>) -> Self {
+        Self { backbones }
+    }
+
+    /// 登録配列から骨格を作成（アノテーションごとに先頭のシグネチャを切り出し、
+    /// アノテーションがなければ一定間隔の断片を用いる）
+    pub fn backbone_from_sequence(
+        &self,
+        name: &str,
+        sequence: &str,
+        features: &[SequenceFeature],
+    ) -> VectorBackbone {
+        let signature = |start: usize, end: usize| {
+            sequence[start..end.min(start + SIGNATURE_LENGTH)].to_ascii_uppercase()
+        };
+
+        let mut elements: Vec<BackboneElement> = features
+            .iter()
+            .filter(|f| f.feature_type != "source" && f.end <= sequence.len())
+            .filter(|f| f.end - f.start >= SIGNATURE_LENGTH)
+            .map(|f| BackboneElement::new(&f.label(), &f.feature_type, &signature(f.start, f.end)))
+            .collect();
+
+        if elements.is_empty() {
+            elements = (0..sequence.len().saturating_sub(SIGNATURE_LENGTH - 1))
+                .step_by(SEGMENT_SPACING)
+                .enumerate()
+                .map(|(i, start)| {
+                    BackboneElement::new(
+                        &format!("segment {}", i + 1),
+                        "misc_feature",
+                        &signature(start, sequence.len()),
+                    )
+                })
+                .collect();
+        }
+
+        VectorBackbone {
+            name: name.to_string(),
+            elements,
+            user_defined: true,
+        }
+    }
+
+    /// Report backbones whose elements are found in `sequence` (either strand),
+    /// best match first, with the largest element-free gap as the insert.
+    pub fn detect(&self, sequence: &str, topology: &Topology) -> Vec<BackboneMatch> {
+        let length = sequence.len();
+        let circular = *topology == Topology::Circular;
+        let longest = self
+            .backbones
+            .iter()
+            .flat_map(|b| b.elements.iter().map(|e| e.signature.len()))
+            .max()
+            .unwrap_or(0);
+        let text = iupac::search_text(sequence, circular, longest);
+
+        let find = |element: &BackboneElement| {
+            let forward = element.signature.to_ascii_uppercase();
+            let reverse = iupac::reverse_complement(&forward);
+            [(Strand::Forward, forward), (Strand::Reverse, reverse)]
+                .into_iter()
+                .find_map(|(strand, pattern)| {
+                    iupac::find_all(&pattern, &text, length, circular)
+                        .first()
+                        .map(|&start| ElementHit {
+                            name: element.name.clone(),
+                            feature_type: element.feature_type.clone(),
+                            start,
+                            end: start + pattern.len(),
+                            strand,
+                        })
+                })
+        };
+
+        let mut matches: Vec<BackboneMatch> = self
+            .backbones
+            .iter()
+            .filter(|backbone| !backbone.elements.is_empty())
+            .filter_map(|backbone| {
+                let mut found = Vec::new();
+                let mut missing = Vec::new();
+                for element in &backbone.elements {
+                    match find(element) {
+                        Some(hit) => found.push(hit),
+                        None => missing.push(element.name.clone()),
+                    }
+                }
+
+                let score = found.len() as f64 / backbone.elements.len() as f64;
+                if score < MIN_BACKBONE_SCORE {
+                    return None;
+                }
+
+                found.sort_by_key(|hit| hit.start);
+                let insert = largest_gap(&found, length, circular);
+                Some(BackboneMatch {
+                    backbone: backbone.name.clone(),
+                    user_defined: backbone.user_defined,
+                    score,
+                    found,
+                    missing,
+                    insert_start: insert.map(|(start, _)| start),
+                    insert_end: insert.map(|(_, end)| end),
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.found.len().cmp(&a.found.len()))
+                .then(a.backbone.cmp(&b.backbone))
+        });
+        matches
+    }
+}
+
+/// 開始位置順の要素間で最大の空白領域（環状配列では末尾から先頭への領域も含む）
+fn largest_gap(hits: &[ElementHit], length: usize, circular: bool) -> Option<(usize, usize)> {
+    if hits.len() < 2 {
+        return None;
+    }
+
+    let mut best: Option<(usize, usize, usize)> = None;
+    let mut covered_end = hits[0].end;
+    let mut consider = |start: usize, end: usize, size: usize| {
+        if size > 0 && best.is_none_or(|(_, _, best_size)| size > best_size) {
+            best = Some((start, end, size));
+        }
+    };
+
+    for hit in &hits[1..] {
+        if hit.start > covered_end {
+            consider(covered_end, hit.start, hit.start - covered_end);
+        }
+        covered_end = covered_end.max(hit.end);
+    }
+    if circular {
+        let wrap_end = hits[0].start + length;
+        if wrap_end > covered_end {
+            consider(covered_end % length, hits[0].start, wrap_end - covered_end);
+        }
+    }
+
+    best.map(|(start, end, _)| (start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::backbone::common_elements;
+
+    fn element(name: &str) -> String {
+        common_elements()
+            .into_iter()
+            .find(|e| e.name == name)
+            .unwrap()
+            .signature
+    }
+
+    #[test]
+    fn test_detects_pet_backbone_and_insert() {
+        let insert = "ATGGCTAGCAAAGGAGAAGAACTTTTCACTGGAGTTGTCCCAATTCTTGTTGAATTAGATGGTGATGTTAAT";
+        let sequence = format!(
+            "{}CC{}GG{}{}{}AA{}TT{}",
+            element("T7 promoter"),
+            element("lac operator"),
+            insert,
+            element("T7 terminator"),
+            element("f1 ori"),
+            iupac::reverse_complement(&element("KanR")),
+            element("ori")
+        );
+
+        let matches = BackboneDetectionService::new().detect(&sequence, &Topology::Circular);
+        let best = &matches[0];
+        assert_eq!(best.backbone, "pET (KanR)");
+        assert_eq!(best.score, 1.0);
+        assert!(best.missing.is_empty());
+
+        let insert_start = sequence.find(insert).unwrap();
+        assert_eq!(best.insert_start, Some(insert_start - 2));
+        assert_eq!(best.insert_end, Some(insert_start + insert.len()));
+        let kan = best.found.iter().find(|h| h.name == "KanR").unwrap();
+        assert_eq!(kan.strand, Strand::Reverse);
+    }
+
+    #[test]
+    fn test_user_backbone_from_unannotated_sequence() {
+        let service = BackboneDetectionService::new();
+        let backbone_seq =
+            "ACGTTGCAAGCTTGGCACTGGCCGTCGTTTTACAACGTCGTGACTGGGAAAACCCTGGCG".repeat(20);
+        let backbone = service.backbone_from_sequence("my vector", &backbone_seq, &[]);
+        assert_eq!(backbone.elements.len(), 3);
+
+        let construct = format!("{}GAATTCTTTT", backbone_seq);
+        let matches = BackboneDetectionService::with_backbones(vec![backbone])
+            .detect(&construct, &Topology::Linear);
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].user_defined);
+    }
+}
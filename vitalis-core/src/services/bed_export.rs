@@ -0,0 +1,162 @@
+// Service layer: Computed regions as BED records
+use crate::domain::bed::BedRecord;
+use crate::domain::feature::SequenceFeature;
+use crate::domain::orf::Orf;
+use crate::domain::primer::PrimerPair;
+use crate::domain::Strand;
+
+/// BED export service
+pub struct BedExportService;
+
+impl Default for BedExportService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BedExportService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// アノテーション（`feature_types` が空ならすべて）。名前はラベル、スコアは score 修飾子
+    pub fn features(
+        &self,
+        chrom: &str,
+        features: &[SequenceFeature],
+        feature_types: &[String],
+    ) -> Vec<BedRecord> {
+        features
+            .iter()
+            .filter(|feature| {
+                feature_types.is_empty() || feature_types.contains(&feature.feature_type)
+            })
+            .map(|feature| BedRecord {
+                chrom: chrom.to_string(),
+                start: feature.start,
+                end: feature.end,
+                name: Some(feature.label()),
+                score: feature
+                    .qualifiers
+                    .get("score")
+                    .and_then(|score| score.parse().ok()),
+                strand: Some(feature.strand),
+            })
+            .collect()
+    }
+
+    /// ORF（名前は読み枠とアミノ酸数、終止コドンを含む区間）
+    pub fn orfs(&self, chrom: &str, orfs: &[Orf]) -> Vec<BedRecord> {
+        orfs.iter()
+            .map(|orf| BedRecord {
+                chrom: chrom.to_string(),
+                start: orf.start,
+                end: orf.end,
+                name: Some(format!("ORF{:+}_{}aa", orf.frame, orf.length_aa())),
+                score: None,
+                strand: Some(orf.strand),
+            })
+            .collect()
+    }
+
+    /// GC% が `threshold` 以上のウィンドウ（開始位置・長さ・GC%）を、重なる・接するものを
+    /// まとめて書き出す。原点をまたぐウィンドウ（環状配列）は `length` で2行に分ける。
+    /// 名前とスコア（×10）は区間内の最大の GC%
+    pub fn gc_regions(
+        &self,
+        chrom: &str,
+        windows: impl IntoIterator<Item = (usize, usize, f64)>,
+        threshold: f64,
+        length: usize,
+    ) -> Vec<BedRecord> {
+        let rich: Vec<(usize, usize, f64)> = windows
+            .into_iter()
+            .filter(|&(_, _, gc)| gc >= threshold)
+            .map(|(position, size, gc)| (position, position + size, gc))
+            .collect();
+
+        let mut regions = Vec::new();
+        for (start, end, gc) in merge(rich) {
+            if end > length {
+                regions.push((start, length, gc));
+                regions.push((0, end - length, gc));
+            } else {
+                regions.push((start, end, gc));
+            }
+        }
+        regions.sort_by_key(|&(start, end, _)| (start, end));
+
+        merge(regions)
+            .into_iter()
+            .map(|(start, end, gc)| BedRecord {
+                chrom: chrom.to_string(),
+                start,
+                end,
+                name: Some(format!("GC={:.1}%", gc)),
+                score: Some(gc * 10.0),
+                strand: None,
+            })
+            .collect()
+    }
+
+    /// 設計済みプライマーの結合部位（名前は "<ペアID>_F" / "<ペアID>_R"）
+    pub fn primer_sites(&self, chrom: &str, pairs: &[PrimerPair]) -> Vec<BedRecord> {
+        let mut records: Vec<BedRecord> = pairs
+            .iter()
+            .flat_map(|pair| {
+                [
+                    (&pair.forward, "F", Strand::Forward),
+                    (&pair.reverse, "R", Strand::Reverse),
+                ]
+                .map(|(primer, suffix, strand)| BedRecord {
+                    chrom: chrom.to_string(),
+                    start: primer.position,
+                    end: primer.position + primer.length,
+                    name: Some(format!("{}_{}", pair.id, suffix)),
+                    score: None,
+                    strand: Some(strand),
+                })
+            })
+            .collect();
+        records.sort_by_key(|record| (record.start, record.end));
+        records
+    }
+}
+
+/// 開始位置順の区間のうち重なる・接するものをまとめる（値は最大値を残す）
+fn merge(regions: Vec<(usize, usize, f64)>) -> Vec<(usize, usize, f64)> {
+    let mut merged: Vec<(usize, usize, f64)> = Vec::new();
+    for (start, end, value) in regions {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => {
+                last.1 = last.1.max(end);
+                last.2 = last.2.max(value);
+            }
+            _ => merged.push((start, end, value)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gc_regions_merge_and_split_at_origin() {
+        let windows = [
+            (0, 10, 70.0),
+            (5, 10, 40.0),
+            (10, 10, 65.0),
+            (30, 10, 62.0),
+            (95, 10, 80.0),
+        ];
+        let records = BedExportService::new().gc_regions("pX", windows, 60.0, 100);
+
+        let spans: Vec<(usize, usize)> = records.iter().map(|r| (r.start, r.end)).collect();
+        // 0..10 と 10..20 は接するのでまとめ、95..105 は 95..100 と 0..5 に分けて 0..20 に含める
+        assert_eq!(spans, vec![(0, 20), (30, 40), (95, 100)]);
+        assert_eq!(records[0].name.as_deref(), Some("GC=80.0%"));
+        assert_eq!(records[1].score, Some(620.0));
+    }
+}
@@ -0,0 +1,549 @@
+// Service layer: Restriction-ligation cloning simulation
+use crate::domain::cloning::{CloningProduct, LigationJunction};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::iupac;
+use crate::domain::restriction::{OverhangType, RestrictionEnzyme, RestrictionSite};
+use crate::domain::{Range, Strand, Topology};
+use crate::services::RestrictionAnalysisService;
+use std::cmp::Reverse;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CloningError {
+    #[error("One or two enzymes are required, got {0}")]
+    EnzymeCount(usize),
+    #[error("The vector must be circular")]
+    LinearVector,
+    #[error("{0} must cut the vector exactly once, found {1} sites")]
+    SiteNotUnique(String, usize),
+    #[error("No fragment of the insert has ends compatible with the digested vector")]
+    NoCompatibleFragment,
+}
+
+/// 消化する配列（ベクターまたは挿入配列）
+pub struct CloningInput<'a> {
+    pub name: &'a str,
+    pub sequence: &'a str,
+    pub topology: &'a Topology,
+    pub features: &'a [SequenceFeature],
+}
+
+/// 切断末端（突出はコンストラクトの上鎖の向き）
+#[derive(Debug, Clone)]
+struct End {
+    enzyme: String,
+    overhang_type: OverhangType,
+    overhang: String,
+}
+
+impl End {
+    fn of(site: &RestrictionSite) -> Self {
+        Self {
+            enzyme: site.enzyme.clone(),
+            overhang_type: site.overhang_type,
+            overhang: site.overhang.to_ascii_uppercase(),
+        }
+    }
+
+    /// 断片を反転して連結するときの末端
+    fn reversed(&self) -> Self {
+        Self {
+            enzyme: self.enzyme.clone(),
+            overhang_type: self.overhang_type,
+            overhang: iupac::reverse_complement(&self.overhang),
+        }
+    }
+
+    /// 突出の形と配列が一致すれば対合する（平滑末端どうしは常に連結できる）
+    fn ligates_with(&self, other: &End) -> bool {
+        self.overhang_type == other.overhang_type && self.overhang == other.overhang
+    }
+}
+
+/// 挿入断片の候補（`sequence` は連結する向きの上鎖）
+struct Fragment {
+    start: usize,
+    end: usize,
+    orientation: Strand,
+    sequence: String,
+    /// 挿入配列上で `sequence` が占める区間の始点（反転した断片では下鎖の切断位置）
+    from: usize,
+    left: End,
+    right: End,
+    directional: bool,
+}
+
+/// Cloning simulation service
+pub struct CloningService {
+    restriction_service: RestrictionAnalysisService,
+}
+
+impl Default for CloningService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CloningService {
+    pub fn new() -> Self {
+        Self {
+            restriction_service: RestrictionAnalysisService::new(),
+        }
+    }
+
+    /// Digest the circular `vector` and `insert` with one or two `enzymes`,
+    /// keep the vector backbone (the larger of the two pieces), pick the
+    /// longest insert fragment whose ends ligate to the backbone in either
+    /// orientation, and ligate them into a circular construct. Features of
+    /// both are carried over and each junction is reported.
+    pub fn simulate(
+        &self,
+        vector: &CloningInput,
+        insert: &CloningInput,
+        enzymes: &[&RestrictionEnzyme],
+    ) -> Result<CloningProduct, CloningError> {
+        if enzymes.is_empty() || enzymes.len() > 2 {
+            return Err(CloningError::EnzymeCount(enzymes.len()));
+        }
+        if *vector.topology != Topology::Circular {
+            return Err(CloningError::LinearVector);
+        }
+
+        let mut warnings = Vec::new();
+        let length = vector.sequence.len();
+
+        let mut cuts = Vec::new();
+        for enzyme in enzymes {
+            let sites = self
+                .restriction_service
+                .scan(vector.sequence, vector.topology, &[enzyme]);
+            match sites.as_slice() {
+                [site] => cuts.push(site.clone()),
+                _ => {
+                    return Err(CloningError::SiteNotUnique(
+                        enzyme.name.clone(),
+                        sites.len(),
+                    ))
+                }
+            }
+        }
+        cuts.sort_by_key(|site| site.cut_top);
+        warn_blocked(&cuts, vector.name, &mut warnings);
+
+        // 2つの切断位置に挟まれた短い方をスタッファーとして除く。
+        // left はベクター断片の上流側が終わる切断、right は下流側が始まる切断
+        let (first, last) = (&cuts[0], &cuts[cuts.len() - 1]);
+        let inner = last.cut_top - first.cut_top;
+        let stuffer_wraps = inner > length - inner;
+        let (left, right) = if stuffer_wraps {
+            (last, first)
+        } else {
+            (first, last)
+        };
+        let (vector_left, vector_right) = (End::of(left), End::of(right));
+
+        let insert_sites = self
+            .restriction_service
+            .scan(insert.sequence, insert.topology, enzymes);
+        warn_blocked(&insert_sites, insert.name, &mut warnings);
+
+        let mut candidates = self.fragments(insert, &insert_sites, &vector_left, &vector_right);
+        candidates.sort_by_key(|fragment| Reverse(fragment.sequence.len()));
+        let candidate_fragments = candidates.len();
+        let fragment = candidates
+            .into_iter()
+            .next()
+            .ok_or(CloningError::NoCompatibleFragment)?;
+        if candidate_fragments > 1 {
+            warnings.push(format!(
+                "{} fragments of {} have compatible ends; the longest ({} bp) was used",
+                candidate_fragments,
+                insert.name,
+                fragment.sequence.len()
+            ));
+        }
+
+        let (x, y) = (left.cut_top, right.cut_top);
+        let insert_length = fragment.sequence.len();
+        // ベクター断片（ベクター上の始点, 長さ, コンストラクト上の位置）
+        let (sequence, insert_start, pieces) = if stuffer_wraps {
+            (
+                format!("{}{}", &vector.sequence[y..x], fragment.sequence),
+                x - y,
+                vec![(y, x - y, 0)],
+            )
+        } else {
+            (
+                format!(
+                    "{}{}{}",
+                    &vector.sequence[..x],
+                    fragment.sequence,
+                    &vector.sequence[y..]
+                ),
+                x,
+                vec![(0, x, 0), (y, length - y, x + insert_length)],
+            )
+        };
+        let insert_end = insert_start + insert_length;
+
+        let mut features = Vec::new();
+        for feature in vector.features {
+            match pieces
+                .iter()
+                .find_map(|&(from, span, at)| place(feature, from, span, length, at, false))
+            {
+                Some(placed) => features.push(placed),
+                None => warnings.push(format!(
+                    "Vector feature '{}' is lost at the cloning sites",
+                    feature.label()
+                )),
+            }
+        }
+        let reverse = fragment.orientation == Strand::Reverse;
+        features.extend(insert.features.iter().filter_map(|feature| {
+            place(
+                feature,
+                fragment.from,
+                insert_length,
+                insert.sequence.len(),
+                insert_start,
+                reverse,
+            )
+        }));
+        let mut insert_feature = SequenceFeature::new(
+            "misc_feature",
+            insert_start,
+            insert_end,
+            fragment.orientation,
+        );
+        insert_feature
+            .qualifiers
+            .insert("label".to_string(), insert.name.to_string());
+        features.push(insert_feature);
+
+        if !fragment.directional {
+            warnings.push(
+                "The insert can ligate in either orientation; screen clones for the orientation"
+                    .to_string(),
+            );
+        }
+        if vector_left.ligates_with(&vector_right) {
+            warnings.push(
+                "The vector ends are compatible with each other; dephosphorylate the backbone to reduce empty-vector background"
+                    .to_string(),
+            );
+        }
+
+        let product_sites = self
+            .restriction_service
+            .scan(&sequence, &Topology::Circular, enzymes);
+        let junction = |position: usize, left_end: &End, right_end: &End, overhang: &End| {
+            let position = position % sequence.len();
+            LigationJunction {
+                position,
+                left_enzyme: left_end.enzyme.clone(),
+                right_enzyme: right_end.enzyme.clone(),
+                overhang_type: overhang.overhang_type,
+                overhang: overhang.overhang.clone(),
+                site_regenerated: product_sites.iter().any(|site| {
+                    site.cut_top == position
+                        && (site.enzyme == left_end.enzyme || site.enzyme == right_end.enzyme)
+                }),
+            }
+        };
+        let junctions = vec![
+            junction(insert_start, &vector_left, &fragment.left, &vector_left),
+            junction(insert_end, &fragment.right, &vector_right, &vector_right),
+        ];
+
+        Ok(CloningProduct {
+            seq_id: String::new(),
+            sequence,
+            enzymes: enzymes.iter().map(|enzyme| enzyme.name.clone()).collect(),
+            vector_start: x,
+            vector_end: y,
+            fragment_start: fragment.start,
+            fragment_end: fragment.end,
+            insert_orientation: fragment.orientation,
+            insert_start,
+            insert_end,
+            directional: fragment.directional,
+            candidate_fragments,
+            junctions,
+            features,
+            warnings,
+        })
+    }
+
+    /// 隣り合う切断で挟まれた挿入配列の断片のうち、ベクターの両末端と連結できるもの
+    /// （両方の向きで連結できる断片は順方向を使う）
+    fn fragments(
+        &self,
+        insert: &CloningInput,
+        sites: &[RestrictionSite],
+        vector_left: &End,
+        vector_right: &End,
+    ) -> Vec<Fragment> {
+        let length = insert.sequence.len();
+        let circular = *insert.topology == Topology::Circular;
+        let mut cuts: Vec<&RestrictionSite> = sites.iter().collect();
+        cuts.sort_by_key(|site| site.cut_top);
+
+        let pairs: Vec<(&RestrictionSite, &RestrictionSite)> = if circular {
+            (0..cuts.len())
+                .map(|i| (cuts[i], cuts[(i + 1) % cuts.len()]))
+                .collect()
+        } else {
+            cuts.windows(2).map(|pair| (pair[0], pair[1])).collect()
+        };
+
+        let mut fragments = Vec::new();
+        for (start_site, end_site) in pairs {
+            // 環状配列を1箇所で切った断片は全長
+            let span = |from: usize, to: usize| match (to + length - from) % length {
+                0 if circular && std::ptr::eq(start_site, end_site) => length,
+                span => span,
+            };
+            if span(start_site.cut_top, end_site.cut_top) == 0
+                || (!circular && end_site.cut_bottom <= start_site.cut_bottom)
+            {
+                continue;
+            }
+
+            let forward = Fragment {
+                start: start_site.cut_top,
+                end: end_site.cut_top,
+                orientation: Strand::Forward,
+                sequence: arc(
+                    insert.sequence,
+                    start_site.cut_top,
+                    span(start_site.cut_top, end_site.cut_top),
+                ),
+                from: start_site.cut_top,
+                left: End::of(start_site),
+                right: End::of(end_site),
+                directional: true,
+            };
+            let reverse = Fragment {
+                orientation: Strand::Reverse,
+                sequence: iupac::reverse_complement(&arc(
+                    insert.sequence,
+                    start_site.cut_bottom,
+                    span(start_site.cut_bottom, end_site.cut_bottom),
+                )),
+                from: start_site.cut_bottom,
+                left: End::of(end_site).reversed(),
+                right: End::of(start_site).reversed(),
+                ..forward
+            };
+
+            let mut fits: Vec<Fragment> = [forward, reverse]
+                .into_iter()
+                .filter(|f| vector_left.ligates_with(&f.left) && f.right.ligates_with(vector_right))
+                .collect();
+            let directional = fits.len() == 1;
+            if let Some(mut fragment) = (!fits.is_empty()).then(|| fits.remove(0)) {
+                fragment.directional = directional;
+                fragments.push(fragment);
+            }
+        }
+        fragments
+    }
+}
+
+fn warn_blocked(sites: &[RestrictionSite], name: &str, warnings: &mut Vec<String>) {
+    for site in sites.iter().filter(|site| site.is_blocked()) {
+        warnings.push(format!(
+            "{} site at {} in {} is blocked by host methylation",
+            site.enzyme,
+            site.position + 1,
+            name
+        ));
+    }
+}
+
+/// `from` から `span` 塩基（環状配列では原点をまたいでもよい）
+fn arc(sequence: &str, from: usize, span: usize) -> String {
+    let length = sequence.len();
+    if from + span <= length {
+        sequence[from..from + span].to_string()
+    } else {
+        format!("{}{}", &sequence[from..], &sequence[..from + span - length])
+    }
+}
+
+/// 元の配列の [from, from + span)（原点をまたいでもよい）に収まるアノテーションを、
+/// コンストラクト上の `at` から始まる区間に移す（`reverse` では向きを反転）
+fn place(
+    feature: &SequenceFeature,
+    from: usize,
+    span: usize,
+    source_length: usize,
+    at: usize,
+    reverse: bool,
+) -> Option<SequenceFeature> {
+    let relative = (feature.start + source_length - from) % source_length;
+    if relative + (feature.end - feature.start) > span {
+        return None;
+    }
+    let map = |pos: usize| relative + (pos - feature.start);
+    let map_range = |start: usize, end: usize| {
+        if reverse {
+            Range::new(at + span - map(end), at + span - map(start))
+        } else {
+            Range::new(at + map(start), at + map(end))
+        }
+    };
+
+    let mut placed = feature.clone();
+    let range = map_range(feature.start, feature.end);
+    placed.start = range.start;
+    placed.end = range.end;
+    placed.segments = feature
+        .segments
+        .iter()
+        .map(|seg| map_range(seg.start, seg.end))
+        .collect();
+    if reverse {
+        placed.segments.reverse();
+        placed.strand = match feature.strand {
+            Strand::Forward => Strand::Reverse,
+            Strand::Reverse => Strand::Forward,
+        };
+    }
+    // IDs belong to the source sequence's store; the construct gets fresh ones
+    placed.id.clear();
+    Some(placed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(
+        name: &'a str,
+        sequence: &'a str,
+        topology: &'a Topology,
+        features: &'a [SequenceFeature],
+    ) -> CloningInput<'a> {
+        CloningInput {
+            name,
+            sequence,
+            topology,
+            features,
+        }
+    }
+
+    #[test]
+    fn test_directional_cloning_replaces_stuffer() {
+        let service = CloningService::new();
+        let restriction = RestrictionAnalysisService::new();
+        let enzymes = restriction
+            .resolve(&["EcoRI".to_string(), "BamHI".to_string()])
+            .unwrap();
+
+        let vector_seq = format!(
+            "{}GAATTC{}GGATCC{}",
+            "A".repeat(30),
+            "T".repeat(10),
+            "C".repeat(30)
+        );
+        let vector_features = vec![
+            SequenceFeature::new("promoter", 0, 20, Strand::Forward),
+            SequenceFeature::new("misc_feature", 38, 44, Strand::Forward),
+            SequenceFeature::new("terminator", 60, 80, Strand::Forward),
+        ];
+        let insert_seq = format!("TTTGAATTC{}GGATCCTTT", "ACGT".repeat(5));
+        let insert_features = vec![SequenceFeature::new("CDS", 9, 29, Strand::Forward)];
+
+        let product = service
+            .simulate(
+                &input("pVec", &vector_seq, &Topology::Circular, &vector_features),
+                &input("gene", &insert_seq, &Topology::Linear, &insert_features),
+                &enzymes,
+            )
+            .unwrap();
+
+        let expected = format!(
+            "{}GAATTC{}GGATCC{}",
+            "A".repeat(30),
+            "ACGT".repeat(5),
+            "C".repeat(30)
+        );
+        assert_eq!(product.sequence, expected);
+        assert_eq!((product.vector_start, product.vector_end), (31, 47));
+        assert_eq!((product.insert_start, product.insert_end), (31, 57));
+        assert_eq!(product.insert_orientation, Strand::Forward);
+        assert!(product.directional);
+        assert_eq!(product.junctions[0].overhang, "AATT");
+        assert_eq!(product.junctions[1].overhang, "GATC");
+        assert!(product.junctions.iter().all(|j| j.site_regenerated));
+
+        // スタッファー上のアノテーションは失われ、下流のものは位置がずれる
+        let types: Vec<(&str, usize)> = product
+            .features
+            .iter()
+            .map(|f| (f.feature_type.as_str(), f.start))
+            .collect();
+        assert!(types.contains(&("promoter", 0)));
+        assert!(types.contains(&("terminator", 70)));
+        assert!(types.contains(&("CDS", 36)));
+        assert!(product
+            .warnings
+            .iter()
+            .any(|w| w.contains("is lost at the cloning sites")));
+
+        // 挿入配列上のサイトの順が逆なら断片を反転して連結する
+        let flipped = "TTTGGATCCAAAACCCCGAATTCTTT";
+        let product = service
+            .simulate(
+                &input("pVec", &vector_seq, &Topology::Circular, &[]),
+                &input("gene", flipped, &Topology::Linear, &[]),
+                &enzymes,
+            )
+            .unwrap();
+        assert_eq!(product.insert_orientation, Strand::Reverse);
+        assert_eq!(&product.sequence[30..50], "GAATTCGGGGTTTTGGATCC");
+    }
+
+    #[test]
+    fn test_single_enzyme_cloning_is_not_directional() {
+        let service = CloningService::new();
+        let restriction = RestrictionAnalysisService::new();
+        let enzymes = restriction.resolve(&["EcoRV".to_string()]).unwrap();
+
+        let vector_seq = format!("{}GATATC{}", "A".repeat(20), "C".repeat(20));
+        let insert_seq = format!("GATATCAAACCC{}GATATC", "G".repeat(8));
+        let features = vec![SequenceFeature::new("CDS", 6, 12, Strand::Forward)];
+
+        let product = service
+            .simulate(
+                &input("pVec", &vector_seq, &Topology::Circular, &[]),
+                &input("orf", &insert_seq, &Topology::Linear, &features),
+                &enzymes,
+            )
+            .unwrap();
+        assert!(!product.directional);
+        assert_eq!(product.sequence.len(), vector_seq.len() + 20);
+        assert!(product
+            .warnings
+            .iter()
+            .any(|w| w.contains("either orientation")));
+        assert!(product
+            .warnings
+            .iter()
+            .any(|w| w.contains("dephosphorylate")));
+
+        let cds = product.features.iter().find(|f| f.feature_type == "CDS");
+        assert_eq!(cds.map(|f| (f.start, f.end)), Some((26, 32)));
+
+        assert!(matches!(
+            service.simulate(
+                &input("pVec", &vector_seq, &Topology::Linear, &[]),
+                &input("orf", &insert_seq, &Topology::Linear, &features),
+                &enzymes,
+            ),
+            Err(CloningError::LinearVector)
+        ));
+    }
+}
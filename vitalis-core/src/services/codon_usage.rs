@@ -0,0 +1,253 @@
+// Service layer: Codon usage with rare codons and CAI judged against a host table
+use crate::domain::codon_usage::{
+    all_codons, CodonAdaptiveness, CodonHost, CodonUsageComparison, CodonUsageParams, RareCodon,
+};
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::CodonUsage;
+use std::collections::HashMap;
+
+/// 宿主で一度も使われないコドンの相対適応度の下限（CAI が 0 にならないように）
+const MIN_ADAPTIVENESS: f64 = 0.01;
+
+/// Codon usage service
+pub struct CodonUsageService;
+
+impl Default for CodonUsageService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodonUsageService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// ACGT だけのコドンを読み枠 0 から数える
+    fn count_codons(sequence: &str) -> HashMap<String, usize> {
+        let mut codon_counts: HashMap<String, usize> = HashMap::new();
+        for codon in sequence.to_ascii_uppercase().as_bytes().chunks_exact(3) {
+            if CodonHost::default().per_thousand(codon).is_none() {
+                continue;
+            }
+            *codon_counts
+                .entry(String::from_utf8_lossy(codon).into_owned())
+                .or_insert(0) += 1;
+        }
+        codon_counts
+    }
+
+    /// Codon Adaptation Index（Sharp & Li 1987）: 宿主での相対適応度の幾何平均。
+    /// 終止コドンと同義コドンのないアミノ酸（Met, Trp など）は除き、
+    /// 相対適応度は MIN_ADAPTIVENESS で下限を切る。数えたコドン数も返す
+    fn cai(
+        codon_counts: &HashMap<String, usize>,
+        code: &GeneticCode,
+        host: CodonHost,
+    ) -> (Option<f64>, usize) {
+        let mut log_sum = 0.0;
+        let mut counted = 0;
+        for (codon, &count) in codon_counts {
+            let amino_acid = code.translate_codon(codon.as_bytes());
+            let synonymous = all_codons()
+                .filter(|c| code.translate_codon(c) == amino_acid)
+                .count();
+            if amino_acid == '*' || synonymous < 2 {
+                continue;
+            }
+            let w = host
+                .relative_adaptiveness(codon.as_bytes(), code)
+                .unwrap_or(0.0)
+                .max(MIN_ADAPTIVENESS);
+            log_sum += w.ln() * count as f64;
+            counted += count;
+        }
+        let cai = (counted > 0).then(|| (log_sum / counted as f64).exp());
+        (cai, counted)
+    }
+
+    /// 読み枠 0 から数えたコドン使用を `host` のコドン使用表と比べる。
+    /// 長さが3の倍数でなければ None
+    pub fn compare(
+        &self,
+        sequence: &str,
+        code: &GeneticCode,
+        host: CodonHost,
+    ) -> Option<CodonUsageComparison> {
+        if !sequence.len().is_multiple_of(3) {
+            return None;
+        }
+
+        let codon_counts = Self::count_codons(sequence);
+        let total: usize = codon_counts.values().sum();
+        let (cai, cai_codons) = Self::cai(&codon_counts, code, host);
+        let codons = all_codons()
+            .map(|codon| {
+                let name = String::from_utf8_lossy(&codon).into_owned();
+                let count = codon_counts.get(&name).copied().unwrap_or(0);
+                CodonAdaptiveness {
+                    amino_acid: code.translate_codon(&codon),
+                    count,
+                    per_thousand: if total == 0 {
+                        0.0
+                    } else {
+                        count as f64 / total as f64 * 1000.0
+                    },
+                    host_per_thousand: host.per_thousand(&codon).unwrap_or(0.0),
+                    relative_adaptiveness: host.relative_adaptiveness(&codon, code).unwrap_or(0.0),
+                    codon: name,
+                }
+            })
+            .collect();
+        Some(CodonUsageComparison {
+            host,
+            host_name: host.name().to_string(),
+            cai,
+            cai_codons,
+            codons,
+        })
+    }
+
+    /// 読み枠 0 から数えたコドン使用統計。稀なコドンは配列内の頻度ではなく、
+    /// `params.host` での相対適応度が `params.rare_threshold` 未満のものとする。
+    /// 長さが3の倍数でなければ None、ACGT 以外を含むコドンは数えない
+    pub fn usage(
+        &self,
+        sequence: &str,
+        code: &GeneticCode,
+        params: &CodonUsageParams,
+    ) -> Option<CodonUsage> {
+        if !sequence.len().is_multiple_of(3) {
+            return None;
+        }
+
+        let codon_counts = Self::count_codons(sequence);
+
+        let total: usize = codon_counts.values().sum();
+        let mut codon_frequencies = HashMap::new();
+        let mut amino_acid_counts: HashMap<char, usize> = HashMap::new();
+        let mut rare_codon_details = Vec::new();
+        for (codon, &count) in &codon_counts {
+            codon_frequencies.insert(codon.clone(), count as f64 / total as f64);
+            let amino_acid = code.translate_codon(codon.as_bytes());
+            *amino_acid_counts.entry(amino_acid).or_insert(0) += count;
+
+            let relative_adaptiveness = params
+                .host
+                .relative_adaptiveness(codon.as_bytes(), code)
+                .unwrap_or(0.0);
+            if relative_adaptiveness < params.rare_threshold {
+                rare_codon_details.push(RareCodon {
+                    codon: codon.clone(),
+                    amino_acid,
+                    count,
+                    host_per_thousand: params.host.per_thousand(codon.as_bytes()).unwrap_or(0.0),
+                    relative_adaptiveness,
+                });
+            }
+        }
+        rare_codon_details.sort_by(|a, b| b.count.cmp(&a.count).then(a.codon.cmp(&b.codon)));
+
+        let (cai, _) = Self::cai(&codon_counts, code, params.host);
+        let rare_count: usize = rare_codon_details.iter().map(|rare| rare.count).sum();
+        Some(CodonUsage {
+            start_codons: codon_counts.get("ATG").copied().unwrap_or(0),
+            stop_codons: amino_acid_counts.get(&'*').copied().unwrap_or(0),
+            rare_codons: rare_codon_details
+                .iter()
+                .map(|rare| rare.codon.clone())
+                .collect(),
+            rare_codon_percent: if total == 0 {
+                0.0
+            } else {
+                rare_count as f64 / total as f64 * 100.0
+            },
+            codon_counts,
+            codon_frequencies,
+            amino_acid_counts,
+            params: params.clone(),
+            rare_codon_details,
+            cai,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rare_codons_relative_to_host() {
+        let service = CodonUsageService::new();
+        let code = GeneticCode::standard();
+        // M-R(AGG)-R(AGG)-R(CGT)-L(CTA)-*
+        let cds = "ATGAGGAGGCGTCTATAA";
+
+        let ecoli = service
+            .usage(cds, &code, &CodonUsageParams::default())
+            .unwrap();
+        assert_eq!(ecoli.rare_codons, vec!["AGG", "CTA"]);
+        assert_eq!(ecoli.rare_codon_details[0].count, 2);
+        assert_eq!(ecoli.rare_codon_details[0].amino_acid, 'R');
+        assert!((ecoli.rare_codon_percent - 50.0).abs() < 1e-9);
+        assert_eq!(ecoli.amino_acid_counts.get(&'R'), Some(&3));
+        assert_eq!((ecoli.start_codons, ecoli.stop_codons), (1, 1));
+
+        // 酵母では AGG も CTA も稀ではなく、CGT は閾値を上げると稀になる
+        let yeast = service
+            .usage(
+                cds,
+                &code,
+                &CodonUsageParams {
+                    host: CodonHost::SaccharomycesCerevisiae,
+                    rare_threshold: 0.35,
+                    ..CodonUsageParams::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(yeast.rare_codons, vec!["CGT"]);
+
+        assert!(service
+            .usage("ATGA", &code, &CodonUsageParams::default())
+            .is_none());
+    }
+
+    #[test]
+    fn test_cai_against_host_tables() {
+        let service = CodonUsageService::new();
+        let code = GeneticCode::standard();
+        // 大腸菌で最も使われる同義コドンだけ: M-L(CTG)-K(AAA)-E(GAA)-W-*
+        let optimal = "ATGCTGAAAGAATGGTAA";
+        let comparison = service
+            .compare(optimal, &code, CodonHost::EscherichiaColi)
+            .unwrap();
+        // ATG, TGG, TAA は CAI に数えない
+        assert_eq!(comparison.cai_codons, 3);
+        assert!((comparison.cai.unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(comparison.codons.len(), 64);
+        let ctg = comparison.codons.iter().find(|c| c.codon == "CTG").unwrap();
+        assert_eq!((ctg.amino_acid, ctg.count), ('L', 1));
+        assert!((ctg.per_thousand - 1000.0 / 6.0).abs() < 1e-9);
+
+        // 稀なコドンに置き換えると下がり、usage にも同じ値が入る
+        let rare = "ATGCTAAAGGAGTGGTAA";
+        let ecoli = service
+            .compare(rare, &code, CodonHost::EscherichiaColi)
+            .unwrap()
+            .cai
+            .unwrap();
+        assert!(ecoli < 0.5);
+        let usage = service
+            .usage(rare, &code, &CodonUsageParams::default())
+            .unwrap();
+        assert!((usage.cai.unwrap() - ecoli).abs() < 1e-9);
+
+        assert_eq!(
+            service
+                .compare("ATGTGGTAA", &code, CodonHost::HomoSapiens)
+                .unwrap()
+                .cai,
+            None
+        );
+    }
+}
@@ -0,0 +1,197 @@
+// Service layer: Sequence-aware molecular weights and concentration unit conversion
+use crate::domain::concentration::{ConcentrationConversion, ConcentrationUnit, MoleculeKind};
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::{iupac, protein};
+use thiserror::Error;
+
+/// アボガドロ定数（1/mol）
+const AVOGADRO: f64 = 6.022_140_76e23;
+/// 直鎖の一本鎖で 5' 末端にリン酸がない分の補正（g/mol）
+const LINEAR_END_CORRECTION: f64 = -61.96;
+
+#[derive(Error, Debug)]
+pub enum ConcentrationError {
+    #[error("Empty sequence")]
+    EmptySequence,
+    #[error("Unsupported residue '{0}' for {1:?}")]
+    UnsupportedResidue(char, MoleculeKind),
+}
+
+/// Concentration conversion service
+pub struct ConcentrationService;
+
+impl Default for ConcentrationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConcentrationService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 配列の分子量と長さ。環状の核酸は末端の補正をしない
+    pub fn molecular_weight(
+        &self,
+        sequence: &str,
+        molecule: MoleculeKind,
+        circular: bool,
+    ) -> Result<(f64, usize), ConcentrationError> {
+        let sequence: String = sequence.chars().filter(|c| !c.is_whitespace()).collect();
+        if sequence.is_empty() {
+            return Err(ConcentrationError::EmptySequence);
+        }
+
+        match molecule {
+            MoleculeKind::SsDna | MoleculeKind::DsDna => {
+                let strand = self.strand_mass(&sequence, molecule, circular)?;
+                let mass = match molecule {
+                    MoleculeKind::DsDna => {
+                        strand
+                            + self.strand_mass(
+                                &iupac::reverse_complement(&sequence),
+                                molecule,
+                                circular,
+                            )?
+                    }
+                    _ => strand,
+                };
+                Ok((mass, sequence.len()))
+            }
+            MoleculeKind::Protein => {
+                let residues = if iupac::is_nucleotide(&sequence) {
+                    let translated = GeneticCode::standard().translate(&sequence);
+                    translated.split('*').next().unwrap_or_default().to_string()
+                } else {
+                    sequence.trim_end_matches('*').to_string()
+                };
+                if residues.is_empty() {
+                    return Err(ConcentrationError::EmptySequence);
+                }
+                let mass = residues
+                    .chars()
+                    .map(|residue| {
+                        protein::residue_mass(residue)
+                            .ok_or(ConcentrationError::UnsupportedResidue(residue, molecule))
+                    })
+                    .sum::<Result<f64, _>>()?;
+                Ok((mass + protein::WATER_MASS, residues.len()))
+            }
+        }
+    }
+
+    /// `value`（単位 `unit`）を ng/µL・nM・copies/µL に換算する
+    pub fn convert(
+        &self,
+        sequence: &str,
+        molecule: MoleculeKind,
+        circular: bool,
+        value: f64,
+        unit: ConcentrationUnit,
+    ) -> Result<ConcentrationConversion, ConcentrationError> {
+        let (molecular_weight, length) = self.molecular_weight(sequence, molecule, circular)?;
+        // 1 ng/µL = 1 mg/L なので nM = ng/µL × 10⁶ / MW、1 nM = 10⁻¹⁵ mol/µL
+        let copies_per_nanomolar = AVOGADRO * 1e-15;
+        let nanomolar = match unit {
+            ConcentrationUnit::NgPerUl => value * 1e6 / molecular_weight,
+            ConcentrationUnit::Nanomolar => value,
+            ConcentrationUnit::CopiesPerUl => value / copies_per_nanomolar,
+        };
+
+        Ok(ConcentrationConversion {
+            molecule,
+            length,
+            molecular_weight,
+            ng_per_ul: nanomolar * molecular_weight / 1e6,
+            nanomolar,
+            copies_per_ul: nanomolar * copies_per_nanomolar,
+        })
+    }
+
+    fn strand_mass(
+        &self,
+        sequence: &str,
+        molecule: MoleculeKind,
+        circular: bool,
+    ) -> Result<f64, ConcentrationError> {
+        let residues = sequence
+            .chars()
+            .map(|base| {
+                iupac::dna_residue_mass(base)
+                    .ok_or(ConcentrationError::UnsupportedResidue(base, molecule))
+            })
+            .sum::<Result<f64, _>>()?;
+        Ok(if circular {
+            residues
+        } else {
+            residues + LINEAR_END_CORRECTION
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_molecular_weight_by_molecule() {
+        let service = ConcentrationService::new();
+
+        let (ss, length) = service
+            .molecular_weight("ATGC", MoleculeKind::SsDna, false)
+            .unwrap();
+        assert!((ss - 1173.84).abs() < 1e-6);
+        assert_eq!(length, 4);
+
+        // 相補鎖 GCAT も同じ組成
+        let (ds, _) = service
+            .molecular_weight("ATGC", MoleculeKind::DsDna, false)
+            .unwrap();
+        assert!((ds - 2347.68).abs() < 1e-6);
+        let (circular, _) = service
+            .molecular_weight("ATGC", MoleculeKind::DsDna, true)
+            .unwrap();
+        assert!((circular - 2471.6).abs() < 1e-6);
+
+        // ATG AAA TAA → MK
+        let (protein, residues) = service
+            .molecular_weight("ATGAAATAAGGG", MoleculeKind::Protein, false)
+            .unwrap();
+        assert!((protein - 277.38194).abs() < 1e-6);
+        assert_eq!(residues, 2);
+
+        assert!(service
+            .molecular_weight("MKJ", MoleculeKind::Protein, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_convert_between_mass_molar_and_copies() {
+        let service = ConcentrationService::new();
+        let sequence = "ACGT".repeat(250);
+
+        let from_mass = service
+            .convert(
+                &sequence,
+                MoleculeKind::DsDna,
+                false,
+                1.0,
+                ConcentrationUnit::NgPerUl,
+            )
+            .unwrap();
+        let from_copies = service
+            .convert(
+                &sequence,
+                MoleculeKind::DsDna,
+                false,
+                from_mass.copies_per_ul,
+                ConcentrationUnit::CopiesPerUl,
+            )
+            .unwrap();
+        assert!((from_copies.ng_per_ul - 1.0).abs() < 1e-9);
+        // 1 kbp の dsDNA（約 618 kDa）1 ng/µL ≈ 1.62 nM ≈ 9.75 × 10⁸ copies/µL
+        assert!((from_mass.nanomolar - 1.62).abs() < 0.01);
+        assert!((from_mass.copies_per_ul / 1e8 - 9.75).abs() < 0.01);
+    }
+}
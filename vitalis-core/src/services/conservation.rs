@@ -0,0 +1,158 @@
+// Service layer: Per-base conservation scores from a multiple alignment
+use crate::domain::alignment::{is_gap, ConservationMethod, MultipleAlignment};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConservationError {
+    #[error("Row '{0}' not found in alignment")]
+    RowNotFound(String),
+    #[error("Alignment has no columns")]
+    EmptyAlignment,
+}
+
+/// 列の記号（A・C・G・T・ギャップ）。その他の縮重コードは None
+fn symbol(c: char) -> Option<usize> {
+    match c.to_ascii_uppercase() {
+        'A' => Some(0),
+        'C' => Some(1),
+        'G' => Some(2),
+        'T' | 'U' => Some(3),
+        c if is_gap(c) => Some(4),
+        _ => None,
+    }
+}
+
+/// Conservation scoring service
+pub struct ConservationService;
+
+impl Default for ConservationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConservationService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 基準行の塩基ごとの保存度（0.0〜1.0）。基準行がギャップの列は除くため、
+    /// 戻り値の長さは基準行のギャップを除いた配列長と一致する
+    pub fn score(
+        &self,
+        alignment: &MultipleAlignment,
+        reference_row: &str,
+        method: ConservationMethod,
+    ) -> Result<Vec<f64>, ConservationError> {
+        let reference = alignment
+            .row_index(reference_row)
+            .ok_or_else(|| ConservationError::RowNotFound(reference_row.to_string()))?;
+        if alignment.columns() == 0 {
+            return Err(ConservationError::EmptyAlignment);
+        }
+
+        let rows: Vec<Vec<char>> = alignment
+            .rows
+            .iter()
+            .map(|row| row.aligned.chars().collect())
+            .collect();
+        let scores = (0..alignment.columns())
+            .filter(|&column| !is_gap(rows[reference][column]))
+            .map(|column| match method {
+                ConservationMethod::Entropy => {
+                    column_entropy_score(rows.iter().map(|row| row[column]))
+                }
+                ConservationMethod::Identity => {
+                    let base = symbol(rows[reference][column]);
+                    let others = rows.len() - 1;
+                    if others == 0 {
+                        return 1.0;
+                    }
+                    let same = rows
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, row)| {
+                            i != reference && base.is_some() && symbol(row[column]) == base
+                        })
+                        .count();
+                    same as f64 / others as f64
+                }
+            })
+            .collect();
+        Ok(scores)
+    }
+}
+
+/// 1 − H / log2(5)。縮重コードは数えない
+fn column_entropy_score(column: impl Iterator<Item = char>) -> f64 {
+    let mut counts = [0usize; 5];
+    for c in column {
+        if let Some(symbol) = symbol(c) {
+            counts[symbol] += 1;
+        }
+    }
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let entropy: f64 = counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum();
+    (1.0 - entropy / 5f64.log2()).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::alignment::AlignmentRow;
+
+    fn alignment(rows: &[(&str, &str)]) -> MultipleAlignment {
+        MultipleAlignment {
+            id: "aln_1".to_string(),
+            name: "test".to_string(),
+            rows: rows
+                .iter()
+                .map(|(name, aligned)| AlignmentRow {
+                    name: name.to_string(),
+                    aligned: aligned.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_scores_follow_reference_bases() {
+        let msa = alignment(&[
+            ("ref", "AC-GT"),
+            ("b", "ACTGA"),
+            ("c", "ATTG-"),
+            ("d", "ACTGC"),
+        ]);
+        let service = ConservationService::new();
+
+        let identity = service
+            .score(&msa, "ref", ConservationMethod::Identity)
+            .unwrap();
+        assert_eq!(identity.len(), 4);
+        assert_eq!(identity[0], 1.0);
+        assert!((identity[1] - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(identity[2], 1.0);
+        assert_eq!(identity[3], 0.0);
+
+        let entropy = service
+            .score(&msa, "ref", ConservationMethod::Entropy)
+            .unwrap();
+        assert_eq!(entropy[0], 1.0);
+        assert!(entropy[1] < 1.0 && entropy[1] > entropy[3]);
+
+        assert!(matches!(
+            service.score(&msa, "zzz", ConservationMethod::Entropy),
+            Err(ConservationError::RowNotFound(_))
+        ));
+    }
+}
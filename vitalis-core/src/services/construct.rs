@@ -0,0 +1,528 @@
+// Service layer: Expression construct design (tags, codon optimization, cloning)
+use crate::domain::construct::{
+    builtin_tags, ecoli_codons, ConstructTag, ExpressionConstruct, TagTerminus,
+};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::iupac;
+use crate::domain::primer::{PrimerDesignService, PrimerDirection};
+use crate::domain::rbs::RbsParams;
+use crate::domain::restriction::{RestrictionEnzyme, RestrictionSite};
+use crate::domain::{Strand, Topology};
+use crate::services::{PrimerDesignServiceImpl, RbsStrengthService, RestrictionAnalysisService};
+use thiserror::Error;
+
+/// クローニングプライマー5'末端の保護塩基（制限酵素の切断効率を確保）
+const PRIMER_CLAMP: &str = "GCGC";
+/// アニーリング部分の長さ範囲と目標Tm
+const ANNEAL_LENGTH_MIN: usize = 18;
+const ANNEAL_LENGTH_MAX: usize = 30;
+const ANNEAL_TM_TARGET: f32 = 58.0;
+
+#[derive(Error, Debug)]
+pub enum ConstructError {
+    #[error("Invalid coding sequence: {0}")]
+    InvalidCds(String),
+    #[error("Unknown tag: {0}")]
+    UnknownTag(String),
+    #[error("Exactly two cloning sites (5' and 3') are required, got {0}")]
+    CloningSiteCount(usize),
+    #[error("{0} must cut the vector exactly once, found {1} sites")]
+    SiteNotUnique(String, usize),
+    #[error("5' site {0} at {1} must lie upstream of 3' site {2} at {3}")]
+    SiteOrder(String, usize, String, usize),
+}
+
+/// 融合タンパク質の構成要素（アミノ酸配列上の区間とアノテーション）
+struct Part {
+    label: String,
+    start: usize,
+    end: usize,
+}
+
+/// コンストラクト上の各境界（ベクター座標で置換区間は [vector_start, vector_end)）
+#[derive(Clone, Copy)]
+struct Layout {
+    vector_start: usize,
+    vector_end: usize,
+    site5_end: usize,
+    orf_start: usize,
+    orf_end: usize,
+    insert_end: usize,
+}
+
+/// Expression construct design service
+pub struct ExpressionConstructService {
+    primer_service: PrimerDesignServiceImpl,
+    restriction_service: RestrictionAnalysisService,
+    rbs_service: RbsStrengthService,
+}
+
+impl Default for ExpressionConstructService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExpressionConstructService {
+    pub fn new() -> Self {
+        Self {
+            primer_service: PrimerDesignServiceImpl::new(),
+            restriction_service: RestrictionAnalysisService::new(),
+            rbs_service: RbsStrengthService::new(),
+        }
+    }
+
+    /// Fuse `tags` to the protein encoded by `gene_cds`, back-translate it with
+    /// E. coli preferred codons (swapping synonymous codons to remove internal
+    /// cloning sites), and place the cassette between the 5' and 3' cloning
+    /// sites of `vector`. The vector region between the two sites is replaced.
+    pub fn design(
+        &self,
+        gene_cds: &str,
+        vector: &str,
+        topology: &Topology,
+        vector_features: &[SequenceFeature],
+        cloning_sites: &[&RestrictionEnzyme],
+        tags: &[ConstructTag],
+    ) -> Result<ExpressionConstruct, ConstructError> {
+        let [five_prime, three_prime] = cloning_sites else {
+            return Err(ConstructError::CloningSiteCount(cloning_sites.len()));
+        };
+
+        let mut warnings = Vec::new();
+        let gene_protein = self.gene_protein(gene_cds, &mut warnings)?;
+        let (protein, parts) = self.fuse(&gene_protein, tags)?;
+
+        // 終止コドンまで含めて最適コドンで逆翻訳
+        let residues: Vec<char> = protein.chars().chain(std::iter::once('*')).collect();
+        let mut codons: Vec<&'static str> =
+            residues.iter().map(|&aa| ecoli_codons(aa)[0]).collect();
+        let sites_removed = self.remove_sites(&mut codons, &residues, cloning_sites);
+        let orf = codons.concat();
+
+        let translated = GeneticCode::standard().translate(&orf);
+        let frame_verified = orf.len().is_multiple_of(3) && translated == format!("{}*", protein);
+
+        // ベクター上のクローニングサイト
+        let vector_site = |enzyme: &RestrictionEnzyme| {
+            let sites = self.restriction_service.scan(vector, topology, &[enzyme]);
+            match sites.as_slice() {
+                [site] => Ok(site.position),
+                _ => Err(ConstructError::SiteNotUnique(
+                    enzyme.name.clone(),
+                    sites.len(),
+                )),
+            }
+        };
+        let site5_pos = vector_site(five_prime)?;
+        let site3_pos = vector_site(three_prime)?;
+        let site5 = five_prime.site.to_ascii_uppercase();
+        let site3 = three_prime.site.to_ascii_uppercase();
+        if site5_pos + site5.len() > site3_pos {
+            return Err(ConstructError::SiteOrder(
+                five_prime.name.clone(),
+                site5_pos,
+                three_prime.name.clone(),
+                site3_pos,
+            ));
+        }
+
+        // NdeI (CATATG) のように認識配列が ATG で終わる場合は開始コドンを共有
+        let start_overlap = if site5.ends_with("ATG") { 3 } else { 0 };
+        let vector_start = site5_pos;
+        let vector_end = site3_pos + site3.len();
+        let orf_start = vector_start + site5.len() - start_overlap;
+        let orf_end = orf_start + orf.len();
+
+        let sequence = format!(
+            "{}{}{}{}{}",
+            &vector[..vector_start],
+            site5,
+            &orf[start_overlap..],
+            site3,
+            &vector[vector_end..]
+        );
+        let insert_end = orf_end + site3.len();
+
+        let unwanted_sites: Vec<RestrictionSite> = self
+            .restriction_service
+            .scan(&sequence, topology, cloning_sites)
+            .into_iter()
+            .filter(|site| {
+                !(site.enzyme == five_prime.name && site.position == vector_start
+                    || site.enzyme == three_prime.name && site.position == orf_end)
+            })
+            .collect();
+        for site in &unwanted_sites {
+            warnings.push(format!(
+                "Additional {} site at {} in the construct",
+                site.enzyme,
+                site.position + 1
+            ));
+        }
+
+        // ベクター側の RBS が挿入した開始コドンを翻訳させられるか
+        let rbs = self
+            .rbs_service
+            .predict(&sequence, orf_start, &RbsParams::default());
+        if rbs.weak {
+            warnings.push(format!(
+                "Weak RBS upstream of the start codon at {} (relative strength {:.2})",
+                orf_start + 1,
+                rbs.relative_strength
+            ));
+        }
+
+        let layout = Layout {
+            vector_start,
+            vector_end,
+            site5_end: vector_start + site5.len(),
+            orf_start,
+            orf_end,
+            insert_end,
+        };
+        let features = self.annotate(
+            vector_features,
+            &parts,
+            &protein,
+            &layout,
+            (&five_prime.name, &three_prime.name),
+            &mut warnings,
+        );
+
+        let (forward_anneal, reverse_anneal) = (
+            self.anneal_length(&orf[start_overlap..], PrimerDirection::Forward),
+            self.anneal_length(&orf, PrimerDirection::Reverse),
+        );
+        let forward_primer = self.primer_service.evaluate_primer(
+            format!(
+                "{}{}{}",
+                PRIMER_CLAMP,
+                site5,
+                &orf[start_overlap..start_overlap + forward_anneal]
+            ),
+            vector_start,
+            PrimerDirection::Forward,
+        );
+        let reverse_primer = self.primer_service.evaluate_primer(
+            format!(
+                "{}{}{}",
+                PRIMER_CLAMP,
+                iupac::reverse_complement(&site3),
+                iupac::reverse_complement(&orf[orf.len() - reverse_anneal..])
+            ),
+            orf_end - reverse_anneal,
+            PrimerDirection::Reverse,
+        );
+
+        Ok(ExpressionConstruct {
+            seq_id: String::new(),
+            sequence,
+            orf_start,
+            orf_end,
+            protein,
+            frame_verified,
+            forward_primer,
+            reverse_primer,
+            unwanted_sites,
+            sites_removed,
+            vector_start,
+            vector_end,
+            features,
+            rbs: Some(rbs),
+            warnings,
+        })
+    }
+
+    /// 遺伝子CDSを翻訳（末端の終止コドンは除去、内部終止コドンはエラー）
+    fn gene_protein(
+        &self,
+        gene_cds: &str,
+        warnings: &mut Vec<String>,
+    ) -> Result<String, ConstructError> {
+        let cds: String = gene_cds
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        if !iupac::is_nucleotide(&cds) || !cds.len().is_multiple_of(3) {
+            return Err(ConstructError::InvalidCds(
+                "length must be a multiple of 3 and contain only nucleotides".to_string(),
+            ));
+        }
+
+        let translated = GeneticCode::standard().translate(&cds);
+        let protein = translated.strip_suffix('*').unwrap_or(&translated);
+        if let Some(codon) = protein.find('*') {
+            return Err(ConstructError::InvalidCds(format!(
+                "internal stop codon at codon {}",
+                codon + 1
+            )));
+        }
+        if protein.contains('X') {
+            return Err(ConstructError::InvalidCds(
+                "ambiguous codons cannot be translated".to_string(),
+            ));
+        }
+        if !cds.starts_with("ATG") {
+            warnings.push("Gene CDS does not start with ATG; a start codon was added".to_string());
+        }
+        Ok(protein.to_string())
+    }
+
+    /// N末端に開始Met、タグ+リンカー、目的タンパク質、リンカー+C末端タグの順に連結
+    fn fuse(
+        &self,
+        gene_protein: &str,
+        tags: &[ConstructTag],
+    ) -> Result<(String, Vec<Part>), ConstructError> {
+        let mut protein = String::from("M");
+        let mut parts = Vec::new();
+        let mut push = |protein: &mut String, label: &str, sequence: &str| {
+            if !sequence.is_empty() {
+                parts.push(Part {
+                    label: label.to_string(),
+                    start: protein.len(),
+                    end: protein.len() + sequence.len(),
+                });
+                protein.push_str(sequence);
+            }
+        };
+
+        let resolved = tags
+            .iter()
+            .map(|tag| {
+                let (name, sequence) = match &tag.protein {
+                    Some(protein) => (tag.name.clone(), protein.to_ascii_uppercase()),
+                    None => builtin_tags()
+                        .iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case(&tag.name))
+                        .map(|(name, protein)| (name.to_string(), protein.to_string()))
+                        .ok_or_else(|| ConstructError::UnknownTag(tag.name.clone()))?,
+                };
+                Ok((tag, name, sequence))
+            })
+            .collect::<Result<Vec<_>, ConstructError>>()?;
+
+        for (tag, name, sequence) in resolved
+            .iter()
+            .filter(|(t, ..)| t.terminus == TagTerminus::N)
+        {
+            push(&mut protein, name, sequence);
+            push(&mut protein, "linker", tag.linker.as_deref().unwrap_or(""));
+        }
+        push(
+            &mut protein,
+            "gene",
+            gene_protein.strip_prefix('M').unwrap_or(gene_protein),
+        );
+        for (tag, name, sequence) in resolved
+            .iter()
+            .filter(|(t, ..)| t.terminus == TagTerminus::C)
+        {
+            push(&mut protein, "linker", tag.linker.as_deref().unwrap_or(""));
+            push(&mut protein, name, sequence);
+        }
+
+        Ok((protein, parts))
+    }
+
+    /// 同義コドンへの置換でORF内部のクローニング酵素サイトを除去し、除去数を返す
+    fn remove_sites(
+        &self,
+        codons: &mut [&'static str],
+        residues: &[char],
+        enzymes: &[&RestrictionEnzyme],
+    ) -> usize {
+        let scan = |codons: &[&str]| {
+            self.restriction_service
+                .scan(&codons.concat(), &Topology::Linear, enzymes)
+        };
+
+        let mut removed = 0;
+        let mut unremovable: Vec<(String, usize)> = Vec::new();
+        loop {
+            let sites = scan(codons);
+            let Some(site) = sites
+                .iter()
+                .find(|s| !unremovable.contains(&(s.enzyme.clone(), s.position)))
+            else {
+                break;
+            };
+            let site_len = enzymes
+                .iter()
+                .find(|e| e.name == site.enzyme)
+                .map(|e| e.site.len())
+                .unwrap_or(1);
+
+            // サイトに重なるコドンを順に同義コドンへ置換し、サイト総数が減る最初の置換を採用
+            let first = site.position / 3;
+            let last = (site.position + site_len - 1) / 3;
+            let replacement = (first..=last).find_map(|index| {
+                ecoli_codons(residues[index])
+                    .iter()
+                    .filter(|&&alt| alt != codons[index])
+                    .find_map(|&alt| {
+                        let mut trial = codons.to_vec();
+                        trial[index] = alt;
+                        (scan(&trial).len() < sites.len()).then_some((index, alt))
+                    })
+            });
+
+            match replacement {
+                Some((index, alt)) => {
+                    codons[index] = alt;
+                    removed += 1;
+                }
+                None => unremovable.push((site.enzyme.clone(), site.position)),
+            }
+        }
+        removed
+    }
+
+    /// Tmが目標に達する最短のアニーリング長（ORF端から）
+    fn anneal_length(&self, orf: &str, direction: PrimerDirection) -> usize {
+        (ANNEAL_LENGTH_MIN..=ANNEAL_LENGTH_MAX.min(orf.len()))
+            .find(|&length| {
+                let region = match direction {
+                    PrimerDirection::Forward => orf[..length].to_string(),
+                    PrimerDirection::Reverse => {
+                        iupac::reverse_complement(&orf[orf.len() - length..])
+                    }
+                };
+                self.primer_service.calculate_tm(&region) >= ANNEAL_TM_TARGET
+            })
+            .unwrap_or(ANNEAL_LENGTH_MAX.min(orf.len()))
+    }
+
+    /// ベクター由来アノテーションの座標を移し、ORF・タグ・クローニングサイトを追加
+    fn annotate(
+        &self,
+        vector_features: &[SequenceFeature],
+        parts: &[Part],
+        protein: &str,
+        layout: &Layout,
+        (site5_name, site3_name): (&str, &str),
+        warnings: &mut Vec<String>,
+    ) -> Vec<SequenceFeature> {
+        let Layout {
+            vector_start,
+            vector_end,
+            site5_end,
+            orf_start,
+            orf_end,
+            insert_end,
+        } = *layout;
+        let offset = insert_end as isize - vector_end as isize;
+        let mut features: Vec<SequenceFeature> = Vec::new();
+        for feature in vector_features {
+            if feature.end <= vector_start {
+                features.push(feature.clone());
+            } else if feature.start >= vector_end {
+                features.push(feature.shifted(offset));
+            } else {
+                warnings.push(format!(
+                    "Vector feature '{}' is replaced by the insert",
+                    feature.label()
+                ));
+                continue;
+            }
+            if let Some(last) = features.last_mut() {
+                // IDs belong to the vector's store; the construct gets fresh ones
+                last.id.clear();
+            }
+        }
+
+        let labeled = |feature_type: &str, start: usize, end: usize, label: &str| {
+            let mut feature = SequenceFeature::new(feature_type, start, end, Strand::Forward);
+            feature
+                .qualifiers
+                .insert("label".to_string(), label.to_string());
+            feature
+        };
+
+        let mut cds = labeled("CDS", orf_start, orf_end, "fusion protein");
+        cds.qualifiers
+            .insert("translation".to_string(), protein.to_string());
+        features.push(cds);
+
+        for part in parts.iter().filter(|part| part.label != "gene") {
+            features.push(labeled(
+                "misc_feature",
+                orf_start + part.start * 3,
+                orf_start + part.end * 3,
+                &part.label,
+            ));
+        }
+        features.push(labeled("misc_feature", vector_start, site5_end, site5_name));
+        features.push(labeled("misc_feature", orf_end, insert_end, site3_name));
+
+        features.sort_by_key(|feature| feature.start);
+        features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // NdeI (CATATG) と XhoI (CTCGAG) を1箇所ずつ持つベクター
+    const VECTOR: &str = "TTACGGATTACAGCATATGGCTAGCAAGCTTGCGGCCGCACTCGAGCACCACTGATTAGCAT";
+
+    fn design(gene: &str, tags: &[ConstructTag]) -> Result<ExpressionConstruct, ConstructError> {
+        let restriction = RestrictionAnalysisService::new();
+        let enzymes = restriction
+            .resolve(&["NdeI".to_string(), "XhoI".to_string()])
+            .unwrap();
+        ExpressionConstructService::new().design(
+            gene,
+            VECTOR,
+            &Topology::Circular,
+            &[],
+            &enzymes,
+            tags,
+        )
+    }
+
+    #[test]
+    fn test_his_tagged_construct_keeps_frame() {
+        let tag = ConstructTag {
+            name: "his6".to_string(),
+            terminus: TagTerminus::N,
+            protein: None,
+            linker: Some("GS".to_string()),
+        };
+        let construct = design("ATGAAACTGGAATTTTAA", &[tag]).unwrap();
+
+        assert!(construct.frame_verified);
+        assert_eq!(construct.protein, "MHHHHHHGSKLEF");
+        assert!(construct.unwanted_sites.is_empty());
+        // NdeI の ATG を開始コドンとして共有
+        assert_eq!(
+            &construct.sequence[construct.orf_start - 3..][..6],
+            "CATATG"
+        );
+        assert!(construct.forward_primer.sequence.starts_with("GCGCCATATG"));
+        assert!(construct.reverse_primer.sequence.starts_with("GCGCCTCGAG"));
+        assert!(construct.features.iter().any(|f| f.label() == "His6"));
+        assert_eq!(construct.vector_start, 13);
+        // ベクターの NdeI 上流に SD 配列がないので弱い RBS として警告
+        assert!(construct.rbs.as_ref().unwrap().weak);
+        assert!(construct.warnings.iter().any(|w| w.starts_with("Weak RBS")));
+    }
+
+    #[test]
+    fn test_internal_cloning_site_is_removed() {
+        // His-Met は最適コドンで CAT ATG となり NdeI サイトを生じる
+        let construct = design("ATGGCTCATATGAAATAA", &[]).unwrap();
+        assert!(construct.sites_removed >= 1);
+        assert!(construct.unwanted_sites.is_empty());
+        assert!(construct.frame_verified);
+
+        assert!(matches!(
+            design("ATGTAAGCT", &[]),
+            Err(ConstructError::InvalidCds(_))
+        ));
+    }
+}
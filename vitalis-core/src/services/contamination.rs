@@ -0,0 +1,263 @@
+// Service layer: Vector / adapter contamination screening (UniVec-style)
+use crate::domain::contamination::{
+    univec_signatures, ContaminantSignature, ContaminationHit, ContaminationReport, MatchStrength,
+};
+use crate::domain::iupac;
+use crate::domain::Strand;
+use std::collections::{HashMap, HashSet};
+
+/// シード（完全一致）の長さ
+const SEED_LENGTH: usize = 12;
+const MATCH_SCORE: i32 = 1;
+const MISMATCH_SCORE: i32 = -5;
+/// 最高スコアからこれだけ下がったら伸長を打ち切る
+const X_DROP: i32 = 20;
+/// 配列端からこの距離以内にかかる一致は末端の一致として扱う
+const TERMINAL_DISTANCE: usize = 25;
+
+/// シグネチャ上の k-mer の位置（シグネチャ番号、鎖、位置）
+type SeedHit = (usize, Strand, usize);
+
+/// Vector contamination screening service
+pub struct VectorContaminationService {
+    signatures: Vec<ContaminantSignature>,
+    /// 鎖ごとのシグネチャ配列（Reverse は逆相補）
+    strands: Vec<[Vec<u8>; 2]>,
+    index: HashMap<Vec<u8>, Vec<SeedHit>>,
+}
+
+impl Default for VectorContaminationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VectorContaminationService {
+    pub fn new() -> Self {
+        Self::with_signatures(univec_signatures())
+    }
+
+    pub fn with_signatures(signatures: Vec<ContaminantSignature>) -> Self {
+        let strands: Vec<[Vec<u8>; 2]> = signatures
+            .iter()
+            .map(|signature| {
+                let forward = signature.sequence.to_ascii_uppercase();
+                let reverse = iupac::reverse_complement(&forward);
+                [forward.into_bytes(), reverse.into_bytes()]
+            })
+            .collect();
+
+        let mut index: HashMap<Vec<u8>, Vec<SeedHit>> = HashMap::new();
+        for (number, pair) in strands.iter().enumerate() {
+            for (strand, bases) in [Strand::Forward, Strand::Reverse].into_iter().zip(pair) {
+                for (offset, kmer) in bases.windows(SEED_LENGTH).enumerate() {
+                    if kmer.iter().all(|b| b"ACGT".contains(b)) {
+                        index
+                            .entry(kmer.to_vec())
+                            .or_default()
+                            .push((number, strand, offset));
+                    }
+                }
+            }
+        }
+        Self {
+            signatures,
+            strands,
+            index,
+        }
+    }
+
+    /// 配列中のシグネチャと一致する区間を探す（配列IDは呼び出し側で設定する）
+    pub fn screen(&self, sequence: &str) -> ContaminationReport {
+        let query = sequence.to_ascii_uppercase().into_bytes();
+        let length = query.len();
+        let mut hits = Vec::new();
+        // 伸長済みの対角線ごとの終端（同じ一致を何度も伸長しない）
+        let mut extended: HashMap<(usize, usize, isize), usize> = HashMap::new();
+
+        for (position, kmer) in query.windows(SEED_LENGTH).enumerate() {
+            let Some(seeds) = self.index.get(kmer) else {
+                continue;
+            };
+            for &(number, strand, offset) in seeds {
+                let diagonal = position as isize - offset as isize;
+                if extended
+                    .get(&(number, strand as usize, diagonal))
+                    .is_some_and(|&end| position < end)
+                {
+                    continue;
+                }
+                let target = &self.strands[number][strand as usize];
+                let (start, end, score, matches) = extend(&query, target, position, offset);
+                extended.insert((number, strand as usize, diagonal), end);
+
+                let terminal = start < TERMINAL_DISTANCE || end + TERMINAL_DISTANCE > length;
+                if let Some(strength) = MatchStrength::classify(score, terminal) {
+                    let signature = &self.signatures[number];
+                    hits.push(ContaminationHit {
+                        signature: signature.name.clone(),
+                        kind: signature.kind,
+                        start,
+                        end,
+                        strand,
+                        score,
+                        identity: matches as f64 / (end - start) as f64 * 100.0,
+                        terminal,
+                        strength,
+                    });
+                }
+            }
+        }
+
+        // 同じシグネチャの重なる一致は最もスコアの高いものだけ残す
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then(a.start.cmp(&b.start)));
+        let mut kept: Vec<ContaminationHit> = Vec::new();
+        for hit in hits {
+            if !kept
+                .iter()
+                .any(|k| k.signature == hit.signature && k.start < hit.end && hit.start < k.end)
+            {
+                kept.push(hit);
+            }
+        }
+        kept.sort_by(|a, b| a.start.cmp(&b.start).then(b.score.cmp(&a.score)));
+
+        let covered: HashSet<usize> = kept.iter().flat_map(|hit| hit.start..hit.end).collect();
+        ContaminationReport {
+            seq_id: String::new(),
+            length,
+            contaminated_bases: covered.len(),
+            strongest: kept.iter().map(|hit| hit.strength).max(),
+            hits: kept,
+        }
+    }
+}
+
+/// シードから両方向にギャップなしで伸長する（X-drop）。
+/// 戻り値は配列上の区間、スコア、一致塩基数
+fn extend(
+    query: &[u8],
+    target: &[u8],
+    position: usize,
+    offset: usize,
+) -> (usize, usize, i32, usize) {
+    let score_at = |q: usize, t: usize| {
+        if query[q] == target[t] && b"ACGT".contains(&query[q]) {
+            MATCH_SCORE
+        } else {
+            MISMATCH_SCORE
+        }
+    };
+    let seed_score: i32 = (0..SEED_LENGTH)
+        .map(|i| score_at(position + i, offset + i))
+        .sum();
+
+    // 右方向
+    let (mut right, mut best_right, mut score) = (0, 0, 0);
+    let mut step = SEED_LENGTH;
+    while position + step < query.len() && offset + step < target.len() {
+        score += score_at(position + step, offset + step);
+        step += 1;
+        if score > best_right {
+            best_right = score;
+            right = step - SEED_LENGTH;
+        } else if best_right - score > X_DROP {
+            break;
+        }
+    }
+
+    // 左方向
+    let (mut left, mut best_left, mut score) = (0, 0, 0);
+    let mut step = 1;
+    while step <= position && step <= offset {
+        score += score_at(position - step, offset - step);
+        if score > best_left {
+            best_left = score;
+            left = step;
+        } else if best_left - score > X_DROP {
+            break;
+        }
+        step += 1;
+    }
+
+    let start = position - left;
+    let end = position + SEED_LENGTH + right;
+    let matches = (start..end)
+        .filter(|&q| score_at(q, q + offset - position) == MATCH_SCORE)
+        .count();
+    (start, end, seed_score + best_right + best_left, matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::contamination::ContaminantKind;
+
+    fn random_sequence(length: usize, seed: u64) -> String {
+        let mut state = seed;
+        (0..length)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4] as char
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_flags_internal_and_terminal_vector_segments() {
+        let service = VectorContaminationService::new();
+        let genomic = random_sequence(2000, 3);
+        assert!(service.screen(&genomic).hits.is_empty());
+
+        // 内部に AmpR（逆鎖）、3' 末端に MCS の断片
+        let amp = "ATGAGTATTCAACATTTCCGTGTCGCCCTTATTCCC";
+        let mcs = &"GAATTCGAGCTCGGTACCCGGGGATCCTCTAGAGTCGACCTGCAGGCATGCAAGCTT"[..20];
+        let sequence = format!(
+            "{}{}{}{}",
+            &genomic[..800],
+            iupac::reverse_complement(amp),
+            &genomic[800..1600],
+            mcs
+        );
+        let report = service.screen(&sequence.to_ascii_lowercase());
+        assert_eq!(report.hits.len(), 2);
+
+        let amp_hit = &report.hits[0];
+        assert_eq!(amp_hit.signature, "AmpR");
+        assert_eq!(amp_hit.kind, ContaminantKind::Vector);
+        assert_eq!((amp_hit.start, amp_hit.end), (800, 800 + amp.len()));
+        assert_eq!(amp_hit.strand, Strand::Reverse);
+        assert_eq!(amp_hit.strength, MatchStrength::Strong);
+        assert!(!amp_hit.terminal);
+
+        // 20 bp は内部なら報告されないが、末端なら Moderate
+        let mcs_hit = &report.hits[1];
+        assert_eq!(mcs_hit.signature, "pUC19 MCS");
+        assert!(mcs_hit.terminal);
+        assert_eq!(mcs_hit.strength, MatchStrength::Moderate);
+        assert_eq!(report.contaminated_bases, amp.len() + 20);
+        assert_eq!(report.strongest, Some(MatchStrength::Strong));
+    }
+
+    #[test]
+    fn test_mismatches_lower_the_score() {
+        let service = VectorContaminationService::with_signatures(vec![ContaminantSignature::new(
+            "adapter",
+            ContaminantKind::Adapter,
+            "AGATCGGAAGAGCACACGTCTGAACTCCAGTCAC",
+        )]);
+        let flank = random_sequence(100, 8);
+        let exact = format!("{}AGATCGGAAGAGCACACGTCTGAACTCCAGTCAC{}", flank, flank);
+        let hit = &service.screen(&exact).hits[0];
+        assert_eq!((hit.score, hit.identity), (34, 100.0));
+        assert_eq!(hit.strength, MatchStrength::Strong);
+
+        let mismatched = format!("{}AGATCGGAAGAGCACACGTCTGAAGTCCAGTCAC{}", flank, flank);
+        let hit = &service.screen(&mismatched).hits[0];
+        assert_eq!(hit.score, 28);
+        assert_eq!(hit.strength, MatchStrength::Moderate);
+        assert!(hit.identity < 100.0);
+    }
+}
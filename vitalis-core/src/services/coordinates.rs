@@ -0,0 +1,370 @@
+// Service layer: Conversion between coordinate numbering schemes
+use crate::domain::coordinates::{ConvertedCoordinate, CoordinateSystem};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::{Range, Strand};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CoordinateError {
+    #[error("Invalid position '{0}'")]
+    InvalidPosition(String),
+    #[error("Position {0} is outside the sequence (length {1})")]
+    OutOfRange(i64, usize),
+    #[error("Position {0} is not within the coding region")]
+    NotCoding(String),
+    #[error("Feature has no coding segments")]
+    EmptyFeature,
+}
+
+/// c. 表記の基準点
+enum CdsAnchor {
+    /// CDS上の1-based位置
+    Coding(i64),
+    /// 開始コドン上流の距離（c.-N）
+    Upstream(i64),
+    /// 終止コドン下流の距離（c.*N）
+    Downstream(i64),
+}
+
+/// 転写方向に並べたCDSのエキソン
+struct CdsModel {
+    exons: Vec<Range>,
+    strand: Strand,
+}
+
+impl CdsModel {
+    fn new(feature: &SequenceFeature) -> Result<Self, CoordinateError> {
+        let mut exons = if feature.segments.is_empty() {
+            vec![Range::new(feature.start, feature.end)]
+        } else {
+            feature.segments.clone()
+        };
+        exons.retain(|exon| exon.end > exon.start);
+        if exons.is_empty() {
+            return Err(CoordinateError::EmptyFeature);
+        }
+        exons.sort_by_key(|exon| exon.start);
+        if feature.strand == Strand::Reverse {
+            exons.reverse();
+        }
+        Ok(Self {
+            exons,
+            strand: feature.strand,
+        })
+    }
+
+    /// 転写方向への移動
+    fn step(&self, position: i64, distance: i64) -> i64 {
+        match self.strand {
+            Strand::Forward => position + distance,
+            Strand::Reverse => position - distance,
+        }
+    }
+
+    /// エキソンの転写方向での先頭・末尾の塩基
+    fn first_base(&self, exon: &Range) -> i64 {
+        match self.strand {
+            Strand::Forward => exon.start as i64,
+            Strand::Reverse => exon.end as i64 - 1,
+        }
+    }
+
+    fn last_base(&self, exon: &Range) -> i64 {
+        match self.strand {
+            Strand::Forward => exon.end as i64 - 1,
+            Strand::Reverse => exon.start as i64,
+        }
+    }
+
+    /// 転写方向に見た `a` から `b` までの距離
+    fn distance(&self, a: i64, b: i64) -> i64 {
+        match self.strand {
+            Strand::Forward => b - a,
+            Strand::Reverse => a - b,
+        }
+    }
+
+    /// 内部座標 → (基準点, イントロン内オフセット)
+    fn locate(&self, position: i64) -> (CdsAnchor, i64) {
+        let mut coding_before = 0;
+        for (i, exon) in self.exons.iter().enumerate() {
+            let into = self.distance(self.first_base(exon), position);
+            if (exon.start as i64..exon.end as i64).contains(&position) {
+                return (CdsAnchor::Coding(coding_before + into + 1), 0);
+            }
+            if i == 0 && into < 0 {
+                return (CdsAnchor::Upstream(-into), 0);
+            }
+
+            let exon_len = exon.len() as i64;
+            let past_end = self.distance(self.last_base(exon), position);
+            match self.exons.get(i + 1) {
+                Some(next) => {
+                    let before_next = self.distance(position, self.first_base(next));
+                    if past_end > 0 && before_next > 0 {
+                        // イントロン中央では上流側（+）を優先
+                        return if past_end <= before_next {
+                            (CdsAnchor::Coding(coding_before + exon_len), past_end)
+                        } else {
+                            (
+                                CdsAnchor::Coding(coding_before + exon_len + 1),
+                                -before_next,
+                            )
+                        };
+                    }
+                }
+                None => return (CdsAnchor::Downstream(past_end), 0),
+            }
+            coding_before += exon_len;
+        }
+        unreachable!("every position is upstream, inside or downstream of the CDS")
+    }
+
+    /// (基準点, オフセット) → 内部座標
+    fn resolve(&self, anchor: CdsAnchor, offset: i64) -> i64 {
+        let base = match anchor {
+            CdsAnchor::Upstream(distance) => self.step(self.first_base(&self.exons[0]), -distance),
+            CdsAnchor::Downstream(distance) => {
+                self.step(self.last_base(self.exons.last().unwrap()), distance)
+            }
+            CdsAnchor::Coding(c) => {
+                let mut remaining = c - 1;
+                let mut resolved = None;
+                for exon in &self.exons {
+                    let exon_len = exon.len() as i64;
+                    if remaining < exon_len {
+                        resolved = Some(self.step(self.first_base(exon), remaining));
+                        break;
+                    }
+                    remaining -= exon_len;
+                }
+                // CDS末尾を越える場合は最終エキソンから延長
+                resolved.unwrap_or_else(|| {
+                    self.step(self.last_base(self.exons.last().unwrap()), remaining + 1)
+                })
+            }
+        };
+        self.step(base, offset)
+    }
+}
+
+/// Coordinate conversion service
+pub struct CoordinateService;
+
+impl Default for CoordinateService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoordinateService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Convert `position` written in `from` into `to`. `cds` is required when
+    /// either system is CDS- or protein-relative.
+    pub fn convert(
+        &self,
+        position: &str,
+        from: &CoordinateSystem,
+        to: &CoordinateSystem,
+        sequence_length: usize,
+        cds_from: Option<&SequenceFeature>,
+        cds_to: Option<&SequenceFeature>,
+    ) -> Result<ConvertedCoordinate, CoordinateError> {
+        let internal = self.parse_position(position, from, cds_from)?;
+        if internal < 0 || internal >= sequence_length as i64 {
+            return Err(CoordinateError::OutOfRange(internal, sequence_length));
+        }
+        let internal = internal as usize;
+        Ok(ConvertedCoordinate {
+            position: internal,
+            display: self.format_position(internal, to, cds_to)?,
+        })
+    }
+
+    /// 内部座標のc.表記（"c." を除いた "45" / "-3" / "88+1" / "*4" の形式）
+    pub fn cds_label(
+        &self,
+        position: usize,
+        cds: &SequenceFeature,
+    ) -> Result<String, CoordinateError> {
+        let (anchor, offset) = CdsModel::new(cds)?.locate(position as i64);
+        let base = match anchor {
+            CdsAnchor::Coding(c) => c.to_string(),
+            CdsAnchor::Upstream(distance) => format!("-{}", distance),
+            CdsAnchor::Downstream(distance) => format!("*{}", distance),
+        };
+        Ok(match offset {
+            0 => base,
+            o if o > 0 => format!("{}+{}", base, o),
+            o => format!("{}{}", base, o),
+        })
+    }
+
+    fn parse_position(
+        &self,
+        position: &str,
+        system: &CoordinateSystem,
+        cds: Option<&SequenceFeature>,
+    ) -> Result<i64, CoordinateError> {
+        let text = position.trim();
+        let invalid = || CoordinateError::InvalidPosition(position.to_string());
+        let number = |s: &str| s.parse::<i64>().map_err(|_| invalid());
+
+        match system {
+            CoordinateSystem::ZeroBased => number(text),
+            CoordinateSystem::OneBased => {
+                let value = number(text)?;
+                if value < 1 {
+                    return Err(invalid());
+                }
+                Ok(value - 1)
+            }
+            CoordinateSystem::Cds { .. } => {
+                let model = CdsModel::new(cds.ok_or(CoordinateError::EmptyFeature)?)?;
+                let text = text.strip_prefix("c.").unwrap_or(text);
+                let (anchor, offset) = parse_cds(text).ok_or_else(invalid)?;
+                Ok(model.resolve(anchor, offset))
+            }
+            CoordinateSystem::Protein { .. } => {
+                let model = CdsModel::new(cds.ok_or(CoordinateError::EmptyFeature)?)?;
+                let text = text.strip_prefix("p.").unwrap_or(text);
+                // p.Met1 のような3文字表記にも対応
+                let digits = text.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+                let codon = number(digits)?;
+                if codon < 1 {
+                    return Err(invalid());
+                }
+                Ok(model.resolve(CdsAnchor::Coding(codon * 3 - 2), 0))
+            }
+        }
+    }
+
+    fn format_position(
+        &self,
+        position: usize,
+        system: &CoordinateSystem,
+        cds: Option<&SequenceFeature>,
+    ) -> Result<String, CoordinateError> {
+        match system {
+            CoordinateSystem::ZeroBased => Ok(position.to_string()),
+            CoordinateSystem::OneBased => Ok((position + 1).to_string()),
+            CoordinateSystem::Cds { .. } => Ok(format!(
+                "c.{}",
+                self.cds_label(position, cds.ok_or(CoordinateError::EmptyFeature)?)?
+            )),
+            CoordinateSystem::Protein { .. } => {
+                let model = CdsModel::new(cds.ok_or(CoordinateError::EmptyFeature)?)?;
+                match model.locate(position as i64) {
+                    (CdsAnchor::Coding(c), 0) => Ok(format!("p.{}", (c - 1) / 3 + 1)),
+                    _ => Err(CoordinateError::NotCoding((position + 1).to_string())),
+                }
+            }
+        }
+    }
+}
+
+/// "123" / "-12" / "*5" / "88+1" / "89-2" を解析
+fn parse_cds(text: &str) -> Option<(CdsAnchor, i64)> {
+    let (anchor_text, offset) = match text[1..].find(['+', '-']) {
+        Some(i) => {
+            let (anchor, offset) = text.split_at(i + 1);
+            (anchor, offset.parse::<i64>().ok()?)
+        }
+        None => (text, 0),
+    };
+
+    let anchor = if let Some(distance) = anchor_text.strip_prefix('-') {
+        CdsAnchor::Upstream(distance.parse().ok().filter(|&d: &i64| d > 0)?)
+    } else if let Some(distance) = anchor_text.strip_prefix('*') {
+        CdsAnchor::Downstream(distance.parse().ok().filter(|&d: &i64| d > 0)?)
+    } else {
+        CdsAnchor::Coding(anchor_text.parse().ok().filter(|&c: &i64| c > 0)?)
+    };
+    Some((anchor, offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 順鎖: エキソン [10,16) と [30,36)、逆鎖: 同じ区間の逆向き
+    fn cds(strand: Strand) -> SequenceFeature {
+        let mut feature = SequenceFeature::new("CDS", 10, 36, strand);
+        feature.segments = vec![Range::new(10, 16), Range::new(30, 36)];
+        feature
+    }
+
+    fn convert(
+        position: &str,
+        from: CoordinateSystem,
+        to: CoordinateSystem,
+        strand: Strand,
+    ) -> String {
+        let feature = cds(strand);
+        CoordinateService::new()
+            .convert(position, &from, &to, 50, Some(&feature), Some(&feature))
+            .unwrap()
+            .display
+    }
+
+    #[test]
+    fn test_spliced_forward_cds() {
+        let c = || CoordinateSystem::Cds {
+            feature_id: "feat_1".to_string(),
+        };
+        use CoordinateSystem::*;
+        let cases = [
+            ("11", "c.1"),
+            ("16", "c.6"),
+            ("17", "c.6+1"),
+            ("30", "c.7-1"),
+            ("31", "c.7"),
+            ("5", "c.-6"),
+            ("40", "c.*4"),
+        ];
+        for (one_based, expected) in cases {
+            assert_eq!(convert(one_based, OneBased, c(), Strand::Forward), expected);
+            assert_eq!(convert(expected, c(), OneBased, Strand::Forward), one_based);
+        }
+        assert_eq!(
+            convert(
+                "p.3",
+                Protein {
+                    feature_id: "feat_1".to_string()
+                },
+                OneBased,
+                Strand::Forward
+            ),
+            "31"
+        );
+    }
+
+    #[test]
+    fn test_reverse_strand_and_non_coding_protein() {
+        let c = || CoordinateSystem::Cds {
+            feature_id: "feat_1".to_string(),
+        };
+        use CoordinateSystem::*;
+        // 逆鎖では内部座標35が c.1、30が c.6、15が c.7
+        assert_eq!(convert("35", ZeroBased, c(), Strand::Reverse), "c.1");
+        assert_eq!(convert("29", ZeroBased, c(), Strand::Reverse), "c.6+1");
+        assert_eq!(convert("c.7", c(), ZeroBased, Strand::Reverse), "15");
+        assert_eq!(convert("c.-2", c(), ZeroBased, Strand::Reverse), "37");
+
+        let feature = cds(Strand::Forward);
+        let result = CoordinateService::new().convert(
+            "20",
+            &ZeroBased,
+            &Protein {
+                feature_id: "feat_1".to_string(),
+            },
+            50,
+            Some(&feature),
+            Some(&feature),
+        );
+        assert!(matches!(result, Err(CoordinateError::NotCoding(_))));
+    }
+}
@@ -0,0 +1,490 @@
+// Service layer: CRISPR guide RNA design with on-target scoring and off-target search
+use crate::domain::crispr::{CasPam, GuideDesignParams, GuideDesignResult, GuideRna, OffTarget};
+use crate::domain::iupac;
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+use crate::domain::Strand;
+
+/// Rule Set 1（Doench 2014）のロジスティック回帰。30-mer（5' 側4塩基 + スペーサー20 +
+/// PAM 3 + 3' 側3塩基）の 0-based 位置に一致する塩基・ジヌクレオチドの重み
+const RULE_SET_1: [(usize, &str, f64); 70] = [
+    (1, "G", -0.275_377_1),
+    (2, "A", -0.323_887_5),
+    (2, "C", 0.172_128_87),
+    (3, "C", -0.100_666_2),
+    (4, "C", -0.201_802_9),
+    (4, "G", 0.245_956_63),
+    (5, "A", 0.036_440_04),
+    (5, "C", 0.098_376_84),
+    (6, "C", -0.741_181_3),
+    (6, "G", -0.393_264_4),
+    (11, "A", -0.466_099),
+    (14, "A", 0.085_376_95),
+    (14, "C", -0.013_814),
+    (15, "A", 0.272_620_51),
+    (15, "C", -0.119_022_6),
+    (15, "T", -0.285_944_2),
+    (16, "A", 0.097_454_59),
+    (16, "G", -0.175_546_2),
+    (17, "C", -0.345_795_5),
+    (17, "G", -0.678_096_4),
+    (18, "A", 0.225_089_03),
+    (18, "C", -0.507_794_1),
+    (19, "G", -0.417_373_6),
+    (19, "T", -0.054_307),
+    (20, "G", 0.379_899_37),
+    (20, "T", -0.090_712_6),
+    (21, "C", 0.057_823_32),
+    (21, "T", -0.530_567_3),
+    (22, "T", -0.877_007_4),
+    (23, "C", -0.876_235_8),
+    (23, "G", 0.278_916_26),
+    (23, "T", -0.403_102_2),
+    (24, "A", -0.077_300_7),
+    (24, "C", 0.287_935_62),
+    (24, "T", -0.221_637_2),
+    (27, "G", -0.689_016_7),
+    (27, "T", 0.117_877_58),
+    (28, "C", -0.160_445_3),
+    (29, "G", 0.386_342_58),
+    (1, "GT", -0.625_778_7),
+    (4, "GC", 0.300_043_32),
+    (5, "AA", -0.834_836_2),
+    (5, "TA", 0.760_627_77),
+    (6, "GG", -0.490_816_7),
+    (11, "GG", -1.516_907_4),
+    (11, "TA", 0.709_261_2),
+    (11, "TC", 0.496_298_61),
+    (11, "TT", -0.586_873_9),
+    (12, "GG", -0.334_563_7),
+    (13, "GA", 0.763_849_93),
+    (13, "GC", -0.537_025_2),
+    (16, "TG", -0.798_146_1),
+    (18, "GG", -0.666_808_7),
+    (18, "TC", 0.353_183_25),
+    (19, "CC", 0.748_072_09),
+    (19, "TG", -0.367_266_8),
+    (20, "AC", 0.568_209_13),
+    (20, "CG", 0.329_072_07),
+    (20, "GA", -0.836_456_8),
+    (20, "GG", -0.782_207_6),
+    (21, "TC", -1.029_693),
+    (22, "CG", 0.856_197_82),
+    (22, "CT", -0.463_207_7),
+    (23, "AA", -0.579_492_4),
+    (23, "AG", 0.649_075_54),
+    (24, "AG", -0.077_300_7),
+    (24, "CG", 0.287_935_62),
+    (24, "TG", -0.221_637_2),
+    (26, "GT", 0.117_877_58),
+    (28, "GG", -0.697_74),
+];
+const RULE_SET_1_INTERCEPT: f64 = 0.597_636_15;
+/// GC 数が半分より多い・少ない場合の1塩基あたりの重み
+const RULE_SET_1_GC_HIGH: f64 = -0.166_587_8;
+const RULE_SET_1_GC_LOW: f64 = -0.202_625_9;
+/// スコア計算に使うスペーサーの 5' 側・3' 側（PAM を含む）の文脈
+const CONTEXT_5: usize = 4;
+const CONTEXT_3: usize = 6;
+
+/// ミスマッチ位置ごとの切断低下の重み（Hsu 2013、PAM から遠い側から近い側の順）
+const MISMATCH_WEIGHTS: [f32; 20] = [
+    0.0, 0.0, 0.014, 0.0, 0.0, 0.395, 0.317, 0.0, 0.389, 0.079, 0.445, 0.508, 0.613, 0.851, 0.732,
+    0.828, 0.615, 0.804, 0.685, 0.583,
+];
+
+/// ミスマッチ1つ以下のオフターゲットはガイドの警告にする
+const NEAR_MATCH_MISMATCHES: usize = 1;
+
+/// 配列上の PAM 部位（座標は鋳型上、プロトスペーサーの0-based開始位置）
+struct PamSite {
+    strand: Strand,
+    start: usize,
+    /// 鎖上のプロトスペーサー開始位置（スコア計算の文脈を取るため）
+    offset: usize,
+    protospacer: String,
+    pam: String,
+    cut_site: usize,
+}
+
+/// CRISPR guide design service
+pub struct CrisprDesignService {
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for CrisprDesignService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrisprDesignService {
+    pub fn new() -> Self {
+        Self {
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// 切断位置が [start, end) に入るガイドを両鎖から列挙し、オンターゲット効率と
+    /// 配列全体でのオフターゲット候補（同じ PAM、`max_mismatches` 以下）から順位を付ける
+    pub fn design_guides(
+        &self,
+        sequence: &str,
+        start: usize,
+        end: usize,
+        pam: CasPam,
+        params: &GuideDesignParams,
+    ) -> GuideDesignResult {
+        let forward = sequence.to_ascii_uppercase();
+        let reverse = iupac::reverse_complement(&forward);
+        let mut sites = self.pam_sites(forward.as_bytes(), Strand::Forward, pam);
+        sites.extend(self.pam_sites(reverse.as_bytes(), Strand::Reverse, pam));
+
+        let candidates: Vec<&PamSite> = sites
+            .iter()
+            .filter(|site| site.cut_site >= start && site.cut_site < end)
+            .collect();
+
+        let mut guides: Vec<GuideRna> = candidates
+            .iter()
+            .filter_map(|site| {
+                let strand_sequence = match site.strand {
+                    Strand::Forward => forward.as_bytes(),
+                    Strand::Reverse => reverse.as_bytes(),
+                };
+                self.evaluate(site, strand_sequence, &sites, pam, params)
+            })
+            .collect();
+
+        guides.sort_by(|a, b| {
+            let rank = |guide: &GuideRna| guide.on_target_score * guide.specificity_score;
+            rank(b)
+                .partial_cmp(&rank(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.start.cmp(&b.start))
+        });
+        guides.truncate(params.max_guides);
+
+        GuideDesignResult {
+            guides,
+            pam,
+            target_start: start,
+            target_end: end,
+            candidates_considered: candidates.len(),
+            params: params.clone(),
+            provenance: Some(self.calculator.provenance()),
+        }
+    }
+
+    /// 鎖 `strand_sequence` 上の PAM 部位（ACGT 以外を含むプロトスペーサーは除く）
+    fn pam_sites(&self, strand_sequence: &[u8], strand: Strand, pam: CasPam) -> Vec<PamSite> {
+        let motif = pam.motif().as_bytes();
+        let spacer = pam.spacer_length();
+        let length = strand_sequence.len();
+        if length < spacer + motif.len() {
+            return Vec::new();
+        }
+
+        let mut sites = Vec::new();
+        for offset in 0..=length - spacer - motif.len() {
+            let (protospacer_at, pam_at) = if pam.is_five_prime() {
+                (offset + motif.len(), offset)
+            } else {
+                (offset, offset + spacer)
+            };
+            if !iupac::matches_at(motif, strand_sequence, pam_at) {
+                continue;
+            }
+            let protospacer = &strand_sequence[protospacer_at..protospacer_at + spacer];
+            if !protospacer.iter().all(|base| b"ACGT".contains(base)) {
+                continue;
+            }
+
+            let cut = if pam.is_five_prime() {
+                protospacer_at + pam.cut_offset()
+            } else {
+                protospacer_at + spacer - pam.cut_offset()
+            };
+            let (start, cut_site) = match strand {
+                Strand::Forward => (protospacer_at, cut),
+                Strand::Reverse => (length - protospacer_at - spacer, length - cut),
+            };
+            sites.push(PamSite {
+                strand,
+                start,
+                offset: protospacer_at,
+                protospacer: String::from_utf8_lossy(protospacer).into_owned(),
+                pam: String::from_utf8_lossy(&strand_sequence[pam_at..pam_at + motif.len()])
+                    .into_owned(),
+                cut_site,
+            });
+        }
+        sites
+    }
+
+    fn evaluate(
+        &self,
+        site: &PamSite,
+        strand_sequence: &[u8],
+        sites: &[PamSite],
+        pam: CasPam,
+        params: &GuideDesignParams,
+    ) -> Option<GuideRna> {
+        let spacer = &site.protospacer;
+        let gc = spacer.bytes().filter(|&b| b == b'G' || b == b'C').count();
+        let gc_content = gc as f32 * 100.0 / spacer.len() as f32;
+        if gc_content < params.gc_min || gc_content > params.gc_max {
+            return None;
+        }
+        if params.exclude_poly_t && spacer.contains("TTTT") {
+            return None;
+        }
+
+        let tm = self
+            .calculator
+            .calculate_tm_nearest_neighbor(spacer)
+            .unwrap_or(0.0);
+        let hairpin_delta_g = self
+            .calculator
+            .calculate_enhanced_hairpin(spacer)
+            .map(|hairpin| hairpin.min_score)
+            .unwrap_or(0.0);
+
+        let off_targets: Vec<OffTarget> = sites
+            .iter()
+            .filter(|other| !(other.strand == site.strand && other.start == site.start))
+            .filter_map(|other| self.off_target(spacer, other, pam, params.max_mismatches))
+            .collect();
+        let specificity_score =
+            100.0 / (1.0 + off_targets.iter().map(|hit| hit.score).sum::<f32>());
+
+        let mut warnings = Vec::new();
+        if hairpin_delta_g < params.max_hairpin {
+            warnings.push(format!(
+                "Spacer hairpin ΔG {:.1} kcal/mol may hinder loading",
+                hairpin_delta_g
+            ));
+        }
+        let near_matches = off_targets
+            .iter()
+            .filter(|hit| hit.mismatches <= NEAR_MATCH_MISMATCHES)
+            .count();
+        if near_matches > 0 {
+            warnings.push(format!(
+                "{} off-target site(s) with at most {} mismatch",
+                near_matches, NEAR_MATCH_MISMATCHES
+            ));
+        }
+
+        Some(GuideRna {
+            spacer: spacer.clone(),
+            pam: site.pam.clone(),
+            strand: site.strand,
+            start: site.start,
+            end: site.start + spacer.len(),
+            cut_site: site.cut_site,
+            gc_content,
+            tm,
+            hairpin_delta_g,
+            on_target_score: self.on_target_score(strand_sequence, site.offset, pam, gc),
+            specificity_score,
+            off_targets,
+            warnings,
+        })
+    }
+
+    /// SpCas9 は Rule Set 1、それ以外は位置の重みが使えないので GC 数の項のみで推定する。
+    /// 配列端で文脈が足りない位置は 'N'（どの重みにも一致しない）として扱う
+    fn on_target_score(
+        &self,
+        strand_sequence: &[u8],
+        offset: usize,
+        pam: CasPam,
+        gc: usize,
+    ) -> f32 {
+        let half = pam.spacer_length() / 2;
+        let gc_weight = if gc > half {
+            RULE_SET_1_GC_HIGH
+        } else {
+            RULE_SET_1_GC_LOW
+        };
+        let mut score = RULE_SET_1_INTERCEPT + half.abs_diff(gc) as f64 * gc_weight;
+
+        if pam == CasPam::Ngg {
+            let context: Vec<u8> = (0..CONTEXT_5 + pam.spacer_length() + CONTEXT_3)
+                .map(|i| {
+                    (offset + i)
+                        .checked_sub(CONTEXT_5)
+                        .and_then(|position| strand_sequence.get(position))
+                        .copied()
+                        .unwrap_or(b'N')
+                })
+                .collect();
+            score += RULE_SET_1
+                .iter()
+                .filter(|(position, bases, _)| {
+                    context.get(*position..*position + bases.len()) == Some(bases.as_bytes())
+                })
+                .map(|&(_, _, weight)| weight)
+                .sum::<f64>();
+        }
+        (1.0 / (1.0 + (-score).exp())) as f32
+    }
+
+    /// `other` のプロトスペーサーがガイドと `max_mismatches` 以下で一致すればオフターゲット
+    fn off_target(
+        &self,
+        spacer: &str,
+        other: &PamSite,
+        pam: CasPam,
+        max_mismatches: usize,
+    ) -> Option<OffTarget> {
+        let mut positions = Vec::new();
+        for (index, (a, b)) in spacer.bytes().zip(other.protospacer.bytes()).enumerate() {
+            if a != b {
+                positions.push(index);
+                if positions.len() > max_mismatches {
+                    return None;
+                }
+            }
+        }
+
+        // PAM からの距離で重みを引く（Cas12a は PAM が 5' 側）
+        let length = spacer.len();
+        let distance = |index: usize| {
+            if pam.is_five_prime() {
+                index
+            } else {
+                length - 1 - index
+            }
+        };
+        let mut score: f32 = positions
+            .iter()
+            .map(|&index| {
+                let weight = MISMATCH_WEIGHTS
+                    .len()
+                    .checked_sub(1 + distance(index))
+                    .map_or(0.0, |i| MISMATCH_WEIGHTS[i]);
+                1.0 - weight
+            })
+            .product();
+        if positions.len() > 1 {
+            let span = (positions[positions.len() - 1] - positions[0]) as f32;
+            let mean_distance = span / (positions.len() - 1) as f32;
+            score /= (19.0 - mean_distance) / 19.0 * 4.0 + 1.0;
+        }
+        if !positions.is_empty() {
+            score /= (positions.len() * positions.len()) as f32;
+        }
+
+        Some(OffTarget {
+            start: other.start,
+            end: other.start + other.protospacer.len(),
+            strand: other.strand,
+            protospacer: other.protospacer.clone(),
+            pam: other.pam.clone(),
+            mismatches: positions.len(),
+            mismatch_positions: positions.iter().map(|index| index + 1).collect(),
+            score,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 標的の 20 nt（TGG の PAM が続く）
+    const PROTOSPACER: &str = "GACGTCAGCTAGCATCGATC";
+
+    #[test]
+    fn test_enumerates_guides_on_both_strands() {
+        let sequence = format!("ATATATATAT{}TGGATATATATATA", PROTOSPACER);
+        let service = CrisprDesignService::new();
+        let result = service.design_guides(
+            &sequence,
+            0,
+            sequence.len(),
+            CasPam::Ngg,
+            &GuideDesignParams::default(),
+        );
+
+        let guide = result
+            .guides
+            .iter()
+            .find(|guide| guide.spacer == PROTOSPACER)
+            .unwrap();
+        assert_eq!((guide.start, guide.end, guide.cut_site), (10, 30, 27));
+        assert_eq!((guide.pam.as_str(), guide.strand), ("TGG", Strand::Forward));
+        assert!(guide.on_target_score > 0.0 && guide.on_target_score < 1.0);
+        assert_eq!(guide.specificity_score, 100.0);
+
+        // 逆鎖: CCN の後ろの 20 nt の逆相補
+        let reverse = format!("CCA{}", iupac::reverse_complement(PROTOSPACER));
+        let result = service.design_guides(
+            &reverse,
+            0,
+            reverse.len(),
+            CasPam::Ngg,
+            &GuideDesignParams::default(),
+        );
+        let guide = &result.guides[0];
+        assert_eq!(guide.spacer, PROTOSPACER);
+        assert_eq!(
+            (guide.strand, guide.start, guide.cut_site),
+            (Strand::Reverse, 3, 6)
+        );
+    }
+
+    #[test]
+    fn test_off_targets_lower_specificity() {
+        // PAM から最も遠い塩基だけが異なる部位をもう1つ置く
+        let off_target = format!("T{}", &PROTOSPACER[1..]);
+        let sequence = format!(
+            "ATATATATAT{}TGGATATATATATAAT{}AGGATATA",
+            PROTOSPACER, off_target
+        );
+        let result = CrisprDesignService::new().design_guides(
+            &sequence,
+            0,
+            40,
+            CasPam::Ngg,
+            &GuideDesignParams::default(),
+        );
+
+        let guide = &result.guides[0];
+        assert_eq!(guide.spacer, PROTOSPACER);
+        assert_eq!(guide.off_targets.len(), 1);
+        let hit = &guide.off_targets[0];
+        assert_eq!(
+            (hit.start, hit.mismatches, hit.pam.as_str()),
+            (46, 1, "AGG")
+        );
+        assert_eq!(hit.mismatch_positions, vec![1]);
+        // PAM 遠位のミスマッチは切断をほとんど妨げない
+        assert!((hit.score - 1.0).abs() < 1e-6);
+        assert!((guide.specificity_score - 50.0).abs() < 1e-3);
+        assert_eq!(guide.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_cas12a_pam_is_five_prime() {
+        let spacer = "GACGTCAGCTAGCATCGATCAGC";
+        let sequence = format!("ATATTTTA{}ATATAT", spacer);
+        let result = CrisprDesignService::new().design_guides(
+            &sequence,
+            0,
+            sequence.len(),
+            CasPam::Tttv,
+            &GuideDesignParams::default(),
+        );
+        let guide = result
+            .guides
+            .iter()
+            .find(|guide| guide.spacer == spacer)
+            .unwrap();
+        assert_eq!(
+            (guide.pam.as_str(), guide.start, guide.cut_site),
+            ("TTTA", 8, 26)
+        );
+    }
+}
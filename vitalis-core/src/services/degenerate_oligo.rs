@@ -0,0 +1,226 @@
+// Service layer: Tm and GC content of oligos with degenerate (IUPAC) bases
+use crate::domain::iupac;
+use crate::domain::oligo::DegenerateEstimate;
+use crate::domain::primer::PrimerDesignService;
+use crate::domain::reproducibility::SplitMix64;
+use crate::services::PrimerDesignServiceImpl;
+use thiserror::Error;
+
+/// すべての展開の Tm を計算する上限（超える場合は同数の標本で推定する）
+const MAX_EXPANSIONS: usize = 1024;
+/// 標本の乱数シード（同じ配列なら毎回同じ推定値になる）
+const SAMPLE_SEED: u64 = 0x5EED;
+
+#[derive(Error, Debug)]
+pub enum DegenerateOligoError {
+    #[error("Empty oligo sequence")]
+    EmptySequence,
+    #[error("Unsupported base '{0}' at position {1} (only IUPAC nucleotide codes)")]
+    UnsupportedBase(char, usize),
+}
+
+/// Degenerate oligo Tm/GC service
+pub struct DegenerateOligoService {
+    primer_service: PrimerDesignServiceImpl,
+}
+
+impl Default for DegenerateOligoService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DegenerateOligoService {
+    pub fn new() -> Self {
+        Self {
+            primer_service: PrimerDesignServiceImpl::new(),
+        }
+    }
+
+    /// 縮重塩基を A/C/G/T に展開した配列ごとの最近接法 Tm。展開が多すぎる場合は
+    /// 各位置の塩基を一様に選んだ標本から推定する
+    pub fn tm(&self, sequence: &str) -> Result<DegenerateEstimate, DegenerateOligoError> {
+        let options = expansion_options(sequence)?;
+        let expansions = expansion_count(&options);
+        let exhaustive = expansions <= MAX_EXPANSIONS as u64;
+        let tms: Vec<f32> = if exhaustive {
+            (0..expansions as usize)
+                .map(|index| {
+                    self.primer_service
+                        .calculate_tm(&expansion(&options, index))
+                })
+                .collect()
+        } else {
+            let mut rng = SplitMix64::new(SAMPLE_SEED);
+            (0..MAX_EXPANSIONS)
+                .map(|_| {
+                    let sample: String = options
+                        .iter()
+                        .map(|bases| bases[rng.below(bases.len())] as char)
+                        .collect();
+                    self.primer_service.calculate_tm(&sample)
+                })
+                .collect()
+        };
+
+        let mut estimate = DegenerateEstimate {
+            value: tms.iter().sum::<f32>() / tms.len() as f32,
+            min: tms.iter().copied().fold(f32::INFINITY, f32::min),
+            max: tms.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            degenerate: expansions > 1,
+            degenerate_positions: degenerate_positions(&options),
+            expansions,
+            exhaustive,
+            warnings: Vec::new(),
+        };
+        if estimate.degenerate {
+            estimate.warnings.push(format!(
+                "Degenerate bases at {}: Tm ranges {:.1}-{:.1} °C over the expansions",
+                positions_text(&estimate.degenerate_positions),
+                estimate.min,
+                estimate.max
+            ));
+        }
+        if !exhaustive {
+            estimate.warnings.push(format!(
+                "{} expansions; Tm estimated from {} sampled sequences",
+                expansions, MAX_EXPANSIONS
+            ));
+        }
+        Ok(estimate)
+    }
+
+    /// GC 含量（%）。各位置が G/C になる割合から平均・最小・最大を厳密に求める
+    pub fn gc_content(&self, sequence: &str) -> Result<DegenerateEstimate, DegenerateOligoError> {
+        let options = expansion_options(sequence)?;
+        let (mut mean, mut min, mut max) = (0.0f32, 0usize, 0usize);
+        for bases in &options {
+            let gc = bases.iter().filter(|&&b| b == b'G' || b == b'C').count();
+            mean += gc as f32 / bases.len() as f32;
+            min += usize::from(gc == bases.len());
+            max += usize::from(gc > 0);
+        }
+        let percent = |count: f32| count / options.len() as f32 * 100.0;
+
+        let expansions = expansion_count(&options);
+        let mut estimate = DegenerateEstimate {
+            value: percent(mean),
+            min: percent(min as f32),
+            max: percent(max as f32),
+            degenerate: expansions > 1,
+            degenerate_positions: degenerate_positions(&options),
+            expansions,
+            exhaustive: true,
+            warnings: Vec::new(),
+        };
+        if estimate.min < estimate.max {
+            estimate.warnings.push(format!(
+                "Degenerate bases at {}: GC content ranges {:.1}-{:.1}%",
+                positions_text(&estimate.degenerate_positions),
+                estimate.min,
+                estimate.max
+            ));
+        }
+        Ok(estimate)
+    }
+}
+
+/// 各位置の取りうる塩基（空白は無視する）
+fn expansion_options(sequence: &str) -> Result<Vec<&'static [u8]>, DegenerateOligoError> {
+    let options = sequence
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .enumerate()
+        .map(|(i, c)| match iupac::expand(c) {
+            "" => Err(DegenerateOligoError::UnsupportedBase(c, i + 1)),
+            bases => Ok(bases.as_bytes()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if options.is_empty() {
+        return Err(DegenerateOligoError::EmptySequence);
+    }
+    Ok(options)
+}
+
+fn expansion_count(options: &[&[u8]]) -> u64 {
+    options.iter().fold(1u64, |count, bases| {
+        count.saturating_mul(bases.len() as u64)
+    })
+}
+
+/// `index` 番目の展開（最後の位置が最も速く変わる）
+fn expansion(options: &[&[u8]], mut index: usize) -> String {
+    let mut bases = vec![0u8; options.len()];
+    for (base, choices) in bases.iter_mut().zip(options).rev() {
+        *base = choices[index % choices.len()];
+        index /= choices.len();
+    }
+    String::from_utf8(bases).unwrap_or_default()
+}
+
+fn degenerate_positions(options: &[&[u8]]) -> Vec<usize> {
+    options
+        .iter()
+        .enumerate()
+        .filter(|(_, bases)| bases.len() > 1)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// 1-based の位置の一覧
+fn positions_text(positions: &[usize]) -> String {
+    positions
+        .iter()
+        .map(|p| (p + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tm_over_expansions() {
+        let service = DegenerateOligoService::new();
+        let tm = |sequence: &str| service.primer_service.calculate_tm(sequence);
+
+        let plain = service.tm("ATGCGTACGTTAGCCTAGCA").unwrap();
+        assert!(!plain.degenerate && plain.warnings.is_empty());
+        assert_eq!(plain.value, tm("ATGCGTACGTTAGCCTAGCA"));
+
+        // R = A/G
+        let degenerate = service.tm("ATGCGTACRTTAGCCTAGCA").unwrap();
+        let (a, g) = (tm("ATGCGTACATTAGCCTAGCA"), tm("ATGCGTACGTTAGCCTAGCA"));
+        assert_eq!(degenerate.expansions, 2);
+        assert_eq!(degenerate.degenerate_positions, vec![8]);
+        assert_eq!((degenerate.min, degenerate.max), (a.min(g), a.max(g)));
+        assert!((degenerate.value - (a + g) / 2.0).abs() < 1e-4);
+        assert!(degenerate.warnings[0].contains("at 9"));
+
+        // 4^8 通りは標本で推定する（同じ配列なら同じ値）
+        let sampled = service.tm("ATGCGTACNNNNNNNNTAGC").unwrap();
+        assert_eq!(sampled.expansions, 65536);
+        assert!(!sampled.exhaustive && sampled.min < sampled.max);
+        assert_eq!(sampled, service.tm("ATGCGTACNNNNNNNNTAGC").unwrap());
+
+        assert!(matches!(
+            service.tm("ATGXC"),
+            Err(DegenerateOligoError::UnsupportedBase('X', 4))
+        ));
+    }
+
+    #[test]
+    fn test_gc_content_range() {
+        let service = DegenerateOligoService::new();
+        let gc = service.gc_content("ACGN").unwrap();
+        assert_eq!((gc.min, gc.max, gc.value), (50.0, 75.0, 62.5));
+        assert_eq!(gc.expansions, 4);
+
+        // S は常に G/C なので範囲はない
+        let strong = service.gc_content("ACGS").unwrap();
+        assert!(strong.degenerate && strong.warnings.is_empty());
+        assert_eq!((strong.min, strong.max), (75.0, 75.0));
+        assert!(service.gc_content(" ").is_err());
+    }
+}
@@ -0,0 +1,439 @@
+// Service layer: Bench protocols for restriction digests
+use crate::domain::export_format::{ExportFormat, Unit};
+use crate::domain::restriction::{
+    CutterClass, DigestProtocol, DigestProtocolParams, EnzymeDose, HeatInactivation,
+    IncubationStep, Methylation, ReactionComponent, RestrictionEnzyme,
+};
+use crate::domain::Topology;
+use crate::services::restriction::digest_bands;
+use crate::services::RestrictionAnalysisService;
+use thiserror::Error;
+
+/// 酵素液（50% グリセロール）の合計を反応液の1割以下に抑え、グリセロールを 5% 未満にする
+const MAX_ENZYME_FRACTION: f64 = 0.1;
+/// 10X バッファーの割合
+const BUFFER_FRACTION: f64 = 0.1;
+/// 分注の分解能（1 µL あたりの刻み数、0.1 µL 単位）
+const PIPETTE_STEPS_PER_UL: f64 = 10.0;
+/// 反応時間の丸め単位（min）
+const TIME_STEP_MIN: f64 = 5.0;
+
+#[derive(Error, Debug)]
+pub enum DigestProtocolError {
+    #[error(
+        "Reaction volume of {volume} µL cannot hold {required:.1} µL of DNA, buffer and enzymes"
+    )]
+    VolumeTooSmall { volume: f64, required: f64 },
+}
+
+/// Digest protocol service
+pub struct DigestProtocolService {
+    restriction_service: RestrictionAnalysisService,
+    format: ExportFormat,
+}
+
+impl Default for DigestProtocolService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DigestProtocolService {
+    pub fn new() -> Self {
+        Self {
+            restriction_service: RestrictionAnalysisService::new(),
+            format: ExportFormat::default(),
+        }
+    }
+
+    /// `enzymes` で `sequence` を消化する反応を組み立てる。酵素量は DNA 量に比例させ、
+    /// 酵素液が反応液の1割を超える場合は量を減らして反応時間を延ばす
+    /// （配列IDは呼び出し側で設定する）
+    pub fn plan(
+        &self,
+        name: &str,
+        sequence: &str,
+        topology: &Topology,
+        enzymes: &[&RestrictionEnzyme],
+        params: &DigestProtocolParams,
+    ) -> Result<DigestProtocol, DigestProtocolError> {
+        let map = self
+            .restriction_service
+            .restriction_map(sequence, topology, enzymes);
+        let mut cuts: Vec<usize> = map
+            .digests
+            .iter()
+            .flat_map(|digest| digest.cut_positions.iter().copied())
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        let fragments = digest_bands(&cuts, map.length, *topology == Topology::Circular);
+
+        // 1 U·h で 1 µg を切るので、量を減らした分だけ時間を延ばす
+        let required_units = params.dna_amount_ug * params.units_per_ug;
+        let volumes: Vec<f64> = enzymes
+            .iter()
+            .map(|enzyme| round_up(required_units / enzyme.conditions.units_per_ul))
+            .collect();
+        let enzyme_cap = params.reaction_volume_ul * MAX_ENZYME_FRACTION;
+        let total: f64 = volumes.iter().sum();
+        let scale = if total > enzyme_cap {
+            enzyme_cap / total
+        } else {
+            1.0
+        };
+        let doses: Vec<EnzymeDose> = enzymes
+            .iter()
+            .zip(&volumes)
+            .map(|(enzyme, &volume)| {
+                let volume_ul = round_down(volume * scale).max(1.0 / PIPETTE_STEPS_PER_UL);
+                let units = round_nearest(volume_ul * enzyme.conditions.units_per_ul);
+                let minutes = params.incubation_minutes * (required_units / units).max(1.0);
+                EnzymeDose {
+                    enzyme: enzyme.name.clone(),
+                    volume_ul,
+                    units,
+                    minutes: (minutes / TIME_STEP_MIN).ceil() * TIME_STEP_MIN,
+                }
+            })
+            .collect();
+
+        let dna_ul = round_nearest(params.dna_amount_ug * 1000.0 / params.dna_concentration_ng_ul);
+        let buffer_ul = params.reaction_volume_ul * BUFFER_FRACTION;
+        let enzyme_ul: f64 = doses.iter().map(|dose| dose.volume_ul).sum();
+        let water_ul = round_nearest(params.reaction_volume_ul - dna_ul - buffer_ul - enzyme_ul);
+        if water_ul < 0.0 {
+            return Err(DigestProtocolError::VolumeTooSmall {
+                volume: params.reaction_volume_ul,
+                required: dna_ul + buffer_ul + enzyme_ul,
+            });
+        }
+        let mut components = vec![
+            ReactionComponent {
+                name: "Nuclease-free water".to_string(),
+                volume_ul: water_ul,
+            },
+            ReactionComponent {
+                name: "10X reaction buffer".to_string(),
+                volume_ul: buffer_ul,
+            },
+            ReactionComponent {
+                name: format!(
+                    "DNA ({} µg at {} ng/µL)",
+                    params.dna_amount_ug, params.dna_concentration_ng_ul
+                ),
+                volume_ul: dna_ul,
+            },
+        ];
+        components.extend(doses.iter().map(|dose| ReactionComponent {
+            name: format!("{} ({} U)", dose.enzyme, dose.units),
+            volume_ul: dose.volume_ul,
+        }));
+
+        // 温度の低い酵素から順に反応させる
+        let mut temperatures: Vec<f64> = enzymes
+            .iter()
+            .map(|enzyme| enzyme.conditions.incubation_temp)
+            .collect();
+        temperatures.sort_by(f64::total_cmp);
+        temperatures.dedup();
+        let incubation: Vec<IncubationStep> = temperatures
+            .into_iter()
+            .map(|temperature| {
+                let (names, minutes): (Vec<String>, Vec<f64>) = enzymes
+                    .iter()
+                    .zip(&doses)
+                    .filter(|(enzyme, _)| enzyme.conditions.incubation_temp == temperature)
+                    .map(|(enzyme, dose)| (enzyme.name.clone(), dose.minutes))
+                    .unzip();
+                IncubationStep {
+                    temperature,
+                    minutes: minutes.into_iter().fold(0.0, f64::max),
+                    enzymes: names,
+                }
+            })
+            .collect();
+
+        let heat_inactivation = enzymes
+            .iter()
+            .map(|enzyme| enzyme.conditions.heat_inactivation)
+            .collect::<Option<Vec<HeatInactivation>>>()
+            .and_then(|conditions| {
+                conditions.into_iter().reduce(|a, b| HeatInactivation {
+                    temperature: a.temperature.max(b.temperature),
+                    minutes: a.minutes.max(b.minutes),
+                })
+            });
+
+        let mut warnings = Vec::new();
+        for digest in &map.digests {
+            if digest.cutter == CutterClass::NonCutter {
+                warnings.push(format!("{} does not cut {}", digest.enzyme, name));
+            }
+            if digest.blocked {
+                let mut methylation: Vec<&str> = map
+                    .sites
+                    .iter()
+                    .filter(|site| site.enzyme == digest.enzyme)
+                    .flat_map(|site| site.blocked_by.iter())
+                    .map(|blocked_by| match blocked_by {
+                        Methylation::Dam => "dam-",
+                        Methylation::Dcm => "dcm-",
+                    })
+                    .collect();
+                methylation.sort_unstable();
+                methylation.dedup();
+                warnings.push(format!(
+                    "{} is blocked by host methylation at some sites; prepare the DNA from a {} strain",
+                    digest.enzyme,
+                    methylation.join("/")
+                ));
+            }
+        }
+        if scale < 1.0 {
+            warnings.push(format!(
+                "Enzymes limited to {} µL (10% of the reaction) to keep glycerol below 5%; incubation extended",
+                enzyme_cap
+            ));
+        }
+        if heat_inactivation.is_none() {
+            let names: Vec<&str> = enzymes
+                .iter()
+                .filter(|enzyme| enzyme.conditions.heat_inactivation.is_none())
+                .map(|enzyme| enzyme.name.as_str())
+                .collect();
+            warnings.push(format!(
+                "{} cannot be heat-inactivated; purify the DNA before downstream steps",
+                names.join(", ")
+            ));
+        }
+
+        let mut protocol = DigestProtocol {
+            map,
+            params: params.clone(),
+            doses,
+            components,
+            incubation,
+            heat_inactivation,
+            fragments,
+            warnings,
+            text: String::new(),
+        };
+        protocol.text = self.text(name, &protocol);
+        Ok(protocol)
+    }
+
+    /// 手順書（反応液の組成、反応条件、予想される断片、注意点）
+    fn text(&self, name: &str, protocol: &DigestProtocol) -> String {
+        let format = &self.format;
+        let topology = match protocol.map.topology {
+            Topology::Circular => "circular",
+            Topology::Linear => "linear",
+        };
+        let mut lines = vec![
+            format!(
+                "Restriction digest of {} ({}, {})",
+                name,
+                format.quantity(protocol.map.length as f64, Unit::BasePairs),
+                topology
+            ),
+            format!(
+                "Enzymes: {}",
+                protocol
+                    .map
+                    .digests
+                    .iter()
+                    .map(|digest| {
+                        let sites = digest.cut_positions.len();
+                        format!(
+                            "{} ({} site{})",
+                            digest.enzyme,
+                            sites,
+                            if sites == 1 { "" } else { "s" }
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            String::new(),
+            format!("Reaction ({} µL)", protocol.params.reaction_volume_ul),
+        ];
+        lines.extend(
+            protocol
+                .components
+                .iter()
+                .map(|component| format!("  {}: {:.1} µL", component.name, component.volume_ul)),
+        );
+
+        lines.push(String::new());
+        lines.push("Incubation".to_string());
+        for (step, incubation) in protocol.incubation.iter().enumerate() {
+            lines.push(format!(
+                "  {}. {} for {} min: {}",
+                step + 1,
+                format.quantity(incubation.temperature, Unit::Celsius),
+                incubation.minutes,
+                incubation.enzymes.join(", ")
+            ));
+        }
+        if let Some(inactivation) = &protocol.heat_inactivation {
+            lines.push(format!(
+                "  {}. Heat-inactivate at {} for {} min",
+                protocol.incubation.len() + 1,
+                format.quantity(inactivation.temperature, Unit::Celsius),
+                inactivation.minutes
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "Expected fragments: {}",
+            protocol
+                .fragments
+                .iter()
+                .map(|&fragment| format.quantity(fragment as f64, Unit::BasePairs))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        if !protocol.warnings.is_empty() {
+            lines.push(String::new());
+            lines.push("Notes".to_string());
+            lines.extend(
+                protocol
+                    .warnings
+                    .iter()
+                    .map(|warning| format!("  - {}", warning)),
+            );
+        }
+
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+}
+
+fn round_up(volume: f64) -> f64 {
+    (volume * PIPETTE_STEPS_PER_UL - 1e-9).ceil() / PIPETTE_STEPS_PER_UL
+}
+
+fn round_down(volume: f64) -> f64 {
+    (volume * PIPETTE_STEPS_PER_UL + 1e-9).floor() / PIPETTE_STEPS_PER_UL
+}
+
+fn round_nearest(volume: f64) -> f64 {
+    (volume * PIPETTE_STEPS_PER_UL).round() / PIPETTE_STEPS_PER_UL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enzymes(names: &[&str]) -> Vec<RestrictionEnzyme> {
+        let service = RestrictionAnalysisService::new();
+        names
+            .iter()
+            .map(|name| service.find_enzyme(name).unwrap().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_double_digest_protocol() {
+        // EcoRI（上鎖 11 で切断）と HindIII（上鎖 41 で切断）
+        let sequence = format!(
+            "{}GAATTC{}AAGCTT{}",
+            "A".repeat(10),
+            "C".repeat(24),
+            "T".repeat(60)
+        );
+        let enzymes = enzymes(&["EcoRI", "HindIII"]);
+        let refs: Vec<&RestrictionEnzyme> = enzymes.iter().collect();
+        let protocol = DigestProtocolService::new()
+            .plan(
+                "pTest",
+                &sequence,
+                &Topology::Circular,
+                &refs,
+                &DigestProtocolParams::default(),
+            )
+            .unwrap();
+
+        assert_eq!(protocol.fragments, vec![76, 30]);
+        assert_eq!(protocol.doses[0].volume_ul, 0.5);
+        assert_eq!(protocol.doses[0].units, 10.0);
+        assert_eq!(protocol.components[0].volume_ul, 34.0);
+        assert_eq!(protocol.incubation.len(), 1);
+        assert_eq!(protocol.incubation[0].minutes, 60.0);
+        assert_eq!(
+            protocol.heat_inactivation,
+            Some(HeatInactivation {
+                temperature: 80.0,
+                minutes: 20
+            })
+        );
+        assert!(protocol.warnings.is_empty());
+        assert_eq!(
+            protocol.text,
+            "Restriction digest of pTest (106 bp, circular)\n\
+             Enzymes: EcoRI (1 site), HindIII (1 site)\n\
+             \n\
+             Reaction (50 µL)\n\
+             \x20 Nuclease-free water: 34.0 µL\n\
+             \x20 10X reaction buffer: 5.0 µL\n\
+             \x20 DNA (1 µg at 100 ng/µL): 10.0 µL\n\
+             \x20 EcoRI (10 U): 0.5 µL\n\
+             \x20 HindIII (10 U): 0.5 µL\n\
+             \n\
+             Incubation\n\
+             \x20 1. 37.0 °C for 60 min: EcoRI, HindIII\n\
+             \x20 2. Heat-inactivate at 80.0 °C for 20 min\n\
+             \n\
+             Expected fragments: 76 bp, 30 bp\n"
+        );
+    }
+
+    #[test]
+    fn test_sequential_temperatures_and_capped_enzyme_volume() {
+        let sequence = format!(
+            "{}CCCGGG{}TGATCA{}",
+            "A".repeat(20),
+            "C".repeat(20),
+            "A".repeat(20)
+        );
+        let enzymes = enzymes(&["BclI", "SmaI"]);
+        let refs: Vec<&RestrictionEnzyme> = enzymes.iter().collect();
+        let params = DigestProtocolParams {
+            dna_amount_ug: 5.0,
+            dna_concentration_ng_ul: 500.0,
+            ..DigestProtocolParams::default()
+        };
+        let protocol = DigestProtocolService::new()
+            .plan("insert", &sequence, &Topology::Linear, &refs, &params)
+            .unwrap();
+
+        // BclI 5 µL + SmaI 2.5 µL を 5 µL に抑え、BclI の時間を延ばす
+        assert_eq!(protocol.doses[0].volume_ul, 3.3);
+        assert_eq!(protocol.doses[0].minutes, 95.0);
+        let steps: Vec<(f64, &str)> = protocol
+            .incubation
+            .iter()
+            .map(|step| (step.temperature, step.enzymes[0].as_str()))
+            .collect();
+        assert_eq!(steps, vec![(25.0, "SmaI"), (50.0, "BclI")]);
+        assert_eq!(protocol.heat_inactivation, None);
+        assert!(protocol.warnings.iter().any(|w| w.contains("dam- strain")));
+        assert!(protocol
+            .warnings
+            .iter()
+            .any(|w| w.contains("10% of the reaction")));
+        assert!(protocol.text.contains("BclI cannot be heat-inactivated"));
+
+        let tiny = DigestProtocolParams {
+            reaction_volume_ul: 10.0,
+            dna_concentration_ng_ul: 100.0,
+            ..params
+        };
+        assert!(DigestProtocolService::new()
+            .plan("insert", &sequence, &Topology::Linear, &refs, &tiny)
+            .is_err());
+    }
+}
@@ -0,0 +1,119 @@
+// Service layer: Oligo resuspension and dilution volumes
+use crate::domain::dilution::{DilutionSeries, DilutionStep, OligoResuspension};
+
+/// 分注の分解能（1 µL あたりの刻み数、0.1 µL 単位）
+const PIPETTE_STEPS_PER_UL: f64 = 10.0;
+/// 1段階で持ち込む液量の下限（µL）
+const MIN_TRANSFER_UL: f64 = 1.0;
+/// 1段階の希釈倍率の上限
+const MAX_STEP_FOLD: f64 = 100.0;
+/// 浮動小数点の誤差で余計な中間希釈を挟まないための許容差
+const FOLD_TOLERANCE: f64 = 1e-9;
+
+/// Dilution service
+pub struct DilutionService;
+
+impl Default for DilutionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DilutionService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `nmoles` nmol の乾燥オリゴを `concentration_um` µM にする液量
+    /// （nmol ÷ µM = mL）
+    pub fn resuspend(&self, nmoles: f64, concentration_um: f64) -> OligoResuspension {
+        OligoResuspension {
+            nmoles,
+            concentration_um,
+            volume_ul: round_nearest(nmoles / concentration_um * 1000.0),
+        }
+    }
+
+    /// `stock` から `target` の液を `volume_ul` µL 作る手順。最終段の持ち込み量が
+    /// 1 µL 未満になる間は、10 の累乗倍（最大 100 倍）の中間希釈を先に行う
+    pub fn dilution_series(&self, stock: f64, target: f64, volume_ul: f64) -> DilutionSeries {
+        let max_fold = (volume_ul / MIN_TRANSFER_UL).min(MAX_STEP_FOLD);
+        let intermediate_fold = 10f64.powi(max_fold.log10().floor() as i32);
+
+        let mut steps = Vec::new();
+        let mut concentration = stock;
+        let mut remaining = stock / target;
+        while remaining > max_fold * (1.0 + FOLD_TOLERANCE) {
+            steps.push(self.step(concentration, intermediate_fold, volume_ul));
+            concentration /= intermediate_fold;
+            remaining /= intermediate_fold;
+        }
+        steps.push(self.step(concentration, remaining, volume_ul));
+
+        DilutionSeries {
+            stock,
+            target,
+            final_volume_ul: volume_ul,
+            steps,
+        }
+    }
+
+    fn step(&self, source_concentration: f64, fold: f64, volume_ul: f64) -> DilutionStep {
+        let transfer_ul = round_nearest(volume_ul / fold);
+        DilutionStep {
+            source_concentration,
+            transfer_ul,
+            diluent_ul: round_nearest(volume_ul - transfer_ul),
+            final_volume_ul: volume_ul,
+            concentration: source_concentration * transfer_ul / volume_ul,
+            fold,
+        }
+    }
+}
+
+fn round_nearest(volume: f64) -> f64 {
+    (volume * PIPETTE_STEPS_PER_UL).round() / PIPETTE_STEPS_PER_UL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resuspend_to_100_um() {
+        let resuspension = DilutionService::new().resuspend(25.3, 100.0);
+        assert_eq!(resuspension.volume_ul, 253.0);
+    }
+
+    #[test]
+    fn test_dilution_series_adds_intermediate_steps() {
+        let service = DilutionService::new();
+
+        // 10 µM を 100 µL: 10 µL + 90 µL の1段階
+        let series = service.dilution_series(100.0, 10.0, 100.0);
+        assert_eq!(series.steps.len(), 1);
+        assert_eq!(
+            (series.steps[0].transfer_ul, series.steps[0].diluent_ul),
+            (10.0, 90.0)
+        );
+
+        // 100 µM → 20 nM（5000 倍）: 100 倍で 1 µM にしてから 50 倍
+        let series = service.dilution_series(100.0, 0.02, 100.0);
+        let volumes: Vec<(f64, f64)> = series
+            .steps
+            .iter()
+            .map(|step| (step.transfer_ul, step.diluent_ul))
+            .collect();
+        assert_eq!(volumes, vec![(1.0, 99.0), (2.0, 98.0)]);
+        assert!((series.steps[1].concentration - 0.02).abs() < 1e-12);
+
+        // 20 µL では 1 段階あたり最大 20 倍なので 10 倍を挟む
+        let series = service.dilution_series(100.0, 1.0, 20.0);
+        let folds: Vec<f64> = series.steps.iter().map(|step| step.fold).collect();
+        assert_eq!(folds, vec![10.0, 10.0]);
+        assert_eq!(
+            (series.steps[1].transfer_ul, series.steps[1].diluent_ul),
+            (2.0, 18.0)
+        );
+    }
+}
@@ -0,0 +1,346 @@
+// Service layer: Dot plots seeded with exact k-mer matches
+use crate::domain::dotplot::{
+    seed_length, DotPlotHit, DotPlotMatches, MAX_HITS, MAX_SEEDS, MAX_SEEDS_PER_KMER,
+};
+use crate::domain::index::encode_base;
+
+/// ドットプロットのサービス
+pub struct DotPlotService;
+
+impl Default for DotPlotService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DotPlotService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `a` と `b` の `window` 塩基のウィンドウの組のうち `threshold` 塩基以上一致するもの
+    /// （同じ向きと逆相補）を、同じ対角線で続く組をまとめた線分として返す。
+    /// 両配列の k-mer を整列して突き合わせた完全一致を種とし、種を含むウィンドウだけを
+    /// 対角線に沿って数える。種が MAX_SEEDS_PER_KMER を超える k-mer（低複雑度の繰り返し）は
+    /// 使わない。ACGT 以外の塩基は一致とみなさない
+    pub fn compare(&self, a: &str, b: &str, window: usize, threshold: usize) -> DotPlotMatches {
+        let threshold = threshold.min(window);
+        let k = seed_length(window, threshold);
+        let mut matches = DotPlotMatches::default();
+        if k == 0 || a.len() < window || b.len() < window {
+            return matches;
+        }
+
+        let a = encode(a, NOT_ACGT_A);
+        let b = encode(b, NOT_ACGT_B);
+        // 逆相補の対角線では B の相補塩基と比べる
+        let b_complement: Vec<u8> = b
+            .iter()
+            .map(|&bits| if bits < 4 { 3 - bits } else { bits })
+            .collect();
+
+        let mut kmers_a = Vec::with_capacity(a.len());
+        let mut kmers_a_reverse = Vec::with_capacity(a.len());
+        for_each_kmer(&a, k, |i, code, reverse| {
+            kmers_a.push((code, i));
+            kmers_a_reverse.push((reverse, i));
+        });
+        let mut kmers_b = Vec::with_capacity(b.len());
+        for_each_kmer(&b, k, |j, code, _| kmers_b.push((code, j)));
+        kmers_a.sort_unstable();
+        kmers_a_reverse.sort_unstable();
+        kmers_b.sort_unstable();
+
+        // 種は対角線の番号と A 側の位置
+        let mut seeds: Vec<(i64, usize)> = Vec::new();
+        for reverse in [false, true] {
+            let kmers = if reverse { &kmers_a_reverse } else { &kmers_a };
+            seeds.clear();
+            join(kmers, &kmers_b, |group_a, group_b| {
+                if group_a.len() * group_b.len() > MAX_SEEDS_PER_KMER {
+                    matches.masked_kmers += 1;
+                    return true;
+                }
+                for &(_, i) in group_a {
+                    for &(_, j) in group_b {
+                        seeds.push(if reverse {
+                            ((i + j + k - 1) as i64, i)
+                        } else {
+                            (j as i64 - i as i64, i)
+                        });
+                    }
+                }
+                seeds.len() <= MAX_SEEDS
+            });
+            if seeds.len() > MAX_SEEDS {
+                matches.truncated = true;
+            }
+            seeds.sort_unstable();
+
+            for seeds in seeds.chunk_by(|x, y| x.0 == y.0) {
+                let diagonal = Diagonal {
+                    a: &a,
+                    b: if reverse { &b_complement } else { &b },
+                    window,
+                    threshold,
+                    key: seeds[0].0,
+                    reverse,
+                };
+                // 種を含むウィンドウの開始位置の区間（重なる・隣り合うものはまとめる）
+                let mut span: Option<(usize, usize)> = None;
+                for &(_, i) in seeds {
+                    let (start, end) = ((i + k).saturating_sub(window), i);
+                    span = match span {
+                        Some((first, last)) if start <= last + 1 => Some((first, last.max(end))),
+                        Some(previous) => {
+                            diagonal.scan(previous, &mut matches.hits);
+                            Some((start, end))
+                        }
+                        None => Some((start, end)),
+                    };
+                }
+                if let Some(span) = span {
+                    diagonal.scan(span, &mut matches.hits);
+                }
+                if matches.hits.len() > MAX_HITS {
+                    matches.hits.truncate(MAX_HITS);
+                    matches.truncated = true;
+                    break;
+                }
+            }
+        }
+        matches
+            .hits
+            .sort_by_key(|hit| (hit.x, hit.y, hit.reverse_complement));
+        matches
+    }
+}
+
+/// 2ビット符号にできない塩基の値（A 側と B 側で変えて一致させない）
+const NOT_ACGT_A: u8 = 4;
+const NOT_ACGT_B: u8 = 5;
+
+/// 塩基を2ビット符号（A=0, C=1, G=2, T/U=3）の列にする
+fn encode(sequence: &str, not_acgt: u8) -> Vec<u8> {
+    sequence
+        .bytes()
+        .map(|base| encode_base(base).map_or(not_acgt, |bits| bits as u8))
+        .collect()
+}
+
+/// 1本の対角線。A の位置 u に向き合う B の位置は、順鎖なら u + key、逆相補なら key - u
+struct Diagonal<'a> {
+    /// 2ビット符号の A と B（逆相補の対角線では B の相補）
+    a: &'a [u8],
+    b: &'a [u8],
+    window: usize,
+    threshold: usize,
+    key: i64,
+    reverse: bool,
+}
+
+impl Diagonal<'_> {
+    /// A の位置 u の塩基が向き合う B の塩基と一致する（逆相補なら相補的）か。
+    /// u は向き合う B のウィンドウが配列内にある範囲に限る
+    fn pair(&self, u: usize) -> bool {
+        let v = if self.reverse {
+            self.key - u as i64
+        } else {
+            self.key + u as i64
+        };
+        self.a[u] == self.b[v as usize]
+    }
+
+    /// B のウィンドウが配列内に収まる A 側の開始位置の範囲
+    fn bounds(&self) -> (i64, i64) {
+        let (window, b_len) = (self.window as i64, self.b.len() as i64);
+        if self.reverse {
+            // y = key - x - window + 1 が 0..=b_len - window
+            (self.key - b_len + 1, self.key - window + 1)
+        } else {
+            // y = key + x が 0..=b_len - window
+            (-self.key, b_len - window - self.key)
+        }
+    }
+
+    /// A 側の開始位置が x のウィンドウに向き合う B 側のウィンドウの開始位置
+    fn y(&self, x: usize) -> usize {
+        let y = if self.reverse {
+            // A のウィンドウの左端は B のウィンドウの右端に向き合う
+            self.key - (x + self.window - 1) as i64
+        } else {
+            self.key + x as i64
+        };
+        y as usize
+    }
+
+    /// 開始位置が [first, last] のウィンドウの一致数を対角線に沿って数え、
+    /// 閾値以上のウィンドウが続く区間を線分として加える
+    fn scan(&self, (first, last): (usize, usize), hits: &mut Vec<DotPlotHit>) {
+        let (low, high) = self.bounds();
+        let first = (first as i64).max(low).max(0) as usize;
+        let last = (last as i64)
+            .min(high)
+            .min((self.a.len() - self.window) as i64);
+        if last < first as i64 {
+            return;
+        }
+        let last = last as usize;
+        let mut count = (first..first + self.window)
+            .filter(|&u| self.pair(u))
+            .count();
+        let mut run: Option<(usize, usize)> = None;
+        for x in first..=last {
+            if x > first {
+                count = count + usize::from(self.pair(x + self.window - 1))
+                    - usize::from(self.pair(x - 1));
+            }
+            let matched = count >= self.threshold;
+            run = match (run, matched) {
+                (Some((start, _)), true) => Some((start, x)),
+                (None, true) => Some((x, x)),
+                (Some(finished), false) => {
+                    self.push(finished, hits);
+                    None
+                }
+                (None, false) => None,
+            };
+        }
+        if let Some(finished) = run {
+            self.push(finished, hits);
+        }
+    }
+
+    fn push(&self, (start, end): (usize, usize), hits: &mut Vec<DotPlotHit>) {
+        // 逆相補では A の右端のウィンドウが B の左端に向き合う
+        hits.push(DotPlotHit {
+            x: start,
+            y: self.y(if self.reverse { end } else { start }),
+            length: end - start + self.window,
+            reverse_complement: self.reverse,
+        });
+    }
+}
+
+/// ACGT だけからなる k-mer ごとに、開始位置・2ビット符号・逆相補の符号を渡す
+/// （`sequence` は2ビット符号の列）
+fn for_each_kmer(sequence: &[u8], k: usize, mut visit: impl FnMut(usize, u64, u64)) {
+    let mask = if k == 32 {
+        u64::MAX
+    } else {
+        (1 << (2 * k)) - 1
+    };
+    let (mut code, mut reverse, mut valid) = (0u64, 0u64, 0usize);
+    for (position, &bits) in sequence.iter().enumerate() {
+        if bits < 4 {
+            let bits = bits as u64;
+            code = ((code << 2) | bits) & mask;
+            reverse = (reverse >> 2) | ((3 - bits) << (2 * (k - 1)));
+            valid += 1;
+        } else {
+            valid = 0;
+        }
+        if valid >= k {
+            visit(position + 1 - k, code, reverse);
+        }
+    }
+}
+
+/// 符号順に整列した2つの k-mer 列で、同じ符号の組ごとに `visit` を呼ぶ（false を返したら打ち切る）
+fn join(
+    a: &[(u64, usize)],
+    b: &[(u64, usize)],
+    mut visit: impl FnMut(&[(u64, usize)], &[(u64, usize)]) -> bool,
+) {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let code = a[i].0;
+        if code < b[j].0 {
+            i += 1;
+        } else if code > b[j].0 {
+            j += 1;
+        } else {
+            let end_a = i + a[i..].partition_point(|&(c, _)| c == code);
+            let end_b = j + b[j..].partition_point(|&(c, _)| c == code);
+            if !visit(&a[i..end_a], &b[j..end_b]) {
+                return;
+            }
+            (i, j) = (end_a, end_b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::iupac;
+
+    #[test]
+    fn test_forward_and_reverse_complement_runs() {
+        let service = DotPlotService::new();
+        let insert = "GATTACAGGCTTCAGGATCCATGCAAGT";
+        let a = format!("CCCCCCCCCC{}CCCCCCCCCC", insert);
+        let b = format!(
+            "TTTTTTTTTT{}TTTTT{}TTTTTTTTTT",
+            insert,
+            iupac::reverse_complement(insert)
+        );
+
+        let matches = service.compare(&a, &b, 12, 12);
+        assert!(!matches.truncated);
+        assert_eq!(
+            matches.hits,
+            vec![
+                DotPlotHit {
+                    x: 10,
+                    y: 10,
+                    length: insert.len(),
+                    reverse_complement: false,
+                },
+                DotPlotHit {
+                    x: 10,
+                    y: 10 + insert.len() + 5,
+                    length: insert.len(),
+                    reverse_complement: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mismatched_windows_are_found_from_shorter_seeds() {
+        let service = DotPlotService::new();
+        let a = "ACGTTGCAAGGCTTACCGATGACTGA";
+        // 8 塩基目と 17 塩基目を置換
+        let b = "ACGTTGCTAGGCTTACCCATGACTGA";
+
+        assert!(service.compare(a, b, 20, 20).hits.is_empty());
+        assert_eq!(
+            service.compare(a, b, 20, 18).hits,
+            vec![DotPlotHit {
+                x: 0,
+                y: 0,
+                length: a.len(),
+                reverse_complement: false,
+            }]
+        );
+        let reverse = service
+            .compare(a, &iupac::reverse_complement(b), 20, 18)
+            .hits;
+        assert_eq!(
+            reverse,
+            vec![DotPlotHit {
+                x: 0,
+                y: 0,
+                length: a.len(),
+                reverse_complement: true,
+            }]
+        );
+
+        // 低複雑度の繰り返しは種にしない
+        let poly_a = "A".repeat(500);
+        let masked = service.compare(&poly_a, &poly_a, 20, 20);
+        assert!(masked.hits.is_empty());
+        assert_eq!(masked.masked_kmers, 1);
+    }
+}
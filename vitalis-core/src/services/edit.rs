@@ -0,0 +1,303 @@
+// Service layer: Sequence editing with per-sequence undo/redo history
+use crate::domain::edit::{EditState, SequenceEdit};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::iupac;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum EditError {
+    #[error("Invalid edit range {start}..{end} for sequence of length {length}")]
+    OutOfRange {
+        start: usize,
+        end: usize,
+        length: usize,
+    },
+    #[error("Invalid bases: {0}")]
+    InvalidBases(String),
+    #[error("Nothing to undo for {0}")]
+    NothingToUndo(String),
+    #[error("Nothing to redo for {0}")]
+    NothingToRedo(String),
+}
+
+/// 1回の編集。逆操作と、編集前のアノテーション（切り詰めは元に戻せないため）を持つ
+#[derive(Debug, Clone)]
+struct EditRecord {
+    edit: SequenceEdit,
+    inverse: SequenceEdit,
+    features: Vec<SequenceFeature>,
+}
+
+#[derive(Debug, Default)]
+struct EditHistory {
+    undo: Vec<EditRecord>,
+    redo: Vec<EditRecord>,
+}
+
+/// Sequence editing service
+#[derive(Debug, Default)]
+pub struct EditService {
+    histories: HashMap<String, EditHistory>,
+}
+
+impl EditService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `edit` を適用した状態を返し、取り消し履歴に積む（やり直し履歴は破棄）
+    pub fn apply(
+        &mut self,
+        seq_id: &str,
+        state: EditState,
+        edit: SequenceEdit,
+    ) -> Result<EditState, EditError> {
+        let (next, inverse) = perform(&state, &edit)?;
+        let history = self.histories.entry(seq_id.to_string()).or_default();
+        history.undo.push(EditRecord {
+            edit,
+            inverse,
+            features: state.features,
+        });
+        history.redo.clear();
+        Ok(next)
+    }
+
+    /// 直前の編集を取り消し、元に戻した状態と適用した逆操作を返す
+    pub fn undo(
+        &mut self,
+        seq_id: &str,
+        state: EditState,
+    ) -> Result<(EditState, SequenceEdit), EditError> {
+        let history = self.histories.entry(seq_id.to_string()).or_default();
+        let record = history
+            .undo
+            .pop()
+            .ok_or_else(|| EditError::NothingToUndo(seq_id.to_string()))?;
+        let (mut previous, _) = match perform(&state, &record.inverse) {
+            Ok(result) => result,
+            Err(e) => {
+                history.undo.push(record);
+                return Err(e);
+            }
+        };
+        previous.features = record.features.clone();
+        let inverse = record.inverse.clone();
+        history.redo.push(record);
+        Ok((previous, inverse))
+    }
+
+    /// 取り消した編集をやり直し、その状態と操作を返す
+    pub fn redo(
+        &mut self,
+        seq_id: &str,
+        state: EditState,
+    ) -> Result<(EditState, SequenceEdit), EditError> {
+        let history = self.histories.entry(seq_id.to_string()).or_default();
+        let record = history
+            .redo
+            .pop()
+            .ok_or_else(|| EditError::NothingToRedo(seq_id.to_string()))?;
+        let (next, _) = match perform(&state, &record.edit) {
+            Ok(result) => result,
+            Err(e) => {
+                history.redo.push(record);
+                return Err(e);
+            }
+        };
+        let edit = record.edit.clone();
+        history.undo.push(record);
+        Ok((next, edit))
+    }
+
+    /// 取り消し・やり直しできる編集の数
+    pub fn depth(&self, seq_id: &str) -> (usize, usize) {
+        self.histories
+            .get(seq_id)
+            .map_or((0, 0), |history| (history.undo.len(), history.redo.len()))
+    }
+}
+
+/// 編集を適用した状態と、それを元に戻す逆操作
+fn perform(state: &EditState, edit: &SequenceEdit) -> Result<(EditState, SequenceEdit), EditError> {
+    let Some((start, end, bases)) = edit.span() else {
+        let SequenceEdit::SetTopology { topology } = edit else {
+            unreachable!("only topology changes have no span");
+        };
+        let inverse = SequenceEdit::SetTopology {
+            topology: state.topology.clone(),
+        };
+        let next = EditState {
+            topology: topology.clone(),
+            ..state.clone()
+        };
+        return Ok((next, inverse));
+    };
+
+    let length = state.sequence.len();
+    let empty_delete = matches!(edit, SequenceEdit::Delete { .. }) && start == end;
+    if start > end || end > length || empty_delete {
+        return Err(EditError::OutOfRange { start, end, length });
+    }
+    let inserts = !matches!(edit, SequenceEdit::Delete { .. });
+    if inserts && (bases.is_empty() || bases.chars().any(|c| iupac::expand(c).is_empty())) {
+        return Err(EditError::InvalidBases(bases.to_string()));
+    }
+
+    let bases = bases.to_ascii_uppercase();
+    let removed = state.sequence[start..end].to_string();
+    let sequence = format!(
+        "{}{}{}",
+        &state.sequence[..start],
+        bases,
+        &state.sequence[end..]
+    );
+    let inverse = match edit {
+        SequenceEdit::Insert { position, .. } => SequenceEdit::Delete {
+            start: *position,
+            end: position + bases.len(),
+        },
+        SequenceEdit::Delete { start, .. } => SequenceEdit::Insert {
+            position: *start,
+            bases: removed,
+        },
+        _ => SequenceEdit::Replace {
+            start,
+            end: start + bases.len(),
+            bases: removed,
+        },
+    };
+    let features = state
+        .features
+        .iter()
+        .filter_map(|feature| edit.map_feature(feature))
+        .collect();
+
+    Ok((
+        EditState {
+            sequence,
+            topology: state.topology.clone(),
+            features,
+        },
+        inverse,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Strand, Topology};
+
+    fn state(sequence: &str, features: Vec<SequenceFeature>) -> EditState {
+        EditState {
+            sequence: sequence.to_string(),
+            topology: Topology::Linear,
+            features,
+        }
+    }
+
+    #[test]
+    fn test_edits_shift_features_and_undo_redo() {
+        let mut service = EditService::new();
+        let gene = SequenceFeature::new("gene", 4, 8, Strand::Forward);
+        let initial = state("AAAACCCCGGGG", vec![gene.clone()]);
+
+        // 遺伝子の直前に挿入すると後ろにずれる
+        let inserted = service
+            .apply(
+                "seq_1",
+                initial.clone(),
+                SequenceEdit::Insert {
+                    position: 4,
+                    bases: "tt".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(inserted.sequence, "AAAATTCCCCGGGG");
+        assert_eq!(
+            (inserted.features[0].start, inserted.features[0].end),
+            (6, 10)
+        );
+
+        // 遺伝子の後半を削除すると切り詰められ、全体を削除すると消える
+        let deleted = service
+            .apply(
+                "seq_1",
+                inserted.clone(),
+                SequenceEdit::Delete { start: 8, end: 12 },
+            )
+            .unwrap();
+        assert_eq!(deleted.sequence, "AAAATTCCGG");
+        assert_eq!((deleted.features[0].start, deleted.features[0].end), (6, 8));
+        let replaced = service
+            .apply(
+                "seq_1",
+                deleted.clone(),
+                SequenceEdit::Replace {
+                    start: 5,
+                    end: 9,
+                    bases: "A".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(replaced.sequence, "AAAATAG");
+        assert!(replaced.features.is_empty());
+        assert_eq!(service.depth("seq_1"), (3, 0));
+
+        let (undone, inverse) = service.undo("seq_1", replaced).unwrap();
+        assert_eq!(undone.sequence, deleted.sequence);
+        assert_eq!(undone.features, deleted.features);
+        assert!(matches!(
+            inverse,
+            SequenceEdit::Replace {
+                start: 5,
+                end: 6,
+                ..
+            }
+        ));
+        let (undone, _) = service.undo("seq_1", undone).unwrap();
+        let (undone, _) = service.undo("seq_1", undone).unwrap();
+        assert_eq!(undone.sequence, initial.sequence);
+        assert_eq!(undone.features, vec![gene]);
+        assert!(service.undo("seq_1", undone.clone()).is_err());
+
+        let (redone, _) = service.redo("seq_1", undone).unwrap();
+        assert_eq!(redone.sequence, inserted.sequence);
+        assert_eq!(service.depth("seq_1"), (1, 2));
+
+        // 新しい編集でやり直し履歴は消える
+        let circular = service
+            .apply(
+                "seq_1",
+                redone,
+                SequenceEdit::SetTopology {
+                    topology: Topology::Circular,
+                },
+            )
+            .unwrap();
+        assert_eq!(circular.topology, Topology::Circular);
+        assert_eq!(service.depth("seq_1"), (2, 0));
+        assert_eq!(service.depth("seq_2"), (0, 0));
+    }
+
+    #[test]
+    fn test_invalid_edits_leave_history_unchanged() {
+        let mut service = EditService::new();
+        let current = state("ACGT", Vec::new());
+        let out_of_range = SequenceEdit::Delete { start: 2, end: 9 };
+        assert!(matches!(
+            service.apply("seq_1", current.clone(), out_of_range),
+            Err(EditError::OutOfRange { length: 4, .. })
+        ));
+        let invalid = SequenceEdit::Insert {
+            position: 1,
+            bases: "AXG".to_string(),
+        };
+        assert!(matches!(
+            service.apply("seq_1", current, invalid),
+            Err(EditError::InvalidBases(_))
+        ));
+        assert_eq!(service.depth("seq_1"), (0, 0));
+    }
+}
@@ -0,0 +1,332 @@
+// Service layer: Frameshift detection between an observed CDS and its expected protein
+use crate::domain::frameshift::{CdsIndel, FrameshiftReport, IndelKind};
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::iupac;
+use crate::domain::region::Region;
+use crate::domain::Strand;
+
+/// コドンが期待どおりの残基をコードする場合のスコア
+const MATCH: i32 = 5;
+const MISMATCH: i32 = -3;
+/// 読み枠を保ったコドン単位のインデル
+const CODON_GAP: i32 = -12;
+/// 読み枠がずれるインデル
+const FRAMESHIFT: i32 = -20;
+const UNREACHABLE: i32 = i32::MIN / 2;
+
+const BASES: [u8; 4] = *b"ACGT";
+
+/// アラインメントの1ステップ（消費する塩基数, 残基数）。同点の場合は先のものを選ぶ
+const MOVES: [(usize, usize); 9] = [
+    (3, 1),
+    (0, 1),
+    (3, 0),
+    (4, 1),
+    (5, 1),
+    (2, 1),
+    (1, 1),
+    (1, 0),
+    (2, 0),
+];
+
+/// 1残基に対応づけた塩基の塊（3塩基でない場合はインデルの位置と塩基）
+struct CodonEvent {
+    matched: bool,
+    /// 塊の先頭からのインデル位置
+    offset: usize,
+    /// 挿入された塩基、または抜けた塩基
+    bases: Vec<u8>,
+}
+
+/// Frameshift detection service
+pub struct FrameshiftService {
+    genetic_code: GeneticCode,
+    /// 残基ごとのコドン（'X' は全コドン）
+    codons: Vec<Vec<[u8; 3]>>,
+}
+
+impl Default for FrameshiftService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameshiftService {
+    pub fn new() -> Self {
+        Self::with_genetic_code(GeneticCode::standard())
+    }
+
+    pub fn with_genetic_code(genetic_code: GeneticCode) -> Self {
+        let mut codons = vec![Vec::new(); 256];
+        for first in BASES {
+            for second in BASES {
+                for third in BASES {
+                    let codon = [first, second, third];
+                    codons[genetic_code.translate_codon(&codon) as usize].push(codon);
+                    codons[b'X' as usize].push(codon);
+                }
+            }
+        }
+        Self {
+            genetic_code,
+            codons,
+        }
+    }
+
+    /// `region` の CDS（逆鎖なら逆相補）を期待タンパク質 `expected` に読み枠を考慮して
+    /// グローバルにアラインメントし（CDS の3'側の余りは無視）、インデルを位置順に報告する
+    pub fn analyze(&self, sequence: &str, region: &Region, expected: &str) -> FrameshiftReport {
+        let bases = sequence[region.start..region.end].to_ascii_uppercase();
+        let coding = match region.strand {
+            Strand::Forward => bases,
+            Strand::Reverse => iupac::reverse_complement(&bases),
+        };
+        let expected: String = expected
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase()
+            .trim_end_matches('*')
+            .to_string();
+
+        let observed = coding.as_bytes();
+        let residues = expected.as_bytes();
+        let steps = self.align(observed, residues);
+
+        let mut indels = Vec::new();
+        let mut matched_residues = 0;
+        let (mut i, mut j) = (0, 0);
+        for (nt, aa) in steps {
+            let chunk = &observed[i..i + nt];
+            let indel = |kind: IndelKind, offset: usize, bases: &[u8], residue: usize| {
+                let cds_position = i + offset;
+                let cds_end = match kind {
+                    IndelKind::Insertion => cds_position + bases.len(),
+                    IndelKind::Deletion => cds_position,
+                };
+                let (start, end) = match region.strand {
+                    Strand::Forward => (region.start + cds_position, region.start + cds_end),
+                    Strand::Reverse => (region.end - cds_end, region.end - cds_position),
+                };
+                CdsIndel {
+                    kind,
+                    cds_position,
+                    start,
+                    end,
+                    bases: String::from_utf8_lossy(bases).into_owned(),
+                    residue,
+                    frameshift: !bases.len().is_multiple_of(3),
+                }
+            };
+            match (nt, aa) {
+                (_, 1) if nt > 0 => {
+                    let event = self.codon_event(chunk, residues[j]);
+                    if event.matched {
+                        matched_residues += 1;
+                    }
+                    let kind = match nt {
+                        3 => None,
+                        4 | 5 => Some(IndelKind::Insertion),
+                        _ => Some(IndelKind::Deletion),
+                    };
+                    if let Some(kind) = kind {
+                        indels.push(indel(kind, event.offset, &event.bases, j));
+                    }
+                }
+                (0, _) => indels.push(indel(IndelKind::Deletion, 0, b"NNN", j)),
+                _ => indels.push(indel(IndelKind::Insertion, 0, chunk, j)),
+            }
+            i += nt;
+            j += aa;
+        }
+
+        let observed_translation = self.genetic_code.translate(&coding);
+        let premature_stop = observed_translation
+            .find('*')
+            .filter(|&codon| codon < residues.len())
+            .map(|codon| codon * 3);
+
+        FrameshiftReport {
+            region: *region,
+            expected_length: residues.len(),
+            matched_residues,
+            frameshift: indels.iter().find(|indel| indel.frameshift).cloned(),
+            indels,
+            observed_translation,
+            premature_stop,
+        }
+    }
+
+    /// 読み枠を考慮した DP で最適なステップ列を求める（CDS は先頭から、タンパク質は全長）
+    fn align(&self, observed: &[u8], residues: &[u8]) -> Vec<(usize, usize)> {
+        let (n, m) = (observed.len(), residues.len());
+        let width = m + 1;
+        let mut score = vec![UNREACHABLE; (n + 1) * width];
+        let mut trace = vec![0u8; (n + 1) * width];
+        score[0] = 0;
+
+        for i in 0..=n {
+            for j in 0..=m {
+                if i == 0 && j == 0 {
+                    continue;
+                }
+                let mut best = UNREACHABLE;
+                for (index, &(nt, aa)) in MOVES.iter().enumerate() {
+                    if nt > i || aa > j {
+                        continue;
+                    }
+                    let previous = score[(i - nt) * width + j - aa];
+                    if previous == UNREACHABLE {
+                        continue;
+                    }
+                    let step = match (nt, aa) {
+                        (0, _) | (3, 0) => CODON_GAP,
+                        (_, 0) => FRAMESHIFT,
+                        _ => {
+                            let event = self.codon_event(&observed[i - nt..i], residues[j - 1]);
+                            let codon = if event.matched { MATCH } else { MISMATCH };
+                            if nt == 3 {
+                                codon
+                            } else {
+                                codon + FRAMESHIFT
+                            }
+                        }
+                    };
+                    if previous + step > best {
+                        best = previous + step;
+                        trace[i * width + j] = index as u8;
+                    }
+                }
+                score[i * width + j] = best;
+            }
+        }
+
+        // CDS の3'側（終止コドンなど）は揃えなくてよい
+        let mut i = (0..=n)
+            .max_by_key(|&i| (score[i * width + m], std::cmp::Reverse(i)))
+            .unwrap_or(0);
+        let mut j = m;
+        let mut steps = Vec::new();
+        while i > 0 || j > 0 {
+            let (nt, aa) = MOVES[trace[i * width + j] as usize];
+            steps.push((nt, aa));
+            i -= nt;
+            j -= aa;
+        }
+        steps.reverse();
+        steps
+    }
+
+    /// 1残基に対応づけた塊を評価する。挿入（4〜5塩基）は取り除くと残基をコードする位置、
+    /// 欠失（1〜2塩基）は補うと残基をコードする位置と塩基を、最も5'側から探す
+    fn codon_event(&self, chunk: &[u8], residue: u8) -> CodonEvent {
+        let encodes = |codon: &[u8]| {
+            residue == b'X' || self.genetic_code.translate_codon(codon) as u8 == residue
+        };
+        if chunk.len() == 3 {
+            return CodonEvent {
+                matched: encodes(chunk),
+                offset: 0,
+                bases: Vec::new(),
+            };
+        }
+
+        if chunk.len() > 3 {
+            let extra = chunk.len() - 3;
+            let offset = (0..=3).find(|&offset| {
+                let codon: Vec<u8> = chunk[..offset]
+                    .iter()
+                    .chain(&chunk[offset + extra..])
+                    .copied()
+                    .collect();
+                encodes(&codon)
+            });
+            let position = offset.unwrap_or(3);
+            return CodonEvent {
+                matched: offset.is_some(),
+                offset: position,
+                bases: chunk[position..position + extra].to_vec(),
+            };
+        }
+
+        let missing = 3 - chunk.len();
+        let fill = (0..=chunk.len()).find_map(|offset| {
+            self.codons[residue as usize]
+                .iter()
+                .find(|codon| {
+                    codon[..offset] == chunk[..offset]
+                        && codon[offset + missing..] == chunk[offset..]
+                })
+                .map(|codon| (offset, codon[offset..offset + missing].to_vec()))
+        });
+        match fill {
+            Some((offset, bases)) => CodonEvent {
+                matched: true,
+                offset,
+                bases,
+            },
+            None => CodonEvent {
+                matched: false,
+                offset: chunk.len(),
+                bases: vec![b'N'; missing],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // M K T A Y I A K Q R Q I S F V K *
+    const CODING: &str = "ATGAAAACCGCGTATATTGCGAAACAGCGCCAGATTAGCTTTGTGAAATAA";
+    const PROTEIN: &str = "MKTAYIAKQRQISFVK";
+
+    #[test]
+    fn test_intact_cds_has_no_indels() {
+        let sequence = format!("GG{}CC", CODING);
+        let report = FrameshiftService::new().analyze(&sequence, &Region::new(2, 53), PROTEIN);
+        assert!(report.indels.is_empty());
+        assert_eq!(report.frameshift, None);
+        assert_eq!(report.matched_residues, 16);
+        assert_eq!(report.premature_stop, None);
+    }
+
+    #[test]
+    fn test_single_base_deletion() {
+        // GCG（Ala4）の C を欠失
+        let observed = format!("{}{}", &CODING[..10], &CODING[11..]);
+        let sequence = format!("GG{}CC", observed);
+        let report = FrameshiftService::new().analyze(&sequence, &Region::new(2, 52), PROTEIN);
+
+        let frameshift = report.frameshift.unwrap();
+        assert_eq!(frameshift.kind, IndelKind::Deletion);
+        assert_eq!(
+            (frameshift.cds_position, frameshift.start, frameshift.end),
+            (10, 12, 12)
+        );
+        assert_eq!((frameshift.bases.as_str(), frameshift.residue), ("C", 3));
+        assert_eq!(report.indels.len(), 1);
+        assert_eq!(report.matched_residues, 16);
+        // ずれた読み枠の TGA
+        assert_eq!(report.premature_stop, Some(42));
+    }
+
+    #[test]
+    fn test_insertion_on_reverse_strand() {
+        // TAT（Tyr5）の中に G を挿入
+        let observed = format!("{}G{}", &CODING[..14], &CODING[14..]);
+        let sequence = format!("AA{}TT", iupac::reverse_complement(&observed));
+        let region = Region::new(2, 54).with_strand(Strand::Reverse);
+        let report = FrameshiftService::new().analyze(&sequence, &region, "mktayiakqrqisfvk*");
+
+        let frameshift = report.frameshift.unwrap();
+        assert_eq!(frameshift.kind, IndelKind::Insertion);
+        assert_eq!(
+            (frameshift.cds_position, frameshift.start, frameshift.end),
+            (14, 39, 40)
+        );
+        assert_eq!((frameshift.bases.as_str(), frameshift.residue), ("G", 4));
+        assert_eq!(&sequence[39..40], "C");
+    }
+}
@@ -0,0 +1,173 @@
+// Service layer: Windowed GC content and cumulative GC skew computed from streamed chunks
+use crate::domain::gc_skew::{GcSkewProfile, GcSkewWindow};
+use std::collections::VecDeque;
+
+/// 配列をチャンクで受け取り、全体を保持せずに GC skew のプロファイルを集計する。
+/// 保持するのは直近 `window` 塩基だけなので、ゲノム規模のファイルでもメモリは
+/// ウィンドウ幅とウィンドウ数に比例する。ウィンドウは 0, step, 2 * step, … から始まり、
+/// 配列の終わりに届かなければ末尾の短いウィンドウを1つ加える
+pub struct StreamingGcSkew {
+    window: usize,
+    step: usize,
+    length: usize,
+    recent: VecDeque<u8>,
+    g: usize,
+    c: usize,
+    cumulative: f64,
+    windows: Vec<GcSkewWindow>,
+}
+
+impl StreamingGcSkew {
+    pub fn new(window: usize, step: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            step: step.max(1),
+            length: 0,
+            recent: VecDeque::with_capacity(window + 1),
+            g: 0,
+            c: 0,
+            cumulative: 0.0,
+            windows: Vec::new(),
+        }
+    }
+
+    /// 配列の続きを渡す（チャンクの境界はどこでもよい）
+    pub fn push(&mut self, chunk: &str) {
+        for &byte in chunk.as_bytes() {
+            let base = byte.to_ascii_uppercase();
+            self.add(base, true);
+            self.recent.push_back(base);
+            if self.recent.len() > self.window {
+                if let Some(base) = self.recent.pop_front() {
+                    self.add(base, false);
+                }
+            }
+            self.length += 1;
+
+            if self.length >= self.window && (self.length - self.window).is_multiple_of(self.step) {
+                self.emit(self.length - self.window);
+            }
+        }
+    }
+
+    /// 末尾の短いウィンドウを加え、累積 skew の最小・最大から複製起点と終結点の候補を選ぶ
+    pub fn finish(mut self) -> GcSkewProfile {
+        let reached_end = self
+            .windows
+            .last()
+            .is_some_and(|window| window.end == self.length);
+        let next = self
+            .windows
+            .last()
+            .map_or(0, |window| window.start + self.step);
+        if !reached_end && next < self.length {
+            while self.length - self.recent.len() < next {
+                if let Some(base) = self.recent.pop_front() {
+                    self.add(base, false);
+                }
+            }
+            self.emit(next);
+        }
+
+        let extreme = |better: fn(f64, f64) -> bool| {
+            self.windows
+                .iter()
+                .fold(None::<&GcSkewWindow>, |best, window| match best {
+                    Some(best) if !better(window.cumulative, best.cumulative) => Some(best),
+                    _ => Some(window),
+                })
+                .map(|window| window.start)
+        };
+        GcSkewProfile {
+            origin: extreme(|a, b| a < b),
+            terminus: extreme(|a, b| a > b),
+            windows: self.windows,
+        }
+    }
+
+    /// `recent` の塩基を `start` からのウィンドウとして記録
+    fn emit(&mut self, start: usize) {
+        let (g, c) = (self.g, self.c);
+        let skew = if g + c > 0 {
+            (g as f64 - c as f64) / (g + c) as f64
+        } else {
+            0.0
+        };
+        self.cumulative += skew;
+        self.windows.push(GcSkewWindow {
+            start,
+            end: self.length,
+            gc_percent: (g + c) as f64 / self.recent.len() as f64 * 100.0,
+            skew,
+            cumulative: self.cumulative,
+        });
+    }
+
+    /// 塩基をウィンドウに加える（`entering` が false なら取り除く）
+    fn add(&mut self, base: u8, entering: bool) {
+        let count = match base {
+            b'G' => &mut self.g,
+            b'C' => &mut self.c,
+            _ => return,
+        };
+        if entering {
+            *count += 1;
+        } else {
+            *count -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(sequence: &str, window: usize, step: usize, chunk: usize) -> GcSkewProfile {
+        let mut skew = StreamingGcSkew::new(window, step);
+        for chunk in sequence.as_bytes().chunks(chunk) {
+            skew.push(std::str::from_utf8(chunk).unwrap());
+        }
+        skew.finish()
+    }
+
+    #[test]
+    fn test_windows_do_not_depend_on_chunk_boundaries() {
+        // C に富む区間・G に富む区間・C に富む区間（末尾は短いウィンドウ）
+        let sequence = format!(
+            "{}{}{}ggat",
+            "CCCA".repeat(5),
+            "GGGA".repeat(10),
+            "CCCA".repeat(5)
+        );
+        let whole = profile(&sequence, 20, 10, sequence.len());
+        assert_eq!(profile(&sequence, 20, 10, 3), whole);
+
+        let spans: Vec<(usize, usize)> = whole.windows.iter().map(|w| (w.start, w.end)).collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0, 20),
+                (10, 30),
+                (20, 40),
+                (30, 50),
+                (40, 60),
+                (50, 70),
+                (60, 80),
+                (70, 84)
+            ]
+        );
+        assert_eq!(whole.windows[0].skew, -1.0);
+        assert_eq!(whole.windows[0].gc_percent, 75.0);
+        assert_eq!(whole.windows[2].skew, 1.0);
+        // 末尾の "CACCCACCCAGGAT" は G 2・C 7
+        assert!((whole.windows[7].skew + 5.0 / 9.0).abs() < 1e-12);
+        assert_eq!(whole.origin, Some(0));
+        assert_eq!(whole.terminus, Some(40));
+
+        // ウィンドウがちょうど配列の終わりに届けば短いウィンドウは加えない
+        let exact = profile(&sequence[..80], 20, 20, 7);
+        assert_eq!(exact.windows.len(), 4);
+        assert!(profile("", 20, 20, 1).windows.is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+// Service layer: Cumulative GC skew analysis plugin (feature "gc-skew-plugin")
+use crate::domain::analysis_result::AnalysisResult;
+use crate::domain::plugin::{PluginError, SequenceAnalysisPlugin};
+use crate::services::StreamingGcSkew;
+use serde::{Deserialize, Serialize};
+
+/// GC skew の条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GcSkewParams {
+    /// ウィンドウ長（bp）
+    pub window: usize,
+    /// ウィンドウをずらす幅（bp）
+    pub step: usize,
+}
+
+impl Default for GcSkewParams {
+    fn default() -> Self {
+        Self {
+            window: 1000,
+            step: 1000,
+        }
+    }
+}
+
+/// Cumulative GC skew plugin
+pub struct GcSkewPlugin;
+
+impl SequenceAnalysisPlugin for GcSkewPlugin {
+    fn name(&self) -> &str {
+        "gc_skew"
+    }
+
+    fn description(&self) -> &str {
+        "Windowed and cumulative GC skew, with the replication origin and terminus candidates"
+    }
+
+    fn parameter_schema(&self) -> serde_json::Value {
+        let defaults = GcSkewParams::default();
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "window": { "type": "integer", "minimum": 1, "default": defaults.window },
+                "step": { "type": "integer", "minimum": 1, "default": defaults.step }
+            }
+        })
+    }
+
+    fn run(
+        &self,
+        seq_id: &str,
+        sequence: &str,
+        params: &serde_json::Value,
+    ) -> Result<AnalysisResult<serde_json::Value>, PluginError> {
+        let invalid = |message: String| PluginError::InvalidParameters(self.name().into(), message);
+        let params: GcSkewParams =
+            serde_json::from_value(params.clone()).map_err(|e| invalid(e.to_string()))?;
+        if params.window == 0 || params.step == 0 {
+            return Err(invalid("window and step must be at least 1".to_string()));
+        }
+
+        let mut profile = StreamingGcSkew::new(params.window, params.step);
+        profile.push(sequence);
+        let result = serde_json::to_value(profile.finish())
+            .map_err(|e| PluginError::Failed(self.name().into(), e.to_string()))?;
+        Ok(AnalysisResult::new(
+            self.name(),
+            seq_id,
+            sequence,
+            &params,
+            result,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::gc_skew::GcSkewProfile;
+    use serde_json::json;
+
+    #[test]
+    fn test_cumulative_skew_locates_origin_and_terminus() {
+        // C に富む区間・G に富む区間・C に富む区間
+        let sequence = format!(
+            "{}{}{}",
+            "CCCA".repeat(5),
+            "GGGA".repeat(10),
+            "CCCA".repeat(5)
+        );
+        let result = GcSkewPlugin
+            .run("seq_1", &sequence, &json!({ "window": 20, "step": 20 }))
+            .unwrap();
+        assert_eq!(result.result_type, "gc_skew");
+        assert_eq!(result.parameters, json!({ "window": 20, "step": 20 }));
+
+        let profile: GcSkewProfile = serde_json::from_value(result.result).unwrap();
+        let skews: Vec<f64> = profile.windows.iter().map(|w| w.skew).collect();
+        assert_eq!(skews, vec![-1.0, 1.0, 1.0, -1.0]);
+        assert_eq!(profile.origin, Some(0));
+        assert_eq!(profile.terminus, Some(40));
+
+        assert!(matches!(
+            GcSkewPlugin.run("seq_1", &sequence, &json!({ "step": 0 })),
+            Err(PluginError::InvalidParameters(..))
+        ));
+    }
+}
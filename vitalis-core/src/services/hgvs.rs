@@ -0,0 +1,515 @@
+// Service layer: HGVS variant nomenclature (g./c./p.)
+use crate::domain::feature::SequenceFeature;
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::iupac;
+use crate::domain::protein::three_letter_code;
+use crate::domain::variant::{HgvsDescription, Variant};
+use crate::domain::{Range, Strand};
+use crate::services::coordinates::{CoordinateError, CoordinateService};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HgvsError {
+    #[error("Variant at {0} extends beyond the sequence")]
+    OutOfRange(usize),
+    #[error("Reference '{0}' does not match the sequence ('{1}')")]
+    ReferenceMismatch(String, String),
+    #[error("Variant does not change the sequence")]
+    NoChange,
+    #[error("Insertions at the sequence ends cannot be described")]
+    TerminalInsertion,
+    #[error(transparent)]
+    Coordinate(#[from] CoordinateError),
+}
+
+/// 正規化した編集（0-based、削除区間 [start, start + deleted.len())、挿入は start の直前）
+#[derive(Debug, Clone)]
+struct Edit {
+    start: usize,
+    deleted: Vec<u8>,
+    inserted: Vec<u8>,
+}
+
+impl Edit {
+    fn end(&self) -> usize {
+        self.start + self.deleted.len()
+    }
+
+    /// 共通の前後塩基を除いた最小の編集
+    fn trimmed(variant: &Variant) -> Self {
+        let mut start = variant.position;
+        let mut deleted = variant.reference.to_ascii_uppercase().into_bytes();
+        let mut inserted = variant.alternate.to_ascii_uppercase().into_bytes();
+
+        let prefix = deleted
+            .iter()
+            .zip(&inserted)
+            .take_while(|(d, i)| d == i)
+            .count();
+        deleted.drain(..prefix);
+        inserted.drain(..prefix);
+        start += prefix;
+
+        while !deleted.is_empty() && !inserted.is_empty() && deleted.last() == inserted.last() {
+            deleted.pop();
+            inserted.pop();
+        }
+        Self {
+            start,
+            deleted,
+            inserted,
+        }
+    }
+
+    /// 単純な挿入・欠失を反復配列内で最も3'側（`rightward` が false なら配列の左側）へ移動
+    fn shifted(mut self, sequence: &[u8], rightward: bool) -> Self {
+        let rotate = |bases: &mut Vec<u8>, incoming: u8, rightward: bool| {
+            if rightward {
+                bases.remove(0);
+                bases.push(incoming);
+            } else {
+                bases.pop();
+                bases.insert(0, incoming);
+            }
+        };
+
+        if self.inserted.is_empty() && !self.deleted.is_empty() {
+            if rightward {
+                while self.end() < sequence.len() && sequence[self.end()] == self.deleted[0] {
+                    let incoming = sequence[self.end()];
+                    rotate(&mut self.deleted, incoming, true);
+                    self.start += 1;
+                }
+            } else {
+                while self.start > 0 && sequence[self.start - 1] == *self.deleted.last().unwrap() {
+                    rotate(&mut self.deleted, sequence[self.start - 1], false);
+                    self.start -= 1;
+                }
+            }
+        } else if self.deleted.is_empty() && !self.inserted.is_empty() {
+            if rightward {
+                while self.start < sequence.len() && sequence[self.start] == self.inserted[0] {
+                    rotate(&mut self.inserted, sequence[self.start], true);
+                    self.start += 1;
+                }
+            } else {
+                while self.start > 0 && sequence[self.start - 1] == *self.inserted.last().unwrap() {
+                    rotate(&mut self.inserted, sequence[self.start - 1], false);
+                    self.start -= 1;
+                }
+            }
+        }
+        self
+    }
+}
+
+/// HGVS description service
+pub struct HgvsService {
+    coordinates: CoordinateService,
+    code: GeneticCode,
+}
+
+impl Default for HgvsService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HgvsService {
+    pub fn new() -> Self {
+        Self {
+            coordinates: CoordinateService::new(),
+            code: GeneticCode::standard(),
+        }
+    }
+
+    /// Describe `variant` at the genomic level and, when `cds` is given, at the
+    /// coding and predicted protein level. Indels are shifted 3' relative to the
+    /// described strand as HGVS requires.
+    pub fn describe(
+        &self,
+        sequence: &str,
+        variant: &Variant,
+        cds: Option<&SequenceFeature>,
+    ) -> Result<HgvsDescription, HgvsError> {
+        let bytes = sequence.to_ascii_uppercase().into_bytes();
+        let ref_end = variant.position + variant.reference.len();
+        if ref_end > bytes.len() || variant.position > bytes.len() {
+            return Err(HgvsError::OutOfRange(variant.position));
+        }
+        let actual = String::from_utf8_lossy(&bytes[variant.position..ref_end]).to_string();
+        if !actual.eq_ignore_ascii_case(&variant.reference) {
+            return Err(HgvsError::ReferenceMismatch(
+                variant.reference.clone(),
+                actual,
+            ));
+        }
+
+        let edit = Edit::trimmed(variant);
+        if edit.deleted.is_empty() && edit.inserted.is_empty() {
+            return Err(HgvsError::NoChange);
+        }
+
+        let genomic_edit = edit.clone().shifted(&bytes, true);
+        let genomic = format!(
+            "g.{}",
+            describe_dna(&bytes, &genomic_edit, Strand::Forward, |p| Ok(
+                (p + 1).to_string()
+            ))?
+        );
+
+        let Some(cds) = cds else {
+            return Ok(HgvsDescription {
+                genomic,
+                feature_id: None,
+                coding: None,
+                protein: None,
+            });
+        };
+
+        let coding_edit = edit.shifted(&bytes, cds.strand == Strand::Forward);
+        let coding = format!(
+            "c.{}",
+            describe_dna(&bytes, &coding_edit, cds.strand, |p| {
+                self.coordinates.cds_label(p, cds)
+            })?
+        );
+        let protein = self.describe_protein(&bytes, &coding_edit, cds);
+
+        Ok(HgvsDescription {
+            genomic,
+            feature_id: Some(cds.id.clone()),
+            coding: Some(coding),
+            protein,
+        })
+    }
+
+    /// 編集がコーディングエキソン内に収まる場合のみタンパク質変化を予測
+    fn describe_protein(
+        &self,
+        sequence: &[u8],
+        edit: &Edit,
+        cds: &SequenceFeature,
+    ) -> Option<String> {
+        let mut exons = if cds.segments.is_empty() {
+            vec![Range::new(cds.start, cds.end)]
+        } else {
+            cds.segments.clone()
+        };
+        exons.sort_by_key(|exon| exon.start);
+
+        let in_exon = |p: usize| exons.iter().any(|e| e.start <= p && p < e.end);
+        let coding = if edit.deleted.is_empty() {
+            edit.start > 0 && in_exon(edit.start - 1) && in_exon(edit.start)
+        } else {
+            (edit.start..edit.end()).all(in_exon)
+        };
+        if !coding || exons.last()?.end > sequence.len() {
+            return None;
+        }
+
+        let mutated: Vec<u8> = [
+            &sequence[..edit.start],
+            &edit.inserted[..],
+            &sequence[edit.end()..],
+        ]
+        .concat();
+        let delta = edit.inserted.len() as isize - edit.deleted.len() as isize;
+        let mutated_exons: Vec<Range> = exons
+            .iter()
+            .map(|exon| {
+                let shift = |p: usize| (p as isize + delta) as usize;
+                if exon.start >= edit.end() && !(exon.start <= edit.start && edit.start < exon.end)
+                {
+                    Range::new(shift(exon.start), shift(exon.end))
+                } else if exon.start <= edit.start && edit.end() <= exon.end {
+                    Range::new(exon.start, shift(exon.end))
+                } else {
+                    exon.clone()
+                }
+            })
+            .collect();
+
+        let reference = self.translate_cds(sequence, &exons, cds.strand);
+        let alternate = self.translate_cds(&mutated, &mutated_exons, cds.strand);
+        Some(describe_protein_change(
+            &reference,
+            &alternate,
+            delta % 3 != 0,
+        ))
+    }
+
+    /// スプライス済みCDSを翻訳（終止コドンがなければ下流の配列まで読み進める）
+    fn translate_cds(&self, sequence: &[u8], exons: &[Range], strand: Strand) -> Vec<char> {
+        let spliced: String = exons
+            .iter()
+            .map(|exon| String::from_utf8_lossy(&sequence[exon.start..exon.end]).to_string())
+            .collect();
+        let (cds, tail) = match strand {
+            Strand::Forward => (
+                spliced,
+                String::from_utf8_lossy(&sequence[exons.last().unwrap().end..]).to_string(),
+            ),
+            Strand::Reverse => (
+                iupac::reverse_complement(&spliced),
+                iupac::reverse_complement(&String::from_utf8_lossy(&sequence[..exons[0].start])),
+            ),
+        };
+
+        let mut protein = Vec::new();
+        for codon in format!("{}{}", cds, tail).as_bytes().chunks_exact(3) {
+            let residue = self.code.translate_codon(codon);
+            protein.push(residue);
+            if residue == '*' {
+                break;
+            }
+        }
+        protein
+    }
+}
+
+/// DNAレベルの記述（プレフィックスなし）。`strand` が逆鎖なら塩基を相補にし、
+/// 座標は転写方向の順に並べる
+fn describe_dna<F>(
+    sequence: &[u8],
+    edit: &Edit,
+    strand: Strand,
+    label: F,
+) -> Result<String, HgvsError>
+where
+    F: Fn(usize) -> Result<String, CoordinateError>,
+{
+    let reverse = strand == Strand::Reverse;
+    let bases = |bytes: &[u8]| {
+        let forward = String::from_utf8_lossy(bytes).to_string();
+        if reverse {
+            iupac::reverse_complement(&forward)
+        } else {
+            forward
+        }
+    };
+    let range = |start: usize, end: usize| -> Result<String, HgvsError> {
+        let (first, last) = if reverse {
+            (end - 1, start)
+        } else {
+            (start, end - 1)
+        };
+        Ok(if first == last {
+            label(first)?
+        } else {
+            format!("{}_{}", label(first)?, label(last)?)
+        })
+    };
+
+    let (start, end) = (edit.start, edit.end());
+    Ok(match (edit.deleted.len(), edit.inserted.len()) {
+        (1, 1) => format!(
+            "{}{}>{}",
+            label(start)?,
+            bases(&edit.deleted),
+            bases(&edit.inserted)
+        ),
+        (_, 0) => format!("{}del", range(start, end)?),
+        (0, length) => {
+            let duplicated = if reverse {
+                sequence.get(start..start + length).map(|s| (start, s))
+            } else {
+                start
+                    .checked_sub(length)
+                    .map(|from| (from, &sequence[from..start]))
+            };
+            match duplicated {
+                Some((from, bases_before)) if bases_before == edit.inserted.as_slice() => {
+                    format!("{}dup", range(from, from + length)?)
+                }
+                _ => {
+                    if start == 0 || start >= sequence.len() {
+                        return Err(HgvsError::TerminalInsertion);
+                    }
+                    format!(
+                        "{}ins{}",
+                        range(start - 1, start + 1)?,
+                        bases(&edit.inserted)
+                    )
+                }
+            }
+        }
+        _ => format!("{}delins{}", range(start, end)?, bases(&edit.inserted)),
+    })
+}
+
+/// 参照・変異タンパク質（終止 '*' まで）の比較から p. 表記を作成
+fn describe_protein_change(reference: &[char], alternate: &[char], frameshift: bool) -> String {
+    let aa = |residue: char| three_letter_code(residue);
+    let residues = |slice: &[char]| slice.iter().map(|&r| aa(r)).collect::<String>();
+
+    let i = reference
+        .iter()
+        .zip(alternate)
+        .take_while(|(r, a)| r == a)
+        .count();
+    if i == reference.len() && i == alternate.len() {
+        return "p.(=)".to_string();
+    }
+    if i == 0 {
+        return format!("p.({}1?)", aa(reference.first().copied().unwrap_or('M')));
+    }
+    let (Some(&ref_aa), alt_aa) = (reference.get(i), alternate.get(i).copied()) else {
+        return "p.?".to_string();
+    };
+    let alt_name = alt_aa.map(aa).unwrap_or("Xaa");
+    let stop_after = |from: usize| {
+        alternate[from..]
+            .iter()
+            .position(|&r| r == '*')
+            .map(|k| (k + 1).to_string())
+            .unwrap_or_else(|| "?".to_string())
+    };
+
+    if ref_aa == '*' {
+        return format!(
+            "p.(Ter{}{}extTer{})",
+            i + 1,
+            alt_name,
+            stop_after((i + 1).min(alternate.len()))
+        );
+    }
+    if alt_aa == Some('*') {
+        return format!("p.({}{}Ter)", aa(ref_aa), i + 1);
+    }
+    if frameshift {
+        return format!(
+            "p.({}{}{}fsTer{})",
+            aa(ref_aa),
+            i + 1,
+            alt_name,
+            stop_after(i)
+        );
+    }
+
+    let suffix = reference[i..]
+        .iter()
+        .rev()
+        .zip(alternate[i..].iter().rev())
+        .take_while(|(r, a)| r == a)
+        .count();
+    let ref_mid = &reference[i..reference.len() - suffix];
+    let alt_mid = &alternate[i..alternate.len() - suffix];
+    let span = |from: usize, slice: &[char]| {
+        if slice.len() == 1 {
+            format!("{}{}", aa(slice[0]), from + 1)
+        } else {
+            format!(
+                "{}{}_{}{}",
+                aa(slice[0]),
+                from + 1,
+                aa(slice[slice.len() - 1]),
+                from + slice.len()
+            )
+        }
+    };
+
+    match (ref_mid.len(), alt_mid.len()) {
+        (1, 1) => format!("p.({}{}{})", aa(ref_aa), i + 1, alt_name),
+        (_, 0) => format!("p.({}del)", span(i, ref_mid)),
+        (0, length) => {
+            if i >= length && &reference[i - length..i] == alt_mid {
+                format!("p.({}dup)", span(i - length, alt_mid))
+            } else {
+                format!(
+                    "p.({}{}_{}{}ins{})",
+                    aa(reference[i - 1]),
+                    i,
+                    aa(ref_aa),
+                    i + 1,
+                    residues(alt_mid)
+                )
+            }
+        }
+        _ => format!("p.({}delins{})", span(i, ref_mid), residues(alt_mid)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 5'UTR(3) + ATG AAA GGC TGG CTG TAA + 3'UTR
+    const SEQUENCE: &str = "CCCATGAAAGGCTGGCTGTAAGCGCGC";
+
+    fn cds(strand: Strand) -> SequenceFeature {
+        let mut feature = SequenceFeature::new("CDS", 3, 21, strand);
+        feature.id = "feat_1".to_string();
+        feature
+    }
+
+    fn describe(position: usize, reference: &str, alternate: &str) -> HgvsDescription {
+        HgvsService::new()
+            .describe(
+                SEQUENCE,
+                &Variant::new(position, reference, alternate),
+                Some(&cds(Strand::Forward)),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_substitutions() {
+        let missense = describe(7, "A", "G");
+        assert_eq!(missense.genomic, "g.8A>G");
+        assert_eq!(missense.coding.as_deref(), Some("c.5A>G"));
+        assert_eq!(missense.protein.as_deref(), Some("p.(Lys2Arg)"));
+
+        // TGG -> TAG
+        assert_eq!(
+            describe(13, "G", "A").protein.as_deref(),
+            Some("p.(Trp4Ter)")
+        );
+        // AAA -> AAG（同義）
+        assert_eq!(describe(8, "A", "G").protein.as_deref(), Some("p.(=)"));
+        // 5'UTR
+        let utr = describe(1, "C", "T");
+        assert_eq!(utr.coding.as_deref(), Some("c.-2C>T"));
+        assert_eq!(utr.protein, None);
+    }
+
+    #[test]
+    fn test_indels_are_shifted_3_prime() {
+        // AAA の1塩基欠失は最も3'側の c.6 として記述され、フレームシフトとなる
+        let deletion = describe(6, "A", "");
+        assert_eq!(deletion.genomic, "g.9del");
+        assert_eq!(deletion.coding.as_deref(), Some("c.6del"));
+        assert!(deletion.protein.unwrap().contains("fsTer"));
+
+        // GGC の重複（インフレーム）
+        let dup = describe(12, "", "GGC");
+        assert_eq!(dup.coding.as_deref(), Some("c.7_9dup"));
+        assert_eq!(dup.protein.as_deref(), Some("p.(Gly3dup)"));
+
+        // VCF形式のアンカー塩基付き挿入
+        let ins = describe(8, "A", "ATTT");
+        assert_eq!(ins.coding.as_deref(), Some("c.6_7insTTT"));
+        assert_eq!(ins.protein.as_deref(), Some("p.(Lys2_Gly3insPhe)"));
+    }
+
+    #[test]
+    fn test_reverse_strand_cds() {
+        let sequence = iupac::reverse_complement(SEQUENCE);
+        let mut feature = cds(Strand::Reverse);
+        feature.start = sequence.len() - 21;
+        feature.end = sequence.len() - 3;
+
+        // 順鎖テストの c.5A>G と同じ変化を逆鎖側から記述
+        let position = sequence.len() - 1 - 7;
+        let description = HgvsService::new()
+            .describe(&sequence, &Variant::new(position, "T", "C"), Some(&feature))
+            .unwrap();
+        assert_eq!(description.coding.as_deref(), Some("c.5A>G"));
+        assert_eq!(description.protein.as_deref(), Some("p.(Lys2Arg)"));
+
+        assert!(matches!(
+            HgvsService::new().describe(&sequence, &Variant::new(0, "A", "C"), None),
+            Err(HgvsError::ReferenceMismatch(_, _))
+        ));
+    }
+}
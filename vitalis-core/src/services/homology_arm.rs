@@ -0,0 +1,346 @@
+// Service layer: Homology arms, donor construct and junction primers for recombineering / HDR
+use crate::domain::feature::SequenceFeature;
+use crate::domain::homology_arm::{
+    HomologyArm, HomologyArmDesign, HomologyArmParams, Junction, JunctionPrimers,
+};
+use crate::domain::iupac;
+use crate::domain::primer::{PrimerDesignParams, PrimerDesignService, PrimerPair};
+use crate::domain::Strand;
+use crate::services::{AmpliconQcService, PrimerDesignServiceImpl};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HomologyArmError {
+    #[error("Edit site {0}..{1} is invalid for a sequence of length {2}")]
+    InvalidSite(usize, usize, usize),
+    #[error("Nothing to edit: the site is empty and no insert was given")]
+    EmptyEdit,
+    #[error("Invalid insert sequence")]
+    InvalidInsert,
+    #[error("Arm length must be greater than 0")]
+    ZeroArmLength,
+}
+
+/// 検証プライマーの1本を置く位置（anchor 付近を探し、[min, max) に収まる候補だけ使う）
+struct PrimerWindow {
+    anchor: usize,
+    min: usize,
+    max: usize,
+}
+
+impl PrimerWindow {
+    fn contains(&self, position: usize, length: usize) -> bool {
+        position >= self.min && position + length <= self.max
+    }
+}
+
+/// Homology arm design service
+pub struct HomologyArmService {
+    primer_service: PrimerDesignServiceImpl,
+    qc_service: AmpliconQcService,
+}
+
+impl Default for HomologyArmService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HomologyArmService {
+    pub fn new() -> Self {
+        Self {
+            primer_service: PrimerDesignServiceImpl::new(),
+            qc_service: AmpliconQcService::new(),
+        }
+    }
+
+    /// 鋳型の [site_start, site_end) を insert で置き換えるドナーを設計する。
+    /// 挿入のみは site_start == site_end、欠失のみは insert を空にする
+    pub fn design(
+        &self,
+        template: &str,
+        site_start: usize,
+        site_end: usize,
+        insert: &str,
+        params: &HomologyArmParams,
+    ) -> Result<HomologyArmDesign, HomologyArmError> {
+        if site_start > site_end || site_end > template.len() {
+            return Err(HomologyArmError::InvalidSite(
+                site_start,
+                site_end,
+                template.len(),
+            ));
+        }
+        if site_start == site_end && insert.is_empty() {
+            return Err(HomologyArmError::EmptyEdit);
+        }
+        if !insert.is_empty() && !iupac::is_nucleotide(insert) {
+            return Err(HomologyArmError::InvalidInsert);
+        }
+        if params.arm_length == 0 {
+            return Err(HomologyArmError::ZeroArmLength);
+        }
+
+        let template = template.to_ascii_uppercase();
+        let insert = insert.to_ascii_uppercase();
+        let mut warnings = Vec::new();
+
+        // アームは配列端で切り詰める
+        let left_start = site_start.saturating_sub(params.arm_length);
+        let right_end = (site_end + params.arm_length).min(template.len());
+        if site_start - left_start < params.arm_length {
+            warnings.push(format!(
+                "Left arm truncated to {} bp by the sequence start",
+                site_start - left_start
+            ));
+        }
+        if right_end - site_end < params.arm_length {
+            warnings.push(format!(
+                "Right arm truncated to {} bp by the sequence end",
+                right_end - site_end
+            ));
+        }
+        let left_arm = self.arm(
+            &template,
+            left_start,
+            site_start,
+            "Left arm",
+            params,
+            &mut warnings,
+        );
+        let right_arm = self.arm(
+            &template,
+            site_end,
+            right_end,
+            "Right arm",
+            params,
+            &mut warnings,
+        );
+
+        let insert_start = left_arm.sequence.len();
+        let insert_end = insert_start + insert.len();
+        let donor_sequence = format!("{}{}{}", left_arm.sequence, insert, right_arm.sequence);
+        let mut features = vec![
+            labeled_feature(0, insert_start, "left homology arm"),
+            labeled_feature(insert_end, donor_sequence.len(), "right homology arm"),
+        ];
+        if !insert.is_empty() {
+            features.insert(1, labeled_feature(insert_start, insert_end, "insert"));
+        }
+
+        // 編集後の遺伝子座: [left_start, site_start) 左アーム、[site_start, edited_insert_end) 挿入配列
+        let edited = format!(
+            "{}{}{}",
+            &template[..site_start],
+            insert,
+            &template[site_end..]
+        );
+        let edited_insert_end = site_start + insert.len();
+        let edited_right_end = right_end - site_end + edited_insert_end;
+        let offset = params.primer_offset;
+        let outside_left = PrimerWindow {
+            anchor: left_start.saturating_sub(offset),
+            min: 0,
+            max: left_start,
+        };
+        let outside_right = PrimerWindow {
+            anchor: (edited_right_end + offset).min(edited.len()),
+            min: edited_right_end,
+            max: edited.len(),
+        };
+
+        let verification = if insert.is_empty() {
+            vec![self.junction_primers(
+                &edited,
+                Junction::Spanning,
+                &outside_left,
+                &outside_right,
+                &params.primer,
+            )]
+        } else {
+            let inset = (insert.len() / 2).min(offset);
+            vec![
+                self.junction_primers(
+                    &edited,
+                    Junction::Left,
+                    &outside_left,
+                    &PrimerWindow {
+                        anchor: site_start + inset,
+                        min: site_start,
+                        max: edited_insert_end,
+                    },
+                    &params.primer,
+                ),
+                self.junction_primers(
+                    &edited,
+                    Junction::Right,
+                    &PrimerWindow {
+                        anchor: edited_insert_end - inset,
+                        min: site_start,
+                        max: edited_insert_end,
+                    },
+                    &outside_right,
+                    &params.primer,
+                ),
+            ]
+        };
+        for primers in verification.iter().filter(|p| p.pair.is_none()) {
+            warnings.push(format!(
+                "No verification primers found for the {:?} junction",
+                primers.junction
+            ));
+        }
+
+        Ok(HomologyArmDesign {
+            donor_seq_id: String::new(),
+            donor_sequence,
+            site_start,
+            site_end,
+            left_arm,
+            right_arm,
+            insert_start,
+            insert_end,
+            verification,
+            features,
+            warnings,
+        })
+    }
+
+    /// アームを切り出し、リピート・ヘアピン・GC 偏りをスクリーニングする
+    fn arm(
+        &self,
+        template: &str,
+        start: usize,
+        end: usize,
+        name: &str,
+        params: &HomologyArmParams,
+        warnings: &mut Vec<String>,
+    ) -> HomologyArm {
+        let sequence = template[start..end].to_string();
+        let qc = self.qc_service.analyze(&sequence, &params.qc);
+        warnings.extend(
+            qc.warnings
+                .iter()
+                .map(|warning| format!("{}: {}", name, warning.message)),
+        );
+        HomologyArm {
+            start,
+            end,
+            sequence,
+            qc,
+        }
+    }
+
+    /// 一方が編集部の外側、もう一方が内側（欠失では反対側の外側）に結合するペアを選ぶ
+    fn junction_primers(
+        &self,
+        edited: &str,
+        junction: Junction,
+        forward: &PrimerWindow,
+        reverse: &PrimerWindow,
+        params: &PrimerDesignParams,
+    ) -> JunctionPrimers {
+        let pair = if forward.anchor < reverse.anchor && forward.min < forward.max {
+            self.primer_service
+                .design_primers(edited, forward.anchor, reverse.anchor, params)
+                .ok()
+                .and_then(|result| {
+                    result.pairs.into_iter().find(|pair: &PrimerPair| {
+                        forward.contains(pair.forward.position, pair.forward.length)
+                            && reverse.contains(pair.reverse.position, pair.reverse.length)
+                    })
+                })
+        } else {
+            None
+        };
+        JunctionPrimers { junction, pair }
+    }
+}
+
+fn labeled_feature(start: usize, end: usize, label: &str) -> SequenceFeature {
+    let mut feature = SequenceFeature::new("misc_feature", start, end, Strand::Forward);
+    feature
+        .qualifiers
+        .insert("label".to_string(), label.to_string());
+    feature
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 再現可能な擬似乱数配列（GC 約50%）
+    fn random_sequence(length: usize, seed: u64) -> String {
+        let mut state = seed;
+        (0..length)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4] as char
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_insertion_donor_and_junction_primers() {
+        let template = random_sequence(3000, 7);
+        let insert = random_sequence(600, 11);
+        let mut params = HomologyArmParams {
+            arm_length: 300,
+            ..HomologyArmParams::default()
+        };
+        params.primer.tm_min = 40.0;
+        params.primer.tm_max = 80.0;
+        params.primer.gc_min = 20.0;
+        params.primer.gc_max = 80.0;
+        let design = HomologyArmService::new()
+            .design(&template, 1500, 1500, &insert, &params)
+            .unwrap();
+
+        assert_eq!(design.left_arm.start, 1200);
+        assert_eq!(design.right_arm.end, 1800);
+        assert_eq!(
+            design.donor_sequence,
+            format!(
+                "{}{}{}",
+                &template[1200..1500],
+                insert,
+                &template[1500..1800]
+            )
+        );
+        assert_eq!((design.insert_start, design.insert_end), (300, 900));
+        assert_eq!(design.features.len(), 3);
+
+        let junctions: Vec<Junction> = design.verification.iter().map(|v| v.junction).collect();
+        assert_eq!(junctions, vec![Junction::Left, Junction::Right]);
+        let left = design.verification[0].pair.as_ref().unwrap();
+        assert!(left.forward.position + left.forward.length <= 1200);
+        assert!(left.reverse.position >= 1500 && left.reverse.position < 2100);
+        let right = design.verification[1].pair.as_ref().unwrap();
+        assert!(right.forward.position >= 1500);
+        assert!(right.reverse.position >= 2400);
+    }
+
+    #[test]
+    fn test_deletion_near_sequence_start() {
+        let template = random_sequence(2000, 3);
+        let service = HomologyArmService::new();
+        let design = service
+            .design(&template, 200, 400, "", &HomologyArmParams::default())
+            .unwrap();
+
+        assert_eq!(design.left_arm.sequence, &template[0..200]);
+        assert_eq!(design.right_arm.sequence, &template[400..900]);
+        assert!(design.warnings[0].starts_with("Left arm truncated to 200 bp"));
+        assert_eq!(design.verification.len(), 1);
+        assert_eq!(design.verification[0].junction, Junction::Spanning);
+        // 左アームの外側に余地がないので検証プライマーは置けない
+        assert!(design.verification[0].pair.is_none());
+
+        assert!(matches!(
+            service.design(&template, 300, 300, "", &HomologyArmParams::default()),
+            Err(HomologyArmError::EmptyEdit)
+        ));
+    }
+}
@@ -0,0 +1,314 @@
+// Service layer: HRM genotyping amplicon tiling across a variant hotspot
+use crate::domain::primer::{
+    HrmAmplicon, HrmTilingParams, HrmTilingResult, PrimerDesignService, PrimerDirection,
+    VariantMeltShift,
+};
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+use crate::domain::variant::Variant;
+use crate::services::PrimerDesignServiceImpl;
+
+/// Tm/GCフィルタを通過したプライマー候補
+struct SiteCandidate {
+    position: usize,
+    length: usize,
+    tm: f32,
+}
+
+/// HRM tiling service
+pub struct HrmTilingService {
+    primer_service: PrimerDesignServiceImpl,
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for HrmTilingService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HrmTilingService {
+    pub fn new() -> Self {
+        Self {
+            primer_service: PrimerDesignServiceImpl::new(),
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// Tile short amplicons over the variants in [start, end) so that every
+    /// variant sits in the internal region (between the primers) of exactly
+    /// one amplicon. `variants` is the full, position-sorted variant list of
+    /// the template; variants outside the hotspot still keep primers off them.
+    pub fn design(
+        &self,
+        template: &str,
+        variants: &[Variant],
+        start: usize,
+        end: usize,
+        params: &HrmTilingParams,
+    ) -> HrmTilingResult {
+        let targets: Vec<&Variant> = variants.iter().filter(|v| v.overlaps(start, end)).collect();
+
+        let mut amplicons = Vec::new();
+        let mut uncovered_variants = Vec::new();
+        let mut i = 0;
+
+        while i < targets.len() {
+            // 1つの増幅産物にできるだけ多くのバリアントを含める
+            let placed = (i..targets.len()).rev().find_map(|j| {
+                let group = &targets[i..=j];
+                let span = group_end(group) - group[0].position;
+                if span + 2 * params.primer.length_min > params.max_amplicon_length {
+                    return None;
+                }
+                self.tile_group(template, variants, group, params)
+                    .map(|amplicon| (j, amplicon))
+            });
+
+            match placed {
+                Some((j, amplicon)) => {
+                    amplicons.push(amplicon);
+                    i = j + 1;
+                }
+                None => {
+                    uncovered_variants.push(targets[i].id.clone());
+                    i += 1;
+                }
+            }
+        }
+
+        HrmTilingResult {
+            amplicons,
+            uncovered_variants,
+            params: params.clone(),
+            provenance: Some(self.calculator.provenance()),
+        }
+    }
+
+    /// グループのバリアントのみを内部領域に含む増幅産物を設計
+    fn tile_group(
+        &self,
+        template: &str,
+        variants: &[Variant],
+        group: &[&Variant],
+        params: &HrmTilingParams,
+    ) -> Option<HrmAmplicon> {
+        let first = group[0].position;
+        let last_end = group_end(group);
+        let in_group = |v: &Variant| group.iter().any(|g| g.id == v.id);
+
+        // グループ外のバリアントが内部領域に入らないよう境界を決める
+        let mut lower = 0;
+        let mut upper = template.len();
+        for variant in variants.iter().filter(|v| !in_group(v)) {
+            if variant.overlaps(first, last_end) {
+                return None;
+            }
+            if variant.end() <= first {
+                lower = lower.max(variant.end());
+            } else if variant.position >= last_end {
+                upper = upper.min(variant.position);
+            }
+        }
+
+        let site_is_clear =
+            |pos: usize, len: usize| !variants.iter().any(|v| v.overlaps(pos, pos + len));
+        let primer = &params.primer;
+
+        let mut forward_sites = Vec::new();
+        let mut reverse_sites = Vec::new();
+        for length in primer.length_min..=primer.length_max {
+            for internal_start in lower.max(length)..=first {
+                let pos = internal_start - length;
+                if pos + params.max_amplicon_length < last_end + primer.length_min {
+                    continue;
+                }
+                if let Some(site) =
+                    self.site_candidate(template, pos, length, PrimerDirection::Forward, params)
+                {
+                    if site_is_clear(pos, length) {
+                        forward_sites.push(site);
+                    }
+                }
+            }
+
+            for pos in last_end..=upper {
+                if pos + length > template.len()
+                    || pos + length + primer.length_min > first + params.max_amplicon_length
+                {
+                    break;
+                }
+                if let Some(site) =
+                    self.site_candidate(template, pos, length, PrimerDirection::Reverse, params)
+                {
+                    if site_is_clear(pos, length) {
+                        reverse_sites.push(site);
+                    }
+                }
+            }
+        }
+
+        // 最大長以内で、Tmが最適値に近く揃ったペアを選ぶ
+        let (forward, reverse) = forward_sites
+            .iter()
+            .flat_map(|f| reverse_sites.iter().map(move |r| (f, r)))
+            .filter(|(f, r)| r.position + r.length - f.position <= params.max_amplicon_length)
+            .min_by(|(f1, r1), (f2, r2)| {
+                let penalty = |f: &SiteCandidate, r: &SiteCandidate| {
+                    (f.tm - primer.tm_optimal).abs()
+                        + (r.tm - primer.tm_optimal).abs()
+                        + (f.tm - r.tm).abs()
+                };
+                penalty(f1, r1)
+                    .partial_cmp(&penalty(f2, r2))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(
+                        (r1.position + r1.length - f1.position)
+                            .cmp(&(r2.position + r2.length - f2.position)),
+                    )
+            })?;
+
+        let amplicon_start = forward.position;
+        let amplicon_end = reverse.position + reverse.length;
+        let sequence = template[amplicon_start..amplicon_end].to_string();
+        let predicted_tm = self
+            .calculator
+            .calculate_tm_nearest_neighbor(&sequence)
+            .ok();
+
+        let variants = group
+            .iter()
+            .map(|variant| {
+                let delta_tm = variant
+                    .apply(&sequence, amplicon_start)
+                    .and_then(|mutated| {
+                        self.calculator.calculate_tm_nearest_neighbor(&mutated).ok()
+                    })
+                    .zip(predicted_tm)
+                    .map(|(mutated_tm, wild_tm)| mutated_tm - wild_tm);
+                VariantMeltShift {
+                    variant_id: variant.id.clone(),
+                    position: variant.position,
+                    delta_tm,
+                }
+            })
+            .collect();
+
+        Some(HrmAmplicon {
+            forward: self.primer_service.evaluate_primer(
+                template[forward.position..forward.position + forward.length].to_string(),
+                forward.position,
+                PrimerDirection::Forward,
+            ),
+            reverse: self.primer_service.evaluate_primer(
+                self.primer_service
+                    .reverse_complement(&template[reverse.position..amplicon_end]),
+                reverse.position,
+                PrimerDirection::Reverse,
+            ),
+            start: amplicon_start,
+            end: amplicon_end,
+            internal_start: forward.position + forward.length,
+            internal_end: reverse.position,
+            sequence,
+            predicted_tm,
+            variants,
+        })
+    }
+
+    fn site_candidate(
+        &self,
+        template: &str,
+        position: usize,
+        length: usize,
+        direction: PrimerDirection,
+        params: &HrmTilingParams,
+    ) -> Option<SiteCandidate> {
+        let site = &template[position..position + length];
+        let primer_seq = if direction == PrimerDirection::Forward {
+            site.to_string()
+        } else {
+            self.primer_service.reverse_complement(site)
+        };
+
+        let tm = self.primer_service.calculate_tm(&primer_seq);
+        let gc = self.primer_service.calculate_gc_content(&primer_seq);
+        let primer = &params.primer;
+        if tm < primer.tm_min || tm > primer.tm_max || gc < primer.gc_min || gc > primer.gc_max {
+            return None;
+        }
+
+        Some(SiteCandidate {
+            position,
+            length,
+            tm,
+        })
+    }
+}
+
+fn group_end(group: &[&Variant]) -> usize {
+    group.iter().map(|v| v.end()).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "ATGCGTACGTTAGCCTAGGCTAACGTAGCTAGGATCCGATCGTACGATCGGCTAGCTAGGCTAACGATCGATGCTAGCTAGCGGATCCGTAGCTAGTCGATCGTAGCTAGGCTAGCGATCGATCGGATCGTAGCTAGCTAGCGATCGTACGATCGATGCTAGC";
+
+    fn variants() -> Vec<Variant> {
+        let mut variants = vec![
+            Variant::new(60, "G", "A"),
+            Variant::new(66, "C", "T"),
+            Variant::new(140, "A", "G"),
+        ];
+        for (i, variant) in variants.iter_mut().enumerate() {
+            variant.id = format!("var_{}", i + 1);
+        }
+        variants
+    }
+
+    #[test]
+    fn test_each_variant_in_exactly_one_internal_region() {
+        let mut params = HrmTilingParams::default();
+        params.primer.tm_min = 40.0;
+        params.primer.tm_max = 80.0;
+        params.primer.gc_min = 20.0;
+        params.primer.gc_max = 80.0;
+
+        let variants = variants();
+        let result =
+            HrmTilingService::new().design(TEMPLATE, &variants, 0, TEMPLATE.len(), &params);
+
+        assert!(result.uncovered_variants.is_empty());
+        for variant in &variants {
+            let containing = result
+                .amplicons
+                .iter()
+                .filter(|a| a.internal_start <= variant.position && variant.end() <= a.internal_end)
+                .count();
+            assert_eq!(
+                containing, 1,
+                "{} is covered {} times",
+                variant.id, containing
+            );
+        }
+        for amplicon in &result.amplicons {
+            assert!(amplicon.end - amplicon.start <= params.max_amplicon_length);
+            assert!(amplicon.variants.iter().all(|v| v.delta_tm.is_some()));
+        }
+    }
+
+    #[test]
+    fn test_unplaceable_variant_is_reported() {
+        let variants = variants();
+        let params = HrmTilingParams {
+            max_amplicon_length: 30,
+            ..Default::default()
+        };
+
+        let result =
+            HrmTilingService::new().design(TEMPLATE, &variants, 0, TEMPLATE.len(), &params);
+        assert!(result.amplicons.is_empty());
+        assert_eq!(result.uncovered_variants.len(), 3);
+    }
+}
@@ -0,0 +1,112 @@
+// Service layer: Kyte-Doolittle hydropathy and transmembrane segment prediction
+use crate::domain::protein::{
+    kyte_doolittle, HydropathyPoint, HydropathyProfile, TransmembraneSegment,
+};
+
+/// Kyte & Doolittle (1982) の膜貫通領域判定に用いるウィンドウ長と閾値
+const TM_WINDOW: usize = 19;
+const TM_THRESHOLD: f64 = 1.6;
+
+/// Hydropathy analysis service
+pub struct HydropathyService;
+
+impl Default for HydropathyService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HydropathyService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `window` 残基の移動平均プロファイルと、19残基ウィンドウの平均が
+    /// 1.6 以上となる領域を膜貫通セグメントとして返す
+    pub fn analyze(&self, protein: &str, window: usize) -> HydropathyProfile {
+        let scores: Vec<f64> = protein
+            .chars()
+            .filter(|&c| c != '*')
+            .map(|c| kyte_doolittle(c).unwrap_or(0.0))
+            .collect();
+        let window = window.max(1);
+
+        let points = window_means(&scores, window)
+            .into_iter()
+            .enumerate()
+            .map(|(start, score)| HydropathyPoint {
+                position: start + window / 2,
+                score,
+            })
+            .collect();
+
+        HydropathyProfile {
+            window,
+            points,
+            tm_segments: self.transmembrane_segments(&scores),
+        }
+    }
+
+    fn transmembrane_segments(&self, scores: &[f64]) -> Vec<TransmembraneSegment> {
+        let mut marked = vec![false; scores.len()];
+        for (start, mean) in window_means(scores, TM_WINDOW).into_iter().enumerate() {
+            if mean >= TM_THRESHOLD {
+                marked[start..start + TM_WINDOW].fill(true);
+            }
+        }
+
+        let mut segments = Vec::new();
+        let mut position = 0;
+        while position < marked.len() {
+            if !marked[position] {
+                position += 1;
+                continue;
+            }
+            let start = position;
+            while position < marked.len() && marked[position] {
+                position += 1;
+            }
+            let region = &scores[start..position];
+            segments.push(TransmembraneSegment {
+                start,
+                end: position,
+                mean_score: region.iter().sum::<f64>() / region.len() as f64,
+            });
+        }
+        segments
+    }
+}
+
+fn window_means(scores: &[f64], window: usize) -> Vec<f64> {
+    if scores.len() < window {
+        return Vec::new();
+    }
+    scores
+        .windows(window)
+        .map(|w| w.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_and_tm_segment() {
+        let protein = format!(
+            "{}{}{}",
+            "DEKR".repeat(10),
+            "LIVLAVLIFLLVAIVLLAI",
+            "DEKR".repeat(10)
+        );
+        let profile = HydropathyService::new().analyze(&protein, 9);
+
+        assert_eq!(profile.points.len(), protein.len() - 8);
+        assert_eq!(profile.points[0].position, 4);
+
+        assert_eq!(profile.tm_segments.len(), 1);
+        let segment = &profile.tm_segments[0];
+        assert!(segment.start <= 40 && segment.end >= 59);
+        assert!(segment.mean_score > 0.0);
+    }
+}
@@ -0,0 +1,1567 @@
+// Service layer: Bounds checks on command inputs before any work starts
+use crate::domain::assembly::AssemblyPrimerParams;
+use crate::domain::bed::BedTrack;
+use crate::domain::codon_usage::CodonUsageParams;
+use crate::domain::crispr::GuideDesignParams;
+use crate::domain::dilution::StandardCurveParams;
+use crate::domain::dotplot;
+use crate::domain::export_format::ExportFormat;
+use crate::domain::feature::FeatureDisplay;
+use crate::domain::homology_arm::HomologyArmParams;
+use crate::domain::iupac;
+use crate::domain::linker::LinkerParams;
+use crate::domain::melt_curve::{MeltCurveParams, MAX_MELT_POINTS};
+use crate::domain::melting_map::{MeltingMapParams, TmGridParams};
+use crate::domain::mutation::MutationRates;
+use crate::domain::nullomer::NullomerParams;
+use crate::domain::oligo::Oligo;
+use crate::domain::primer::{
+    HrmTilingParams, JunctionPrimerParams, PanelStressParams, PrimerDesignParams, QpcrAssayParams,
+};
+use crate::domain::probe_label::{ProbeLabelInput, ProbeLabelParams};
+use crate::domain::protein_motif::ProteinMotif;
+use crate::domain::rbs::RbsParams;
+use crate::domain::read_stats::ReadSetParams;
+use crate::domain::reference::EntrezSettings;
+use crate::domain::region::Region;
+use crate::domain::repeat::{self, RepeatParams};
+use crate::domain::restriction::DigestProtocolParams;
+use crate::domain::screening::ScreeningParams;
+use crate::domain::search::SearchOptions;
+use crate::domain::thermodynamics::SaltCorrectionParams;
+use crate::domain::validation::{
+    ConstraintViolation, InputLimits, ValidationErrors, ViolationCode,
+};
+use crate::domain::variant::Variant;
+use crate::domain::StatsOptions;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Display;
+
+/// 制約違反を集める（最初の違反で止めず、全て報告する）
+#[derive(Default)]
+struct Violations(Vec<ConstraintViolation>);
+
+impl Violations {
+    fn push(&mut self, field: &str, code: ViolationCode, message: String) {
+        self.0.push(ConstraintViolation {
+            field: field.to_string(),
+            code,
+            message,
+        });
+    }
+
+    /// min ≤ value ≤ max（NaN は常に違反）
+    fn within<T: PartialOrd + Display + Serialize>(
+        &mut self,
+        field: &str,
+        value: T,
+        min: T,
+        max: T,
+    ) {
+        if !(value >= min && value <= max) {
+            self.push(
+                field,
+                ViolationCode::OutOfRange {
+                    min: json(&min),
+                    max: json(&max),
+                    value: json(&value),
+                },
+                format!("must be between {} and {} (got {})", min, max, value),
+            );
+        }
+    }
+
+    fn at_most(&mut self, field: &str, value: usize, max: usize) {
+        if value > max {
+            self.push(
+                field,
+                ViolationCode::TooLarge { max, value },
+                format!("must be at most {} (got {})", max, value),
+            );
+        }
+    }
+
+    fn finite(&mut self, field: &str, value: f64) {
+        if !value.is_finite() {
+            self.push(
+                field,
+                ViolationCode::NotFinite {
+                    value: json(&value),
+                },
+                format!("must be a finite number (got {})", value),
+            );
+        }
+    }
+
+    fn ordered<T: PartialOrd + Display + Serialize>(&mut self, low: (&str, T), high: (&str, T)) {
+        if low.1 > high.1 {
+            self.push(
+                low.0,
+                ViolationCode::ExceedsField {
+                    other: high.0.to_string(),
+                    value: json(&low.1),
+                    other_value: json(&high.1),
+                },
+                format!("must not exceed {} ({} > {})", high.0, low.1, high.1),
+            );
+        }
+    }
+
+    fn empty(&mut self, field: &str) {
+        self.push(field, ViolationCode::Empty, "must not be empty".to_string());
+    }
+
+    /// 許容ミスマッチ数は配列長未満
+    fn below_length(&mut self, field: &str, mismatches: usize, what: &str, length: usize) {
+        if length > 0 && mismatches >= length {
+            self.push(
+                field,
+                ViolationCode::NotLessThanLength { length },
+                format!("must be less than the {} length ({})", what, length),
+            );
+        }
+    }
+
+    fn iupac(&mut self, field: &str, sequence: &str) {
+        if let Some(invalid) = sequence.chars().find(|&c| iupac::expand(c).is_empty()) {
+            self.push(
+                field,
+                ViolationCode::NotIupacCode { character: invalid },
+                format!(
+                    "contains a character that is not an IUPAC code: '{}'",
+                    invalid
+                ),
+            );
+        }
+    }
+
+    fn finish(self) -> Result<(), ValidationErrors> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors { violations: self.0 })
+        }
+    }
+}
+
+/// 違反した値をパラメータとして渡す（NaN・無限大は null）
+fn json<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// Input validation service
+pub struct InputValidationService {
+    limits: InputLimits,
+}
+
+impl Default for InputValidationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputValidationService {
+    pub fn new() -> Self {
+        Self::with_limits(InputLimits::default())
+    }
+
+    pub fn with_limits(limits: InputLimits) -> Self {
+        Self { limits }
+    }
+
+    pub fn limits(&self) -> &InputLimits {
+        &self.limits
+    }
+
+    /// 取り込むテキスト（FASTA・GenBank・アラインメントなど）の長さ
+    pub fn check_text(&self, field: &str, text: &str) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.at_most(field, text.len(), self.limits.max_text_length);
+        violations.finish()
+    }
+
+    /// 一度に取得・整形・描画する領域の長さ（配列長との整合は Region::validate で見る）
+    pub fn check_region(&self, field: &str, region: &Region) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, field, region);
+        violations.finish()
+    }
+
+    /// スライディングウィンドウの長さとステップ
+    pub fn check_window(
+        &self,
+        window_size: usize,
+        step: Option<usize>,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("window_size", window_size, 1, self.limits.max_window_size);
+        if let Some(step) = step {
+            violations.within("step", step, 1, self.limits.max_window_size);
+        }
+        violations.finish()
+    }
+
+    /// BED に書き出す区間の条件
+    pub fn check_bed_track(&self, track: &BedTrack) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        match track {
+            BedTrack::Orfs { min_length, .. } => {
+                violations.within(
+                    "track.min_length",
+                    *min_length,
+                    1,
+                    self.limits.max_region_length,
+                );
+            }
+            BedTrack::GcWindows {
+                window_size,
+                step,
+                threshold,
+            } => {
+                violations.within(
+                    "track.window_size",
+                    *window_size,
+                    1,
+                    self.limits.max_window_size,
+                );
+                violations.within("track.step", *step, 1, self.limits.max_window_size);
+                violations.within("track.threshold", *threshold, 0.0, 100.0);
+            }
+            BedTrack::Features { .. } | BedTrack::PrimerSites => {}
+        }
+        violations.finish()
+    }
+
+    /// ギャップとみなす N の連続の長さ
+    pub fn check_stats_options(&self, options: &StatsOptions) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        if let Some(min_gap_length) = options.min_gap_length {
+            violations.within(
+                "options.min_gap_length",
+                min_gap_length,
+                1,
+                self.limits.max_window_size,
+            );
+        }
+        violations.finish()
+    }
+
+    /// Tm・GC 計算などに渡す単一のプライマー配列
+    pub fn check_oligo_sequence(
+        &self,
+        field: &str,
+        sequence: &str,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(field, sequence.len(), 1, self.limits.max_oligo_length);
+        violations.finish()
+    }
+
+    /// OD260・µg・pmol 換算するオリゴ配列と量
+    pub fn check_oligo_amount(&self, sequence: &str, amount: f64) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("sequence", sequence.len(), 1, self.limits.max_oligo_length);
+        violations.within("amount", amount, 0.0, 1e12);
+        violations.finish()
+    }
+
+    pub fn check_primer_design(
+        &self,
+        region: &Region,
+        params: &PrimerDesignParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, "region", region);
+        self.primer_params(&mut violations, "params", params);
+        violations.finish()
+    }
+
+    pub fn check_relaxation(
+        &self,
+        region: &Region,
+        params: &PrimerDesignParams,
+        min_pairs: usize,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, "region", region);
+        self.primer_params(&mut violations, "params", params);
+        // 設計結果は上位10組まで
+        violations.within("min_pairs", min_pairs, 1, 10);
+        violations.finish()
+    }
+
+    pub fn check_guide_design(
+        &self,
+        region: &Region,
+        params: &GuideDesignParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, "region", region);
+        violations.within("params.gc_min", params.gc_min, 0.0, 100.0);
+        violations.within("params.gc_max", params.gc_max, 0.0, 100.0);
+        violations.ordered(
+            ("params.gc_min", params.gc_min),
+            ("params.gc_max", params.gc_max),
+        );
+        violations.at_most(
+            "params.max_mismatches",
+            params.max_mismatches,
+            self.limits.max_mismatches,
+        );
+        violations.within(
+            "params.max_guides",
+            params.max_guides,
+            1,
+            self.limits.max_page_size,
+        );
+        violations.finish()
+    }
+
+    pub fn check_hrm_tiling(
+        &self,
+        region: &Region,
+        params: &HrmTilingParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, "region", region);
+        violations.within(
+            "params.max_amplicon_length",
+            params.max_amplicon_length,
+            1,
+            self.limits.max_hrm_amplicon_length,
+        );
+        self.primer_params(&mut violations, "params.primer", &params.primer);
+        violations.finish()
+    }
+
+    pub fn check_qpcr_assay(
+        &self,
+        region: &Region,
+        params: &QpcrAssayParams,
+    ) -> Result<(), ValidationErrors> {
+        let limits = &self.limits;
+        let mut violations = Violations::default();
+        violations.at_most(
+            "region.length",
+            region.len(),
+            limits.max_assay_region_length,
+        );
+        self.primer_params(&mut violations, "params.primer", &params.primer);
+
+        violations.within(
+            "params.amplicon_min",
+            params.amplicon_min,
+            1,
+            limits.max_assay_region_length,
+        );
+        violations.within(
+            "params.amplicon_max",
+            params.amplicon_max,
+            1,
+            limits.max_assay_region_length,
+        );
+        violations.ordered(
+            ("params.amplicon_min", params.amplicon_min),
+            ("params.amplicon_max", params.amplicon_max),
+        );
+        violations.within(
+            "params.max_primer_tm_difference",
+            params.max_primer_tm_difference,
+            0.0,
+            100.0,
+        );
+
+        violations.within(
+            "params.probe_length_min",
+            params.probe_length_min,
+            limits.min_primer_length,
+            limits.max_primer_length,
+        );
+        violations.within(
+            "params.probe_length_max",
+            params.probe_length_max,
+            limits.min_primer_length,
+            limits.max_primer_length,
+        );
+        violations.ordered(
+            ("params.probe_length_min", params.probe_length_min),
+            ("params.probe_length_max", params.probe_length_max),
+        );
+        violations.at_most(
+            "params.probe_length_max - probe_length_min",
+            params
+                .probe_length_max
+                .saturating_sub(params.probe_length_min),
+            limits.max_primer_length_span,
+        );
+        violations.within(
+            "params.probe_tm_offset_min",
+            params.probe_tm_offset_min,
+            0.0,
+            50.0,
+        );
+        violations.within(
+            "params.probe_tm_offset_max",
+            params.probe_tm_offset_max,
+            0.0,
+            50.0,
+        );
+        violations.ordered(
+            ("params.probe_tm_offset_min", params.probe_tm_offset_min),
+            ("params.probe_tm_offset_max", params.probe_tm_offset_max),
+        );
+        violations.within("params.probe_gc_min", params.probe_gc_min, 0.0, 100.0);
+        violations.within("params.probe_gc_max", params.probe_gc_max, 0.0, 100.0);
+        violations.ordered(
+            ("params.probe_gc_min", params.probe_gc_min),
+            ("params.probe_gc_max", params.probe_gc_max),
+        );
+        violations.finish()
+    }
+
+    pub fn check_homology_arms(
+        &self,
+        site: &Region,
+        insert: &str,
+        params: &HomologyArmParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, "site", site);
+        violations.at_most("insert", insert.len(), self.limits.max_region_length);
+        violations.within(
+            "params.arm_length",
+            params.arm_length,
+            1,
+            self.limits.max_homology_arm_length,
+        );
+        violations.at_most(
+            "params.primer_offset",
+            params.primer_offset,
+            self.limits.max_homology_arm_length,
+        );
+        violations.within(
+            "params.qc.gc_window",
+            params.qc.gc_window,
+            1,
+            self.limits.max_window_size,
+        );
+        self.primer_params(&mut violations, "params.primer", &params.primer);
+        violations.finish()
+    }
+
+    /// 増幅産物は設計サービスの上限（3000 bp）以下
+    pub fn check_junction_primers(
+        &self,
+        junction_count: usize,
+        params: &JunctionPrimerParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.at_most("junctions", junction_count, self.limits.max_junctions);
+        violations.within(
+            "params.max_amplicon_length",
+            params.max_amplicon_length,
+            100,
+            3000,
+        );
+        self.primer_params(&mut violations, "params.primer", &params.primer);
+        violations.finish()
+    }
+
+    pub fn check_screening(&self, params: &ScreeningParams) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "params.min_band",
+            params.min_band,
+            1,
+            params.max_band.max(1),
+        );
+        violations.at_most(
+            "params.max_band",
+            params.max_band,
+            self.limits.max_region_length,
+        );
+        violations.within("params.resolution", params.resolution, 0.0, 1.0);
+        violations.within("params.max_bands", params.max_bands, 1, 20);
+        violations.within("params.max_enzymes", params.max_enzymes, 1, 2);
+        violations.within("params.max_digests", params.max_digests, 1, 100);
+        violations.within(
+            "params.junction.max_amplicon_length",
+            params.junction.max_amplicon_length,
+            100,
+            3000,
+        );
+        self.primer_params(
+            &mut violations,
+            "params.junction.primer",
+            &params.junction.primer,
+        );
+        violations.finish()
+    }
+
+    pub fn check_export_format(&self, format: &ExportFormat) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        if format.delimiter == '.'
+            || format.delimiter == '-'
+            || format.delimiter == '"'
+            || format.delimiter.is_ascii_digit()
+        {
+            violations.push(
+                "format.delimiter",
+                ViolationCode::InvalidDelimiter {
+                    delimiter: format.delimiter,
+                },
+                format!(
+                    "must not be a digit, '.', '-' or '\"' (got '{}')",
+                    format.delimiter
+                ),
+            );
+        }
+        for (field, precision) in [
+            ("format.temperature_precision", format.temperature_precision),
+            ("format.percent_precision", format.percent_precision),
+            ("format.energy_precision", format.energy_precision),
+            (
+                "format.dimensionless_precision",
+                format.dimensionless_precision,
+            ),
+        ] {
+            violations.at_most(field, precision, 10);
+        }
+        violations.finish()
+    }
+
+    /// 表示色は #RRGGBB（16進数は大文字・小文字どちらでもよい）
+    pub fn check_feature_display(&self, display: &FeatureDisplay) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        let color = display.color.as_bytes();
+        if color.len() != 7 || color[0] != b'#' || !color[1..].iter().all(u8::is_ascii_hexdigit) {
+            violations.push(
+                "display.color",
+                ViolationCode::InvalidColor {
+                    value: display.color.clone(),
+                },
+                format!("must be a #RRGGBB color (got '{}')", display.color),
+            );
+        }
+        violations.finish()
+    }
+
+    /// 4^k ビットの出現表を持つので k は 13（8 MiB）まで
+    pub fn check_nullomers(
+        &self,
+        n_sequences: usize,
+        k: usize,
+        params: &NullomerParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        if n_sequences == 0 {
+            violations.empty("seq_ids");
+        }
+        violations.within("k", k, 1, 13);
+        violations.within("params.gc_min", params.gc_min, 0.0, 100.0);
+        violations.within("params.gc_max", params.gc_max, 0.0, 100.0);
+        violations.ordered(
+            ("params.gc_min", params.gc_min),
+            ("params.gc_max", params.gc_max),
+        );
+        violations.within(
+            "params.max_results",
+            params.max_results,
+            1,
+            self.limits.max_page_size,
+        );
+        violations.finish()
+    }
+
+    /// 断片数・オーバーラップ長とアニーリング部分の条件
+    pub fn check_assembly_primers(
+        &self,
+        n_fragments: usize,
+        overlap_length: usize,
+        params: &AssemblyPrimerParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "fragment_seq_ids",
+            n_fragments,
+            2,
+            self.limits.max_junctions,
+        );
+        // Gibson / In-Fusion で実用的なオーバーラップ長
+        violations.within("overlap_length", overlap_length, 15, 80);
+        violations.within(
+            "params.anneal_length_min",
+            params.anneal_length_min,
+            self.limits.min_primer_length,
+            self.limits.max_primer_length,
+        );
+        violations.within(
+            "params.anneal_length_max",
+            params.anneal_length_max,
+            self.limits.min_primer_length,
+            self.limits.max_primer_length,
+        );
+        violations.ordered(
+            ("params.anneal_length_min", params.anneal_length_min),
+            ("params.anneal_length_max", params.anneal_length_max),
+        );
+        violations.within("params.anneal_tm", params.anneal_tm as f64, 30.0, 80.0);
+        violations.finite("params.min_overlap_tm", params.min_overlap_tm as f64);
+        violations.within(
+            "params.max_overlap_tm_spread",
+            params.max_overlap_tm_spread as f64,
+            0.0,
+            100.0,
+        );
+        violations.finite("params.max_hairpin", params.max_hairpin as f64);
+        violations.finish()
+    }
+
+    /// リンカーの長さ・GC% と生成条件
+    pub fn check_linkers(
+        &self,
+        length: usize,
+        gc_percent: f64,
+        params: &LinkerParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("length", length, 1, self.limits.max_oligo_length);
+        violations.within("gc_percent", gc_percent, 0.0, 100.0);
+        violations.within(
+            "params.max_homopolymer",
+            params.max_homopolymer,
+            1,
+            length.max(1),
+        );
+        violations.finite("params.max_hairpin", params.max_hairpin as f64);
+        violations.within("params.count", params.count, 1, 100);
+        violations.finish()
+    }
+
+    pub fn check_read_set(
+        &self,
+        n_reads: usize,
+        params: &ReadSetParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        if n_reads == 0 {
+            violations.empty("seq_ids");
+        }
+        violations.within("params.dedup_length", params.dedup_length, 1, 10_000);
+        violations.within(
+            "params.rarefaction_points",
+            params.rarefaction_points,
+            1,
+            1000,
+        );
+        violations.within(
+            "params.overrepresented_percent",
+            params.overrepresented_percent,
+            0.0,
+            100.0,
+        );
+        violations.within(
+            "params.max_overrepresented",
+            params.max_overrepresented,
+            1,
+            1000,
+        );
+        violations.within("params.kmer_size", params.kmer_size, 3, 12);
+        violations.finite("params.min_kmer_enrichment", params.min_kmer_enrichment);
+        violations.within("params.max_kmers", params.max_kmers, 1, 1000);
+        violations.finish()
+    }
+
+    /// スキャフォールドを分割するギャップの最小長
+    pub fn check_gap_split(&self, min_gap: usize) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("min_gap", min_gap, 1, self.limits.max_window_size);
+        violations.finish()
+    }
+
+    /// NCBI Entrez の接続先とタイムアウト（秒）
+    pub fn check_entrez_settings(&self, settings: &EntrezSettings) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        if settings.base_url.trim().is_empty() {
+            violations.empty("base_url");
+        }
+        violations.within("timeout_secs", settings.timeout_secs, 1, 600);
+        violations.finish()
+    }
+
+    /// リードセットから1度に返すリードの数
+    pub fn check_read_page(&self, count: usize) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("count", count, 1, self.limits.max_page_size);
+        violations.finish()
+    }
+
+    pub fn check_histogram_bins(&self, bins: usize) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("bins", bins, 1, 1000);
+        violations.finish()
+    }
+
+    /// ドットプロットのウィンドウ長と、種が短くなりすぎない一致塩基数
+    pub fn check_dotplot(&self, window: usize, threshold: usize) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "window",
+            window,
+            dotplot::MIN_SEED_LENGTH,
+            dotplot::MAX_WINDOW,
+        );
+        if (dotplot::MIN_SEED_LENGTH..=dotplot::MAX_WINDOW).contains(&window) {
+            violations.within(
+                "threshold",
+                threshold,
+                dotplot::min_threshold(window),
+                window,
+            );
+        }
+        violations.finish()
+    }
+
+    /// 縦列反復の単位長・コピー数と逆位反復の腕・ループの長さ
+    pub fn check_repeats(&self, params: &RepeatParams) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "params.max_period",
+            params.max_period,
+            1,
+            repeat::MAX_PERIOD,
+        );
+        violations.within("params.min_copies", params.min_copies, 2.0, 1000.0);
+        violations.within(
+            "params.min_tandem_length",
+            params.min_tandem_length,
+            2,
+            self.limits.max_window_size,
+        );
+        violations.within(
+            "params.min_arm_length",
+            params.min_arm_length,
+            4,
+            self.limits.max_window_size,
+        );
+        violations.at_most("params.max_spacer", params.max_spacer, repeat::MAX_SPACER);
+        violations.within(
+            "params.max_results",
+            params.max_results,
+            1,
+            self.limits.max_page_size,
+        );
+        violations.finish()
+    }
+
+    /// k-mer スペクトルの k（4^k 個のカウンタで数えられる範囲）
+    pub fn check_kmer_spectrum(&self, k: usize) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("k", k, 1, 12);
+        violations.finish()
+    }
+
+    pub fn check_search(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("query", query.len(), 1, self.limits.max_query_length);
+        violations.within(
+            "options.page_size",
+            options.page_size,
+            1,
+            self.limits.max_page_size,
+        );
+        violations.within(
+            "options.window_size",
+            options.window_size,
+            1,
+            self.limits.max_region_length,
+        );
+        violations.finish()
+    }
+
+    /// 縮重モチーフ（IUPAC コード）と許容ミスマッチ数
+    pub fn check_motif(
+        &self,
+        motif: &str,
+        allow_mismatches: usize,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("motif", motif.len(), 1, self.limits.max_query_length);
+        violations.iupac("motif", motif);
+        violations.at_most(
+            "allow_mismatches",
+            allow_mismatches,
+            self.limits.max_mismatches,
+        );
+        violations.below_length("allow_mismatches", allow_mismatches, "motif", motif.len());
+        violations.finish()
+    }
+
+    /// 特異性を調べるプライマー配列（IUPAC コード）と許容ミスマッチ数
+    pub fn check_primer_specificity(
+        &self,
+        primer: &str,
+        max_mismatches: usize,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "primer_sequence",
+            primer.len(),
+            1,
+            self.limits.max_oligo_length,
+        );
+        violations.iupac("primer_sequence", primer);
+        violations.at_most("max_mismatches", max_mismatches, self.limits.max_mismatches);
+        violations.below_length("max_mismatches", max_mismatches, "primer", primer.len());
+        violations.finish()
+    }
+
+    /// 標識を選ぶプローブ（IUPAC コード）、1反応あたりのプローブ数と装置のチャネル
+    pub fn check_probe_labels(
+        &self,
+        probes: &[ProbeLabelInput],
+        multiplex: usize,
+        params: &ProbeLabelParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        if probes.is_empty() {
+            violations.empty("probes");
+        }
+        violations.at_most("probes", probes.len(), self.limits.max_oligos);
+        for (i, probe) in probes.iter().enumerate() {
+            let field = format!("probes[{}].sequence", i);
+            violations.within(
+                &field,
+                probe.sequence.len(),
+                1,
+                self.limits.max_oligo_length,
+            );
+            violations.iupac(&field, &probe.sequence);
+        }
+        violations.within("multiplex", multiplex, 1, self.limits.max_oligos);
+        if params.channels.is_empty() {
+            violations.empty("params.channels");
+        }
+        for (i, channel) in params.channels.iter().enumerate() {
+            for (name, min, max) in [
+                ("excitation", channel.excitation_min, channel.excitation_max),
+                ("emission", channel.emission_min, channel.emission_max),
+            ] {
+                let low = format!("params.channels[{}].{}_min", i, name);
+                let high = format!("params.channels[{}].{}_max", i, name);
+                violations.within(&low, min as f64, 200.0, 1000.0);
+                violations.within(&high, max as f64, 200.0, 1000.0);
+                violations.ordered((low.as_str(), min), (high.as_str(), max));
+            }
+        }
+        violations.within(
+            "params.double_quench_length",
+            params.double_quench_length,
+            1,
+            self.limits.max_oligo_length,
+        );
+        violations.finish()
+    }
+
+    /// 二本鎖を表示するプライマー配列（IUPAC コード、スタッキングを求めるため2塩基以上）
+    pub fn check_primer_duplex(&self, primer: &str) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "primer_sequence",
+            primer.len(),
+            2,
+            self.limits.max_oligo_length,
+        );
+        violations.iupac("primer_sequence", primer);
+        violations.finish()
+    }
+
+    /// 翻訳検索のクエリ（1文字表記のアミノ酸、'X' は任意の残基、'*' は終止）
+    pub fn check_protein_query(&self, query: &str) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("query", query.len(), 1, self.limits.max_query_length / 3);
+        Self::protein(&mut violations, "query", query);
+        violations.finish()
+    }
+
+    /// ユーザー定義の PROSITE 形式モチーフ（数と各パターンの長さ）
+    pub fn check_protein_motifs(&self, motifs: &[ProteinMotif]) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("patterns", motifs.len(), 1, self.limits.max_protein_motifs);
+        for (i, motif) in motifs.iter().enumerate() {
+            violations.within(
+                &format!("patterns[{}].pattern", i),
+                motif.pattern.len(),
+                1,
+                self.limits.max_query_length,
+            );
+        }
+        violations.finish()
+    }
+
+    /// ORF の最短長（アミノ酸数）
+    pub fn check_orfs(&self, min_length: usize) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("min_length", min_length, 1, self.limits.max_region_length);
+        violations.finish()
+    }
+
+    /// 配列に対して実行するスクリプト（空でなく、長さは上限以下）
+    pub fn check_script(&self, script: &str) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        if script.trim().is_empty() {
+            violations.empty("script");
+        }
+        violations.at_most("script", script.len(), self.limits.max_script_length);
+        violations.finish()
+    }
+
+    /// 配列全体を読み込んで走査する解析（コンタミネーション検出など）の配列長
+    pub fn check_sequence_length(
+        &self,
+        field: &str,
+        length: usize,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.at_most(
+            &format!("{}.length", field),
+            length,
+            self.limits.max_region_length,
+        );
+        violations.finish()
+    }
+
+    /// HGVS 表記にする変異（参照・代替塩基は IUPAC、どちらかは空でない）
+    pub fn check_variant(&self, variant: &Variant) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        for (field, bases) in [
+            ("change.reference", &variant.reference),
+            ("change.alternate", &variant.alternate),
+        ] {
+            violations.at_most(field, bases.len(), self.limits.max_query_length);
+            violations.iupac(field, bases);
+        }
+        if variant.reference.is_empty() && variant.alternate.is_empty() {
+            violations.empty("change.alternate");
+        }
+        violations.finish()
+    }
+
+    /// 制限酵素クローニングに使う酵素（1種類か2種類）
+    pub fn check_cloning(&self, enzymes: &[String]) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("enzymes", enzymes.len(), 1, 2);
+        violations.finish()
+    }
+
+    /// 削除・置換する領域と置換後の塩基
+    pub fn check_edit(&self, region: &Region, bases: Option<&str>) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.ordered(("region.start", region.start), ("region.end", region.end));
+        if let Some(bases) = bases {
+            violations.at_most("bases", bases.len(), self.limits.max_text_length);
+            violations.iupac("bases", bases);
+        }
+        violations.finish()
+    }
+
+    /// 融解曲線を予測するアンプリコン・塩濃度（M）・温度範囲（°C）
+    pub fn check_melt_curve(
+        &self,
+        amplicon: &str,
+        salt: &SaltCorrectionParams,
+        params: &MeltCurveParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "amplicon_sequence",
+            amplicon.len(),
+            2,
+            self.limits.max_hrm_amplicon_length,
+        );
+        for (field, concentration) in [
+            (
+                "salt_conditions.sodium_concentration",
+                salt.sodium_concentration,
+            ),
+            (
+                "salt_conditions.magnesium_concentration",
+                salt.magnesium_concentration,
+            ),
+            (
+                "salt_conditions.potassium_concentration",
+                salt.potassium_concentration,
+            ),
+            ("salt_conditions.other_monovalent", salt.other_monovalent),
+        ] {
+            violations.finite(field, concentration as f64);
+            violations.within(field, concentration, 0.0, 5.0);
+        }
+        violations.finite("params.temperature_min", params.temperature_min as f64);
+        violations.finite("params.temperature_max", params.temperature_max as f64);
+        violations.within("params.temperature_min", params.temperature_min, 0.0, 150.0);
+        violations.within("params.temperature_max", params.temperature_max, 0.0, 150.0);
+        violations.ordered(
+            ("params.temperature_min", params.temperature_min),
+            ("params.temperature_max", params.temperature_max),
+        );
+        violations.finite("params.step", params.step as f64);
+        // 点数が MAX_MELT_POINTS を超えない刻み
+        let min_step = ((params.temperature_max - params.temperature_min)
+            / (MAX_MELT_POINTS - 1) as f32)
+            .max(0.01);
+        violations.within("params.step", params.step, min_step, 5.0);
+        violations.within(
+            "params.window",
+            params.window,
+            2,
+            self.limits.max_hrm_amplicon_length,
+        );
+        violations.finish()
+    }
+
+    /// 融解マップの領域とウィンドウ
+    pub fn check_melting_map(
+        &self,
+        region: &Region,
+        params: &MeltingMapParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, "region", region);
+        violations.within(
+            "params.window",
+            params.window,
+            self.limits.min_primer_length,
+            self.limits.max_primer_length,
+        );
+        violations.at_most("params.window", params.window, region.len());
+        violations.within("params.step", params.step, 1, params.window.max(1));
+        violations.within(
+            "params.three_prime_length",
+            params.three_prime_length,
+            2,
+            params.window.max(2),
+        );
+        violations.ordered(
+            ("params.tm_min", params.tm_min),
+            ("params.tm_max", params.tm_max),
+        );
+        violations.finite(
+            "params.max_three_prime_stability",
+            params.max_three_prime_stability as f64,
+        );
+        violations.finish()
+    }
+
+    /// Tm グリッドの領域（qPCR アッセイ領域と同じ上限）とプライマー長の範囲
+    pub fn check_tm_grid(
+        &self,
+        region: &Region,
+        params: &TmGridParams,
+    ) -> Result<(), ValidationErrors> {
+        let limits = &self.limits;
+        let mut violations = Violations::default();
+        violations.at_most(
+            "region.length",
+            region.len(),
+            limits.max_assay_region_length,
+        );
+        for (field, length) in [
+            ("params.length_min", params.length_min),
+            ("params.length_max", params.length_max),
+        ] {
+            violations.within(
+                field,
+                length,
+                limits.min_primer_length,
+                limits.max_primer_length,
+            );
+        }
+        violations.ordered(
+            ("params.length_min", params.length_min),
+            ("params.length_max", params.length_max),
+        );
+        violations.at_most(
+            "params.length_max - length_min",
+            params.length_max.saturating_sub(params.length_min),
+            limits.max_primer_length_span,
+        );
+        violations.at_most("params.length_min", params.length_min, region.len());
+        violations.within("params.step", params.step, 1, region.len().max(1));
+        violations.finish()
+    }
+
+    /// 稀なコドンの閾値（宿主での相対適応度）
+    pub fn check_codon_usage(&self, params: &CodonUsageParams) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("params.rare_threshold", params.rare_threshold, 0.0, 1.0);
+        violations.finish()
+    }
+
+    /// RBS を探す上流の長さと強度推定の条件
+    pub fn check_rbs(&self, params: &RbsParams) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("params.upstream", params.upstream, 1, 100);
+        violations.within("params.min_sd_length", params.min_sd_length, 2, 9);
+        violations.within("params.spacing_penalty", params.spacing_penalty, 0.0, 10.0);
+        violations.within("params.weak_threshold", params.weak_threshold, 0.0, 1.0);
+        violations.finish()
+    }
+
+    /// 観察 CDS の領域と期待タンパク質（DP 行列の大きさも見る）
+    pub fn check_frameshift(
+        &self,
+        region: &Region,
+        expected_protein: &str,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        self.region(&mut violations, "region", region);
+        let protein: String = expected_protein
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        violations.within(
+            "expected_protein",
+            protein.len(),
+            1,
+            self.limits.max_query_length,
+        );
+        Self::protein(&mut violations, "expected_protein", &protein);
+        violations.at_most(
+            "region.length * expected_protein",
+            region.len().saturating_mul(protein.len()),
+            self.limits.max_alignment_cells,
+        );
+        violations.finish()
+    }
+
+    /// 消化プロトコルの酵素数と反応条件
+    pub fn check_digest_protocol(
+        &self,
+        n_enzymes: usize,
+        params: &DigestProtocolParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("enzymes", n_enzymes, 1, 4);
+        violations.within("params.dna_amount_ug", params.dna_amount_ug, 0.01, 100.0);
+        violations.within(
+            "params.dna_concentration_ng_ul",
+            params.dna_concentration_ng_ul,
+            1.0,
+            10_000.0,
+        );
+        violations.within(
+            "params.reaction_volume_ul",
+            params.reaction_volume_ul,
+            5.0,
+            1000.0,
+        );
+        violations.within("params.units_per_ug", params.units_per_ug, 0.1, 100.0);
+        violations.within(
+            "params.incubation_minutes",
+            params.incubation_minutes,
+            5.0,
+            24.0 * 60.0,
+        );
+        violations.finish()
+    }
+
+    /// 換算する濃度（単位によらず負や非有限の値は不可）
+    pub fn check_concentration(&self, value: f64) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("value", value, 0.0, 1e18);
+        violations.finish()
+    }
+
+    /// 乾燥オリゴの合成量（nmol）と目的濃度（µM）
+    pub fn check_resuspension(
+        &self,
+        nmoles: f64,
+        target_conc: f64,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("nmoles", nmoles, 0.01, 100_000.0);
+        violations.within("target_conc", target_conc, 0.001, 10_000.0);
+        violations.finish()
+    }
+
+    /// 希釈倍率は1以上（中間希釈の段数が増えすぎないよう上限も設ける）
+    pub fn check_dilution(
+        &self,
+        stock: f64,
+        target: f64,
+        volume: f64,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("stock", stock, 1e-6, 1e6);
+        violations.within("stock / target", stock / target, 1.0, 1e12);
+        violations.within("volume", volume, 10.0, 1e6);
+        violations.finish()
+    }
+
+    /// 標準曲線は2〜12点、1反応あたりのコピー数は1以上
+    pub fn check_standard_curve(
+        &self,
+        stock: f64,
+        params: &StandardCurveParams,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within("stock", stock, 1e-9, 1e18);
+        violations.within(
+            "params.top_copies_per_reaction",
+            params.top_copies_per_reaction,
+            1.0,
+            1e12,
+        );
+        violations.within("params.points", params.points, 2, 12);
+        violations.within("params.fold", params.fold, 2.0, 100.0);
+        violations.within(
+            "params.template_volume_ul",
+            params.template_volume_ul,
+            0.5,
+            20.0,
+        );
+        violations.within(
+            "params.standard_volume_ul",
+            params.standard_volume_ul,
+            10.0,
+            1000.0,
+        );
+        violations.within("params.replicates", params.replicates, 1, 10);
+        violations.finish()
+    }
+
+    pub fn check_mutations(
+        &self,
+        n_variants: usize,
+        rates: &MutationRates,
+    ) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.within(
+            "n_variants",
+            n_variants,
+            1,
+            self.limits.max_simulated_variants,
+        );
+        violations.within("rates.snv", rates.snv, 0.0, 1.0);
+        violations.within("rates.insertion", rates.insertion, 0.0, 1.0);
+        violations.within("rates.deletion", rates.deletion, 0.0, 1.0);
+        violations.within(
+            "rates.max_indel_length",
+            rates.max_indel_length,
+            1,
+            self.limits.max_oligo_length,
+        );
+        violations.finish()
+    }
+
+    pub fn check_panel_stress(&self, params: &PanelStressParams) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.at_most(
+            "params.max_mismatches",
+            params.max_mismatches,
+            self.limits.max_mismatches,
+        );
+        violations.at_most(
+            "params.three_prime_window",
+            params.three_prime_window,
+            self.limits.max_primer_length,
+        );
+        violations.ordered(
+            (
+                "params.max_three_prime_mismatches",
+                params.max_three_prime_mismatches,
+            ),
+            ("params.three_prime_window", params.three_prime_window),
+        );
+        violations.within(
+            "params.max_product_ratio",
+            params.max_product_ratio,
+            1.0,
+            100.0,
+        );
+        violations.within(
+            "params.fragile_threshold",
+            params.fragile_threshold,
+            0.0,
+            1.0,
+        );
+        violations.finish()
+    }
+
+    pub fn check_oligos(&self, oligos: &[Oligo]) -> Result<(), ValidationErrors> {
+        let mut violations = Violations::default();
+        violations.at_most("oligos", oligos.len(), self.limits.max_oligos);
+        for (i, oligo) in oligos.iter().enumerate() {
+            violations.within(
+                &format!("oligos[{}].sequence", i),
+                oligo.sequence.len(),
+                1,
+                self.limits.max_oligo_length,
+            );
+        }
+        violations.finish()
+    }
+
+    /// 1文字表記のアミノ酸（'X' は任意の残基、'*' は終止）
+    fn protein(violations: &mut Violations, field: &str, protein: &str) {
+        if let Some(invalid) = protein
+            .chars()
+            .find(|c| !"ACDEFGHIKLMNPQRSTVWYX*".contains(c.to_ascii_uppercase()))
+        {
+            violations.push(
+                field,
+                ViolationCode::NotAminoAcid { character: invalid },
+                format!(
+                    "contains a character that is not an amino acid: '{}'",
+                    invalid
+                ),
+            );
+        }
+    }
+
+    fn region(&self, violations: &mut Violations, field: &str, region: &Region) {
+        violations.at_most(
+            &format!("{}.length", field),
+            region.len(),
+            self.limits.max_region_length,
+        );
+    }
+
+    fn primer_params(&self, violations: &mut Violations, field: &str, params: &PrimerDesignParams) {
+        let limits = &self.limits;
+        let name = |name: &str| format!("{}.{}", field, name);
+
+        violations.within(
+            &name("length_min"),
+            params.length_min,
+            limits.min_primer_length,
+            limits.max_primer_length,
+        );
+        violations.within(
+            &name("length_max"),
+            params.length_max,
+            limits.min_primer_length,
+            limits.max_primer_length,
+        );
+        violations.ordered(
+            (&name("length_min"), params.length_min),
+            (&name("length_max"), params.length_max),
+        );
+        violations.at_most(
+            &name("length_max - length_min"),
+            params.length_max.saturating_sub(params.length_min),
+            limits.max_primer_length_span,
+        );
+        violations.at_most(
+            &name("search_flank"),
+            params.search_flank,
+            limits.max_primer_search_flank,
+        );
+
+        for (key, value) in [
+            ("tm_min", params.tm_min),
+            ("tm_max", params.tm_max),
+            ("tm_optimal", params.tm_optimal),
+        ] {
+            violations.within(&name(key), value, 0.0, 100.0);
+        }
+        violations.ordered(
+            (&name("tm_min"), params.tm_min),
+            (&name("tm_optimal"), params.tm_optimal),
+        );
+        violations.ordered(
+            (&name("tm_optimal"), params.tm_optimal),
+            (&name("tm_max"), params.tm_max),
+        );
+
+        violations.within(&name("gc_min"), params.gc_min, 0.0, 100.0);
+        violations.within(&name("gc_max"), params.gc_max, 0.0, 100.0);
+        violations.ordered(
+            (&name("gc_min"), params.gc_min),
+            (&name("gc_max"), params.gc_max),
+        );
+
+        // ΔG の閾値（kcal/mol）
+        for (key, value) in [
+            ("max_self_dimer", params.max_self_dimer),
+            ("max_hairpin", params.max_hairpin),
+            ("max_hetero_dimer", params.max_hetero_dimer),
+        ] {
+            violations.within(&name(key), value, -100.0, 0.0);
+        }
+
+        if let Some(weighting) = &params.weighting {
+            violations.finite(&name("weighting.min_weight"), weighting.min_weight);
+            violations.within(
+                &name("weighting.penalty_per_base"),
+                weighting.penalty_per_base,
+                0.0,
+                1000.0,
+            );
+            violations.at_most(
+                &name("weighting.three_prime_window"),
+                weighting.three_prime_window,
+                limits.max_primer_length,
+            );
+        }
+        if let Some(mask) = &params.quality_mask {
+            violations.at_most(
+                &name("quality_mask.min_quality"),
+                mask.min_quality as usize,
+                93,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::feature::LabelVisibility;
+
+    #[test]
+    fn test_reports_every_violated_primer_constraint() {
+        let service = InputValidationService::new();
+        assert!(service
+            .check_primer_design(&Region::new(0, 500), &PrimerDesignParams::default())
+            .is_ok());
+
+        let params = PrimerDesignParams {
+            length_min: 30,
+            length_max: 5,
+            tm_min: f32::NAN,
+            gc_min: 70.0,
+            ..PrimerDesignParams::default()
+        };
+        let error = service
+            .check_primer_design(&Region::new(0, 20_000_000), &params)
+            .unwrap_err();
+        assert_eq!(
+            error.fields(),
+            vec![
+                "region.length",
+                "params.length_max",
+                "params.length_min",
+                "params.tm_min",
+                "params.gc_min",
+            ]
+        );
+        let message = error.to_string();
+        assert!(message.starts_with("Invalid input: region.length must be at most 10000000"));
+        assert!(message.contains("params.length_max must be between 8 and 60 (got 5)"));
+
+        // フロントエンド向けの code と params
+        assert_eq!(
+            error.violations[1].code,
+            ViolationCode::OutOfRange {
+                min: 8.into(),
+                max: 60.into(),
+                value: 5.into(),
+            }
+        );
+        assert_eq!(
+            error.violations[2].code,
+            ViolationCode::ExceedsField {
+                other: "params.length_max".to_string(),
+                value: 30.into(),
+                other_value: 5.into(),
+            }
+        );
+        assert!(matches!(
+            &error.violations[3].code,
+            ViolationCode::OutOfRange {
+                value: Value::Null,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_window_and_search_bounds() {
+        let service = InputValidationService::new();
+        assert!(service.check_window(100, Some(10)).is_ok());
+        assert_eq!(
+            service.check_window(0, Some(0)).unwrap_err().fields(),
+            vec!["window_size", "step"]
+        );
+
+        let options = SearchOptions {
+            page_size: 0,
+            ..SearchOptions::default()
+        };
+        let error = service
+            .check_search(&"A".repeat(20_000), &options)
+            .unwrap_err();
+        assert_eq!(error.fields(), vec!["query", "options.page_size"]);
+    }
+
+    #[test]
+    fn test_feature_display_color() {
+        let service = InputValidationService::new();
+        for color in ["#1f77B4", "#000000"] {
+            let display = FeatureDisplay::new(color, 0, LabelVisibility::Always);
+            assert!(service.check_feature_display(&display).is_ok());
+        }
+        for color in ["red", "#fff", "1f77b4", "#1f77bz", "#1f77b40"] {
+            let display = FeatureDisplay::new(color, 0, LabelVisibility::Always);
+            let error = service.check_feature_display(&display).unwrap_err();
+            assert_eq!(
+                error.violations[0].code,
+                ViolationCode::InvalidColor {
+                    value: color.to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_edit_variant_and_motif_inputs() {
+        let service = InputValidationService::new();
+        assert!(service.check_edit(&Region::new(2, 5), Some("ACGN")).is_ok());
+        assert_eq!(
+            service
+                .check_edit(&Region::new(5, 2), Some("AC-G"))
+                .unwrap_err()
+                .fields(),
+            vec!["region.start", "bases"]
+        );
+
+        assert!(service.check_variant(&Variant::new(10, "", "T")).is_ok());
+        assert_eq!(
+            service
+                .check_variant(&Variant::new(10, "", ""))
+                .unwrap_err()
+                .fields(),
+            vec!["change.alternate"]
+        );
+
+        assert!(service.check_cloning(&[]).is_err());
+        assert!(service.check_orfs(0).is_err());
+        let motifs = vec![ProteinMotif::new("empty", "", ""); 2];
+        assert_eq!(
+            service.check_protein_motifs(&motifs).unwrap_err().fields(),
+            vec!["patterns[0].pattern", "patterns[1].pattern"]
+        );
+    }
+
+    #[test]
+    fn test_frameshift_alignment_size() {
+        let service = InputValidationService::new();
+        assert!(service
+            .check_frameshift(&Region::new(0, 3000), "MK TAY\n")
+            .is_ok());
+        let error = service
+            .check_frameshift(&Region::new(0, 30_000), &"MB".repeat(1000))
+            .unwrap_err();
+        assert_eq!(
+            error.fields(),
+            vec!["expected_protein", "region.length * expected_protein"]
+        );
+    }
+}
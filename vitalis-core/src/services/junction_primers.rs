@@ -0,0 +1,180 @@
+// Service layer: Junction-spanning verification primers for assembled or edited constructs
+use crate::domain::feature::SequenceFeature;
+use crate::domain::primer::{JunctionPcr, JunctionPrimerParams, PrimerDesignService};
+use crate::domain::{DerivationKind, SequenceOrigin};
+use crate::services::PrimerDesignServiceImpl;
+
+/// プライマー候補は設計サービスの探索点の前後50 bp から選ばれる
+const CANDIDATE_SPREAD: usize = 50;
+
+/// Junction primer service
+pub struct JunctionPrimerService {
+    primer_service: PrimerDesignServiceImpl,
+}
+
+impl Default for JunctionPrimerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JunctionPrimerService {
+    pub fn new() -> Self {
+        Self {
+            primer_service: PrimerDesignServiceImpl::new(),
+        }
+    }
+
+    /// コンストラクトの連結部を推定する（位置, ラベル）。
+    /// 発現コンストラクトとライゲーション産物は由来情報から挿入部の両端を求め、それ以外は隣接する
+    /// アノテーション（a.end == b.start）の境界を連結部とみなす
+    pub fn infer_junctions(
+        &self,
+        length: usize,
+        origin: Option<&SequenceOrigin>,
+        parent_length: Option<usize>,
+        features: &[SequenceFeature],
+    ) -> Vec<(usize, String)> {
+        if let (Some(origin), Some(parent_length)) = (origin, parent_length) {
+            let replaced = matches!(
+                origin.kind,
+                DerivationKind::ExpressionConstruct | DerivationKind::Ligation
+            );
+            if replaced && origin.start <= origin.end {
+                let kept = parent_length - (origin.end - origin.start);
+                let insert_end = origin.start + length.saturating_sub(kept);
+                return vec![
+                    (origin.start, "vector | insert".to_string()),
+                    (insert_end, "insert | vector".to_string()),
+                ];
+            }
+        }
+
+        let mut junctions: Vec<(usize, String)> = Vec::new();
+        for left in features {
+            if left.end == 0 || left.end >= length {
+                continue;
+            }
+            if let Some(right) = features.iter().find(|right| right.start == left.end) {
+                if junctions.iter().all(|(position, _)| *position != left.end) {
+                    junctions.push((left.end, format!("{} | {}", left.label(), right.label())));
+                }
+            }
+        }
+        junctions.sort_by_key(|(position, _)| *position);
+        junctions
+    }
+
+    /// 連結部ごとに、上流と下流に1本ずつ結合し増幅産物が上限以下のペアを選ぶ
+    pub fn design(
+        &self,
+        sequence: &str,
+        junctions: &[(usize, String)],
+        params: &JunctionPrimerParams,
+    ) -> Vec<JunctionPcr> {
+        // 探索点を連結部から distance 離すと、産物長はおおよそ 2 × distance ± 探索幅になる
+        let distance = params
+            .max_amplicon_length
+            .saturating_sub(2 * CANDIDATE_SPREAD + params.primer.length_max)
+            / 2;
+
+        junctions
+            .iter()
+            .map(|(junction, label)| {
+                let junction = *junction;
+                let start = junction.saturating_sub(distance);
+                let end = (junction + distance).min(sequence.len());
+                let pair = if start < end {
+                    self.primer_service
+                        .design_primers(sequence, start, end, &params.primer)
+                        .ok()
+                        .and_then(|result| {
+                            result.pairs.into_iter().find(|pair| {
+                                pair.forward.position + pair.forward.length <= junction
+                                    && pair.reverse.position >= junction
+                                    && pair.amplicon_length <= params.max_amplicon_length
+                            })
+                        })
+                } else {
+                    None
+                };
+                JunctionPcr {
+                    junction,
+                    label: label.clone(),
+                    pair,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Strand;
+
+    fn labeled(start: usize, end: usize, label: &str) -> SequenceFeature {
+        let mut feature = SequenceFeature::new("misc_feature", start, end, Strand::Forward);
+        feature
+            .qualifiers
+            .insert("label".to_string(), label.to_string());
+        feature
+    }
+
+    #[test]
+    fn test_infer_junctions() {
+        let service = JunctionPrimerService::new();
+        let features = vec![
+            labeled(0, 300, "left arm"),
+            labeled(300, 900, "insert"),
+            labeled(900, 1200, "right arm"),
+            labeled(100, 200, "promoter"),
+        ];
+        assert_eq!(
+            service.infer_junctions(1200, None, None, &features),
+            vec![
+                (300, "left arm | insert".to_string()),
+                (900, "insert | right arm".to_string()),
+            ]
+        );
+
+        // ベクター [100, 150) を 400 bp のカセットで置換
+        let origin = SequenceOrigin {
+            parent_id: "vector".to_string(),
+            start: 100,
+            end: 150,
+            kind: DerivationKind::ExpressionConstruct,
+        };
+        let junctions = service.infer_junctions(3350, Some(&origin), Some(3000), &[]);
+        assert_eq!(junctions[0].0, 100);
+        assert_eq!(junctions[1].0, 500);
+    }
+
+    #[test]
+    fn test_primers_flank_each_junction() {
+        let mut state = 17u64;
+        let sequence: String = (0..2400)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4] as char
+            })
+            .collect();
+        let mut params = JunctionPrimerParams::default();
+        params.primer.tm_min = 40.0;
+        params.primer.tm_max = 80.0;
+        params.primer.gc_min = 20.0;
+        params.primer.gc_max = 80.0;
+
+        let junctions = vec![(800, "a | b".to_string()), (1600, "b | c".to_string())];
+        let set = JunctionPrimerService::new().design(&sequence, &junctions, &params);
+        assert_eq!(set.len(), 2);
+        for pcr in &set {
+            let pair = pcr.pair.as_ref().unwrap();
+            assert!(pair.forward.position + pair.forward.length <= pcr.junction);
+            assert!(pair.reverse.position >= pcr.junction);
+            assert!(pair.amplicon_length <= 800);
+        }
+    }
+}
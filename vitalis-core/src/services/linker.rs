@@ -0,0 +1,238 @@
+// Service layer: Random linker / spacer generation
+use crate::domain::iupac;
+use crate::domain::linker::{Linker, LinkerParams, LinkerSet};
+use crate::domain::reproducibility::{self, SplitMix64};
+use crate::domain::restriction::RestrictionEnzyme;
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+use thiserror::Error;
+
+/// 候補1つあたりの試行回数の上限
+const ATTEMPTS_PER_LINKER: usize = 2000;
+
+#[derive(Error, Debug)]
+pub enum LinkerError {
+    #[error("No linker satisfied the constraints after {0} attempts")]
+    NoCandidate(usize),
+}
+
+/// Linker design service
+pub struct LinkerDesignService {
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for LinkerDesignService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkerDesignService {
+    pub fn new() -> Self {
+        Self {
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// G/C を GC% に相当する数だけ含む長さ `length` のランダム配列を作る。
+    /// ホモポリマーや `enzymes` の認識配列（両鎖）ができる塩基は選ばず、
+    /// 行き詰まった場合と完成後のヘアピンが安定すぎる場合は作り直す
+    pub fn generate(
+        &self,
+        length: usize,
+        gc_percent: f64,
+        enzymes: &[&RestrictionEnzyme],
+        params: &LinkerParams,
+    ) -> Result<LinkerSet, LinkerError> {
+        let gc_count = (length as f64 * gc_percent / 100.0).round() as usize;
+        let actual_gc = gc_count as f64 / length as f64 * 100.0;
+
+        let mut sites: Vec<Vec<u8>> = Vec::new();
+        for enzyme in enzymes {
+            let forward = enzyme.site.to_ascii_uppercase();
+            for site in [iupac::reverse_complement(&forward), forward] {
+                if !sites.contains(&site.as_bytes().to_vec()) {
+                    sites.push(site.into_bytes());
+                }
+            }
+        }
+
+        // シード未指定ならワークスペースの乱数源から（再現性モードでは決定的）
+        let seed = params.seed.unwrap_or_else(reproducibility::workspace_u64);
+        let mut rng = SplitMix64::new(seed);
+
+        let max_attempts = params.count * ATTEMPTS_PER_LINKER;
+        let mut attempts = 0;
+        let mut linkers: Vec<Linker> = Vec::new();
+        while linkers.len() < params.count && attempts < max_attempts {
+            attempts += 1;
+            let Some(sequence) =
+                self.draw(length, gc_count, &sites, params.max_homopolymer, &mut rng)
+            else {
+                continue;
+            };
+            if linkers.iter().any(|linker| linker.sequence == sequence) {
+                continue;
+            }
+
+            let hairpin_delta_g = self
+                .calculator
+                .calculate_enhanced_hairpin(&sequence)
+                .map(|hairpin| hairpin.min_score)
+                .unwrap_or(0.0);
+            if hairpin_delta_g < params.max_hairpin {
+                continue;
+            }
+
+            linkers.push(Linker {
+                gc_percent: actual_gc,
+                tm: self
+                    .calculator
+                    .calculate_tm_nearest_neighbor(&sequence)
+                    .unwrap_or(0.0),
+                hairpin_delta_g,
+                longest_homopolymer: longest_run(sequence.as_bytes()),
+                sequence,
+            });
+        }
+
+        if linkers.is_empty() {
+            return Err(LinkerError::NoCandidate(attempts));
+        }
+
+        let mut warnings = Vec::new();
+        if (actual_gc - gc_percent).abs() > 0.5 {
+            warnings.push(format!(
+                "GC rounded to {:.1}% ({} of {} bases)",
+                actual_gc, gc_count, length
+            ));
+        }
+        if linkers.len() < params.count {
+            warnings.push(format!(
+                "Only {} of {} linkers satisfied the constraints after {} attempts",
+                linkers.len(),
+                params.count,
+                attempts
+            ));
+        }
+
+        linkers.sort_by(|a, b| b.hairpin_delta_g.total_cmp(&a.hairpin_delta_g));
+        Ok(LinkerSet {
+            length,
+            gc_target: gc_percent,
+            gc_count,
+            avoided_enzymes: enzymes.iter().map(|enzyme| enzyme.name.clone()).collect(),
+            linkers,
+            attempts,
+            seed,
+            warnings,
+        })
+    }
+
+    /// 1塩基ずつ伸ばす。残りの G/C を残りの位置に一様に配り、
+    /// G/C・A/T の中では条件を破らない塩基を無作為な順に試す
+    fn draw(
+        &self,
+        length: usize,
+        gc_count: usize,
+        sites: &[Vec<u8>],
+        max_homopolymer: usize,
+        rng: &mut SplitMix64,
+    ) -> Option<String> {
+        let mut sequence: Vec<u8> = Vec::with_capacity(length);
+        let mut gc_left = gc_count;
+        for position in 0..length {
+            let strong = rng.below(length - position) < gc_left;
+            let pool: &[u8; 2] = if strong { b"GC" } else { b"AT" };
+            let first = rng.below(pool.len());
+            let base = (0..pool.len())
+                .map(|i| pool[(first + i) % pool.len()])
+                .find(|&base| {
+                    sequence.push(base);
+                    let acceptable = acceptable(&sequence, sites, max_homopolymer);
+                    sequence.pop();
+                    acceptable
+                })?;
+            sequence.push(base);
+            if strong {
+                gc_left -= 1;
+            }
+        }
+        Some(sequence.into_iter().map(char::from).collect())
+    }
+}
+
+/// 末尾に加えた塩基でホモポリマーが長すぎたり、認識配列が完成したりしないか
+fn acceptable(sequence: &[u8], sites: &[Vec<u8>], max_homopolymer: usize) -> bool {
+    let last = sequence[sequence.len() - 1];
+    let run = sequence
+        .iter()
+        .rev()
+        .take_while(|&&base| base == last)
+        .count();
+    run <= max_homopolymer
+        && !sites.iter().any(|site| {
+            sequence.len() >= site.len()
+                && iupac::matches_at(site, sequence, sequence.len() - site.len())
+        })
+}
+
+fn longest_run(sequence: &[u8]) -> usize {
+    sequence
+        .chunk_by(|a, b| a == b)
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linkers_meet_gc_site_and_homopolymer_constraints() {
+        let service = LinkerDesignService::new();
+        let ecori = RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5);
+        let bsai = RestrictionEnzyme::new("BsaI", "GGTCTC", 7, 11);
+        let params = LinkerParams {
+            count: 10,
+            seed: Some(7),
+            ..LinkerParams::default()
+        };
+
+        let set = service
+            .generate(30, 50.0, &[&ecori, &bsai], &params)
+            .unwrap();
+        assert_eq!(set.gc_count, 15);
+        assert_eq!(set.linkers.len(), 10);
+        for linker in &set.linkers {
+            let sequence = &linker.sequence;
+            assert_eq!(sequence.len(), 30);
+            assert_eq!(sequence.chars().filter(|c| "GC".contains(*c)).count(), 15);
+            for site in ["GAATTC", "GGTCTC", "GAGACC"] {
+                assert!(!sequence.contains(site), "{} in {}", site, sequence);
+            }
+            assert!(linker.longest_homopolymer <= 3);
+            assert!(linker.hairpin_delta_g >= params.max_hairpin);
+        }
+
+        // 同じシードなら同じ候補
+        let again = service
+            .generate(30, 50.0, &[&ecori, &bsai], &params)
+            .unwrap();
+        assert_eq!(again.linkers, set.linkers);
+    }
+
+    #[test]
+    fn test_impossible_constraints() {
+        // G/C のみでホモポリマー1なら GCGC… しか作れない
+        let hhai = RestrictionEnzyme::new("HhaI", "GCGC", 3, 1);
+        let params = LinkerParams {
+            max_homopolymer: 1,
+            count: 1,
+            seed: Some(1),
+            ..LinkerParams::default()
+        };
+        let result = LinkerDesignService::new().generate(8, 100.0, &[&hhai], &params);
+        assert!(matches!(result, Err(LinkerError::NoCandidate(2000))));
+    }
+}
@@ -0,0 +1,396 @@
+// Service layer: Linear map layout shared by the viewer and report export
+use crate::domain::feature::{FeatureDisplay, LabelVisibility, SequenceFeature};
+use crate::domain::map_model::{
+    ArrowDirection, MapFeatureGlyph, MapLabel, MapLine, MapModel, MapPoint, MapStyle, MapTick,
+    MapTrack, TextAnchor,
+};
+use crate::domain::{Range, Strand};
+
+/// 等幅フォントを仮定した1文字あたりの幅（font_size に対する比）
+const CHAR_WIDTH_RATIO: f64 = 0.6;
+/// 同じトラック上の図形（外側ラベルを含む）の最小間隔
+const MIN_GLYPH_GAP: f64 = 2.0;
+/// 外側ラベルと図形の間隔
+const LABEL_OFFSET: f64 = 3.0;
+
+/// トラックに配置する前のアノテーション
+struct PlacedFeature<'a> {
+    feature: &'a SequenceFeature,
+    display: FeatureDisplay,
+    segments: Vec<Range>,
+    start: usize,
+    end: usize,
+    /// ラベルを図形の内側に描くか（None はラベルなし）
+    label_inside: Option<bool>,
+    label: String,
+}
+
+/// Linear map layout service
+pub struct MapLayoutService;
+
+impl Default for MapLayoutService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MapLayoutService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Lay out `features` over `range` as tracks of arrows with ticks and
+    /// non-overlapping labels. `range` must be non-empty.
+    pub fn layout(
+        &self,
+        range: &Range,
+        features: &[SequenceFeature],
+        style: &MapStyle,
+    ) -> MapModel {
+        let length = range.len().max(1);
+        let scale = (style.width - 2.0 * style.margin).max(1.0) / length as f64;
+        let x = |position: usize| style.margin + (position - range.start) as f64 * scale;
+        let text_width =
+            |text: &str| text.chars().count() as f64 * style.font_size * CHAR_WIDTH_RATIO;
+
+        let backbone_y = style.margin + style.ruler_height;
+        let backbone = MapLine {
+            from: MapPoint {
+                x: x(range.start),
+                y: backbone_y,
+            },
+            to: MapPoint {
+                x: x(range.start) + length as f64 * scale,
+                y: backbone_y,
+            },
+        };
+
+        let mut placed: Vec<PlacedFeature> = features
+            .iter()
+            .filter(|f| f.overlaps(range.start, range.end))
+            .filter(|f| !style.hidden_types.contains(&f.feature_type))
+            .filter_map(|feature| self.place(feature, range, style, scale, &text_width))
+            .collect();
+        placed.sort_by(|a, b| {
+            a.display
+                .tier
+                .cmp(&b.display.tier)
+                .then(a.start.cmp(&b.start))
+                .then((b.end - b.start).cmp(&(a.end - a.start)))
+        });
+
+        // 表示段ごとに、重ならないアノテーションを同じトラックへ詰める
+        let mut rows: Vec<(u8, f64)> = Vec::new();
+        let mut tracks = Vec::new();
+        let mut glyphs = Vec::new();
+        let mut labels = Vec::new();
+        for item in &placed {
+            let left = x(item.start);
+            let right = x(item.end);
+            let occupied_right = match item.label_inside {
+                Some(false) => right + LABEL_OFFSET + text_width(&item.label),
+                _ => right,
+            };
+
+            let track = match rows
+                .iter()
+                .position(|&(tier, edge)| tier == item.display.tier && edge + MIN_GLYPH_GAP <= left)
+            {
+                Some(track) => {
+                    rows[track].1 = occupied_right;
+                    track
+                }
+                None => {
+                    rows.push((item.display.tier, occupied_right));
+                    let index = rows.len() - 1;
+                    tracks.push(MapTrack {
+                        index,
+                        tier: item.display.tier,
+                        y: backbone_y
+                            + style.track_gap
+                            + index as f64 * (style.feature_height + style.track_gap),
+                        height: style.feature_height,
+                    });
+                    index
+                }
+            };
+
+            let y = tracks[track].y;
+            let glyph = self.glyph(item, track, y, range, style, &x);
+            if let Some(inside) = item.label_inside {
+                labels.push(MapLabel {
+                    feature_id: item.feature.id.clone(),
+                    text: item.label.clone(),
+                    x: if inside {
+                        (left + right) / 2.0
+                    } else {
+                        right + LABEL_OFFSET
+                    },
+                    y: y + style.feature_height / 2.0 + style.font_size * 0.35,
+                    anchor: if inside {
+                        TextAnchor::Middle
+                    } else {
+                        TextAnchor::Start
+                    },
+                    inside,
+                });
+            }
+            glyphs.push(glyph);
+        }
+
+        let height = match tracks.last() {
+            Some(track) => track.y + track.height + style.margin,
+            None => backbone_y + style.margin,
+        };
+
+        MapModel {
+            width: style.width,
+            height,
+            start: range.start,
+            end: range.end,
+            scale,
+            backbone,
+            ticks: self.ticks(range, style, scale),
+            tracks,
+            features: glyphs,
+            labels,
+        }
+    }
+
+    /// 描画範囲に切り詰め、ラベルの配置を決める
+    fn place<'a>(
+        &self,
+        feature: &'a SequenceFeature,
+        range: &Range,
+        style: &MapStyle,
+        scale: f64,
+        text_width: &dyn Fn(&str) -> f64,
+    ) -> Option<PlacedFeature<'a>> {
+        let mut segments: Vec<Range> = if feature.segments.is_empty() {
+            vec![Range::new(feature.start, feature.end)]
+        } else {
+            feature.segments.clone()
+        };
+        segments.retain(|seg| seg.start < range.end && range.start < seg.end);
+        for seg in segments.iter_mut() {
+            seg.start = seg.start.max(range.start);
+            seg.end = seg.end.min(range.end);
+        }
+        segments.sort_by_key(|seg| seg.start);
+        let start = segments.first()?.start;
+        let end = segments.iter().map(|seg| seg.end).max()?;
+
+        let display = feature
+            .display
+            .clone()
+            .unwrap_or_else(|| FeatureDisplay::for_type(&feature.feature_type));
+        let label = feature.label();
+        let fits = text_width(&label) + 2.0 * LABEL_OFFSET <= (end - start) as f64 * scale;
+        let label_inside = match display.label {
+            _ if !style.show_labels => None,
+            LabelVisibility::Never => None,
+            _ if fits => Some(true),
+            LabelVisibility::Always => Some(false),
+            LabelVisibility::WhenSpace => None,
+        };
+
+        Some(PlacedFeature {
+            feature,
+            display,
+            segments,
+            start,
+            end,
+            label_inside,
+            label,
+        })
+    }
+
+    fn glyph(
+        &self,
+        item: &PlacedFeature,
+        track: usize,
+        y: f64,
+        range: &Range,
+        style: &MapStyle,
+        x: &dyn Fn(usize) -> f64,
+    ) -> MapFeatureGlyph {
+        let feature = item.feature;
+        let height = style.feature_height;
+        let direction = match feature.strand {
+            Strand::Forward if feature.end <= range.end => ArrowDirection::Right,
+            Strand::Reverse if feature.start >= range.start => ArrowDirection::Left,
+            _ => ArrowDirection::None,
+        };
+
+        let last = item.segments.len() - 1;
+        let shapes = item
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(i, seg)| {
+                let (left, right) = (x(seg.start), x(seg.end));
+                let head = (height * 0.6).min(right - left);
+                let (top, middle, bottom) = (y, y + height / 2.0, y + height);
+                let point = |x: f64, y: f64| MapPoint { x, y };
+                match direction {
+                    ArrowDirection::Right if i == last => vec![
+                        point(left, top),
+                        point(right - head, top),
+                        point(right, middle),
+                        point(right - head, bottom),
+                        point(left, bottom),
+                    ],
+                    ArrowDirection::Left if i == 0 => vec![
+                        point(left, middle),
+                        point(left + head, top),
+                        point(right, top),
+                        point(right, bottom),
+                        point(left + head, bottom),
+                    ],
+                    _ => vec![
+                        point(left, top),
+                        point(right, top),
+                        point(right, bottom),
+                        point(left, bottom),
+                    ],
+                }
+            })
+            .collect();
+
+        let connectors = item
+            .segments
+            .windows(2)
+            .map(|pair| MapLine {
+                from: MapPoint {
+                    x: x(pair[0].end),
+                    y: y + height / 2.0,
+                },
+                to: MapPoint {
+                    x: x(pair[1].start),
+                    y: y + height / 2.0,
+                },
+            })
+            .collect();
+
+        MapFeatureGlyph {
+            feature_id: feature.id.clone(),
+            feature_type: feature.feature_type.clone(),
+            label: item.label.clone(),
+            color: item.display.color.clone(),
+            track,
+            x: x(item.start),
+            y,
+            width: x(item.end) - x(item.start),
+            height,
+            direction,
+            shapes,
+            connectors,
+            truncated: feature.start < range.start || feature.end > range.end,
+        }
+    }
+
+    /// 目盛り間隔を 1・2・5 × 10^n 塩基に丸め、各塩基の中央に配置
+    fn ticks(&self, range: &Range, style: &MapStyle, scale: f64) -> Vec<MapTick> {
+        let raw = (style.tick_spacing / scale).max(1.0);
+        let magnitude = 10f64.powf(raw.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|m| m * magnitude)
+            .find(|&step| step >= raw)
+            .unwrap_or(10.0 * magnitude) as usize;
+
+        let first = (range.start / step + 1) * step;
+        (first..=range.end)
+            .step_by(step.max(1))
+            .map(|position| MapTick {
+                position,
+                x: style.margin + ((position - 1 - range.start) as f64 + 0.5) * scale,
+                label: position.to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(
+        id: &str,
+        feature_type: &str,
+        start: usize,
+        end: usize,
+        strand: Strand,
+    ) -> SequenceFeature {
+        let mut feature = SequenceFeature::new(feature_type, start, end, strand);
+        feature.id = id.to_string();
+        feature
+    }
+
+    #[test]
+    fn test_ticks_and_track_packing() {
+        let features = vec![
+            feature("a", "CDS", 100, 400, Strand::Forward),
+            feature("b", "CDS", 300, 600, Strand::Reverse),
+            feature("c", "CDS", 700, 900, Strand::Forward),
+            feature("p", "promoter", 50, 100, Strand::Forward),
+            feature("s", "source", 0, 1000, Strand::Forward),
+        ];
+        let model =
+            MapLayoutService::new().layout(&Range::new(0, 1000), &features, &MapStyle::default());
+
+        // 760px / 1000 bp → 目盛り間隔 80px ≈ 105 bp → 200 bp
+        let positions: Vec<usize> = model.ticks.iter().map(|t| t.position).collect();
+        assert_eq!(positions, vec![200, 400, 600, 800, 1000]);
+
+        // source は非表示、重なる CDS は別トラック、c は a のトラックに戻る
+        assert_eq!(model.features.len(), 4);
+        let track = |id: &str| {
+            model
+                .features
+                .iter()
+                .find(|g| g.feature_id == id)
+                .unwrap()
+                .track
+        };
+        assert_eq!(track("a"), 0);
+        assert_eq!(track("b"), 1);
+        assert_eq!(track("c"), 0);
+        assert_eq!(track("p"), 2);
+        assert_eq!(model.tracks.len(), 3);
+        assert!(model.height > model.tracks[2].y);
+    }
+
+    #[test]
+    fn test_truncated_arrows_and_labels() {
+        let mut gene = feature("g", "gene", 300, 500, Strand::Forward);
+        gene.qualifiers
+            .insert("gene".to_string(), "a_rather_long_gene_name".to_string());
+        let mut spliced = feature("x", "CDS", 150, 450, Strand::Reverse);
+        spliced.segments = vec![Range::new(150, 200), Range::new(300, 450)];
+
+        let model = MapLayoutService::new().layout(
+            &Range::new(100, 400),
+            &[gene, spliced],
+            &MapStyle {
+                width: 340.0,
+                ..MapStyle::default()
+            },
+        );
+
+        let gene = model.features.iter().find(|g| g.feature_id == "g").unwrap();
+        assert!(gene.truncated);
+        assert_eq!(gene.direction, ArrowDirection::None);
+
+        let cds = model.features.iter().find(|g| g.feature_id == "x").unwrap();
+        assert_eq!(cds.direction, ArrowDirection::Left);
+        assert_eq!(cds.shapes.len(), 2);
+        assert_eq!(cds.shapes[0].len(), 5);
+        assert_eq!(cds.connectors.len(), 1);
+
+        // 長いラベルは図形の外側に
+        let label = model.labels.iter().find(|l| l.feature_id == "g").unwrap();
+        assert!(!label.inside);
+        assert_eq!(label.anchor, TextAnchor::Start);
+    }
+}
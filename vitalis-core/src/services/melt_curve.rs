@@ -0,0 +1,176 @@
+// Service layer: Predicted high-resolution melt (HRM) curves for amplicons
+use crate::domain::melt_curve::{MeltCurve, MeltCurveParams, MeltPeak, MIN_PEAK_FRACTION};
+use crate::domain::thermodynamic_calculator::{
+    ThermodynamicCalculator, ThermodynamicError, ThermodynamicProvenance,
+};
+use crate::domain::thermodynamics::SaltCorrectionParams;
+
+/// 気体定数 (kcal/mol·K)
+const GAS_CONSTANT: f64 = 1.987e-3;
+
+/// 融解曲線予測のサービス
+pub struct MeltCurveService {
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for MeltCurveService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MeltCurveService {
+    pub fn new() -> Self {
+        Self {
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// 各塩基対を、その周り `window` 塩基の最近接 ΔH・ΔS を持つ2状態の融解とみなし、
+    /// 二本鎖の割合の平均を蛍光として -dF/dT を温度ごとに計算する。
+    /// GC リッチなドメインは高温側で融けるため、ドメインごとにピークが分かれる
+    pub fn predict(
+        &self,
+        amplicon: &str,
+        salt: &SaltCorrectionParams,
+        params: &MeltCurveParams,
+    ) -> Result<MeltCurve, ThermodynamicError> {
+        let amplicon = amplicon.to_ascii_uppercase();
+        let length = amplicon.len();
+        let window = params.window.clamp(2, length.max(2));
+
+        // 開始位置ごとのウィンドウの (ΔH, ΔS)
+        let windows = (0..=length.saturating_sub(window))
+            .map(|start| {
+                self.calculator
+                    .duplex_enthalpy_entropy(&amplicon[start..start + window], salt)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // 各塩基はそれを中心とするウィンドウ（端では内側に寄せる）で評価する
+        let pairs: Vec<(f64, f64)> = (0..length)
+            .map(|i| {
+                let (delta_h, delta_s) =
+                    windows[i.saturating_sub(window / 2).min(windows.len() - 1)];
+                (delta_h as f64, delta_s as f64 / 1000.0)
+            })
+            .collect();
+
+        let points: Vec<(f32, f32)> = (0..params.point_count())
+            .map(|i| {
+                let temperature = params.temperature_min + i as f32 * params.step;
+                (temperature, Self::negative_derivative(&pairs, temperature))
+            })
+            .collect();
+
+        Ok(MeltCurve {
+            length,
+            tm: self
+                .calculator
+                .calculate_tm_with_conditions(
+                    &amplicon,
+                    salt,
+                    self.calculator.get_conditions().temperature_k,
+                )
+                .ok(),
+            peaks: Self::peaks(&points),
+            points,
+            params: params.clone(),
+            provenance: Some(ThermodynamicProvenance {
+                salt: salt.clone(),
+                ..self.calculator.provenance()
+            }),
+        })
+    }
+
+    /// 温度 `temperature`（°C）での -dF/dT（/°C）。2状態の二本鎖の割合
+    /// θ = 1 / (1 + exp(ΔG / RT)) の温度微分は dθ/dT = θ(1 - θ)ΔH / RT²
+    fn negative_derivative(pairs: &[(f64, f64)], temperature: f32) -> f32 {
+        let t = temperature as f64 + 273.15;
+        let sum: f64 = pairs
+            .iter()
+            .map(|&(delta_h, delta_s)| {
+                let x = (delta_h - t * delta_s) / (GAS_CONSTANT * t);
+                // θ(1 - θ) を桁あふれなく
+                let e = (-x.abs()).exp();
+                let spread = e / ((1.0 + e) * (1.0 + e));
+                -spread * delta_h / (GAS_CONSTANT * t * t)
+            })
+            .sum();
+        (sum / pairs.len().max(1) as f64) as f32
+    }
+
+    /// 最大の極大の MIN_PEAK_FRACTION 以上の高さを持つ極大
+    fn peaks(points: &[(f32, f32)]) -> Vec<MeltPeak> {
+        let highest = points.iter().map(|p| p.1).fold(0.0f32, f32::max);
+        if highest <= 0.0 {
+            return Vec::new();
+        }
+        (0..points.len())
+            .filter(|&i| {
+                let height = points[i].1;
+                let rising = i == 0 || points[i - 1].1 < height;
+                let falling = i + 1 == points.len() || points[i + 1].1 <= height;
+                rising && falling && height >= highest * MIN_PEAK_FRACTION
+            })
+            .map(|i| MeltPeak {
+                temperature: points[i].0,
+                height: points[i].1,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curve_peaks_near_the_duplex_tm() {
+        let service = MeltCurveService::new();
+        let amplicon = "GCTAGCATGGATCCTGACTGCAGTACGATCGTAGCTAGGCATGCAATTGCGATCCGTA";
+        let params = MeltCurveParams::default();
+        let curve = service
+            .predict(amplicon, &SaltCorrectionParams::default(), &params)
+            .unwrap();
+
+        assert_eq!(curve.points.len(), params.point_count());
+        assert_eq!(curve.points[0].0, params.temperature_min);
+        assert!(curve.points.iter().all(|&(_, d)| d >= 0.0));
+        // 面積（融けた割合）はほぼ 1
+        let area: f32 = curve.points.iter().map(|&(_, d)| d * params.step).sum();
+        assert!((area - 1.0).abs() < 0.05, "area {}", area);
+        assert_eq!(curve.peaks.len(), 1);
+        let tm = curve.tm.unwrap();
+        assert!((curve.peaks[0].temperature - tm).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_at_and_gc_domains_melt_separately() {
+        let service = MeltCurveService::new();
+        let amplicon = format!(
+            "{}{}",
+            "ATTATAAATTTATAATTAATATTATAAATTTATAATTAAT", "GCGGCCGCGGAGCTCGGCCCGCGGCCGCGGAGCTCGGCCC"
+        );
+        let params = MeltCurveParams {
+            temperature_min: 20.0,
+            temperature_max: 120.0,
+            ..MeltCurveParams::default()
+        };
+        let curve = service
+            .predict(&amplicon, &SaltCorrectionParams::default(), &params)
+            .unwrap();
+        // 境目のウィンドウは中間の小さなピークになる
+        let tm = curve.tm.unwrap();
+        let (at, gc) = (&curve.peaks[0], curve.peaks.last().unwrap());
+        assert!(at.temperature < tm - 15.0 && gc.temperature > tm + 15.0);
+        assert!(curve
+            .peaks
+            .iter()
+            .all(|p| p.height <= at.height.max(gc.height)));
+
+        // ACGT 以外は計算できない
+        assert!(service
+            .predict("ACGTNACGT", &SaltCorrectionParams::default(), &params)
+            .is_err());
+    }
+}
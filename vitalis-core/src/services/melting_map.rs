@@ -0,0 +1,239 @@
+// Service layer: Binding-site melting map (per-window primer-ability profile)
+use crate::domain::iupac;
+use crate::domain::melting_map::{
+    MeltingMap, MeltingMapParams, MeltingPoint, TmGrid, TmGridParams,
+};
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+
+/// Melting map service
+pub struct MeltingMapService {
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for MeltingMapService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MeltingMapService {
+    pub fn new() -> Self {
+        Self {
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// [start, end) に収まる各ウィンドウ（`step` 塩基おき）をプライマーとみなし、
+    /// Tm と、順方向・逆方向それぞれの3'末端 ΔG を計算する
+    pub fn profile(
+        &self,
+        sequence: &str,
+        start: usize,
+        end: usize,
+        params: &MeltingMapParams,
+    ) -> MeltingMap {
+        let sequence = sequence.to_ascii_uppercase();
+        let end = end.min(sequence.len());
+        let step = params.step.max(1);
+
+        let mut points = Vec::new();
+        let mut position = start;
+        while position + params.window <= end {
+            points.push(self.point(
+                &sequence[position..position + params.window],
+                position,
+                params,
+            ));
+            position += step;
+        }
+
+        MeltingMap {
+            seq_id: String::new(),
+            start,
+            end,
+            params: params.clone(),
+            points,
+            provenance: Some(self.calculator.provenance()),
+        }
+    }
+
+    /// [start, end) 内の長さ `length_min`〜`length_max` のすべての候補（開始位置は
+    /// `step` 塩基おき）の Tm を、長さを行・開始位置を列とする行列で返す
+    pub fn tm_grid(
+        &self,
+        sequence: &str,
+        start: usize,
+        end: usize,
+        params: &TmGridParams,
+    ) -> TmGrid {
+        let sequence = sequence.to_ascii_uppercase();
+        let end = end.min(sequence.len());
+        let lengths: Vec<usize> = (params.length_min..=params.length_max).collect();
+        let positions: Vec<usize> = (start..end.saturating_sub(params.length_min.max(1) - 1))
+            .step_by(params.step.max(1))
+            .collect();
+
+        let tm: Vec<Vec<Option<f32>>> = lengths
+            .iter()
+            .map(|&length| {
+                positions
+                    .iter()
+                    .map(|&position| {
+                        let candidate = sequence.get(position..position + length)?;
+                        if position + length > end
+                            || !candidate
+                                .bytes()
+                                .all(|b| matches!(b, b'A' | b'C' | b'G' | b'T'))
+                        {
+                            return None;
+                        }
+                        self.calculator
+                            .calculate_tm_nearest_neighbor(candidate)
+                            .ok()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let values = tm.iter().flatten().flatten().copied();
+        TmGrid {
+            seq_id: String::new(),
+            start,
+            end,
+            tm_min: values.clone().reduce(f32::min),
+            tm_max: values.reduce(f32::max),
+            lengths,
+            positions,
+            tm,
+            params: params.clone(),
+            provenance: Some(self.calculator.provenance()),
+        }
+    }
+
+    fn point(&self, window: &str, position: usize, params: &MeltingMapParams) -> MeltingPoint {
+        let gc = window.bytes().filter(|b| matches!(b, b'G' | b'C')).count();
+        let gc_percent = gc as f64 / window.len() as f64 * 100.0;
+
+        if !window
+            .bytes()
+            .all(|b| matches!(b, b'A' | b'C' | b'G' | b'T'))
+        {
+            return MeltingPoint {
+                position,
+                gc_percent,
+                tm: None,
+                forward_three_prime_dg: None,
+                reverse_three_prime_dg: None,
+                forward_ok: false,
+                reverse_ok: false,
+            };
+        }
+
+        let tm = self.calculator.calculate_tm_nearest_neighbor(window).ok();
+        let tail = params.three_prime_length.min(window.len());
+        let forward_three_prime_dg = self.three_prime_delta_g(window, tail);
+        let reverse_three_prime_dg =
+            self.three_prime_delta_g(&iupac::reverse_complement(window), tail);
+
+        let tm_ok = tm.is_some_and(|tm| (params.tm_min..=params.tm_max).contains(&tm));
+        let acceptable = |delta_g: Option<f32>| {
+            tm_ok && delta_g.is_some_and(|dg| dg >= params.max_three_prime_stability)
+        };
+
+        MeltingPoint {
+            position,
+            gc_percent,
+            tm,
+            forward_three_prime_dg,
+            reverse_three_prime_dg,
+            forward_ok: acceptable(forward_three_prime_dg),
+            reverse_ok: acceptable(reverse_three_prime_dg),
+        }
+    }
+
+    /// プライマー配列の3'末端 `length` 塩基の ΔG（計算条件の温度）
+    fn three_prime_delta_g(&self, primer: &str, length: usize) -> Option<f32> {
+        let temperature_k = self.calculator.get_conditions().temperature_k;
+        self.calculator
+            .calculate_delta_g(&primer[primer.len() - length..], temperature_k)
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_windows_and_three_prime_ends() {
+        let service = MeltingMapService::new();
+        let params = MeltingMapParams {
+            window: 20,
+            step: 5,
+            ..MeltingMapParams::default()
+        };
+        // AT リッチな前半と GC リッチな後半
+        let sequence = format!("{}{}", "ATTATAAATTTATAATTAAT", "GCGGCCGCGGAGCTCGGCCC");
+
+        let map = service.profile(&sequence, 0, sequence.len(), &params);
+        let positions: Vec<usize> = map.points.iter().map(|p| p.position).collect();
+        assert_eq!(positions, vec![0, 5, 10, 15, 20]);
+
+        let at_rich = &map.points[0];
+        let gc_rich = &map.points[4];
+        assert!(gc_rich.tm.unwrap() > at_rich.tm.unwrap() + 20.0);
+        assert!(!at_rich.forward_ok && !at_rich.reverse_ok);
+
+        // 3'末端が GC の順方向は、3'末端が AT の逆方向より安定
+        let junction = &map.points[2];
+        assert!(
+            junction.forward_three_prime_dg.unwrap() < junction.reverse_three_prime_dg.unwrap()
+        );
+        assert!(map.provenance.is_some());
+    }
+
+    #[test]
+    fn test_ambiguous_windows_have_no_values() {
+        let service = MeltingMapService::new();
+        let params = MeltingMapParams {
+            window: 10,
+            ..MeltingMapParams::default()
+        };
+        let map = service.profile("ACGTNACGTACGTACG", 0, 16, &params);
+        assert_eq!(map.points.len(), 7);
+        assert!(map.points[..5]
+            .iter()
+            .all(|p| p.tm.is_none() && !p.forward_ok));
+        assert!(map.points[5].tm.is_some());
+    }
+
+    #[test]
+    fn test_tm_grid_rows_by_length() {
+        let service = MeltingMapService::new();
+        let params = TmGridParams {
+            length_min: 18,
+            length_max: 22,
+            step: 2,
+        };
+        let sequence = format!("{}{}", "ATTATAAATTTATAATTAAT", "GCGGCCGCGGAGCTCGGCCC");
+
+        let grid = service.tm_grid(&sequence, 0, 40, &params);
+        assert_eq!(grid.lengths, vec![18, 19, 20, 21, 22]);
+        assert_eq!(grid.positions, (0..=22).step_by(2).collect::<Vec<_>>());
+        assert_eq!(grid.tm.len(), 5);
+        assert!(grid.tm.iter().all(|row| row.len() == grid.positions.len()));
+
+        // 同じ開始位置なら長いほど Tm が高く、領域をはみ出す候補は値がない
+        let (short, long) = (grid.tm[0][0].unwrap(), grid.tm[4][0].unwrap());
+        assert!(long > short);
+        assert!(grid.tm[4][11].is_none() && grid.tm[0][11].is_some());
+        assert_eq!(
+            grid.tm[2][0],
+            service
+                .calculator
+                .calculate_tm_nearest_neighbor(&sequence[..20])
+                .ok()
+        );
+        assert!(grid.tm_max.unwrap() > grid.tm_min.unwrap() + 20.0);
+    }
+}
@@ -1,6 +1,122 @@
 // Service layer - アプリケーションサービス
+pub mod amplicon;
+pub mod amplicon_qc;
+pub mod assay_document;
+pub mod assembly_primers;
+pub mod backbone;
+pub mod bed_export;
+pub mod cloning;
+pub mod codon_usage;
+pub mod concentration;
+pub mod conservation;
+pub mod construct;
+pub mod contamination;
+pub mod coordinates;
+pub mod crispr_design;
+pub mod degenerate_oligo;
+pub mod digest_protocol;
+pub mod dilution;
+pub mod dotplot;
+pub mod edit;
+pub mod frameshift;
+pub mod gc_skew;
+#[cfg(feature = "gc-skew-plugin")]
+pub mod gc_skew_plugin;
+pub mod hgvs;
+pub mod homology_arm;
+pub mod hrm_tiling;
+pub mod hydropathy;
+pub mod input_validation;
+pub mod junction_primers;
+pub mod linker;
+pub mod map_layout;
+pub mod melt_curve;
+pub mod melting_map;
+pub mod mutagenesis;
+pub mod navigation;
+pub mod nullomer;
+pub mod oligo_distance;
+pub mod oligo_properties;
+pub mod orf;
+pub mod panel_stress;
+pub mod plugin_registry;
 pub mod primer_design;
+pub mod primer_specificity;
+pub mod probe_labeling;
+pub mod project_archive;
+pub mod protein_motif;
+pub mod proteome;
+pub mod qpcr_assay;
+pub mod rbs;
+pub mod read_stats;
+pub mod repeat_finder;
+pub mod restriction;
+pub mod scaffold;
+pub mod screening;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod sequence_format;
+pub mod sequence_search;
+pub mod signal_peptide;
+pub mod standard_curve;
 pub mod stats;
 
+pub use amplicon::AmpliconService;
+pub use amplicon_qc::AmpliconQcService;
+pub use assay_document::AssayDocumentService;
+pub use assembly_primers::AssemblyPrimerService;
+pub use backbone::BackboneDetectionService;
+pub use bed_export::BedExportService;
+pub use cloning::CloningService;
+pub use codon_usage::CodonUsageService;
+pub use concentration::ConcentrationService;
+pub use conservation::ConservationService;
+pub use construct::ExpressionConstructService;
+pub use contamination::VectorContaminationService;
+pub use coordinates::CoordinateService;
+pub use crispr_design::CrisprDesignService;
+pub use degenerate_oligo::DegenerateOligoService;
+pub use digest_protocol::DigestProtocolService;
+pub use dilution::DilutionService;
+pub use dotplot::DotPlotService;
+pub use edit::EditService;
+pub use frameshift::FrameshiftService;
+pub use gc_skew::StreamingGcSkew;
+pub use hgvs::HgvsService;
+pub use homology_arm::HomologyArmService;
+pub use hrm_tiling::HrmTilingService;
+pub use hydropathy::HydropathyService;
+pub use input_validation::InputValidationService;
+pub use junction_primers::JunctionPrimerService;
+pub use linker::LinkerDesignService;
+pub use map_layout::MapLayoutService;
+pub use melt_curve::MeltCurveService;
+pub use melting_map::MeltingMapService;
+pub use mutagenesis::MutationSimulationService;
+pub use navigation::NavigationService;
+pub use nullomer::NullomerService;
+pub use oligo_distance::OligoDistanceService;
+pub use oligo_properties::OligoPropertiesService;
+pub use orf::OrfService;
+pub use panel_stress::PanelStressService;
+pub use plugin_registry::PluginRegistry;
 pub use primer_design::PrimerDesignServiceImpl;
-pub use stats::StatsServiceImpl;
+pub use primer_specificity::PrimerSpecificityService;
+pub use probe_labeling::ProbeLabelService;
+pub use project_archive::ProjectArchiveService;
+pub use protein_motif::ProteinMotifService;
+pub use proteome::ProteomeService;
+pub use qpcr_assay::QpcrAssayService;
+pub use rbs::RbsStrengthService;
+pub use read_stats::ReadSetStatsService;
+pub use repeat_finder::RepeatFinderService;
+pub use restriction::RestrictionAnalysisService;
+pub use scaffold::ScaffoldService;
+pub use screening::ScreeningService;
+#[cfg(feature = "scripting")]
+pub use scripting::{ScriptService, WindowReader};
+pub use sequence_format::SequenceFormatService;
+pub use sequence_search::{SequenceSearchService, StreamingSearch};
+pub use signal_peptide::SignalPeptideService;
+pub use standard_curve::StandardCurveService;
+pub use stats::{StatsServiceImpl, StreamingStats};
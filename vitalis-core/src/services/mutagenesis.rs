@@ -0,0 +1,186 @@
+// Service layer: Randomized in-silico mutagenesis
+use crate::domain::mutation::{MutationRates, SimulatedMutant};
+use crate::domain::reproducibility::{self, SplitMix64};
+use crate::domain::variant::Variant;
+use thiserror::Error;
+
+const BASES: [u8; 4] = *b"ACGT";
+
+#[derive(Error, Debug)]
+pub enum MutationError {
+    #[error("Mutation rates must be between 0 and 1 and sum to at most 1")]
+    InvalidRates,
+    #[error("Template sequence is empty")]
+    EmptyTemplate,
+}
+
+/// Mutation simulation service
+pub struct MutationSimulationService;
+
+impl Default for MutationSimulationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MutationSimulationService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Produce `count` independently mutated copies of `template`. Each base is
+    /// deleted, preceded by an insertion, or substituted with the given
+    /// probabilities; mutations never overlap.
+    pub fn simulate(
+        &self,
+        template: &str,
+        count: usize,
+        rates: &MutationRates,
+    ) -> Result<Vec<SimulatedMutant>, MutationError> {
+        if template.is_empty() {
+            return Err(MutationError::EmptyTemplate);
+        }
+        let probabilities = [rates.snv, rates.insertion, rates.deletion];
+        if probabilities.iter().any(|p| !(0.0..=1.0).contains(p))
+            || probabilities.iter().sum::<f64>() > 1.0
+        {
+            return Err(MutationError::InvalidRates);
+        }
+
+        // シード未指定ならワークスペースの乱数源から（再現性モードでは決定的）
+        let seed = rates.seed.unwrap_or_else(reproducibility::workspace_u64);
+        let mut rng = SplitMix64::new(seed);
+        let template = template.to_ascii_uppercase();
+
+        Ok((0..count)
+            .map(|_| {
+                let variants = self.sample_variants(template.as_bytes(), rates, &mut rng);
+                let mut sequence = template.clone();
+                // 後ろから適用して前方の座標をずらさない
+                for variant in variants.iter().rev() {
+                    if let Some(mutated) = variant.apply(&sequence, 0) {
+                        sequence = mutated;
+                    }
+                }
+                SimulatedMutant {
+                    seq_id: String::new(),
+                    sequence,
+                    variants,
+                }
+            })
+            .collect())
+    }
+
+    fn sample_variants(
+        &self,
+        template: &[u8],
+        rates: &MutationRates,
+        rng: &mut SplitMix64,
+    ) -> Vec<Variant> {
+        let max_indel = rates.max_indel_length.max(1);
+        let random_bases = |rng: &mut SplitMix64, length: usize| -> String {
+            (0..length)
+                .map(|_| BASES[rng.below(BASES.len())] as char)
+                .collect()
+        };
+
+        let mut variants = Vec::new();
+        let mut position = 0;
+        while position < template.len() {
+            let draw = rng.next_f64();
+            let reference = template[position] as char;
+
+            if draw < rates.deletion {
+                let length = (1 + rng.below(max_indel)).min(template.len() - position);
+                let deleted = String::from_utf8_lossy(&template[position..position + length]);
+                variants.push(Variant::new(position, &deleted, ""));
+                position += length;
+                continue;
+            }
+
+            if draw < rates.deletion + rates.insertion {
+                let length = 1 + rng.below(max_indel);
+                let inserted = random_bases(rng, length);
+                variants.push(Variant::new(position, "", &inserted));
+            } else if draw < rates.deletion + rates.insertion + rates.snv {
+                let alternatives: Vec<u8> = BASES
+                    .iter()
+                    .copied()
+                    .filter(|&b| b != reference as u8)
+                    .collect();
+                let alternate = alternatives[rng.below(alternatives.len())] as char;
+                variants.push(Variant::new(
+                    position,
+                    &reference.to_string(),
+                    &alternate.to_string(),
+                ));
+            }
+            position += 1;
+        }
+        variants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rates(snv: f64, insertion: f64, deletion: f64) -> MutationRates {
+        MutationRates {
+            snv,
+            insertion,
+            deletion,
+            max_indel_length: 3,
+            seed: Some(42),
+        }
+    }
+
+    #[test]
+    fn test_mutants_are_reproducible_and_consistent() {
+        let template = "ACGTTGCAAGCTTGGCACTGGCCGTCGTTTTACAACGTCGTGACTGGGAAAACC".repeat(10);
+        let service = MutationSimulationService::new();
+        let mutants = service
+            .simulate(&template, 5, &rates(0.02, 0.005, 0.005))
+            .unwrap();
+        assert_eq!(mutants.len(), 5);
+        assert_eq!(
+            mutants,
+            service
+                .simulate(&template, 5, &rates(0.02, 0.005, 0.005))
+                .unwrap()
+        );
+
+        for mutant in &mutants {
+            assert!(!mutant.variants.is_empty());
+            let delta: isize = mutant
+                .variants
+                .iter()
+                .map(|v| v.alternate.len() as isize - v.reference.len() as isize)
+                .sum();
+            assert_eq!(
+                mutant.sequence.len() as isize,
+                template.len() as isize + delta
+            );
+            for variant in &mutant.variants {
+                let end = variant.position + variant.reference.len();
+                assert_eq!(&template[variant.position..end], variant.reference);
+                assert_ne!(variant.reference, variant.alternate);
+            }
+        }
+    }
+
+    #[test]
+    fn test_substitution_only_rates() {
+        let template = "A".repeat(200);
+        let mutants = MutationSimulationService::new()
+            .simulate(&template, 1, &rates(1.0, 0.0, 0.0))
+            .unwrap();
+        assert_eq!(mutants[0].variants.len(), 200);
+        assert!(!mutants[0].sequence.contains('A'));
+
+        assert!(matches!(
+            MutationSimulationService::new().simulate(&template, 1, &rates(0.6, 0.3, 0.3)),
+            Err(MutationError::InvalidRates)
+        ));
+    }
+}
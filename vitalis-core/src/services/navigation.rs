@@ -0,0 +1,295 @@
+// Service layer: Next/previous navigation over annotations, sites, ORFs and bookmarks
+use crate::domain::feature::SequenceFeature;
+use crate::domain::navigation::{Bookmark, NavigationDirection, NavigationHit, NavigationKind};
+use crate::domain::restriction::RestrictionEnzyme;
+use crate::domain::Topology;
+use crate::services::{OrfService, RestrictionAnalysisService};
+use std::cmp::Reverse;
+
+/// 制限酵素サイトを探す最初の窓幅（見つからなければ倍々に広げる）
+const SITE_WINDOW: usize = 64 * 1024;
+/// 窓の両側に読み足す塩基数（窓の端にかかる認識配列と窓外の切断位置のため）
+const SITE_CONTEXT: usize = 64;
+
+/// 「次の遺伝子へ」などの移動先を探すサービス。
+/// `from` より後（Next）または前（Previous）で最も近い位置から始まるものを返す
+pub struct NavigationService {
+    restriction: RestrictionAnalysisService,
+    orf: OrfService,
+}
+
+impl Default for NavigationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NavigationService {
+    pub fn new() -> Self {
+        Self {
+            restriction: RestrictionAnalysisService::new(),
+            orf: OrfService::new(),
+        }
+    }
+
+    /// 開始位置順の `features` から、種類が `feature_types` に含まれる（空ならすべて）ものを探す
+    pub fn feature(
+        &self,
+        features: &[SequenceFeature],
+        feature_types: &[String],
+        from: usize,
+        direction: NavigationDirection,
+    ) -> Option<NavigationHit> {
+        let wanted = |f: &&SequenceFeature| {
+            feature_types.is_empty() || feature_types.contains(&f.feature_type)
+        };
+        let feature = match direction {
+            NavigationDirection::Next => {
+                let first = features.partition_point(|f| f.start <= from);
+                features[first..].iter().find(wanted)
+            }
+            NavigationDirection::Previous => {
+                let end = features.partition_point(|f| f.start < from);
+                features[..end].iter().rev().find(wanted)
+            }
+        }?;
+        Some(NavigationHit {
+            kind: NavigationKind::Feature,
+            id: Some(feature.id.clone()),
+            label: feature.label(),
+            start: feature.start,
+            end: feature.end,
+            strand: Some(feature.strand),
+        })
+    }
+
+    /// 位置順の `bookmarks` から探す
+    pub fn bookmark(
+        &self,
+        bookmarks: &[Bookmark],
+        from: usize,
+        direction: NavigationDirection,
+    ) -> Option<NavigationHit> {
+        let bookmark = match direction {
+            NavigationDirection::Next => {
+                bookmarks.get(bookmarks.partition_point(|b| b.position <= from))
+            }
+            NavigationDirection::Previous => {
+                bookmarks[..bookmarks.partition_point(|b| b.position < from)].last()
+            }
+        }?;
+        Some(NavigationHit {
+            kind: NavigationKind::Bookmark,
+            id: Some(bookmark.id.clone()),
+            label: bookmark.label.clone(),
+            start: bookmark.position,
+            end: bookmark.position + 1,
+            strand: None,
+        })
+    }
+
+    /// 配列全体の ORF（`min_length` アミノ酸以上）から探す
+    pub fn orf(
+        &self,
+        sequence: &str,
+        min_length: usize,
+        from: usize,
+        direction: NavigationDirection,
+    ) -> Option<NavigationHit> {
+        let hits = self
+            .orf
+            .find_orfs(sequence, min_length)
+            .into_iter()
+            .filter(|orf| match direction {
+                NavigationDirection::Next => orf.start > from,
+                NavigationDirection::Previous => orf.start < from,
+            })
+            .map(|orf| NavigationHit {
+                kind: NavigationKind::Orf,
+                id: None,
+                label: format!("ORF {:+} ({} aa)", orf.frame, orf.length_aa()),
+                start: orf.start,
+                end: orf.end,
+                strand: Some(orf.strand),
+            });
+        Self::nearest(hits, direction)
+    }
+
+    /// `enzymes` の認識サイトを `from` から外側へ窓を広げながら探す。
+    /// `read(start, end)` で窓の塩基を読むため、近くにあれば配列全体は読まない
+    /// （環状配列でも起点はまたがない）
+    pub fn restriction_site<S: AsRef<str>, E>(
+        &self,
+        length: usize,
+        enzymes: &[&RestrictionEnzyme],
+        from: usize,
+        direction: NavigationDirection,
+        mut read: impl FnMut(usize, usize) -> Result<S, E>,
+    ) -> Result<Option<NavigationHit>, E> {
+        let mut scan = |lo: usize, hi: usize| -> Result<Option<NavigationHit>, E> {
+            let context_start = lo.saturating_sub(SITE_CONTEXT);
+            let context_end = (hi + SITE_CONTEXT).min(length);
+            let text = read(context_start, context_end)?;
+            let hits = self
+                .restriction
+                .scan(text.as_ref(), &Topology::Linear, enzymes)
+                .into_iter()
+                .map(|site| (context_start + site.position, site))
+                .filter(|(position, _)| (lo..hi).contains(position))
+                .map(|(position, site)| {
+                    let site_length = enzymes
+                        .iter()
+                        .find(|e| e.name == site.enzyme)
+                        .map_or(1, |e| e.site.len());
+                    NavigationHit {
+                        kind: NavigationKind::RestrictionSite,
+                        id: None,
+                        label: site.enzyme,
+                        start: position,
+                        end: position + site_length,
+                        strand: Some(site.strand),
+                    }
+                });
+            Ok(Self::nearest(hits, direction))
+        };
+
+        let mut size = SITE_WINDOW;
+        match direction {
+            NavigationDirection::Next => {
+                let mut lo = from + 1;
+                while lo < length {
+                    let hi = (lo + size).min(length);
+                    if let Some(hit) = scan(lo, hi)? {
+                        return Ok(Some(hit));
+                    }
+                    lo = hi;
+                    size *= 2;
+                }
+            }
+            NavigationDirection::Previous => {
+                let mut hi = from.min(length);
+                while hi > 0 {
+                    let lo = hi.saturating_sub(size);
+                    if let Some(hit) = scan(lo, hi)? {
+                        return Ok(Some(hit));
+                    }
+                    hi = lo;
+                    size *= 2;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// 候補のうち移動方向で最も近いもの（同じ位置なら先に渡したもの）
+    pub fn nearest(
+        hits: impl IntoIterator<Item = NavigationHit>,
+        direction: NavigationDirection,
+    ) -> Option<NavigationHit> {
+        match direction {
+            NavigationDirection::Next => hits.into_iter().min_by_key(|hit| hit.start),
+            NavigationDirection::Previous => hits.into_iter().min_by_key(|hit| Reverse(hit.start)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Strand;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_feature_and_bookmark_navigation() {
+        let service = NavigationService::new();
+        let features = vec![
+            SequenceFeature::new("gene", 10, 50, Strand::Forward),
+            SequenceFeature::new("CDS", 60, 90, Strand::Reverse),
+            SequenceFeature::new("gene", 120, 200, Strand::Reverse),
+        ];
+        let genes = ["gene".to_string()];
+
+        let next = service
+            .feature(&features, &genes, 10, NavigationDirection::Next)
+            .unwrap();
+        assert_eq!((next.start, next.label.as_str()), (120, "gene"));
+        let previous = service
+            .feature(&features, &[], 120, NavigationDirection::Previous)
+            .unwrap();
+        assert_eq!(previous.start, 60);
+        assert!(service
+            .feature(&features, &genes, 10, NavigationDirection::Previous)
+            .is_none());
+
+        let bookmarks = vec![Bookmark {
+            id: "bookmark_1".to_string(),
+            position: 100,
+            label: "check".to_string(),
+        }];
+        let bookmark = service
+            .bookmark(&bookmarks, 10, NavigationDirection::Next)
+            .unwrap();
+        let nearest =
+            NavigationService::nearest([next, bookmark.clone()], NavigationDirection::Next);
+        assert_eq!(nearest, Some(bookmark));
+    }
+
+    #[test]
+    fn test_restriction_site_search_widens_windows() {
+        let service = NavigationService::new();
+        let enzymes = RestrictionAnalysisService::new();
+        let eco_ri = enzymes.resolve(&["EcoRI".to_string()]).unwrap();
+        let filler = "AT".repeat(100_000);
+        let sequence = format!("{}GAATTC{}GAATTC{}", &filler[..500], filler, &filler[..500]);
+        let second = 500 + 6 + filler.len();
+
+        let read_bases = Cell::new(0);
+        let read = |start: usize, end: usize| -> Result<&str, ()> {
+            read_bases.set(read_bases.get() + end - start);
+            Ok(&sequence[start..end])
+        };
+        let hit = service
+            .restriction_site(sequence.len(), &eco_ri, 0, NavigationDirection::Next, read)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (hit.start, hit.end, hit.label.as_str()),
+            (500, 506, "EcoRI")
+        );
+        // 近くのサイトは最初の窓だけで見つかる
+        assert!(read_bases.get() < SITE_WINDOW * 2);
+
+        let hit = service
+            .restriction_site(
+                sequence.len(),
+                &eco_ri,
+                500,
+                NavigationDirection::Next,
+                read,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(hit.start, second);
+        let hit = service
+            .restriction_site(
+                sequence.len(),
+                &eco_ri,
+                second,
+                NavigationDirection::Previous,
+                read,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(hit.start, 500);
+        assert!(service
+            .restriction_site(
+                sequence.len(),
+                &eco_ri,
+                500,
+                NavigationDirection::Previous,
+                read
+            )
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,172 @@
+// Service layer: Absent k-mer (nullomer) search across a sequence set
+use crate::domain::index::encode_base;
+use crate::domain::iupac;
+use crate::domain::nullomer::{NullomerParams, NullomerReport};
+
+const BASES: [u8; 4] = *b"ACGT";
+
+/// Nullomer search service
+pub struct NullomerService;
+
+impl Default for NullomerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NullomerService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `sequences`（配列と環状かどうか）のどこにも現れない k-mer を数え、条件を満たすものを
+    /// 辞書順に返す。4^k 通りの出現をビット列で記録する
+    pub fn find(
+        &self,
+        sequences: &[(&str, bool)],
+        k: usize,
+        params: &NullomerParams,
+    ) -> NullomerReport {
+        let possible = 1u64 << (2 * k);
+        let mask = possible - 1;
+        let mut seen = vec![0u64; possible.div_ceil(64) as usize];
+        let mut mark = |code: u64| seen[(code / 64) as usize] |= 1 << (code % 64);
+
+        let mut scanned_positions = 0;
+        for &(sequence, circular) in sequences {
+            let text = iupac::search_text(sequence, circular, k - 1);
+            let (mut code, mut reverse, mut valid) = (0u64, 0u64, 0usize);
+            for &base in &text {
+                match encode_base(base) {
+                    Some(bits) => {
+                        code = ((code << 2) | bits) & mask;
+                        reverse = (reverse >> 2) | ((3 - bits) << (2 * (k - 1)));
+                        valid += 1;
+                    }
+                    None => valid = 0,
+                }
+                if valid >= k {
+                    scanned_positions += 1;
+                    mark(code);
+                    if params.both_strands {
+                        mark(reverse);
+                    }
+                }
+            }
+        }
+
+        let mut present = 0;
+        let mut matching = 0;
+        let mut nullomers = Vec::new();
+        for code in 0..possible {
+            if seen[(code / 64) as usize] & (1 << (code % 64)) != 0 {
+                present += 1;
+                continue;
+            }
+            if !self.passes(code, k, params) {
+                continue;
+            }
+            matching += 1;
+            if nullomers.len() < params.max_results {
+                nullomers.push(decode(code, k));
+            }
+        }
+
+        NullomerReport {
+            k,
+            sequences: sequences.len(),
+            scanned_positions,
+            possible,
+            present,
+            absent: possible - present,
+            matching,
+            truncated: matching > nullomers.len() as u64,
+            nullomers,
+        }
+    }
+
+    /// 文字列にせず符号のまま GC 数と最長ホモポリマーを調べる
+    fn passes(&self, code: u64, k: usize, params: &NullomerParams) -> bool {
+        let bases = (0..k).map(|i| (code >> (2 * i)) & 3);
+        let gc = bases.clone().filter(|&bits| bits == 1 || bits == 2).count();
+        let gc_percent = gc as f64 * 100.0 / k as f64;
+        if gc_percent < params.gc_min || gc_percent > params.gc_max {
+            return false;
+        }
+        let Some(max) = params.max_homopolymer else {
+            return true;
+        };
+        let mut run = 0;
+        let mut previous = None;
+        for bits in bases {
+            run = if previous == Some(bits) { run + 1 } else { 1 };
+            if run > max {
+                return false;
+            }
+            previous = Some(bits);
+        }
+        true
+    }
+}
+
+fn decode(code: u64, k: usize) -> String {
+    (0..k)
+        .rev()
+        .map(|i| BASES[((code >> (2 * i)) & 3) as usize] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absent_dimers() {
+        let service = NullomerService::new();
+        let single = NullomerParams {
+            both_strands: false,
+            ..NullomerParams::default()
+        };
+
+        // AACG の 2-mer は AA, AC, CG のみ
+        let report = service.find(&[("AACG", false)], 2, &single);
+        assert_eq!(
+            (report.possible, report.present, report.absent),
+            (16, 3, 13)
+        );
+        assert_eq!(report.scanned_positions, 3);
+        assert!(report.nullomers.contains(&"GA".to_string()));
+        assert!(!report.nullomers.contains(&"CG".to_string()));
+
+        // 相補鎖の CG, GT, TT も出現とみなす
+        let report = service.find(&[("AACG", false)], 2, &NullomerParams::default());
+        assert_eq!(report.present, 5);
+        assert!(!report.nullomers.contains(&"TT".to_string()));
+
+        // 環状なら GA（末尾→先頭）も現れる。N をまたぐ k-mer は数えない
+        let report = service.find(&[("AACG", true), ("CCNGG", false)], 2, &single);
+        assert_eq!(report.scanned_positions, 4 + 2);
+        assert!(!report.nullomers.contains(&"GA".to_string()));
+        assert!(!report.nullomers.contains(&"GG".to_string()));
+    }
+
+    #[test]
+    fn test_filters_and_truncation() {
+        let params = NullomerParams {
+            gc_min: 40.0,
+            gc_max: 60.0,
+            max_homopolymer: Some(2),
+            max_results: 5,
+            ..NullomerParams::default()
+        };
+        let report = NullomerService::new().find(&[("ACGTACGTAC", false)], 4, &params);
+        assert_eq!(report.nullomers.len(), 5);
+        assert!(report.truncated);
+        assert!(report.matching > 5);
+        assert!(report.nullomers.iter().all(|kmer| {
+            let gc = kmer.bytes().filter(|&b| b == b'G' || b == b'C').count();
+            gc == 2 && !kmer.contains("AAA") && !kmer.contains("CCC")
+        }));
+        assert!(report.nullomers.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,234 @@
+// Service layer: Pairwise edit distances and cross-talk clustering of oligo sets
+use crate::domain::iupac;
+use crate::domain::oligo::{
+    ClusterMerge, CrossTalkParams, CrossTalkReport, Linkage, Oligo, OligoCluster,
+};
+
+/// Oligo distance service
+pub struct OligoDistanceService;
+
+impl Default for OligoDistanceService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OligoDistanceService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 全組の編集距離を求め、凝集型の階層クラスタリングで似たオリゴをまとめる。
+    /// 計算量はオリゴ数 n に対して O(n³)（結合ごとに最小距離の組を全探索）
+    pub fn analyze(&self, oligos: &[Oligo], params: &CrossTalkParams) -> CrossTalkReport {
+        let sequences: Vec<Vec<u8>> = oligos.iter().map(|o| normalize(&o.sequence)).collect();
+        let reverse: Vec<Vec<u8>> = oligos
+            .iter()
+            .map(|o| normalize(&iupac::reverse_complement(&o.sequence)))
+            .collect();
+
+        let n = oligos.len();
+        let mut distances = vec![vec![0usize; n]; n];
+        let mut reverse_complement_pairs = Vec::new();
+        for i in 0..n {
+            for j in i + 1..n {
+                let forward = levenshtein(&sequences[i], &sequences[j]);
+                let distance = if params.include_reverse_complement {
+                    let rc = levenshtein(&sequences[i], &reverse[j]);
+                    if rc < forward {
+                        reverse_complement_pairs.push((i, j));
+                    }
+                    forward.min(rc)
+                } else {
+                    forward
+                };
+                distances[i][j] = distance;
+                distances[j][i] = distance;
+            }
+        }
+
+        let (merges, groups) = cluster(&distances, params);
+        let clusters = groups
+            .into_iter()
+            .filter(|members| members.len() > 1)
+            .map(|members| {
+                let pairs: Vec<usize> = members
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(k, &a)| {
+                        members[k + 1..]
+                            .iter()
+                            .map(|&b| distances[a][b])
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                OligoCluster {
+                    members: members.iter().map(|&i| oligos[i].name.clone()).collect(),
+                    max_distance: pairs.iter().copied().max().unwrap_or(0),
+                    min_distance: pairs.iter().copied().min().unwrap_or(0),
+                }
+            })
+            .collect();
+
+        CrossTalkReport {
+            names: oligos.iter().map(|o| o.name.clone()).collect(),
+            distances,
+            reverse_complement_pairs,
+            merges,
+            clusters,
+        }
+    }
+}
+
+fn normalize(sequence: &str) -> Vec<u8> {
+    sequence
+        .bytes()
+        .map(|b| match b.to_ascii_uppercase() {
+            b'U' => b'T',
+            b => b,
+        })
+        .collect()
+}
+
+/// Levenshtein 距離（置換・挿入・欠失それぞれコスト1）
+pub fn levenshtein(a: &[u8], b: &[u8]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, &x) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, &y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != y);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// 全結合の履歴と、閾値以下の結合だけで得られるグループ（入力順のインデックス）
+fn cluster(
+    distances: &[Vec<usize>],
+    params: &CrossTalkParams,
+) -> (Vec<ClusterMerge>, Vec<Vec<usize>>) {
+    let n = distances.len();
+    let mut between: Vec<Vec<f64>> = distances
+        .iter()
+        .map(|row| row.iter().map(|&d| d as f64).collect())
+        .collect();
+    // スロット i のクラスタID・メンバー（None は結合済み）
+    let mut ids: Vec<usize> = (0..n).collect();
+    let mut members: Vec<Option<Vec<usize>>> = (0..n).map(|i| Some(vec![i])).collect();
+    let mut merges = Vec::new();
+    let mut groups = None;
+
+    for step in 0..n.saturating_sub(1) {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for i in 0..n {
+            if members[i].is_none() {
+                continue;
+            }
+            for j in i + 1..n {
+                if members[j].is_some() && best.is_none_or(|(_, _, d)| between[i][j] < d) {
+                    best = Some((i, j, between[i][j]));
+                }
+            }
+        }
+        let Some((i, j, distance)) = best else {
+            break;
+        };
+        if distance > params.max_cluster_distance && groups.is_none() {
+            groups = Some(members.iter().flatten().cloned().collect::<Vec<_>>());
+        }
+
+        let right = members[j].take().unwrap_or_default();
+        let (size_i, size_j) = (
+            members[i].as_ref().map_or(0, Vec::len) as f64,
+            right.len() as f64,
+        );
+        for k in 0..n {
+            if k == i || members[k].is_none() {
+                continue;
+            }
+            let updated = match params.linkage {
+                Linkage::Single => between[i][k].min(between[j][k]),
+                Linkage::Complete => between[i][k].max(between[j][k]),
+                Linkage::Average => {
+                    (size_i * between[i][k] + size_j * between[j][k]) / (size_i + size_j)
+                }
+            };
+            between[i][k] = updated;
+            between[k][i] = updated;
+        }
+
+        let merged = members[i].get_or_insert_with(Vec::new);
+        merged.extend(right);
+        merged.sort_unstable();
+        merges.push(ClusterMerge {
+            left: ids[i],
+            right: ids[j],
+            distance,
+            size: merged.len(),
+        });
+        ids[i] = n + step;
+    }
+
+    let groups = groups.unwrap_or_else(|| members.into_iter().flatten().collect());
+    (merges, groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oligo(name: &str, sequence: &str) -> Oligo {
+        Oligo {
+            name: name.to_string(),
+            sequence: sequence.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein(b"ACGT", b"ACGT"), 0);
+        assert_eq!(levenshtein(b"ACGT", b"AGT"), 1);
+        assert_eq!(levenshtein(b"ACGTAC", b"TGCA"), 4);
+        assert_eq!(levenshtein(b"", b"ACG"), 3);
+    }
+
+    #[test]
+    fn test_clusters_similar_barcodes() {
+        let oligos = vec![
+            oligo("bc1", "ACGTACGTAA"),
+            oligo("bc2", "ACGTACGTAT"),
+            oligo("bc3", "GGCCTTAAGC"),
+            oligo("bc4", "ACGTACGAAT"),
+            // bc1 の逆相補鎖に近い
+            oligo("bc5", "TTACGTACGA"),
+        ];
+        let service = OligoDistanceService::new();
+        let report = service.analyze(&oligos, &CrossTalkParams::default());
+
+        assert_eq!(report.distances[0][1], 1);
+        assert_eq!(report.distances[1][0], 1);
+        assert_eq!(report.distances[0][4], 1);
+        assert!(report.reverse_complement_pairs.contains(&(0, 4)));
+        assert_eq!(report.merges.len(), 4);
+        assert_eq!(report.merges.last().unwrap().size, 5);
+
+        assert_eq!(report.clusters.len(), 1);
+        let cluster = &report.clusters[0];
+        assert_eq!(cluster.members, vec!["bc1", "bc2", "bc4", "bc5"]);
+        assert_eq!(cluster.min_distance, 1);
+
+        let forward_only = service.analyze(
+            &oligos,
+            &CrossTalkParams {
+                include_reverse_complement: false,
+                max_cluster_distance: 1.0,
+                linkage: Linkage::Single,
+            },
+        );
+        assert!(forward_only.reverse_complement_pairs.is_empty());
+        assert_eq!(forward_only.clusters[0].members, vec!["bc1", "bc2", "bc4"]);
+    }
+}
@@ -0,0 +1,183 @@
+// Service layer: Extinction coefficients, molecular weights and OD260 conversions of oligos
+use crate::domain::oligo::{NucleicAcid, OligoAmount, OligoAmountUnit, OligoProperties};
+use thiserror::Error;
+
+/// 最近接塩基対の ε260（×10³ L/(mol·cm)）。行・列とも A, C, G, T(U) の順
+/// （Cantor et al. 1970 / Puglisi & Tinoco 1989）
+const DNA_NEAREST_NEIGHBOR: [[f64; 4]; 4] = [
+    [27.4, 21.2, 25.0, 22.8],
+    [21.2, 14.6, 18.0, 15.2],
+    [25.2, 17.6, 21.6, 20.0],
+    [23.4, 16.2, 19.0, 16.8],
+];
+const DNA_SINGLE: [f64; 4] = [15.4, 7.4, 11.5, 8.7];
+const RNA_NEAREST_NEIGHBOR: [[f64; 4]; 4] = [
+    [27.4, 21.0, 25.0, 24.0],
+    [21.0, 14.2, 17.8, 16.2],
+    [25.2, 17.4, 21.6, 21.2],
+    [24.6, 17.2, 20.0, 19.6],
+];
+const RNA_SINGLE: [f64; 4] = [15.4, 7.2, 11.5, 9.9];
+
+/// ヌクレオチド残基の質量（g/mol）。A, C, G, T(U) の順
+const DNA_RESIDUE_MASS: [f64; 4] = [313.21, 289.18, 329.21, 304.2];
+const RNA_RESIDUE_MASS: [f64; 4] = [329.21, 305.18, 345.21, 306.17];
+/// 5' 末端にリン酸がない分の補正（-HPO₃ + H₂O 相当）
+const TERMINAL_CORRECTION: f64 = -61.96;
+
+#[derive(Error, Debug)]
+pub enum OligoPropertiesError {
+    #[error("Empty oligo sequence")]
+    EmptySequence,
+    #[error("Unsupported base '{0}' (only A, C, G, T or U)")]
+    UnsupportedBase(char),
+    #[error("Oligo sequence contains both T and U")]
+    MixedBackbone,
+}
+
+/// Oligo properties service
+pub struct OligoPropertiesService;
+
+impl Default for OligoPropertiesService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OligoPropertiesService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 一本鎖オリゴの ε260（最近接塩基対の和から内側の塩基の単独値を引く）と分子量
+    pub fn properties(&self, sequence: &str) -> Result<OligoProperties, OligoPropertiesError> {
+        let mut indices = Vec::with_capacity(sequence.len());
+        let (mut has_t, mut has_u) = (false, false);
+        for c in sequence.chars().filter(|c| !c.is_whitespace()) {
+            let index = match c.to_ascii_uppercase() {
+                'A' => 0,
+                'C' => 1,
+                'G' => 2,
+                'T' => {
+                    has_t = true;
+                    3
+                }
+                'U' => {
+                    has_u = true;
+                    3
+                }
+                other => return Err(OligoPropertiesError::UnsupportedBase(other)),
+            };
+            indices.push(index);
+        }
+        if indices.is_empty() {
+            return Err(OligoPropertiesError::EmptySequence);
+        }
+        if has_t && has_u {
+            return Err(OligoPropertiesError::MixedBackbone);
+        }
+
+        let (molecule, nearest_neighbor, single, residue_mass) = if has_u {
+            (
+                NucleicAcid::Rna,
+                &RNA_NEAREST_NEIGHBOR,
+                &RNA_SINGLE,
+                &RNA_RESIDUE_MASS,
+            )
+        } else {
+            (
+                NucleicAcid::Dna,
+                &DNA_NEAREST_NEIGHBOR,
+                &DNA_SINGLE,
+                &DNA_RESIDUE_MASS,
+            )
+        };
+
+        let extinction = if indices.len() == 1 {
+            single[indices[0]]
+        } else {
+            let pairs: f64 = indices
+                .windows(2)
+                .map(|pair| nearest_neighbor[pair[0]][pair[1]])
+                .sum();
+            let internal: f64 = indices[1..indices.len() - 1]
+                .iter()
+                .map(|&base| single[base])
+                .sum();
+            pairs - internal
+        };
+        let extinction_coefficient = (extinction * 1000.0).round();
+        let molecular_weight =
+            indices.iter().map(|&base| residue_mass[base]).sum::<f64>() + TERMINAL_CORRECTION;
+        let nmol_per_od = 1e6 / extinction_coefficient;
+
+        Ok(OligoProperties {
+            molecule,
+            length: indices.len(),
+            extinction_coefficient,
+            molecular_weight,
+            nmol_per_od,
+            ug_per_od: nmol_per_od * molecular_weight / 1000.0,
+        })
+    }
+
+    /// `amount`（単位 `unit`）を OD260・µg・pmol に換算する
+    pub fn convert(
+        &self,
+        properties: &OligoProperties,
+        amount: f64,
+        unit: OligoAmountUnit,
+    ) -> OligoAmount {
+        let od260 = match unit {
+            OligoAmountUnit::Od260 => amount,
+            OligoAmountUnit::Micrograms => amount / properties.ug_per_od,
+            OligoAmountUnit::Picomoles => amount / 1000.0 / properties.nmol_per_od,
+        };
+        OligoAmount {
+            od260,
+            micrograms: od260 * properties.ug_per_od,
+            picomoles: od260 * properties.nmol_per_od * 1000.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_neighbor_extinction_and_molecular_weight() {
+        let service = OligoPropertiesService::new();
+
+        // AT + TG + GC − (T + G) = 22.8 + 19.0 + 17.6 − 20.2
+        let dna = service.properties("atgc").unwrap();
+        assert_eq!(dna.molecule, NucleicAcid::Dna);
+        assert_eq!(dna.extinction_coefficient, 39_200.0);
+        assert!((dna.molecular_weight - 1173.84).abs() < 1e-6);
+        assert!((dna.ug_per_od - 29.945).abs() < 1e-3);
+
+        let rna = service.properties("AUGC").unwrap();
+        assert_eq!(rna.molecule, NucleicAcid::Rna);
+        assert_eq!(rna.extinction_coefficient, 40_000.0);
+        assert_eq!(
+            service.properties("A").unwrap().extinction_coefficient,
+            15_400.0
+        );
+
+        assert!(service.properties("ATGU").is_err());
+        assert!(service.properties("ATGN").is_err());
+    }
+
+    #[test]
+    fn test_convert_between_od_micrograms_and_picomoles() {
+        let service = OligoPropertiesService::new();
+        let properties = service.properties("ATGC").unwrap();
+
+        let amount = service.convert(&properties, 2.0, OligoAmountUnit::Od260);
+        let from_pmol = service.convert(&properties, amount.picomoles, OligoAmountUnit::Picomoles);
+        let from_ug = service.convert(&properties, amount.micrograms, OligoAmountUnit::Micrograms);
+        assert!((amount.picomoles - 2.0e6 / 39.2).abs() < 1e-6);
+        assert!((from_pmol.od260 - 2.0).abs() < 1e-9);
+        assert!((from_ug.picomoles - amount.picomoles).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,182 @@
+// Service layer: Open reading frame detection
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::iupac;
+use crate::domain::orf::{FrameTranslation, InternalStop, Orf};
+use crate::domain::Strand;
+
+/// ORF finder service
+pub struct OrfService {
+    genetic_code: GeneticCode,
+}
+
+impl Default for OrfService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrfService {
+    pub fn new() -> Self {
+        Self::with_genetic_code(GeneticCode::standard())
+    }
+
+    pub fn with_genetic_code(genetic_code: GeneticCode) -> Self {
+        Self { genetic_code }
+    }
+
+    /// 6フレームを走査し、開始コドンから終止コドンまでのORFを検出
+    /// （`min_length_aa` 未満のもの、終止コドンのないものは除外）
+    pub fn find_orfs(&self, sequence: &str, min_length_aa: usize) -> Vec<Orf> {
+        let length = sequence.len();
+        let forward = sequence.to_ascii_uppercase();
+        let reverse = iupac::reverse_complement(&forward);
+
+        let mut orfs = Vec::new();
+        for (strand, strand_seq) in [(Strand::Forward, &forward), (Strand::Reverse, &reverse)] {
+            let bytes = strand_seq.as_bytes();
+            for offset in 0..3 {
+                let mut open: Option<usize> = None;
+                let mut position = offset;
+                while position + 3 <= length {
+                    let codon = &bytes[position..position + 3];
+                    if open.is_none() && self.genetic_code.is_start(codon) {
+                        open = Some(position);
+                    } else if self.genetic_code.is_stop(codon) {
+                        if let Some(orf_start) = open.take() {
+                            // 開始コドンは代替開始コドン（GTG など）でも Met として翻訳される
+                            let mut protein = self
+                                .genetic_code
+                                .translate(&strand_seq[orf_start..position]);
+                            protein.replace_range(..1, "M");
+                            if protein.len() >= min_length_aa {
+                                let orf_end = position + 3;
+                                let (start, end) = match strand {
+                                    Strand::Forward => (orf_start, orf_end),
+                                    Strand::Reverse => (length - orf_end, length - orf_start),
+                                };
+                                let frame = (offset + 1) as i8;
+                                orfs.push(Orf {
+                                    start,
+                                    end,
+                                    strand,
+                                    frame: if strand == Strand::Forward {
+                                        frame
+                                    } else {
+                                        -frame
+                                    },
+                                    protein,
+                                });
+                            }
+                        }
+                    }
+                    position += 3;
+                }
+            }
+        }
+
+        orfs.sort_by_key(|orf| (orf.start, orf.end));
+        orfs
+    }
+
+    /// 読み枠 `frame`（+1〜+3 は順鎖、-1〜-3 は逆相補鎖の先頭からのずれ）で
+    /// 配列全体を翻訳し、末尾以外の終止コドンの位置を返す
+    pub fn translate_frame(&self, sequence: &str, frame: i8) -> FrameTranslation {
+        let length = sequence.len();
+        let offset = (frame.unsigned_abs() as usize)
+            .saturating_sub(1)
+            .min(length);
+        let strand = if frame < 0 {
+            Strand::Reverse
+        } else {
+            Strand::Forward
+        };
+        let forward = sequence.to_ascii_uppercase();
+        let strand_seq = match strand {
+            Strand::Forward => forward,
+            Strand::Reverse => iupac::reverse_complement(&forward),
+        };
+
+        let protein = self.genetic_code.translate(&strand_seq[offset..]);
+        let translated = protein.len() * 3;
+        let (start, end) = match strand {
+            Strand::Forward => (offset, offset + translated),
+            Strand::Reverse => (length - offset - translated, length - offset),
+        };
+        let internal_stops = protein
+            .match_indices('*')
+            .map(|(residue, _)| residue)
+            .filter(|&residue| residue + 1 < protein.len())
+            .map(|residue| {
+                let (codon_start, codon_end) = match strand {
+                    Strand::Forward => (start + residue * 3, start + residue * 3 + 3),
+                    Strand::Reverse => (end - residue * 3 - 3, end - residue * 3),
+                };
+                InternalStop {
+                    residue,
+                    start: codon_start,
+                    end: codon_end,
+                }
+            })
+            .collect();
+
+        FrameTranslation {
+            frame,
+            strand,
+            genetic_code: self.genetic_code.id,
+            start,
+            end,
+            protein,
+            internal_stops,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_orfs_both_strands() {
+        // 順鎖に ATG AAA TGA、逆鎖に ATG AAA TAA
+        let sequence = "CCATGAAATGACCTTATTTCATCC";
+        let orfs = OrfService::new().find_orfs(sequence, 2);
+
+        assert_eq!(orfs.len(), 2);
+        assert_eq!((orfs[0].start, orfs[0].end, orfs[0].frame), (2, 11, 3));
+        assert_eq!(orfs[0].protein, "MK");
+        assert_eq!(orfs[1].strand, Strand::Reverse);
+        assert_eq!((orfs[1].start, orfs[1].end), (13, 22));
+        assert_eq!(orfs[1].frame, -3);
+        assert_eq!(orfs[1].dna_range(1, 2), (16, 19));
+    }
+    #[test]
+    fn test_translate_frame_reports_internal_stops() {
+        // +1: ATG TAA AAA TGA C
+        let sequence = "ATGTAAAAATGAC";
+        let service = OrfService::new();
+
+        let forward = service.translate_frame(sequence, 1);
+        assert_eq!(forward.protein, "M*K*");
+        assert_eq!((forward.start, forward.end), (0, 12));
+        assert_eq!(
+            forward.internal_stops,
+            vec![InternalStop {
+                residue: 1,
+                start: 3,
+                end: 6
+            }]
+        );
+
+        // -2: 逆相補 GTCATTTTTACAT の2塩基目から TCA TTT TTA CAT
+        let reverse = service.translate_frame(sequence, -2);
+        assert_eq!(reverse.protein, "SFLH");
+        assert_eq!((reverse.start, reverse.end), (0, 12));
+        assert!(reverse.internal_stops.is_empty());
+
+        // 繊毛虫の暗号では TAA は Gln
+        let ciliate = OrfService::with_genetic_code(GeneticCode::by_id(6).unwrap())
+            .translate_frame(sequence, 1);
+        assert_eq!(ciliate.protein, "MQK*");
+        assert!(ciliate.internal_stops.is_empty());
+    }
+}
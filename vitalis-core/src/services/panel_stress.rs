@@ -0,0 +1,217 @@
+// Service layer: Primer panel robustness against template diversity
+use crate::domain::iupac;
+use crate::domain::primer::{PairStressResult, PanelStressParams, PanelStressReport, PrimerPair};
+
+/// Primer panel stress test service
+pub struct PanelStressService;
+
+impl Default for PanelStressService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PanelStressService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// For each pair, count the `templates` (seq ID, sequence) it still
+    /// amplifies and flag pairs below the fragility threshold.
+    pub fn evaluate(
+        &self,
+        pairs: &[PrimerPair],
+        templates: &[(String, String)],
+        params: &PanelStressParams,
+    ) -> PanelStressReport {
+        let results: Vec<PairStressResult> = pairs
+            .iter()
+            .map(|pair| {
+                let failed_templates: Vec<String> = templates
+                    .iter()
+                    .filter(|(_, sequence)| !self.amplifies(pair, sequence, params))
+                    .map(|(seq_id, _)| seq_id.clone())
+                    .collect();
+                let templates_amplified = templates.len() - failed_templates.len();
+                let amplified_fraction = if templates.is_empty() {
+                    1.0
+                } else {
+                    templates_amplified as f64 / templates.len() as f64
+                };
+
+                PairStressResult {
+                    pair_id: pair.id.clone(),
+                    templates_tested: templates.len(),
+                    templates_amplified,
+                    amplified_fraction,
+                    fragile: amplified_fraction < params.fragile_threshold,
+                    failed_templates,
+                }
+            })
+            .collect();
+
+        let mut fragile: Vec<&PairStressResult> = results.iter().filter(|r| r.fragile).collect();
+        fragile.sort_by(|a, b| {
+            a.amplified_fraction
+                .partial_cmp(&b.amplified_fraction)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        PanelStressReport {
+            templates: templates.iter().map(|(seq_id, _)| seq_id.clone()).collect(),
+            fragile_pairs: fragile.iter().map(|r| r.pair_id.clone()).collect(),
+            pairs: results,
+            params: params.clone(),
+        }
+    }
+
+    /// 両プライマーが結合し、許容長の産物ができるか
+    pub fn amplifies(&self, pair: &PrimerPair, template: &str, params: &PanelStressParams) -> bool {
+        let template = template.to_ascii_uppercase();
+        let forward = pair.forward.sequence.to_ascii_uppercase();
+        let reverse = iupac::reverse_complement(&pair.reverse.sequence.to_ascii_uppercase());
+
+        // 順方向プライマーの3'末端は右端、逆方向プライマー（相補鎖上）の3'末端は左端
+        let forward_sites = binding_sites(&forward, &template, params, false);
+        let reverse_sites = binding_sites(&reverse, &template, params, true);
+        let max_product = (pair.amplicon_length as f64 * params.max_product_ratio) as usize;
+
+        forward_sites.iter().any(|&f| {
+            reverse_sites.iter().any(|&r| {
+                let end = r + reverse.len();
+                r >= f && end - f <= max_product.max(forward.len() + reverse.len())
+            })
+        })
+    }
+}
+
+/// ミスマッチ許容でプライマーが結合する開始位置
+fn binding_sites(
+    primer: &str,
+    template: &str,
+    params: &PanelStressParams,
+    three_prime_left: bool,
+) -> Vec<usize> {
+    let primer = primer.as_bytes();
+    let template = template.as_bytes();
+    if primer.is_empty() || primer.len() > template.len() {
+        return Vec::new();
+    }
+
+    let window = params.three_prime_window.min(primer.len());
+    let in_window = |i: usize| {
+        if three_prime_left {
+            i < window
+        } else {
+            i >= primer.len() - window
+        }
+    };
+
+    (0..=template.len() - primer.len())
+        .filter(|&start| {
+            let mut mismatches = 0;
+            let mut three_prime = 0;
+            for (i, &base) in primer.iter().enumerate() {
+                if !iupac::matches(base as char, template[start + i] as char) {
+                    mismatches += 1;
+                    if in_window(i) {
+                        three_prime += 1;
+                    }
+                    if mismatches > params.max_mismatches
+                        || three_prime > params.max_three_prime_mismatches
+                    {
+                        return false;
+                    }
+                }
+            }
+            true
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::primer::{Primer, PrimerDirection, ValidationResults};
+    use chrono::Utc;
+
+    const TEMPLATE: &str =
+        "TTGACAGCTAGCTCAGTCCTAGGTATAATGCTAGCGGATCCAAGGAGGTAAAGCTTATGCGTAAAGGCGAAGAGCTGTTCACTGG";
+
+    fn primer(sequence: &str, position: usize, direction: PrimerDirection) -> Primer {
+        Primer {
+            sequence: sequence.to_string(),
+            position,
+            length: sequence.len(),
+            tm: 60.0,
+            gc_content: 50.0,
+            self_dimer_score: 0.0,
+            hairpin_score: 0.0,
+            three_prime_stability: 0.0,
+            direction,
+            quality_score: 100.0,
+            quality_warnings: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    fn pair() -> PrimerPair {
+        let forward = &TEMPLATE[0..20];
+        let reverse = iupac::reverse_complement(&TEMPLATE[60..80]);
+        PrimerPair {
+            id: "pair_1".to_string(),
+            forward: primer(forward, 0, PrimerDirection::Forward),
+            reverse: primer(&reverse, 60, PrimerDirection::Reverse),
+            amplicon_length: 80,
+            amplicon_sequence: TEMPLATE[..80].to_string(),
+            target_gene: None,
+            target_transcript: None,
+            compatibility_score: 0.0,
+            created_by: "test".to_string(),
+            created_at: Utc::now(),
+            tags: Vec::new(),
+            validation_results: ValidationResults::new(),
+        }
+    }
+
+    fn mutate(position: usize) -> String {
+        let mut bytes = TEMPLATE.as_bytes().to_vec();
+        bytes[position] = if bytes[position] == b'A' { b'C' } else { b'A' };
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_three_prime_mismatches_break_binding() {
+        let service = PanelStressService::new();
+        let params = PanelStressParams::default();
+        let pair = pair();
+
+        assert!(service.amplifies(&pair, TEMPLATE, &params));
+        // 5'側のミスマッチは許容
+        assert!(service.amplifies(&pair, &mutate(2), &params));
+        // 順方向プライマーの3'末端
+        assert!(!service.amplifies(&pair, &mutate(18), &params));
+        // 逆方向プライマーの3'末端（テンプレート上では結合部位の左端）
+        assert!(!service.amplifies(&pair, &mutate(61), &params));
+    }
+
+    #[test]
+    fn test_report_flags_fragile_pairs() {
+        let templates = vec![
+            ("seq_1".to_string(), TEMPLATE.to_string()),
+            ("seq_2".to_string(), mutate(5)),
+            ("seq_3".to_string(), mutate(19)),
+        ];
+        let report = PanelStressService::new().evaluate(
+            &[pair()],
+            &templates,
+            &PanelStressParams::default(),
+        );
+
+        let result = &report.pairs[0];
+        assert_eq!(result.templates_amplified, 2);
+        assert_eq!(result.failed_templates, vec!["seq_3".to_string()]);
+        assert!(result.fragile);
+        assert_eq!(report.fragile_pairs, vec!["pair_1".to_string()]);
+    }
+}
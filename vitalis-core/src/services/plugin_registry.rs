@@ -0,0 +1,159 @@
+// Service layer: Registry of optional sequence analysis plugins
+use crate::domain::analysis_result::AnalysisResult;
+use crate::domain::plugin::{PluginError, PluginInfo, SequenceAnalysisPlugin};
+
+/// Analysis plugin registry
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Box<dyn SequenceAnalysisPlugin>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 有効な cargo feature の組み込みプラグインを登録したレジストリ
+    pub fn with_builtin_plugins() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+        #[cfg(feature = "gc-skew-plugin")]
+        registry
+            .register(Box::new(crate::services::gc_skew_plugin::GcSkewPlugin))
+            .expect("built-in plugin names are unique");
+        registry
+    }
+
+    /// 同じ名前のプラグインは登録できない
+    pub fn register(&mut self, plugin: Box<dyn SequenceAnalysisPlugin>) -> Result<(), PluginError> {
+        if self.get(plugin.name()).is_some() {
+            return Err(PluginError::AlreadyRegistered(plugin.name().to_string()));
+        }
+        self.plugins.push(plugin);
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, name: &str) -> Result<(), PluginError> {
+        let index = self
+            .plugins
+            .iter()
+            .position(|plugin| plugin.name() == name)
+            .ok_or_else(|| PluginError::NotFound(name.to_string()))?;
+        self.plugins.remove(index);
+        Ok(())
+    }
+
+    /// 登録順の一覧
+    pub fn list(&self) -> Vec<PluginInfo> {
+        self.plugins
+            .iter()
+            .map(|plugin| PluginInfo::of(plugin.as_ref()))
+            .collect()
+    }
+
+    /// `params` が null のときは空のオブジェクトを渡す（プラグインの既定値を使う）
+    pub fn run(
+        &self,
+        name: &str,
+        seq_id: &str,
+        sequence: &str,
+        params: &serde_json::Value,
+    ) -> Result<AnalysisResult<serde_json::Value>, PluginError> {
+        let plugin = self
+            .get(name)
+            .ok_or_else(|| PluginError::NotFound(name.to_string()))?;
+        let empty = serde_json::json!({});
+        let params = if params.is_null() { &empty } else { params };
+        plugin.run(seq_id, sequence, params)
+    }
+
+    fn get(&self, name: &str) -> Option<&dyn SequenceAnalysisPlugin> {
+        self.plugins
+            .iter()
+            .find(|plugin| plugin.name() == name)
+            .map(|plugin| plugin.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 指定した塩基の数を数えるだけのプラグイン
+    struct BaseCountPlugin;
+
+    impl SequenceAnalysisPlugin for BaseCountPlugin {
+        fn name(&self) -> &str {
+            "base_count"
+        }
+
+        fn description(&self) -> &str {
+            "Count one base"
+        }
+
+        fn parameter_schema(&self) -> serde_json::Value {
+            json!({ "type": "object", "properties": { "base": { "type": "string" } } })
+        }
+
+        fn run(
+            &self,
+            seq_id: &str,
+            sequence: &str,
+            params: &serde_json::Value,
+        ) -> Result<AnalysisResult<serde_json::Value>, PluginError> {
+            let base = params.get("base").and_then(|b| b.as_str()).unwrap_or("G");
+            let base = match base.as_bytes() {
+                [b] => b.to_ascii_uppercase(),
+                _ => {
+                    return Err(PluginError::InvalidParameters(
+                        self.name().to_string(),
+                        "base must be one letter".to_string(),
+                    ))
+                }
+            };
+            let count = sequence
+                .bytes()
+                .filter(|b| b.to_ascii_uppercase() == base)
+                .count();
+            Ok(AnalysisResult::new(
+                self.name(),
+                seq_id,
+                sequence,
+                params,
+                json!(count),
+            ))
+        }
+    }
+
+    #[test]
+    fn test_register_and_run_plugin() {
+        let mut registry = PluginRegistry::new();
+        registry.register(Box::new(BaseCountPlugin)).unwrap();
+        assert!(matches!(
+            registry.register(Box::new(BaseCountPlugin)),
+            Err(PluginError::AlreadyRegistered(_))
+        ));
+        assert_eq!(registry.list()[0].name, "base_count");
+
+        let result = registry
+            .run("base_count", "seq_1", "ggATG", &serde_json::Value::Null)
+            .unwrap();
+        assert_eq!(result.result, json!(3));
+        assert_eq!(result.parameters, json!({}));
+        let a = registry
+            .run("base_count", "seq_1", "ggATG", &json!({ "base": "a" }))
+            .unwrap();
+        assert_eq!(a.result, json!(1));
+        assert!(matches!(
+            registry.run("base_count", "seq_1", "ACGT", &json!({ "base": "AC" })),
+            Err(PluginError::InvalidParameters(..))
+        ));
+
+        registry.unregister("base_count").unwrap();
+        assert!(matches!(
+            registry.run("base_count", "seq_1", "ACGT", &json!({})),
+            Err(PluginError::NotFound(_))
+        ));
+    }
+}
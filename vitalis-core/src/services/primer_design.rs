@@ -1,7 +1,9 @@
 use crate::domain::primer::*;
+use crate::domain::reproducibility;
+use crate::domain::warning::{Warning, WarningCategory, WarningCode};
 use chrono::Utc;
 use std::collections::HashMap;
-use uuid::Uuid;
+use std::time::{Duration, Instant};
 
 /// DNA塩基の相補性をチェック
 fn is_complement(base1: char, base2: char) -> bool {
@@ -11,6 +13,132 @@ fn is_complement(base1: char, base2: char) -> bool {
     }
 }
 
+/// 品質スコアで推奨する Tm（°C）・GC含量（%）・長さ（bp）の範囲
+const RECOMMENDED_TM: (f32, f32) = (55.0, 65.0);
+const RECOMMENDED_GC: (f32, f32) = (40.0, 60.0);
+const RECOMMENDED_LENGTH: (usize, usize) = (18, 25);
+
+/// ペアの Tm 差の上限（°C）と増幅産物長の範囲（bp）
+const MAX_PAIR_TM_DIFFERENCE: f32 = 3.0;
+pub const AMPLICON_LENGTH: (usize, usize) = (100, 3000);
+
+/// 診断に残す惜しい候補の数（条件ごと）
+const NEAR_MISSES_PER_REASON: usize = 3;
+
+/// 逐次設計で途中経過を通知する目安（見つかったペア数と経過時間のどちらか早い方）
+const PROGRESS_BATCH_PAIRS: usize = 20;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 条件緩和で試す刻みと上限（片側あたり、parameter の単位）
+const RELAXATION_STEPS: [(RelaxedParameter, f32, f32); 5] = [
+    (RelaxedParameter::TmRange, 0.5, 5.0),
+    (RelaxedParameter::GcRange, 2.5, 15.0),
+    (RelaxedParameter::LengthRange, 1.0, 3.0),
+    (RelaxedParameter::HeteroDimer, 1.0, 5.0),
+    (RelaxedParameter::SearchFlank, 25.0, 200.0),
+];
+
+/// `parameter` を `change` だけ緩めた設計条件
+fn relaxed_params(
+    params: &PrimerDesignParams,
+    parameter: RelaxedParameter,
+    change: f32,
+) -> PrimerDesignParams {
+    let mut relaxed = params.clone();
+    match parameter {
+        RelaxedParameter::TmRange => {
+            relaxed.tm_min = (params.tm_min - change).max(0.0);
+            relaxed.tm_max = (params.tm_max + change).min(100.0);
+        }
+        RelaxedParameter::GcRange => {
+            relaxed.gc_min = (params.gc_min - change).max(0.0);
+            relaxed.gc_max = (params.gc_max + change).min(100.0);
+        }
+        RelaxedParameter::LengthRange => {
+            relaxed.length_min = params.length_min.saturating_sub(change as usize).max(1);
+            relaxed.length_max = params.length_max + change as usize;
+        }
+        RelaxedParameter::HeteroDimer => {
+            relaxed.max_hetero_dimer = (params.max_hetero_dimer - change).max(-100.0);
+        }
+        RelaxedParameter::SearchFlank => {
+            relaxed.search_flank = params.search_flank + change as usize
+        }
+    }
+    relaxed
+}
+
+/// 診断から見た `parameter` の除外数と、緩める量の下限（これ未満では除外された候補が
+/// 1つも通らない）。関係する除外がなければ None（検索範囲の拡大は常に試す）
+fn relaxation_hint(
+    diagnostics: Option<&DesignDiagnostics>,
+    parameter: RelaxedParameter,
+) -> Option<(usize, f32)> {
+    let Some(diagnostics) = diagnostics else {
+        return Some((0, 0.0));
+    };
+    let rejected = &diagnostics.rejected;
+    let (count, reason) = match parameter {
+        RelaxedParameter::TmRange => (rejected.tm, Some(RejectionReason::Tm)),
+        RelaxedParameter::GcRange => (rejected.gc, Some(RejectionReason::Gc)),
+        RelaxedParameter::LengthRange => (rejected.tm + rejected.gc, None),
+        RelaxedParameter::HeteroDimer => {
+            (rejected.hetero_dimer, Some(RejectionReason::HeteroDimer))
+        }
+        RelaxedParameter::SearchFlank => return Some((0, 0.0)),
+    };
+    let closest = diagnostics
+        .near_misses
+        .iter()
+        .filter(|miss| Some(miss.reason) == reason)
+        .map(|miss| miss.excess)
+        .fold(f32::INFINITY, f32::min);
+    (count > 0).then_some((count, if closest.is_finite() { closest } else { 0.0 }))
+}
+
+/// 値が [min, max] の外にあれば、はみ出した量
+fn outside(value: f32, min: f32, max: f32) -> Option<f32> {
+    if value < min {
+        Some(min - value)
+    } else if value > max {
+        Some(value - max)
+    } else {
+        None
+    }
+}
+
+/// `reason` の惜しい候補として残す余地があるか
+fn near_miss_wanted(diagnostics: &DesignDiagnostics, reason: RejectionReason, excess: f32) -> bool {
+    let kept: Vec<f32> = diagnostics
+        .near_misses
+        .iter()
+        .filter(|miss| miss.reason == reason)
+        .map(|miss| miss.excess)
+        .collect();
+    kept.len() < NEAR_MISSES_PER_REASON || kept.iter().any(|&worst| excess < worst)
+}
+
+/// 惜しい候補を加え、条件ごとに超過量の小さい `NEAR_MISSES_PER_REASON` 件に絞る
+fn keep_near_miss(diagnostics: &mut DesignDiagnostics, near_miss: NearMiss) {
+    let reason = near_miss.reason;
+    diagnostics.near_misses.push(near_miss);
+    diagnostics.near_misses.sort_by(|a, b| {
+        a.reason.cmp(&b.reason).then(
+            a.excess
+                .partial_cmp(&b.excess)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
+    });
+    let mut seen = 0;
+    diagnostics.near_misses.retain(|miss| {
+        if miss.reason != reason {
+            return true;
+        }
+        seen += 1;
+        seen <= NEAR_MISSES_PER_REASON
+    });
+}
+
 pub struct PrimerDesignServiceImpl {
     /// NNDB 2024対応熱力学計算エンジン
     thermodynamic_calculator: crate::domain::thermodynamic_calculator::ThermodynamicCalculator,
@@ -49,8 +177,13 @@ impl PrimerDesignServiceImpl {
 }
 
 impl PrimerDesignServiceImpl {
+    /// Tm・ΔG計算に用いるパラメータセットと条件
+    pub fn provenance(&self) -> crate::domain::thermodynamic_calculator::ThermodynamicProvenance {
+        self.thermodynamic_calculator.provenance()
+    }
+
     /// DNA配列を逆相補配列に変換
-    fn reverse_complement(&self, sequence: &str) -> String {
+    pub fn reverse_complement(&self, sequence: &str) -> String {
         sequence
             .chars()
             .rev()
@@ -64,7 +197,8 @@ impl PrimerDesignServiceImpl {
             .collect()
     }
 
-    /// 配列から候補プライマーを生成
+    /// 配列から候補プライマーを生成（除外した候補は `diagnostics` に数える）
+    #[allow(clippy::too_many_arguments)]
     fn generate_primer_candidates(
         &self,
         sequence: &str,
@@ -72,29 +206,47 @@ impl PrimerDesignServiceImpl {
         end: usize,
         params: &PrimerDesignParams,
         direction: PrimerDirection,
+        weights: Option<&[f64]>,
+        diagnostics: &mut DesignDiagnostics,
     ) -> Vec<Primer> {
         let mut primers = Vec::new();
-        let _target_seq = &sequence[start..=end];
+        let _target_seq = &sequence[start..end];
 
         for length in params.length_min..=params.length_max {
             // Forward primers: target regionの開始付近
             // Reverse primers: target regionの終了付近
-            let positions = if direction == PrimerDirection::Forward {
-                // Forward primer positions around start
-                let range_start = start.saturating_sub(50);
-                let range_end = (start + 50).min(sequence.len().saturating_sub(length));
-                range_start..=range_end
-            } else {
-                // Reverse primer positions around end
-                let range_start = end.saturating_sub(50);
-                let range_end = (end + 50).min(sequence.len().saturating_sub(length));
-                range_start..=range_end
+            // search_anywhere では領域を含み増幅産物長の上限に収まる範囲のどこからでも探す
+            let flank = params.search_flank;
+            let last = sequence.len().saturating_sub(length);
+            let positions = match (params.search_anywhere, &direction) {
+                (false, PrimerDirection::Forward) => {
+                    // Forward primer positions around start
+                    start.saturating_sub(flank)..=(start + flank).min(last)
+                }
+                (false, PrimerDirection::Reverse) => {
+                    // Reverse primer positions around end
+                    end.saturating_sub(flank)..=(end + flank).min(last)
+                }
+                (true, PrimerDirection::Forward) => {
+                    end.saturating_sub(AMPLICON_LENGTH.1)..=start.min(last)
+                }
+                (true, PrimerDirection::Reverse) => {
+                    end.saturating_sub(length)
+                        ..=(start + AMPLICON_LENGTH.1).saturating_sub(length).min(last)
+                }
             };
 
             for pos in positions {
                 if pos + length > sequence.len() {
                     continue;
                 }
+                diagnostics.candidates_evaluated += 1;
+                if let Some(mask) = &params.quality_mask {
+                    if mask.masks(pos, pos + length) {
+                        diagnostics.rejected.add(RejectionReason::QualityMask);
+                        continue;
+                    }
+                }
 
                 let primer_seq = if direction == PrimerDirection::Forward {
                     sequence[pos..pos + length].to_string()
@@ -106,53 +258,45 @@ impl PrimerDesignServiceImpl {
                 let gc = self.calculate_gc_content(&primer_seq);
 
                 // 基本フィルタリング
-                if tm >= params.tm_min
-                    && tm <= params.tm_max
-                    && gc >= params.gc_min
-                    && gc <= params.gc_max
-                {
-                    let self_dimer = self.calculate_self_dimer(&primer_seq);
-                    let hairpin = self.calculate_hairpin(&primer_seq);
-
-                    let mut stability_warnings = Vec::new();
-                    let three_prime =
-                        self.enhanced_three_prime_stability(&primer_seq, &mut stability_warnings);
-
-                    // 包括的な品質評価システムを適用
-                    let mut quality_warnings = stability_warnings;
-
-                    // 一時的なPrimerインスタンスを作成して品質評価
-                    let temp_primer = Primer {
-                        sequence: primer_seq.clone(),
-                        position: pos,
-                        length,
-                        tm,
-                        gc_content: gc,
-                        self_dimer_score: self_dimer,
-                        hairpin_score: hairpin,
-                        three_prime_stability: three_prime,
-                        direction: direction.clone(),
-                        quality_score: 0.0,           // 仮の値
-                        quality_warnings: Vec::new(), // 仮の値
-                    };
-
-                    let quality_score =
-                        self.calculate_primer_quality_score(&temp_primer, &mut quality_warnings);
-
-                    primers.push(Primer {
-                        sequence: primer_seq,
-                        position: pos,
-                        length,
-                        tm,
-                        gc_content: gc,
-                        self_dimer_score: self_dimer,
-                        hairpin_score: hairpin,
-                        three_prime_stability: three_prime,
-                        direction: direction.clone(),
-                        quality_score,
-                        quality_warnings,
+                let rejection = outside(tm, params.tm_min, params.tm_max)
+                    .map(|excess| (RejectionReason::Tm, excess))
+                    .or_else(|| {
+                        outside(gc, params.gc_min, params.gc_max)
+                            .map(|excess| (RejectionReason::Gc, excess))
                     });
+                if let Some((reason, excess)) = rejection {
+                    diagnostics.rejected.add(reason);
+                    if near_miss_wanted(diagnostics, reason, excess) {
+                        let primer = self.evaluate_primer(primer_seq, pos, direction.clone());
+                        keep_near_miss(
+                            diagnostics,
+                            NearMiss {
+                                reason,
+                                excess,
+                                primers: vec![primer],
+                            },
+                        );
+                    }
+                    continue;
                 }
+
+                let mut primer = self.evaluate_primer(primer_seq, pos, direction.clone());
+                if let (Some(weighting), Some(weights)) = (&params.weighting, weights) {
+                    if !self.apply_position_weighting(&mut primer, weighting, weights) {
+                        diagnostics.rejected.add(RejectionReason::PositionWeighting);
+                        continue;
+                    }
+                }
+                let flagged = &mut diagnostics.flagged;
+                flagged.self_dimer += usize::from(primer.self_dimer_score < params.max_self_dimer);
+                flagged.hairpin += usize::from(primer.hairpin_score < params.max_hairpin);
+                flagged.three_prime += usize::from(
+                    primer
+                        .quality_warnings
+                        .iter()
+                        .any(|warning| warning.category == WarningCategory::ThreePrimeEnd),
+                );
+                primers.push(primer);
             }
         }
 
@@ -180,6 +324,110 @@ impl PrimerDesignServiceImpl {
         primers
     }
 
+    /// プライマー配列を評価して品質スコア付きのPrimerを構築
+    /// （Tm/GCによるフィルタリングは呼び出し側で行う）
+    pub fn evaluate_primer(
+        &self,
+        primer_seq: String,
+        position: usize,
+        direction: PrimerDirection,
+    ) -> Primer {
+        let length = primer_seq.len();
+        let tm = self.calculate_tm(&primer_seq);
+        let gc = self.calculate_gc_content(&primer_seq);
+        let self_dimer = self.calculate_self_dimer(&primer_seq);
+        let hairpin = self.calculate_hairpin(&primer_seq);
+
+        let mut stability_warnings = Vec::new();
+        let three_prime = self.enhanced_three_prime_stability(&primer_seq, &mut stability_warnings);
+
+        // 包括的な品質評価システムを適用
+        let mut quality_warnings = stability_warnings;
+
+        // 一時的なPrimerインスタンスを作成して品質評価
+        let temp_primer = Primer {
+            sequence: primer_seq.clone(),
+            position,
+            length,
+            tm,
+            gc_content: gc,
+            self_dimer_score: self_dimer,
+            hairpin_score: hairpin,
+            three_prime_stability: three_prime,
+            direction: direction.clone(),
+            quality_score: 0.0,           // 仮の値
+            quality_warnings: Vec::new(), // 仮の値
+            provenance: None,
+        };
+
+        let quality_score =
+            self.calculate_primer_quality_score(&temp_primer, &mut quality_warnings);
+
+        Primer {
+            sequence: primer_seq,
+            position,
+            length,
+            tm,
+            gc_content: gc,
+            self_dimer_score: self_dimer,
+            hairpin_score: hairpin,
+            three_prime_stability: three_prime,
+            direction,
+            quality_score,
+            quality_warnings,
+            provenance: Some(self.provenance()),
+        }
+    }
+
+    /// 低重みの塩基を含む候補を減点する。除外すべき候補なら false
+    fn apply_position_weighting(
+        &self,
+        primer: &mut Primer,
+        weighting: &PositionWeighting,
+        weights: &[f64],
+    ) -> bool {
+        let mut low = 0;
+        let mut penalty = 0.0f32;
+        for offset in 0..primer.length {
+            let Some(&weight) = weights.get(primer.position + offset) else {
+                continue;
+            };
+            let weight = if weighting.invert {
+                1.0 - weight
+            } else {
+                weight
+            };
+            if weight >= weighting.min_weight {
+                continue;
+            }
+            low += 1;
+            // Reverse プライマーの3'末端は鋳型上の左端
+            let from_three_prime = match primer.direction {
+                PrimerDirection::Forward => primer.length - 1 - offset,
+                PrimerDirection::Reverse => offset,
+            };
+            penalty += if from_three_prime < weighting.three_prime_window {
+                2.0 * weighting.penalty_per_base
+            } else {
+                weighting.penalty_per_base
+            };
+        }
+
+        if low > 0 {
+            primer.quality_score = (primer.quality_score - penalty).max(0.0);
+            primer.quality_warnings.push(
+                WarningCode::LowWeightBases {
+                    count: low,
+                    min_weight: weighting.min_weight,
+                }
+                .into(),
+            );
+        }
+        weighting
+            .max_low_weight_bases
+            .is_none_or(|max_low| low <= max_low)
+    }
+
     /// 3'末端の安定性を計算
     fn calculate_three_prime_stability(&self, sequence: &str) -> f32 {
         if sequence.len() < 5 {
@@ -190,21 +438,24 @@ impl PrimerDesignServiceImpl {
         self.calculate_tm(three_prime)
     }
 
-    /// プライマーペアの適合性をチェック
-    fn is_compatible_pair(
+    /// プライマーペアの適合性をチェック（不適合なら条件と超過量）
+    fn check_pair_compatibility(
         &self,
         forward: &Primer,
         reverse: &Primer,
         params: &PrimerDesignParams,
-    ) -> bool {
+    ) -> Result<(), (RejectionReason, f32)> {
         // Tm差が大きすぎる場合は不適合
         let tm_diff = (forward.tm - reverse.tm).abs();
-        if tm_diff > 3.0 {
+        if tm_diff > MAX_PAIR_TM_DIFFERENCE {
             println!(
                 "DEBUG: Pair rejected for Tm diff: {:.2} (forward: {:.2}°C, reverse: {:.2}°C)",
                 tm_diff, forward.tm, reverse.tm
             );
-            return false;
+            return Err((
+                RejectionReason::TmDifference,
+                tm_diff - MAX_PAIR_TM_DIFFERENCE,
+            ));
         }
 
         // プライマー間の相互作用をチェック
@@ -214,27 +465,42 @@ impl PrimerDesignServiceImpl {
                 "DEBUG: Pair rejected for hetero-dimer: {:.2} < {:.2} (forward: {}, reverse: {})",
                 hetero_dimer, params.max_hetero_dimer, forward.sequence, reverse.sequence
             );
-            return false;
+            return Err((
+                RejectionReason::HeteroDimer,
+                params.max_hetero_dimer - hetero_dimer,
+            ));
         }
 
         println!(
             "DEBUG: Pair accepted - Tm diff: {:.2}, hetero-dimer: {:.2}",
             tm_diff, hetero_dimer
         );
-        true
+        Ok(())
     }
-}
 
-impl PrimerDesignService for PrimerDesignServiceImpl {
-    type Error = anyhow::Error;
+    /// `weights` は `params.weighting` に対応する塩基ごとの重み（鋳型配列と同じ長さ）
+    pub fn design_primers_weighted(
+        &self,
+        sequence: &str,
+        start: usize,
+        end: usize,
+        params: &PrimerDesignParams,
+        weights: Option<&[f64]>,
+    ) -> anyhow::Result<PrimerDesignResult> {
+        self.design_primers_streamed(sequence, start, end, params, weights, &mut |_| {})
+    }
 
-    fn design_primers(
+    /// `design_primers_weighted` と同じ設計を行い、検証を通ったペアを見つかった順に
+    /// `on_progress` へまとめて渡す。戻り値は全候補を順位付けした最終結果
+    pub fn design_primers_streamed(
         &self,
         sequence: &str,
         start: usize,
         end: usize,
         params: &PrimerDesignParams,
-    ) -> Result<PrimerDesignResult, Self::Error> {
+        weights: Option<&[f64]>,
+        on_progress: &mut dyn FnMut(PrimerDesignProgress),
+    ) -> anyhow::Result<PrimerDesignResult> {
         println!(
             "DEBUG: Primer design called with sequence length: {}, start: {}, end: {}",
             sequence.len(),
@@ -247,11 +513,26 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
         }
 
         // Forward and reverse primer candidates generation
-        let forward_candidates =
-            self.generate_primer_candidates(sequence, start, end, params, PrimerDirection::Forward);
+        let mut diagnostics = DesignDiagnostics::default();
+        let forward_candidates = self.generate_primer_candidates(
+            sequence,
+            start,
+            end,
+            params,
+            PrimerDirection::Forward,
+            weights,
+            &mut diagnostics,
+        );
 
-        let reverse_candidates =
-            self.generate_primer_candidates(sequence, start, end, params, PrimerDirection::Reverse);
+        let reverse_candidates = self.generate_primer_candidates(
+            sequence,
+            start,
+            end,
+            params,
+            PrimerDirection::Reverse,
+            weights,
+            &mut diagnostics,
+        );
 
         println!(
             "DEBUG: Found {} forward candidates, {} reverse candidates",
@@ -260,28 +541,57 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
         );
 
         let mut pairs = Vec::new();
+        let pairs_total = forward_candidates.len() * reverse_candidates.len();
+        let mut reported = 0;
+        let mut last_report = Instant::now();
+        let mut report = |pairs: &[PrimerPair], reported: &mut usize, evaluated: usize| {
+            on_progress(PrimerDesignProgress {
+                pairs: pairs[*reported..].to_vec(),
+                pairs_found: pairs.len(),
+                pairs_evaluated: evaluated,
+                pairs_total,
+            });
+            *reported = pairs.len();
+        };
         println!("DEBUG: Starting pair compatibility check");
 
         // Generate primer pairs
         for forward in &forward_candidates {
             for reverse in &reverse_candidates {
-                if !self.is_compatible_pair(forward, reverse, params) {
-                    println!(
-                        "DEBUG: Pair failed compatibility check - forward pos: {}, reverse pos: {}",
-                        forward.position, reverse.position
-                    );
-                    continue;
-                }
-
+                diagnostics.pairs_evaluated += 1;
                 let amplicon_start = forward.position.min(reverse.position);
                 let amplicon_end =
                     forward.position.max(reverse.position) + forward.length.max(reverse.length);
                 let amplicon_length = amplicon_end - amplicon_start;
 
-                // 適切な増幅産物サイズかチェック
-                if amplicon_length < 100 || amplicon_length > 3000 {
-                    println!("DEBUG: Pair filtered out by amplicon size: {} bp (forward: {}, reverse: {})",
-                             amplicon_length, forward.position, reverse.position);
+                let rejection = self
+                    .check_pair_compatibility(forward, reverse, params)
+                    .err()
+                    .or_else(|| {
+                        // 適切な増幅産物サイズかチェック
+                        outside(
+                            amplicon_length as f32,
+                            AMPLICON_LENGTH.0 as f32,
+                            AMPLICON_LENGTH.1 as f32,
+                        )
+                        .map(|excess| (RejectionReason::ProductSize, excess))
+                    });
+                if let Some((reason, excess)) = rejection {
+                    println!(
+                        "DEBUG: Pair rejected by {:?} - forward pos: {}, reverse pos: {}, amplicon: {} bp",
+                        reason, forward.position, reverse.position, amplicon_length
+                    );
+                    diagnostics.rejected.add(reason);
+                    if near_miss_wanted(&diagnostics, reason, excess) {
+                        keep_near_miss(
+                            &mut diagnostics,
+                            NearMiss {
+                                reason,
+                                excess,
+                                primers: vec![forward.clone(), reverse.clone()],
+                            },
+                        );
+                    }
                     continue;
                 }
 
@@ -301,7 +611,7 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
                     && reverse.hairpin_score >= params.max_hairpin;
 
                 let pair = PrimerPair {
-                    id: Uuid::new_v4().to_string(),
+                    id: reproducibility::workspace_id().to_string(),
                     forward: forward.clone(),
                     reverse: reverse.clone(),
                     amplicon_length,
@@ -316,8 +626,17 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
                 };
 
                 pairs.push(pair);
+                if pairs.len() - reported >= PROGRESS_BATCH_PAIRS
+                    || last_report.elapsed() >= PROGRESS_INTERVAL
+                {
+                    report(&pairs, &mut reported, diagnostics.pairs_evaluated);
+                    last_report = Instant::now();
+                }
             }
         }
+        if pairs.len() > reported {
+            report(&pairs, &mut reported, diagnostics.pairs_evaluated);
+        }
 
         println!(
             "DEBUG: Found {} total valid pairs before sorting",
@@ -343,16 +662,107 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
             None
         };
 
+        let diagnostics = pairs.is_empty().then_some(diagnostics);
         Ok(PrimerDesignResult {
             pairs,
             design_params: params.clone(),
-            target_sequence: sequence[start..=end].to_string(),
+            target_sequence: sequence[start..end].to_string(),
             target_start: start,
             target_end: end,
             multiplex_compatibility,
+            provenance: Some(self.provenance()),
+            blocked_pairs: Vec::new(),
+            diagnostics,
         })
     }
 
+    /// 設計結果の診断から除外の多かった条件を選び、1つずつ段階的に緩めて再設計して、
+    /// `min_pairs` 組以上のペアが得られる最小の変更を条件ごとに提案する。
+    /// `sequence` と `weights` は `result` を設計したときと同じものを渡す
+    pub fn suggest_relaxation(
+        &self,
+        sequence: &str,
+        result: &PrimerDesignResult,
+        weights: Option<&[f64]>,
+        min_pairs: usize,
+    ) -> RelaxationPlan {
+        let mut plan = RelaxationPlan {
+            min_pairs,
+            current_pairs: result.pairs.len(),
+            suggestions: Vec::new(),
+        };
+        if result.pairs.len() >= min_pairs {
+            return plan;
+        }
+
+        let params = &result.design_params;
+        let mut ranked: Vec<(usize, RelaxationSuggestion)> = Vec::new();
+        for (parameter, step, max) in RELAXATION_STEPS {
+            // 領域のどこからでも探す設定では検索範囲は広げられない
+            if parameter == RelaxedParameter::SearchFlank && params.search_anywhere {
+                continue;
+            }
+            let Some((count, closest)) = relaxation_hint(result.diagnostics.as_ref(), parameter)
+            else {
+                continue;
+            };
+            let steps = (1..)
+                .map(|i| i as f32 * step)
+                .take_while(|&change| change <= max)
+                .filter(|&change| change >= closest);
+            for change in steps {
+                let relaxed = relaxed_params(params, parameter, change);
+                let Ok(redesigned) = self.design_primers_weighted(
+                    sequence,
+                    result.target_start,
+                    result.target_end,
+                    &relaxed,
+                    weights,
+                ) else {
+                    break;
+                };
+                if redesigned.pairs.len() >= min_pairs {
+                    ranked.push((
+                        count,
+                        RelaxationSuggestion {
+                            parameter,
+                            change,
+                            params: relaxed,
+                            pairs: redesigned.pairs.len(),
+                        },
+                    ));
+                    break;
+                }
+            }
+        }
+
+        ranked.sort_by_key(|(count, suggestion)| {
+            (
+                suggestion.parameter == RelaxedParameter::SearchFlank,
+                std::cmp::Reverse(*count),
+            )
+        });
+        plan.suggestions = ranked
+            .into_iter()
+            .map(|(_, suggestion)| suggestion)
+            .collect();
+        plan
+    }
+}
+
+impl PrimerDesignService for PrimerDesignServiceImpl {
+    type Error = anyhow::Error;
+
+    fn design_primers(
+        &self,
+        sequence: &str,
+        start: usize,
+        end: usize,
+        params: &PrimerDesignParams,
+    ) -> Result<PrimerDesignResult, Self::Error> {
+        self.design_primers_weighted(sequence, start, end, params, None)
+    }
+
     fn calculate_tm(&self, sequence: &str) -> f32 {
         // 新しい熱力学計算機を使用
         match self
@@ -605,7 +1015,7 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
         &self,
         pair1: &PrimerPair,
         pair2: &PrimerPair,
-        warnings: &mut Vec<String>,
+        warnings: &mut Vec<Warning>,
     ) -> f32 {
         let mut compatibility_score: f32 = 1.0;
         let mut penalty: f32 = 0.0;
@@ -617,10 +1027,14 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
 
         if max_tm_diff > 5.0 {
             penalty += 0.2;
-            warnings.push(format!(
-                "Large Tm difference between {} and {} ({:.1}°C)",
-                pair1.id, pair2.id, max_tm_diff
-            ));
+            warnings.push(
+                WarningCode::MultiplexTmDifference {
+                    pair1: pair1.id.clone(),
+                    pair2: pair2.id.clone(),
+                    difference: max_tm_diff,
+                }
+                .into(),
+            );
         }
 
         // 2. Cross-reactivity analysis
@@ -638,16 +1052,24 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
         // Strong hetero-dimer formation is problematic
         if min_cross_reactivity < -8.0 {
             penalty += 0.4;
-            warnings.push(format!(
-                "Strong cross-reactivity detected between {} and {} (ΔG: {:.1} kcal/mol)",
-                pair1.id, pair2.id, min_cross_reactivity
-            ));
+            warnings.push(
+                WarningCode::StrongCrossReactivity {
+                    pair1: pair1.id.clone(),
+                    pair2: pair2.id.clone(),
+                    delta_g: min_cross_reactivity,
+                }
+                .into(),
+            );
         } else if min_cross_reactivity < -5.0 {
             penalty += 0.2;
-            warnings.push(format!(
-                "Moderate cross-reactivity detected between {} and {} (ΔG: {:.1} kcal/mol)",
-                pair1.id, pair2.id, min_cross_reactivity
-            ));
+            warnings.push(
+                WarningCode::ModerateCrossReactivity {
+                    pair1: pair1.id.clone(),
+                    pair2: pair2.id.clone(),
+                    delta_g: min_cross_reactivity,
+                }
+                .into(),
+            );
         }
 
         compatibility_score = (1.0 - penalty).max(0.0);
@@ -657,7 +1079,7 @@ impl PrimerDesignService for PrimerDesignServiceImpl {
 
 impl PrimerDesignServiceImpl {
     /// リピート配列の検出（Plascadアルゴリズムを参考）
-    fn check_nucleotide_repeats(&self, sequence: &str, warnings: &mut Vec<String>) -> f32 {
+    fn check_nucleotide_repeats(&self, sequence: &str, warnings: &mut Vec<Warning>) -> f32 {
         let mut penalty = 0.0f32;
         let seq_upper = sequence.to_uppercase();
 
@@ -666,10 +1088,12 @@ impl PrimerDesignServiceImpl {
         for repeat in &single_repeats {
             if seq_upper.contains(repeat) {
                 penalty += 0.3;
-                warnings.push(format!(
-                    "Long single nucleotide repeat {} detected in primer",
-                    repeat
-                ));
+                warnings.push(
+                    WarningCode::SingleBaseRepeat {
+                        repeat: repeat.to_string(),
+                    }
+                    .into(),
+                );
                 break; // 一つでも見つかったら十分
             }
         }
@@ -679,7 +1103,12 @@ impl PrimerDesignServiceImpl {
         for repeat in &dinucleotide_repeats {
             if seq_upper.contains(repeat) {
                 penalty += 0.25;
-                warnings.push(format!("Dinucleotide repeat pattern detected in primer"));
+                warnings.push(
+                    WarningCode::DinucleotideRepeat {
+                        repeat: repeat.to_string(),
+                    }
+                    .into(),
+                );
                 break;
             }
         }
@@ -689,10 +1118,12 @@ impl PrimerDesignServiceImpl {
         for repeat in &short_problematic {
             if seq_upper.contains(repeat) {
                 penalty += 0.4; // より強いペナルティ
-                warnings.push(format!(
-                    "Very long single nucleotide repeat {} detected",
-                    repeat
-                ));
+                warnings.push(
+                    WarningCode::LongSingleBaseRepeat {
+                        repeat: repeat.to_string(),
+                    }
+                    .into(),
+                );
                 break;
             }
         }
@@ -701,7 +1132,7 @@ impl PrimerDesignServiceImpl {
     }
 
     /// 改良された3'末端安定性解析
-    fn enhanced_three_prime_stability(&self, sequence: &str, warnings: &mut Vec<String>) -> f32 {
+    fn enhanced_three_prime_stability(&self, sequence: &str, warnings: &mut Vec<Warning>) -> f32 {
         let seq_chars: Vec<char> = sequence.to_uppercase().chars().collect();
         let mut stability_score = 0.0f32;
         let mut issues = Vec::new();
@@ -731,7 +1162,12 @@ impl PrimerDesignServiceImpl {
             0 => {
                 stability_score = 0.5;
                 issues.push("Weak AT-rich 3' end".to_string());
-                warnings.push("3' end is AT-rich and may have weak binding".to_string());
+                warnings.push(
+                    WarningCode::AtRichThreePrimeEnd {
+                        end: last_three.clone(),
+                    }
+                    .into(),
+                );
             }
             _ => stability_score = 1.0,
         }
@@ -739,7 +1175,7 @@ impl PrimerDesignServiceImpl {
         // 特定の問題のある末端パターンをチェック
         if last_three.ends_with("AA") || last_three.ends_with("TT") {
             stability_score *= 0.8;
-            warnings.push("3' end has weak AA/TT terminus".to_string());
+            warnings.push(WarningCode::WeakThreePrimeTerminus { end: last_three }.into());
         }
 
         // 非常に良いGC末端
@@ -751,49 +1187,53 @@ impl PrimerDesignServiceImpl {
     }
 
     /// プライマー品質の包括的評価
-    fn calculate_primer_quality_score(&self, primer: &Primer, warnings: &mut Vec<String>) -> f32 {
+    fn calculate_primer_quality_score(&self, primer: &Primer, warnings: &mut Vec<Warning>) -> f32 {
         let mut quality_score = 100.0f32; // 100点満点から減点方式
 
         // 1. Tm適正範囲チェック (55-65°C)
-        if primer.tm < 55.0 {
+        let (min, max) = RECOMMENDED_TM;
+        if primer.tm < min {
             quality_score -= 15.0;
-            warnings.push(format!("Low Tm: {:.1}°C (recommended: 55-65°C)", primer.tm));
-        } else if primer.tm > 65.0 {
+            warnings.push(
+                WarningCode::LowTm {
+                    tm: primer.tm,
+                    min,
+                    max,
+                }
+                .into(),
+            );
+        } else if primer.tm > max {
             quality_score -= 10.0;
-            warnings.push(format!(
-                "High Tm: {:.1}°C (recommended: 55-65°C)",
-                primer.tm
-            ));
+            warnings.push(
+                WarningCode::HighTm {
+                    tm: primer.tm,
+                    min,
+                    max,
+                }
+                .into(),
+            );
         }
 
         // 2. GC含量チェック (40-60%)
-        if primer.gc_content < 40.0 {
+        let (min, max) = RECOMMENDED_GC;
+        let gc = primer.gc_content;
+        if gc < min {
             quality_score -= 10.0;
-            warnings.push(format!(
-                "Low GC content: {:.1}% (recommended: 40-60%)",
-                primer.gc_content
-            ));
-        } else if primer.gc_content > 60.0 {
+            warnings.push(WarningCode::LowGc { gc, min, max }.into());
+        } else if gc > max {
             quality_score -= 8.0;
-            warnings.push(format!(
-                "High GC content: {:.1}% (recommended: 40-60%)",
-                primer.gc_content
-            ));
+            warnings.push(WarningCode::HighGc { gc, min, max }.into());
         }
 
         // 3. 長さチェック (18-25 bp)
-        if primer.length < 18 {
+        let (min, max) = RECOMMENDED_LENGTH;
+        let length = primer.length;
+        if length < min {
             quality_score -= 12.0;
-            warnings.push(format!(
-                "Short primer: {} bp (recommended: 18-25 bp)",
-                primer.length
-            ));
-        } else if primer.length > 25 {
+            warnings.push(WarningCode::ShortPrimer { length, min, max }.into());
+        } else if length > max {
             quality_score -= 8.0;
-            warnings.push(format!(
-                "Long primer: {} bp (recommended: 18-25 bp)",
-                primer.length
-            ));
+            warnings.push(WarningCode::LongPrimer { length, min, max }.into());
         }
 
         // 4. リピート配列のペナルティ
@@ -807,30 +1247,42 @@ impl PrimerDesignServiceImpl {
         // 6. セルフダイマーとヘアピン構造のペナルティ
         if primer.self_dimer_score < -8.0 {
             quality_score -= 15.0;
-            warnings.push(format!(
-                "Strong self-dimer potential: {:.1} kcal/mol",
-                primer.self_dimer_score
-            ));
+            warnings.push(
+                WarningCode::StrongSelfDimer {
+                    delta_g: primer.self_dimer_score,
+                    threshold: -8.0,
+                }
+                .into(),
+            );
         } else if primer.self_dimer_score < -5.0 {
             quality_score -= 8.0;
-            warnings.push(format!(
-                "Moderate self-dimer potential: {:.1} kcal/mol",
-                primer.self_dimer_score
-            ));
+            warnings.push(
+                WarningCode::ModerateSelfDimer {
+                    delta_g: primer.self_dimer_score,
+                    threshold: -5.0,
+                }
+                .into(),
+            );
         }
 
         if primer.hairpin_score < -5.0 {
             quality_score -= 10.0;
-            warnings.push(format!(
-                "Strong hairpin potential: {:.1} kcal/mol",
-                primer.hairpin_score
-            ));
+            warnings.push(
+                WarningCode::StrongHairpin {
+                    delta_g: primer.hairpin_score,
+                    threshold: -5.0,
+                }
+                .into(),
+            );
         } else if primer.hairpin_score < -3.0 {
             quality_score -= 5.0;
-            warnings.push(format!(
-                "Moderate hairpin potential: {:.1} kcal/mol",
-                primer.hairpin_score
-            ));
+            warnings.push(
+                WarningCode::ModerateHairpin {
+                    delta_g: primer.hairpin_score,
+                    threshold: -3.0,
+                }
+                .into(),
+            );
         }
 
         // 最低0点、最高110点程度に制限
@@ -1055,7 +1507,7 @@ impl PrimerDesignServiceImpl {
         &self,
         pair1: &PrimerPair,
         pair2: &PrimerPair,
-        warnings: &mut Vec<String>,
+        warnings: &mut Vec<Warning>,
     ) -> f32 {
         let mut compatibility_score: f32 = 1.0; // Perfect compatibility = 1.0
         let mut penalty: f32 = 0.0;
@@ -1067,10 +1519,14 @@ impl PrimerDesignServiceImpl {
 
         if max_tm_diff > 5.0 {
             penalty += 0.2;
-            warnings.push(format!(
-                "Large Tm difference between {} and {} ({:.1}°C)",
-                pair1.id, pair2.id, max_tm_diff
-            ));
+            warnings.push(
+                WarningCode::MultiplexTmDifference {
+                    pair1: pair1.id.clone(),
+                    pair2: pair2.id.clone(),
+                    difference: max_tm_diff,
+                }
+                .into(),
+            );
         }
 
         // 2. Cross-reactivity analysis (hetero-dimer formation)
@@ -1088,16 +1544,24 @@ impl PrimerDesignServiceImpl {
         // Strong hetero-dimer formation is problematic (more negative = stronger binding)
         if min_cross_reactivity < -8.0 {
             penalty += 0.4;
-            warnings.push(format!(
-                "Strong cross-reactivity detected between {} and {} (ΔG: {:.1} kcal/mol)",
-                pair1.id, pair2.id, min_cross_reactivity
-            ));
+            warnings.push(
+                WarningCode::StrongCrossReactivity {
+                    pair1: pair1.id.clone(),
+                    pair2: pair2.id.clone(),
+                    delta_g: min_cross_reactivity,
+                }
+                .into(),
+            );
         } else if min_cross_reactivity < -5.0 {
             penalty += 0.2;
-            warnings.push(format!(
-                "Moderate cross-reactivity between {} and {} (ΔG: {:.1} kcal/mol)",
-                pair1.id, pair2.id, min_cross_reactivity
-            ));
+            warnings.push(
+                WarningCode::ModerateCrossReactivity {
+                    pair1: pair1.id.clone(),
+                    pair2: pair2.id.clone(),
+                    delta_g: min_cross_reactivity,
+                }
+                .into(),
+            );
         }
 
         // 3. Amplicon length compatibility
@@ -1110,10 +1574,15 @@ impl PrimerDesignServiceImpl {
 
         if length_ratio > 5.0 {
             penalty += 0.2;
-            warnings.push(format!(
-                "Large amplicon size difference between {} ({} bp) and {} ({} bp)",
-                pair1.id, pair1.amplicon_length, pair2.id, pair2.amplicon_length
-            ));
+            warnings.push(
+                WarningCode::MultiplexAmpliconSizeDifference {
+                    pair1: pair1.id.clone(),
+                    length1: pair1.amplicon_length,
+                    pair2: pair2.id.clone(),
+                    length2: pair2.amplicon_length,
+                }
+                .into(),
+            );
         }
 
         // 4. GC content compatibility
@@ -1123,10 +1592,14 @@ impl PrimerDesignServiceImpl {
 
         if max_gc_diff > 20.0 {
             penalty += 0.1;
-            warnings.push(format!(
-                "Large GC content difference between {} and {} ({:.1}%)",
-                pair1.id, pair2.id, max_gc_diff
-            ));
+            warnings.push(
+                WarningCode::MultiplexGcDifference {
+                    pair1: pair1.id.clone(),
+                    pair2: pair2.id.clone(),
+                    difference: max_gc_diff,
+                }
+                .into(),
+            );
         }
 
         // Apply penalties to compatibility score
@@ -1139,6 +1612,7 @@ impl PrimerDesignServiceImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::warning::WarningSeverity;
 
     #[test]
     fn test_tm_calculation() {
@@ -1151,6 +1625,263 @@ mod tests {
         assert!(tm < 95.0); // Updated upper bound for GC-rich 12-mer
     }
 
+    #[test]
+    fn test_primer_records_provenance() {
+        let service = PrimerDesignServiceImpl::new_santalucia_1998();
+        let primer = service.evaluate_primer(
+            "ATGCGTACGTTAGCCTAGCA".to_string(),
+            0,
+            PrimerDirection::Forward,
+        );
+        let provenance = primer.provenance.unwrap();
+        assert_eq!(provenance.parameter_set, "SantaLucia");
+        assert_eq!(provenance, service.provenance());
+    }
+
+    #[test]
+    fn test_position_weighting_penalizes_low_weight_bases() {
+        let service = PrimerDesignServiceImpl::new();
+        let weighting = PositionWeighting {
+            max_low_weight_bases: Some(1),
+            ..PositionWeighting::default()
+        };
+        let mut weights = vec![1.0; 40];
+        weights[2] = 0.1;
+        weights[19] = 0.1;
+
+        // Forward の3'末端（位置19）は2倍減点、2塩基で除外
+        let mut forward = service.evaluate_primer(
+            "ATGCGTACGTTAGCCTAGCA".to_string(),
+            0,
+            PrimerDirection::Forward,
+        );
+        let before = forward.quality_score;
+        assert!(!service.apply_position_weighting(&mut forward, &weighting, &weights));
+        assert_eq!(forward.quality_score, (before - 15.0).max(0.0));
+
+        // 反転すると高重みの塩基が減点対象になる
+        let mut reverse = service.evaluate_primer(
+            "ATGCGTACGTTAGCCTAGCA".to_string(),
+            20,
+            PrimerDirection::Reverse,
+        );
+        let inverted = PositionWeighting {
+            invert: true,
+            ..weighting
+        };
+        assert!(!service.apply_position_weighting(&mut reverse, &inverted, &weights));
+        let warning = reverse.quality_warnings.last().unwrap();
+        assert_eq!(
+            warning.code,
+            WarningCode::LowWeightBases {
+                count: 20,
+                min_weight: 0.8
+            }
+        );
+        assert_eq!(warning.severity, WarningSeverity::Warning);
+        assert_eq!(warning.message, "20 low-weight bases in primer");
+    }
+
+    #[test]
+    fn test_quality_mask_excludes_low_quality_positions() {
+        let service = PrimerDesignServiceImpl::new();
+        let sequence = "ATGCGTACGTTAGCCTAGCAGGCATTCGATCGGATCCAAGCTTGCATGCAGTCGACCTGCAGGCATG";
+        let mut qualities = vec![40u8; sequence.len()];
+        qualities[10] = 5;
+        let params = PrimerDesignParams {
+            tm_min: 0.0,
+            tm_max: 100.0,
+            gc_min: 0.0,
+            gc_max: 100.0,
+            quality_mask: Some(QualityMask {
+                qualities,
+                min_quality: 20,
+            }),
+            ..PrimerDesignParams::default()
+        };
+
+        let candidates = service.generate_primer_candidates(
+            sequence,
+            5,
+            40,
+            &params,
+            PrimerDirection::Forward,
+            None,
+            &mut DesignDiagnostics::default(),
+        );
+        assert!(!candidates.is_empty());
+        assert!(candidates
+            .iter()
+            .all(|p| p.position > 10 || p.position + p.length <= 10));
+    }
+
+    #[test]
+    fn test_diagnostics_explain_empty_result() {
+        let service = PrimerDesignServiceImpl::new();
+        let sequence =
+            "ATGCGTACGTTAGCCTAGCAGGCATTCGATCGGATCCAAGCTTGCATGCAGTCGACCTGCAGGCATG".repeat(5);
+        let params = PrimerDesignParams {
+            tm_min: 95.0,
+            tm_max: 99.0,
+            ..PrimerDesignParams::default()
+        };
+
+        let result = service
+            .design_primers(&sequence, 100, 200, &params)
+            .unwrap();
+        assert!(result.pairs.is_empty());
+        let diagnostics = result.diagnostics.unwrap();
+        assert!(diagnostics.candidates_evaluated > 0);
+        assert_eq!(diagnostics.rejected.tm, diagnostics.candidates_evaluated);
+        assert_eq!(diagnostics.pairs_evaluated, 0);
+
+        // 惜しい候補は Tm の上限に近い順に3件まで
+        let misses = &diagnostics.near_misses;
+        assert_eq!(misses.len(), NEAR_MISSES_PER_REASON);
+        assert!(misses.iter().all(|m| m.reason == RejectionReason::Tm));
+        assert!(misses.windows(2).all(|w| w[0].excess <= w[1].excess));
+        assert!((params.tm_min - misses[0].primers[0].tm - misses[0].excess).abs() < 1e-3);
+
+        let relaxed = PrimerDesignParams {
+            tm_min: 40.0,
+            tm_max: 80.0,
+            gc_min: 20.0,
+            gc_max: 80.0,
+            max_hetero_dimer: -50.0,
+            ..PrimerDesignParams::default()
+        };
+        let result = service
+            .design_primers(&sequence, 100, 200, &relaxed)
+            .unwrap();
+        assert!(!result.pairs.is_empty());
+        assert!(result.diagnostics.is_none());
+    }
+
+    #[test]
+    fn test_search_anywhere_reaches_distant_primer_sites() {
+        let service = PrimerDesignServiceImpl::new();
+        let motif = "ATGCGTACGTTAGCCTAGCAGGCATTCGATCGGATCCAAGCTTGCATGCAGTCGACCTGCAGGCATG";
+        // 領域の前後 ±200 bp はプライマーにならない poly(A)
+        let sequence = format!("{}{}{}", motif.repeat(3), "A".repeat(400), motif.repeat(3));
+        let params = PrimerDesignParams {
+            tm_min: 40.0,
+            tm_max: 80.0,
+            gc_min: 20.0,
+            gc_max: 80.0,
+            max_hetero_dimer: -50.0,
+            ..PrimerDesignParams::default()
+        };
+        let result = service
+            .design_primers(&sequence, 351, 451, &params)
+            .unwrap();
+        assert!(result.pairs.is_empty());
+
+        let anywhere = PrimerDesignParams {
+            search_anywhere: true,
+            ..params
+        };
+        let result = service
+            .design_primers(&sequence, 351, 451, &anywhere)
+            .unwrap();
+        assert!(!result.pairs.is_empty());
+        for pair in &result.pairs {
+            assert!(pair.forward.position + pair.forward.length <= 201);
+            assert!(pair.reverse.position >= 601);
+            assert!(pair.amplicon_length <= AMPLICON_LENGTH.1);
+        }
+    }
+
+    #[test]
+    fn test_streamed_design_reports_every_valid_pair() {
+        let service = PrimerDesignServiceImpl::new();
+        let sequence =
+            "ATGCGTACGTTAGCCTAGCAGGCATTCGATCGGATCCAAGCTTGCATGCAGTCGACCTGCAGGCATG".repeat(5);
+        let params = PrimerDesignParams {
+            tm_min: 40.0,
+            tm_max: 80.0,
+            gc_min: 20.0,
+            gc_max: 80.0,
+            max_hetero_dimer: -50.0,
+            ..PrimerDesignParams::default()
+        };
+
+        let mut progress = Vec::new();
+        let result = service
+            .design_primers_streamed(&sequence, 120, 220, &params, None, &mut |p| {
+                progress.push(p)
+            })
+            .unwrap();
+        assert_eq!(result.pairs.len(), 10);
+
+        let streamed: Vec<&PrimerPair> = progress.iter().flat_map(|p| &p.pairs).collect();
+        let last = progress.last().unwrap();
+        assert_eq!(last.pairs_found, streamed.len());
+        assert!(streamed.len() > result.pairs.len());
+        assert!(progress
+            .iter()
+            .all(|p| p.pairs.len() <= PROGRESS_BATCH_PAIRS));
+        assert!(last.pairs_evaluated <= last.pairs_total);
+        // 最終結果は途中経過で届いたペアを順位付けしたもの
+        for pair in &result.pairs {
+            assert!(streamed.iter().any(|s| s.id == pair.id));
+        }
+    }
+
+    #[test]
+    fn test_suggest_relaxation_reaches_min_pairs() {
+        let service = PrimerDesignServiceImpl::new();
+        let sequence =
+            "ATGCGTACGTTAGCCTAGCAGGCATTCGATCGGATCCAAGCTTGCATGCAGTCGACCTGCAGGCATG".repeat(5);
+        let params = PrimerDesignParams {
+            tm_min: 40.0,
+            tm_max: 80.0,
+            max_hetero_dimer: -50.0,
+            ..PrimerDesignParams::default()
+        };
+        let result = service
+            .design_primers(&sequence, 100, 200, &params)
+            .unwrap();
+        assert!(service
+            .suggest_relaxation(&sequence, &result, None, 1)
+            .suggestions
+            .is_empty());
+
+        // GC の範囲を狭めて除外されたペアを、GC の範囲を広げれば取り戻せる
+        let strict = PrimerDesignParams {
+            gc_min: 58.0,
+            gc_max: 59.0,
+            ..params.clone()
+        };
+        let result = service
+            .design_primers(&sequence, 100, 200, &strict)
+            .unwrap();
+        assert!(result.pairs.is_empty());
+        let plan = service.suggest_relaxation(&sequence, &result, None, 3);
+        assert_eq!((plan.min_pairs, plan.current_pairs), (3, 0));
+
+        let gc = plan
+            .suggestions
+            .iter()
+            .find(|s| s.parameter == RelaxedParameter::GcRange)
+            .unwrap();
+        assert!(gc.pairs >= 3);
+        assert_eq!(gc.params.gc_min, strict.gc_min - gc.change);
+        // 1段階小さい変更では足りない
+        let smaller = relaxed_params(&strict, RelaxedParameter::GcRange, gc.change - 2.5);
+        assert!(
+            service
+                .design_primers(&sequence, 100, 200, &smaller)
+                .unwrap()
+                .pairs
+                .len()
+                < 3
+        );
+        assert!(plan
+            .suggestions
+            .iter()
+            .all(|s| s.parameter != RelaxedParameter::HeteroDimer));
+    }
+
     #[test]
     fn test_gc_content() {
         let service = PrimerDesignServiceImpl::new();
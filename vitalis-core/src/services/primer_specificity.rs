@@ -0,0 +1,255 @@
+// Service layer: Primer binding-site search across the loaded sequences
+use crate::domain::index::KmerIndex;
+use crate::domain::iupac;
+use crate::domain::primer::{DuplexPair, PrimerBindingSite, PrimerDuplex, THREE_PRIME_WINDOW};
+use crate::domain::search::MotifMatch;
+use crate::domain::thermodynamic_calculator::{ThermodynamicCalculator, ThermodynamicError};
+use crate::domain::{Strand, Topology};
+use crate::services::SequenceSearchService;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DuplexError {
+    #[error("Binding site {0}..{1} lies outside the {2} bp sequence")]
+    OutOfRange(usize, usize, usize),
+    #[error("Cannot evaluate duplex: {0}")]
+    Thermodynamics(#[from] ThermodynamicError),
+}
+
+/// Primer specificity service
+pub struct PrimerSpecificityService {
+    search_service: SequenceSearchService,
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for PrimerSpecificityService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrimerSpecificityService {
+    pub fn new() -> Self {
+        Self {
+            search_service: SequenceSearchService::new(),
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// 検索対象配列から構築した k-mer インデックスで結合部位の候補を絞る
+    pub fn with_index(index: Arc<KmerIndex>) -> Self {
+        Self {
+            search_service: SequenceSearchService::with_index(index),
+            ..Self::new()
+        }
+    }
+
+    /// `sequence` の両鎖から `primer` が `max_mismatches` 以下で結合しうる部位を探し、
+    /// 各部位の3'末端のミスマッチを調べる（位置順）
+    pub fn binding_sites(
+        &self,
+        seq_id: &str,
+        sequence: &str,
+        topology: &Topology,
+        primer: &str,
+        max_mismatches: usize,
+    ) -> Vec<PrimerBindingSite> {
+        let primer = primer.to_ascii_uppercase();
+        self.search_service
+            .find_motif(sequence, topology, &primer, max_mismatches)
+            .into_iter()
+            .map(|hit| self.binding_site(seq_id, &primer, hit))
+            .collect()
+    }
+
+    /// `start` から `strand` 上に結合した `primer` と鋳型の二本鎖を並べ、
+    /// ミスマッチの位置と塩基対ごとのスタッキング ΔG を求める
+    pub fn duplex(
+        &self,
+        seq_id: &str,
+        sequence: &str,
+        topology: &Topology,
+        primer: &str,
+        start: usize,
+        strand: Strand,
+    ) -> Result<PrimerDuplex, DuplexError> {
+        let primer = primer.to_ascii_uppercase();
+        let length = sequence.len();
+        let end = start + primer.len();
+        let circular = *topology == Topology::Circular;
+        let in_range = if circular {
+            start < length && primer.len() <= length
+        } else {
+            end <= length
+        };
+        if !in_range {
+            return Err(DuplexError::OutOfRange(start, end, length));
+        }
+
+        let text = iupac::search_text(sequence, circular, primer.len());
+        let bases = String::from_utf8_lossy(&text[start..end]).into_owned();
+        let matched = match strand {
+            Strand::Forward => bases,
+            Strand::Reverse => iupac::reverse_complement(&bases),
+        };
+        let mismatches = primer
+            .chars()
+            .zip(matched.chars())
+            .filter(|&(code, base)| !iupac::matches(code, base))
+            .count();
+        let site = self.binding_site(
+            seq_id,
+            &primer,
+            MotifMatch {
+                start,
+                end,
+                strand,
+                mismatches,
+                matched,
+            },
+        );
+
+        // 鋳型鎖はプライマーに向かい合う向き（3'→5'）に並べる
+        let template: String = site.matched.chars().map(iupac::complement).collect();
+        let energy = self
+            .calculator
+            .calculate_duplex_energy(&primer, &template)?;
+        let pairs: Vec<DuplexPair> = primer
+            .chars()
+            .zip(site.matched.chars())
+            .zip(template.chars())
+            .enumerate()
+            .map(|(index, ((primer_base, base), template_base))| DuplexPair {
+                index,
+                primer_base,
+                template_base,
+                mismatch: !iupac::matches(primer_base, base),
+                stack_delta_g: energy.stacks.get(index).copied().flatten(),
+            })
+            .collect();
+
+        Ok(PrimerDuplex {
+            match_line: pairs
+                .iter()
+                .map(|pair| if pair.mismatch { ' ' } else { '|' })
+                .collect(),
+            mismatch_positions: pairs
+                .iter()
+                .filter(|pair| pair.mismatch)
+                .map(|pair| pair.index)
+                .collect(),
+            primer_line: primer,
+            template_line: template,
+            pairs,
+            delta_g: energy.delta_g,
+            provenance: energy.provenance,
+            site,
+        })
+    }
+
+    fn binding_site(&self, seq_id: &str, primer: &str, hit: MotifMatch) -> PrimerBindingSite {
+        // matched はプライマーの向きなので、末尾から数えた位置が3'末端からの距離
+        let mismatched: Vec<bool> = primer
+            .chars()
+            .zip(hit.matched.chars())
+            .map(|(code, base)| !iupac::matches(code, base))
+            .collect();
+        let tail = mismatched.len().saturating_sub(THREE_PRIME_WINDOW);
+        PrimerBindingSite {
+            seq_id: seq_id.to_string(),
+            start: hit.start,
+            end: hit.end,
+            strand: hit.strand,
+            mismatches: hit.mismatches,
+            three_prime_mismatches: mismatched[tail..].iter().filter(|&&m| m).count(),
+            terminal_mismatch: mismatched.last().copied().unwrap_or(false),
+            matched: hit.matched,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binding_sites_report_three_prime_mismatches() {
+        let service = PrimerSpecificityService::new();
+        let primer = "ACGTTGCAAC";
+        // 完全一致、5'側の1ミスマッチ、逆鎖上の3'末端ミスマッチ
+        let sequence = format!(
+            "{}AAAAA{}AAAAA{}",
+            primer,
+            "TCGTTGCAAC",
+            iupac::reverse_complement("ACGTTGCAAG")
+        );
+
+        let sites = service.binding_sites("seq_1", &sequence, &Topology::Linear, primer, 1);
+        assert_eq!(sites.len(), 3);
+        assert_eq!((sites[0].start, sites[0].mismatches), (0, 0));
+        assert_eq!((sites[1].start, sites[1].mismatches), (15, 1));
+        assert_eq!(sites[1].three_prime_mismatches, 0);
+        assert!(!sites[1].terminal_mismatch);
+
+        let reverse = &sites[2];
+        assert_eq!((reverse.start, reverse.end), (30, 40));
+        assert_eq!(reverse.strand, Strand::Reverse);
+        assert_eq!(reverse.matched, "ACGTTGCAAG");
+        assert_eq!(reverse.three_prime_mismatches, 1);
+        assert!(reverse.terminal_mismatch);
+    }
+
+    #[test]
+    fn test_duplex_aligns_mismatches_with_stacking_energy() {
+        let service = PrimerSpecificityService::new();
+        let primer = "ACGTTGCAAC";
+        let sequence = format!("GG{}GG", iupac::reverse_complement("ACGATGCAAG"));
+
+        let duplex = service
+            .duplex(
+                "seq_1",
+                &sequence,
+                &Topology::Linear,
+                primer,
+                2,
+                Strand::Reverse,
+            )
+            .unwrap();
+        assert_eq!(duplex.primer_line, "ACGTTGCAAC");
+        assert_eq!(duplex.match_line, "||| ||||| ");
+        assert_eq!(duplex.template_line, "TGCTACGTTC");
+        assert_eq!(duplex.mismatch_positions, vec![3, 9]);
+        assert!(duplex.site.terminal_mismatch);
+        assert_eq!(duplex.site.three_prime_mismatches, 1);
+        // ミスマッチに接するスタッキングはパラメータがなく、離れた位置は WC の値
+        assert!(duplex.pairs[2].stack_delta_g.is_none());
+        assert!(duplex.pairs[5].stack_delta_g.unwrap() < 0.0);
+        assert!(duplex.pairs[9].stack_delta_g.is_none());
+
+        let perfect = service
+            .duplex(
+                "seq_1",
+                &format!("GG{}GG", primer),
+                &Topology::Linear,
+                primer,
+                2,
+                Strand::Forward,
+            )
+            .unwrap();
+        assert!(perfect.mismatch_positions.is_empty());
+        assert!(perfect.delta_g < duplex.delta_g);
+
+        assert!(matches!(
+            service.duplex(
+                "seq_1",
+                &sequence,
+                &Topology::Linear,
+                primer,
+                5,
+                Strand::Forward
+            ),
+            Err(DuplexError::OutOfRange(5, 15, 14))
+        ));
+    }
+}
@@ -0,0 +1,314 @@
+// Service layer: Fluorophore/quencher selection for multiplexed hydrolysis probes
+use crate::domain::probe_label::{
+    builtin_fluorophores, builtin_quenchers, Fluorophore, InstrumentChannel, LabelOption,
+    ProbeLabelInput, ProbeLabelParams, ProbeLabelPlan, ProbeLabelSuggestion, Quencher,
+};
+use crate::domain::warning::WarningCode;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProbeLabelError {
+    #[error("Unknown fluorophore '{1}' on probe {0}")]
+    UnknownFluorophore(String, String),
+}
+
+/// Probe labeling service
+pub struct ProbeLabelService {
+    fluorophores: Vec<Fluorophore>,
+    quenchers: Vec<Quencher>,
+}
+
+impl Default for ProbeLabelService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProbeLabelService {
+    pub fn new() -> Self {
+        Self {
+            fluorophores: builtin_fluorophores(),
+            quenchers: builtin_quenchers(),
+        }
+    }
+
+    /// 入力順に `multiplex` 本ずつを1反応とし、各プローブにチャネルと色素・クエンチャーの
+    /// 組み合わせを提案する。色素を指定したプローブはそのチャネルに固定し、同じ反応で
+    /// チャネルが重なる組を警告する。残りのプローブには空いているチャネルを波長順に割り当てる
+    pub fn suggest(
+        &self,
+        probes: &[ProbeLabelInput],
+        multiplex: usize,
+        params: &ProbeLabelParams,
+    ) -> Result<ProbeLabelPlan, ProbeLabelError> {
+        let requested = probes
+            .iter()
+            .map(|probe| {
+                probe
+                    .fluorophore
+                    .as_deref()
+                    .map(|name| {
+                        self.fluorophore(name).ok_or_else(|| {
+                            ProbeLabelError::UnknownFluorophore(
+                                probe.name.clone(),
+                                name.to_string(),
+                            )
+                        })
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<Option<&Fluorophore>>, _>>()?;
+
+        // 組み込みの色素のどれかを検出できるチャネル
+        let usable: Vec<&InstrumentChannel> = params
+            .channels
+            .iter()
+            .filter(|channel| self.fluorophores.iter().any(|dye| channel.detects(dye)))
+            .collect();
+
+        let mut suggestions = Vec::new();
+        let mut warnings = Vec::new();
+        for (index, (group, dyes)) in probes
+            .chunks(multiplex)
+            .zip(requested.chunks(multiplex))
+            .enumerate()
+        {
+            let reaction = index + 1;
+            if group.len() > usable.len() {
+                warnings.push(
+                    WarningCode::MultiplexExceedsChannels {
+                        reaction,
+                        probes: group.len(),
+                        channels: usable.len(),
+                    }
+                    .into(),
+                );
+            }
+
+            let mut assigned: Vec<Option<&InstrumentChannel>> = Vec::new();
+            for (probe, dye) in group.iter().zip(dyes) {
+                let channel = dye.and_then(|dye| {
+                    let channel = params.channels.iter().find(|channel| channel.detects(dye));
+                    if channel.is_none() {
+                        warnings.push(
+                            WarningCode::FluorophoreOutsideChannels {
+                                probe: probe.name.clone(),
+                                fluorophore: dye.name.clone(),
+                            }
+                            .into(),
+                        );
+                    }
+                    channel
+                });
+                assigned.push(channel);
+            }
+            for i in 0..group.len() {
+                for j in i + 1..group.len() {
+                    if let (Some(a), Some(b)) = (assigned[i], assigned[j]) {
+                        if a.name == b.name {
+                            warnings.push(
+                                WarningCode::ProbeChannelConflict {
+                                    reaction,
+                                    channel: a.name.clone(),
+                                    probe1: group[i].name.clone(),
+                                    probe2: group[j].name.clone(),
+                                }
+                                .into(),
+                            );
+                        }
+                    }
+                }
+            }
+
+            let mut free = usable
+                .iter()
+                .filter(|channel| {
+                    !assigned
+                        .iter()
+                        .flatten()
+                        .any(|used| used.name == channel.name)
+                })
+                .copied()
+                .collect::<Vec<_>>()
+                .into_iter();
+            for (channel, dye) in assigned.iter_mut().zip(dyes) {
+                if dye.is_none() {
+                    *channel = free.next();
+                }
+            }
+
+            let used: Vec<&InstrumentChannel> = assigned.iter().flatten().copied().collect();
+            for ((probe, dye), channel) in group.iter().zip(dyes).zip(&assigned) {
+                let candidates: Vec<&Fluorophore> = match (dye, channel) {
+                    (Some(dye), _) => vec![*dye],
+                    (None, Some(channel)) => self
+                        .fluorophores
+                        .iter()
+                        .filter(|dye| channel.detects(dye))
+                        .collect(),
+                    (None, None) => Vec::new(),
+                };
+                suggestions.push(ProbeLabelSuggestion {
+                    probe: probe.name.clone(),
+                    reaction,
+                    channel: channel.map(|channel| channel.name.clone()),
+                    options: self.options(
+                        &candidates,
+                        &used,
+                        probe.sequence.len() >= params.double_quench_length,
+                    ),
+                });
+            }
+        }
+
+        Ok(ProbeLabelPlan {
+            multiplex,
+            suggestions,
+            channels: params.channels.clone(),
+            warnings,
+        })
+    }
+
+    fn fluorophore(&self, name: &str) -> Option<&Fluorophore> {
+        self.fluorophores
+            .iter()
+            .find(|dye| dye.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// 色素の蛍光を吸収できるクエンチャーを、長いプローブでは二重消光、短いプローブでは
+    /// 単一消光を先に、ダーククエンチャー、吸収域の中心が蛍光極大に近い順に並べる。
+    /// 蛍光性クエンチャーは自身の蛍光が反応内で使うチャネルに入る場合は除く
+    fn options(
+        &self,
+        dyes: &[&Fluorophore],
+        used: &[&InstrumentChannel],
+        long_probe: bool,
+    ) -> Vec<LabelOption> {
+        let mut ranked: Vec<((bool, bool, f32), LabelOption)> = Vec::new();
+        for dye in dyes {
+            for quencher in &self.quenchers {
+                let bleeds = quencher.emission_nm.is_some_and(|emission| {
+                    used.iter()
+                        .any(|channel| channel.detects_emission(emission))
+                });
+                if !quencher.quenches(dye) || bleeds {
+                    continue;
+                }
+                let center = (quencher.absorption_min + quencher.absorption_max) / 2.0;
+                ranked.push((
+                    (
+                        quencher.double_quenched != long_probe,
+                        quencher.emission_nm.is_some(),
+                        (dye.emission_nm - center).abs(),
+                    ),
+                    LabelOption {
+                        fluorophore: dye.name.clone(),
+                        quencher: quencher.name.clone(),
+                        double_quenched: quencher.double_quenched,
+                    },
+                ));
+            }
+        }
+        ranked.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.into_iter().map(|(_, option)| option).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(name: &str, length: usize, fluorophore: Option<&str>) -> ProbeLabelInput {
+        ProbeLabelInput {
+            name: name.to_string(),
+            sequence: format!("{}{}", "ACGT".repeat(length / 4), "C".repeat(length % 4)),
+            fluorophore: fluorophore.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_assigns_free_channels_and_ranks_quenchers() {
+        let service = ProbeLabelService::new();
+        let probes = [probe("GAPDH", 20, Some("fam")), probe("ACTB", 30, None)];
+        let plan = service
+            .suggest(&probes, 2, &ProbeLabelParams::default())
+            .unwrap();
+        assert!(plan.warnings.is_empty());
+
+        let gapdh = &plan.suggestions[0];
+        assert_eq!(gapdh.channel.as_deref(), Some("FAM"));
+        assert!(gapdh.options.iter().all(|o| o.fluorophore == "FAM"));
+        assert_eq!(gapdh.options[0].quencher, "Iowa Black FQ");
+        assert!(!gapdh.options[0].double_quenched);
+
+        // 長いプローブは次に空いているチャネルで、二重消光を先に勧める
+        let actb = &plan.suggestions[1];
+        assert_eq!(actb.channel.as_deref(), Some("HEX"));
+        assert!(actb.options[0].double_quenched);
+        let dyes: Vec<&str> = actb
+            .options
+            .iter()
+            .map(|o| o.fluorophore.as_str())
+            .collect();
+        assert!(dyes.contains(&"HEX") && dyes.contains(&"VIC"));
+    }
+
+    #[test]
+    fn test_warns_on_shared_channels_and_overfull_reactions() {
+        let service = ProbeLabelService::new();
+        let probes = [
+            probe("A", 20, Some("HEX")),
+            probe("B", 20, Some("VIC")),
+            probe("C", 20, Some("Cy3")),
+            probe("D", 20, None),
+        ];
+        let plan = service
+            .suggest(&probes, 2, &ProbeLabelParams::default())
+            .unwrap();
+        assert_eq!(
+            plan.suggestions
+                .iter()
+                .map(|s| s.reaction)
+                .collect::<Vec<_>>(),
+            vec![1, 1, 2, 2]
+        );
+        assert!(matches!(
+            &plan.warnings[0].code,
+            WarningCode::ProbeChannelConflict { reaction: 1, channel, .. } if channel == "HEX"
+        ));
+        assert!(matches!(
+            &plan.warnings[1].code,
+            WarningCode::FluorophoreOutsideChannels { probe, .. } if probe == "C"
+        ));
+        assert_eq!(plan.suggestions[2].channel, None);
+        assert_eq!(plan.suggestions[3].channel.as_deref(), Some("FAM"));
+
+        let params = ProbeLabelParams {
+            channels: ProbeLabelParams::default().channels[..2].to_vec(),
+            ..ProbeLabelParams::default()
+        };
+        let plan = service.suggest(&probes[3..], 1, &params).unwrap();
+        assert!(plan.warnings.is_empty());
+        let crowded = [
+            probe("D", 20, None),
+            probe("E", 20, None),
+            probe("F", 20, None),
+        ];
+        let plan = service.suggest(&crowded, 3, &params).unwrap();
+        assert!(matches!(
+            plan.warnings[0].code,
+            WarningCode::MultiplexExceedsChannels {
+                probes: 3,
+                channels: 2,
+                ..
+            }
+        ));
+        assert_eq!(plan.suggestions[2].channel, None);
+        assert!(plan.suggestions[2].options.is_empty());
+
+        assert!(matches!(
+            service.suggest(&[probe("X", 20, Some("Alexa 488"))], 1, &params),
+            Err(ProbeLabelError::UnknownFluorophore(..))
+        ));
+    }
+}
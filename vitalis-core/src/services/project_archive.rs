@@ -0,0 +1,306 @@
+// Service layer: Primer lists, HTML reports and manifest for project archives
+use crate::domain::export_format::{ExportFormat, Unit};
+use crate::domain::feature::SequenceFeature;
+use crate::domain::primer::{Primer, PrimerPair};
+use crate::domain::{SequenceMetadata, StatsService, Strand, Topology};
+use crate::services::StatsServiceImpl;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+/// Project archive content service
+pub struct ProjectArchiveService {
+    stats: StatsServiceImpl,
+}
+
+impl Default for ProjectArchiveService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectArchiveService {
+    pub fn new() -> Self {
+        Self {
+            stats: StatsServiceImpl::new(),
+        }
+    }
+
+    /// ファイル名に使える配列名（英数字と `-_.` 以外は `_`）。`taken` と重複する場合は連番を付ける
+    pub fn file_stem(&self, metadata: &SequenceMetadata, taken: &mut HashSet<String>) -> String {
+        let name = if metadata.name.trim().is_empty() {
+            &metadata.id
+        } else {
+            &metadata.name
+        };
+        let base: String = name
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || "-_.".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        let mut stem = base.clone();
+        let mut suffix = 2;
+        while !taken.insert(stem.to_lowercase()) {
+            stem = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        stem
+    }
+
+    /// プライマー一覧（1ペアにつき forward・reverse の2行）
+    pub fn primer_csv(&self, pairs: &[PrimerPair], format: &ExportFormat) -> String {
+        let header = [
+            "pair_id".to_string(),
+            "target_gene".to_string(),
+            "primer".to_string(),
+            "sequence".to_string(),
+            "position".to_string(),
+            format.header("length", Unit::BasePairs),
+            format.header("tm", Unit::Celsius),
+            format.header("gc_content", Unit::Percent),
+            format.header("amplicon_length", Unit::BasePairs),
+            "tags".to_string(),
+        ];
+        let mut out = format.csv_row(&header);
+        out.push('\n');
+        for pair in pairs {
+            for (role, primer) in [("forward", &pair.forward), ("reverse", &pair.reverse)] {
+                out.push_str(&primer_row(pair, role, primer, format));
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// 配列ごとの解析レポート（組成統計とアノテーション一覧）
+    pub fn report_html(
+        &self,
+        metadata: &SequenceMetadata,
+        sequence: &str,
+        features: &[SequenceFeature],
+        created_at: DateTime<Utc>,
+        format: &ExportFormat,
+    ) -> String {
+        let stats = self.stats.calculate_detailed_stats(sequence);
+        let topology = match metadata.topology {
+            Topology::Linear => "linear",
+            Topology::Circular => "circular",
+        };
+        let title = escape_html(&metadata.name);
+
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", title));
+        out.push_str(
+            "<style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse}\
+             td,th{border:1px solid #ccc;padding:2px 8px;text-align:left}</style>\n",
+        );
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!("<h1>{}</h1>\n", title));
+        out.push_str(&format!(
+            "<p>{} &middot; generated {}</p>\n",
+            escape_html(&metadata.id),
+            created_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+
+        out.push_str("<h2>Composition</h2>\n<table>\n");
+        let rows = [
+            (
+                "Length",
+                format.quantity(stats.length as f64, Unit::BasePairs),
+            ),
+            ("Topology", topology.to_string()),
+            ("GC", format.quantity(stats.gc_percent, Unit::Percent)),
+            ("AT", format.quantity(stats.at_percent, Unit::Percent)),
+            ("N", format.quantity(stats.n_percent, Unit::Percent)),
+            (
+                "GC skew",
+                format.quantity(stats.gc_skew, Unit::Dimensionless),
+            ),
+            (
+                "AT skew",
+                format.quantity(stats.at_skew, Unit::Dimensionless),
+            ),
+            (
+                "Entropy",
+                format.quantity(stats.entropy, Unit::Dimensionless),
+            ),
+        ];
+        for (name, value) in rows {
+            out.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", name, value));
+        }
+        out.push_str("</table>\n");
+
+        out.push_str(&format!("<h2>Features ({})</h2>\n", features.len()));
+        if !features.is_empty() {
+            out.push_str(
+                "<table>\n<tr><th>Type</th><th>Label</th><th>Start</th><th>End</th>\
+                 <th>Strand</th><th>Length</th></tr>\n",
+            );
+            let mut sorted: Vec<&SequenceFeature> = features.iter().collect();
+            sorted.sort_by_key(|f| (f.start, f.end));
+            for feature in sorted {
+                let length = if feature.segments.is_empty() {
+                    feature.end - feature.start
+                } else {
+                    feature.segments.iter().map(|seg| seg.len()).sum()
+                };
+                out.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape_html(&feature.feature_type),
+                    escape_html(&feature.label()),
+                    feature.start + 1,
+                    feature.end,
+                    match feature.strand {
+                        Strand::Forward => "+",
+                        Strand::Reverse => "-",
+                    },
+                    length
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn primer_row(pair: &PrimerPair, role: &str, primer: &Primer, format: &ExportFormat) -> String {
+    format.csv_row(&[
+        pair.id.clone(),
+        pair.target_gene.clone().unwrap_or_default(),
+        role.to_string(),
+        primer.sequence.clone(),
+        primer.position.to_string(),
+        primer.length.to_string(),
+        format.number(primer.tm as f64, Unit::Celsius),
+        format.number(primer.gc_content as f64, Unit::Percent),
+        pair.amplicon_length.to_string(),
+        pair.tags.join(";"),
+    ])
+}
+
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::primer::{PrimerDirection, ValidationResults};
+
+    fn primer(sequence: &str, position: usize, direction: PrimerDirection) -> Primer {
+        Primer {
+            sequence: sequence.to_string(),
+            position,
+            length: sequence.len(),
+            tm: 60.04,
+            gc_content: 50.0,
+            self_dimer_score: 0.0,
+            hairpin_score: 0.0,
+            three_prime_stability: 0.0,
+            direction,
+            quality_score: 100.0,
+            quality_warnings: Vec::new(),
+            provenance: None,
+        }
+    }
+
+    fn metadata(id: &str, name: &str) -> SequenceMetadata {
+        SequenceMetadata {
+            id: id.to_string(),
+            name: name.to_string(),
+            length: 0,
+            topology: Topology::Linear,
+            file_path: None,
+            derived_from: None,
+        }
+    }
+
+    #[test]
+    fn test_primer_csv_and_file_stems() {
+        let service = ProjectArchiveService::new();
+        let pair = PrimerPair {
+            id: "pair_1".to_string(),
+            forward: primer("ACGTACGTACGTACGTACGT", 10, PrimerDirection::Forward),
+            reverse: primer("TTGCAATTGCAATTGCAATT", 190, PrimerDirection::Reverse),
+            amplicon_length: 200,
+            amplicon_sequence: String::new(),
+            target_gene: Some("lacZ, alpha".to_string()),
+            target_transcript: None,
+            compatibility_score: 0.0,
+            created_by: "test".to_string(),
+            created_at: Utc::now(),
+            tags: vec!["qPCR".to_string(), "panel".to_string()],
+            validation_results: ValidationResults::new(),
+        };
+        let csv = service.primer_csv(std::slice::from_ref(&pair), &ExportFormat::default());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "pair_id,target_gene,primer,sequence,position,length (bp),tm (°C),\
+             gc_content (%),amplicon_length (bp),tags"
+        );
+        assert_eq!(
+            lines[1],
+            "pair_1,\"lacZ, alpha\",forward,ACGTACGTACGTACGTACGT,10,20,60.0,50.0,200,qPCR;panel"
+        );
+        assert!(lines[2].contains(",reverse,TTGCAATTGCAATTGCAATT,190,"));
+
+        // ロケールで小数点がカンマになる環境向けの区切り
+        let semicolons = ExportFormat {
+            delimiter: ';',
+            temperature_precision: 2,
+            ..ExportFormat::default()
+        };
+        let csv = service.primer_csv(&[pair], &semicolons);
+        assert!(csv
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("pair_1;lacZ, alpha;forward;ACGTACGTACGTACGTACGT;10;20;60.04;50.0;"));
+
+        let mut taken = HashSet::new();
+        assert_eq!(
+            service.file_stem(&metadata("seq_1", "pUC19 <mod>"), &mut taken),
+            "pUC19__mod_"
+        );
+        assert_eq!(
+            service.file_stem(&metadata("seq_2", "pUC19 <mod>"), &mut taken),
+            "pUC19__mod__2"
+        );
+        assert_eq!(
+            service.file_stem(&metadata("seq_3", " "), &mut taken),
+            "seq_3"
+        );
+    }
+
+    #[test]
+    fn test_report_lists_features() {
+        let mut feature = SequenceFeature::new("CDS", 0, 9, Strand::Reverse);
+        feature
+            .qualifiers
+            .insert("gene".to_string(), "a<b>".to_string());
+        let html = ProjectArchiveService::new().report_html(
+            &metadata("seq_1", "Test & Co"),
+            "ATGGCCTAA",
+            &[feature],
+            Utc::now(),
+            &ExportFormat::default(),
+        );
+        assert!(html.contains("<title>Test &amp; Co</title>"));
+        assert!(html.contains("<tr><th>Length</th><td>9 bp</td></tr>"));
+        assert!(html.contains("<tr><td>CDS</td><td>a&lt;b&gt;</td><td>1</td><td>9</td><td>-</td>"));
+    }
+}
@@ -0,0 +1,191 @@
+// Service layer: PROSITE-style protein motif scanning
+use crate::domain::orf::Orf;
+use crate::domain::protein_motif::{bundled_motifs, MotifHit, ProteinMotif};
+use crate::services::SignalPeptideService;
+use regex::Regex;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProteinMotifError {
+    #[error("Invalid PROSITE pattern '{0}': {1}")]
+    InvalidPattern(String, String),
+}
+
+/// Protein motif scanning service
+pub struct ProteinMotifService {
+    motifs: Vec<(ProteinMotif, Regex)>,
+}
+
+impl ProteinMotifService {
+    /// 組み込みモチーフで初期化
+    pub fn new() -> Self {
+        Self::with_motifs(bundled_motifs()).expect("bundled motifs are valid")
+    }
+
+    pub fn with_motifs(motifs: Vec<ProteinMotif>) -> Result<Self, ProteinMotifError> {
+        let compiled = motifs
+            .into_iter()
+            .map(|motif| {
+                let regex = prosite_to_regex(&motif.pattern).and_then(|re| {
+                    Regex::new(&re).map_err(|e| {
+                        ProteinMotifError::InvalidPattern(motif.pattern.clone(), e.to_string())
+                    })
+                })?;
+                Ok((motif, regex))
+            })
+            .collect::<Result<Vec<_>, ProteinMotifError>>()?;
+        Ok(Self { motifs: compiled })
+    }
+
+    /// タンパク質配列をスキャン（N末端のシグナルペプチドも予測して含める）
+    pub fn scan_protein(&self, protein: &str) -> Vec<MotifHit> {
+        let protein = protein.to_ascii_uppercase();
+        let mut hits: Vec<MotifHit> = self
+            .motifs
+            .iter()
+            .flat_map(|(motif, regex)| {
+                regex.find_iter(&protein).map(|m| MotifHit {
+                    motif: motif.name.clone(),
+                    start: m.start(),
+                    end: m.end(),
+                    matched: m.as_str().to_string(),
+                    dna_start: None,
+                    dna_end: None,
+                    strand: None,
+                })
+            })
+            .collect();
+
+        if let Some(end) = SignalPeptideService::new()
+            .predict(&protein)
+            .map(|prediction| prediction.cleavage_site)
+        {
+            hits.push(MotifHit {
+                motif: "Signal peptide".to_string(),
+                start: 0,
+                end,
+                matched: protein[..end].to_string(),
+                dna_start: None,
+                dna_end: None,
+                strand: None,
+            });
+        }
+
+        hits.sort_by_key(|hit| (hit.start, hit.end));
+        hits
+    }
+
+    /// ORFの翻訳産物をスキャンし、ヒットを塩基配列上の座標にも対応付ける
+    pub fn scan_orfs(&self, orfs: &[Orf]) -> Vec<MotifHit> {
+        orfs.iter()
+            .flat_map(|orf| {
+                self.scan_protein(&orf.protein)
+                    .into_iter()
+                    .map(move |mut hit| {
+                        let (dna_start, dna_end) = orf.dna_range(hit.start, hit.end);
+                        hit.dna_start = Some(dna_start);
+                        hit.dna_end = Some(dna_end);
+                        hit.strand = Some(orf.strand);
+                        hit
+                    })
+            })
+            .collect()
+    }
+}
+
+impl Default for ProteinMotifService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// PROSITEパターンを正規表現に変換
+pub fn prosite_to_regex(pattern: &str) -> Result<String, ProteinMotifError> {
+    let invalid =
+        |reason: &str| ProteinMotifError::InvalidPattern(pattern.to_string(), reason.to_string());
+
+    let body = pattern.trim().trim_end_matches('.');
+    if body.is_empty() {
+        return Err(invalid("empty pattern"));
+    }
+
+    let mut regex = String::new();
+    for element in body.split('-') {
+        let mut element = element.trim();
+        if let Some(rest) = element.strip_prefix('<') {
+            regex.push('^');
+            element = rest;
+        }
+        let anchored_end = element.ends_with('>');
+        let element = element.trim_end_matches('>');
+
+        let (core, repeat) = match element.find('(') {
+            Some(open) => {
+                let repeat = element[open + 1..]
+                    .strip_suffix(')')
+                    .ok_or_else(|| invalid("unclosed repetition"))?;
+                if repeat.is_empty() || !repeat.chars().all(|c| c.is_ascii_digit() || c == ',') {
+                    return Err(invalid("malformed repetition"));
+                }
+                (&element[..open], Some(repeat))
+            }
+            None => (element, None),
+        };
+
+        let residues = |inner: &str| {
+            if !inner.is_empty() && inner.chars().all(|c| c.is_ascii_uppercase()) {
+                Ok(inner.to_string())
+            } else {
+                Err(invalid("invalid residue class"))
+            }
+        };
+        let core = if core.eq_ignore_ascii_case("x") {
+            ".".to_string()
+        } else if let Some(inner) = core.strip_prefix('[').and_then(|c| c.strip_suffix(']')) {
+            format!("[{}]", residues(inner)?)
+        } else if let Some(inner) = core.strip_prefix('{').and_then(|c| c.strip_suffix('}')) {
+            format!("[^{}]", residues(inner)?)
+        } else if core.len() == 1 {
+            residues(core)?
+        } else {
+            return Err(invalid("unrecognized element"));
+        };
+
+        regex.push_str(&core);
+        if let Some(repeat) = repeat {
+            regex.push_str(&format!("{{{}}}", repeat));
+        }
+        if anchored_end {
+            regex.push('$');
+        }
+    }
+    Ok(regex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prosite_conversion() {
+        assert_eq!(
+            prosite_to_regex("C-x(2,4)-C-x(3)-[LIVMFYWC]-x(8)-H-x(3,5)-H.").unwrap(),
+            "C.{2,4}C.{3}[LIVMFYWC].{8}H.{3,5}H"
+        );
+        assert_eq!(prosite_to_regex("<M-{P}-[ST]>").unwrap(), "^M[^P][ST]$");
+        assert!(prosite_to_regex("C-x(2").is_err());
+        assert!(prosite_to_regex("C-[12]").is_err());
+    }
+
+    #[test]
+    fn test_scan_tags_and_signal_peptide() {
+        let protein = "MKKLLLALLVAGSAQADYKDDDDKGSHHHHHHNGTS";
+        let hits = ProteinMotifService::new().scan_protein(protein);
+        let names: Vec<&str> = hits.iter().map(|h| h.motif.as_str()).collect();
+
+        assert!(names.contains(&"Signal peptide"));
+        assert!(names.contains(&"FLAG"));
+        assert!(names.contains(&"His-tag"));
+        assert!(names.contains(&"N-glycosylation"));
+    }
+}
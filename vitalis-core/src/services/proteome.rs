@@ -0,0 +1,214 @@
+// Service layer: Proteome extraction (annotated CDSs and unannotated ORFs)
+use crate::domain::feature::SequenceFeature;
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::iupac;
+use crate::domain::protein::{ProteinRecord, ProteinSource};
+use crate::domain::{Range, Strand};
+use crate::services::OrfService;
+
+/// FASTA の1行あたりの残基数
+const FASTA_LINE_WIDTH: usize = 60;
+
+/// Proteome extraction service
+pub struct ProteomeService {
+    orf_service: OrfService,
+}
+
+impl Default for ProteomeService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProteomeService {
+    pub fn new() -> Self {
+        Self {
+            orf_service: OrfService::new(),
+        }
+    }
+
+    /// CDS アノテーションを翻訳し、どの CDS とも終止コドンを共有しない ORF
+    /// （`min_orf_length` アミノ酸以上）を加える。CDS、ORF の順にそれぞれ位置順で並べ、
+    /// ID は `name` に通し番号を付ける
+    pub fn collect(
+        &self,
+        name: &str,
+        sequence: &str,
+        features: &[SequenceFeature],
+        min_orf_length: usize,
+    ) -> Vec<ProteinRecord> {
+        let cds: Vec<&SequenceFeature> = features
+            .iter()
+            .filter(|feature| feature.feature_type == "CDS")
+            .collect();
+
+        let mut annotated: Vec<ProteinRecord> = cds
+            .iter()
+            .filter_map(|feature| {
+                Some(ProteinRecord {
+                    id: String::new(),
+                    source: ProteinSource::AnnotatedCds,
+                    start: feature.start,
+                    end: feature.end,
+                    strand: feature.strand,
+                    product: feature.qualifiers.get("product").cloned(),
+                    protein: translate_cds(sequence, feature)?,
+                })
+            })
+            .collect();
+        annotated.sort_by_key(|record| (record.start, record.end));
+
+        let shares_stop = |start: usize, end: usize, strand: Strand| {
+            cds.iter().any(|feature| {
+                feature.strand == strand
+                    && match strand {
+                        Strand::Forward => feature.end == end,
+                        Strand::Reverse => feature.start == start,
+                    }
+            })
+        };
+        let predicted = self
+            .orf_service
+            .find_orfs(sequence, min_orf_length)
+            .into_iter()
+            .filter(|orf| !shares_stop(orf.start, orf.end, orf.strand))
+            .map(|orf| ProteinRecord {
+                id: String::new(),
+                source: ProteinSource::PredictedOrf,
+                start: orf.start,
+                end: orf.end,
+                strand: orf.strand,
+                product: None,
+                protein: orf.protein,
+            });
+
+        let mut records: Vec<ProteinRecord> = annotated.into_iter().chain(predicted).collect();
+        for (n, record) in records.iter_mut().enumerate() {
+            let kind = match record.source {
+                ProteinSource::AnnotatedCds => "cds",
+                ProteinSource::PredictedOrf => "orf",
+            };
+            record.id = format!("{}_{}{}", name, kind, n + 1);
+        }
+        records
+    }
+
+    /// タンパク質マルチ FASTA（ヘッダーに産物名、1-based の位置と鎖、由来を書く）
+    pub fn fasta(&self, records: &[ProteinRecord]) -> String {
+        let mut text = String::new();
+        for record in records {
+            let location = format!("{}..{}", record.start + 1, record.end);
+            let location = match record.strand {
+                Strand::Forward => location,
+                Strand::Reverse => format!("complement({})", location),
+            };
+            let source = match record.source {
+                ProteinSource::AnnotatedCds => "CDS",
+                ProteinSource::PredictedOrf => "ORF",
+            };
+            text.push_str(&format!(
+                ">{} {} [location={}] [source={}]\n",
+                record.id,
+                record.product.as_deref().unwrap_or("hypothetical protein"),
+                location,
+                source
+            ));
+            for line in record.protein.as_bytes().chunks(FASTA_LINE_WIDTH) {
+                text.push_str(&String::from_utf8_lossy(line));
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+/// CDS の翻訳産物（/translation があればそれを使う。/codon_start と /transl_table に従う）
+fn translate_cds(sequence: &str, cds: &SequenceFeature) -> Option<String> {
+    if let Some(translation) = cds.qualifiers.get("translation") {
+        return Some(translation.split_whitespace().collect());
+    }
+
+    let mut exons = if cds.segments.is_empty() {
+        vec![Range::new(cds.start, cds.end)]
+    } else {
+        cds.segments.clone()
+    };
+    exons.sort_by_key(|exon| exon.start);
+    if exons.last()?.end > sequence.len() {
+        return None;
+    }
+    let spliced: String = exons
+        .iter()
+        .map(|exon| &sequence[exon.start..exon.end])
+        .collect();
+    let coding = match cds.strand {
+        Strand::Forward => spliced.to_ascii_uppercase(),
+        Strand::Reverse => iupac::reverse_complement(&spliced.to_ascii_uppercase()),
+    };
+
+    let codon_start: usize = cds
+        .qualifiers
+        .get("codon_start")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(1);
+    let code = cds
+        .qualifiers
+        .get("transl_table")
+        .and_then(|value| value.parse().ok())
+        .and_then(GeneticCode::by_id)
+        .unwrap_or_else(GeneticCode::standard);
+
+    let coding = coding.get(codon_start.saturating_sub(1)..)?;
+    let mut protein = code.translate(coding);
+    if protein.ends_with('*') {
+        protein.pop();
+    }
+    // 完全な CDS の開始コドンは代替開始コドンでも Met
+    if codon_start == 1 && code.is_start(&coding.as_bytes()[..coding.len().min(3)]) {
+        protein.replace_range(..1, "M");
+    }
+    (!protein.is_empty()).then_some(protein)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotated_cds_and_unannotated_orfs() {
+        // 0..12: ATG GCC GCC TAA（CDS）、15..27: 逆鎖の ATG AAA CCC TGA（ORF）
+        let sequence = "ATGGCCGCCTAAGGGTCAGGGTTTCATGG";
+        let mut cds = SequenceFeature::new("CDS", 0, 12, Strand::Forward);
+        cds.qualifiers
+            .insert("product".to_string(), "tiny protein".to_string());
+
+        let service = ProteomeService::new();
+        let records = service.collect("pTest", sequence, &[cds], 3);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "pTest_cds1");
+        assert_eq!(records[0].protein, "MAA");
+        assert_eq!(records[1].source, ProteinSource::PredictedOrf);
+        assert_eq!((records[1].start, records[1].end), (15, 27));
+        assert_eq!(records[1].protein, "MKP");
+
+        let fasta = service.fasta(&records);
+        assert_eq!(
+            fasta,
+            ">pTest_cds1 tiny protein [location=1..12] [source=CDS]\nMAA\n\
+             >pTest_orf2 hypothetical protein [location=complement(16..27)] [source=ORF]\nMKP\n"
+        );
+    }
+
+    #[test]
+    fn test_spliced_reverse_cds_with_codon_start() {
+        // 逆鎖の CDS: exon 2..8 と 11..18、codon_start=2 で先頭1塩基を飛ばす
+        let coding = "CATGAAACCCTAA";
+        let rc = iupac::reverse_complement(coding);
+        let sequence = format!("GG{}TTT{}GG", &rc[..6], &rc[6..]);
+        let mut cds = SequenceFeature::new("CDS", 2, 18, Strand::Reverse);
+        cds.segments = vec![Range::new(11, 18), Range::new(2, 8)];
+        cds.qualifiers
+            .insert("codon_start".to_string(), "2".to_string());
+        assert_eq!(translate_cds(&sequence, &cds).as_deref(), Some("MKP"));
+    }
+}
@@ -0,0 +1,355 @@
+// Service layer: qPCR (TaqMan) assay design — primer pair plus internal hydrolysis probe
+use crate::domain::primer::{
+    Primer, PrimerDesignService, PrimerDirection, QpcrAssay, QpcrAssayParams, ValidationResults,
+};
+use crate::domain::warning::WarningCode;
+use crate::services::PrimerDesignServiceImpl;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum QpcrAssayError {
+    #[error("Region {0}..{1} is shorter than the minimum amplicon length {2}")]
+    RegionTooShort(usize, usize, usize),
+    #[error("No qPCR assay in {0}..{1} satisfies the primer, probe and amplicon constraints")]
+    NoAssay(usize, usize),
+}
+
+/// Tm/GCフィルタを通過したオリゴ候補（位置は領域内の0-based）
+struct Site {
+    position: usize,
+    length: usize,
+    tm: f32,
+    direction: PrimerDirection,
+}
+
+impl Site {
+    fn end(&self) -> usize {
+        self.position + self.length
+    }
+}
+
+/// qPCR assay design service
+pub struct QpcrAssayService {
+    primer_service: PrimerDesignServiceImpl,
+}
+
+impl Default for QpcrAssayService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QpcrAssayService {
+    pub fn new() -> Self {
+        Self {
+            primer_service: PrimerDesignServiceImpl::new(),
+        }
+    }
+
+    /// [start, end) の中に増幅産物全体が収まるアッセイを設計する。Tm が最適値に近く
+    /// 揃ったプライマーペアから順に、間にプライマーより 8–10 °C 高い Tm のプローブ
+    /// （5'末端が G でなく、G の4連続を含まないもの）を探し、3本のオリゴ間の
+    /// ヘテロダイマーが閾値を超えない最初の組み合わせを返す
+    pub fn design(
+        &self,
+        template: &str,
+        start: usize,
+        end: usize,
+        params: &QpcrAssayParams,
+    ) -> Result<QpcrAssay, QpcrAssayError> {
+        if end - start < params.amplicon_min {
+            return Err(QpcrAssayError::RegionTooShort(
+                start,
+                end,
+                params.amplicon_min,
+            ));
+        }
+        let region = template[start..end].to_ascii_uppercase();
+        let primer = &params.primer;
+
+        let mut forward_sites = Vec::new();
+        let mut reverse_sites = Vec::new();
+        for length in primer.length_min..=primer.length_max.min(region.len()) {
+            for position in 0..=region.len() - length {
+                let forward = &region[position..position + length];
+                let reverse = self.primer_service.reverse_complement(forward);
+                for (sequence, direction, sites) in [
+                    (forward, PrimerDirection::Forward, &mut forward_sites),
+                    (&reverse, PrimerDirection::Reverse, &mut reverse_sites),
+                ] {
+                    let tm = self.primer_service.calculate_tm(sequence);
+                    let gc = self.primer_service.calculate_gc_content(sequence);
+                    if (primer.tm_min..=primer.tm_max).contains(&tm)
+                        && (primer.gc_min..=primer.gc_max).contains(&gc)
+                    {
+                        sites.push(Site {
+                            position,
+                            length,
+                            tm,
+                            direction,
+                        });
+                    }
+                }
+            }
+        }
+
+        // 開始位置ごとのプローブ候補
+        let probes_at: Vec<Vec<Site>> = (0..region.len())
+            .map(|position| {
+                (params.probe_length_min..=params.probe_length_max)
+                    .take_while(|length| position + length <= region.len())
+                    .filter_map(|length| self.probe_site(&region, position, length, params))
+                    .collect()
+            })
+            .collect();
+
+        let mut pairs: Vec<(f32, &Site, &Site)> = Vec::new();
+        for forward in &forward_sites {
+            for reverse in &reverse_sites {
+                let amplicon = reverse.end().saturating_sub(forward.position);
+                if amplicon < params.amplicon_min
+                    || amplicon > params.amplicon_max
+                    || reverse.position < forward.end() + params.probe_length_min
+                    || (forward.tm - reverse.tm).abs() > params.max_primer_tm_difference
+                {
+                    continue;
+                }
+                let penalty = (forward.tm - primer.tm_optimal).abs()
+                    + (reverse.tm - primer.tm_optimal).abs()
+                    + (forward.tm - reverse.tm).abs();
+                pairs.push((penalty, forward, reverse));
+            }
+        }
+        pairs.sort_by(|(p1, f1, r1), (p2, f2, r2)| {
+            p1.partial_cmp(p2)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then((r1.end() - f1.position).cmp(&(r2.end() - f2.position)))
+        });
+
+        for (_, forward, reverse) in pairs {
+            let forward = self.oligo(&region, forward, start);
+            let reverse = self.oligo(&region, reverse, start);
+            if self
+                .primer_service
+                .calculate_hetero_dimer(&forward.sequence, &reverse.sequence)
+                < primer.max_hetero_dimer
+            {
+                continue;
+            }
+
+            let primer_tm = (forward.tm + reverse.tm) / 2.0;
+            let target_tm =
+                primer_tm + (params.probe_tm_offset_min + params.probe_tm_offset_max) / 2.0;
+            let inner_start = forward.position + forward.length - start;
+            let inner_end = reverse.position - start;
+            let mut probes: Vec<&Site> = probes_at[inner_start..inner_end]
+                .iter()
+                .flatten()
+                .filter(|probe| {
+                    let offset = probe.tm - primer_tm;
+                    probe.end() <= inner_end
+                        && offset >= params.probe_tm_offset_min
+                        && offset <= params.probe_tm_offset_max
+                })
+                .collect();
+            probes.sort_by(|a, b| {
+                (a.tm - target_tm)
+                    .abs()
+                    .partial_cmp(&(b.tm - target_tm).abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            for probe in probes {
+                let probe = self.oligo(&region, probe, start);
+                let dimers_ok = [&forward, &reverse].iter().all(|primer| {
+                    self.primer_service
+                        .calculate_hetero_dimer(&primer.sequence, &probe.sequence)
+                        >= params.primer.max_hetero_dimer
+                });
+                if dimers_ok {
+                    return Ok(self.assemble(template, forward, reverse, probe, params));
+                }
+            }
+        }
+        Err(QpcrAssayError::NoAssay(start, end))
+    }
+
+    /// 5'末端が G でなく G の4連続を含まない鎖の配列をプローブ候補にする（両鎖とも
+    /// 使える場合は C が G より多い鎖を選ぶ）
+    fn probe_site(
+        &self,
+        region: &str,
+        position: usize,
+        length: usize,
+        params: &QpcrAssayParams,
+    ) -> Option<Site> {
+        let forward = &region[position..position + length];
+        let gc = self.primer_service.calculate_gc_content(forward);
+        if !(params.probe_gc_min..=params.probe_gc_max).contains(&gc) || forward.contains("GGGG") {
+            return None;
+        }
+        let reverse = self.primer_service.reverse_complement(forward);
+        if reverse.contains("GGGG") {
+            return None;
+        }
+        let usable = |sequence: &str| !sequence.starts_with('G');
+        let c_rich = |sequence: &str| sequence.matches('C').count() > sequence.matches('G').count();
+        let (sequence, direction) = match (usable(forward), usable(&reverse)) {
+            (true, true) if !c_rich(forward) && c_rich(&reverse) => {
+                (reverse.as_str(), PrimerDirection::Reverse)
+            }
+            (true, _) => (forward, PrimerDirection::Forward),
+            (false, true) => (reverse.as_str(), PrimerDirection::Reverse),
+            (false, false) => return None,
+        };
+        Some(Site {
+            position,
+            length,
+            tm: self.primer_service.calculate_tm(sequence),
+            direction,
+        })
+    }
+
+    /// 候補を評価済みのオリゴにする（位置はテンプレート上に戻す）
+    fn oligo(&self, region: &str, site: &Site, offset: usize) -> Primer {
+        let bases = &region[site.position..site.end()];
+        let sequence = match site.direction {
+            PrimerDirection::Forward => bases.to_string(),
+            PrimerDirection::Reverse => self.primer_service.reverse_complement(bases),
+        };
+        self.primer_service.evaluate_primer(
+            sequence,
+            site.position + offset,
+            site.direction.clone(),
+        )
+    }
+
+    fn assemble(
+        &self,
+        template: &str,
+        forward: Primer,
+        reverse: Primer,
+        probe: Primer,
+        params: &QpcrAssayParams,
+    ) -> QpcrAssay {
+        let oligos = [
+            ("Forward primer", &forward),
+            ("Reverse primer", &reverse),
+            ("Probe", &probe),
+        ];
+        let mut validation = ValidationResults::new();
+        validation.self_dimer_check = oligos
+            .iter()
+            .all(|(_, oligo)| oligo.self_dimer_score >= params.primer.max_self_dimer);
+        validation.hairpin_check = oligos
+            .iter()
+            .all(|(_, oligo)| oligo.hairpin_score >= params.primer.max_hairpin);
+        // 設計時に3組すべてのヘテロダイマーを確認している
+        validation.hetero_dimer_check = Some(true);
+        for (name, oligo) in oligos {
+            if oligo.self_dimer_score < params.primer.max_self_dimer {
+                validation.warnings.push(
+                    WarningCode::OligoSelfDimer {
+                        oligo: name.to_string(),
+                        delta_g: oligo.self_dimer_score,
+                        threshold: params.primer.max_self_dimer,
+                    }
+                    .into(),
+                );
+            }
+            if oligo.hairpin_score < params.primer.max_hairpin {
+                validation.warnings.push(
+                    WarningCode::OligoHairpin {
+                        oligo: name.to_string(),
+                        delta_g: oligo.hairpin_score,
+                        threshold: params.primer.max_hairpin,
+                    }
+                    .into(),
+                );
+            }
+        }
+        let (g, c) = (
+            probe.sequence.matches('G').count(),
+            probe.sequence.matches('C').count(),
+        );
+        if g > c {
+            validation
+                .warnings
+                .push(WarningCode::ProbeMoreGThanC { g, c }.into());
+        }
+
+        let amplicon_start = forward.position;
+        let amplicon_end = reverse.position + reverse.length;
+        QpcrAssay {
+            probe_tm_offset: probe.tm - (forward.tm + reverse.tm) / 2.0,
+            amplicon_sequence: template[amplicon_start..amplicon_end].to_string(),
+            amplicon_start,
+            amplicon_end,
+            forward,
+            reverse,
+            probe,
+            validation_results: validation,
+            params: params.clone(),
+            provenance: Some(self.primer_service.provenance()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::reproducibility::SplitMix64;
+
+    fn random_template(length: usize, seed: u64) -> String {
+        let mut rng = SplitMix64::new(seed);
+        (0..length).map(|_| b"ACGT"[rng.below(4)] as char).collect()
+    }
+
+    #[test]
+    fn test_assay_meets_taqman_constraints() {
+        let service = QpcrAssayService::new();
+        let mut params = QpcrAssayParams::default();
+        params.primer.tm_min = 62.0;
+        params.primer.tm_max = 70.0;
+        params.primer.tm_optimal = 66.0;
+        let template = random_template(400, 7);
+
+        let assay = service.design(&template, 50, 350, &params).unwrap();
+        let amplicon_length = assay.amplicon_end - assay.amplicon_start;
+        assert!((70..=150).contains(&amplicon_length));
+        assert!(assay.amplicon_start >= 50 && assay.amplicon_end <= 350);
+        assert_eq!(
+            assay.amplicon_sequence,
+            template[assay.amplicon_start..assay.amplicon_end]
+        );
+        assert!((assay.forward.tm - assay.reverse.tm).abs() <= 2.0);
+        assert!((8.0..=10.0).contains(&assay.probe_tm_offset));
+
+        // プローブはプライマーの間にあり、5'末端は G ではない
+        let probe = &assay.probe;
+        assert!(probe.position >= assay.forward.position + assay.forward.length);
+        assert!(probe.position + probe.length <= assay.reverse.position);
+        assert!(!probe.sequence.starts_with('G'));
+        let bases = &template[probe.position..probe.position + probe.length];
+        let expected = match probe.direction {
+            PrimerDirection::Forward => bases.to_string(),
+            PrimerDirection::Reverse => service.primer_service.reverse_complement(bases),
+        };
+        assert_eq!(probe.sequence, expected);
+        assert_eq!(assay.validation_results.hetero_dimer_check, Some(true));
+    }
+
+    #[test]
+    fn test_rejects_short_or_unsuitable_region() {
+        let service = QpcrAssayService::new();
+        let params = QpcrAssayParams::default();
+        assert!(matches!(
+            service.design(&"ACGT".repeat(50), 0, 60, &params),
+            Err(QpcrAssayError::RegionTooShort(0, 60, 70))
+        ));
+        assert!(matches!(
+            service.design(&"A".repeat(200), 0, 200, &params),
+            Err(QpcrAssayError::NoAssay(0, 200))
+        ));
+    }
+}
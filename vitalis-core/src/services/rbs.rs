@@ -0,0 +1,134 @@
+// Service layer: Ribosome binding site strength (SD complementarity + spacing model)
+use crate::domain::rbs::{RbsParams, RbsPrediction, OPTIMAL_SPACING, SD_CONSENSUS};
+use crate::domain::thermodynamic_calculator::ThermodynamicCalculator;
+
+/// 結合自由エネルギーを翻訳開始頻度に換算する係数（mol/kcal）
+const BETA: f64 = 0.45;
+/// SD 配列と開始コドンの間に最低限必要な塩基数
+const MIN_SPACING: usize = 3;
+
+/// RBS strength estimation service
+pub struct RbsStrengthService {
+    calculator: ThermodynamicCalculator,
+}
+
+impl Default for RbsStrengthService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RbsStrengthService {
+    pub fn new() -> Self {
+        Self {
+            calculator: ThermodynamicCalculator::new_nndb_2024(),
+        }
+    }
+
+    /// `start_codon` の上流 `params.upstream` nt から、16S rRNA と連続して相補な
+    /// SD 様配列のうち、結合 ΔG と間隔の罰則の和が最も低いものを選ぶ。
+    /// 相対強度は exp(-β(ΔG - ΔG_ref))（ΔG_ref はコンセンサス SD を最適間隔に置いた値）
+    pub fn predict(&self, sequence: &str, start_codon: usize, params: &RbsParams) -> RbsPrediction {
+        let region_start = start_codon.saturating_sub(params.upstream);
+        let region = sequence[region_start..start_codon].to_ascii_uppercase();
+
+        // (SD の ΔG, 間隔の罰則, 領域内の位置, 長さ)
+        let mut best: Option<(f64, f64, usize, usize)> = None;
+        for position in 0..region.len() {
+            for length in params.min_sd_length.max(2)..=SD_CONSENSUS.len() {
+                let end = position + length;
+                if end + MIN_SPACING > region.len() {
+                    break;
+                }
+                // 短い一致がなければ、それを含む長い一致もない
+                let candidate = &region[position..end];
+                if !SD_CONSENSUS.contains(candidate) {
+                    break;
+                }
+                let Some(sd_delta_g) = self.delta_g(candidate) else {
+                    continue;
+                };
+                let offset = (region.len() - end) as f64 - OPTIMAL_SPACING as f64;
+                let spacing_delta_g = params.spacing_penalty * offset * offset;
+                if best.is_none_or(|(dg, penalty, ..)| sd_delta_g + spacing_delta_g < dg + penalty)
+                {
+                    best = Some((sd_delta_g, spacing_delta_g, position, length));
+                }
+            }
+        }
+
+        let reference = self.delta_g(SD_CONSENSUS).unwrap_or(0.0);
+        let start_codon_sequence = sequence
+            .get(start_codon..start_codon + 3)
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        match best {
+            Some((sd_delta_g, spacing_delta_g, position, length)) => {
+                let relative_strength = (-BETA * (sd_delta_g + spacing_delta_g - reference))
+                    .exp()
+                    .min(1.0);
+                RbsPrediction {
+                    start_codon,
+                    start_codon_sequence,
+                    sd_sequence: Some(region[position..position + length].to_string()),
+                    sd_position: Some(region_start + position),
+                    sd_delta_g,
+                    spacing: Some(region.len() - position - length),
+                    spacing_delta_g,
+                    relative_strength,
+                    weak: relative_strength < params.weak_threshold,
+                }
+            }
+            None => RbsPrediction {
+                start_codon,
+                start_codon_sequence,
+                sd_sequence: None,
+                sd_position: None,
+                sd_delta_g: 0.0,
+                spacing: None,
+                spacing_delta_g: 0.0,
+                relative_strength: 0.0,
+                weak: true,
+            },
+        }
+    }
+
+    fn delta_g(&self, sequence: &str) -> Option<f64> {
+        let temperature_k = self.calculator.get_conditions().temperature_k;
+        self.calculator
+            .calculate_delta_g(sequence, temperature_k)
+            .ok()
+            .map(f64::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consensus_sd_at_optimal_spacing_is_strongest() {
+        let service = RbsStrengthService::new();
+        let params = RbsParams::default();
+
+        // pET 系の RBS（AAGGAG、開始コドンまで 8 nt）
+        let pet = "TTTGTTTAACTTTAAGAAGGAGATATACATATG";
+        let strong = service.predict(pet, pet.len() - 3, &params);
+        assert_eq!(strong.sd_sequence.as_deref(), Some("AAGGAG"));
+        assert_eq!(strong.spacing, Some(8));
+        assert_eq!(strong.start_codon_sequence, "ATG");
+        assert!(!strong.weak && strong.relative_strength < 1.0);
+
+        let consensus = "CCCTAAGGAGGTAAAAAAAATG";
+        let best = service.predict(consensus, consensus.len() - 3, &params);
+        assert!((best.relative_strength - 1.0).abs() < 1e-9);
+
+        // 同じ SD でも開始コドンから離れすぎると弱い
+        let distant = "CCCTAAGGAGGTAAAAAAAAAAAAAAATG";
+        let far = service.predict(distant, distant.len() - 3, &params);
+        assert!(far.relative_strength < best.relative_strength * 0.1);
+
+        let none = service.predict("CCCCCCCCCCCCCCCCCCCCATG", 20, &params);
+        assert!(none.sd_sequence.is_none() && none.weak);
+    }
+}
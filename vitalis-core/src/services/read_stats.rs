@@ -0,0 +1,660 @@
+// Service layer: Read-set library complexity (duplication, rarefaction, overrepresentation)
+use crate::domain::read_set::{ReadQualityStats, PHRED_OFFSET};
+use crate::domain::read_stats::{
+    DuplicationBin, EnrichedKmer, HistogramBin, OverrepresentedSequence, RarefactionPoint,
+    ReadSetDistributions, ReadSetParams, ReadSetStats,
+};
+use crate::domain::reproducibility::{self, SplitMix64};
+use crate::services::VectorContaminationService;
+use std::collections::{HashMap, HashSet};
+
+/// 重複レベルの区間（下限、上限）
+const DUPLICATION_BINS: [(usize, Option<usize>); 16] = [
+    (1, Some(1)),
+    (2, Some(2)),
+    (3, Some(3)),
+    (4, Some(4)),
+    (5, Some(5)),
+    (6, Some(6)),
+    (7, Some(7)),
+    (8, Some(8)),
+    (9, Some(9)),
+    (10, Some(49)),
+    (50, Some(99)),
+    (100, Some(499)),
+    (500, Some(999)),
+    (1000, Some(4999)),
+    (5000, Some(9999)),
+    (10000, None),
+];
+/// 報告する k-mer の最低出現数（少数のリードでは比が偶然大きくなるため）
+const MIN_KMER_COUNT: usize = 10;
+/// 品質統計のリード長の区間数
+const QUALITY_LENGTH_BINS: usize = 20;
+
+/// Read-set statistics service
+pub struct ReadSetStatsService {
+    contamination_service: VectorContaminationService,
+}
+
+impl Default for ReadSetStatsService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadSetStatsService {
+    pub fn new() -> Self {
+        Self {
+            contamination_service: VectorContaminationService::new(),
+        }
+    }
+
+    pub fn analyze<S: AsRef<str>>(&self, reads: &[S], params: &ReadSetParams) -> ReadSetStats {
+        let keys: Vec<String> = reads
+            .iter()
+            .map(|read| {
+                let read = read.as_ref();
+                read[..read.len().min(params.dedup_length)].to_ascii_uppercase()
+            })
+            .collect();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for key in &keys {
+            *counts.entry(key).or_default() += 1;
+        }
+
+        let read_count = reads.len();
+        let percent = |n: usize| {
+            if read_count == 0 {
+                0.0
+            } else {
+                n as f64 / read_count as f64 * 100.0
+            }
+        };
+
+        let duplication_levels = DUPLICATION_BINS
+            .iter()
+            .map(|&(min_copies, max_copies)| {
+                let in_bin = counts
+                    .values()
+                    .filter(|&&c| c >= min_copies && max_copies.is_none_or(|max| c <= max));
+                let (distinct_sequences, reads) =
+                    in_bin.fold((0, 0), |(distinct, reads), &c| (distinct + 1, reads + c));
+                DuplicationBin {
+                    min_copies,
+                    max_copies,
+                    distinct_sequences,
+                    reads,
+                    percent_of_reads: percent(reads),
+                }
+            })
+            .collect();
+
+        let mut overrepresented: Vec<(&str, usize)> = counts
+            .iter()
+            .map(|(&key, &count)| (key, count))
+            .filter(|&(_, count)| percent(count) >= params.overrepresented_percent)
+            .collect();
+        overrepresented.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        overrepresented.truncate(params.max_overrepresented);
+        let overrepresented = overrepresented
+            .into_iter()
+            .map(|(sequence, count)| OverrepresentedSequence {
+                sequence: sequence.to_string(),
+                count,
+                percent: percent(count),
+                possible_source: self.possible_source(sequence),
+            })
+            .collect();
+
+        let seed = params.seed.unwrap_or_else(reproducibility::workspace_u64);
+        ReadSetStats {
+            read_count,
+            distinct_sequences: counts.len(),
+            percent_remaining_if_deduplicated: percent(counts.len()),
+            duplication_levels,
+            rarefaction: rarefaction(&keys, params.rarefaction_points, seed),
+            overrepresented,
+            enriched_kmers: enriched_kmers(reads, params),
+        }
+    }
+
+    /// リードを1本ずつ読みながら GC 含量と長さのヒストグラムを作る。
+    /// 長さの範囲（メタデータから事前に分かる）で区間を決めるので1パスで済む。
+    /// リードセット ID は呼び出し側で設定する
+    pub fn distributions<I, S, E>(
+        &self,
+        reads: I,
+        bins: usize,
+        length_range: (usize, usize),
+    ) -> Result<ReadSetDistributions, E>
+    where
+        I: IntoIterator<Item = Result<S, E>>,
+        S: AsRef<str>,
+    {
+        let mut accumulator =
+            ReadDistributionAccumulator::new(bins, length_range.0, length_range.1);
+        for read in reads {
+            accumulator.add(read?.as_ref());
+        }
+        Ok(accumulator.finish())
+    }
+
+    /// リードの長さと品質（Phred+33、FASTA 由来なら None）を1本ずつ受け取り、
+    /// 長さ・リードごとの平均品質・位置ごとの平均品質を集計する。
+    /// 長さの区間は `distributions` と同じく事前に分かる範囲から決める
+    pub fn quality_stats<I, Q, E>(
+        &self,
+        reads: I,
+        length_range: (usize, usize),
+    ) -> Result<ReadQualityStats, E>
+    where
+        I: IntoIterator<Item = Result<(usize, Option<Q>), E>>,
+        Q: AsRef<[u8]>,
+    {
+        let mut accumulator = ReadQualityAccumulator::new(length_range.0, length_range.1);
+        for read in reads {
+            let (length, quality) = read?;
+            accumulator.add(length, quality.as_ref().map(AsRef::as_ref));
+        }
+        Ok(accumulator.finish())
+    }
+
+    /// 最もスコアの高い混入シグネチャの名前
+    fn possible_source(&self, sequence: &str) -> Option<String> {
+        self.contamination_service
+            .screen(sequence)
+            .hits
+            .into_iter()
+            .max_by_key(|hit| hit.score)
+            .map(|hit| hit.signature)
+    }
+}
+
+/// 長さの範囲を等幅（bp 単位）に分けたヒストグラム
+struct LengthHistogram {
+    counts: Vec<usize>,
+    min_length: usize,
+    /// 区間幅（bp）
+    width: usize,
+}
+
+impl LengthHistogram {
+    fn new(bins: usize, min_length: usize, max_length: usize) -> Self {
+        let span = max_length.saturating_sub(min_length) + 1;
+        let width = span.div_ceil(bins.max(1));
+        Self {
+            counts: vec![0; span.div_ceil(width)],
+            min_length,
+            width,
+        }
+    }
+
+    fn add(&mut self, length: usize) {
+        let bin = (length.saturating_sub(self.min_length) / self.width).min(self.counts.len() - 1);
+        self.counts[bin] += 1;
+    }
+
+    fn bins(&self) -> Vec<HistogramBin> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let start = self.min_length + i * self.width;
+                HistogramBin {
+                    start: start as f64,
+                    end: (start + self.width) as f64,
+                    count,
+                }
+            })
+            .collect()
+    }
+}
+
+/// GC 含量とリード長のヒストグラムを1リードずつ積み上げる（リード全体を保持しない）
+struct ReadDistributionAccumulator {
+    gc_counts: Vec<usize>,
+    lengths: LengthHistogram,
+    read_count: usize,
+    gc_reads: usize,
+    gc_sum: f64,
+    length_sum: usize,
+}
+
+impl ReadDistributionAccumulator {
+    fn new(bins: usize, min_length: usize, max_length: usize) -> Self {
+        Self {
+            gc_counts: vec![0; bins.max(1)],
+            lengths: LengthHistogram::new(bins, min_length, max_length),
+            read_count: 0,
+            gc_reads: 0,
+            gc_sum: 0.0,
+            length_sum: 0,
+        }
+    }
+
+    fn add(&mut self, read: &str) {
+        let (mut gc, mut called) = (0usize, 0usize);
+        for b in read.bytes() {
+            match b.to_ascii_uppercase() {
+                b'G' | b'C' => {
+                    gc += 1;
+                    called += 1;
+                }
+                b'A' | b'T' | b'U' => called += 1,
+                _ => {}
+            }
+        }
+        if called > 0 {
+            let percent = gc as f64 / called as f64 * 100.0;
+            let bins = self.gc_counts.len();
+            let bin = ((percent / 100.0 * bins as f64) as usize).min(bins - 1);
+            self.gc_counts[bin] += 1;
+            self.gc_reads += 1;
+            self.gc_sum += percent;
+        }
+
+        self.lengths.add(read.len());
+        self.read_count += 1;
+        self.length_sum += read.len();
+    }
+
+    fn finish(self) -> ReadSetDistributions {
+        let gc_width = 100.0 / self.gc_counts.len() as f64;
+        let mean = |sum: f64, n: usize| if n == 0 { 0.0 } else { sum / n as f64 };
+        ReadSetDistributions {
+            readset_id: String::new(),
+            read_count: self.read_count,
+            gc_content: self
+                .gc_counts
+                .iter()
+                .enumerate()
+                .map(|(i, &count)| HistogramBin {
+                    start: i as f64 * gc_width,
+                    end: (i + 1) as f64 * gc_width,
+                    count,
+                })
+                .collect(),
+            length: self.lengths.bins(),
+            mean_gc: mean(self.gc_sum, self.gc_reads),
+            mean_length: mean(self.length_sum as f64, self.read_count),
+        }
+    }
+}
+
+/// リード長と品質の分布を1リードずつ積み上げる
+struct ReadQualityAccumulator {
+    lengths: LengthHistogram,
+    read_count: usize,
+    total_bases: usize,
+    min_length: usize,
+    max_length: usize,
+    /// 平均品質の整数部ごとのリード数
+    mean_quality_counts: Vec<usize>,
+    /// 位置ごとの品質の合計とリード数
+    position_sums: Vec<u64>,
+    position_reads: Vec<usize>,
+    reads_with_quality: usize,
+    quality_bases: usize,
+    q20_bases: usize,
+    q30_bases: usize,
+}
+
+impl ReadQualityAccumulator {
+    fn new(min_length: usize, max_length: usize) -> Self {
+        Self {
+            lengths: LengthHistogram::new(QUALITY_LENGTH_BINS, min_length, max_length),
+            read_count: 0,
+            total_bases: 0,
+            min_length: usize::MAX,
+            max_length: 0,
+            mean_quality_counts: Vec::new(),
+            position_sums: Vec::new(),
+            position_reads: Vec::new(),
+            reads_with_quality: 0,
+            quality_bases: 0,
+            q20_bases: 0,
+            q30_bases: 0,
+        }
+    }
+
+    fn add(&mut self, length: usize, quality: Option<&[u8]>) {
+        self.lengths.add(length);
+        self.read_count += 1;
+        self.total_bases += length;
+        self.min_length = self.min_length.min(length);
+        self.max_length = self.max_length.max(length);
+
+        let Some(quality) = quality.filter(|quality| !quality.is_empty()) else {
+            return;
+        };
+        if self.position_sums.len() < quality.len() {
+            self.position_sums.resize(quality.len(), 0);
+            self.position_reads.resize(quality.len(), 0);
+        }
+        let mut sum = 0u64;
+        for (position, &symbol) in quality.iter().enumerate() {
+            let phred = symbol.saturating_sub(PHRED_OFFSET);
+            sum += u64::from(phred);
+            self.position_sums[position] += u64::from(phred);
+            self.position_reads[position] += 1;
+            self.q20_bases += usize::from(phred >= 20);
+            self.q30_bases += usize::from(phred >= 30);
+        }
+
+        let bin = (sum / quality.len() as u64) as usize;
+        if self.mean_quality_counts.len() <= bin {
+            self.mean_quality_counts.resize(bin + 1, 0);
+        }
+        self.mean_quality_counts[bin] += 1;
+        self.reads_with_quality += 1;
+        self.quality_bases += quality.len();
+    }
+
+    fn finish(self) -> ReadQualityStats {
+        let percent = |count: usize| {
+            if self.quality_bases == 0 {
+                0.0
+            } else {
+                count as f64 / self.quality_bases as f64 * 100.0
+            }
+        };
+        ReadQualityStats {
+            readset_id: String::new(),
+            read_count: self.read_count,
+            reads_with_quality: self.reads_with_quality,
+            total_bases: self.total_bases,
+            min_length: if self.read_count == 0 {
+                0
+            } else {
+                self.min_length
+            },
+            max_length: self.max_length,
+            mean_length: if self.read_count == 0 {
+                0.0
+            } else {
+                self.total_bases as f64 / self.read_count as f64
+            },
+            length: self.lengths.bins(),
+            mean_quality: self
+                .mean_quality_counts
+                .iter()
+                .enumerate()
+                .map(|(phred, &count)| HistogramBin {
+                    start: phred as f64,
+                    end: (phred + 1) as f64,
+                    count,
+                })
+                .collect(),
+            per_position_quality: self
+                .position_sums
+                .iter()
+                .zip(&self.position_reads)
+                .map(|(&sum, &reads)| sum as f64 / reads as f64)
+                .collect(),
+            q20_percent: percent(self.q20_bases),
+            q30_percent: percent(self.q30_bases),
+        }
+    }
+}
+
+/// リードを無作為な順に並べ、先頭から points 等分ごとの異なる配列数を数える
+/// （順列の先頭 n 個は大きさ n の非復元サブサンプル）
+pub fn rarefaction(keys: &[String], points: usize, seed: u64) -> Vec<RarefactionPoint> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    let mut rng = SplitMix64::new(seed);
+    for i in (1..order.len()).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+
+    let mut checkpoints: Vec<usize> = (1..=points.max(1))
+        .map(|i| keys.len() * i / points.max(1))
+        .filter(|&n| n > 0)
+        .collect();
+    checkpoints.dedup();
+
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut curve = Vec::with_capacity(checkpoints.len());
+    let mut next = checkpoints.iter().peekable();
+    for (taken, &index) in order.iter().enumerate() {
+        seen.insert(&keys[index]);
+        if next.peek().is_some_and(|&&n| n == taken + 1) {
+            next.next();
+            curve.push(RarefactionPoint {
+                reads: taken + 1,
+                distinct_sequences: seen.len(),
+            });
+        }
+    }
+    curve
+}
+
+/// 塩基組成から期待される数より多く出現する k-mer（観測/期待比の高い順）
+fn enriched_kmers<S: AsRef<str>>(reads: &[S], params: &ReadSetParams) -> Vec<EnrichedKmer> {
+    let k = params.kmer_size;
+    let mut counts: HashMap<Vec<u8>, usize> = HashMap::new();
+    let mut bases = [0usize; 4];
+    let mut total_kmers = 0usize;
+    for read in reads {
+        let read = read.as_ref().to_ascii_uppercase().into_bytes();
+        for b in &read {
+            if let Some(i) = b"ACGT".iter().position(|x| x == b) {
+                bases[i] += 1;
+            }
+        }
+        for kmer in read.windows(k) {
+            if kmer.iter().all(|b| b"ACGT".contains(b)) {
+                *counts.entry(kmer.to_vec()).or_default() += 1;
+                total_kmers += 1;
+            }
+        }
+    }
+    let total_bases: usize = bases.iter().sum();
+    if total_bases == 0 {
+        return Vec::new();
+    }
+    let frequency = |b: u8| {
+        let i = b"ACGT".iter().position(|&x| x == b).unwrap_or(0);
+        bases[i] as f64 / total_bases as f64
+    };
+
+    let mut enriched: Vec<(Vec<u8>, usize, f64)> = counts
+        .into_iter()
+        .filter(|&(_, count)| count >= MIN_KMER_COUNT)
+        .map(|(kmer, count)| {
+            let expected = total_kmers as f64 * kmer.iter().map(|&b| frequency(b)).product::<f64>();
+            let ratio = count as f64 / expected;
+            (kmer, count, ratio)
+        })
+        .filter(|&(_, _, ratio)| ratio >= params.min_kmer_enrichment)
+        .collect();
+    enriched.sort_by(|a, b| b.2.total_cmp(&a.2).then(b.1.cmp(&a.1)).then(a.0.cmp(&b.0)));
+    enriched.truncate(params.max_kmers);
+
+    // 報告する k-mer だけ位置ごとの出現数を数える
+    let mut positions: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (kmer, _, _) in &enriched {
+        positions.insert(kmer, Vec::new());
+    }
+    for read in reads {
+        let read = read.as_ref().to_ascii_uppercase().into_bytes();
+        for (position, kmer) in read.windows(k).enumerate() {
+            if let Some(by_position) = positions.get_mut(kmer) {
+                if by_position.len() <= position {
+                    by_position.resize(position + 1, 0);
+                }
+                by_position[position] += 1;
+            }
+        }
+    }
+
+    enriched
+        .iter()
+        .map(|(kmer, count, ratio)| {
+            let by_position = &positions[kmer.as_slice()];
+            let peak_position = (0..by_position.len())
+                .max_by_key(|&p| (by_position[p], std::cmp::Reverse(p)))
+                .unwrap_or(0);
+            EnrichedKmer {
+                kmer: String::from_utf8_lossy(kmer).into_owned(),
+                count: *count,
+                observed_over_expected: *ratio,
+                peak_position,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_sequence(length: usize, seed: u64) -> String {
+        let mut state = seed;
+        (0..length)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4] as char
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_duplication_levels_and_overrepresented_adapter() {
+        // 異なるリード 100 本、3 コピーのリード 10 種、アダプターダイマー 30 本
+        let adapter = "AGATCGGAAGAGCACACGTCTGAACTCCAGTCACATCACGATCTCGTATGCCGT";
+        let mut reads: Vec<String> = (0..100).map(|i| random_sequence(60, i)).collect();
+        for i in 0..10 {
+            for _ in 0..3 {
+                reads.push(random_sequence(60, 1000 + i));
+            }
+        }
+        reads.extend((0..30).map(|_| adapter.to_lowercase()));
+
+        let params = ReadSetParams {
+            overrepresented_percent: 5.0,
+            seed: Some(1),
+            ..ReadSetParams::default()
+        };
+        let stats = ReadSetStatsService::new().analyze(&reads, &params);
+        assert_eq!(stats.read_count, 160);
+        assert_eq!(stats.distinct_sequences, 111);
+        let level = |copies: usize| {
+            stats
+                .duplication_levels
+                .iter()
+                .find(|bin| bin.min_copies == copies)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!((level(1).distinct_sequences, level(1).reads), (100, 100));
+        assert_eq!((level(3).distinct_sequences, level(3).reads), (10, 30));
+        assert_eq!((level(10).distinct_sequences, level(10).reads), (1, 30));
+
+        assert_eq!(stats.overrepresented.len(), 1);
+        let dimer = &stats.overrepresented[0];
+        assert_eq!(dimer.sequence, &adapter[..50]);
+        assert_eq!(dimer.count, 30);
+        assert_eq!(
+            dimer.possible_source.as_deref(),
+            Some("Illumina TruSeq adapter (read 1)")
+        );
+        assert!(!stats.enriched_kmers.is_empty());
+        assert!(stats
+            .enriched_kmers
+            .iter()
+            .all(|kmer| adapter.find(&kmer.kmer) == Some(kmer.peak_position)));
+    }
+
+    #[test]
+    fn test_gc_and_length_histograms() {
+        let reads = [
+            "AT".repeat(50),
+            "GC".repeat(50),
+            format!("{}{}", "GC".repeat(30), "AT".repeat(45)),
+            "N".repeat(120),
+        ];
+        let distributions = ReadSetStatsService::new()
+            .distributions(reads.iter().map(Ok::<_, ()>), 4, (100, 150))
+            .unwrap();
+        assert_eq!(distributions.read_count, 4);
+        assert_eq!(
+            distributions
+                .gc_content
+                .iter()
+                .map(|bin| bin.count)
+                .collect::<Vec<_>>(),
+            vec![1, 1, 0, 1]
+        );
+        assert_eq!(distributions.mean_gc, 140.0 / 3.0);
+        // 51 bp の範囲を 13 bp 幅の 4 区間に分ける
+        assert_eq!(distributions.length.len(), 4);
+        assert_eq!(
+            (distributions.length[0].start, distributions.length[0].end),
+            (100.0, 113.0)
+        );
+        assert_eq!(
+            distributions
+                .length
+                .iter()
+                .map(|bin| bin.count)
+                .collect::<Vec<_>>(),
+            vec![2, 1, 0, 1]
+        );
+        assert_eq!(distributions.mean_length, 117.5);
+    }
+
+    #[test]
+    fn test_quality_distributions() {
+        // Phred 40（'I'）、Phred 10（'+'）、末尾だけ低品質のリードと品質のないリード
+        let reads: Vec<(usize, Option<String>)> = vec![
+            (4, Some("IIII".to_string())),
+            (4, Some("++++".to_string())),
+            (6, Some("IIII++".to_string())),
+            (5, None),
+        ];
+        let stats = ReadSetStatsService::new()
+            .quality_stats(reads.into_iter().map(Ok::<_, ()>), (4, 6))
+            .unwrap();
+        assert_eq!(stats.read_count, 4);
+        assert_eq!(stats.reads_with_quality, 3);
+        assert_eq!(
+            (stats.min_length, stats.max_length, stats.total_bases),
+            (4, 6, 19)
+        );
+        assert_eq!(stats.length.len(), 3);
+        assert_eq!(
+            stats.length.iter().map(|bin| bin.count).collect::<Vec<_>>(),
+            vec![2, 1, 1]
+        );
+
+        // 平均品質 40, 10, 30
+        let counts: Vec<usize> = stats.mean_quality.iter().map(|bin| bin.count).collect();
+        assert_eq!(counts.len(), 41);
+        assert_eq!((counts[10], counts[30], counts[40]), (1, 1, 1));
+        assert_eq!(
+            stats.per_position_quality,
+            vec![30.0, 30.0, 30.0, 30.0, 10.0, 10.0]
+        );
+        assert_eq!(stats.q20_percent, stats.q30_percent);
+        assert_eq!(stats.q30_percent, 8.0 / 14.0 * 100.0);
+    }
+
+    #[test]
+    fn test_rarefaction_is_monotonic_and_seeded() {
+        let keys: Vec<String> = (0..500).map(|i| format!("R{}", i % 200)).collect();
+        let curve = rarefaction(&keys, 5, 9);
+        assert_eq!(
+            curve.iter().map(|p| p.reads).collect::<Vec<_>>(),
+            vec![100, 200, 300, 400, 500]
+        );
+        assert!(curve
+            .windows(2)
+            .all(|w| w[0].distinct_sequences <= w[1].distinct_sequences));
+        assert_eq!(curve[4].distinct_sequences, 200);
+        assert_eq!(curve, rarefaction(&keys, 5, 9));
+        assert_eq!(rarefaction(&keys[..3], 10, 9).len(), 3);
+    }
+}
@@ -0,0 +1,292 @@
+// Service layer: Tandem and inverted repeat detection
+use crate::domain::repeat::{InvertedRepeat, RepeatParams, RepeatReport, TandemRepeat};
+
+/// 縦列反復の延長で、周期がずれた位置1つあたりの減点（一致は +1）
+const MISMATCH_PENALTY: i64 = 3;
+/// 得点がこれを超えて最高点から下がったら縦列反復を打ち切る
+/// （単位長 1 でも1塩基の置換は越えて延ばす）
+const X_DROP: i64 = 6;
+
+/// 縦列反復・逆位反復を探すサービス
+pub struct RepeatFinderService;
+
+impl Default for RepeatFinderService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RepeatFinderService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 線形配列として縦列反復と逆位反復を探す。ACGT 以外の塩基はどちらにも含めない
+    pub fn find(&self, sequence: &str, params: &RepeatParams) -> RepeatReport {
+        let bases = sequence.to_ascii_uppercase().into_bytes();
+        let mut tandem = self.tandem_repeats(&bases, params);
+        let mut inverted: Vec<InvertedRepeat> = self
+            .inverted_repeats(&bases, params)
+            .into_iter()
+            .filter(|repeat| {
+                // 自己相補的な単位の縦列反復は単位ごとに逆位反復になるので除く
+                !tandem
+                    .iter()
+                    .any(|t| t.start <= repeat.start && repeat.end <= t.end)
+            })
+            .collect();
+
+        let truncated = tandem.len() > params.max_results || inverted.len() > params.max_results;
+        tandem.truncate(params.max_results);
+        inverted.truncate(params.max_results);
+        RepeatReport {
+            length: bases.len(),
+            tandem,
+            inverted,
+            truncated,
+        }
+    }
+
+    /// 単位長ごとに i と i + period の一致を数え、置換を挟んでも得点が落ちきらない区間を
+    /// 縦列反復とする。より短い単位の繰り返しになっている単位は、その単位長で報告済みなので除く
+    pub fn tandem_repeats(&self, bases: &[u8], params: &RepeatParams) -> Vec<TandemRepeat> {
+        let mut repeats = Vec::new();
+        for period in 1..=params.max_period {
+            if bases.len() < 2 * period {
+                break;
+            }
+            let mut segment: Option<Segment> = None;
+            for i in 0..bases.len() - period {
+                let matched = is_acgt(bases[i]) && bases[i] == bases[i + period];
+                if segment.is_none() && !matched {
+                    continue;
+                }
+                let current = segment.get_or_insert_with(|| Segment::new(i));
+                if current.extend(i, matched) {
+                    let (start, end) = current.best;
+                    repeats.extend(self.tandem(bases, start, end + period, period, params));
+                    segment = None;
+                }
+            }
+            if let Some(Segment {
+                best: (start, end), ..
+            }) = segment
+            {
+                repeats.extend(self.tandem(bases, start, end + period, period, params));
+            }
+        }
+        repeats.sort_by_key(|repeat| (repeat.start, repeat.period));
+        repeats
+    }
+
+    /// 開始位置 `start`・終了位置 `end`・単位長 `period` の区間を条件で絞り、共通単位を求める
+    fn tandem(
+        &self,
+        bases: &[u8],
+        start: usize,
+        end: usize,
+        period: usize,
+        params: &RepeatParams,
+    ) -> Option<TandemRepeat> {
+        let length = end - start;
+        let copies = length as f64 / period as f64;
+        if length < params.min_tandem_length || copies < params.min_copies {
+            return None;
+        }
+
+        let region = &bases[start..end];
+        let consensus: Vec<u8> = (0..period)
+            .map(|phase| {
+                let mut counts = [0usize; 4];
+                for &base in region.iter().skip(phase).step_by(period) {
+                    if let Some(index) = b"ACGT".iter().position(|&b| b == base) {
+                        counts[index] += 1;
+                    }
+                }
+                // 同数なら ACGT の順で先のもの
+                let best = (0..4).rev().max_by_key(|&index| counts[index]).unwrap_or(0);
+                b"ACGT"[best]
+            })
+            .collect();
+        if (1..period).any(|unit| {
+            period.is_multiple_of(unit) && consensus[unit..] == consensus[..period - unit]
+        }) {
+            return None;
+        }
+
+        let identical = region
+            .iter()
+            .enumerate()
+            .filter(|&(offset, &base)| base == consensus[offset % period])
+            .count();
+        Some(TandemRepeat {
+            start,
+            end,
+            period,
+            consensus: String::from_utf8_lossy(&consensus).into_owned(),
+            copies,
+            identity: identical as f64 / length as f64,
+        })
+    }
+
+    /// 腕の間が 0〜max_spacer 塩基の逆位反復。腕の内側の端から外へ相補的な塩基対を延ばし、
+    /// 内側にもう1組の塩基対を作れるもの（同じ腕のより短いループ）は数えない
+    pub fn inverted_repeats(&self, bases: &[u8], params: &RepeatParams) -> Vec<InvertedRepeat> {
+        let pairs = |x: u8, y: u8| is_acgt(x) && complement(x) == y;
+        let mut repeats = Vec::new();
+        for spacer in 0..=params.max_spacer {
+            for inner in 0..bases.len() {
+                let right = inner + 1 + spacer;
+                if right >= bases.len() {
+                    break;
+                }
+                if !pairs(bases[inner], bases[right])
+                    || (spacer >= 2 && pairs(bases[inner + 1], bases[right - 1]))
+                {
+                    continue;
+                }
+                let mut arm = 1;
+                while arm <= inner
+                    && right + arm < bases.len()
+                    && pairs(bases[inner - arm], bases[right + arm])
+                {
+                    arm += 1;
+                }
+                if arm >= params.min_arm_length {
+                    let start = inner + 1 - arm;
+                    repeats.push(InvertedRepeat {
+                        start,
+                        end: right + arm,
+                        arm_length: arm,
+                        spacer,
+                        arm: String::from_utf8_lossy(&bases[start..=inner]).into_owned(),
+                    });
+                }
+            }
+        }
+        repeats.sort_by_key(|repeat| (repeat.start, repeat.end));
+        repeats
+    }
+}
+
+/// 縦列反復の候補区間。i と i + period の一致を +1、不一致を -MISMATCH_PENALTY とした
+/// 得点の和が最大になる区間を `best` に持つ
+struct Segment {
+    score: i64,
+    peak: i64,
+    /// これまでの得点の最小値と、その直後の位置（最大区間の開始位置の候補）
+    low: (i64, usize),
+    best_score: i64,
+    best: (usize, usize),
+}
+
+impl Segment {
+    fn new(start: usize) -> Self {
+        Self {
+            score: 0,
+            peak: 0,
+            low: (0, start),
+            best_score: 0,
+            best: (start, start),
+        }
+    }
+
+    /// 位置 i の比較を加え、得点が最高点から X_DROP を超えて下がったら true
+    fn extend(&mut self, i: usize, matched: bool) -> bool {
+        self.score += if matched { 1 } else { -MISMATCH_PENALTY };
+        self.peak = self.peak.max(self.score);
+        if self.score - self.low.0 > self.best_score {
+            self.best_score = self.score - self.low.0;
+            self.best = (self.low.1, i + 1);
+        }
+        if self.score < self.low.0 {
+            self.low = (self.score, i + 1);
+        }
+        self.peak - self.score > X_DROP
+    }
+}
+
+fn is_acgt(base: u8) -> bool {
+    matches!(base, b'A' | b'C' | b'G' | b'T')
+}
+
+fn complement(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'T' => b'A',
+        b'G' => b'C',
+        b'C' => b'G',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tandem_repeats_with_substitution() {
+        let service = RepeatFinderService::new();
+        // (CAG)x8 の途中に1塩基の置換、ほかに単一塩基の連続
+        let sequence = format!(
+            "GATTCGTACT{}CAT{}TTGACGCTAGAAAAAAAAAAAAGCTCATCG",
+            "CAG".repeat(4),
+            "CAG".repeat(3)
+        );
+        let report = service.find(&sequence, &RepeatParams::default());
+
+        let repeats: Vec<_> = report
+            .tandem
+            .iter()
+            .map(|r| (r.start, r.end, r.period, r.consensus.as_str()))
+            .collect();
+        assert_eq!(repeats, vec![(10, 34, 3, "CAG"), (44, 56, 1, "A")]);
+        let cag = &report.tandem[0];
+        assert_eq!(cag.copies, 8.0);
+        assert!((cag.identity - 23.0 / 24.0).abs() < 1e-12);
+        // (CAG)n は単位長 6・9 でも周期的だが、CAG として一度だけ報告する
+        assert!(report.tandem.iter().all(|r| r.period != 6 && r.period != 9));
+    }
+
+    #[test]
+    fn test_inverted_repeats_and_palindromes() {
+        let service = RepeatFinderService::new();
+        let arm = "GGATCCTTAGCA";
+        let stem_loop = format!(
+            "ACACACATTT{}GTTTGAA{}TTTACAC",
+            arm,
+            crate::domain::iupac::reverse_complement(arm)
+        );
+        let params = RepeatParams {
+            min_arm_length: 8,
+            ..RepeatParams::default()
+        };
+        let report = service.find(&stem_loop, &params);
+        assert_eq!(
+            report.inverted,
+            vec![InvertedRepeat {
+                start: 10,
+                end: 10 + 12 + 7 + 12,
+                arm_length: 12,
+                spacer: 7,
+                arm: arm.to_string(),
+            }]
+        );
+
+        // EcoRI サイトを含む 16 塩基の回文はループなし。(AT)n の中の回文は除く
+        let palindrome = service.find(
+            &format!("TTTTGACCAGAATTCTGGTCGGGG{}GGGG", "AT".repeat(9)),
+            &params,
+        );
+        let found: Vec<_> = palindrome
+            .inverted
+            .iter()
+            .map(|r| (r.start, r.arm_length, r.spacer))
+            .collect();
+        assert_eq!(found, vec![(4, 8, 0)]);
+        assert!(palindrome
+            .tandem
+            .iter()
+            .any(|r| (r.start, r.end, r.consensus.as_str()) == (24, 42, "AT")));
+    }
+}
@@ -0,0 +1,356 @@
+// Service layer: Restriction site scanning
+use crate::domain::iupac;
+use crate::domain::restriction::{
+    builtin_enzymes, CutterClass, CutterEntry, CutterSummary, EnzymeDigest, Methylation,
+    OverhangType, RestrictionEnzyme, RestrictionMap, RestrictionSite,
+};
+use crate::domain::{Strand, Topology};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RestrictionError {
+    #[error("Unknown enzyme: {0}")]
+    UnknownEnzyme(String),
+}
+
+/// Restriction analysis service
+pub struct RestrictionAnalysisService {
+    enzymes: Vec<RestrictionEnzyme>,
+}
+
+impl Default for RestrictionAnalysisService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RestrictionAnalysisService {
+    /// 組み込み酵素テーブルで初期化
+    pub fn new() -> Self {
+        Self::with_enzymes(builtin_enzymes())
+    }
+
+    pub fn with_enzymes(enzymes: Vec<RestrictionEnzyme>) -> Self {
+        Self { enzymes }
+    }
+
+    pub fn enzymes(&self) -> &[RestrictionEnzyme] {
+        &self.enzymes
+    }
+
+    /// 酵素名で検索（大文字小文字は区別しない）
+    pub fn find_enzyme(&self, name: &str) -> Option<&RestrictionEnzyme> {
+        self.enzymes
+            .iter()
+            .find(|enzyme| enzyme.name.eq_ignore_ascii_case(name))
+    }
+
+    /// 酵素名のリストを解決（空の場合は全酵素）
+    pub fn resolve(&self, names: &[String]) -> Result<Vec<&RestrictionEnzyme>, RestrictionError> {
+        if names.is_empty() {
+            return Ok(self.enzymes.iter().collect());
+        }
+        names
+            .iter()
+            .map(|name| {
+                self.find_enzyme(name)
+                    .ok_or_else(|| RestrictionError::UnknownEnzyme(name.clone()))
+            })
+            .collect()
+    }
+
+    /// Scan both strands for every enzyme. Circular sequences are searched
+    /// across the origin and cut positions wrap modulo the sequence length;
+    /// on linear sequences sites whose cuts fall off either end are dropped.
+    pub fn scan(
+        &self,
+        sequence: &str,
+        topology: &Topology,
+        enzymes: &[&RestrictionEnzyme],
+    ) -> Vec<RestrictionSite> {
+        let length = sequence.len();
+        if length == 0 {
+            return Vec::new();
+        }
+
+        let circular = *topology == Topology::Circular;
+        let longest = enzymes
+            .iter()
+            .map(|e| e.site.len())
+            .chain(std::iter::once(Methylation::Dcm.motif().len()))
+            .max()
+            .unwrap_or(0);
+        let text = iupac::search_text(sequence, circular, longest);
+
+        let dam_hits = iupac::find_all(Methylation::Dam.motif(), &text, length, circular);
+        let dcm_hits = iupac::find_all(Methylation::Dcm.motif(), &text, length, circular);
+
+        let mut sites = Vec::new();
+        for enzyme in enzymes {
+            let forward = enzyme.site.to_ascii_uppercase();
+            let reverse = iupac::reverse_complement(&forward);
+            let site_len = forward.len() as isize;
+
+            let mut strands = vec![(Strand::Forward, forward.clone())];
+            if reverse != forward {
+                strands.push((Strand::Reverse, reverse));
+            }
+
+            for (strand, pattern) in strands {
+                for position in iupac::find_all(&pattern, &text, length, circular) {
+                    let start = position as isize;
+                    let (raw_top, raw_bottom) = match strand {
+                        Strand::Forward => (start + enzyme.cut_top, start + enzyme.cut_bottom),
+                        Strand::Reverse => (
+                            start + site_len - enzyme.cut_bottom,
+                            start + site_len - enzyme.cut_top,
+                        ),
+                    };
+
+                    if !circular {
+                        let in_bounds = |cut: isize| (0..=length as isize).contains(&cut);
+                        if !in_bounds(raw_top) || !in_bounds(raw_bottom) {
+                            continue;
+                        }
+                    }
+
+                    let overhang_type = enzyme.overhang_type();
+                    let (from, to) = (raw_top.min(raw_bottom), raw_top.max(raw_bottom));
+                    let overhang: String = (from..to)
+                        .map(|i| text[i.rem_euclid(length as isize) as usize] as char)
+                        .collect();
+
+                    let mut blocked_by = Vec::new();
+                    let site_end = position + forward.len();
+                    if enzyme.dam_sensitive
+                        && overlaps_any(&dam_hits, 4, position, site_end, length, circular)
+                    {
+                        blocked_by.push(Methylation::Dam);
+                    }
+                    if enzyme.dcm_sensitive
+                        && overlaps_any(&dcm_hits, 5, position, site_end, length, circular)
+                    {
+                        blocked_by.push(Methylation::Dcm);
+                    }
+
+                    sites.push(RestrictionSite {
+                        enzyme: enzyme.name.clone(),
+                        position,
+                        strand,
+                        cut_top: raw_top.rem_euclid(length as isize) as usize,
+                        cut_bottom: raw_bottom.rem_euclid(length as isize) as usize,
+                        overhang_type,
+                        overhang: if overhang_type == OverhangType::Blunt {
+                            String::new()
+                        } else {
+                            overhang
+                        },
+                        blocked_by,
+                    });
+                }
+            }
+        }
+
+        sites.sort_by(|a, b| a.position.cmp(&b.position).then(a.enzyme.cmp(&b.enzyme)));
+        sites
+    }
+
+    /// 酵素ごとのサイト数から 0/1/2 カッターを分類（3箇所以上は含めない）
+    pub fn cutter_summary(
+        &self,
+        sequence: &str,
+        topology: &Topology,
+        enzymes: &[&RestrictionEnzyme],
+    ) -> CutterSummary {
+        let sites = self.scan(sequence, topology, enzymes);
+        let mut summary = CutterSummary {
+            non_cutters: Vec::new(),
+            single_cutters: Vec::new(),
+            double_cutters: Vec::new(),
+        };
+
+        for enzyme in enzymes {
+            let enzyme_sites: Vec<&RestrictionSite> =
+                sites.iter().filter(|s| s.enzyme == enzyme.name).collect();
+            let entry = CutterEntry {
+                enzyme: enzyme.name.clone(),
+                positions: enzyme_sites.iter().map(|s| s.position).collect(),
+                blocked: enzyme_sites.iter().any(|s| s.is_blocked()),
+            };
+            match enzyme_sites.len() {
+                0 => summary.non_cutters.push(entry.enzyme),
+                1 => summary.single_cutters.push(entry),
+                2 => summary.double_cutters.push(entry),
+                _ => {}
+            }
+        }
+
+        summary
+    }
+
+    /// サイトに加えて酵素ごとの切断位置・断片長・カッター分類をまとめる
+    /// （配列IDは呼び出し側で設定する）
+    pub fn restriction_map(
+        &self,
+        sequence: &str,
+        topology: &Topology,
+        enzymes: &[&RestrictionEnzyme],
+    ) -> RestrictionMap {
+        let length = sequence.len();
+        let circular = *topology == Topology::Circular;
+        let sites = self.scan(sequence, topology, enzymes);
+
+        let digests = enzymes
+            .iter()
+            .map(|enzyme| {
+                let enzyme_sites: Vec<&RestrictionSite> =
+                    sites.iter().filter(|s| s.enzyme == enzyme.name).collect();
+                let mut cut_positions: Vec<usize> =
+                    enzyme_sites.iter().map(|s| s.cut_top).collect();
+                cut_positions.sort_unstable();
+                cut_positions.dedup();
+                EnzymeDigest {
+                    enzyme: enzyme.name.clone(),
+                    cutter: match cut_positions.len() {
+                        0 => CutterClass::NonCutter,
+                        1 => CutterClass::SingleCutter,
+                        _ => CutterClass::MultiCutter,
+                    },
+                    fragments: digest_bands(&cut_positions, length, circular),
+                    cut_positions,
+                    blocked: enzyme_sites.iter().any(|s| s.is_blocked()),
+                }
+            })
+            .collect();
+
+        RestrictionMap {
+            seq_id: String::new(),
+            length,
+            topology: topology.clone(),
+            sites,
+            digests,
+        }
+    }
+}
+
+/// 切断位置から断片長を求める（降順）。切断がなければ全長の1本
+pub fn digest_bands(cuts: &[usize], length: usize, circular: bool) -> Vec<usize> {
+    let mut bands: Vec<usize> = match (cuts.first(), cuts.last()) {
+        (Some(&first), Some(&last)) if circular => cuts
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .chain(std::iter::once(length - last + first))
+            .collect(),
+        (Some(&first), Some(&last)) => std::iter::once(first)
+            .chain(cuts.windows(2).map(|w| w[1] - w[0]))
+            .chain(std::iter::once(length - last))
+            .collect(),
+        _ => vec![length],
+    };
+    bands.retain(|&band| band > 0);
+    bands.sort_unstable_by(|a, b| b.cmp(a));
+    bands
+}
+
+/// [start, end) とメチル化モチーフのいずれかが重なるか
+fn overlaps_any(
+    hits: &[usize],
+    motif_len: usize,
+    start: usize,
+    end: usize,
+    length: usize,
+    circular: bool,
+) -> bool {
+    hits.iter().any(|&hit| {
+        let shifts: &[isize] = if circular { &[-1, 0, 1] } else { &[0] };
+        shifts.iter().any(|shift| {
+            let hit = hit as isize + shift * length as isize;
+            hit < end as isize && (start as isize) < hit + motif_len as isize
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(sequence: &str, topology: Topology, names: &[&str]) -> Vec<RestrictionSite> {
+        let service = RestrictionAnalysisService::new();
+        let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let enzymes = service.resolve(&names).unwrap();
+        service.scan(sequence, &topology, &enzymes)
+    }
+
+    #[test]
+    fn test_digest_bands() {
+        assert_eq!(digest_bands(&[100, 400], 1000, false), vec![600, 300, 100]);
+        assert_eq!(digest_bands(&[100, 400], 1000, true), vec![700, 300]);
+        assert_eq!(digest_bands(&[250], 1000, true), vec![1000]);
+        assert_eq!(digest_bands(&[], 1000, false), vec![1000]);
+    }
+
+    #[test]
+    fn test_sticky_end_and_degenerate_site() {
+        let sites = scan("TTGAATTCAACTCGGGTT", Topology::Linear, &["EcoRI", "AvaI"]);
+        assert_eq!(sites.len(), 2);
+
+        let eco = &sites[0];
+        assert_eq!((eco.cut_top, eco.cut_bottom), (3, 7));
+        assert_eq!(eco.overhang_type, OverhangType::FivePrime);
+        assert_eq!(eco.overhang, "AATT");
+
+        let ava = &sites[1];
+        assert_eq!(ava.enzyme, "AvaI");
+        assert_eq!(ava.overhang, "TCGG");
+    }
+
+    #[test]
+    fn test_type_iis_reverse_strand() {
+        // GAGACC は BsaI (GGTCTC) の逆鎖
+        let sites = scan("AAAAAAAAAAGAGACCAAAA", Topology::Linear, &["BsaI"]);
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].strand, Strand::Reverse);
+        assert_eq!((sites[0].cut_top, sites[0].cut_bottom), (5, 9));
+        assert_eq!(sites[0].overhang, "AAAA");
+    }
+
+    #[test]
+    fn test_cutter_summary() {
+        let service = RestrictionAnalysisService::new();
+        let names: Vec<String> = ["EcoRI", "BamHI", "HindIII", "XhoI"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        let enzymes = service.resolve(&names).unwrap();
+        let sequence = "GAATTCAAGGATCCAAGGATCCAAAAGCTTAAGCTTAAGCTT";
+
+        let summary = service.cutter_summary(sequence, &Topology::Linear, &enzymes);
+        assert_eq!(summary.non_cutters, vec!["XhoI".to_string()]);
+        assert_eq!(summary.single_cutters[0].enzyme, "EcoRI");
+        assert_eq!(summary.double_cutters[0].positions, vec![8, 16]);
+
+        let map = service.restriction_map(sequence, &Topology::Linear, &enzymes);
+        let bam = &map.digests[1];
+        assert_eq!(bam.cut_positions, vec![9, 17]);
+        assert_eq!(bam.fragments, vec![25, 9, 8]);
+        assert_eq!(bam.cutter, CutterClass::MultiCutter);
+        assert_eq!(map.digests[3].cutter, CutterClass::NonCutter);
+        assert_eq!(map.digests[3].fragments, vec![42]);
+        let circular = service.restriction_map(sequence, &Topology::Circular, &enzymes);
+        assert_eq!(circular.digests[1].fragments, vec![34, 8]);
+    }
+
+    #[test]
+    fn test_dam_blocking_and_circular_wrap() {
+        let sites = scan("ATCGATCAAAAAATCGATAAA", Topology::Linear, &["ClaI"]);
+        assert_eq!(sites.len(), 2);
+        assert!(sites[0].is_blocked());
+        assert!(!sites[1].is_blocked());
+
+        // ATCGAT split across the origin, followed by C forming GATC
+        let sites = scan("CGATCAAAAAAAT", Topology::Circular, &["ClaI"]);
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].position, 11);
+        assert_eq!(sites[0].blocked_by, vec![Methylation::Dam]);
+    }
+}
@@ -0,0 +1,77 @@
+// Service layer: Scaffold splitting at assembly gaps
+use crate::domain::scaffold::{Contig, ScaffoldSplit};
+use crate::services::stats::find_gaps;
+
+/// Scaffold service
+pub struct ScaffoldService;
+
+impl Default for ScaffoldService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScaffoldService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// `min_gap` 塩基以上の N の連続をギャップとし、その間の区間をコンティグにする。
+    /// 先頭・末尾のギャップの外側のような空の区間は含めない（seq_id は呼び出し側で設定する）
+    pub fn split(&self, sequence: &str, min_gap: usize) -> ScaffoldSplit {
+        let gaps = find_gaps(sequence, min_gap);
+
+        let mut contigs = Vec::with_capacity(gaps.len() + 1);
+        let mut start = 0;
+        let bounds = gaps.iter().map(|gap| (gap.start, gap.end));
+        for (end, next) in bounds.chain(std::iter::once((sequence.len(), sequence.len()))) {
+            if end > start {
+                contigs.push(Contig {
+                    seq_id: String::new(),
+                    start,
+                    end,
+                });
+            }
+            start = next;
+        }
+
+        ScaffoldSplit {
+            scaffold_id: String::new(),
+            min_gap,
+            gaps,
+            contigs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Range;
+
+    #[test]
+    fn test_split_between_long_n_runs() {
+        // 先頭のギャップ、短い N（コンティグ内に残る）、末尾のギャップ
+        let sequence = format!(
+            "{}ACGTNNACGT{}GGCC{}",
+            "N".repeat(6),
+            "N".repeat(10),
+            "N".repeat(5)
+        );
+        let split = ScaffoldService::new().split(&sequence, 5);
+        assert_eq!(
+            split.gaps,
+            vec![Range::new(0, 6), Range::new(16, 26), Range::new(30, 35)]
+        );
+        let contigs: Vec<(usize, usize)> = split
+            .contigs
+            .iter()
+            .map(|contig| (contig.start, contig.end))
+            .collect();
+        assert_eq!(contigs, vec![(6, 16), (26, 30)]);
+
+        let whole = ScaffoldService::new().split("ACGTNNNACGT", 5);
+        assert!(whole.gaps.is_empty());
+        assert_eq!((whole.contigs[0].start, whole.contigs[0].end), (0, 11));
+    }
+}
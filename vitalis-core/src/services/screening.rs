@@ -0,0 +1,282 @@
+// Service layer: Clone screening strategies (diagnostic digest or junction PCR)
+use crate::domain::iupac;
+use crate::domain::screening::{
+    DigestScreen, PcrScreen, ScreeningMethod, ScreeningParams, ScreeningStrategy,
+};
+use crate::domain::Topology;
+use crate::services::restriction::digest_bands;
+use crate::services::{JunctionPrimerService, RestrictionAnalysisService};
+use std::collections::BTreeMap;
+
+/// Screening strategy service
+pub struct ScreeningService {
+    restriction_service: RestrictionAnalysisService,
+    junction_service: JunctionPrimerService,
+}
+
+impl Default for ScreeningService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScreeningService {
+    pub fn new() -> Self {
+        Self {
+            restriction_service: RestrictionAnalysisService::new(),
+            junction_service: JunctionPrimerService::new(),
+        }
+    }
+
+    /// コンストラクトと親配列を区別する消化パターンと連結部PCRを提案する
+    /// （配列IDは呼び出し側で設定する）
+    pub fn suggest(
+        &self,
+        construct: (&str, &Topology),
+        parent: (&str, &Topology),
+        junctions: &[(usize, String)],
+        params: &ScreeningParams,
+    ) -> ScreeningStrategy {
+        let digests = self.digests(construct, parent, params);
+        let mut warnings = Vec::new();
+
+        let construct_upper = construct.0.to_ascii_uppercase();
+        let parent_upper = parent.0.to_ascii_uppercase();
+        let mut pcr = Vec::new();
+        for junction in self
+            .junction_service
+            .design(&construct_upper, junctions, &params.junction)
+        {
+            match junction.pair {
+                Some(pair) => pcr.push(PcrScreen {
+                    junction: junction.junction,
+                    label: junction.label,
+                    correct_size: pair.amplicon_length,
+                    parent_size: product_size(
+                        &parent_upper,
+                        &pair.forward.sequence,
+                        &pair.reverse.sequence,
+                    ),
+                    pair,
+                }),
+                None => warnings.push(format!(
+                    "No primer pair found across {} ({})",
+                    junction.junction, junction.label
+                )),
+            }
+        }
+
+        let distinct_pcr = pcr
+            .iter()
+            .any(|screen| screen.parent_size != Some(screen.correct_size));
+        let recommended = match digests.first() {
+            Some(best) if best.separation >= 2.0 * params.resolution => {
+                Some(ScreeningMethod::Digest)
+            }
+            _ if distinct_pcr => Some(ScreeningMethod::JunctionPcr),
+            Some(_) => Some(ScreeningMethod::Digest),
+            None => None,
+        };
+        if recommended.is_none() {
+            warnings.push(
+                "No digest or junction PCR distinguishes the construct from the parent".to_string(),
+            );
+        }
+
+        ScreeningStrategy {
+            construct_seq_id: String::new(),
+            parent_seq_id: String::new(),
+            recommended,
+            digests,
+            pcr,
+            warnings,
+        }
+    }
+
+    /// 1〜max_enzymes 種の酵素で、コンストラクトのバンドがゲルで読め、
+    /// 親配列とパターンが resolution 以上異なる消化を選ぶ（酵素数の少ない順、判別しやすい順）
+    fn digests(
+        &self,
+        construct: (&str, &Topology),
+        parent: (&str, &Topology),
+        params: &ScreeningParams,
+    ) -> Vec<DigestScreen> {
+        let construct_cuts = self.cuts(construct.0, construct.1);
+        let parent_cuts = self.cuts(parent.0, parent.1);
+        let no_cuts = Vec::new();
+
+        let cutters: Vec<&String> = construct_cuts
+            .iter()
+            .filter(|(_, cuts)| !cuts.is_empty() && cuts.len() <= params.max_bands)
+            .map(|(name, _)| name)
+            .collect();
+        let mut combinations: Vec<Vec<&String>> = cutters.iter().map(|&e| vec![e]).collect();
+        if params.max_enzymes >= 2 {
+            for (i, &a) in cutters.iter().enumerate() {
+                for &b in &cutters[i + 1..] {
+                    combinations.push(vec![a, b]);
+                }
+            }
+        }
+
+        let mut screens: Vec<DigestScreen> = combinations
+            .into_iter()
+            .filter_map(|enzymes| {
+                let merge = |cuts: &BTreeMap<String, Vec<usize>>| {
+                    let mut merged: Vec<usize> = enzymes
+                        .iter()
+                        .flat_map(|&e| cuts.get(e).unwrap_or(&no_cuts))
+                        .copied()
+                        .collect();
+                    merged.sort_unstable();
+                    merged.dedup();
+                    merged
+                };
+                let construct_all = digest_bands(
+                    &merge(&construct_cuts),
+                    construct.0.len(),
+                    *construct.1 == Topology::Circular,
+                );
+                if construct_all.len() > params.max_bands
+                    || construct_all.iter().any(|&band| band > params.max_band)
+                {
+                    return None;
+                }
+                let parent_all = digest_bands(
+                    &merge(&parent_cuts),
+                    parent.0.len(),
+                    *parent.1 == Topology::Circular,
+                );
+
+                let visible = |bands: Vec<usize>| -> Vec<usize> {
+                    bands
+                        .into_iter()
+                        .filter(|&band| band >= params.min_band)
+                        .collect()
+                };
+                let construct_bands = visible(construct_all);
+                let parent_bands = visible(parent_all);
+                if construct_bands.is_empty() {
+                    return None;
+                }
+                let separation = separation(&construct_bands, &parent_bands);
+                (separation >= params.resolution).then(|| DigestScreen {
+                    enzymes: enzymes.iter().map(|&e| e.clone()).collect(),
+                    construct_bands,
+                    parent_bands,
+                    separation,
+                })
+            })
+            .collect();
+
+        screens.sort_by(|a, b| {
+            a.enzymes
+                .len()
+                .cmp(&b.enzymes.len())
+                .then(b.separation.total_cmp(&a.separation))
+                .then(a.enzymes.cmp(&b.enzymes))
+        });
+        screens.truncate(params.max_digests);
+        screens
+    }
+
+    /// 酵素ごとの上鎖切断位置（メチル化で阻害されるサイトは切れないものとする）
+    fn cuts(&self, sequence: &str, topology: &Topology) -> BTreeMap<String, Vec<usize>> {
+        let enzymes: Vec<_> = self.restriction_service.enzymes().iter().collect();
+        let mut cuts: BTreeMap<String, Vec<usize>> = enzymes
+            .iter()
+            .map(|enzyme| (enzyme.name.clone(), Vec::new()))
+            .collect();
+        for site in self.restriction_service.scan(sequence, topology, &enzymes) {
+            if !site.is_blocked() {
+                cuts.entry(site.enzyme).or_default().push(site.cut_top);
+            }
+        }
+        for positions in cuts.values_mut() {
+            positions.sort_unstable();
+            positions.dedup();
+        }
+        cuts
+    }
+}
+
+/// 各バンドについて相手のパターンで最も近いバンドとの相対差を求め、その最大値を返す
+fn separation(a: &[usize], b: &[usize]) -> f64 {
+    let nearest = |band: usize, others: &[usize]| {
+        others
+            .iter()
+            .map(|&other| band.abs_diff(other) as f64 / band.max(other) as f64)
+            .fold(1.0, f64::min)
+    };
+    a.iter()
+        .map(|&band| nearest(band, b))
+        .chain(b.iter().map(|&band| nearest(band, a)))
+        .fold(0.0, f64::max)
+}
+
+/// 親配列上でフォワードの下流にリバースが結合する場合の産物長
+fn product_size(template: &str, forward: &str, reverse: &str) -> Option<usize> {
+    let forward_position = template.find(&forward.to_ascii_uppercase())?;
+    let reverse_site = iupac::reverse_complement(&reverse.to_ascii_uppercase());
+    let offset = template[forward_position..].find(&reverse_site)?;
+    Some(offset + reverse_site.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_sequence(length: usize, seed: u64) -> String {
+        let mut state = seed;
+        (0..length)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4] as char
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_band_separation() {
+        assert!(separation(&[3000, 1000], &[3000]) > 0.6);
+        assert!(separation(&[3000], &[3050]) < 0.1);
+    }
+
+    #[test]
+    fn test_suggests_digest_and_junction_pcr() {
+        let parent = random_sequence(3000, 5);
+        let insert = random_sequence(800, 9);
+        let construct = format!("{}{}{}", &parent[..1000], insert, &parent[1000..]);
+        let mut params = ScreeningParams::default();
+        params.junction.primer.tm_min = 40.0;
+        params.junction.primer.tm_max = 80.0;
+        params.junction.primer.gc_min = 20.0;
+        params.junction.primer.gc_max = 80.0;
+
+        let junctions = vec![
+            (1000, "vector | insert".to_string()),
+            (1800, "insert | vector".to_string()),
+        ];
+        let strategy = ScreeningService::new().suggest(
+            (&construct, &Topology::Circular),
+            (&parent, &Topology::Circular),
+            &junctions,
+            &params,
+        );
+
+        assert!(strategy.recommended.is_some());
+        let best = &strategy.digests[0];
+        assert!(best.separation >= params.resolution);
+        assert_ne!(best.construct_bands, best.parent_bands);
+        assert!(best.construct_bands.iter().sum::<usize>() <= construct.len());
+
+        assert_eq!(strategy.pcr.len(), 2);
+        for screen in &strategy.pcr {
+            assert_eq!(screen.correct_size, screen.pair.amplicon_length);
+            // 片方のプライマーは挿入配列に結合するので親配列では増幅されない
+            assert_eq!(screen.parent_size, None);
+        }
+    }
+}
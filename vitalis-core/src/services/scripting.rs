@@ -0,0 +1,362 @@
+// Service layer: Sandboxed Rhai scripts over a read-only sequence (feature "scripting")
+use crate::domain::primer::PrimerDesignService;
+use crate::domain::StatsService;
+use crate::services::{PrimerDesignServiceImpl, StatsServiceImpl};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, EvalAltResult, ImmutableString, Position, Scope, INT};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// 1回の実行で評価できる演算数の上限（無限ループを止める）。ヘルパー関数は処理する
+/// 塩基数を演算として数える
+const MAX_OPERATIONS: u64 = 10_000_000;
+/// 関数呼び出しの深さの上限
+const MAX_CALL_LEVELS: usize = 32;
+/// スクリプトが作る配列・マップの要素数の上限
+const MAX_COLLECTION_SIZE: usize = 1_000_000;
+/// スクリプトが作る文字列の長さの上限。定数 `sequence` として渡す配列の長さの上限でもあり、
+/// これより長い配列は `window` で区間ごとに読む
+const MAX_STRING_SIZE: usize = 1_000_000;
+
+#[derive(Error, Debug)]
+pub enum ScriptError {
+    #[error("Script error: {0}")]
+    Evaluation(String),
+    #[error("Script result cannot be converted to JSON: {0}")]
+    Conversion(String),
+}
+
+/// スクリプトから配列の区間 [start, end) を読む関数。ファイル上の配列は該当する行だけを読む
+pub type WindowReader = Arc<dyn Fn(usize, usize) -> Result<String, String> + Send + Sync>;
+
+/// 1回の実行で使った演算数（エンジンが数える演算と、ヘルパー関数が処理した塩基数）
+#[derive(Debug, Default)]
+struct Budget {
+    operations: AtomicU64,
+    charged: AtomicU64,
+}
+
+impl Budget {
+    fn used(&self) -> u64 {
+        self.operations.load(Ordering::Relaxed) + self.charged.load(Ordering::Relaxed)
+    }
+
+    /// `cost` 塩基分を処理する前に呼ぶ。上限を超えるなら処理せずにエラー
+    fn charge(&self, cost: usize) -> Result<(), Box<EvalAltResult>> {
+        if self.used().saturating_add(cost as u64) > MAX_OPERATIONS {
+            return Err(EvalAltResult::ErrorTooManyOperations(Position::NONE).into());
+        }
+        self.charged.fetch_add(cost as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn reset(&self) {
+        self.operations.store(0, Ordering::Relaxed);
+        self.charged.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Script execution service
+pub struct ScriptService {
+    engine: Engine,
+    budget: Arc<Budget>,
+}
+
+impl Default for ScriptService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptService {
+    /// ファイルやモジュールの読み込み・`eval` を禁じ、演算数・再帰の深さ・文字列の長さを
+    /// 制限したエンジンに、配列統計のヘルパー関数を登録する。ヘルパー関数は処理する塩基数を
+    /// 実行前に演算数として差し引くので、長い配列を何度渡しても上限で止まる
+    pub fn new() -> Self {
+        let budget = Arc::new(Budget::default());
+        let mut engine = Engine::new();
+        engine
+            .set_module_resolver(DummyModuleResolver::new())
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_array_size(MAX_COLLECTION_SIZE)
+            .set_max_map_size(MAX_COLLECTION_SIZE)
+            .set_max_string_size(MAX_STRING_SIZE)
+            .on_print(|_| {})
+            .on_debug(|_, _, _| {});
+        {
+            let budget = Arc::clone(&budget);
+            engine.on_progress(move |operations| {
+                budget.operations.store(operations, Ordering::Relaxed);
+                (budget.used() > MAX_OPERATIONS).then_some(Dynamic::UNIT)
+            });
+        }
+        engine.disable_symbol("eval");
+
+        let primers = Arc::new(PrimerDesignServiceImpl::new());
+        let stats = Arc::new(StatsServiceImpl::new());
+
+        {
+            let budget = Arc::clone(&budget);
+            engine.register_fn(
+                "gc_content",
+                move |s: &str| -> Result<f64, Box<EvalAltResult>> {
+                    budget.charge(s.len())?;
+                    Ok(gc_percent(s))
+                },
+            );
+        }
+        {
+            let budget = Arc::clone(&budget);
+            engine.register_fn(
+                "count",
+                move |s: &str, motif: &str| -> Result<INT, Box<EvalAltResult>> {
+                    budget.charge(s.len())?;
+                    Ok(count_overlapping(s, motif))
+                },
+            );
+        }
+        {
+            let (primers, budget) = (Arc::clone(&primers), Arc::clone(&budget));
+            engine.register_fn(
+                "reverse_complement",
+                move |s: &str| -> Result<String, Box<EvalAltResult>> {
+                    budget.charge(s.len())?;
+                    Ok(primers.reverse_complement(s))
+                },
+            );
+        }
+        {
+            let budget = Arc::clone(&budget);
+            engine.register_fn("tm", move |s: &str| -> Result<f64, Box<EvalAltResult>> {
+                budget.charge(s.len())?;
+                Ok(primers.calculate_tm(s) as f64)
+            });
+        }
+        {
+            let (stats, budget) = (Arc::clone(&stats), Arc::clone(&budget));
+            engine.register_fn(
+                "stats",
+                move |s: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+                    budget.charge(s.len())?;
+                    rhai::serde::to_dynamic(stats.calculate_detailed_stats(s))
+                },
+            );
+        }
+        {
+            let budget = Arc::clone(&budget);
+            engine.register_fn(
+                "window_stats",
+                move |s: &str, window: INT, step: INT| -> Result<Dynamic, Box<EvalAltResult>> {
+                    let windows = window_count(s.len(), window, step)?;
+                    budget.charge(windows.saturating_mul(window as usize).max(s.len()))?;
+                    rhai::serde::to_dynamic(stats.calculate_window_stats(
+                        s,
+                        window as usize,
+                        step as usize,
+                    ))
+                },
+            );
+        }
+
+        Self { engine, budget }
+    }
+
+    /// 配列（`read_window` で読む長さ `length` の配列）に対して `script` を実行し、最後の式の
+    /// 値を JSON で返す。定数 `seq_id`・`length` と、区間を読む関数 `window(start, end)` を渡す。
+    /// 配列が MAX_STRING_SIZE 以下なら定数 `sequence` としても渡す（それより長い配列、たとえば
+    /// ファイル上のゲノムは全体をメモリに載せないので `window` で読む）
+    pub fn run(
+        &mut self,
+        seq_id: &str,
+        length: usize,
+        read_window: WindowReader,
+        script: &str,
+    ) -> Result<serde_json::Value, ScriptError> {
+        self.budget.reset();
+        let mut scope = Scope::new();
+        scope.push_constant("seq_id", ImmutableString::from(seq_id));
+        scope.push_constant("length", length as INT);
+        if length <= MAX_STRING_SIZE {
+            let sequence = read_window(0, length).map_err(ScriptError::Evaluation)?;
+            scope.push_constant("sequence", ImmutableString::from(sequence));
+        }
+        let budget = Arc::clone(&self.budget);
+        self.engine.register_fn(
+            "window",
+            move |start: INT, end: INT| -> Result<String, Box<EvalAltResult>> {
+                if start < 0 || end < start || end - start > MAX_STRING_SIZE as INT {
+                    return Err(format!(
+                        "window must satisfy 0 <= start <= end and end - start <= {}",
+                        MAX_STRING_SIZE
+                    )
+                    .into());
+                }
+                let (start, end) = (start as usize, (end as usize).min(length));
+                if start >= end {
+                    return Ok(String::new());
+                }
+                budget.charge(end - start)?;
+                read_window(start, end).map_err(Into::into)
+            },
+        );
+
+        let result: Dynamic = self
+            .engine
+            .eval_with_scope(&mut scope, script)
+            .map_err(|e| ScriptError::Evaluation(e.to_string()))?;
+        rhai::serde::from_dynamic(&result).map_err(|e| ScriptError::Conversion(e.to_string()))
+    }
+}
+
+fn gc_percent(sequence: &str) -> f64 {
+    if sequence.is_empty() {
+        return 0.0;
+    }
+    let gc = sequence
+        .bytes()
+        .filter(|b| matches!(b.to_ascii_uppercase(), b'G' | b'C'))
+        .count();
+    gc as f64 / sequence.len() as f64 * 100.0
+}
+
+/// `window_stats` が計算するウィンドウ数。ウィンドウ長とステップが正で、ウィンドウ数が
+/// 配列・マップの要素数の上限以下であることを計算の前に確かめる
+fn window_count(length: usize, window: INT, step: INT) -> Result<usize, Box<EvalAltResult>> {
+    if window < 1 || step < 1 {
+        return Err("window and step must be positive".into());
+    }
+    let (window, step) = (window as usize, step as usize);
+    let windows = if window > length {
+        0
+    } else {
+        (length - window) / step + 1
+    };
+    if windows > MAX_COLLECTION_SIZE {
+        return Err(format!(
+            "window_stats would return {} windows (at most {})",
+            windows, MAX_COLLECTION_SIZE
+        )
+        .into());
+    }
+    Ok(windows)
+}
+
+/// 大文字・小文字を区別せず、重なりも含めて数える
+fn count_overlapping(sequence: &str, motif: &str) -> INT {
+    if motif.is_empty() || motif.len() > sequence.len() {
+        return 0;
+    }
+    let sequence = sequence.as_bytes();
+    let motif = motif.as_bytes();
+    sequence
+        .windows(motif.len())
+        .filter(|window| window.eq_ignore_ascii_case(motif))
+        .count() as INT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// メモリ上の配列を渡して実行する
+    fn run(
+        service: &mut ScriptService,
+        sequence: &str,
+        script: &str,
+    ) -> Result<serde_json::Value, ScriptError> {
+        let bases: Arc<str> = sequence.into();
+        let read_window: WindowReader =
+            Arc::new(move |start, end| Ok(bases[start..end].to_string()));
+        service.run("seq_1", sequence.len(), read_window, script)
+    }
+
+    #[test]
+    fn test_script_scores_windows_with_helpers() {
+        let mut service = ScriptService::new();
+        let script = r#"
+            let best = #{ start: 0, gc: -1.0 };
+            for start in range(0, length - 3, 4) {
+                let gc = gc_content(sequence.sub_string(start, 4));
+                if gc > best.gc { best = #{ start: start, gc: gc }; }
+            }
+            best.seq_id = seq_id;
+            best.cg = count(sequence, "cg");
+            best
+        "#;
+        let result = run(&mut service, "ATATGGCGATAT", script).unwrap();
+        assert_eq!(
+            result,
+            json!({ "start": 4, "gc": 100.0, "seq_id": "seq_1", "cg": 1 })
+        );
+
+        let stats = run(&mut service, "GGCC", "stats(sequence).gc_percent").unwrap();
+        assert_eq!(stats, json!(100.0));
+        assert_eq!(
+            run(&mut service, "AACG", "reverse_complement(sequence)").unwrap(),
+            json!("CGTT")
+        );
+    }
+
+    #[test]
+    fn test_script_sandbox_limits() {
+        let mut service = ScriptService::new();
+        // 定数の配列は書き換えられない
+        assert!(run(&mut service, "ACGT", "sequence = \"\"; 1").is_err());
+        // 無限ループは演算数の上限で止まる
+        assert!(matches!(
+            run(&mut service, "ACGT", "loop {}"),
+            Err(ScriptError::Evaluation(_))
+        ));
+        assert!(run(&mut service, "ACGT", "import \"secret\" as s; 1").is_err());
+        assert!(run(&mut service, "ACGT", "eval(\"1\")").is_err());
+        // 文字列を倍々に伸ばすと長さの上限で止まる
+        match run(&mut service, "ACGT", "let s = sequence; loop { s += s; }") {
+            Err(ScriptError::Evaluation(message)) => assert!(message.contains("too large")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_script_reads_long_sequences_by_window_within_budget() {
+        let mut service = ScriptService::new();
+        let genome = "ACGG".repeat(MAX_STRING_SIZE / 2);
+        // 長い配列は定数 `sequence` にせず、区間ごとに読む
+        assert!(run(&mut service, &genome, "sequence.len()").is_err());
+        assert_eq!(
+            run(
+                &mut service,
+                &genome,
+                "[length, window(4, 12), gc_content(window(0, 1000))]"
+            )
+            .unwrap(),
+            json!([genome.len(), "ACGGACGG", 75.0])
+        );
+        assert!(run(&mut service, &genome, "window(0, length)").is_err());
+
+        // ヘルパー関数は処理する塩基数を演算として数え、1回の呼び出しは数演算でも止まる
+        match run(
+            &mut service,
+            &genome,
+            "loop { gc_content(window(0, 1000000)); }",
+        ) {
+            Err(ScriptError::Evaluation(message)) => {
+                assert!(message.contains("Too many operations"))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        // ウィンドウの計算量は実行前に見積もって断る
+        assert!(run(
+            &mut service,
+            &genome,
+            "window_stats(window(0, 1000000), 100000, 1)"
+        )
+        .is_err());
+        assert!(run(&mut service, "ACGT", "window_stats(sequence, 0, 1)").is_err());
+        assert_eq!(
+            run(&mut service, "ACGTAC", "window_stats(sequence, 2, 2).len()").unwrap(),
+            json!(3)
+        );
+    }
+}
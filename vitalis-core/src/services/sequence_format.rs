@@ -0,0 +1,137 @@
+// Service layer: Formatted sequence text for clipboard copy
+use crate::domain::copy_format::CopyStyle;
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::iupac;
+
+/// Sequence formatting service
+pub struct SequenceFormatService;
+
+impl Default for SequenceFormatService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SequenceFormatService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Lay out `sequence` in numbered lines of blocks. `first_position` is the
+    /// 0-based position of the first base, used for the line numbers.
+    pub fn format(
+        &self,
+        sequence: &str,
+        first_position: usize,
+        style: &CopyStyle,
+        code: &GeneticCode,
+    ) -> String {
+        let bases: Vec<char> = sequence.chars().collect();
+        let complement: Vec<char> = bases.iter().map(|&b| iupac::complement(b)).collect();
+        let translation: Vec<char> = {
+            let mut track = vec![' '; bases.len()];
+            for (i, codon) in sequence.as_bytes().chunks_exact(3).enumerate() {
+                track[i * 3 + 1] = code.translate_codon(codon);
+            }
+            track
+        };
+
+        let line_width = if style.line_width == 0 {
+            bases.len().max(1)
+        } else {
+            style.line_width
+        };
+        let number_width = (first_position + bases.len()).to_string().len();
+
+        let layout = |track: &[char]| -> String {
+            track
+                .iter()
+                .enumerate()
+                .fold(String::new(), |mut text, (i, &c)| {
+                    if i > 0 && style.block_size > 0 && i % style.block_size == 0 {
+                        text.push(' ');
+                    }
+                    text.push(c);
+                    text
+                })
+        };
+
+        let mut lines = Vec::new();
+        for line_start in (0..bases.len()).step_by(line_width) {
+            let line_end = (line_start + line_width).min(bases.len());
+            let prefix = |label: Option<usize>| {
+                if !style.numbering {
+                    return String::new();
+                }
+                match label {
+                    Some(position) => format!("{:>width$} ", position, width = number_width),
+                    None => " ".repeat(number_width + 1),
+                }
+            };
+
+            lines.push(format!(
+                "{}{}",
+                prefix(Some(first_position + line_start + 1)),
+                layout(&bases[line_start..line_end])
+            ));
+            if style.complement {
+                lines.push(format!(
+                    "{}{}",
+                    prefix(None),
+                    layout(&complement[line_start..line_end])
+                ));
+            }
+            if style.translation {
+                lines.push(format!(
+                    "{}{}",
+                    prefix(None),
+                    layout(&translation[line_start..line_end]).trim_end()
+                ));
+            }
+            if style.complement || style.translation {
+                lines.push(String::new());
+            }
+        }
+
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbered_blocks_with_complement_and_translation() {
+        let style = CopyStyle {
+            block_size: 3,
+            line_width: 6,
+            complement: true,
+            translation: true,
+            ..Default::default()
+        };
+        let text =
+            SequenceFormatService::new().format("ATGGCCTAA", 99, &style, &GeneticCode::standard());
+
+        assert_eq!(
+            text,
+            "100 ATG GCC\n    TAC CGG\n     M   A\n\n106 TAA\n    ATT\n     *"
+        );
+    }
+
+    #[test]
+    fn test_plain_unnumbered_lines() {
+        let style = CopyStyle {
+            block_size: 0,
+            line_width: 4,
+            numbering: false,
+            ..Default::default()
+        };
+        let text =
+            SequenceFormatService::new().format("ACGTACGTAC", 0, &style, &GeneticCode::standard());
+        assert_eq!(text, "ACGT\nACGT\nAC");
+    }
+}
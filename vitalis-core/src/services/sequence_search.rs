@@ -0,0 +1,685 @@
+// Service layer: In-sequence search for viewer navigation
+use crate::domain::genetic_code::GeneticCode;
+use crate::domain::index::{same_bases, KmerIndex};
+use crate::domain::iupac;
+use crate::domain::search::{MotifMatch, SearchHit, SearchOptions, SearchPage, TranslatedHit};
+use crate::domain::{Range, Strand, Topology};
+use std::sync::Arc;
+
+/// Sequence search service
+pub struct SequenceSearchService {
+    /// 検索対象配列の k-mer インデックス（あれば完全一致検索に使う）
+    index: Option<Arc<KmerIndex>>,
+}
+
+impl Default for SequenceSearchService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SequenceSearchService {
+    pub fn new() -> Self {
+        Self { index: None }
+    }
+
+    /// 検索対象配列から構築した k-mer インデックスを使う
+    pub fn with_index(index: Arc<KmerIndex>) -> Self {
+        Self { index: Some(index) }
+    }
+
+    /// 全ヒットを位置順に取得
+    pub fn find_all(
+        &self,
+        sequence: &str,
+        topology: &Topology,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Vec<SearchHit> {
+        let length = sequence.len();
+        let query = query.to_ascii_uppercase();
+        if query.is_empty() || length == 0 {
+            return Vec::new();
+        }
+
+        let circular = *topology == Topology::Circular;
+        let text = iupac::search_text(sequence, circular, query.len());
+
+        let mut patterns = vec![(Strand::Forward, query.clone())];
+        let reverse = iupac::reverse_complement(&query);
+        if options.both_strands && reverse != query {
+            patterns.push((Strand::Reverse, reverse));
+        }
+
+        let mut hits: Vec<SearchHit> = patterns
+            .into_iter()
+            .flat_map(|(strand, pattern)| {
+                // インデックスはIUPAC一致（U=T）と同じ判定。原点をまたぐ一致は含まないため線状配列のみ
+                let indexed = self
+                    .index
+                    .as_ref()
+                    .filter(|_| options.iupac && !circular)
+                    .and_then(|index| index.find_exact(&pattern, &text));
+                let positions = match indexed {
+                    Some(positions) => positions,
+                    None if options.iupac => iupac::find_all(&pattern, &text, length, circular),
+                    None => find_literal(pattern.as_bytes(), &text, length, circular),
+                };
+                let query_len = pattern.len();
+                positions.into_iter().map(move |start| SearchHit {
+                    start,
+                    end: start + query_len,
+                    strand,
+                })
+            })
+            .collect();
+
+        hits.sort_by_key(|hit| (hit.start, hit.strand == Strand::Reverse));
+        hits
+    }
+
+    /// Return one page of hits plus the fewest `window_size` windows that
+    /// cover them, so the viewer only fetches the bases it will display.
+    pub fn search(
+        &self,
+        sequence: &str,
+        topology: &Topology,
+        query: &str,
+        options: &SearchOptions,
+    ) -> SearchPage {
+        let all_hits = self.find_all(sequence, topology, query, options);
+        page_of(&all_hits, sequence.len(), options)
+    }
+
+    /// 配列を読まずにインデックスだけで全ヒットを求め、1ページ分を返す。候補の位置の塩基だけを
+    /// `window(start, end)` で読んで確かめる（ファイル由来の配列向け）。インデックスを使えない
+    /// 検索（環状配列、文字どおりの一致、k 未満や縮重コードを含むクエリ）は None
+    pub fn search_indexed(
+        &self,
+        length: usize,
+        topology: &Topology,
+        query: &str,
+        options: &SearchOptions,
+        mut window: impl FnMut(usize, usize) -> Option<String>,
+    ) -> Option<SearchPage> {
+        let index = self
+            .index
+            .as_ref()
+            .filter(|_| options.iupac && *topology == Topology::Linear)?;
+        let query = query.to_ascii_uppercase();
+        let mut patterns = vec![(Strand::Forward, query.clone())];
+        let reverse = iupac::reverse_complement(&query);
+        if options.both_strands && reverse != query {
+            patterns.push((Strand::Reverse, reverse));
+        }
+
+        let mut hits = Vec::new();
+        for (strand, pattern) in patterns {
+            let pattern = pattern.as_bytes();
+            for start in index.candidates(pattern)? {
+                let end = start + pattern.len();
+                if end > length {
+                    continue;
+                }
+                if window(start, end).is_some_and(|bases| same_bases(bases.as_bytes(), pattern)) {
+                    hits.push(SearchHit { start, end, strand });
+                }
+            }
+        }
+        hits.sort_by_key(|hit| (hit.start, hit.strand == Strand::Reverse));
+        Some(page_of(&hits, length, options))
+    }
+
+    /// IUPAC モチーフに `max_mismatches` 以下で一致する区間を両鎖から全て探す（位置順）。
+    /// 回文のモチーフは順鎖のみ報告する
+    pub fn find_motif(
+        &self,
+        sequence: &str,
+        topology: &Topology,
+        motif: &str,
+        max_mismatches: usize,
+    ) -> Vec<MotifMatch> {
+        let length = sequence.len();
+        let motif = motif.to_ascii_uppercase();
+        let circular = *topology == Topology::Circular;
+        if motif.is_empty() || length == 0 || (!circular && motif.len() > length) {
+            return Vec::new();
+        }
+
+        let text = iupac::search_text(sequence, circular, motif.len());
+        let last = if circular {
+            length
+        } else {
+            length - motif.len() + 1
+        };
+
+        let mut patterns = vec![(Strand::Forward, motif.clone())];
+        let reverse = iupac::reverse_complement(&motif);
+        if reverse != motif {
+            patterns.push((Strand::Reverse, reverse));
+        }
+
+        let mut matches = Vec::new();
+        for (strand, pattern) in &patterns {
+            let pattern = pattern.as_bytes();
+            let starts: Box<dyn Iterator<Item = usize>> =
+                match self.motif_candidates(pattern, max_mismatches, circular) {
+                    Some(candidates) => {
+                        Box::new(candidates.into_iter().filter(move |&start| start < last))
+                    }
+                    None => Box::new(0..last),
+                };
+            for start in starts {
+                let window = &text[start..start + pattern.len()];
+                let mut mismatches = 0;
+                for (&code, &base) in pattern.iter().zip(window) {
+                    if !iupac::matches(code as char, base as char) {
+                        mismatches += 1;
+                        if mismatches > max_mismatches {
+                            break;
+                        }
+                    }
+                }
+                if mismatches > max_mismatches {
+                    continue;
+                }
+                let bases = String::from_utf8_lossy(window).into_owned();
+                matches.push(MotifMatch {
+                    start,
+                    end: start + pattern.len(),
+                    strand: *strand,
+                    mismatches,
+                    matched: match strand {
+                        Strand::Forward => bases,
+                        Strand::Reverse => iupac::reverse_complement(&bases),
+                    },
+                });
+            }
+        }
+
+        matches.sort_by_key(|hit| (hit.start, hit.strand == Strand::Reverse));
+        matches
+    }
+
+    /// ミスマッチ `max_mismatches` 以下の一致では、パターンを `max_mismatches + 1` 個に分けた
+    /// 断片のどれかが完全に一致する。全ての断片が k 塩基以上で ACGT のみなら、断片ごとの
+    /// インデックスの候補から開始位置の候補を集める（線状配列のみ。それ以外は None で全走査）
+    fn motif_candidates(
+        &self,
+        pattern: &[u8],
+        max_mismatches: usize,
+        circular: bool,
+    ) -> Option<Vec<usize>> {
+        let index = self.index.as_ref().filter(|_| !circular)?;
+        let pieces = max_mismatches + 1;
+        let piece_length = pattern.len() / pieces;
+        if piece_length < index.k() {
+            return None;
+        }
+        let mut starts = Vec::new();
+        for piece in 0..pieces {
+            let from = piece * piece_length;
+            let to = if piece + 1 == pieces {
+                pattern.len()
+            } else {
+                from + piece_length
+            };
+            starts.extend(
+                index
+                    .candidates(&pattern[from..to])?
+                    .into_iter()
+                    .filter_map(|start| start.checked_sub(from)),
+            );
+        }
+        starts.sort_unstable();
+        starts.dedup();
+        Some(starts)
+    }
+
+    /// アミノ酸配列 `peptide`（'X' は任意の残基）をコードしうる区間を6フレームの翻訳から探す
+    /// （位置順、配列IDは呼び出し側で設定する）
+    pub fn find_translated(
+        &self,
+        sequence: &str,
+        topology: &Topology,
+        peptide: &str,
+        code: &GeneticCode,
+    ) -> Vec<TranslatedHit> {
+        let length = sequence.len();
+        let peptide = peptide.to_ascii_uppercase().into_bytes();
+        let span = peptide.len() * 3;
+        if peptide.is_empty() || length == 0 {
+            return Vec::new();
+        }
+
+        // 環状配列は原点をまたぐ区間も翻訳できるよう先頭を末尾に継ぎ足す
+        let circular = *topology == Topology::Circular;
+        let text = iupac::search_text(sequence, circular, span - 1);
+        let text_length = text.len();
+        let forward = String::from_utf8_lossy(&text).into_owned();
+        let reverse = iupac::reverse_complement(&forward);
+
+        let mut hits = Vec::new();
+        for (strand, strand_text) in [(Strand::Forward, &forward), (Strand::Reverse, &reverse)] {
+            for offset in 0..3 {
+                let translation = code.translate(strand_text.get(offset..).unwrap_or_default());
+                let residues = translation.as_bytes();
+                for (i, window) in residues.windows(peptide.len()).enumerate() {
+                    let encodes = window
+                        .iter()
+                        .zip(&peptide)
+                        .all(|(&residue, &query)| query == b'X' || residue == query);
+                    if !encodes {
+                        continue;
+                    }
+                    let position = offset + i * 3;
+                    let start = match strand {
+                        Strand::Forward => position,
+                        Strand::Reverse => text_length - position - span,
+                    };
+                    // 継ぎ足した部分だけのヒットは原点側のヒットと重複する
+                    if start >= length {
+                        continue;
+                    }
+                    let end = start + span;
+                    let frame = match strand {
+                        Strand::Forward => (start % 3) as i8 + 1,
+                        Strand::Reverse => {
+                            -((length as isize - end as isize).rem_euclid(3) as i8 + 1)
+                        }
+                    };
+                    hits.push(TranslatedHit {
+                        seq_id: String::new(),
+                        start,
+                        end,
+                        strand,
+                        frame,
+                        coding: strand_text[position..position + span].to_string(),
+                        peptide: String::from_utf8_lossy(window).into_owned(),
+                    });
+                }
+            }
+        }
+
+        hits.sort_by_key(|hit| (hit.start, hit.strand == Strand::Reverse));
+        hits
+    }
+}
+
+fn find_literal(pattern: &[u8], text: &[u8], length: usize, circular: bool) -> Vec<usize> {
+    if !circular && pattern.len() > length {
+        return Vec::new();
+    }
+    let last = if circular {
+        length
+    } else {
+        length - pattern.len() + 1
+    };
+    (0..last)
+        .filter(|&i| text.get(i..i + pattern.len()) == Some(pattern))
+        .collect()
+}
+
+/// 配列をチャンクで受け取り、全体を保持せずに `SequenceSearchService::search` と同じ
+/// ページを作る（インデックスは使わない）。チャンクの境界をまたぐ一致を探せるよう
+/// 直前のチャンクの末尾（クエリ長 - 1 塩基）を残し、ページに入るヒットだけを保持する
+pub struct StreamingSearch {
+    patterns: Vec<(Strand, String)>,
+    iupac: bool,
+    circular: bool,
+    overlap: usize,
+    /// まだ開始位置を調べていない末尾と、その配列上の開始位置
+    carry: Vec<u8>,
+    carry_start: usize,
+    /// 環状配列で原点をまたぐ一致を探すための先頭の塩基
+    head: Vec<u8>,
+    length: usize,
+    /// ページの先頭のヒットの通し番号
+    skip: usize,
+    page: usize,
+    page_size: usize,
+    window_size: usize,
+    total_hits: usize,
+    hits: Vec<SearchHit>,
+}
+
+impl StreamingSearch {
+    pub fn new(query: &str, topology: &Topology, options: &SearchOptions) -> Self {
+        let query = query.to_ascii_uppercase();
+        let mut patterns = Vec::new();
+        if !query.is_empty() {
+            let reverse = iupac::reverse_complement(&query);
+            patterns.push((Strand::Forward, query.clone()));
+            if options.both_strands && reverse != query {
+                patterns.push((Strand::Reverse, reverse));
+            }
+        }
+        let page_size = options.page_size.max(1);
+        Self {
+            patterns,
+            iupac: options.iupac,
+            circular: *topology == Topology::Circular,
+            overlap: query.len().saturating_sub(1),
+            carry: Vec::new(),
+            carry_start: 0,
+            head: Vec::new(),
+            length: 0,
+            skip: options.page.saturating_mul(page_size),
+            page: options.page,
+            page_size,
+            window_size: options.window_size,
+            total_hits: 0,
+            hits: Vec::new(),
+        }
+    }
+
+    /// 配列の続き（大文字）を渡す
+    pub fn push(&mut self, chunk: &str) {
+        let chunk = chunk.as_bytes();
+        if self.circular && self.head.len() < self.overlap {
+            let take = (self.overlap - self.head.len()).min(chunk.len());
+            self.head.extend_from_slice(&chunk[..take]);
+        }
+        self.length += chunk.len();
+        self.carry.extend_from_slice(chunk);
+
+        // 末尾 overlap 塩基より前から始まる一致はこのチャンクまでで確定する
+        let scanned = self.carry.len().saturating_sub(self.overlap);
+        let text = std::mem::take(&mut self.carry);
+        self.scan(&text, scanned, false);
+        self.carry = text[scanned..].to_vec();
+        self.carry_start += scanned;
+    }
+
+    pub fn finish(mut self) -> SearchPage {
+        if self.circular && self.length > 0 {
+            // 残りの開始位置は原点をまたいで先頭の塩基に続ける
+            let scanned = self.carry.len();
+            let mut text = std::mem::take(&mut self.carry);
+            text.extend(self.head.iter().cycle().take(self.overlap));
+            self.scan(&text, scanned, true);
+        }
+        SearchPage {
+            total_hits: self.total_hits,
+            page: self.page,
+            page_size: self.page_size,
+            windows: cover_windows(&self.hits, self.length, self.window_size),
+            hits: self.hits,
+        }
+    }
+
+    /// `text` 上で `0..starts` から始まる一致を位置順に数える
+    fn scan(&mut self, text: &[u8], starts: usize, circular: bool) {
+        let length = if circular { starts } else { text.len() };
+        let mut found: Vec<SearchHit> = Vec::new();
+        for (strand, pattern) in &self.patterns {
+            let positions = if self.iupac {
+                iupac::find_all(pattern, text, length, circular)
+            } else {
+                find_literal(pattern.as_bytes(), text, length, circular)
+            };
+            found.extend(positions.into_iter().map(|start| SearchHit {
+                start: self.carry_start + start,
+                end: self.carry_start + start + pattern.len(),
+                strand: *strand,
+            }));
+        }
+        found.sort_by_key(|hit| (hit.start, hit.strand == Strand::Reverse));
+
+        for hit in found {
+            if self.total_hits >= self.skip && self.hits.len() < self.page_size {
+                self.hits.push(hit);
+            }
+            self.total_hits += 1;
+        }
+    }
+}
+
+/// 位置順の全ヒットから `options` のページを切り出す
+fn page_of(all_hits: &[SearchHit], length: usize, options: &SearchOptions) -> SearchPage {
+    let page_size = options.page_size.max(1);
+    let hits: Vec<SearchHit> = all_hits
+        .iter()
+        .skip(options.page.saturating_mul(page_size))
+        .take(page_size)
+        .cloned()
+        .collect();
+
+    SearchPage {
+        total_hits: all_hits.len(),
+        page: options.page,
+        page_size,
+        windows: cover_windows(&hits, length, options.window_size),
+        hits,
+    }
+}
+
+/// ヒットを覆う最小数の固定長ウィンドウ（ヒットがウィンドウより長い場合はヒット全体）
+fn cover_windows(hits: &[SearchHit], length: usize, window_size: usize) -> Vec<Range> {
+    let window_size = window_size.max(1);
+
+    // 原点をまたぐヒットは2つの区間に分割
+    let mut ranges: Vec<(usize, usize)> = hits
+        .iter()
+        .flat_map(|hit| {
+            if hit.end > length {
+                vec![(hit.start, length), (0, hit.end - length)]
+            } else {
+                vec![(hit.start, hit.end)]
+            }
+        })
+        .collect();
+    ranges.sort();
+
+    let mut windows: Vec<Range> = Vec::new();
+    for (start, end) in ranges {
+        if let Some(last) = windows.last() {
+            if start >= last.start && end <= last.end {
+                continue;
+            }
+        }
+        let window_end = (start + window_size).max(end).min(length);
+        windows.push(Range::new(start, window_end));
+    }
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paged_hits_and_windows() {
+        let sequence = format!(
+            "{}GAATTC{}GAATTC{}",
+            "A".repeat(10),
+            "C".repeat(20),
+            "T".repeat(3000)
+        );
+        let options = SearchOptions {
+            page_size: 1,
+            window_size: 100,
+            ..Default::default()
+        };
+
+        let service = SequenceSearchService::new();
+        let page = service.search(&sequence, &Topology::Linear, "GAATTC", &options);
+        assert_eq!(page.total_hits, 2);
+        assert_eq!(page.hits[0].start, 10);
+
+        let all = SearchOptions {
+            window_size: 100,
+            ..Default::default()
+        };
+        let page = service.search(&sequence, &Topology::Linear, "GAATTC", &all);
+        assert_eq!(page.windows, vec![Range::new(10, 110)]);
+    }
+
+    #[test]
+    fn test_streaming_search_matches_in_memory_search() {
+        let sequence = format!(
+            "TCAGG{}GAATTCGAAT{}CCTGA",
+            "A".repeat(37),
+            "ACGTN".repeat(11)
+        );
+        let service = SequenceSearchService::new();
+        for topology in [Topology::Linear, Topology::Circular] {
+            for (query, iupac) in [("GAATTC", true), ("TGATCAG", true), ("GTNAC", false)] {
+                for page in [0, 1, 2, usize::MAX] {
+                    let options = SearchOptions {
+                        iupac,
+                        page,
+                        page_size: 1,
+                        window_size: 10,
+                        ..Default::default()
+                    };
+                    let expected = service.search(&sequence, &topology, query, &options);
+                    // クエリより短いチャンクでも境界と原点をまたぐ一致を取りこぼさない
+                    for chunk_size in [3, 8, sequence.len()] {
+                        let mut streaming = StreamingSearch::new(query, &topology, &options);
+                        for chunk in sequence.as_bytes().chunks(chunk_size) {
+                            streaming.push(std::str::from_utf8(chunk).unwrap());
+                        }
+                        let page = streaming.finish();
+                        assert_eq!(page.total_hits, expected.total_hits);
+                        assert_eq!(page.hits, expected.hits);
+                        assert_eq!(page.windows, expected.windows);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reverse_strand_and_literal_mode() {
+        let service = SequenceSearchService::new();
+        let hits = service.find_all(
+            "AAGGTCTCAA",
+            &Topology::Linear,
+            "GAGACC",
+            &SearchOptions::default(),
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].strand, Strand::Reverse);
+
+        let literal = SearchOptions {
+            iupac: false,
+            ..Default::default()
+        };
+        assert!(service
+            .find_all("ACGTAC", &Topology::Linear, "ACN", &literal)
+            .is_empty());
+        assert_eq!(
+            service
+                .find_all(
+                    "ACGTAC",
+                    &Topology::Linear,
+                    "ACN",
+                    &SearchOptions::default()
+                )
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_motif_with_degenerate_codes_and_mismatches() {
+        let service = SequenceSearchService::new();
+        // TATA ボックス TATAWAWR
+        let sequence = "GGTATAAAAGGCCTATAAGAGCC";
+        let matches = service.find_motif(sequence, &Topology::Linear, "tatawawr", 0);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].start, matches[0].strand), (2, Strand::Forward));
+        assert_eq!(matches[0].matched, "TATAAAAG");
+
+        // 1 ミスマッチを許すと TATAAGAG も一致する
+        let matches = service.find_motif(sequence, &Topology::Linear, "TATAWAWR", 1);
+        assert_eq!(matches.len(), 2);
+        assert_eq!((matches[1].start, matches[1].mismatches), (13, 1));
+
+        // 逆鎖の一致が環状配列の原点をまたぐ
+        let matches = service.find_motif("TACGGGCCTTTTA", &Topology::Circular, "TATAWAWR", 0);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            (matches[0].start, matches[0].end, matches[0].strand),
+            (7, 15, Strand::Reverse)
+        );
+        assert_eq!(matches[0].matched, "TATAAAAG");
+    }
+
+    #[test]
+    fn test_translated_search_all_frames() {
+        let code = GeneticCode::standard();
+        let service = SequenceSearchService::new();
+        // DYKDDDDK（FLAG）を +2 フレームに、逆鎖に HHH を置く
+        let flag = "GATTACAAGGATGACGACGATAAG";
+        let sequence = format!("C{}AAA{}TT", flag, iupac::reverse_complement("CATCACCAT"));
+        let hits = service.find_translated(&sequence, &Topology::Linear, "dykxdddk", &code);
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].start, hits[0].end, hits[0].frame), (1, 25, 2));
+        assert_eq!(hits[0].coding, flag);
+        assert_eq!(hits[0].peptide, "DYKDDDDK");
+
+        let his = service.find_translated(&sequence, &Topology::Linear, "HHH", &code);
+        assert_eq!(his.len(), 1);
+        assert_eq!((his[0].start, his[0].end), (28, 37));
+        assert_eq!(his[0].strand, Strand::Reverse);
+        assert_eq!(his[0].frame, -3);
+
+        // 原点をまたぐコドン
+        let wrapped = format!("{}{}", &flag[20..], &flag[..20]);
+        let hits = service.find_translated(&wrapped, &Topology::Circular, "DYKDDDDK", &code);
+        assert_eq!((hits.len(), hits[0].start, hits[0].end), (1, 4, 28));
+        assert!(service
+            .find_translated(&wrapped, &Topology::Linear, "DYKDDDDK", &code)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_indexed_search_matches_scan() {
+        let sequence = format!("{}GGATCCAAGCTT{}GGATCC", "ACGT".repeat(50), "N".repeat(20));
+        let index = Arc::new(KmerIndex::build(&sequence, 4).unwrap());
+        let options = SearchOptions::default();
+
+        for query in ["GGATCC", "AAGCTT", "ACGTACGTAC", "GGNTCC"] {
+            assert_eq!(
+                SequenceSearchService::with_index(index.clone()).find_all(
+                    &sequence,
+                    &Topology::Linear,
+                    query,
+                    &options
+                ),
+                SequenceSearchService::new().find_all(
+                    &sequence,
+                    &Topology::Linear,
+                    query,
+                    &options
+                )
+            );
+        }
+
+        // ミスマッチを許すモチーフ検索は断片ごとの候補から調べる
+        for (motif, mismatches) in [
+            ("GGATCCAAGCTT", 0),
+            ("GGATCAAAGCTT", 1),
+            ("GGTTCCAAGCTTAC", 2),
+            ("ACGTACGTAC", 1),
+            ("GGAT", 0),
+        ] {
+            assert_eq!(
+                SequenceSearchService::with_index(index.clone()).find_motif(
+                    &sequence,
+                    &Topology::Linear,
+                    motif,
+                    mismatches
+                ),
+                SequenceSearchService::new().find_motif(
+                    &sequence,
+                    &Topology::Linear,
+                    motif,
+                    mismatches
+                )
+            );
+        }
+    }
+}
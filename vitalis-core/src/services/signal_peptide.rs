@@ -0,0 +1,137 @@
+// Service layer: Rule-based signal peptide detection
+use crate::domain::orf::Orf;
+use crate::domain::protein::{kyte_doolittle, SignalPeptide};
+
+/// H領域の最小長と平均ハイドロパシーの閾値
+const H_REGION_MIN: usize = 7;
+const H_REGION_THRESHOLD: f64 = 1.8;
+/// H領域が始まり得る最大位置
+const H_REGION_MAX_START: usize = 15;
+/// C領域（H領域終端から切断部位まで）の長さ
+const C_REGION_MIN: usize = 3;
+const C_REGION_MAX: usize = 7;
+
+/// Signal peptide detection service (von Heijne style N/H/C-region rules)
+pub struct SignalPeptideService;
+
+impl Default for SignalPeptideService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalPeptideService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 正電荷のN領域、疎水性のH領域、-3/-1 に小型中性残基を持つ切断部位が
+    /// この順に揃う場合にシグナルペプチドと判定する
+    pub fn predict(&self, protein: &str) -> Option<SignalPeptide> {
+        let residues: Vec<char> = protein.to_ascii_uppercase().chars().collect();
+        let hydropathy = |range: std::ops::Range<usize>| {
+            let len = range.len() as f64;
+            residues[range]
+                .iter()
+                .map(|&aa| kyte_doolittle(aa).unwrap_or(0.0))
+                .sum::<f64>()
+                / len
+        };
+
+        // H領域: 閾値を超える最初の疎水性ウィンドウを、閾値を保てる限り延長
+        let h_start = (1..=H_REGION_MAX_START).find(|&start| {
+            start + H_REGION_MIN <= residues.len()
+                && hydropathy(start..start + H_REGION_MIN) >= H_REGION_THRESHOLD
+        })?;
+        let mut h_end = h_start + H_REGION_MIN;
+        while h_end < residues.len()
+            && kyte_doolittle(residues[h_end]).unwrap_or(0.0) > 0.0
+            && hydropathy(h_start..h_end + 1) >= H_REGION_THRESHOLD
+        {
+            h_end += 1;
+        }
+
+        // N領域: H領域の手前で正味の正電荷
+        let n_region_charge: i32 = residues[..h_start]
+            .iter()
+            .map(|aa| match aa {
+                'K' | 'R' => 1,
+                'D' | 'E' => -1,
+                _ => 0,
+            })
+            .sum();
+        if n_region_charge < 1 {
+            return None;
+        }
+
+        // C領域: -3 と -1 に小型中性残基、+1 にプロリンを持たない切断部位
+        let small_minus_one = |aa: char| matches!(aa, 'A' | 'G' | 'S' | 'C' | 'T');
+        let small_minus_three =
+            |aa: char| matches!(aa, 'A' | 'G' | 'S' | 'C' | 'T' | 'V' | 'I' | 'L');
+        let candidates: Vec<usize> = (h_end + C_REGION_MIN..=h_end + C_REGION_MAX)
+            .filter(|&site| {
+                site < residues.len()
+                    && small_minus_one(residues[site - 1])
+                    && small_minus_three(residues[site - 3])
+                    && residues[site] != 'P'
+            })
+            .collect();
+        // 典型的な A-X-A を優先
+        let cleavage_site = candidates
+            .iter()
+            .copied()
+            .find(|&site| residues[site - 3] == 'A' && residues[site - 1] == 'A')
+            .or_else(|| candidates.first().copied())?;
+
+        Some(SignalPeptide {
+            n_region_end: h_start,
+            h_region_start: h_start,
+            h_region_end: h_end,
+            cleavage_site,
+            cleavage_motif: residues[cleavage_site - 3..cleavage_site].iter().collect(),
+            n_region_charge,
+            h_region_hydropathy: hydropathy(h_start..h_end),
+            dna_start: None,
+            dna_end: None,
+            strand: None,
+        })
+    }
+
+    /// ORFの翻訳産物ごとに予測し、シグナル配列を塩基配列上の座標に対応付ける
+    pub fn predict_orfs(&self, orfs: &[Orf]) -> Vec<SignalPeptide> {
+        orfs.iter()
+            .filter_map(|orf| {
+                let mut prediction = self.predict(&orf.protein)?;
+                let (dna_start, dna_end) = orf.dna_range(0, prediction.cleavage_site);
+                prediction.dna_start = Some(dna_start);
+                prediction.dna_end = Some(dna_end);
+                prediction.strand = Some(orf.strand);
+                Some(prediction)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predicts_pelb_leader() {
+        // pelB リーダー配列（切断部位は MA-QP の間ではなく A-M-A の直後）
+        let pelb = "MKYLLPTAAAGLLLLAAQPAMAMDIGINSD";
+        let prediction = SignalPeptideService::new().predict(pelb).unwrap();
+
+        assert!(prediction.n_region_charge >= 1);
+        assert_eq!(prediction.cleavage_site, 22);
+        assert_eq!(prediction.cleavage_motif, "AMA");
+    }
+
+    #[test]
+    fn test_rejects_cytosolic_protein() {
+        let service = SignalPeptideService::new();
+        assert!(service.predict("MSDEEKNQRTEDGSKPQETHRE").is_none());
+        // 疎水性領域はあるが N 末端に正電荷がない
+        assert!(service.predict("MDELLLLLLLLLAAAGAS").is_none());
+    }
+}
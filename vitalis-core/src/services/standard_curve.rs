@@ -0,0 +1,211 @@
+// Service layer: qPCR standard curve dilution planning for primer pair amplicons
+use crate::domain::concentration::{ConcentrationUnit, MoleculeKind};
+use crate::domain::dilution::{StandardCurveParams, StandardCurvePlan, StandardPoint};
+use crate::domain::primer::PrimerPair;
+use crate::domain::warning::{WarningCategory, WarningCode};
+use crate::services::concentration::{ConcentrationError, ConcentrationService};
+use crate::services::dilution::DilutionService;
+use thiserror::Error;
+
+/// これ未満のコピー数ではポアソン分布によるばらつきが大きい
+const MIN_RELIABLE_COPIES: f64 = 10.0;
+/// MIQE が推奨する標準曲線の最小点数
+const MIN_RECOMMENDED_POINTS: usize = 5;
+/// 分注の分解能（0.1 µL 単位）
+const PIPETTE_STEPS_PER_UL: f64 = 10.0;
+
+#[derive(Error, Debug)]
+pub enum StandardCurveError {
+    #[error(transparent)]
+    Concentration(#[from] ConcentrationError),
+    #[error("Stock ({stock:.3e} copies/µL) is below the top standard ({top:.3e} copies/µL)")]
+    StockTooDilute { stock: f64, top: f64 },
+}
+
+/// Standard curve planning service
+pub struct StandardCurveService {
+    concentration: ConcentrationService,
+    dilution: DilutionService,
+}
+
+impl Default for StandardCurveService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StandardCurveService {
+    pub fn new() -> Self {
+        Self {
+            concentration: ConcentrationService::new(),
+            dilution: DilutionService::new(),
+        }
+    }
+
+    /// 増幅産物の標準曲線を計画し、結果と警告をペアのバリデーション結果に追加
+    pub fn apply(
+        &self,
+        pair: &mut PrimerPair,
+        stock: f64,
+        unit: ConcentrationUnit,
+        params: &StandardCurveParams,
+    ) -> Result<(), StandardCurveError> {
+        let plan = self.plan(&pair.amplicon_sequence, stock, unit, params)?;
+        let validation = &mut pair.validation_results;
+        validation
+            .warnings
+            .retain(|warning| warning.category != WarningCategory::StandardCurve);
+        validation.warnings.extend(plan.warnings.iter().cloned());
+        validation.standard_curve = Some(plan);
+        Ok(())
+    }
+
+    /// 直鎖 dsDNA の増幅産物ストックから最も濃い標準を作り、そこから `fold` 倍ずつ
+    /// 段階希釈する。各チューブは次へ持ち出した後に `standard_volume_ul` 残るよう、
+    /// 持ち出し量 V/(fold−1) と希釈液 V を組む
+    pub fn plan(
+        &self,
+        amplicon: &str,
+        stock: f64,
+        unit: ConcentrationUnit,
+        params: &StandardCurveParams,
+    ) -> Result<StandardCurvePlan, StandardCurveError> {
+        let conversion =
+            self.concentration
+                .convert(amplicon, MoleculeKind::DsDna, false, stock, unit)?;
+        let top = params.top_copies_per_reaction / params.template_volume_ul;
+        if conversion.copies_per_ul < top {
+            return Err(StandardCurveError::StockTooDilute {
+                stock: conversion.copies_per_ul,
+                top,
+            });
+        }
+
+        let volume = params.standard_volume_ul;
+        let transfer_ul = round_nearest(volume / (params.fold - 1.0));
+        let preparation =
+            self.dilution
+                .dilution_series(conversion.copies_per_ul, top, volume + transfer_ul);
+
+        let mut standards = Vec::with_capacity(params.points);
+        let mut copies_per_ul = top;
+        let mut volumes = (0.0, 0.0);
+        if let Some(last) = preparation.steps.last() {
+            copies_per_ul = last.concentration;
+            volumes = (last.transfer_ul, last.diluent_ul);
+        }
+        for index in 0..params.points {
+            if index > 0 {
+                copies_per_ul *= transfer_ul / (transfer_ul + volume);
+                volumes = (transfer_ul, volume);
+            }
+            standards.push(StandardPoint {
+                label: format!("S{}", index + 1),
+                copies_per_ul,
+                copies_per_reaction: copies_per_ul * params.template_volume_ul,
+                transfer_ul: volumes.0,
+                diluent_ul: volumes.1,
+            });
+        }
+
+        let mut warnings = Vec::new();
+        if let Some(lowest) = standards.last() {
+            if lowest.copies_per_reaction < MIN_RELIABLE_COPIES {
+                warnings.push(
+                    WarningCode::LowCopyStandard {
+                        label: lowest.label.clone(),
+                        copies: lowest.copies_per_reaction,
+                        min_copies: MIN_RELIABLE_COPIES,
+                    }
+                    .into(),
+                );
+            }
+        }
+        if params.points < MIN_RECOMMENDED_POINTS {
+            warnings.push(
+                WarningCode::FewStandards {
+                    points: params.points,
+                    recommended: MIN_RECOMMENDED_POINTS,
+                }
+                .into(),
+            );
+        }
+
+        Ok(StandardCurvePlan {
+            amplicon_length: conversion.length,
+            molecular_weight: conversion.molecular_weight,
+            stock_ng_per_ul: conversion.ng_per_ul,
+            stock_copies_per_ul: conversion.copies_per_ul,
+            preparation,
+            standards,
+            template_volume_ul: params.template_volume_ul,
+            replicates: params.replicates,
+            wells: (params.points + 1) * params.replicates,
+            warnings,
+        })
+    }
+}
+
+fn round_nearest(volume: f64) -> f64 {
+    (volume * PIPETTE_STEPS_PER_UL).round() / PIPETTE_STEPS_PER_UL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMPLICON: &str = "ATGGCTAGCTAGGACTTACGATCGATCGGATCCAGTCAGTACGATGCATGCAAGCTTGACTGA";
+
+    #[test]
+    fn test_plan_from_copies_stock() {
+        let params = StandardCurveParams::default();
+        let plan = StandardCurveService::new()
+            .plan(AMPLICON, 1e10, ConcentrationUnit::CopiesPerUl, &params)
+            .unwrap();
+
+        // 1e10 → 5e6 copies/µL（2000 倍）は 100 µL に 100 倍を挟んでから 20 倍
+        let folds: Vec<f64> = plan
+            .preparation
+            .steps
+            .iter()
+            .map(|step| step.fold)
+            .collect();
+        assert_eq!(folds.len(), 2);
+        assert!((folds[1] - 20.0).abs() < 1e-9);
+        assert_eq!(plan.standards.len(), 7);
+        assert!((plan.standards[0].copies_per_reaction - 1e7).abs() < 1.0);
+        assert_eq!(
+            (plan.standards[1].transfer_ul, plan.standards[1].diluent_ul),
+            (10.0, 90.0)
+        );
+        assert!((plan.standards[6].copies_per_reaction - 10.0).abs() < 1e-6);
+        assert_eq!(plan.wells, 24);
+        assert!(plan.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_dilute_stock_is_rejected_and_low_points_warn() {
+        let service = StandardCurveService::new();
+        let params = StandardCurveParams::default();
+        assert!(matches!(
+            service.plan(AMPLICON, 1e6, ConcentrationUnit::CopiesPerUl, &params),
+            Err(StandardCurveError::StockTooDilute { .. })
+        ));
+
+        let params = StandardCurveParams {
+            top_copies_per_reaction: 1e3,
+            points: 4,
+            ..StandardCurveParams::default()
+        };
+        let plan = service
+            .plan(AMPLICON, 1.0, ConcentrationUnit::NgPerUl, &params)
+            .unwrap();
+        assert!(plan.stock_copies_per_ul > 1e10);
+        assert_eq!(plan.warnings.len(), 2);
+        assert!(matches!(
+            &plan.warnings[0].code,
+            WarningCode::LowCopyStandard { label, .. } if label == "S4"
+        ));
+        assert!(plan.warnings[0].message.starts_with("S4 has 1.0 copies"));
+    }
+}
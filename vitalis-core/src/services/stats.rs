@@ -1,6 +1,16 @@
 // Service layer: Statistics service implementation
-use crate::domain::{BaseCount, DetailedStats, StatsService, WindowStats};
-use std::collections::HashMap;
+use crate::domain::index::encode_base;
+use crate::domain::{
+    BaseCount, DetailedStats, GapStats, KmerCount, KmerFrequency, KmerSpectrum, Range,
+    StatsOptions, StatsService, Topology, WindowStats,
+};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+const BASES: [u8; 4] = *b"ACGT";
+/// スペクトルに載せる k-mer の数（出現数・観測/期待比それぞれ）
+const REPORTED_KMERS: usize = 20;
+/// ストリーミング集計で3塩基の出現を記録するビット表の語数（任意の3バイト）
+const TRIMER_WORDS: usize = (1 << 24) / 64;
 
 /// Statistics service implementation
 pub struct StatsServiceImpl;
@@ -24,146 +34,686 @@ impl StatsServiceImpl {
             *freq_map.entry(c.to_ascii_uppercase()).or_insert(0) += 1;
         }
 
-        // Calculate entropy
-        let mut entropy = 0.0;
-        for count in freq_map.values() {
-            let p = *count as f64 / length;
-            if p > 0.0 {
-                entropy -= p * p.log2();
+        entropy_of_counts(freq_map.values().copied(), sequence.len())
+    }
+
+    /// k-mer を2ビット符号で数え、出現回数の分布と偏りの大きい k-mer を求める。
+    /// 4^k が配列長の2倍以下なら 4^k 個のカウンタ、そうでなければ出現した符号を
+    /// 並べ替えて数える（どちらか小さい方のメモリで済む）。期待値は塩基組成から求める
+    pub fn calculate_kmer_spectrum(&self, sequence: &str, k: usize) -> KmerSpectrum {
+        let possible = 1u64 << (2 * k);
+        let mask = possible - 1;
+        let dense = possible <= 2 * sequence.len() as u64;
+
+        let mut base_counts = [0u64; 4];
+        let mut counters = if dense {
+            vec![0u32; possible as usize]
+        } else {
+            Vec::new()
+        };
+        let mut codes = Vec::new();
+        let mut code = 0u64;
+        let mut run = 0;
+        let mut total = 0u64;
+        for &base in sequence.as_bytes() {
+            let Some(bits) = encode_base(base) else {
+                run = 0;
+                continue;
+            };
+            base_counts[bits as usize] += 1;
+            code = ((code << 2) | bits) & mask;
+            run += 1;
+            if run >= k {
+                total += 1;
+                if dense {
+                    counters[code as usize] += 1;
+                } else {
+                    codes.push(code);
+                }
             }
         }
 
-        entropy
+        let bases = base_counts.iter().sum::<u64>().max(1) as f64;
+        let probabilities = base_counts.map(|count| count as f64 / bases);
+        let count_of = |code: u64, count: u64| {
+            let expected = total as f64
+                * (0..k)
+                    .map(|i| probabilities[((code >> (2 * i)) & 3) as usize])
+                    .product::<f64>();
+            KmerCount {
+                kmer: decode(code, k),
+                count,
+                expected,
+                ratio: if expected > 0.0 {
+                    count as f64 / expected
+                } else {
+                    0.0
+                },
+            }
+        };
+
+        let mut distinct = 0u64;
+        let mut histogram: BTreeMap<u64, u64> = BTreeMap::new();
+        let mut most_frequent: Vec<(u64, u64)> = Vec::new();
+        let mut over_represented: Vec<KmerCount> = Vec::new();
+        let mut visit = |code: u64, count: u64| {
+            distinct += 1;
+            *histogram.entry(count).or_default() += 1;
+            most_frequent.push((code, count));
+            if most_frequent.len() > 4 * REPORTED_KMERS {
+                keep_top(&mut most_frequent, |a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            }
+            if count >= 2 {
+                over_represented.push(count_of(code, count));
+                if over_represented.len() > 4 * REPORTED_KMERS {
+                    keep_top(&mut over_represented, by_ratio);
+                }
+            }
+        };
+
+        if dense {
+            for (code, &count) in counters.iter().enumerate() {
+                if count > 0 {
+                    visit(code as u64, count as u64);
+                }
+            }
+        } else {
+            codes.sort_unstable();
+            for group in codes.chunk_by(|a, b| a == b) {
+                visit(group[0], group.len() as u64);
+            }
+        }
+
+        keep_top(&mut most_frequent, |a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        keep_top(&mut over_represented, by_ratio);
+
+        KmerSpectrum {
+            k,
+            total_kmers: total,
+            distinct_kmers: distinct,
+            possible_kmers: possible,
+            spectrum: histogram
+                .into_iter()
+                .map(|(occurrences, kmers)| KmerFrequency { occurrences, kmers })
+                .collect(),
+            most_frequent: most_frequent
+                .into_iter()
+                .map(|(code, count)| count_of(code, count))
+                .collect(),
+            over_represented,
+        }
     }
 
-    /// Calculate linguistic complexity (ratio of unique k-mers)
-    fn calculate_complexity(&self, sequence: &str) -> f64 {
-        if sequence.len() < 3 {
+    /// Calculate linguistic complexity (ratio of unique k-mers); 3-mers never
+    /// span two segments
+    fn calculate_complexity(&self, segments: &[&str]) -> f64 {
+        let positions: usize = segments
+            .iter()
+            .map(|segment| segment.len().saturating_sub(2))
+            .sum();
+        if positions == 0 {
             return 0.0;
         }
 
         let mut unique_3mers = HashMap::new();
-        let chars: Vec<char> = sequence.chars().collect();
-
-        for window in chars.windows(3) {
-            let kmer = window.iter().collect::<String>();
-            *unique_3mers.entry(kmer).or_insert(0) += 1;
+        for segment in segments {
+            let chars: Vec<char> = segment.chars().collect();
+            for window in chars.windows(3) {
+                let kmer = window.iter().collect::<String>();
+                *unique_3mers.entry(kmer).or_insert(0) += 1;
+            }
         }
 
-        let max_possible = (sequence.len() - 2).min(64); // 4^3 = 64 possible 3-mers
+        let max_possible = positions.min(64); // 4^3 = 64 possible 3-mers
         let unique_count = unique_3mers.len();
 
         unique_count as f64 / max_possible as f64
     }
 }
 
-impl StatsService for StatsServiceImpl {
-    fn calculate_detailed_stats(&self, sequence: &str) -> DetailedStats {
-        let mut base_counts = BaseCount::new();
-        let mut dinucleotides: HashMap<String, usize> = HashMap::new();
+/// `min_length` 塩基以上の N の連続（0-based 半開区間）
+pub fn find_gaps(sequence: &str, min_length: usize) -> Vec<Range> {
+    let bytes = sequence.as_bytes();
+    let mut gaps = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].eq_ignore_ascii_case(&b'N') {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && bytes[i].eq_ignore_ascii_case(&b'N') {
+            i += 1;
+        }
+        if i - start >= min_length {
+            gaps.push(Range::new(start, i));
+        }
+    }
+    gaps
+}
 
-        let chars: Vec<char> = sequence.chars().collect();
-        let length = chars.len();
+/// 塩基数などの集計から割合・スキューを求めて詳細統計にまとめる。
+/// 割合の分母はギャップを除いた長さ
+fn summarize(
+    length: usize,
+    effective_length: usize,
+    base_counts: BaseCount,
+    dinucleotide_counts: HashMap<String, usize>,
+    entropy: f64,
+    complexity: f64,
+    gap_stats: Option<GapStats>,
+) -> DetailedStats {
+    let percent = |count: usize| {
+        if effective_length > 0 {
+            (count as f64 / effective_length as f64) * 100.0
+        } else {
+            0.0
+        }
+    };
+    let gc_percent = percent(base_counts.g + base_counts.c);
+    let at_percent = percent(base_counts.a + base_counts.t);
+    let n_percent = percent(base_counts.n);
 
-        // Count bases
-        for c in &chars {
-            match c.to_ascii_uppercase() {
-                'A' => base_counts.a += 1,
-                'T' | 'U' => base_counts.t += 1,
-                'G' => base_counts.g += 1,
-                'C' => base_counts.c += 1,
-                'N' => base_counts.n += 1,
-                _ => base_counts.other += 1,
-            }
+    // Calculate GC skew: (G - C) / (G + C)
+    let gc_skew = if base_counts.g + base_counts.c > 0 {
+        (base_counts.g as f64 - base_counts.c as f64)
+            / (base_counts.g as f64 + base_counts.c as f64)
+    } else {
+        0.0
+    };
+
+    // Calculate AT skew: (A - T) / (A + T)
+    let at_skew = if base_counts.a + base_counts.t > 0 {
+        (base_counts.a as f64 - base_counts.t as f64)
+            / (base_counts.a as f64 + base_counts.t as f64)
+    } else {
+        0.0
+    };
+
+    DetailedStats {
+        length,
+        gc_percent,
+        at_percent,
+        n_percent,
+        base_counts,
+        dinucleotide_counts,
+        gc_skew,
+        at_skew,
+        entropy,
+        complexity,
+        codon_usage: None,   // Will be calculated separately if needed
+        quality_stats: None, // Will be added from FASTQ data if available
+        gap_stats,
+    }
+}
+
+/// 文字ごとの出現数から Shannon エントロピーを求める
+fn entropy_of_counts(counts: impl IntoIterator<Item = usize>, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut entropy = 0.0;
+    for count in counts {
+        let p = count as f64 / total as f64;
+        if p > 0.0 {
+            entropy -= p * p.log2();
         }
+    }
+    entropy
+}
 
-        // Count dinucleotides
-        for window in chars.windows(2) {
-            if window.len() == 2 {
-                let dinuc = format!("{}{}", window[0], window[1]).to_uppercase();
-                *dinucleotides.entry(dinuc).or_insert(0) += 1;
+/// 配列をチャンクで受け取り、全体を保持せずに詳細統計とウィンドウ統計を集計する。
+/// 結果は大文字の配列に対する `calculate_detailed_stats_with_options` /
+/// `calculate_window_stats_with_options` と同じ。N の連続はギャップかどうかが
+/// 決まるまで（`min_gap_length` 塩基に達するか途切れるまで）保留する
+pub struct StreamingStats {
+    min_gap_length: Option<usize>,
+    length: usize,
+    /// 進行中の N の連続の長さと、ギャップと確定したか
+    n_run: usize,
+    n_run_is_gap: bool,
+    base_counts: BaseCount,
+    symbols: [usize; 256],
+    /// 2バイトを添字にした二塩基の出現数
+    dinucleotides: Vec<usize>,
+    /// 出現した3塩基のビット表とその種類数
+    trimers: Vec<u64>,
+    unique_trimers: usize,
+    trimer_positions: usize,
+    /// 直前の2塩基（ギャップを挟むと途切れる）
+    previous: [Option<u8>; 2],
+    gap_count: usize,
+    gap_length: usize,
+    largest_gap: usize,
+    windows: Option<WindowAccumulator>,
+}
+
+/// 直近 `window_size` 塩基の集計（ギャップの塩基は GC% とエントロピーに含めない）
+struct WindowAccumulator {
+    window_size: usize,
+    step: usize,
+    /// 環状配列として原点をまたぐウィンドウを作る（先頭の塩基を `head` に残す）
+    circular: bool,
+    head: Vec<(u8, bool)>,
+    /// 線形配列の末尾の不完全なウィンドウも出力する
+    partial: bool,
+    /// ギャップかどうかが確定した塩基の数
+    resolved: usize,
+    recent: VecDeque<(u8, bool)>,
+    symbols: [usize; 256],
+    /// これまでに現れた文字（エントロピーはこの中だけを見る）
+    alphabet: Vec<u8>,
+    gc: usize,
+    gap_bases: usize,
+    stats: Vec<WindowStats>,
+}
+
+impl StreamingStats {
+    /// `windows` に (ウィンドウ幅, ステップ) を渡すとウィンドウ統計も集計する
+    pub fn new(
+        options: &StatsOptions,
+        topology: &Topology,
+        windows: Option<(usize, usize)>,
+    ) -> Self {
+        Self {
+            min_gap_length: options.min_gap_length,
+            length: 0,
+            n_run: 0,
+            n_run_is_gap: false,
+            base_counts: BaseCount::new(),
+            symbols: [0; 256],
+            dinucleotides: vec![0; 1 << 16],
+            trimers: vec![0; TRIMER_WORDS],
+            unique_trimers: 0,
+            trimer_positions: 0,
+            previous: [None, None],
+            gap_count: 0,
+            gap_length: 0,
+            largest_gap: 0,
+            windows: windows.map(|(window_size, step)| WindowAccumulator {
+                window_size,
+                step: step.max(1),
+                circular: *topology == Topology::Circular,
+                head: Vec::new(),
+                partial: options.partial_windows,
+                resolved: 0,
+                recent: VecDeque::with_capacity(window_size + 1),
+                symbols: [0; 256],
+                alphabet: Vec::new(),
+                gc: 0,
+                gap_bases: 0,
+                stats: Vec::new(),
+            }),
+        }
+    }
+
+    /// 配列の続きを渡す（チャンクの境界はどこでもよい）
+    pub fn push(&mut self, chunk: &str) {
+        for &byte in chunk.as_bytes() {
+            let base = byte.to_ascii_uppercase();
+            self.length += 1;
+            match self.min_gap_length {
+                Some(min_length) if base == b'N' => self.extend_n_run(min_length),
+                _ => {
+                    self.close_n_run();
+                    self.resolve(base, false);
+                }
             }
         }
+    }
+
+    /// 詳細統計と（指定していれば）ウィンドウ統計
+    pub fn finish(mut self) -> (DetailedStats, Vec<WindowStats>) {
+        self.close_n_run();
 
-        // Calculate percentages
-        let gc_percent = if length > 0 {
-            ((base_counts.g + base_counts.c) as f64 / length as f64) * 100.0
+        let effective_length = self.length - self.gap_length;
+        let entropy = entropy_of_counts(self.symbols, effective_length);
+        let complexity = if self.trimer_positions > 0 {
+            self.unique_trimers as f64 / self.trimer_positions.min(64) as f64
         } else {
             0.0
         };
+        let dinucleotide_counts = self
+            .dinucleotides
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(code, &count)| {
+                let dinucleotide = [(code >> 8) as u8 as char, code as u8 as char];
+                (dinucleotide.iter().collect(), count)
+            })
+            .collect();
+
+        let detailed = summarize(
+            self.length,
+            effective_length,
+            self.base_counts,
+            dinucleotide_counts,
+            entropy,
+            complexity,
+            self.min_gap_length.map(|min_gap_length| GapStats {
+                min_gap_length,
+                count: self.gap_count,
+                total_length: self.gap_length,
+                largest: self.largest_gap,
+                effective_length,
+            }),
+        );
+        let windows = self
+            .windows
+            .map(WindowAccumulator::finish)
+            .unwrap_or_default();
+        (detailed, windows)
+    }
+
+    fn extend_n_run(&mut self, min_length: usize) {
+        self.n_run += 1;
+        if self.n_run_is_gap {
+            self.resolve(b'N', true);
+        } else if self.n_run == min_length {
+            self.n_run_is_gap = true;
+            for _ in 0..min_length {
+                self.resolve(b'N', true);
+            }
+        }
+    }
 
-        let at_percent = if length > 0 {
-            ((base_counts.a + base_counts.t) as f64 / length as f64) * 100.0
+    /// N の連続が途切れたら、ギャップでなかった分を通常の塩基として数える
+    fn close_n_run(&mut self) {
+        if self.n_run == 0 {
+            return;
+        }
+        if self.n_run_is_gap {
+            self.gap_count += 1;
+            self.gap_length += self.n_run;
+            self.largest_gap = self.largest_gap.max(self.n_run);
         } else {
-            0.0
-        };
+            for _ in 0..self.n_run {
+                self.resolve(b'N', false);
+            }
+        }
+        self.n_run = 0;
+        self.n_run_is_gap = false;
+    }
 
-        let n_percent = if length > 0 {
-            (base_counts.n as f64 / length as f64) * 100.0
+    fn resolve(&mut self, base: u8, gap: bool) {
+        if gap {
+            self.previous = [None, None];
         } else {
-            0.0
-        };
+            self.count(base);
+        }
+        if let Some(windows) = &mut self.windows {
+            windows.push(base, gap);
+        }
+    }
+
+    fn count(&mut self, base: u8) {
+        match base {
+            b'A' => self.base_counts.a += 1,
+            b'T' | b'U' => self.base_counts.t += 1,
+            b'G' => self.base_counts.g += 1,
+            b'C' => self.base_counts.c += 1,
+            b'N' => self.base_counts.n += 1,
+            _ => self.base_counts.other += 1,
+        }
+        self.symbols[base as usize] += 1;
+
+        if let Some(last) = self.previous[1] {
+            self.dinucleotides[(last as usize) << 8 | base as usize] += 1;
+            if let Some(first) = self.previous[0] {
+                let code = (first as usize) << 16 | (last as usize) << 8 | base as usize;
+                let (word, bit) = (code / 64, 1u64 << (code % 64));
+                if self.trimers[word] & bit == 0 {
+                    self.trimers[word] |= bit;
+                    self.unique_trimers += 1;
+                }
+                self.trimer_positions += 1;
+            }
+        }
+        self.previous = [self.previous[1], Some(base)];
+    }
+}
 
-        // Calculate GC skew: (G - C) / (G + C)
-        let gc_skew = if base_counts.g + base_counts.c > 0 {
-            (base_counts.g as f64 - base_counts.c as f64)
-                / (base_counts.g as f64 + base_counts.c as f64)
+impl WindowAccumulator {
+    fn push(&mut self, base: u8, gap: bool) {
+        if self.circular && self.head.len() + 1 < self.window_size {
+            self.head.push((base, gap));
+        }
+        self.add(base, gap, true);
+        self.recent.push_back((base, gap));
+        if self.recent.len() > self.window_size {
+            if let Some((base, gap)) = self.recent.pop_front() {
+                self.add(base, gap, false);
+            }
+        }
+        self.resolved += 1;
+
+        if self.resolved >= self.window_size
+            && (self.resolved - self.window_size).is_multiple_of(self.step)
+        {
+            self.emit(self.resolved - self.window_size);
+        }
+    }
+
+    /// 配列の終わりで、環状なら先頭の塩基を続けて原点をまたぐウィンドウを、
+    /// 線形で指定があれば末尾の短いウィンドウを加える
+    fn finish(mut self) -> Vec<WindowStats> {
+        let length = self.resolved;
+        if self.circular && self.window_size <= length {
+            self.circular = false;
+            for (base, gap) in std::mem::take(&mut self.head) {
+                self.push(base, gap);
+            }
+        } else if self.partial {
+            let first = (length + 1).saturating_sub(self.window_size);
+            let mut position = first.div_ceil(self.step) * self.step;
+            while position < length {
+                while length - self.recent.len() < position {
+                    if let Some((base, gap)) = self.recent.pop_front() {
+                        self.add(base, gap, false);
+                    }
+                }
+                self.emit(position);
+                position += self.step;
+            }
+        }
+        self.stats
+    }
+
+    /// `recent` の塩基を `position` からのウィンドウとして記録
+    fn emit(&mut self, position: usize) {
+        let window_size = self.recent.len();
+        let counted = window_size - self.gap_bases;
+        let gc_percent = if counted > 0 {
+            (self.gc as f64 / counted as f64) * 100.0
         } else {
             0.0
         };
+        self.stats.push(WindowStats {
+            position,
+            window_size,
+            gc_percent,
+            entropy: entropy_of_counts(
+                self.alphabet
+                    .iter()
+                    .map(|&symbol| self.symbols[symbol as usize]),
+                counted,
+            ),
+            gap_bases: self.gap_bases,
+        });
+    }
+
+    /// 塩基をウィンドウに加える（`entering` が false なら取り除く）
+    fn add(&mut self, base: u8, gap: bool, entering: bool) {
+        if gap {
+            if entering {
+                self.gap_bases += 1;
+            } else {
+                self.gap_bases -= 1;
+            }
+            return;
+        }
 
-        // Calculate AT skew: (A - T) / (A + T)
-        let at_skew = if base_counts.a + base_counts.t > 0 {
-            (base_counts.a as f64 - base_counts.t as f64)
-                / (base_counts.a as f64 + base_counts.t as f64)
+        if !self.alphabet.contains(&base) {
+            self.alphabet.push(base);
+        }
+        let is_gc = matches!(base, b'G' | b'C');
+        if entering {
+            self.symbols[base as usize] += 1;
+            self.gc += usize::from(is_gc);
         } else {
-            0.0
-        };
+            self.symbols[base as usize] -= 1;
+            self.gc -= usize::from(is_gc);
+        }
+    }
+}
+
+fn by_ratio(a: &KmerCount, b: &KmerCount) -> std::cmp::Ordering {
+    b.ratio
+        .total_cmp(&a.ratio)
+        .then(b.count.cmp(&a.count))
+        .then(a.kmer.cmp(&b.kmer))
+}
+
+/// 並べ替えて上位 REPORTED_KMERS 件だけ残す
+fn keep_top<T>(items: &mut Vec<T>, compare: impl FnMut(&T, &T) -> std::cmp::Ordering) {
+    items.sort_by(compare);
+    items.truncate(REPORTED_KMERS);
+}
+
+fn decode(code: u64, k: usize) -> String {
+    (0..k)
+        .rev()
+        .map(|i| BASES[((code >> (2 * i)) & 3) as usize] as char)
+        .collect()
+}
+
+impl StatsService for StatsServiceImpl {
+    /// ギャップを除く場合、塩基数・二塩基・割合・エントロピー・複雑度はギャップの間の
+    /// 区間だけから求める（length は配列全体の長さのまま）
+    fn calculate_detailed_stats_with_options(
+        &self,
+        sequence: &str,
+        options: &StatsOptions,
+    ) -> DetailedStats {
+        let mut base_counts = BaseCount::new();
+        let mut dinucleotides: HashMap<String, usize> = HashMap::new();
+
+        let gaps = options
+            .min_gap_length
+            .map(|min_length| find_gaps(sequence, min_length))
+            .unwrap_or_default();
+        let mut segments = Vec::with_capacity(gaps.len() + 1);
+        let mut segment_start = 0;
+        for gap in &gaps {
+            segments.push(&sequence[segment_start..gap.start]);
+            segment_start = gap.end;
+        }
+        segments.push(&sequence[segment_start..]);
+
+        let length = sequence.chars().count();
+        let gap_length: usize = gaps.iter().map(|gap| gap.len()).sum();
+        let effective_length = length - gap_length;
+
+        for segment in &segments {
+            let chars: Vec<char> = segment.chars().collect();
+
+            // Count bases
+            for c in &chars {
+                match c.to_ascii_uppercase() {
+                    'A' => base_counts.a += 1,
+                    'T' | 'U' => base_counts.t += 1,
+                    'G' => base_counts.g += 1,
+                    'C' => base_counts.c += 1,
+                    'N' => base_counts.n += 1,
+                    _ => base_counts.other += 1,
+                }
+            }
+
+            // Count dinucleotides
+            for window in chars.windows(2) {
+                let dinuc = format!("{}{}", window[0], window[1]).to_uppercase();
+                *dinucleotides.entry(dinuc).or_insert(0) += 1;
+            }
+        }
 
         // Calculate Shannon entropy
-        let entropy = self.calculate_entropy(sequence);
+        let entropy = if gaps.is_empty() {
+            self.calculate_entropy(sequence)
+        } else {
+            self.calculate_entropy(&segments.concat())
+        };
 
         // Calculate sequence complexity
-        let complexity = self.calculate_complexity(sequence);
+        let complexity = self.calculate_complexity(&segments);
 
-        DetailedStats {
+        summarize(
             length,
-            gc_percent,
-            at_percent,
-            n_percent,
+            effective_length,
             base_counts,
-            dinucleotide_counts: dinucleotides,
-            gc_skew,
-            at_skew,
+            dinucleotides,
             entropy,
             complexity,
-            codon_usage: None,   // Will be calculated separately if needed
-            quality_stats: None, // Will be added from FASTQ data if available
-        }
+            options.min_gap_length.map(|min_gap_length| GapStats {
+                min_gap_length,
+                count: gaps.len(),
+                total_length: gap_length,
+                largest: gaps.iter().map(|gap| gap.len()).max().unwrap_or(0),
+                effective_length,
+            }),
+        )
     }
 
-    fn calculate_window_stats(
+    /// ギャップを除く場合、各ウィンドウの GC% とエントロピーはギャップ外の塩基から求める。
+    /// 環状配列のウィンドウは原点をまたいで配列の末尾まで並ぶ（配列より長いウィンドウは
+    /// 線形と同じ扱い）。線形配列の末尾の短いウィンドウは `options.partial_windows` のときだけ
+    fn calculate_window_stats_with_options(
         &self,
         sequence: &str,
         window_size: usize,
         step: usize,
+        topology: &Topology,
+        options: &StatsOptions,
     ) -> Vec<WindowStats> {
         let mut stats = Vec::new();
         let chars: Vec<char> = sequence.chars().collect();
 
-        for pos in (0..chars.len()).step_by(step) {
-            if pos + window_size > chars.len() {
-                break;
+        let mut in_gap = vec![false; chars.len()];
+        if let Some(min_length) = options.min_gap_length {
+            for gap in find_gaps(sequence, min_length) {
+                in_gap[gap.start..gap.end].fill(true);
             }
+        }
 
-            let window_seq: String = chars[pos..pos + window_size].iter().collect();
+        let length = chars.len();
+        let wraps = *topology == Topology::Circular && window_size <= length;
+        for pos in (0..length).step_by(step) {
+            let window_size = if wraps || pos + window_size <= length {
+                window_size
+            } else if options.partial_windows {
+                length - pos
+            } else {
+                break;
+            };
+
+            let window_seq: String = (pos..pos + window_size)
+                .map(|i| i % length)
+                .filter(|&i| !in_gap[i])
+                .map(|i| chars[i])
+                .collect();
+            let counted = window_seq.len();
 
             // Calculate GC% for window
             let gc_count = window_seq
                 .chars()
                 .filter(|&c| c == 'G' || c == 'C' || c == 'g' || c == 'c')
                 .count();
-            let gc_percent = (gc_count as f64 / window_size as f64) * 100.0;
+            let gc_percent = if counted > 0 {
+                (gc_count as f64 / counted as f64) * 100.0
+            } else {
+                0.0
+            };
 
             // Calculate entropy for window
             let entropy = self.calculate_entropy(&window_seq);
@@ -173,9 +723,181 @@ impl StatsService for StatsServiceImpl {
                 window_size,
                 gc_percent,
                 entropy,
+                gap_bases: window_size - counted,
             });
         }
 
         stats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_n_runs_excluded_from_denominators() {
+        let service = StatsServiceImpl::new();
+        let sequence = format!("GGCC{}AATTNN", "N".repeat(10));
+
+        let plain = service.calculate_detailed_stats(&sequence);
+        assert_eq!(plain.length, 20);
+        assert_eq!(plain.gc_percent, 20.0);
+        assert!(plain.gap_stats.is_none());
+
+        // 10 塩基の N の連続だけがギャップ（末尾の NN は短いので残る）
+        let options = StatsOptions {
+            min_gap_length: Some(5),
+            ..StatsOptions::default()
+        };
+        let stats = service.calculate_detailed_stats_with_options(&sequence, &options);
+        assert_eq!(stats.length, 20);
+        assert_eq!(stats.gc_percent, 40.0);
+        assert_eq!(stats.n_percent, 20.0);
+        assert_eq!(stats.base_counts.n, 2);
+        assert!(!stats.dinucleotide_counts.contains_key("CN"));
+        let gaps = stats.gap_stats.unwrap();
+        assert_eq!((gaps.count, gaps.total_length, gaps.largest), (1, 10, 10));
+        assert_eq!(gaps.effective_length, 10);
+
+        let windows = service.calculate_window_stats_with_options(
+            &sequence,
+            8,
+            8,
+            &Topology::Linear,
+            &options,
+        );
+        assert_eq!((windows[0].gc_percent, windows[0].gap_bases), (100.0, 4));
+        assert_eq!((windows[1].gc_percent, windows[1].gap_bases), (0.0, 6));
+    }
+
+    #[test]
+    fn test_streaming_stats_match_in_memory() {
+        let service = StatsServiceImpl::new();
+        let sequence = format!(
+            "ACGTTGCANNNGGCATTA{}CCGTAGCTTAN{}ATGCGCAT",
+            "N".repeat(7),
+            "N".repeat(12)
+        );
+        let options = StatsOptions {
+            min_gap_length: Some(5),
+            ..StatsOptions::default()
+        };
+        let expected = service.calculate_detailed_stats_with_options(&sequence, &options);
+        let expected_windows = service.calculate_window_stats_with_options(
+            &sequence,
+            10,
+            3,
+            &Topology::Linear,
+            &options,
+        );
+
+        // チャンクの境界が N の連続や二塩基の途中に来ても結果は変わらない
+        for chunk_size in [1, 4, 9, sequence.len()] {
+            let mut streaming = StreamingStats::new(&options, &Topology::Linear, Some((10, 3)));
+            for chunk in sequence.as_bytes().chunks(chunk_size) {
+                streaming.push(std::str::from_utf8(chunk).unwrap());
+            }
+            let (stats, windows) = streaming.finish();
+
+            assert_eq!(stats.length, expected.length);
+            assert_eq!(stats.base_counts.n, expected.base_counts.n);
+            assert_eq!(stats.gc_percent, expected.gc_percent);
+            assert_eq!(stats.dinucleotide_counts, expected.dinucleotide_counts);
+            assert_eq!(stats.gap_stats, expected.gap_stats);
+            assert!((stats.entropy - expected.entropy).abs() < 1e-12);
+            assert_eq!(stats.complexity, expected.complexity);
+
+            assert_eq!(windows.len(), expected_windows.len());
+            for (window, expected) in windows.iter().zip(&expected_windows) {
+                assert_eq!(
+                    (window.position, window.gap_bases, window.gc_percent),
+                    (expected.position, expected.gap_bases, expected.gc_percent)
+                );
+                assert!((window.entropy - expected.entropy).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_circular_and_partial_windows() {
+        let service = StatsServiceImpl::new();
+        let sequence = "GGGGCCNNNNNNAAATTTAC";
+        let options = StatsOptions {
+            min_gap_length: Some(5),
+            partial_windows: true,
+        };
+
+        // 線形: 末尾の短いウィンドウ（16..20, 8..20 のうち 8 以降）
+        let linear = service.calculate_window_stats_with_options(
+            sequence,
+            8,
+            4,
+            &Topology::Linear,
+            &options,
+        );
+        let sizes: Vec<(usize, usize)> =
+            linear.iter().map(|w| (w.position, w.window_size)).collect();
+        assert_eq!(sizes, vec![(0, 8), (4, 8), (8, 8), (12, 8), (16, 4)]);
+
+        // 環状: 原点をまたいで 16..20 + 0..4
+        let circular = service.calculate_window_stats_with_options(
+            sequence,
+            8,
+            4,
+            &Topology::Circular,
+            &options,
+        );
+        assert_eq!(circular.len(), 5);
+        assert_eq!((circular[4].window_size, circular[4].gc_percent), (8, 62.5));
+
+        for (topology, expected) in [(Topology::Linear, &linear), (Topology::Circular, &circular)] {
+            let mut streaming = StreamingStats::new(&options, &topology, Some((8, 4)));
+            streaming.push(sequence);
+            let (_, windows) = streaming.finish();
+            let summary = |windows: &[WindowStats]| -> Vec<(usize, usize, f64, usize)> {
+                windows
+                    .iter()
+                    .map(|w| (w.position, w.window_size, w.gc_percent, w.gap_bases))
+                    .collect()
+            };
+            assert_eq!(summary(&windows), summary(expected));
+        }
+    }
+
+    #[test]
+    fn test_kmer_spectrum_dense_and_sparse_counts_agree() {
+        let service = StatsServiceImpl::new();
+        let sequence = "ACGTACGTNNACGTTTTTTT";
+
+        // 4^2 <= 2 * 20 なのでカウンタ配列で数える
+        let spectrum = service.calculate_kmer_spectrum(sequence, 2);
+        assert_eq!(spectrum.total_kmers, 16);
+        assert_eq!(spectrum.possible_kmers, 16);
+        assert_eq!(spectrum.most_frequent[0].kmer, "TT");
+        assert_eq!(spectrum.most_frequent[0].count, 6);
+        assert_eq!(
+            spectrum
+                .spectrum
+                .iter()
+                .map(|f| f.kmers * f.occurrences)
+                .sum::<u64>(),
+            16
+        );
+
+        // 4^4 > 2 * 20 なので符号を並べ替えて数える
+        let spectrum = service.calculate_kmer_spectrum(sequence, 4);
+        assert_eq!(spectrum.total_kmers, 12);
+        let count = |kmer: &str| {
+            spectrum
+                .most_frequent
+                .iter()
+                .find(|c| c.kmer == kmer)
+                .map(|c| c.count)
+        };
+        assert_eq!(count("TTTT"), Some(4));
+        assert_eq!(count("ACGT"), Some(3));
+        assert_eq!(spectrum.distinct_kmers, 7);
+        assert_eq!(spectrum.over_represented[0].kmer, "ACGT");
+    }
+}
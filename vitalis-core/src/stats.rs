@@ -1,3 +1,5 @@
+use crate::domain::codon_usage::CodonUsageParams;
+use crate::domain::genetic_code::GeneticCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -271,8 +273,9 @@ pub fn calculate_codon_usage(sequence: &str, genetic_code: Option<u8>) -> Option
     let mut start_codons = 0;
     let mut stop_codons = 0;
 
-    // Standard genetic code (code 1)
-    let genetic_code_table = get_genetic_code(genetic_code.unwrap_or(1));
+    // Unknown table ids fall back to the standard code (NCBI code 1)
+    let genetic_code_table =
+        GeneticCode::by_id(genetic_code.unwrap_or(1)).unwrap_or_else(GeneticCode::standard);
 
     // Process sequence in triplets
     for chunk in sequence.chars().collect::<Vec<_>>().chunks(3) {
@@ -287,14 +290,15 @@ pub fn calculate_codon_usage(sequence: &str, genetic_code: Option<u8>) -> Option
             *codon_counts.entry(codon.clone()).or_insert(0) += 1;
 
             // Translate codon to amino acid
-            if let Some(&aa) = genetic_code_table.get(codon.as_str()) {
+            let aa = genetic_code_table.translate_codon(codon.as_bytes());
+            if aa != 'X' {
                 *amino_acid_counts.entry(aa).or_insert(0) += 1;
 
                 // Count start and stop codons
                 if codon == "ATG" {
                     start_codons += 1;
                 }
-                if codon == "TAA" || codon == "TAG" || codon == "TGA" {
+                if aa == '*' {
                     stop_codons += 1;
                 }
             }
@@ -308,11 +312,17 @@ pub fn calculate_codon_usage(sequence: &str, genetic_code: Option<u8>) -> Option
         codon_frequencies.insert(codon.clone(), *count as f64 / total_codons as f64);
     }
 
-    // Identify rare codons (frequency < 0.01)
-    let rare_codons: Vec<String> = codon_frequencies
-        .iter()
-        .filter(|(_, freq)| **freq < 0.01)
-        .map(|(codon, _)| codon.clone())
+    // Identify rare codons against the default host table (E. coli)
+    let params = CodonUsageParams::default();
+    let rare_codons: Vec<String> = codon_counts
+        .keys()
+        .filter(|codon| {
+            params
+                .host
+                .relative_adaptiveness(codon.as_bytes(), &genetic_code_table)
+                .is_some_and(|w| w < params.rare_threshold)
+        })
+        .cloned()
         .collect();
 
     Some(CodonUsage {
@@ -325,100 +335,6 @@ pub fn calculate_codon_usage(sequence: &str, genetic_code: Option<u8>) -> Option
     })
 }
 
-/// Get genetic code table
-fn get_genetic_code(_code: u8) -> HashMap<&'static str, char> {
-    // Standard genetic code (NCBI code 1)
-    let mut table = HashMap::new();
-
-    // Phenylalanine
-    table.insert("TTT", 'F');
-    table.insert("TTC", 'F');
-    // Leucine
-    table.insert("TTA", 'L');
-    table.insert("TTG", 'L');
-    table.insert("CTT", 'L');
-    table.insert("CTC", 'L');
-    table.insert("CTA", 'L');
-    table.insert("CTG", 'L');
-    // Isoleucine
-    table.insert("ATT", 'I');
-    table.insert("ATC", 'I');
-    table.insert("ATA", 'I');
-    // Methionine
-    table.insert("ATG", 'M');
-    // Valine
-    table.insert("GTT", 'V');
-    table.insert("GTC", 'V');
-    table.insert("GTA", 'V');
-    table.insert("GTG", 'V');
-    // Serine
-    table.insert("TCT", 'S');
-    table.insert("TCC", 'S');
-    table.insert("TCA", 'S');
-    table.insert("TCG", 'S');
-    table.insert("AGT", 'S');
-    table.insert("AGC", 'S');
-    // Proline
-    table.insert("CCT", 'P');
-    table.insert("CCC", 'P');
-    table.insert("CCA", 'P');
-    table.insert("CCG", 'P');
-    // Threonine
-    table.insert("ACT", 'T');
-    table.insert("ACC", 'T');
-    table.insert("ACA", 'T');
-    table.insert("ACG", 'T');
-    // Alanine
-    table.insert("GCT", 'A');
-    table.insert("GCC", 'A');
-    table.insert("GCA", 'A');
-    table.insert("GCG", 'A');
-    // Tyrosine
-    table.insert("TAT", 'Y');
-    table.insert("TAC", 'Y');
-    // Stop codons
-    table.insert("TAA", '*');
-    table.insert("TAG", '*');
-    table.insert("TGA", '*');
-    // Histidine
-    table.insert("CAT", 'H');
-    table.insert("CAC", 'H');
-    // Glutamine
-    table.insert("CAA", 'Q');
-    table.insert("CAG", 'Q');
-    // Asparagine
-    table.insert("AAT", 'N');
-    table.insert("AAC", 'N');
-    // Lysine
-    table.insert("AAA", 'K');
-    table.insert("AAG", 'K');
-    // Aspartic acid
-    table.insert("GAT", 'D');
-    table.insert("GAC", 'D');
-    // Glutamic acid
-    table.insert("GAA", 'E');
-    table.insert("GAG", 'E');
-    // Cysteine
-    table.insert("TGT", 'C');
-    table.insert("TGC", 'C');
-    // Tryptophan
-    table.insert("TGG", 'W');
-    // Arginine
-    table.insert("CGT", 'R');
-    table.insert("CGC", 'R');
-    table.insert("CGA", 'R');
-    table.insert("CGG", 'R');
-    table.insert("AGA", 'R');
-    table.insert("AGG", 'R');
-    // Glycine
-    table.insert("GGT", 'G');
-    table.insert("GGC", 'G');
-    table.insert("GGA", 'G');
-    table.insert("GGG", 'G');
-
-    table
-}
-
 /// Calculate quality statistics for FASTQ sequences
 pub fn calculate_quality_stats(quality_scores: &[u8]) -> QualityStats {
     if quality_scores.is_empty() {
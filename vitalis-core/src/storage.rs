@@ -215,15 +215,19 @@ impl SequenceStorage {
             .ok_or_else(|| format!("Sequence not found: {}", seq_id))?;
 
         // Use new layered architecture
-        crate::application::get_window(seq_id.to_string(), 0, metadata.length)
-            .map(|response| response.bases)
-            .map_err(|e| e)
+        crate::application::get_window(
+            seq_id.to_string(),
+            crate::domain::region::Region::new(0, metadata.length),
+        )
+        .map(|response| response.bases.to_string())
+        .map_err(String::from)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::region::Region;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -237,7 +241,7 @@ mod tests {
         let seq_id = result.seq_id;
 
         // Use new layered architecture for window access
-        let response = crate::application::get_window(seq_id, 2, 6).unwrap();
+        let response = crate::application::get_window(seq_id, Region::new(2, 6)).unwrap();
         assert_eq!(response.bases, "CGAT");
     }
 
@@ -257,13 +261,13 @@ mod tests {
         let seq_id = result.seq_id;
 
         // Test window access using new layered architecture
-        let response = crate::application::get_window(seq_id.clone(), 0, 8).unwrap();
+        let response = crate::application::get_window(seq_id.clone(), Region::new(0, 8)).unwrap();
         assert_eq!(response.bases, "ATCGATCG");
 
-        let response = crate::application::get_window(seq_id.clone(), 8, 16).unwrap();
+        let response = crate::application::get_window(seq_id.clone(), Region::new(8, 16)).unwrap();
         assert_eq!(response.bases, "GCTAGCTA");
 
-        let response = crate::application::get_window(seq_id, 4, 12).unwrap();
+        let response = crate::application::get_window(seq_id, Region::new(4, 12)).unwrap();
         assert_eq!(response.bases, "ATCGGCTA");
     }
 }
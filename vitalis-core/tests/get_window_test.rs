@@ -3,13 +3,14 @@ use tempfile::NamedTempFile;
 use vitalis_core::application::{
     get_window, import_from_file, parse_and_import, ImportFromFileRequest,
 };
+use vitalis_core::Region;
 
 #[test]
 fn test_get_window_basic() {
     let fasta_content = ">test_seq\nATCGATCGATCGATCG".to_string();
     let result = parse_and_import(fasta_content, "fasta".to_string()).unwrap();
 
-    let window = get_window(result.seq_id, 2, 6).unwrap();
+    let window = get_window(result.seq_id, Region::new(2, 6)).unwrap();
     assert_eq!(window.bases, "CGAT");
 }
 
@@ -19,15 +20,15 @@ fn test_get_window_boundary_conditions() {
     let result = parse_and_import(fasta_content, "fasta".to_string()).unwrap();
 
     // Test start at 0
-    let window = get_window(result.seq_id.clone(), 0, 4).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(0, 4)).unwrap();
     assert_eq!(window.bases, "ATCG");
 
     // Test end at sequence length
-    let window = get_window(result.seq_id.clone(), 4, 8).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(4, 8)).unwrap();
     assert_eq!(window.bases, "ATCG");
 
     // Test full sequence
-    let window = get_window(result.seq_id, 0, 8).unwrap();
+    let window = get_window(result.seq_id, Region::new(0, 8)).unwrap();
     assert_eq!(window.bases, "ATCGATCG");
 }
 
@@ -37,17 +38,17 @@ fn test_get_window_invalid_ranges() {
     let result = parse_and_import(fasta_content, "fasta".to_string()).unwrap();
 
     // Test start >= sequence length
-    let result_err = get_window(result.seq_id.clone(), 8, 10);
+    let result_err = get_window(result.seq_id.clone(), Region::new(8, 10));
     assert!(result_err.is_err());
-    let error_msg = result_err.unwrap_err();
+    let error_msg = result_err.unwrap_err().to_string();
     assert!(error_msg.contains("Invalid range"));
 
     // Test start >= end (should return empty)
-    let window = get_window(result.seq_id.clone(), 5, 5).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(5, 5)).unwrap();
     assert_eq!(window.bases, "");
 
     // Test start > end (should return empty)
-    let window = get_window(result.seq_id, 6, 4).unwrap();
+    let window = get_window(result.seq_id, Region::new(6, 4)).unwrap();
     assert_eq!(window.bases, "");
 }
 
@@ -57,39 +58,42 @@ fn test_get_window_end_exceeds_length() {
     let result = parse_and_import(fasta_content, "fasta".to_string()).unwrap();
 
     // Test end > sequence length (should clamp to sequence length)
-    let window = get_window(result.seq_id, 4, 20).unwrap();
+    let window = get_window(result.seq_id, Region::new(4, 20)).unwrap();
     assert_eq!(window.bases, "ATCG");
 }
 
 #[test]
 fn test_get_window_nonexistent_sequence() {
-    let result = get_window("nonexistent_seq".to_string(), 0, 4);
+    let result = get_window("nonexistent_seq".to_string(), Region::new(0, 4));
     assert!(result.is_err());
-    let error_msg = result.unwrap_err();
+    let error_msg = result.unwrap_err().to_string();
     assert!(error_msg.contains("not found") || error_msg.contains("Sequence not found"));
 }
 
 #[test]
 fn test_get_window_large_sequence_memory() {
     // Create a large sequence (1000 bases) in memory
-    let large_seq = "A".repeat(500) + &"T".repeat(500);
+    let large_seq = format!("{}{}", "A".repeat(500), "T".repeat(500));
     let fasta_content = format!(">large_seq\n{}", large_seq);
     let result = parse_and_import(fasta_content, "fasta".to_string()).unwrap();
 
     // Test various windows
-    let window = get_window(result.seq_id.clone(), 0, 100).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(0, 100)).unwrap();
     assert_eq!(window.bases, "A".repeat(100));
 
-    let window = get_window(result.seq_id.clone(), 250, 350).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(250, 350)).unwrap();
     assert_eq!(window.bases, "A".repeat(100));
 
-    let window = get_window(result.seq_id.clone(), 900, 1000).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(900, 1000)).unwrap();
     assert_eq!(window.bases, "T".repeat(100));
 
     // Test overlapping windows
-    let window1 = get_window(result.seq_id.clone(), 490, 510).unwrap();
-    let window2 = get_window(result.seq_id.clone(), 500, 520).unwrap();
-    assert_eq!(window1.bases, "A".repeat(10) + &"T".repeat(10));
+    let window1 = get_window(result.seq_id.clone(), Region::new(490, 510)).unwrap();
+    let window2 = get_window(result.seq_id.clone(), Region::new(500, 520)).unwrap();
+    assert_eq!(
+        window1.bases,
+        format!("{}{}", "A".repeat(10), "T".repeat(10))
+    );
     assert_eq!(window2.bases, "T".repeat(20));
 }
 
@@ -106,24 +110,25 @@ fn test_get_window_file_based_sequence() {
     let request = ImportFromFileRequest {
         file_path: temp_file.path().to_string_lossy().to_string(),
         format: "fasta".to_string(),
+        prebuild_index: false,
     };
 
     let result = import_from_file(request).unwrap();
 
     // Test various windows across line boundaries
-    let window = get_window(result.seq_id.clone(), 0, 8).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(0, 8)).unwrap();
     assert_eq!(window.bases, "ATCGATCG");
 
-    let window = get_window(result.seq_id.clone(), 14, 18).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(14, 18)).unwrap();
     assert_eq!(window.bases, "CGGC"); // Cross line boundary
 
-    let window = get_window(result.seq_id.clone(), 16, 32).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(16, 32)).unwrap();
     assert_eq!(window.bases, "GCTAGCTAGCTAGCTA"); // Exactly second line
 
-    let window = get_window(result.seq_id.clone(), 30, 40).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(30, 40)).unwrap();
     assert_eq!(window.bases, "TATTAATTAA"); // Cross to third line
 
-    let window = get_window(result.seq_id, 40, 48).unwrap();
+    let window = get_window(result.seq_id, Region::new(40, 48)).unwrap();
     assert_eq!(window.bases, "TTAATTAA"); // End of sequence
 }
 
@@ -141,15 +146,16 @@ fn test_get_window_multiline_sequences() {
     let request = ImportFromFileRequest {
         file_path: temp_file.path().to_string_lossy().to_string(),
         format: "fasta".to_string(),
+        prebuild_index: false,
     };
 
     let result = import_from_file(request).unwrap();
 
     // Test windows that span multiple lines
-    let window = get_window(result.seq_id.clone(), 5, 15).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(5, 15)).unwrap();
     assert_eq!(window.bases, "AAAAATTTTT"); // A's to T's
 
-    let window = get_window(result.seq_id.clone(), 18, 32).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(18, 32)).unwrap();
     assert_eq!(window.bases, "TTGGGGGGGGGGCC"); // T's to G's to C's
 }
 
@@ -173,6 +179,7 @@ fn test_get_window_performance_large_file() {
     let request = ImportFromFileRequest {
         file_path: temp_file.path().to_string_lossy().to_string(),
         format: "fasta".to_string(),
+        prebuild_index: false,
     };
 
     let result = import_from_file(request).unwrap();
@@ -181,13 +188,13 @@ fn test_get_window_performance_large_file() {
     let start = std::time::Instant::now();
 
     // Small windows
-    let _window = get_window(result.seq_id.clone(), 100, 200).unwrap();
-    let _window = get_window(result.seq_id.clone(), 1000, 1100).unwrap();
-    let _window = get_window(result.seq_id.clone(), 5000, 5100).unwrap();
+    let _window = get_window(result.seq_id.clone(), Region::new(100, 200)).unwrap();
+    let _window = get_window(result.seq_id.clone(), Region::new(1000, 1100)).unwrap();
+    let _window = get_window(result.seq_id.clone(), Region::new(5000, 5100)).unwrap();
 
     // Large windows
-    let _window = get_window(result.seq_id.clone(), 0, 1000).unwrap();
-    let _window = get_window(result.seq_id.clone(), 2000, 4000).unwrap();
+    let _window = get_window(result.seq_id.clone(), Region::new(0, 1000)).unwrap();
+    let _window = get_window(result.seq_id.clone(), Region::new(2000, 4000)).unwrap();
 
     let elapsed = start.elapsed();
 
@@ -205,11 +212,11 @@ fn test_get_window_special_characters() {
     let result = parse_and_import(fasta_content, "fasta".to_string()).unwrap();
 
     // Test that lowercase letters are handled (should be converted to uppercase)
-    let window = get_window(result.seq_id.clone(), 8, 12).unwrap();
+    let window = get_window(result.seq_id.clone(), Region::new(8, 12)).unwrap();
     assert_eq!(window.bases, "ATCG");
 
     // Test that N's and other characters are preserved
-    let window = get_window(result.seq_id, 4, 8).unwrap();
+    let window = get_window(result.seq_id, Region::new(4, 8)).unwrap();
     assert_eq!(window.bases, "NNNN");
 }
 
@@ -219,7 +226,7 @@ fn test_get_window_empty_sequence() {
     let result = parse_and_import(fasta_content, "fasta".to_string()).unwrap();
 
     // Any window request on empty sequence should fail
-    let result_err = get_window(result.seq_id, 0, 1);
+    let result_err = get_window(result.seq_id, Region::new(0, 1));
     assert!(result_err.is_err());
 }
 
@@ -239,6 +246,7 @@ fn test_get_window_consistency_memory_vs_file() {
     let request = ImportFromFileRequest {
         file_path: temp_file.path().to_string_lossy().to_string(),
         format: "fasta".to_string(),
+        prebuild_index: false,
     };
     let file_result = import_from_file(request).unwrap();
 
@@ -246,8 +254,8 @@ fn test_get_window_consistency_memory_vs_file() {
     let test_cases = vec![(0, 8), (8, 16), (16, 24), (24, 32), (5, 15), (10, 20)];
 
     for (start, end) in test_cases {
-        let mem_window = get_window(mem_result.seq_id.clone(), start, end).unwrap();
-        let file_window = get_window(file_result.seq_id.clone(), start, end).unwrap();
+        let mem_window = get_window(mem_result.seq_id.clone(), Region::new(start, end)).unwrap();
+        let file_window = get_window(file_result.seq_id.clone(), Region::new(start, end)).unwrap();
 
         assert_eq!(
             mem_window.bases, file_window.bases,